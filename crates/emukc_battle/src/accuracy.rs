@@ -0,0 +1,398 @@
+//! Hit-check stage: accuracy, evasion and critical rolls.
+//!
+//! Every attack rolls [`roll_hit`] before damage is computed. The result drives
+//! both the wire flag (`api_cl_list` / `api_fcl` / `api_*cl_flag`) and the damage
+//! pipeline: a miss deals no damage, a critical multiplies the post-cap power by
+//! [`CRITICAL_MULTIPLIER`].
+//!
+//! The model follows the community-documented formula:
+//!
+//! ```text
+//! accuracy = floor((base + 2√lv + 1.5√luck + equip) × formation × morale)
+//! evasion  = cap(floor(kaihi × formation))
+//! hit%     = clamp(floor((accuracy − evasion) × defender_morale) + 1, 10, 96)
+//! crit%    = floor(√hit% × coeff) + 1
+//! ```
+
+use emukc_model::{
+    codex::Codex,
+    kc2::{KcSlotItemType3, start2::ApiMstSlotitem},
+};
+
+use crate::random::BattleRng;
+use crate::types::BattleRuntimeShip;
+
+/// Post-cap damage multiplier applied on a critical hit.
+pub(crate) const CRITICAL_MULTIPLIER: f64 = 1.5;
+
+/// Lowest possible hit rate (percent), regardless of evasion.
+const MIN_HIT_RATE: i64 = 10;
+/// Highest possible hit rate (percent), regardless of accuracy.
+const MAX_HIT_RATE: i64 = 96;
+
+/// Attack family, selecting the base accuracy and the critical coefficient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttackKind {
+    /// Day shelling, including cut-ins and special attacks.
+    Shelling,
+    /// Opening and closing torpedo.
+    Torpedo,
+    /// Opening ASW and day ASW attacks.
+    Asw,
+    /// Kouku stage 3 bombing.
+    Airstrike,
+    /// Night battle attacks.
+    Night,
+    /// Support fleet shelling and long-range torpedo.
+    Support,
+}
+
+impl AttackKind {
+    /// Flat base accuracy before level / luck / equipment terms.
+    const fn base_accuracy(self) -> f64 {
+        match self {
+            Self::Shelling => 90.0,
+            Self::Torpedo => 85.0,
+            Self::Asw => 80.0,
+            Self::Airstrike => 95.0,
+            Self::Night => 69.0,
+            Self::Support => 64.0,
+        }
+    }
+
+    /// Coefficient in `crit% = floor(√hit% × coeff) + 1`.
+    const fn critical_coeff(self) -> f64 {
+        match self {
+            Self::Torpedo | Self::Airstrike => 1.5,
+            Self::Shelling | Self::Asw | Self::Night | Self::Support => 1.3,
+        }
+    }
+
+    /// Whether the attacker's formation accuracy modifier applies.
+    const fn uses_formation(self) -> bool {
+        matches!(self, Self::Shelling | Self::Asw)
+    }
+}
+
+/// Outcome of a single hit check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HitResult {
+    Miss,
+    Hit,
+    Critical,
+}
+
+impl HitResult {
+    /// `api_cl_list` / `api_fcl` convention: 0 = miss, 1 = hit, 2 = critical.
+    pub(crate) const fn api_cl(self) -> i64 {
+        match self {
+            Self::Miss => 0,
+            Self::Hit => 1,
+            Self::Critical => 2,
+        }
+    }
+
+    pub(crate) const fn is_miss(self) -> bool {
+        matches!(self, Self::Miss)
+    }
+
+    pub(crate) const fn is_critical(self) -> bool {
+        matches!(self, Self::Critical)
+    }
+
+    /// Post-cap multiplier contributed by this result.
+    pub(crate) const fn damage_multiplier(self) -> f64 {
+        match self {
+            Self::Critical => CRITICAL_MULTIPLIER,
+            Self::Miss | Self::Hit => 1.0,
+        }
+    }
+}
+
+/// Formations on both sides of a hit check.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HitCheck {
+    pub kind: AttackKind,
+    pub attacker_formation_id: i64,
+    pub defender_formation_id: i64,
+}
+
+impl HitCheck {
+    pub(crate) const fn new(
+        kind: AttackKind,
+        attacker_formation_id: i64,
+        defender_formation_id: i64,
+    ) -> Self {
+        Self {
+            kind,
+            attacker_formation_id,
+            defender_formation_id,
+        }
+    }
+}
+
+/// Roll a hit check for one attack.
+///
+/// Consumes exactly one `roll_range(0, 100)` draw.
+pub(crate) fn roll_hit(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    attacker: &BattleRuntimeShip,
+    defender: &BattleRuntimeShip,
+    check: &HitCheck,
+) -> HitResult {
+    let hit = hit_rate(codex, attacker, defender, check);
+    roll_against(rng, hit, check.kind)
+}
+
+/// Roll a hit check for a land-based air squadron.
+///
+/// Squadrons have no level, luck or morale, so the accuracy term is the
+/// airstrike base plus the plane's own `api_houm`. Consumes exactly one
+/// `roll_range(0, 100)` draw.
+pub(crate) fn roll_squadron_hit(
+    rng: &mut impl BattleRng,
+    plane_accuracy: i64,
+    defender: &BattleRuntimeShip,
+    defender_formation_id: i64,
+) -> HitResult {
+    let accuracy = (AttackKind::Airstrike.base_accuracy() + plane_accuracy as f64).floor() as i64;
+    let evasion = evasion_term(defender, defender_formation_id);
+    let hit = (((accuracy - evasion) as f64 * defender_morale_modifier(defender.ship.api_cond))
+        .floor() as i64
+        + 1)
+    .clamp(MIN_HIT_RATE, MAX_HIT_RATE);
+    roll_against(rng, hit, AttackKind::Airstrike)
+}
+
+fn roll_against(rng: &mut impl BattleRng, hit: i64, kind: AttackKind) -> HitResult {
+    let crit = critical_rate(hit, kind);
+    let roll = rng.roll_range(0, 100);
+    if roll < crit {
+        HitResult::Critical
+    } else if roll < hit {
+        HitResult::Hit
+    } else {
+        HitResult::Miss
+    }
+}
+
+/// Final hit rate in percent, clamped to `[10, 96]`.
+pub(crate) fn hit_rate(
+    codex: &Codex,
+    attacker: &BattleRuntimeShip,
+    defender: &BattleRuntimeShip,
+    check: &HitCheck,
+) -> i64 {
+    let accuracy = accuracy_term(codex, attacker, check);
+    let evasion = evasion_term(defender, check.defender_formation_id);
+    let raw = ((accuracy - evasion) as f64 * defender_morale_modifier(defender.ship.api_cond))
+        .floor() as i64
+        + 1;
+    raw.clamp(MIN_HIT_RATE, MAX_HIT_RATE)
+}
+
+/// Critical rate in percent; never exceeds the hit rate it derives from.
+pub(crate) fn critical_rate(hit_rate: i64, kind: AttackKind) -> i64 {
+    let hit_rate = hit_rate.max(0);
+    (((hit_rate as f64).sqrt() * kind.critical_coeff()).floor() as i64 + 1).min(hit_rate)
+}
+
+/// Attacker accuracy term.
+fn accuracy_term(codex: &Codex, attacker: &BattleRuntimeShip, check: &HitCheck) -> i64 {
+    let level = attacker.ship.api_lv.max(1) as f64;
+    let luck = attacker.ship.api_lucky[0].max(0) as f64;
+    let mut base = check.kind.base_accuracy() + 2.0 * level.sqrt() + 1.5 * luck.sqrt();
+    base += match check.kind {
+        AttackKind::Airstrike | AttackKind::Support => 0.0,
+        AttackKind::Torpedo => {
+            (attacker.ship.api_raisou[0].max(0) / 5) as f64 + equipment_accuracy(codex, attacker)
+        }
+        AttackKind::Asw => 2.0 * sonar_asw_total(codex, attacker),
+        AttackKind::Shelling | AttackKind::Night => equipment_accuracy(codex, attacker),
+    };
+    let formation = if check.kind.uses_formation() {
+        formation_accuracy_modifier(check.attacker_formation_id)
+    } else {
+        1.0
+    };
+    (base * formation * attacker_morale_modifier(attacker.ship.api_cond)).floor() as i64
+}
+
+/// Defender evasion term after the formation modifier and the soft cap.
+fn evasion_term(defender: &BattleRuntimeShip, formation_id: i64) -> i64 {
+    let raw = (defender.ship.api_kaihi[0].max(0) as f64 * formation_evasion_modifier(formation_id))
+        .floor();
+    cap_evasion(raw)
+}
+
+/// Evasion soft cap: linear below 40, then `40 + 3√(E−40)` below 65, then `55 + 2√(E−65)`.
+pub(crate) fn cap_evasion(evasion: f64) -> i64 {
+    if evasion < 40.0 {
+        evasion.floor() as i64
+    } else if evasion < 65.0 {
+        (40.0 + 3.0 * (evasion - 40.0).sqrt()).floor() as i64
+    } else {
+        (55.0 + 2.0 * (evasion - 65.0).sqrt()).floor() as i64
+    }
+}
+
+/// Sum of `api_houm` over all equipped items, plus `√★` for improved items.
+fn equipment_accuracy(codex: &Codex, ship: &BattleRuntimeShip) -> f64 {
+    ship.slot_items
+        .iter()
+        .filter_map(|si| {
+            let mst = codex.find::<ApiMstSlotitem>(&si.api_slotitem_id).ok()?;
+            Some(mst.api_houm as f64 + (si.api_level.max(0) as f64).sqrt())
+        })
+        .sum()
+}
+
+/// Sum of `api_tais` over equipped sonars (small and large).
+fn sonar_asw_total(codex: &Codex, ship: &BattleRuntimeShip) -> f64 {
+    ship.slot_items
+        .iter()
+        .filter_map(|si| {
+            let mst = codex.find::<ApiMstSlotitem>(&si.api_slotitem_id).ok()?;
+            matches!(
+                KcSlotItemType3::n(mst.api_type[2]),
+                Some(KcSlotItemType3::Sonar | KcSlotItemType3::LargeSonar)
+            )
+            .then_some(mst.api_tais.max(0) as f64)
+        })
+        .sum()
+}
+
+// ---------------------------------------------------------------------------
+// Modifiers
+// ---------------------------------------------------------------------------
+
+/// Attacker formation accuracy: Double Line / Echelon / Line Abreast = 1.2×.
+pub(crate) fn formation_accuracy_modifier(formation_id: i64) -> f64 {
+    match formation_id {
+        2 | 4 | 5 => 1.2,
+        _ => 1.0,
+    }
+}
+
+/// Defender formation evasion: Diamond 1.1×, Echelon 1.2×, Line Abreast 1.3×.
+pub(crate) fn formation_evasion_modifier(formation_id: i64) -> f64 {
+    match formation_id {
+        3 => 1.1,
+        4 => 1.2,
+        5 => 1.3,
+        _ => 1.0,
+    }
+}
+
+/// Attacker morale accuracy: sparkled 1.2×, orange 0.8×, red 0.5×.
+pub(crate) fn attacker_morale_modifier(cond: i64) -> f64 {
+    match cond {
+        53.. => 1.2,
+        30..=52 => 1.0,
+        20..=29 => 0.8,
+        _ => 0.5,
+    }
+}
+
+/// Defender morale: sparkled ships evade more (0.7×), tired ships less.
+pub(crate) fn defender_morale_modifier(cond: i64) -> f64 {
+    match cond {
+        53.. => 0.7,
+        30..=52 => 1.0,
+        20..=29 => 1.2,
+        _ => 1.4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SeededRng;
+    use crate::test_utils::make_test_ship;
+    use emukc_model::codex::Codex;
+
+    fn ship(lv: i64, luck: i64, kaihi: i64, cond: i64) -> BattleRuntimeShip {
+        let mut s = make_test_ship(50, 50, 50, 50);
+        s.ship.api_lv = lv;
+        s.ship.api_lucky[0] = luck;
+        s.ship.api_kaihi[0] = kaihi;
+        s.ship.api_cond = cond;
+        s
+    }
+
+    fn shelling() -> HitCheck {
+        HitCheck::new(AttackKind::Shelling, 1, 1)
+    }
+
+    #[test]
+    fn evasion_cap_breakpoints() {
+        assert_eq!(cap_evasion(0.0), 0);
+        assert_eq!(cap_evasion(39.0), 39);
+        assert_eq!(cap_evasion(40.0), 40);
+        assert_eq!(cap_evasion(49.0), 49); // 40 + 3×3
+        assert_eq!(cap_evasion(65.0), 55);
+        assert_eq!(cap_evasion(81.0), 63); // 55 + 2×4
+    }
+
+    #[test]
+    fn critical_rate_never_exceeds_hit_rate() {
+        for hit in 0..=100 {
+            for kind in [AttackKind::Shelling, AttackKind::Torpedo, AttackKind::Airstrike] {
+                assert!(critical_rate(hit, kind) <= hit, "hit {hit} kind {kind:?}");
+            }
+        }
+        assert_eq!(critical_rate(96, AttackKind::Shelling), 13); // floor(9.79×1.3)+1
+    }
+
+    #[test]
+    fn hit_rate_is_clamped() {
+        let codex = Codex::default();
+        let ace = ship(175, 100, 0, 100);
+        let dodger = ship(1, 0, 200, 100);
+        let check = shelling();
+        assert_eq!(hit_rate(&codex, &ace, &ship(1, 0, 0, 10), &check), MAX_HIT_RATE);
+        assert_eq!(hit_rate(&codex, &ship(1, 0, 0, 0), &dodger, &check), MIN_HIT_RATE);
+    }
+
+    #[test]
+    fn morale_and_formation_shift_hit_rate() {
+        let codex = Codex::default();
+        let target = ship(50, 10, 60, 49);
+        let check = shelling();
+        let normal = hit_rate(&codex, &ship(50, 10, 0, 49), &target, &check);
+        let red = hit_rate(&codex, &ship(50, 10, 0, 10), &target, &check);
+        assert!(red < normal, "red morale attacker must hit less ({red} vs {normal})");
+
+        let sparkled_target = ship(50, 10, 60, 60);
+        let vs_sparkled = hit_rate(&codex, &ship(50, 10, 0, 49), &sparkled_target, &check);
+        assert!(vs_sparkled < normal, "sparkled defender must evade more");
+
+        let abreast = hit_rate(
+            &codex,
+            &ship(50, 10, 0, 49),
+            &target,
+            &HitCheck::new(AttackKind::Shelling, 1, 5),
+        );
+        assert!(abreast < normal, "line abreast defender must evade more");
+    }
+
+    #[test]
+    fn roll_hit_consumes_one_draw_and_yields_all_outcomes() {
+        let codex = Codex::default();
+        let attacker = ship(50, 10, 0, 49);
+        let target = ship(50, 10, 70, 49);
+        let check = shelling();
+        let mut seen = [false; 3];
+        for seed in 0..500u64 {
+            let mut rng = SeededRng::new(seed);
+            let result = roll_hit(&codex, &mut rng, &attacker, &target, &check);
+            seen[result.api_cl() as usize] = true;
+
+            let mut shadow = SeededRng::new(seed);
+            let _ = shadow.roll_range(0, 100);
+            assert_eq!(rng.roll_range(0, 1_000), shadow.roll_range(0, 1_000), "seed {seed}");
+        }
+        assert_eq!(seen, [true; 3], "expected miss, hit and critical across seeds");
+    }
+}
@@ -1,14 +1,44 @@
-use crate::types::BattleType;
+use crate::types::{BattleType, CombinedFleetType};
 
 /// Identifies a single phase within a battle flow.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum BattlePhaseKind {
+    BaseAirAttack,
     Kouku,
+    /// Support expedition fleet strikes the enemy.
+    Support,
     OpeningAsw,
     OpeningTorpedo,
     Shelling1,
     Shelling2,
     ClosingTorpedo,
+    /// Combined fleets: main fleets exchange fire.
+    MainShelling(HougekiSlot),
+    /// Combined fleets: the main fleets' second round, only when a
+    /// battleship-class ship is present.
+    MainShellingRound2(HougekiSlot),
+    /// Combined fleets: escort fleets exchange fire (a single fleet fights
+    /// the opposing escort with its main fleet).
+    EscortShelling(HougekiSlot),
+}
+
+/// Which `api_hougeki{1,2,3}` slot a combined-fleet shelling phase fills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HougekiSlot {
+    First,
+    Second,
+    Third,
+}
+
+impl HougekiSlot {
+    /// Index into `api_hourai_flag`.
+    pub(crate) const fn index(self) -> usize {
+        match self {
+            Self::First => 0,
+            Self::Second => 1,
+            Self::Third => 2,
+        }
+    }
 }
 
 /// Defines the ordered sequence of phases for a battle type.
@@ -26,12 +56,36 @@ impl BattleFlow {
             BattleType::LdShooting => &LD_SHOOTING,
         }
     }
+
+    /// Select the flow for a combined-fleet battle.
+    ///
+    /// `friendly_type` is `None` when the player sorties a single fleet
+    /// against an enemy combined fleet.
+    pub fn for_combined(
+        battle_type: BattleType,
+        friendly_type: Option<CombinedFleetType>,
+        enemy_combined: bool,
+    ) -> &'static BattleFlow {
+        match battle_type {
+            BattleType::Normal => match (friendly_type, enemy_combined) {
+                (Some(CombinedFleetType::SurfaceTaskForce), true) => &COMBINED_SURFACE_EACH_DAY,
+                (Some(CombinedFleetType::SurfaceTaskForce), false) => &COMBINED_SURFACE_DAY,
+                (Some(_), true) => &COMBINED_EACH_DAY,
+                _ => &COMBINED_CARRIER_DAY,
+            },
+            BattleType::AirBattle => &AIR_BATTLE,
+            BattleType::LdAirBattle => &LD_AIR_BATTLE,
+            BattleType::LdShooting => &COMBINED_LD_SHOOTING,
+        }
+    }
 }
 
 /// Normal surface battle: full phase sequence.
 pub(crate) static SURFACE_DAY: BattleFlow = BattleFlow {
     phases: &[
+        BattlePhaseKind::BaseAirAttack,
         BattlePhaseKind::Kouku,
+        BattlePhaseKind::Support,
         BattlePhaseKind::OpeningAsw,
         BattlePhaseKind::OpeningTorpedo,
         BattlePhaseKind::Shelling1,
@@ -40,9 +94,14 @@ pub(crate) static SURFACE_DAY: BattleFlow = BattleFlow {
     ],
 };
 
-/// Air battle: kouku + OASW only.
+/// Air battle: land-based air + kouku + support + OASW only.
 pub(crate) static AIR_BATTLE: BattleFlow = BattleFlow {
-    phases: &[BattlePhaseKind::Kouku, BattlePhaseKind::OpeningAsw],
+    phases: &[
+        BattlePhaseKind::BaseAirAttack,
+        BattlePhaseKind::Kouku,
+        BattlePhaseKind::Support,
+        BattlePhaseKind::OpeningAsw,
+    ],
 };
 
 /// Land-based air battle: kouku only.
@@ -54,3 +113,148 @@ pub(crate) static LD_AIR_BATTLE: BattleFlow = BattleFlow {
 pub(crate) static LD_SHOOTING: BattleFlow = BattleFlow {
     phases: &[BattlePhaseKind::Shelling1, BattlePhaseKind::Shelling2],
 };
+
+/// Carrier / transport task force against a single fleet, and a single fleet
+/// against an enemy combined fleet: escorts shell first.
+pub(crate) static COMBINED_CARRIER_DAY: BattleFlow = BattleFlow {
+    phases: &[
+        BattlePhaseKind::BaseAirAttack,
+        BattlePhaseKind::Kouku,
+        BattlePhaseKind::Support,
+        BattlePhaseKind::OpeningAsw,
+        BattlePhaseKind::OpeningTorpedo,
+        BattlePhaseKind::EscortShelling(HougekiSlot::First),
+        BattlePhaseKind::ClosingTorpedo,
+        BattlePhaseKind::MainShelling(HougekiSlot::Second),
+        BattlePhaseKind::MainShellingRound2(HougekiSlot::Third),
+    ],
+};
+
+/// Surface task force against a single fleet: main fleets shell twice, then
+/// the escorts, then closing torpedo.
+pub(crate) static COMBINED_SURFACE_DAY: BattleFlow = BattleFlow {
+    phases: &[
+        BattlePhaseKind::BaseAirAttack,
+        BattlePhaseKind::Kouku,
+        BattlePhaseKind::Support,
+        BattlePhaseKind::OpeningAsw,
+        BattlePhaseKind::OpeningTorpedo,
+        BattlePhaseKind::MainShelling(HougekiSlot::First),
+        BattlePhaseKind::MainShellingRound2(HougekiSlot::Second),
+        BattlePhaseKind::EscortShelling(HougekiSlot::Third),
+        BattlePhaseKind::ClosingTorpedo,
+    ],
+};
+
+/// Carrier / transport task force against an enemy combined fleet (12 vs 12).
+pub(crate) static COMBINED_EACH_DAY: BattleFlow = BattleFlow {
+    phases: &[
+        BattlePhaseKind::BaseAirAttack,
+        BattlePhaseKind::Kouku,
+        BattlePhaseKind::Support,
+        BattlePhaseKind::OpeningAsw,
+        BattlePhaseKind::OpeningTorpedo,
+        BattlePhaseKind::MainShelling(HougekiSlot::First),
+        BattlePhaseKind::EscortShelling(HougekiSlot::Second),
+        BattlePhaseKind::ClosingTorpedo,
+        BattlePhaseKind::MainShellingRound2(HougekiSlot::Third),
+    ],
+};
+
+/// Surface task force against an enemy combined fleet (12 vs 12): the escorts
+/// shell between the main fleets' two rounds, and closing torpedo comes last.
+pub(crate) static COMBINED_SURFACE_EACH_DAY: BattleFlow = BattleFlow {
+    phases: &[
+        BattlePhaseKind::BaseAirAttack,
+        BattlePhaseKind::Kouku,
+        BattlePhaseKind::Support,
+        BattlePhaseKind::OpeningAsw,
+        BattlePhaseKind::OpeningTorpedo,
+        BattlePhaseKind::MainShelling(HougekiSlot::First),
+        BattlePhaseKind::EscortShelling(HougekiSlot::Second),
+        BattlePhaseKind::MainShellingRound2(HougekiSlot::Third),
+        BattlePhaseKind::ClosingTorpedo,
+    ],
+};
+
+/// Combined-fleet long-distance shelling: main fleets, then escorts.
+pub(crate) static COMBINED_LD_SHOOTING: BattleFlow = BattleFlow {
+    phases: &[
+        BattlePhaseKind::MainShelling(HougekiSlot::First),
+        BattlePhaseKind::EscortShelling(HougekiSlot::Second),
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(
+        battle_type: BattleType,
+        friendly_type: Option<CombinedFleetType>,
+        enemy_combined: bool,
+    ) -> &'static BattleFlow {
+        BattleFlow::for_combined(battle_type, friendly_type, enemy_combined)
+    }
+
+    #[test]
+    fn combined_day_flow_follows_fleet_types() {
+        use CombinedFleetType::{CarrierTaskForce, SurfaceTaskForce, TransportEscort};
+
+        assert!(std::ptr::eq(
+            flow(BattleType::Normal, Some(SurfaceTaskForce), false),
+            &COMBINED_SURFACE_DAY
+        ));
+        assert!(std::ptr::eq(
+            flow(BattleType::Normal, Some(SurfaceTaskForce), true),
+            &COMBINED_SURFACE_EACH_DAY
+        ));
+        assert!(std::ptr::eq(
+            flow(BattleType::Normal, Some(CarrierTaskForce), true),
+            &COMBINED_EACH_DAY
+        ));
+        assert!(std::ptr::eq(
+            flow(BattleType::Normal, Some(TransportEscort), false),
+            &COMBINED_CARRIER_DAY
+        ));
+        assert!(std::ptr::eq(flow(BattleType::Normal, None, true), &COMBINED_CARRIER_DAY));
+        assert!(std::ptr::eq(
+            flow(BattleType::LdShooting, Some(CarrierTaskForce), false),
+            &COMBINED_LD_SHOOTING
+        ));
+        assert!(std::ptr::eq(flow(BattleType::LdAirBattle, None, true), &LD_AIR_BATTLE));
+    }
+
+    #[test]
+    fn twelve_vs_twelve_flows_order_shelling_by_fleet_type() {
+        use BattlePhaseKind::{
+            ClosingTorpedo, EscortShelling, MainShelling, MainShellingRound2, OpeningTorpedo,
+        };
+        use CombinedFleetType::{CarrierTaskForce, SurfaceTaskForce};
+        use HougekiSlot::{First, Second, Third};
+
+        let after_opening = |flow: &BattleFlow| {
+            let start = flow.phases.iter().position(|p| *p == OpeningTorpedo).unwrap() + 1;
+            flow.phases[start..].to_vec()
+        };
+
+        assert_eq!(
+            after_opening(flow(BattleType::Normal, Some(CarrierTaskForce), true)),
+            [
+                MainShelling(First),
+                EscortShelling(Second),
+                ClosingTorpedo,
+                MainShellingRound2(Third)
+            ]
+        );
+        assert_eq!(
+            after_opening(flow(BattleType::Normal, Some(SurfaceTaskForce), true)),
+            [
+                MainShelling(First),
+                EscortShelling(Second),
+                MainShellingRound2(Third),
+                ClosingTorpedo
+            ]
+        );
+    }
+}
@@ -5,13 +5,14 @@
 //! references to battle state and return computed values.
 
 use emukc_model::{
-    codex::Codex,
+    codex::{Codex, historical_bonus::HistoricalBonus},
     kc2::{
         KcApiSlotItem, KcShipType, KcSlotItemType3,
         start2::{ApiMstShip, ApiMstSlotitem},
     },
 };
 
+use crate::accuracy::HitResult;
 use crate::random::BattleRng;
 use crate::targeting::{
     has_active_asw_aircraft, has_slotitem_type, is_airstrike_attack_type, ship_type,
@@ -123,11 +124,50 @@ pub(crate) fn ammo_modifier(codex: &Codex, attacker: &BattleRuntimeShip) -> f64
     (ammo_percent as f64 / 50.0).min(1.0)
 }
 
+/// Give each ship the historical bonuses (特効) of the node that boost it.
+///
+/// A ship whose master data is unknown matches by id and equipment only.
+pub(crate) fn assign_historical_bonuses(
+    codex: &Codex,
+    ships: &mut [BattleRuntimeShip],
+    bonuses: &[HistoricalBonus],
+) {
+    for ship in ships {
+        let ship_id = ship.ship.api_ship_id;
+        let ship_class =
+            codex.find::<ApiMstShip>(&ship_id).map(|mst| mst.api_ctype).unwrap_or_default();
+        let equipment: Vec<i64> = ship.slot_items.iter().map(|si| si.api_slotitem_id).collect();
+        ship.historical_bonuses = bonuses
+            .iter()
+            .filter(|bonus| bonus.applies_to_ship(ship_id, ship_class, &equipment))
+            .cloned()
+            .collect();
+    }
+}
+
+/// Post-cap historical bonus modifier (特効補正) of `attacker` against `defender`.
+///
+/// Every bonus the attacker carries that counts against the defender
+/// multiplies in; `1.0` when none does.
+pub(crate) fn historical_modifier(
+    attacker: &BattleRuntimeShip,
+    defender: &BattleRuntimeShip,
+) -> f64 {
+    attacker
+        .historical_bonuses
+        .iter()
+        .filter(|bonus| bonus.applies_to_enemy(defender.ship.api_ship_id))
+        .map(|bonus| bonus.multiplier)
+        .product()
+}
+
 /// Calculate shelling damage for a single attack.
 ///
 /// When `ci_multiplier` is `Some(m)`, the multiplier is applied post-cap
 /// (after the daytime soft cap of 220) — matching `KanColle`'s artillery
-/// spotting damage pipeline.
+/// spotting damage pipeline. A missed `hit` deals 0 without drawing RNG; a
+/// critical applies its multiplier post-cap.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn calculate_shelling_damage(
     codex: &Codex,
     rng: &mut impl BattleRng,
@@ -136,7 +176,11 @@ pub(crate) fn calculate_shelling_damage(
     formation_id: i64,
     engagement: EngagementType,
     ci_multiplier: Option<f64>,
+    hit: HitResult,
 ) -> i64 {
+    if hit.is_miss() {
+        return 0;
+    }
     let basic_power = if is_cv_type(codex, attacker) {
         let bomber_count = bomber_plane_count(codex, attacker);
         if bomber_count > 0 {
@@ -147,7 +191,9 @@ pub(crate) fn calculate_shelling_damage(
     } else {
         attacker.ship.api_karyoku[0].max(0) as f64 + 5.0
     };
-    let bonus = improvement_bonus_day(codex, attacker) + light_gun_bonus(codex, attacker);
+    let bonus = improvement_bonus_day(codex, attacker)
+        + light_gun_bonus(codex, attacker)
+        + attacker.combined_power_bonus as f64;
     let dmg_state =
         damage_state_modifier(attacker.hp(), attacker.ship.api_maxhp, BattlePhase::DayShelling);
     let pre_cap = (basic_power + bonus)
@@ -158,12 +204,15 @@ pub(crate) fn calculate_shelling_damage(
     if let Some(m) = ci_multiplier {
         capped_power *= m;
     }
+    capped_power *= hit.damage_multiplier();
     capped_power *= ammo_modifier(codex, attacker);
+    capped_power *= historical_modifier(attacker, defender);
     let defense = calculate_defense_power(rng, defender.ship.api_soukou[0]);
     resolve_damage(rng, capped_power, defense, defender.hp())
 }
 
 /// Calculate torpedo damage for a single attack.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn calculate_torpedo_damage(
     codex: &Codex,
     rng: &mut impl BattleRng,
@@ -172,14 +221,20 @@ pub(crate) fn calculate_torpedo_damage(
     formation_id: i64,
     engagement: EngagementType,
     phase: BattlePhase,
+    hit: HitResult,
 ) -> i64 {
+    if hit.is_miss() {
+        return 0;
+    }
     let basic_power =
         attacker.ship.api_raisou[0].max(0) as f64 + improvement_bonus_torpedo(codex, attacker);
     let dmg_state = damage_state_modifier(attacker.hp(), attacker.ship.api_maxhp, phase);
     let pre_cap =
         basic_power * formation_modifier(formation_id) * engagement.modifier() * dmg_state;
     let mut capped_power = apply_cap(pre_cap, TORPEDO_CAP) as f64;
+    capped_power *= hit.damage_multiplier();
     capped_power *= ammo_modifier(codex, attacker);
+    capped_power *= historical_modifier(attacker, defender);
     let defense = calculate_defense_power(rng, defender.ship.api_soukou[0]);
     resolve_damage(rng, capped_power, defense, defender.hp())
 }
@@ -188,6 +243,7 @@ pub(crate) fn calculate_torpedo_damage(
 ///
 /// When `ci_multiplier` is `Some(m)`, the multiplier is applied to basic power
 /// *before* the soft cap at 360 — matching `KanColle`'s CI damage pipeline.
+/// The critical multiplier, unlike the CI one, is applied post-cap.
 pub(crate) fn calculate_night_damage(
     codex: &Codex,
     rng: &mut impl BattleRng,
@@ -195,7 +251,11 @@ pub(crate) fn calculate_night_damage(
     defender: &BattleRuntimeShip,
     air_state: Option<&AirState>,
     ci_multiplier: Option<f64>,
+    hit: HitResult,
 ) -> i64 {
+    if hit.is_miss() {
+        return 0;
+    }
     let basic_power = (attacker.ship.api_karyoku[0].max(0) + attacker.ship.api_raisou[0].max(0) + 5)
         as f64
         + improvement_bonus_night(codex, attacker)
@@ -205,7 +265,9 @@ pub(crate) fn calculate_night_damage(
         None => basic_power,
     };
     let mut capped_power = apply_cap(pre_cap, NIGHT_CAP) as f64;
+    capped_power *= hit.damage_multiplier();
     capped_power *= ammo_modifier(codex, attacker);
+    capped_power *= historical_modifier(attacker, defender);
     let defense = calculate_defense_power(rng, defender.ship.api_soukou[0]);
     resolve_damage(rng, capped_power, defense, defender.hp())
 }
@@ -218,7 +280,11 @@ pub(crate) fn calculate_asw_damage(
     defender: &BattleRuntimeShip,
     formation_id: i64,
     engagement: EngagementType,
+    hit: HitResult,
 ) -> i64 {
+    if hit.is_miss() {
+        return 0;
+    }
     let ship_asw = attacker.ship.api_taisen[0].max(0) as f64;
     let equip_asw = equipment_asw_total(codex, attacker);
     // base ASW = total ASW - equipment ASW (modernization + innate)
@@ -243,7 +309,9 @@ pub(crate) fn calculate_asw_damage(
         damage_state_modifier(attacker.hp(), attacker.ship.api_maxhp, BattlePhase::DayShelling);
     let modified =
         raw_power * asw_formation_modifier(formation_id) * engagement.modifier() * dmg_state;
-    let capped = apply_cap(modified, ASW_CAP) as f64;
+    let capped = apply_cap(modified, ASW_CAP) as f64
+        * hit.damage_multiplier()
+        * historical_modifier(attacker, defender);
     let defense = calculate_defense_power(rng, defender.ship.api_soukou[0]);
     let armor_reduction = depth_charge_armor_reduction(codex, attacker);
     let adjusted_defense = (defense - armor_reduction).max(0.0);
@@ -377,22 +445,28 @@ pub(crate) fn night_recon_bonus(
 // ---------------------------------------------------------------------------
 
 /// Shelling formation modifier.
+///
+/// Combined-fleet cruising formations: 第一 (11) 0.8×, 第二 (12) 1.0×,
+/// 第三 (13) 0.7×, 第四 (14) 1.1×.
 pub(crate) fn formation_modifier(formation_id: i64) -> f64 {
     match formation_id {
-        2 => 0.8,
-        3 => 0.7,
+        2 | 11 => 0.8,
+        3 | 13 => 0.7,
         4 => 0.85,
         5 => 0.6,
+        14 => 1.1,
         _ => 1.0,
     }
 }
 
-/// ASW formation modifier: Diamond (3) = 1.2×, Echelon (4) = 1.1×, Line Abreast (5) = 1.3×
+/// ASW formation modifier: Diamond (3) = 1.2×, Echelon (4) = 1.1×, Line Abreast (5) = 1.3×;
+/// combined 第一 (11) = 1.3×, 第二 (12) = 1.1×, 第四 (14) = 0.7×.
 pub(crate) fn asw_formation_modifier(formation_id: i64) -> f64 {
     match formation_id {
         3 => 1.2,
-        4 => 1.1,
-        5 => 1.3,
+        4 | 12 => 1.1,
+        5 | 11 => 1.3,
+        14 => 0.7,
         _ => 1.0,
     }
 }
@@ -548,6 +622,28 @@ mod tests {
     use emukc_model::kc2::types::KcShipType;
     use emukc_model::kc2::types::KcSlotItemType3;
 
+    #[test]
+    fn historical_modifier_multiplies_bonuses_against_the_target() {
+        let bonus = |enemy_ids: Vec<i64>, multiplier: f64| HistoricalBonus {
+            cells: vec![],
+            enemy_ids,
+            ship_ids: vec![],
+            ship_classes: vec![],
+            equipment: vec![],
+            multiplier,
+        };
+        let mut attacker = make_test_ship(30, 30, 30, 30);
+        let mut defender = make_test_ship_ctx(50, 50, 50, 50, false, true);
+        defender.ship.api_ship_id = 1653;
+        assert!((historical_modifier(&attacker, &defender) - 1.0).abs() < f64::EPSILON);
+
+        attacker.historical_bonuses = vec![bonus(vec![], 1.25), bonus(vec![1653], 1.2)];
+        assert!((historical_modifier(&attacker, &defender) - 1.5).abs() < 1e-9);
+
+        defender.ship.api_ship_id = 1501;
+        assert!((historical_modifier(&attacker, &defender) - 1.25).abs() < 1e-9);
+    }
+
     #[test]
     fn day_shelling_cap_matches_reference_example() {
         assert_eq!(apply_cap(250.0, 220.0), 225);
@@ -664,6 +760,7 @@ mod tests {
                 1,
                 EngagementType::SameCourse,
                 None,
+                HitResult::Hit,
             )
         };
 
@@ -705,6 +802,7 @@ mod tests {
                 1,
                 EngagementType::SameCourse,
                 BattlePhase::OpeningTorpedo,
+                HitResult::Hit,
             );
             let (atk, def) = build(bull_max / 10);
             let mut rng = crate::random::SeededRng::new(seed);
@@ -716,15 +814,18 @@ mod tests {
                 1,
                 EngagementType::SameCourse,
                 BattlePhase::OpeningTorpedo,
+                HitResult::Hit,
             );
             assert!(torp_low <= torp_full, "torpedo seed {seed}: {torp_low} > {torp_full}");
 
             let (atk, def) = build(bull_max);
             let mut rng = crate::random::SeededRng::new(seed);
-            let night_full = calculate_night_damage(&codex, &mut rng, &atk, &def, None, None);
+            let night_full =
+                calculate_night_damage(&codex, &mut rng, &atk, &def, None, None, HitResult::Hit);
             let (atk, def) = build(bull_max / 10);
             let mut rng = crate::random::SeededRng::new(seed);
-            let night_low = calculate_night_damage(&codex, &mut rng, &atk, &def, None, None);
+            let night_low =
+                calculate_night_damage(&codex, &mut rng, &atk, &def, None, None, HitResult::Hit);
             assert!(night_low <= night_full, "night seed {seed}: {night_low} > {night_full}");
         }
     }
@@ -750,6 +851,7 @@ mod tests {
                 1,
                 EngagementType::SameCourse,
                 None,
+                HitResult::Hit,
             )
         };
 
@@ -831,6 +933,7 @@ mod tests {
             1,
             EngagementType::SameCourse,
             None,
+            HitResult::Hit,
         );
         // Scratch damage is proportional to target HP: 0.06*H + 0.08*rand(0,H-1)
         // It should be much less than capped_power - defense (which would be negative)
@@ -855,6 +958,7 @@ mod tests {
             1,
             EngagementType::SameCourse,
             None,
+            HitResult::Hit,
         );
         // capped ~205, defense ~7-13, so damage should be 192-198
         assert!(dmg > 100, "normal damage should be large: got {dmg}");
@@ -876,6 +980,7 @@ mod tests {
             1,
             EngagementType::SameCourse,
             BattlePhase::OpeningTorpedo,
+            HitResult::Hit,
         );
         // Basic power = 100 (NOT 105). After formation (1.0) and engagement (1.0), capped at 100.
         // Defense with armor 10: ~7-13. Damage ~87-93.
@@ -901,6 +1006,7 @@ mod tests {
             1,
             EngagementType::SameCourse,
             BattlePhase::OpeningTorpedo,
+            HitResult::Hit,
         );
         assert_eq!(dmg, 0, "taiha torpedo should deal 0 damage, got {dmg}");
     }
@@ -940,6 +1046,7 @@ mod tests {
             &defender,
             1, // line ahead
             EngagementType::SameCourse,
+            HitResult::Hit,
         );
 
         // Verify damage is positive and uses the ASW formula (not shelling formula)
@@ -974,6 +1081,7 @@ mod tests {
             1,
             EngagementType::SameCourse,
             None,
+            HitResult::Hit,
         );
         let penalized_damage = calculate_shelling_damage(
             &codex,
@@ -983,6 +1091,7 @@ mod tests {
             5,
             EngagementType::TDisadvantage,
             None,
+            HitResult::Hit,
         );
 
         assert!(normal_damage > penalized_damage);
@@ -1021,6 +1130,7 @@ mod tests {
             1,
             EngagementType::SameCourse,
             None,
+            HitResult::Hit,
         );
         assert!(dmg > 0, "CV with bombers should deal shelling damage");
     }
@@ -1057,6 +1167,7 @@ mod tests {
             &defender,
             1,
             EngagementType::SameCourse,
+            HitResult::Hit,
         );
         assert!(dmg > 0, "ASW with both aircraft and depth charge should deal damage");
     }
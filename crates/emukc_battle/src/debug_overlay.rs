@@ -116,7 +116,10 @@ pub(crate) fn apply_day_debug(
     god_mode: bool,
     one_hit_kill: bool,
 ) -> BattleSimulation {
-    if !god_mode && !one_hit_kill {
+    // The event transforms index single fleets only; combined-fleet battles
+    // pass through untouched.
+    let is_combined = !sim.friendly_escort.is_empty() || !sim.enemy_escort.is_empty();
+    if (!god_mode && !one_hit_kill) || is_combined {
         return sim;
     }
 
@@ -142,7 +145,9 @@ pub(crate) fn apply_night_debug(
     god_mode: bool,
     one_hit_kill: bool,
 ) -> NightBattleSimulation {
-    if !god_mode && !one_hit_kill {
+    // Single fleets only, as in `apply_day_debug`.
+    let is_combined = !sim.friendly_escort.is_empty() || !sim.enemy_escort.is_empty();
+    if (!god_mode && !one_hit_kill) || is_combined {
         return sim;
     }
 
@@ -461,6 +466,8 @@ mod tests {
             formation: [1, 1, 1],
             friendly_nowhps,
             enemy_nowhps,
+            friendly_nowhps_combined: None,
+            enemy_nowhps_combined: None,
             smoke_type: 0,
             balloon_cell: 0,
             atoll_cell: 0,
@@ -477,6 +484,9 @@ mod tests {
             hougeki2: None,
             hougeki3: None,
             raigeki: None,
+            air_base_attack: None,
+            support_flag: 0,
+            support_info: None,
         }
     }
 
@@ -485,10 +495,14 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![make_ship(0, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: BattlePacket {
                 formation: [1, 1, 1],
                 friendly_nowhps: vec![30],
                 enemy_nowhps: vec![0],
+                friendly_nowhps_combined: None,
+                enemy_nowhps_combined: None,
                 smoke_type: 0,
                 balloon_cell: 0,
                 atoll_cell: 0,
@@ -505,12 +519,16 @@ mod tests {
                 hougeki2: None,
                 hougeki3: None,
                 raigeki: None,
+                air_base_attack: None,
+                support_flag: 0,
+                support_info: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::S,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim.clone(), false, false);
         assert_eq!(result.friendly[0].hp(), 30);
@@ -525,10 +543,14 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![damaged_ship],
             enemy: vec![make_ship(0, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: BattlePacket {
                 formation: [1, 1, 1],
                 friendly_nowhps: vec![10],
                 enemy_nowhps: vec![0],
+                friendly_nowhps_combined: None,
+                enemy_nowhps_combined: None,
                 smoke_type: 0,
                 balloon_cell: 0,
                 atoll_cell: 0,
@@ -545,12 +567,16 @@ mod tests {
                 hougeki2: None,
                 hougeki3: None,
                 raigeki: None,
+                air_base_attack: None,
+                support_flag: 0,
+                support_info: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::S,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, true, false);
         assert_eq!(result.friendly[0].hp(), 30, "god_mode restores friendly HP");
@@ -564,10 +590,14 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![enemy_ship],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: BattlePacket {
                 formation: [1, 1, 1],
                 friendly_nowhps: vec![30],
                 enemy_nowhps: vec![30],
+                friendly_nowhps_combined: None,
+                enemy_nowhps_combined: None,
                 smoke_type: 0,
                 balloon_cell: 0,
                 atoll_cell: 0,
@@ -584,12 +614,16 @@ mod tests {
                 hougeki2: None,
                 hougeki3: None,
                 raigeki: None,
+                air_base_attack: None,
+                support_flag: 0,
+                support_info: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, false, true);
         assert_eq!(result.enemy[0].hp(), 0, "one_hit_kill sinks all enemies");
@@ -605,10 +639,14 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![damaged],
             enemy: vec![make_ship(30, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: BattlePacket {
                 formation: [1, 1, 1],
                 friendly_nowhps: vec![10],
                 enemy_nowhps: vec![30],
+                friendly_nowhps_combined: None,
+                enemy_nowhps_combined: None,
                 smoke_type: 0,
                 balloon_cell: 0,
                 atoll_cell: 0,
@@ -625,12 +663,16 @@ mod tests {
                 hougeki2: None,
                 hougeki3: None,
                 raigeki: None,
+                air_base_attack: None,
+                support_flag: 0,
+                support_info: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, true, true);
         assert_eq!(result.friendly[0].hp(), 30, "god_mode restores friendly");
@@ -644,10 +686,14 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![make_ship(30, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: BattlePacket {
                 formation: [1, 1, 1],
                 friendly_nowhps: vec![30],
                 enemy_nowhps: vec![30],
+                friendly_nowhps_combined: None,
+                enemy_nowhps_combined: None,
                 smoke_type: 0,
                 balloon_cell: 0,
                 atoll_cell: 0,
@@ -664,12 +710,16 @@ mod tests {
                 hougeki2: None,
                 hougeki3: None,
                 raigeki: None,
+                air_base_attack: None,
+                support_flag: 0,
+                support_info: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
                 mvp: 0,
                 can_midnight: true,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, false, true);
         assert!(!result.outcome.can_midnight, "one_hit_kill clears can_midnight");
@@ -682,10 +732,14 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![make_ship(30, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: BattlePacket {
                 formation: [1, 1, 1],
                 friendly_nowhps: vec![30],
                 enemy_nowhps: vec![30],
+                friendly_nowhps_combined: None,
+                enemy_nowhps_combined: None,
                 smoke_type: 0,
                 balloon_cell: 0,
                 atoll_cell: 0,
@@ -702,12 +756,16 @@ mod tests {
                 hougeki2: None,
                 hougeki3: None,
                 raigeki: None,
+                air_base_attack: None,
+                support_flag: 0,
+                support_info: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, false, true);
         assert_eq!(result.outcome.win_rank, KcSortieResultRank::S);
@@ -722,10 +780,14 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![sunk_ship],
             enemy: vec![make_test_ship_ctx(0, 0, 0, 40, false, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: BattlePacket {
                 formation: [1, 1, 1],
                 friendly_nowhps: vec![0],
                 enemy_nowhps: vec![0],
+                friendly_nowhps_combined: None,
+                enemy_nowhps_combined: None,
                 smoke_type: 0,
                 balloon_cell: 0,
                 atoll_cell: 0,
@@ -742,12 +804,16 @@ mod tests {
                 hougeki2: None,
                 hougeki3: None,
                 raigeki: None,
+                air_base_attack: None,
+                support_flag: 0,
+                support_info: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::B,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, true, false);
         assert_eq!(result.friendly[0].hp(), 30, "god_mode revives practice-friendly to entry HP");
@@ -760,15 +826,25 @@ mod tests {
         let sim = NightBattleSimulation {
             friendly: vec![damaged],
             enemy: vec![make_ship(0, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: crate::NightBattlePacket {
                 formation: [1, 1, 1],
+                active_deck: [1, 1],
                 friendly_nowhps: vec![10],
                 friendly_maxhps: vec![40],
                 enemy_nowhps: vec![0],
                 enemy_maxhps: vec![40],
+                friendly_nowhps_combined: None,
+                friendly_maxhps_combined: None,
+                enemy_nowhps_combined: None,
+                enemy_maxhps_combined: None,
                 touch_plane: [-1, -1],
                 flare_pos: [-1, -1],
                 hougeki: None,
+                friendly_info: None,
+                friendly_kouku: None,
+                friendly_battle: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::S,
@@ -786,15 +862,25 @@ mod tests {
         let sim = NightBattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![make_ship(30, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: crate::NightBattlePacket {
                 formation: [1, 1, 1],
+                active_deck: [1, 1],
                 friendly_nowhps: vec![30],
                 friendly_maxhps: vec![40],
                 enemy_nowhps: vec![30],
                 enemy_maxhps: vec![40],
+                friendly_nowhps_combined: None,
+                friendly_maxhps_combined: None,
+                enemy_nowhps_combined: None,
+                enemy_maxhps_combined: None,
                 touch_plane: [-1, -1],
                 flare_pos: [-1, -1],
                 hougeki: None,
+                friendly_info: None,
+                friendly_kouku: None,
+                friendly_battle: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
@@ -818,12 +904,15 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![make_ship(25, 40, false), make_ship(12, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: day_packet(vec![30], vec![25, 12]),
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, false, true);
         let h3 = result.packet.hougeki3.expect("finishing volley synthesized");
@@ -844,12 +933,15 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![make_ship(25, 40, false), make_ship(12, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: day_packet(vec![30], vec![25, 12]),
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, false, true);
         let h3 = result.packet.hougeki3.expect("finishing volley synthesized");
@@ -885,12 +977,15 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![damaged],
             enemy: vec![make_ship(25, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: day_packet(vec![10], vec![25]),
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::B,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, true, false);
         assert!(result.packet.hougeki3.is_none(), "god_mode adds no finishing volley");
@@ -927,12 +1022,15 @@ mod tests {
         let sim = BattleSimulation {
             friendly: vec![damaged],
             enemy: vec![make_ship(0, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet,
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::B,
                 mvp: 0,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         };
         let result = apply_day_debug(sim, true, false);
 
@@ -979,15 +1077,25 @@ mod tests {
         let sim = NightBattleSimulation {
             friendly: vec![make_ship(30, 40, true)],
             enemy: vec![make_ship(25, 40, false), make_ship(12, 40, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: crate::NightBattlePacket {
                 formation: [1, 1, 1],
+                active_deck: [1, 1],
                 friendly_nowhps: vec![30],
                 friendly_maxhps: vec![40],
                 enemy_nowhps: vec![25, 12],
                 enemy_maxhps: vec![40, 40],
+                friendly_nowhps_combined: None,
+                friendly_maxhps_combined: None,
+                enemy_nowhps_combined: None,
+                enemy_maxhps_combined: None,
                 touch_plane: [-1, -1],
                 flare_pos: [-1, -1],
                 hougeki: None,
+                friendly_info: None,
+                friendly_kouku: None,
+                friendly_battle: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::D,
@@ -7,8 +7,11 @@ use emukc_model::codex::Codex;
 
 use crate::debug_overlay::{apply_day_debug, apply_night_debug};
 use crate::random::BattleRng;
-use crate::simulation::{simulate_day, simulate_night};
-use crate::types::{BattleContext, BattleSimulation, NightBattleInput, NightBattleSimulation};
+use crate::simulation::{base_air, simulate_day, simulate_night};
+use crate::types::{
+    AirRaidInput, AirRaidSimulation, BattleContext, BattleRuntimeShip, BattleSimulation,
+    NightBattleInput, NightBattleSimulation,
+};
 
 /// Execute a day battle and apply the debug policy from `Codex`.
 ///
@@ -42,6 +45,28 @@ pub fn execute_night(
     )
 }
 
+/// Execute an enemy air raid on the player's airbases.
+///
+/// Airbases are not ships, so the debug overlay does not apply.
+pub fn execute_air_raid(
+    codex: &Codex,
+    input: AirRaidInput,
+    rng: &mut impl BattleRng,
+) -> AirRaidSimulation {
+    let mut bases = input.bases;
+    let mut enemy: Vec<BattleRuntimeShip> = input
+        .enemy_ships
+        .into_iter()
+        .map(|ship| BattleRuntimeShip::new(ship, false, true))
+        .collect();
+    let (packet, base_damage) = base_air::simulate_air_raid(codex, rng, &mut bases, &mut enemy);
+    AirRaidSimulation {
+        packet,
+        bases,
+        base_damage,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use emukc_model::codex::Codex;
@@ -52,7 +77,8 @@ mod tests {
     use crate::simulation::{simulate_day, simulate_night};
     use crate::test_utils::sample_ship;
     use crate::types::{
-        BattleContext, BattleRuntimeShip, BattleType, EngagementType, NightBattleInput,
+        BattleContext, BattlePacket, BattleRuntimeShip, BattleType, EngagementType,
+        NightBattleInput,
     };
 
     const SEED: u64 = 0xE7EC_0710;
@@ -74,6 +100,10 @@ mod tests {
             engagement: EngagementType::SameCourse,
             friend_ships: vec![sample_ship(codex, 79, 99), sample_ship(codex, 79, 99)],
             enemy_ships: vec![sample_ship(codex, 412, 99), sample_ship(codex, 412, 99)],
+            air_base_waves: Vec::new(),
+            combined: None,
+            support: None,
+            historical_bonuses: Vec::new(),
         }
     }
 
@@ -87,10 +117,14 @@ mod tests {
                 BattleRuntimeShip::new(sample_ship(codex, 412, 99), false, true),
                 BattleRuntimeShip::new(sample_ship(codex, 412, 99), false, true),
             ],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             friendly_formation_id: 1,
             enemy_formation_id: 1,
             engagement: EngagementType::SameCourse,
             air_state: None,
+            friend_fleet: None,
+            historical_bonuses: Vec::new(),
         }
     }
 
@@ -109,6 +143,20 @@ mod tests {
         assert_eq!(raw_next, executed_next, "execution facade must not consume extra RNG");
     }
 
+    #[test]
+    fn day_simulation_state_roundtrips_through_json() {
+        let codex = load_codex(false, false);
+        let day = execute_day(&codex, day_context(&codex), &mut SeededRng::new(SEED));
+
+        let packet: BattlePacket =
+            serde_json::from_str(&serde_json::to_string(&day.packet).unwrap()).unwrap();
+        assert_eq!(format!("{packet:#?}"), format!("{:#?}", day.packet));
+
+        let friendly: Vec<BattleRuntimeShip> =
+            serde_json::from_str(&serde_json::to_string(&day.friendly).unwrap()).unwrap();
+        assert_eq!(format!("{friendly:#?}"), format!("{:#?}", day.friendly));
+    }
+
     #[test]
     fn execute_night_matches_raw_simulation_when_debug_is_disabled() {
         let codex = load_codex(false, false);
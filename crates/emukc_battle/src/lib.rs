@@ -3,6 +3,8 @@
 //! Pure computation crate — takes `Codex` (read-only) and battle inputs,
 //! produces battle simulation results. No database, HTTP, or side effects.
 
+/// Hit checks: accuracy, evasion and critical rolls.
+mod accuracy;
 /// Internal battle configuration.
 mod config;
 mod damage;
@@ -40,10 +42,15 @@ mod test_utils;
 
 // Public API — types
 pub use types::{
-    AirState, BattleContext, BattleHougeki, BattleKouku, BattleKoukuStage1, BattleKoukuStage2,
-    BattleKoukuStage3, BattleNightHougeki, BattleOpeningAttack, BattleOutcome, BattlePacket,
-    BattleRaigeki, BattleRuntimeShip, BattleShipInput, BattleSimulation, BattleType,
-    EngagementType, NightBattleInput, NightBattlePacket, NightBattleSimulation, SiListId,
+    AirRaidInput, AirRaidSimulation, AirState, BattleAirBaseAttack, BattleAirBaseSquadron,
+    BattleAirBaseStage3, BattleAirBaseWave, BattleAirFire, BattleAirRaid, BattleAirRaidAttack,
+    BattleAirRaidStage3, BattleContext, BattleFriendlyBattle, BattleFriendlyInfo, BattleHougeki,
+    BattleKouku, BattleKoukuStage1, BattleKoukuStage2, BattleKoukuStage3, BattleNightHougeki,
+    BattleOpeningAttack, BattleOutcome, BattlePacket, BattleRaigeki, BattleRuntimeShip,
+    BattleShipInput, BattleSimulation, BattleSquadronPlane, BattleSupportAirAttack,
+    BattleSupportHourai, BattleSupportInfo, BattleSupportInput, BattleType, CombinedFleetInput,
+    CombinedFleetType, EngagementType, FriendFleetInput, NightBattleInput, NightBattlePacket,
+    NightBattleSimulation, SiListId,
 };
 
 // Public API — RNG
@@ -52,7 +59,7 @@ pub use random::BattleRng;
 // Public API — utilities
 pub use damage::apply_cap;
 // Public API — complete battle execution
-pub use execution::{execute_day, execute_night};
+pub use execution::{execute_air_raid, execute_day, execute_night};
 pub use outcome::{calculate_mvp, calculate_win_rank};
 pub use targeting::any_alive;
 
@@ -57,7 +57,7 @@ pub fn calculate_win_rank(
 }
 
 /// Post-simulation integrity check: verifies that protected friendly ships
-/// (non-taiha at entry + flagship) have HP >= 1.
+/// (non-taiha at entry + flagships) have HP >= 1.
 /// Panics in debug builds, logs error in release builds.
 pub(crate) fn verify_protected_ships_alive(ships: &[BattleRuntimeShip]) {
     for (idx, ship) in ships.iter().enumerate() {
@@ -65,7 +65,7 @@ pub(crate) fn verify_protected_ships_alive(ships: &[BattleRuntimeShip]) {
             continue;
         }
         let was_taiha = ship.entry_hp * 4 <= ship.ship.api_maxhp;
-        let is_protected = idx == 0 || !was_taiha;
+        let is_protected = idx == 0 || ship.is_escort_flagship || !was_taiha;
         if is_protected && ship.hp() < 1 {
             let msg = format!(
                 "BUG: protected ship at index {} has hp={}, entry_hp={}, maxhp={}",
@@ -0,0 +1,772 @@
+//! Anti-air fire (kouku stage 2).
+//!
+//! Every attacking bomber slot is fired on by one random alive defender. The
+//! defender shoots planes down in two independent parts, each succeeding on a
+//! 50% roll:
+//!
+//! ```text
+//! proportional = floor(slot × adjusted_aa × 0.02 × 0.25)
+//! fixed        = floor((adjusted_aa + fleet_aa) × side × aaci_modifier / 10)
+//! ```
+//!
+//! followed by a guaranteed minimum (1 for the player fleet, plus the AACI
+//! fixed bonus). The friendly fleet can trigger at most one anti-air cut-in
+//! (対空カットイン, AACI) per battle; it is reported in `api_air_fire`.
+
+use emukc_model::{
+    codex::Codex,
+    kc2::{KcShipType, KcSlotItemType3, start2::ApiMstSlotitem},
+};
+
+use crate::random::BattleRng;
+use crate::targeting::{is_airstrike_attack_type, ship_mst, ship_type};
+use crate::types::{BattleAirFire, BattleRuntimeShip};
+
+/// Equipment icon (`api_type[3]`) shared by all high-angle guns.
+const HIGH_ANGLE_GUN_ICON: i64 = 16;
+
+/// High-angle guns with a built-in AA fire director (高角砲+高射装置).
+const HIGH_ANGLE_GUN_WITH_DIRECTOR_IDS: &[i64] = &[122, 130, 135, 172, 275, 295, 296, 308, 313];
+
+/// Minimum equipment AA for a radar to count as an air radar.
+const AIR_RADAR_MIN_AA: i64 = 2;
+
+/// Minimum equipment AA for a machine gun to count as concentrated (集中配備).
+const CONCENTRATED_AA_GUN_MIN_AA: i64 = 9;
+
+/// Minimum equipment AA for the secondary machine gun of kind 12.
+const AA_GUN_MIN_AA: i64 = 3;
+
+/// 秋月型 class id (`api_ctype`).
+const AKIZUKI_CLASS: i64 = 54;
+/// 摩耶改二.
+const MAYA_K2_ID: i64 = 428;
+/// 五十鈴改二.
+const ISUZU_K2_ID: i64 = 141;
+/// 霞改二乙.
+const KASUMI_K2B_ID: i64 = 470;
+/// 皐月改二.
+const SATSUKI_K2_ID: i64 = 418;
+/// 鬼怒改二.
+const KINU_K2_ID: i64 = 487;
+/// 由良改二.
+const YURA_K2_ID: i64 = 488;
+/// 文月改二.
+const FUMIZUKI_K2_ID: i64 = 548;
+
+/// Ship types allowed to fire the main-gun + AA shell cut-ins (kinds 4 and 6).
+const BB_CLASS_TYPES: &[KcShipType] =
+    &[KcShipType::FBB, KcShipType::BB, KcShipType::BBV, KcShipType::XBB];
+
+/// Fixed shootdown side modifier when the player fleet is firing.
+const FRIENDLY_FIXED_MODIFIER: f64 = 1.0;
+/// Fixed shootdown side modifier when the enemy fleet is firing.
+const ENEMY_FIXED_MODIFIER: f64 = 0.75;
+
+// ---------------------------------------------------------------------------
+// Equipment classification
+// ---------------------------------------------------------------------------
+
+/// An equipment piece reduced to the fields the anti-air formulas read.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AaEquip {
+    id: i64,
+    type3: i64,
+    icon: i64,
+    tyku: i64,
+}
+
+impl AaEquip {
+    fn from_mst(mst: &ApiMstSlotitem) -> Self {
+        Self {
+            id: mst.api_id,
+            type3: mst.api_type[2],
+            icon: mst.api_type[3],
+            tyku: mst.api_tyku.max(0),
+        }
+    }
+
+    fn type3(self) -> Option<KcSlotItemType3> {
+        KcSlotItemType3::n(self.type3)
+    }
+
+    fn is_high_angle_gun(self) -> bool {
+        self.icon == HIGH_ANGLE_GUN_ICON
+    }
+
+    fn has_builtin_director(self) -> bool {
+        self.is_high_angle_gun() && HIGH_ANGLE_GUN_WITH_DIRECTOR_IDS.contains(&self.id)
+    }
+
+    fn is_aa_director(self) -> bool {
+        self.type3() == Some(KcSlotItemType3::AntiAircraftGunMount)
+    }
+
+    fn is_air_radar(self) -> bool {
+        matches!(
+            self.type3(),
+            Some(
+                KcSlotItemType3::SmallRadar
+                    | KcSlotItemType3::LargeRadar
+                    | KcSlotItemType3::LargeRadar2
+            )
+        ) && self.tyku >= AIR_RADAR_MIN_AA
+    }
+
+    fn is_aa_shell(self) -> bool {
+        self.type3() == Some(KcSlotItemType3::AntiAircraftShell)
+    }
+
+    fn is_aa_gun(self) -> bool {
+        self.type3() == Some(KcSlotItemType3::AntiAircraftGun)
+    }
+
+    fn is_large_main_gun(self) -> bool {
+        matches!(
+            self.type3(),
+            Some(KcSlotItemType3::LargeCaliberMainGun | KcSlotItemType3::LargeCaliberMainGun2)
+        )
+    }
+
+    /// Per-point weight of equipment AA in the ship's adjusted AA.
+    fn adjusted_multiplier(self) -> f64 {
+        if self.is_aa_gun() {
+            6.0
+        } else if self.is_high_angle_gun() || self.is_aa_director() {
+            4.0
+        } else if self.is_air_radar() {
+            3.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Per-point weight of equipment AA in the fleet AA bonus.
+    fn fleet_multiplier(self) -> f64 {
+        if self.is_high_angle_gun() || self.is_aa_director() {
+            0.35
+        } else if self.is_air_radar() {
+            0.4
+        } else if self.is_aa_shell() {
+            0.6
+        } else {
+            0.2
+        }
+    }
+}
+
+/// Equipment slot requirement of an AACI kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AaRequirement {
+    HighAngleGun,
+    HighAngleGunWithDirector,
+    HighAngleGunWithoutDirector,
+    AaDirector,
+    AirRadar,
+    AaShell,
+    LargeMainGun,
+    AaGun,
+    ConcentratedAaGun,
+}
+
+impl AaRequirement {
+    fn matches(self, equip: AaEquip) -> bool {
+        match self {
+            Self::HighAngleGun => equip.is_high_angle_gun(),
+            Self::HighAngleGunWithDirector => equip.has_builtin_director(),
+            Self::HighAngleGunWithoutDirector => {
+                equip.is_high_angle_gun() && !equip.has_builtin_director()
+            }
+            Self::AaDirector => equip.is_aa_director(),
+            Self::AirRadar => equip.is_air_radar(),
+            Self::AaShell => equip.is_aa_shell(),
+            Self::LargeMainGun => equip.is_large_main_gun(),
+            Self::AaGun => equip.is_aa_gun() && equip.tyku >= AA_GUN_MIN_AA,
+            Self::ConcentratedAaGun => {
+                equip.is_aa_gun() && equip.tyku >= CONCENTRATED_AA_GUN_MIN_AA
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AACI table
+// ---------------------------------------------------------------------------
+
+/// Which ships may fire an AACI kind.
+#[derive(Debug, Clone, Copy)]
+enum ShipGate {
+    Any,
+    Class(i64),
+    Ship(i64),
+    Types(&'static [KcShipType]),
+}
+
+/// One anti-air cut-in kind (`api_kind`).
+#[derive(Debug)]
+pub(crate) struct AaciKind {
+    pub(crate) kind: i64,
+    /// Added to every fixed shootdown while the cut-in is active.
+    pub(crate) fixed_bonus: i64,
+    /// Multiplies the fixed shootdown while the cut-in is active.
+    pub(crate) modifier: f64,
+    /// Trigger chance in percent.
+    rate: i64,
+    gate: ShipGate,
+    /// Distinct equipment pieces required, most specific first.
+    requires: &'static [AaRequirement],
+}
+
+use AaRequirement as R;
+
+/// Known AACI kinds in trigger priority order: ship-specific kinds first, then
+/// the generic equipment kinds from strongest to weakest.
+const AACI_KINDS: &[AaciKind] = &[
+    AaciKind {
+        kind: 1,
+        fixed_bonus: 7,
+        modifier: 1.7,
+        rate: 65,
+        gate: ShipGate::Class(AKIZUKI_CLASS),
+        requires: &[R::HighAngleGun, R::HighAngleGun, R::AirRadar],
+    },
+    AaciKind {
+        kind: 2,
+        fixed_bonus: 6,
+        modifier: 1.7,
+        rate: 58,
+        gate: ShipGate::Class(AKIZUKI_CLASS),
+        requires: &[R::HighAngleGun, R::AirRadar],
+    },
+    AaciKind {
+        kind: 3,
+        fixed_bonus: 4,
+        modifier: 1.6,
+        rate: 50,
+        gate: ShipGate::Class(AKIZUKI_CLASS),
+        requires: &[R::HighAngleGun, R::HighAngleGun],
+    },
+    AaciKind {
+        kind: 10,
+        fixed_bonus: 8,
+        modifier: 1.65,
+        rate: 60,
+        gate: ShipGate::Ship(MAYA_K2_ID),
+        requires: &[R::ConcentratedAaGun, R::HighAngleGun, R::AirRadar],
+    },
+    AaciKind {
+        kind: 11,
+        fixed_bonus: 6,
+        modifier: 1.5,
+        rate: 55,
+        gate: ShipGate::Ship(MAYA_K2_ID),
+        requires: &[R::ConcentratedAaGun, R::HighAngleGun],
+    },
+    AaciKind {
+        kind: 14,
+        fixed_bonus: 4,
+        modifier: 1.45,
+        rate: 63,
+        gate: ShipGate::Ship(ISUZU_K2_ID),
+        requires: &[R::HighAngleGun, R::AaGun, R::AirRadar],
+    },
+    AaciKind {
+        kind: 15,
+        fixed_bonus: 3,
+        modifier: 1.3,
+        rate: 54,
+        gate: ShipGate::Ship(ISUZU_K2_ID),
+        requires: &[R::HighAngleGun, R::AaGun],
+    },
+    AaciKind {
+        kind: 16,
+        fixed_bonus: 4,
+        modifier: 1.4,
+        rate: 60,
+        gate: ShipGate::Ship(KASUMI_K2B_ID),
+        requires: &[R::HighAngleGun, R::AaGun, R::AirRadar],
+    },
+    AaciKind {
+        kind: 17,
+        fixed_bonus: 2,
+        modifier: 1.25,
+        rate: 55,
+        gate: ShipGate::Ship(KASUMI_K2B_ID),
+        requires: &[R::HighAngleGun, R::AaGun],
+    },
+    AaciKind {
+        kind: 18,
+        fixed_bonus: 2,
+        modifier: 1.2,
+        rate: 60,
+        gate: ShipGate::Ship(SATSUKI_K2_ID),
+        requires: &[R::ConcentratedAaGun],
+    },
+    AaciKind {
+        kind: 19,
+        fixed_bonus: 5,
+        modifier: 1.45,
+        rate: 55,
+        gate: ShipGate::Ship(KINU_K2_ID),
+        requires: &[R::ConcentratedAaGun, R::HighAngleGunWithoutDirector],
+    },
+    AaciKind {
+        kind: 20,
+        fixed_bonus: 3,
+        modifier: 1.25,
+        rate: 65,
+        gate: ShipGate::Ship(KINU_K2_ID),
+        requires: &[R::ConcentratedAaGun],
+    },
+    AaciKind {
+        kind: 21,
+        fixed_bonus: 5,
+        modifier: 1.45,
+        rate: 60,
+        gate: ShipGate::Ship(YURA_K2_ID),
+        requires: &[R::HighAngleGun, R::AirRadar],
+    },
+    AaciKind {
+        kind: 22,
+        fixed_bonus: 2,
+        modifier: 1.2,
+        rate: 60,
+        gate: ShipGate::Ship(FUMIZUKI_K2_ID),
+        requires: &[R::ConcentratedAaGun],
+    },
+    AaciKind {
+        kind: 4,
+        fixed_bonus: 6,
+        modifier: 1.5,
+        rate: 52,
+        gate: ShipGate::Types(BB_CLASS_TYPES),
+        requires: &[R::LargeMainGun, R::AaShell, R::AaDirector, R::AirRadar],
+    },
+    AaciKind {
+        kind: 5,
+        fixed_bonus: 4,
+        modifier: 1.5,
+        rate: 55,
+        gate: ShipGate::Any,
+        requires: &[R::HighAngleGunWithDirector, R::HighAngleGunWithDirector, R::AirRadar],
+    },
+    AaciKind {
+        kind: 6,
+        fixed_bonus: 4,
+        modifier: 1.45,
+        rate: 40,
+        gate: ShipGate::Types(BB_CLASS_TYPES),
+        requires: &[R::LargeMainGun, R::AaShell, R::AaDirector],
+    },
+    AaciKind {
+        kind: 8,
+        fixed_bonus: 4,
+        modifier: 1.4,
+        rate: 50,
+        gate: ShipGate::Any,
+        requires: &[R::HighAngleGunWithDirector, R::AirRadar],
+    },
+    AaciKind {
+        kind: 7,
+        fixed_bonus: 3,
+        modifier: 1.35,
+        rate: 45,
+        gate: ShipGate::Any,
+        requires: &[R::HighAngleGun, R::AaDirector, R::AirRadar],
+    },
+    AaciKind {
+        kind: 9,
+        fixed_bonus: 2,
+        modifier: 1.3,
+        rate: 40,
+        gate: ShipGate::Any,
+        requires: &[R::HighAngleGun, R::AaDirector],
+    },
+    AaciKind {
+        kind: 12,
+        fixed_bonus: 3,
+        modifier: 1.25,
+        rate: 45,
+        gate: ShipGate::Any,
+        requires: &[R::ConcentratedAaGun, R::AaGun, R::AirRadar],
+    },
+];
+
+/// Identity of a ship as seen by the AACI ship gates.
+#[derive(Debug, Clone, Copy, Default)]
+struct ShipIdentity {
+    ship_id: i64,
+    ctype: i64,
+    stype: Option<KcShipType>,
+}
+
+impl ShipGate {
+    fn allows(self, ship: ShipIdentity) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Class(ctype) => ship.ctype == ctype,
+            Self::Ship(id) => ship.ship_id == id,
+            Self::Types(types) => ship.stype.is_some_and(|st| types.contains(&st)),
+        }
+    }
+}
+
+impl AaciKind {
+    /// Match the kind's requirements against distinct equipment pieces.
+    ///
+    /// Returns the equipment ids used, in requirement order, or `None` when the
+    /// loadout cannot satisfy every requirement.
+    fn match_equipment(&self, equips: &[AaEquip]) -> Option<Vec<i64>> {
+        let mut used = vec![false; equips.len()];
+        let mut use_items = Vec::with_capacity(self.requires.len());
+        for requirement in self.requires {
+            let idx = equips
+                .iter()
+                .enumerate()
+                .position(|(idx, &equip)| !used[idx] && requirement.matches(equip))?;
+            used[idx] = true;
+            use_items.push(equips[idx].id);
+        }
+        Some(use_items)
+    }
+}
+
+/// A triggered anti-air cut-in.
+#[derive(Debug)]
+pub(crate) struct AntiAirCutin {
+    /// 0-based index of the firing ship.
+    pub(crate) ship_idx: usize,
+    pub(crate) kind: &'static AaciKind,
+    pub(crate) use_items: Vec<i64>,
+}
+
+impl AntiAirCutin {
+    pub(crate) fn to_air_fire(&self) -> BattleAirFire {
+        BattleAirFire {
+            api_idx: self.ship_idx as i64,
+            api_kind: self.kind.kind,
+            api_use_items: self.use_items.clone(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AA values
+// ---------------------------------------------------------------------------
+
+fn equipment(codex: &Codex, ship: &BattleRuntimeShip) -> Vec<AaEquip> {
+    ship.slot_items
+        .iter()
+        .filter_map(|si| codex.find::<ApiMstSlotitem>(&si.api_slotitem_id).ok())
+        .map(AaEquip::from_mst)
+        .collect()
+}
+
+fn identity(codex: &Codex, ship: &BattleRuntimeShip) -> ShipIdentity {
+    ShipIdentity {
+        ship_id: ship.ship.api_ship_id,
+        ctype: ship_mst(codex, ship).map_or(0, |mst| mst.api_ctype),
+        stype: ship_type(codex, ship),
+    }
+}
+
+/// Adjusted AA (加重対空値) of one ship.
+///
+/// `api_taiku[0]` includes equipment AA, so the ship's own AA is recovered by
+/// subtracting it. Player ships round the total down to an even number; enemy
+/// ships use `2 × floor(√base)` for their own AA.
+fn adjusted_aa_from(taiku: i64, equips: &[AaEquip], is_friendly: bool) -> i64 {
+    let equip_total: i64 = equips.iter().map(|e| e.tyku).sum();
+    let base = (taiku - equip_total).max(0);
+    let weighted: f64 = equips.iter().map(|e| e.adjusted_multiplier() * e.tyku as f64).sum();
+    if is_friendly {
+        let total = (base as f64 + weighted).floor() as i64;
+        total - total % 2
+    } else {
+        2 * (base as f64).sqrt().floor() as i64 + weighted.floor() as i64
+    }
+}
+
+pub(crate) fn adjusted_aa(codex: &Codex, ship: &BattleRuntimeShip) -> i64 {
+    adjusted_aa_from(ship.ship.api_taiku[0], &equipment(codex, ship), ship.is_friendly)
+}
+
+/// Formation multiplier on the fleet AA bonus.
+fn formation_aa_modifier(formation_id: i64) -> f64 {
+    match formation_id {
+        2 => 1.2,  // 複縦陣
+        3 => 1.6,  // 輪形陣
+        11 => 1.1, // 第一警戒航行序列
+        13 => 1.5, // 第三警戒航行序列
+        _ => 1.0,
+    }
+}
+
+/// Fleet AA bonus (艦隊防空値) from each ship's equipment.
+fn fleet_aa_from(ships: &[Vec<AaEquip>], formation_id: i64, is_friendly: bool) -> i64 {
+    let sum: i64 = ships
+        .iter()
+        .map(|equips| {
+            equips.iter().map(|e| e.fleet_multiplier() * e.tyku as f64).sum::<f64>().floor() as i64
+        })
+        .sum();
+    let fleet = (sum as f64 * formation_aa_modifier(formation_id)).floor();
+    if is_friendly {
+        (fleet * 2.0 / 1.3).floor() as i64
+    } else {
+        fleet as i64
+    }
+}
+
+pub(crate) fn fleet_aa(codex: &Codex, ships: &[BattleRuntimeShip], formation_id: i64) -> i64 {
+    let is_friendly = ships.first().is_some_and(|s| s.is_friendly);
+    let loadouts: Vec<Vec<AaEquip>> =
+        ships.iter().filter(|s| s.is_alive()).map(|s| equipment(codex, s)).collect();
+    fleet_aa_from(&loadouts, formation_id, is_friendly)
+}
+
+// ---------------------------------------------------------------------------
+// AACI detection & trigger
+// ---------------------------------------------------------------------------
+
+/// AACI kinds a ship can fire with its loadout, in priority order.
+fn available_cutins(
+    ship: ShipIdentity,
+    equips: &[AaEquip],
+) -> impl Iterator<Item = (&'static AaciKind, Vec<i64>)> {
+    AACI_KINDS.iter().filter(move |k| k.gate.allows(ship)).filter_map(move |k| {
+        let use_items = k.match_equipment(equips)?;
+        Some((k, use_items))
+    })
+}
+
+/// Roll the fleet's anti-air cut-in.
+///
+/// Ships are checked in fleet order and each available kind is rolled in
+/// priority order; the first success wins, so at most one AACI fires.
+pub(crate) fn roll_cutin(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    ships: &[BattleRuntimeShip],
+) -> Option<AntiAirCutin> {
+    for (ship_idx, ship) in ships.iter().enumerate() {
+        if !ship.is_alive() {
+            continue;
+        }
+        let equips = equipment(codex, ship);
+        for (kind, use_items) in available_cutins(identity(codex, ship), &equips) {
+            if rng.roll_range(0, 100) < kind.rate {
+                return Some(AntiAirCutin {
+                    ship_idx,
+                    kind,
+                    use_items,
+                });
+            }
+        }
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// Shootdown
+// ---------------------------------------------------------------------------
+
+/// Planes shot down from one slot of `onslot` planes by one defender.
+fn slot_shootdown(
+    rng: &mut impl BattleRng,
+    onslot: i64,
+    adjusted: i64,
+    fleet: i64,
+    defender_is_friendly: bool,
+    cutin: Option<&AaciKind>,
+) -> i64 {
+    let mut shot = 0;
+    if rng.roll_range(0, 2) == 0 {
+        shot += (onslot as f64 * adjusted as f64 * 0.02 * 0.25).floor() as i64;
+    }
+    if rng.roll_range(0, 2) == 0 {
+        let side = if defender_is_friendly {
+            FRIENDLY_FIXED_MODIFIER
+        } else {
+            ENEMY_FIXED_MODIFIER
+        };
+        let modifier = cutin.map_or(1.0, |k| k.modifier);
+        shot += ((adjusted + fleet) as f64 * side * modifier / 10.0).floor() as i64;
+    }
+    if defender_is_friendly {
+        shot += 1;
+    }
+    shot += cutin.map_or(0, |k| k.fixed_bonus);
+    shot.min(onslot)
+}
+
+/// Anti-air fire of one defending fleet, prepared once per stage 2.
+pub(crate) struct AntiAirFire {
+    shooters: Vec<(bool, i64)>,
+    fleet: i64,
+    cutin: Option<&'static AaciKind>,
+}
+
+impl AntiAirFire {
+    /// Collect the alive `defenders`' AA; `None` when nobody can fire.
+    pub(crate) fn new(
+        codex: &Codex,
+        defenders: &[BattleRuntimeShip],
+        defender_formation_id: i64,
+        cutin: Option<&AntiAirCutin>,
+    ) -> Option<Self> {
+        let shooters: Vec<(bool, i64)> = defenders
+            .iter()
+            .filter(|s| s.is_alive())
+            .map(|s| (s.is_friendly, adjusted_aa(codex, s)))
+            .collect();
+        if shooters.is_empty() {
+            return None;
+        }
+        Some(Self {
+            shooters,
+            fleet: fleet_aa(codex, defenders, defender_formation_id),
+            cutin: cutin.map(|c| c.kind),
+        })
+    }
+
+    /// Planes shot down from one slot of `onslot` planes by a random defender.
+    pub(crate) fn fire(&self, rng: &mut impl BattleRng, onslot: i64) -> i64 {
+        let shooter = rng.choose_index(self.shooters.len()).expect("shooters non-empty");
+        let (is_friendly, adjusted) = self.shooters[shooter];
+        slot_shootdown(rng, onslot, adjusted, self.fleet, is_friendly, self.cutin)
+    }
+}
+
+/// Fire `defenders`' AA at every attacking bomber slot of `attackers`.
+///
+/// Removes the shot-down planes from `api_onslot` and returns the total lost.
+pub(crate) fn shoot_down(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    attackers: &mut [BattleRuntimeShip],
+    defenders: &[BattleRuntimeShip],
+    defender_formation_id: i64,
+    cutin: Option<&AntiAirCutin>,
+) -> i64 {
+    let Some(fire) = AntiAirFire::new(codex, defenders, defender_formation_id, cutin) else {
+        return 0;
+    };
+
+    let mut lost = 0;
+    for ship in attackers.iter_mut() {
+        for (slot_idx, slot_item) in ship.slot_items.iter().enumerate().take(5) {
+            let onslot = ship.ship.api_onslot[slot_idx];
+            if onslot <= 0 {
+                continue;
+            }
+            let is_bomber = codex
+                .find::<ApiMstSlotitem>(&slot_item.api_slotitem_id)
+                .ok()
+                .is_some_and(|mst| is_airstrike_attack_type(mst.api_type[2]));
+            if !is_bomber {
+                continue;
+            }
+            let shot = fire.fire(rng, onslot);
+            ship.ship.api_onslot[slot_idx] -= shot;
+            lost += shot;
+        }
+    }
+    lost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SeededRng;
+
+    fn equip(id: i64, type3: KcSlotItemType3, icon: i64, tyku: i64) -> AaEquip {
+        AaEquip {
+            id,
+            type3: type3 as i64,
+            icon,
+            tyku,
+        }
+    }
+
+    fn ha_gun(id: i64) -> AaEquip {
+        equip(id, KcSlotItemType3::SmallCaliberMainGun, HIGH_ANGLE_GUN_ICON, 7)
+    }
+
+    fn air_radar(id: i64) -> AaEquip {
+        equip(id, KcSlotItemType3::SmallRadar, 11, 4)
+    }
+
+    fn aa_gun(id: i64, tyku: i64) -> AaEquip {
+        equip(id, KcSlotItemType3::AntiAircraftGun, 15, tyku)
+    }
+
+    fn kinds(ship: ShipIdentity, equips: &[AaEquip]) -> Vec<i64> {
+        available_cutins(ship, equips).map(|(k, _)| k.kind).collect()
+    }
+
+    #[test]
+    fn adjusted_aa_weights_equipment_by_category() {
+        let equips = [aa_gun(1, 5), ha_gun(2), air_radar(3)];
+        // base = 60 - (5 + 7 + 4) = 44; weighted = 30 + 28 + 12 = 70.
+        assert_eq!(adjusted_aa_from(60, &equips, true), 114);
+        // enemy: 2 × floor(√44) + 70.
+        assert_eq!(adjusted_aa_from(60, &equips, false), 2 * 6 + 70);
+        // Player totals round down to even.
+        assert_eq!(adjusted_aa_from(21, &[], true), 20);
+    }
+
+    #[test]
+    fn fleet_aa_applies_formation_and_player_scaling() {
+        let ships = vec![vec![ha_gun(1), air_radar(2)], vec![aa_gun(3, 10)]];
+        // floor(7×0.35 + 4×0.4) = 4, floor(10×0.2) = 2.
+        assert_eq!(fleet_aa_from(&ships, 1, false), 6);
+        assert_eq!(fleet_aa_from(&ships, 3, false), 9);
+        assert_eq!(fleet_aa_from(&ships, 1, true), 9);
+    }
+
+    #[test]
+    fn aaci_detection_respects_ship_gates_and_distinct_items() {
+        let akizuki = ShipIdentity {
+            ship_id: 421,
+            ctype: AKIZUKI_CLASS,
+            stype: Some(KcShipType::DD),
+        };
+        let generic = ShipIdentity {
+            ship_id: 1,
+            ctype: 1,
+            stype: Some(KcShipType::DD),
+        };
+
+        let loadout = [ha_gun(10), ha_gun(11), air_radar(12)];
+        assert_eq!(kinds(akizuki, &loadout), vec![1, 2, 3]);
+        assert!(kinds(generic, &loadout).is_empty(), "plain HA guns need a director");
+
+        // One HA gun cannot fill both kind-3 slots.
+        assert_eq!(kinds(akizuki, &[ha_gun(10)]), Vec::<i64>::new());
+
+        let director = equip(13, KcSlotItemType3::AntiAircraftGunMount, 30, 6);
+        let (kind, items) =
+            available_cutins(generic, &[ha_gun(10), director, air_radar(12)]).next().unwrap();
+        assert_eq!(kind.kind, 7);
+        assert_eq!(items, vec![10, 13, 12]);
+    }
+
+    #[test]
+    fn slot_shootdown_is_capped_and_guarantees_player_minimum() {
+        let kind5 = AACI_KINDS.iter().find(|k| k.kind == 5).unwrap();
+        for seed in 0..32 {
+            let mut rng = SeededRng::new(seed);
+            let shot = slot_shootdown(&mut rng, 3, 400, 200, true, Some(kind5));
+            assert_eq!(shot, 3, "a strong cut-in wipes a small slot (seed {seed})");
+
+            let mut rng = SeededRng::new(seed);
+            let shot = slot_shootdown(&mut rng, 18, 0, 0, true, None);
+            assert_eq!(shot, 1, "player AA always downs at least one plane");
+
+            let mut rng = SeededRng::new(seed);
+            let shot = slot_shootdown(&mut rng, 18, 0, 0, false, None);
+            assert_eq!(shot, 0, "enemy AA has no guaranteed minimum");
+        }
+    }
+}
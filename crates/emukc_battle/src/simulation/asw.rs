@@ -2,6 +2,7 @@
 
 use emukc_model::codex::Codex;
 
+use crate::accuracy::{AttackKind, HitCheck, roll_hit};
 use crate::damage::calculate_asw_damage;
 use crate::random::BattleRng;
 use crate::targeting::{can_opening_asw, day_attack_display_ids, select_submarine_target};
@@ -33,6 +34,13 @@ pub(crate) fn simulate_opening_taisen(
         let Some(target_idx) = select_submarine_target(codex, rng, enemy) else {
             continue;
         };
+        let hit = roll_hit(
+            codex,
+            rng,
+            ship,
+            &enemy[target_idx],
+            &HitCheck::new(AttackKind::Asw, friendly_formation_id, enemy_formation_id),
+        );
         let raw = calculate_asw_damage(
             codex,
             rng,
@@ -40,6 +48,7 @@ pub(crate) fn simulate_opening_taisen(
             &enemy[target_idx],
             friendly_formation_id,
             engagement,
+            hit,
         );
         let (raw_dmg, dealt) = enemy[target_idx].apply_damage(rng, raw, target_idx);
         ship.damage_dealt += dealt;
@@ -50,7 +59,7 @@ pub(crate) fn simulate_opening_taisen(
         at_type.push(7); // ASW attack type
         df_list.push(vec![target_idx as i64]);
         si_list.push(SiListId::num_from_i64(&day_attack_display_ids(codex, ship, true)));
-        cl_list.push(vec![1]);
+        cl_list.push(vec![hit.api_cl()]);
         damage.push(vec![display.into()]);
     }
 
@@ -62,6 +71,13 @@ pub(crate) fn simulate_opening_taisen(
         let Some(target_idx) = select_submarine_target(codex, rng, friendly) else {
             continue;
         };
+        let hit = roll_hit(
+            codex,
+            rng,
+            ship,
+            &friendly[target_idx],
+            &HitCheck::new(AttackKind::Asw, enemy_formation_id, friendly_formation_id),
+        );
         let raw = calculate_asw_damage(
             codex,
             rng,
@@ -69,6 +85,7 @@ pub(crate) fn simulate_opening_taisen(
             &friendly[target_idx],
             enemy_formation_id,
             engagement,
+            hit,
         );
         let (_, dealt) = friendly[target_idx].apply_damage(rng, raw, target_idx);
         ship.damage_dealt += dealt;
@@ -78,7 +95,7 @@ pub(crate) fn simulate_opening_taisen(
         at_type.push(7);
         df_list.push(vec![target_idx as i64]);
         si_list.push(SiListId::num_from_i64(&day_attack_display_ids(codex, ship, true)));
-        cl_list.push(vec![1]);
+        cl_list.push(vec![hit.api_cl()]);
         damage.push(vec![dealt.into()]);
     }
 
@@ -128,6 +145,10 @@ mod tests {
             engagement: EngagementType::SameCourse,
             friend_ships: vec![friend],
             enemy_ships: vec![enemy],
+            air_base_waves: Vec::new(),
+            combined: None,
+            support: None,
+            historical_bonuses: Vec::new(),
         };
 
         let result =
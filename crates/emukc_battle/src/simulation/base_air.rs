@@ -0,0 +1,490 @@
+//! Land-based air corps (基地航空隊) phase simulation.
+//!
+//! Two engagements share the kouku stage 1 loss ratios:
+//! a sortied airbase wave strikes the enemy fleet ahead of kouku (stage 1
+//! fighter combat, stage 2 enemy anti-air fire, stage 3 bombing), and an enemy
+//! air raid strikes the player's airbases (stage 1 interception, stage 3
+//! bombing of the bases).
+
+use std::collections::BTreeMap;
+
+use emukc_model::{
+    codex::Codex,
+    kc2::{KcSlotItemType3, start2::ApiMstSlotitem},
+};
+
+use crate::accuracy::{HitResult, roll_squadron_hit};
+use crate::damage::{apply_cap, calculate_defense_power, resolve_damage};
+use crate::random::BattleRng;
+use crate::simulation::{anti_air::AntiAirFire, kouku};
+use crate::targeting::{display_damage, is_airstrike_attack_type, target_class};
+use crate::types::{
+    AirState, BattleAirBaseAttack, BattleAirBaseSquadron, BattleAirBaseStage3, BattleAirBaseWave,
+    BattleAirRaid, BattleAirRaidAttack, BattleAirRaidStage3, BattleKoukuStage1, BattleKoukuStage2,
+    BattleRuntimeShip, BattleSquadronPlane,
+};
+
+/// Pre-cap power cap of a land-based air attack.
+const SQUADRON_POWER_CAP: f64 = 220.0;
+
+/// Power modifier of land-based attackers against surface ships.
+const LAND_ATTACKER_MODIFIER: f64 = 0.8;
+
+/// Pre-cap power cap of a bomber slot hitting an airbase.
+const RAID_POWER_CAP: f64 = 150.0;
+
+/// HP of every airbase during an air raid.
+pub(crate) const AIRBASE_MAX_HP: i64 = 200;
+
+// ---------------------------------------------------------------------------
+// Squadron helpers
+// ---------------------------------------------------------------------------
+
+fn is_recon_type(slotitem_type: i64) -> bool {
+    matches!(
+        KcSlotItemType3::n(slotitem_type),
+        Some(
+            KcSlotItemType3::CarrierBasedRecon
+                | KcSlotItemType3::CarrierBasedRecon2
+                | KcSlotItemType3::SeaBasedRecon
+                | KcSlotItemType3::LargeFlyingBoat
+                | KcSlotItemType3::LandBasedRecon
+                | KcSlotItemType3::JetRecon
+        )
+    )
+}
+
+/// Squadron types that bomb in stage 3.
+fn is_squadron_bomber_type(slotitem_type: i64) -> bool {
+    is_airstrike_attack_type(slotitem_type)
+        || matches!(
+            KcSlotItemType3::n(slotitem_type),
+            Some(KcSlotItemType3::LandBasedAttacker | KcSlotItemType3::LargeLandBasedAircraft)
+        )
+}
+
+fn is_land_attacker_type(slotitem_type: i64) -> bool {
+    matches!(
+        KcSlotItemType3::n(slotitem_type),
+        Some(KcSlotItemType3::LandBasedAttacker | KcSlotItemType3::LargeLandBasedAircraft)
+    )
+}
+
+/// Squadron types that drop torpedoes (and use `api_raig`) in stage 3.
+fn is_squadron_torpedo_type(slotitem_type: i64) -> bool {
+    is_land_attacker_type(slotitem_type)
+        || KcSlotItemType3::n(slotitem_type) == Some(KcSlotItemType3::CarrierBasedTorpedoBomber)
+}
+
+fn squadron_mst<'a>(
+    codex: &'a Codex,
+    squadron: &'a BattleAirBaseSquadron,
+) -> Option<&'a ApiMstSlotitem> {
+    codex.find::<ApiMstSlotitem>(&squadron.slot_item.api_slotitem_id).ok()
+}
+
+/// Fighter power of squadrons; recons only extend range and add none.
+fn squadron_fighter_power<'a>(
+    codex: &Codex,
+    squadrons: impl IntoIterator<Item = &'a BattleAirBaseSquadron>,
+) -> i64 {
+    squadrons
+        .into_iter()
+        .filter(|sq| sq.count > 0)
+        .filter_map(|sq| {
+            let mst = squadron_mst(codex, sq)?;
+            if is_recon_type(mst.api_type[2]) {
+                return None;
+            }
+            Some((mst.api_tyku.max(0) as f64 * (sq.count as f64).sqrt()).floor() as i64)
+        })
+        .sum()
+}
+
+fn squadron_plane(squadrons: &[BattleAirBaseSquadron]) -> Vec<BattleSquadronPlane> {
+    squadrons
+        .iter()
+        .map(|sq| BattleSquadronPlane {
+            api_mst_id: sq.slot_item.api_slotitem_id,
+            api_count: sq.count,
+        })
+        .collect()
+}
+
+/// Stage 1 losses of one side's squadrons, rolled per squadron.
+fn apply_squadron_stage1_losses(
+    rng: &mut impl BattleRng,
+    squadrons: &mut [BattleAirBaseSquadron],
+    (min, max): (f64, f64),
+) -> i64 {
+    let mut lost = 0;
+    for sq in squadrons.iter_mut().filter(|sq| sq.count > 0) {
+        let shot = (sq.count as f64 * rng.random_f64_range(min, max)).floor() as i64;
+        sq.count -= shot;
+        lost += shot;
+    }
+    lost
+}
+
+/// Damage of one squadron against a ship.
+///
+/// `stat × √(1.8 × count) + 25`, ×0.8 for land-based attackers, capped at 220.
+fn calculate_squadron_damage(
+    rng: &mut impl BattleRng,
+    mst: &ApiMstSlotitem,
+    count: i64,
+    defender: &BattleRuntimeShip,
+    hit: HitResult,
+) -> i64 {
+    if count <= 0 || hit.is_miss() {
+        return 0;
+    }
+    let stat = if is_squadron_torpedo_type(mst.api_type[2]) {
+        mst.api_raig.max(0)
+    } else {
+        mst.api_baku.max(0)
+    };
+    if stat <= 0 {
+        return 0;
+    }
+    let mut raw_power = stat as f64 * (1.8 * count as f64).sqrt() + 25.0;
+    if is_land_attacker_type(mst.api_type[2]) {
+        raw_power *= LAND_ATTACKER_MODIFIER;
+    }
+    let capped = apply_cap(raw_power, SQUADRON_POWER_CAP) as f64 * hit.damage_multiplier();
+    let defense = calculate_defense_power(rng, defender.ship.api_soukou[0]);
+    resolve_damage(rng, capped, defense, defender.hp())
+}
+
+// ---------------------------------------------------------------------------
+// Sortied airbase wave
+// ---------------------------------------------------------------------------
+
+/// Run one land-based air wave against the enemy fleet.
+///
+/// Shot-down planes are removed from `wave`, so it ends up holding the
+/// squadrons' surviving counts.
+pub(crate) fn simulate_air_base_wave(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    wave: &mut BattleAirBaseWave,
+    enemy: &mut [BattleRuntimeShip],
+    enemy_formation_id: i64,
+) -> BattleAirBaseAttack {
+    let api_squadron_plane = squadron_plane(&wave.squadrons);
+    let f_count: i64 = wave.squadrons.iter().map(|sq| sq.count.max(0)).sum();
+    let e_count = kouku::total_plane_count(codex, enemy);
+
+    // Stage 1: fighter combat against the enemy's carrier planes.
+    let air_state = AirState::from_power(
+        squadron_fighter_power(codex, &wave.squadrons),
+        kouku::calculate_fighter_power(codex, enemy),
+    );
+    let stage1_f_lost = apply_squadron_stage1_losses(
+        rng,
+        &mut wave.squadrons,
+        air_state.stage1_friendly_loss_ratio(),
+    );
+    let (e_loss_min, e_loss_max) = air_state.stage1_enemy_loss_ratio();
+    let stage1_e_lost =
+        (e_count as f64 * rng.random_f64_range(e_loss_min, e_loss_max)).floor() as i64;
+    kouku::apply_plane_losses(codex, enemy, stage1_e_lost);
+
+    // Stage 2: enemy anti-air fire at every bombing squadron.
+    let f_count_after_s1: i64 = wave.squadrons.iter().map(|sq| sq.count.max(0)).sum();
+    let mut stage2_f_lost = 0;
+    if let Some(fire) = AntiAirFire::new(codex, enemy, enemy_formation_id, None) {
+        for sq in wave.squadrons.iter_mut().filter(|sq| sq.count > 0) {
+            let is_bomber = codex
+                .find::<ApiMstSlotitem>(&sq.slot_item.api_slotitem_id)
+                .ok()
+                .is_some_and(|mst| is_squadron_bomber_type(mst.api_type[2]));
+            if !is_bomber {
+                continue;
+            }
+            let shot = fire.fire(rng, sq.count);
+            sq.count -= shot;
+            stage2_f_lost += shot;
+        }
+    }
+
+    // Stage 3: each bombing squadron picks one surface target.
+    let mut api_edam = vec![0i64; enemy.len()];
+    let mut api_erai_flag = vec![0i64; enemy.len()];
+    let mut api_ebak_flag = vec![0i64; enemy.len()];
+    let mut api_ecl_flag = vec![0i64; enemy.len()];
+    for sq in wave.squadrons.iter().filter(|sq| sq.count > 0) {
+        let Some(mst) = squadron_mst(codex, sq) else {
+            continue;
+        };
+        if !is_squadron_bomber_type(mst.api_type[2]) {
+            continue;
+        }
+        let targets: Vec<usize> = enemy
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_alive() && target_class(codex, s).is_surface_like())
+            .map(|(i, _)| i)
+            .collect();
+        let Some(pick) = rng.choose_index(targets.len()) else {
+            continue;
+        };
+        let target_idx = targets[pick];
+        let hit = roll_squadron_hit(rng, mst.api_houm, &enemy[target_idx], enemy_formation_id);
+        let damage = calculate_squadron_damage(rng, mst, sq.count, &enemy[target_idx], hit);
+        let (raw_dmg, dealt) = enemy[target_idx].apply_damage(rng, damage, target_idx);
+        api_edam[target_idx] += display_damage(&enemy[target_idx], raw_dmg, dealt);
+        if is_squadron_torpedo_type(mst.api_type[2]) {
+            api_erai_flag[target_idx] = 1;
+        } else {
+            api_ebak_flag[target_idx] = 1;
+        }
+        if hit.is_critical() {
+            api_ecl_flag[target_idx] = 1;
+        }
+    }
+
+    BattleAirBaseAttack {
+        api_base_id: wave.base_id,
+        api_stage_flag: [1, 1, 1],
+        api_plane_from: [None, Some(kouku::attack_plane_from(codex, enemy))],
+        api_squadron_plane,
+        api_stage1: BattleKoukuStage1 {
+            api_f_count: f_count,
+            api_f_lostcount: stage1_f_lost,
+            api_e_count: e_count,
+            api_e_lostcount: stage1_e_lost,
+            api_disp_seiku: air_state.api_disp_seiku(),
+            api_touch_plane: [-1, -1],
+        },
+        api_stage2: BattleKoukuStage2 {
+            api_f_count: f_count_after_s1,
+            api_f_lostcount: stage2_f_lost,
+            api_e_count: 0,
+            api_e_lostcount: 0,
+            api_air_fire: None,
+        },
+        api_stage3: BattleAirBaseStage3 {
+            api_erai_flag,
+            api_ebak_flag,
+            api_ecl_flag,
+            api_edam,
+            api_e_sp_list: vec![None; enemy.len()],
+        },
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Air raid on airbases
+// ---------------------------------------------------------------------------
+
+/// `api_lost_kind` of an air raid.
+fn air_raid_lost_kind(base_damage: i64, planes_lost: i64) -> i64 {
+    match (base_damage > 0, planes_lost > 0) {
+        (true, false) => 1,
+        (true, true) => 2,
+        (false, true) => 3,
+        (false, false) => 4,
+    }
+}
+
+/// Run an enemy air raid against the airbases of one area.
+///
+/// Every squadron in `bases` intercepts. Intercepting planes shot down are
+/// removed from `bases`; the returned vector is the damage each base took.
+pub(crate) fn simulate_air_raid(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    bases: &mut [BattleAirBaseWave],
+    enemy: &mut [BattleRuntimeShip],
+) -> (BattleAirRaid, Vec<i64>) {
+    let api_map_squadron_plane: BTreeMap<String, Vec<BattleSquadronPlane>> = bases
+        .iter()
+        .filter(|base| base.squadrons.iter().any(|sq| sq.count > 0))
+        .map(|base| (base.base_id.to_string(), squadron_plane(&base.squadrons)))
+        .collect();
+    let f_count: i64 =
+        bases.iter().flat_map(|base| &base.squadrons).map(|sq| sq.count.max(0)).sum();
+    let e_count = kouku::total_plane_count(codex, enemy);
+
+    // Stage 1: interception.
+    let air_state = AirState::from_power(
+        squadron_fighter_power(codex, bases.iter().flat_map(|base| &base.squadrons)),
+        kouku::calculate_fighter_power(codex, enemy),
+    );
+    let f_ratio = air_state.stage1_friendly_loss_ratio();
+    let stage1_f_lost: i64 = bases
+        .iter_mut()
+        .map(|base| apply_squadron_stage1_losses(rng, &mut base.squadrons, f_ratio))
+        .sum();
+    let (e_loss_min, e_loss_max) = air_state.stage1_enemy_loss_ratio();
+    let stage1_e_lost =
+        (e_count as f64 * rng.random_f64_range(e_loss_min, e_loss_max)).floor() as i64;
+    kouku::apply_plane_losses(codex, enemy, stage1_e_lost);
+
+    // Stage 3: each surviving enemy bomber slot hits one airbase.
+    let mut api_fdam = vec![0i64; bases.len()];
+    let mut api_frai_flag = vec![0i64; bases.len()];
+    let mut api_fbak_flag = vec![0i64; bases.len()];
+    if !bases.is_empty() {
+        for ship in enemy.iter().filter(|s| s.is_alive()) {
+            for (slot_item, onslot) in ship.slot_items.iter().zip(ship.ship.api_onslot) {
+                if onslot <= 0 {
+                    continue;
+                }
+                let Ok(mst) = codex.find::<ApiMstSlotitem>(&slot_item.api_slotitem_id) else {
+                    continue;
+                };
+                if !is_airstrike_attack_type(mst.api_type[2]) {
+                    continue;
+                }
+                let is_torpedo = is_squadron_torpedo_type(mst.api_type[2]);
+                let stat = if is_torpedo {
+                    mst.api_raig.max(0)
+                } else {
+                    mst.api_baku.max(0)
+                };
+                let raw_power = stat as f64 * (onslot as f64).sqrt() + 25.0;
+                let capped = apply_cap(raw_power, RAID_POWER_CAP) as f64;
+                let damage = (capped * rng.random_f64_range(0.5, 1.0)).floor() as i64;
+
+                let target = rng.choose_index(bases.len()).expect("bases non-empty");
+                api_fdam[target] = (api_fdam[target] + damage).min(AIRBASE_MAX_HP);
+                if is_torpedo {
+                    api_frai_flag[target] = 1;
+                } else {
+                    api_fbak_flag[target] = 1;
+                }
+            }
+        }
+    }
+
+    let total_damage: i64 = api_fdam.iter().sum();
+    let packet = BattleAirRaid {
+        api_f_nowhps: api_fdam.iter().map(|dmg| AIRBASE_MAX_HP - dmg).collect(),
+        api_f_maxhps: vec![AIRBASE_MAX_HP; bases.len()],
+        api_air_base_attack: BattleAirRaidAttack {
+            api_stage_flag: [1, 0, 1],
+            api_plane_from: [None, Some(kouku::attack_plane_from(codex, enemy))],
+            api_map_squadron_plane,
+            api_stage1: BattleKoukuStage1 {
+                api_f_count: f_count,
+                api_f_lostcount: stage1_f_lost,
+                api_e_count: e_count,
+                api_e_lostcount: stage1_e_lost,
+                api_disp_seiku: air_state.api_disp_seiku(),
+                api_touch_plane: [-1, -1],
+            },
+            api_stage3: BattleAirRaidStage3 {
+                api_frai_flag,
+                api_fbak_flag,
+                api_fcl_flag: vec![0; bases.len()],
+                api_fdam: api_fdam.clone(),
+            },
+        },
+        api_lost_kind: air_raid_lost_kind(total_damage, stage1_f_lost),
+    };
+    (packet, api_fdam)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SeededRng;
+    use crate::test_utils::make_test_ship_ctx;
+    use emukc_model::kc2::KcApiSlotItem;
+
+    const ATTACKER_ID: i64 = 168;
+    const FIGHTER_ID: i64 = 175;
+
+    fn codex() -> Codex {
+        let mut codex = Codex::default();
+        codex.manifest.api_mst_slotitem.push(ApiMstSlotitem {
+            api_id: ATTACKER_ID,
+            api_type: [22, 37, KcSlotItemType3::LandBasedAttacker as i64, 37, 0],
+            api_raig: 10,
+            api_baku: 12,
+            api_tyku: 1,
+            ..Default::default()
+        });
+        codex.manifest.api_mst_slotitem.push(ApiMstSlotitem {
+            api_id: FIGHTER_ID,
+            api_type: [22, 39, KcSlotItemType3::LocalFighter as i64, 44, 0],
+            api_tyku: 10,
+            ..Default::default()
+        });
+        codex
+    }
+
+    fn squadron(mst_id: i64, count: i64) -> BattleAirBaseSquadron {
+        BattleAirBaseSquadron {
+            slot_item: KcApiSlotItem {
+                api_id: mst_id,
+                api_slotitem_id: mst_id,
+                api_locked: 0,
+                api_level: 0,
+                api_alv: None,
+            },
+            count,
+        }
+    }
+
+    #[test]
+    fn fighter_power_skips_recons_and_empty_squadrons() {
+        let codex = codex();
+        let squadrons = [squadron(FIGHTER_ID, 18), squadron(ATTACKER_ID, 0)];
+        assert_eq!(squadron_fighter_power(&codex, &squadrons), (10.0 * 18f64.sqrt()) as i64);
+    }
+
+    #[test]
+    fn air_base_wave_without_enemy_planes_keeps_supremacy_and_hits_only_the_fleet() {
+        let codex = codex();
+        let mut wave = BattleAirBaseWave {
+            base_id: 1,
+            squadrons: vec![squadron(ATTACKER_ID, 18), squadron(FIGHTER_ID, 18)],
+        };
+        let mut enemy = vec![make_test_ship_ctx(40, 40, 40, 40, false, true)];
+
+        let attack =
+            simulate_air_base_wave(&codex, &mut SeededRng::new(7), &mut wave, &mut enemy, 1);
+
+        assert_eq!(attack.api_base_id, 1);
+        assert_eq!(attack.api_stage1.api_disp_seiku, 1);
+        assert_eq!(attack.api_stage1.api_f_count, 36);
+        assert_eq!(attack.api_squadron_plane[0].api_count, 18);
+        let survivors: i64 = wave.squadrons.iter().map(|sq| sq.count).sum();
+        assert_eq!(
+            survivors,
+            36 - attack.api_stage1.api_f_lostcount - attack.api_stage2.api_f_lostcount
+        );
+        assert_eq!(attack.api_stage3.api_edam[0], 40 - enemy[0].hp());
+        // Only the attacker squadron bombs, and it flies torpedoes.
+        assert_eq!(attack.api_stage3.api_ebak_flag, vec![0]);
+    }
+
+    #[test]
+    fn lost_kind_reflects_base_and_plane_damage() {
+        assert_eq!(air_raid_lost_kind(10, 0), 1);
+        assert_eq!(air_raid_lost_kind(10, 2), 2);
+        assert_eq!(air_raid_lost_kind(0, 2), 3);
+        assert_eq!(air_raid_lost_kind(0, 0), 4);
+    }
+
+    #[test]
+    fn air_raid_without_bombers_leaves_bases_intact() {
+        let codex = codex();
+        let mut bases = vec![BattleAirBaseWave {
+            base_id: 1,
+            squadrons: vec![squadron(FIGHTER_ID, 18)],
+        }];
+        let mut enemy = vec![make_test_ship_ctx(40, 40, 40, 40, false, true)];
+
+        let (packet, damage) =
+            simulate_air_raid(&codex, &mut SeededRng::new(3), &mut bases, &mut enemy);
+
+        assert_eq!(damage, vec![0]);
+        assert_eq!(packet.api_f_nowhps, vec![AIRBASE_MAX_HP]);
+        assert_eq!(packet.api_lost_kind, 4);
+        assert!(packet.api_air_base_attack.api_map_squadron_plane.contains_key("1"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Friend fleet (友軍艦隊) night phase simulation.
+//!
+//! A friend fleet arrives ahead of the player's night battle: its carriers
+//! launch an air attack, then it trades one round of night fire with the
+//! enemy fleet that fights at night. Friend fleet ships never count toward
+//! the battle result.
+
+use emukc_model::codex::Codex;
+
+use crate::random::BattleRng;
+use crate::simulation::{kouku, night};
+use crate::targeting::any_alive;
+use crate::types::{
+    BattleFriendlyBattle, BattleFriendlyInfo, BattleKouku, BattleRuntimeShip, EngagementType,
+    FriendFleetInput, NightBattleParams,
+};
+
+/// Friend fleet formation: always line ahead.
+const FRIEND_FLEET_FORMATION_ID: i64 = 1;
+
+/// Packet parts of the friend fleet phase.
+pub(crate) struct FriendlyPhase {
+    pub info: BattleFriendlyInfo,
+    pub kouku: Option<BattleKouku>,
+    pub battle: Option<BattleFriendlyBattle>,
+}
+
+/// Run the friend fleet's air attack and night fire against `enemy`.
+///
+/// `enemy_offset` is the API slot of `enemy[0]` (6 for an enemy escort).
+pub(crate) fn simulate_friend_fleet(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    input: FriendFleetInput,
+    enemy: &mut [BattleRuntimeShip],
+    enemy_offset: usize,
+    enemy_formation_id: i64,
+    engagement: EngagementType,
+) -> FriendlyPhase {
+    let mut friends: Vec<BattleRuntimeShip> =
+        input.ships.into_iter().map(|ship| BattleRuntimeShip::new(ship, true, false)).collect();
+    let info = friendly_info(&friends, input.production_type, input.voice_ids);
+
+    let friend_count = friends.len();
+    let kouku = kouku::has_any_air_combat_planes(codex, &friends).then(|| {
+        let mut kouku = kouku::simulate_kouku(
+            codex,
+            &mut friends,
+            enemy,
+            rng,
+            FRIEND_FLEET_FORMATION_ID,
+            enemy_formation_id,
+        );
+        // An enemy escort is reported in the combined stage 3 arrays.
+        if enemy_offset > 0 {
+            kouku.api_stage3_combined = Some(kouku.api_stage3.split_off_escorts(friend_count, 0));
+        }
+        kouku
+    });
+
+    let battle = if any_alive(&friends) && any_alive(enemy) {
+        night::simulate_night_hougeki(
+            codex,
+            rng,
+            &mut friends,
+            enemy,
+            &NightBattleParams {
+                friendly_formation_id: FRIEND_FLEET_FORMATION_ID,
+                enemy_formation_id,
+                engagement,
+                air_state: None,
+            },
+        )
+        .map(|mut hougeki| {
+            hougeki.offset_indices(0, enemy_offset);
+            BattleFriendlyBattle {
+                api_flare_pos: [-1, -1],
+                api_hougeki: hougeki,
+            }
+        })
+    } else {
+        None
+    };
+
+    FriendlyPhase {
+        info,
+        kouku,
+        battle,
+    }
+}
+
+/// `api_friendly_info` from the friend fleet as it arrives.
+fn friendly_info(
+    friends: &[BattleRuntimeShip],
+    production_type: i64,
+    voice_ids: Vec<i64>,
+) -> BattleFriendlyInfo {
+    let item_mst = |ship: &BattleRuntimeShip, api_id: i64| {
+        ship.slot_items
+            .iter()
+            .find(|item| api_id > 0 && item.api_id == api_id)
+            .map_or(-1, |item| item.api_slotitem_id)
+    };
+    let mut voice_ids = voice_ids;
+    voice_ids.resize(friends.len(), 0);
+
+    BattleFriendlyInfo {
+        api_production_type: production_type,
+        api_ship_id: friends.iter().map(|ship| ship.ship.api_ship_id).collect(),
+        api_ship_lv: friends.iter().map(|ship| ship.ship.api_lv).collect(),
+        api_nowhps: friends.iter().map(|ship| ship.hp().max(0)).collect(),
+        api_maxhps: friends.iter().map(|ship| ship.ship.api_maxhp).collect(),
+        api_slot: friends
+            .iter()
+            .map(|ship| ship.ship.api_slot.map(|id| item_mst(ship, id)))
+            .collect(),
+        api_slot_ex: friends.iter().map(|ship| item_mst(ship, ship.ship.api_slot_ex)).collect(),
+        api_param: friends
+            .iter()
+            .map(|ship| {
+                [
+                    ship.ship.api_karyoku[0],
+                    ship.ship.api_raisou[0],
+                    ship.ship.api_taiku[0],
+                    ship.ship.api_soukou[0],
+                ]
+            })
+            .collect(),
+        api_voice_p_no: voice_ids.iter().map(|id| i64::from(*id > 0)).collect(),
+        api_voice_id: voice_ids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_api_ship;
+    use crate::types::BattleShipInput;
+    use emukc_model::kc2::KcApiSlotItem;
+
+    #[test]
+    fn friendly_info_reports_equipment_master_ids() {
+        let mut ship = test_api_ship(40, 45);
+        ship.api_ship_id = 151;
+        ship.api_lv = 99;
+        ship.api_slot = [1, 2, -1, -1, -1];
+        ship.api_slot_ex = 3;
+        let item = |api_id: i64, mst_id: i64| KcApiSlotItem {
+            api_id,
+            api_slotitem_id: mst_id,
+            api_locked: 0,
+            api_level: 0,
+            api_alv: None,
+        };
+        let friend = BattleRuntimeShip::new(
+            BattleShipInput {
+                ship,
+                slot_items: vec![item(1, 9), item(2, 59), item(3, 43)],
+                effect_list: vec![],
+                married: false,
+            },
+            true,
+            false,
+        );
+
+        let info = friendly_info(&[friend], 1, vec![]);
+
+        assert_eq!(info.api_ship_id, vec![151]);
+        assert_eq!(info.api_nowhps, vec![40]);
+        assert_eq!(info.api_maxhps, vec![45]);
+        assert_eq!(info.api_slot, vec![[9, 59, -1, -1, -1]]);
+        assert_eq!(info.api_slot_ex, vec![43]);
+        assert_eq!(info.api_voice_id, vec![0]);
+        assert_eq!(info.api_voice_p_no, vec![0]);
+    }
+}
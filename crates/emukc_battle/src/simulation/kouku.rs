@@ -9,8 +9,10 @@ use emukc_model::{
     kc2::{KcApiSlotItem, KcShipType, KcSlotItemType3, start2::ApiMstSlotitem},
 };
 
-use crate::damage::{apply_cap, calculate_defense_power, resolve_damage};
+use crate::accuracy::{AttackKind, HitCheck, HitResult, roll_hit};
+use crate::damage::{apply_cap, calculate_defense_power, historical_modifier, resolve_damage};
 use crate::random::BattleRng;
+use crate::simulation::anti_air;
 use crate::targeting::{is_air_combat_type, is_airstrike_attack_type, ship_type};
 use crate::types::{
     AirState, AirstrikeOutput, BattleKouku, BattleKoukuStage1, BattleKoukuStage2,
@@ -160,7 +162,11 @@ fn best_bomber_index(codex: &Codex, ships: &[BattleRuntimeShip]) -> Option<usize
 // Plane loss application
 // ---------------------------------------------------------------------------
 
-fn apply_plane_losses(codex: &Codex, ships: &mut [BattleRuntimeShip], mut lostcount: i64) {
+pub(crate) fn apply_plane_losses(
+    codex: &Codex,
+    ships: &mut [BattleRuntimeShip],
+    mut lostcount: i64,
+) {
     while lostcount > 0 {
         let mut best_slot: Option<(usize, usize, i64)> = None;
         for (ship_idx, ship) in ships.iter().enumerate() {
@@ -196,15 +202,19 @@ fn apply_plane_losses(codex: &Codex, ships: &mut [BattleRuntimeShip], mut lostco
 
 /// Calculate airstrike damage for a single bomber slot.
 ///
-/// Uses bomb/torpedo stat × √(onslot) + 25, capped at 170.
+/// Uses bomb/torpedo stat × √(onslot) + 25, capped at 170. A critical `hit`
+/// multiplies the capped power, as do the attacker's historical bonuses; a
+/// miss deals 0.
 fn calculate_single_slot_airstrike_damage(
     codex: &Codex,
     rng: &mut impl BattleRng,
+    attacker: &BattleRuntimeShip,
     slot_item: &KcApiSlotItem,
     onslot: i64,
     defender: &BattleRuntimeShip,
+    hit: HitResult,
 ) -> i64 {
-    if onslot <= 0 {
+    if onslot <= 0 || hit.is_miss() {
         return 0;
     }
     let Ok(mst) = codex.find::<ApiMstSlotitem>(&slot_item.api_slotitem_id) else {
@@ -225,7 +235,9 @@ fn calculate_single_slot_airstrike_damage(
         return 0;
     }
     let raw_power = bomb_power + 25.0;
-    let capped = apply_cap(raw_power, 170.0) as f64;
+    let capped = apply_cap(raw_power, 170.0) as f64
+        * hit.damage_multiplier()
+        * historical_modifier(attacker, defender);
     let defense = calculate_defense_power(rng, defender.ship.api_soukou[0]);
     resolve_damage(rng, capped, defense, defender.hp())
 }
@@ -234,6 +246,9 @@ fn calculate_single_slot_airstrike_damage(
 // Airstrike phase execution
 // ---------------------------------------------------------------------------
 
+/// Kouku carries no formations, so airstrike hit checks use neutral modifiers.
+const AIRSTRIKE_HIT_CHECK: HitCheck = HitCheck::new(AttackKind::Airstrike, 0, 0);
+
 fn execute_airstrike_phase(
     codex: &Codex,
     rng: &mut impl BattleRng,
@@ -274,14 +289,18 @@ fn execute_airstrike_phase(
             let target_idx = alive_targets[rng
                 .choose_index(alive_targets.len())
                 .expect("alive_targets non-empty by construction")];
+            let hit = roll_hit(codex, rng, ship, &defenders[target_idx], &AIRSTRIKE_HIT_CHECK);
             let damage = calculate_single_slot_airstrike_damage(
                 codex,
                 rng,
+                ship,
                 slot_item,
                 onslot,
                 &defenders[target_idx],
+                hit,
             );
-            if damage > 0 {
+            // A miss still counts as an attack on the target (flag set, 0 damage).
+            if damage > 0 || hit.is_miss() {
                 let (raw_dmg, dealt) = defenders[target_idx].apply_damage(rng, damage, target_idx);
                 // display_damage returns dealt for friendly defenders (sinking protection),
                 // raw for enemy defenders. Must NOT accumulate raw_dmg directly.
@@ -290,6 +309,9 @@ fn execute_airstrike_phase(
                 output.damage[target_idx] += display;
                 output.bak_targets[ship_idx] = target_idx as i64;
                 output.bak_flags[target_idx] = 1;
+                if hit.is_critical() {
+                    output.cl_flags[target_idx] = 1;
+                }
             }
         }
     }
@@ -322,14 +344,18 @@ fn execute_airstrike_phase(
             let target_idx = alive_targets[rng
                 .choose_index(alive_targets.len())
                 .expect("alive_targets non-empty by construction")];
+            let hit = roll_hit(codex, rng, ship, &defenders[target_idx], &AIRSTRIKE_HIT_CHECK);
             let damage = calculate_single_slot_airstrike_damage(
                 codex,
                 rng,
+                ship,
                 slot_item,
                 onslot,
                 &defenders[target_idx],
+                hit,
             );
-            if damage > 0 {
+            // A miss still counts as an attack on the target (flag set, 0 damage).
+            if damage > 0 || hit.is_miss() {
                 let (raw_dmg, dealt) = defenders[target_idx].apply_damage(rng, damage, target_idx);
                 // display_damage returns dealt for friendly defenders (sinking protection),
                 // raw for enemy defenders. Must NOT accumulate raw_dmg directly.
@@ -338,6 +364,9 @@ fn execute_airstrike_phase(
                 output.damage[target_idx] += display;
                 output.rai_targets[ship_idx] = target_idx as i64;
                 output.rai_flags[target_idx] = 1;
+                if hit.is_critical() {
+                    output.cl_flags[target_idx] = 1;
+                }
             }
         }
     }
@@ -358,6 +387,8 @@ pub(crate) fn simulate_kouku(
     friendly: &mut [BattleRuntimeShip],
     enemy: &mut [BattleRuntimeShip],
     rng: &mut impl BattleRng,
+    friendly_formation_id: i64,
+    enemy_formation_id: i64,
 ) -> BattleKouku {
     let friend_planes = total_plane_count(codex, friendly);
     let enemy_planes = total_plane_count(codex, enemy);
@@ -377,23 +408,13 @@ pub(crate) fn simulate_kouku(
     apply_plane_losses(codex, friendly, stage1_f_lost);
     apply_plane_losses(codex, enemy, stage1_e_lost);
 
-    // Stage 2: anti-air fire — simplified proportional model.
-    // NOTE: Real KanColle uses per-ship AA with slot-level shootdowns and fleet AA modifiers.
-    // This linear approximation (total_aa / 400 × plane_count) is a known simplification.
-    // Should be replaced with per-ship AA calculation before implementing airbattle / ld_airbattle.
+    // Stage 2: anti-air fire — each bomber slot is fired on by one defender.
     let friend_planes_after_s1 = total_plane_count(codex, friendly);
     let enemy_planes_after_s1 = total_plane_count(codex, enemy);
-    let friendly_aa: f64 = friendly.iter().map(|s| s.ship.api_taiku[0].max(0) as f64).sum();
-    let enemy_aa: f64 = enemy.iter().map(|s| s.ship.api_taiku[0].max(0) as f64).sum();
-    let stage2_f_lost = ((enemy_aa / 400.0) * friend_planes_after_s1 as f64)
-        .floor()
-        .min(friend_planes_after_s1 as f64) as i64;
-    let stage2_e_lost = ((friendly_aa / 400.0) * enemy_planes_after_s1 as f64)
-        .floor()
-        .min(enemy_planes_after_s1 as f64) as i64;
-
-    apply_plane_losses(codex, friendly, stage2_f_lost);
-    apply_plane_losses(codex, enemy, stage2_e_lost);
+    let cutin = anti_air::roll_cutin(codex, rng, friendly);
+    let stage2_f_lost = anti_air::shoot_down(codex, rng, friendly, enemy, enemy_formation_id, None);
+    let stage2_e_lost =
+        anti_air::shoot_down(codex, rng, enemy, friendly, friendly_formation_id, cutin.as_ref());
 
     // Stage 3: bombing damage
     let mut api_edam = vec![0i64; enemy.len()];
@@ -407,6 +428,7 @@ pub(crate) fn simulate_kouku(
     let mut api_frai_flag = vec![0i64; friendly.len()];
     let mut api_fbak_flag = vec![0i64; friendly.len()];
     let mut api_fcl_flag = vec![0i64; friendly.len()];
+    let mut api_ecl_flag = vec![0i64; enemy.len()];
 
     // Stage 3: Per-slot bombing — split into dive bombing and torpedo bombing phases
     // Each bomber slot independently selects a random alive target.
@@ -422,6 +444,7 @@ pub(crate) fn simulate_kouku(
             rai_targets: &mut api_frai,
             bak_flags: &mut api_ebak_flag,
             rai_flags: &mut api_erai_flag,
+            cl_flags: &mut api_ecl_flag,
         },
     );
     execute_airstrike_phase(
@@ -436,9 +459,9 @@ pub(crate) fn simulate_kouku(
             rai_targets: &mut api_erai,
             bak_flags: &mut api_fbak_flag,
             rai_flags: &mut api_frai_flag,
+            cl_flags: &mut api_fcl_flag,
         },
     );
-    api_fcl_flag = api_fdam.iter().map(|&d| i64::from(d > 0)).collect();
 
     BattleKouku {
         api_plane_from: [attack_plane_from(codex, friendly), attack_plane_from(codex, enemy)],
@@ -458,6 +481,7 @@ pub(crate) fn simulate_kouku(
             api_f_lostcount: stage2_f_lost,
             api_e_count: enemy_planes_after_s1,
             api_e_lostcount: stage2_e_lost,
+            api_air_fire: cutin.as_ref().map(anti_air::AntiAirCutin::to_air_fire),
         },
         api_stage3: BattleKoukuStage3 {
             api_frai,
@@ -469,12 +493,13 @@ pub(crate) fn simulate_kouku(
             api_fbak_flag,
             api_ebak_flag,
             api_fcl_flag,
-            api_ecl_flag: api_edam.iter().map(|dam| i64::from(*dam > 0)).collect(),
+            api_ecl_flag,
             api_fdam,
             api_edam,
             api_f_sp_list: vec![None; friendly.len()],
             api_e_sp_list: vec![None; enemy.len()],
         },
+        api_stage3_combined: None,
     }
 }
 
@@ -524,7 +549,7 @@ mod tests {
         let mut enemies = vec![BattleRuntimeShip::from(enemy)];
         let mut rng = crate::random::SeededRng::new(42);
 
-        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
 
         assert!(kouku.api_stage1.api_f_count > 0);
         assert!(kouku.api_stage1.api_e_count > 0);
@@ -554,7 +579,7 @@ mod tests {
         let mut enemies = vec![BattleRuntimeShip::from(enemy)];
         let mut rng = crate::random::SeededRng::new(42);
 
-        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
 
         let remaining_enemy_planes = total_plane_count(&codex, &enemies);
         assert!(remaining_enemy_planes > 0, "enemy planes should not be fully wiped");
@@ -589,7 +614,7 @@ mod tests {
         let mut enemies = vec![BattleRuntimeShip::from(enemy)];
         let mut rng = crate::random::SeededRng::new(42);
 
-        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
         assert_eq!(kouku.api_stage1.api_disp_seiku, 1); // supremacy
     }
 
@@ -618,7 +643,7 @@ mod tests {
         }
         let mut rng = crate::random::SeededRng::new(42);
 
-        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
 
         let s3 = &kouku.api_stage3;
         assert_eq!(s3.api_frai_flag.len(), 3, "api_frai_flag should be friendly-sized (3)");
@@ -652,7 +677,7 @@ mod tests {
         let mut enemies = vec![BattleRuntimeShip::new(enemy, false, true)];
         let mut rng = crate::random::SeededRng::new(42);
 
-        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
 
         let fdam = kouku.api_stage3.api_fdam[0];
         assert!(fdam > 0, "enemy CVL with bombers must deal airstrike damage");
@@ -687,7 +712,7 @@ mod tests {
         let mut enemies = vec![BattleRuntimeShip::new(enemy, false, true)];
         let mut rng = crate::random::SeededRng::new(42);
 
-        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
 
         let fdam = kouku.api_stage3.api_fdam[0];
         let hp_after = friendly[0].hp();
@@ -720,7 +745,7 @@ mod tests {
         let mut enemies = vec![BattleRuntimeShip::new(enemy, false, true)];
         let mut rng = crate::random::SeededRng::new(42);
 
-        simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
 
         assert!(
             friendly[0].hp() > 0,
@@ -752,7 +777,7 @@ mod tests {
         let mut enemies = vec![BattleRuntimeShip::from(enemy)];
         let mut rng = crate::random::SeededRng::new(42);
 
-        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng);
+        let kouku = simulate_kouku(&codex, &mut friendly, &mut enemies, &mut rng, 1, 1);
 
         let edam = kouku.api_stage3.api_edam[0];
         let enemy_hp_after = enemies[0].hp();
@@ -6,21 +6,29 @@
 
 use emukc_model::codex::Codex;
 
-use crate::config::{BattleFlow, BattlePhaseKind};
+use std::ops::Range;
+
+use crate::config::{BattleFlow, BattlePhaseKind, HougekiSlot};
+use crate::damage::assign_historical_bonuses;
 use crate::random::BattleRng;
 use crate::state::BattleState;
 use crate::targeting::{any_alive, can_closing_torpedo, can_opening_torpedo, fleet_has_bb_class};
 use crate::types::{
-    AirState, BattleContext, BattlePhase, BattleRuntimeShip, BattleSimulation, NightBattleInput,
-    NightBattleSimulation, ShellingParams,
+    AirState, BattleContext, BattleHougeki, BattleOpeningAttack, BattlePhase, BattleRaigeki,
+    BattleRuntimeShip, BattleSimulation, ESCORT_SLOT_OFFSET, NightBattleInput,
+    NightBattleSimulation, ShellingParams, TorpedoAttackerSide,
 };
 
+pub(crate) mod anti_air;
 pub(crate) mod asw;
+pub(crate) mod base_air;
 pub(crate) mod day_cutin;
+pub(crate) mod friendly;
 pub(crate) mod kouku;
 pub(crate) mod night;
 pub(crate) mod shelling;
 pub(crate) mod special_attack;
+pub(crate) mod support;
 pub(crate) mod torpedo;
 
 /// Returns the fleet speed value (minimum `api_soku` among alive ships).
@@ -40,17 +48,27 @@ fn enemy_shells_first(friendly: &[BattleRuntimeShip], enemy: &[BattleRuntimeShip
 /// dispatches each phase in order. Runtime preconditions (planes, torpedo-capable ships,
 /// alive counts) are checked within each phase arm.
 ///
-/// The `rng` parameter is consumed sequentially across all phases (kouku → OASW →
+/// The `rng` parameter is consumed sequentially across all phases (kouku → support → OASW →
 /// opening torpedo → shelling → closing torpedo), so the same seed produces a
 /// deterministic full battle result. Callers must NOT share the RNG instance across
 /// separate battle simulations if determinism is required.
 pub(crate) fn simulate_day(
     codex: &Codex,
-    context: BattleContext,
+    mut context: BattleContext,
     rng: &mut impl BattleRng,
 ) -> BattleSimulation {
+    let historical_bonuses = std::mem::take(&mut context.historical_bonuses);
     let mut state = BattleState::from_context(context);
-    let flow = BattleFlow::for_battle_type(state.battle_type());
+    assign_historical_bonuses(codex, &mut state.friendly, &historical_bonuses);
+    let flow = if state.is_combined() {
+        BattleFlow::for_combined(
+            state.battle_type(),
+            state.friendly_type(),
+            state.is_enemy_combined(),
+        )
+    } else {
+        BattleFlow::for_battle_type(state.battle_type())
+    };
     let enemy_first = enemy_shells_first(&state.friendly, &state.enemy);
 
     let has_bb =
@@ -59,28 +77,108 @@ pub(crate) fn simulate_day(
 
     for &phase in flow.phases {
         match phase {
+            BattlePhaseKind::BaseAirAttack => execute_base_air_attack(codex, &mut state, rng),
             BattlePhaseKind::Kouku => execute_kouku(codex, &mut state, rng),
+            BattlePhaseKind::Support => execute_support(codex, &mut state, rng),
             BattlePhaseKind::OpeningAsw => execute_opening_asw(codex, &mut state, rng),
+            BattlePhaseKind::OpeningTorpedo if state.is_combined() => {
+                execute_combined_opening_torpedo(codex, &mut state, rng);
+            }
             BattlePhaseKind::OpeningTorpedo => execute_opening_torpedo(codex, &mut state, rng),
             BattlePhaseKind::Shelling1 => execute_shelling1(codex, &mut state, rng, enemy_first),
             BattlePhaseKind::Shelling2 => execute_shelling2(codex, &mut state, rng, enemy_first),
+            BattlePhaseKind::ClosingTorpedo if state.is_combined() => {
+                execute_combined_closing_torpedo(codex, &mut state, rng);
+            }
             BattlePhaseKind::ClosingTorpedo => execute_closing_torpedo(codex, &mut state, rng),
+            BattlePhaseKind::MainShelling(slot) => {
+                let fleets = (state.friendly_main(), state.enemy_main());
+                execute_combined_shelling(codex, &mut state, rng, enemy_first, slot, fleets);
+            }
+            BattlePhaseKind::MainShellingRound2(slot) => {
+                if state.has_bb_class_at_start() {
+                    let fleets = (state.friendly_main(), state.enemy_main());
+                    execute_combined_shelling(codex, &mut state, rng, enemy_first, slot, fleets);
+                }
+            }
+            BattlePhaseKind::EscortShelling(slot) => {
+                let fleets = (state.friendly_escort_or_main(), state.enemy_escort_or_main());
+                execute_combined_shelling(codex, &mut state, rng, enemy_first, slot, fleets);
+            }
         }
     }
 
     state.finalize_day()
 }
 
+fn execute_base_air_attack(codex: &Codex, state: &mut BattleState, rng: &mut impl BattleRng) {
+    let enemy_form = state.enemy_formation_id();
+    for idx in 0..state.air_base_waves.len() {
+        // A base's second wave flies with what its first wave brought back.
+        let base_id = state.air_base_waves[idx].base_id;
+        if let Some(counts) = state.air_base_waves[..idx]
+            .iter()
+            .rev()
+            .find(|wave| wave.base_id == base_id)
+            .map(|wave| wave.squadrons.iter().map(|sq| sq.count).collect::<Vec<_>>())
+        {
+            for (squadron, count) in state.air_base_waves[idx].squadrons.iter_mut().zip(counts) {
+                squadron.count = count;
+            }
+        }
+
+        if !any_alive(&state.enemy)
+            || state.air_base_waves[idx].squadrons.iter().all(|sq| sq.count <= 0)
+        {
+            continue;
+        }
+        let attack = base_air::simulate_air_base_wave(
+            codex,
+            rng,
+            &mut state.air_base_waves[idx],
+            &mut state.enemy,
+            enemy_form,
+        );
+        state.push_air_base_attack(attack);
+    }
+}
+
 fn execute_kouku(codex: &Codex, state: &mut BattleState, rng: &mut impl BattleRng) {
     if kouku::has_any_air_combat_planes(codex, &state.friendly)
         || kouku::has_any_air_combat_planes(codex, &state.enemy)
     {
-        let kouku = kouku::simulate_kouku(codex, &mut state.friendly, &mut state.enemy, rng);
+        let friendly_form = state.friendly_formation_id();
+        let enemy_form = state.enemy_formation_id();
+        let kouku = kouku::simulate_kouku(
+            codex,
+            &mut state.friendly,
+            &mut state.enemy,
+            rng,
+            friendly_form,
+            enemy_form,
+        );
         state.set_stage_flag([1, 1, 1]);
         state.set_kouku(kouku);
     }
 }
 
+fn execute_support(codex: &Codex, state: &mut BattleState, rng: &mut impl BattleRng) {
+    if !any_alive(&state.enemy) {
+        return;
+    }
+    let [_, enemy_slots] = state.escort_slot_gaps();
+    let enemy_form = state.enemy_formation_id();
+    let eng = state.engagement();
+    let Some(fleet) = state.support.as_mut() else {
+        return;
+    };
+    if let Some((kind, info)) =
+        support::simulate_support(codex, rng, fleet, &mut state.enemy, enemy_slots, enemy_form, eng)
+    {
+        state.set_support(kind.api_flag(), info);
+    }
+}
+
 fn execute_opening_asw(codex: &Codex, state: &mut BattleState, rng: &mut impl BattleRng) {
     let friendly_form = state.friendly_formation_id();
     let enemy_form = state.enemy_formation_id();
@@ -251,7 +349,203 @@ fn execute_closing_torpedo(codex: &Codex, state: &mut BattleState, rng: &mut imp
     }
 }
 
+/// Combined-fleet shelling phase between one friendly and one enemy fleet.
+///
+/// Unlike the single-fleet phases, both sides fire within the phase: the
+/// faster side first, and the merged hougeki addresses ships by their
+/// combined slot (escorts start at slot 6).
+fn execute_combined_shelling(
+    codex: &Codex,
+    state: &mut BattleState,
+    rng: &mut impl BattleRng,
+    enemy_first: bool,
+    slot: HougekiSlot,
+    (friendly_fleet, enemy_fleet): (Range<usize>, Range<usize>),
+) {
+    let friendly_form = state.friendly_formation_id();
+    let enemy_form = state.enemy_formation_id();
+    let eng = state.engagement();
+    let air_state =
+        state.kouku().and_then(|k| AirState::from_api_disp_seiku(k.api_stage1.api_disp_seiku));
+
+    let mut merged: Option<BattleHougeki> = None;
+    for attacker_is_enemy in [enemy_first, !enemy_first] {
+        let friendly = &mut state.friendly[friendly_fleet.clone()];
+        let enemy = &mut state.enemy[enemy_fleet.clone()];
+        if !any_alive(friendly) || !any_alive(enemy) {
+            break;
+        }
+        let (attackers, defenders, formation_id, defender_formation_id) = if attacker_is_enemy {
+            (enemy, friendly, enemy_form, friendly_form)
+        } else {
+            (friendly, enemy, friendly_form, enemy_form)
+        };
+        let Some(mut hougeki) = shelling::simulate_shelling_side(
+            codex,
+            rng,
+            attackers,
+            defenders,
+            &ShellingParams {
+                attacker_is_enemy,
+                formation_id,
+                defender_formation_id,
+                engagement: eng,
+                phase: BattlePhase::DayShelling,
+                air_state: air_state.as_ref(),
+            },
+        ) else {
+            continue;
+        };
+        hougeki.offset_indices(escort_slot(&friendly_fleet), escort_slot(&enemy_fleet));
+        match merged.as_mut() {
+            Some(merged) => merged.append(hougeki),
+            None => merged = Some(hougeki),
+        }
+    }
+
+    if merged.is_some() {
+        state.set_hourai_flag(slot.index(), 1);
+    }
+    match slot {
+        HougekiSlot::First => state.set_hougeki1(merged),
+        HougekiSlot::Second => state.set_hougeki2(merged),
+        HougekiSlot::Third => state.set_hougeki3(merged),
+    }
+}
+
+/// Ships the enemy's torpedoes may target: a player escort fleet draws all
+/// fire from a single enemy fleet, otherwise the whole player side is exposed.
+fn enemy_torpedo_targets(state: &BattleState) -> Range<usize> {
+    if state.friendly_type().is_some() && !state.is_enemy_combined() {
+        state.friendly_escort_or_main()
+    } else {
+        0..state.friendly.len()
+    }
+}
+
+/// API slot offset of a fleet range: escorts start at [`ESCORT_SLOT_OFFSET`].
+fn escort_slot(fleet: &Range<usize>) -> usize {
+    if fleet.start == 0 {
+        0
+    } else {
+        ESCORT_SLOT_OFFSET
+    }
+}
+
+/// Combined-fleet opening torpedo: each side's escort (or its only fleet)
+/// fires; friendly torpedoes may hit the whole enemy side.
+fn execute_combined_opening_torpedo(
+    codex: &Codex,
+    state: &mut BattleState,
+    rng: &mut impl BattleRng,
+) {
+    let friendly_fleet = state.friendly_escort_or_main();
+    let enemy_fleet = state.enemy_escort_or_main();
+    if !can_opening_torpedo(codex, &state.friendly[friendly_fleet.clone()])
+        && !can_opening_torpedo(codex, &state.enemy[enemy_fleet.clone()])
+    {
+        return;
+    }
+    let enemy_targets = enemy_torpedo_targets(state);
+    let [friendly_salvo, enemy_salvo] =
+        combined_torpedo_salvos(state, &friendly_fleet, &enemy_fleet, &enemy_targets);
+    let mut payload = BattleOpeningAttack::blank(state.friendly.len().max(state.enemy.len()));
+    let [friendly_gap, enemy_gap] = state.escort_slot_gaps();
+    let friendly_fired = torpedo::opening_torpedo_salvo(
+        codex,
+        rng,
+        &mut state.friendly[friendly_fleet],
+        &mut state.enemy,
+        &friendly_salvo,
+        &mut payload,
+    );
+    let enemy_fired = torpedo::opening_torpedo_salvo(
+        codex,
+        rng,
+        &mut state.enemy[enemy_fleet],
+        &mut state.friendly[enemy_targets],
+        &enemy_salvo,
+        &mut payload,
+    );
+    payload.pad_escort_slots(friendly_gap, enemy_gap);
+    state.set_opening_attack((friendly_fired || enemy_fired).then_some(payload));
+}
+
+/// Combined-fleet closing torpedo, with the same fleets as the opening one.
+fn execute_combined_closing_torpedo(
+    codex: &Codex,
+    state: &mut BattleState,
+    rng: &mut impl BattleRng,
+) {
+    let friendly_fleet = state.friendly_escort_or_main();
+    let enemy_fleet = state.enemy_escort_or_main();
+    if !any_alive(&state.friendly)
+        || !any_alive(&state.enemy)
+        || (!can_closing_torpedo(codex, &state.friendly[friendly_fleet.clone()])
+            && !can_closing_torpedo(codex, &state.enemy[enemy_fleet.clone()]))
+    {
+        return;
+    }
+    let enemy_targets = enemy_torpedo_targets(state);
+    let [friendly_salvo, enemy_salvo] =
+        combined_torpedo_salvos(state, &friendly_fleet, &enemy_fleet, &enemy_targets);
+    let mut payload = BattleRaigeki::blank(state.friendly.len().max(state.enemy.len()));
+    let [friendly_gap, enemy_gap] = state.escort_slot_gaps();
+    let friendly_fired = torpedo::raigeki_salvo(
+        codex,
+        rng,
+        &mut state.friendly[friendly_fleet],
+        &mut state.enemy,
+        &friendly_salvo,
+        &mut payload,
+    );
+    let enemy_fired = torpedo::raigeki_salvo(
+        codex,
+        rng,
+        &mut state.enemy[enemy_fleet],
+        &mut state.friendly[enemy_targets],
+        &enemy_salvo,
+        &mut payload,
+    );
+    if friendly_fired || enemy_fired {
+        payload.pad_escort_slots(friendly_gap, enemy_gap);
+        state.set_raigeki(Some(payload));
+        state.set_hourai_flag(3, 1);
+    }
+}
+
+fn combined_torpedo_salvos(
+    state: &BattleState,
+    friendly_fleet: &Range<usize>,
+    enemy_fleet: &Range<usize>,
+    enemy_targets: &Range<usize>,
+) -> [torpedo::TorpedoSalvo; 2] {
+    let friendly_form = state.friendly_formation_id();
+    let enemy_form = state.enemy_formation_id();
+    [
+        torpedo::TorpedoSalvo {
+            side: TorpedoAttackerSide::Friendly,
+            attacker_offset: friendly_fleet.start,
+            defender_offset: 0,
+            attacker_formation_id: friendly_form,
+            defender_formation_id: enemy_form,
+            engagement: state.engagement(),
+        },
+        torpedo::TorpedoSalvo {
+            side: TorpedoAttackerSide::Enemy,
+            attacker_offset: enemy_fleet.start,
+            defender_offset: enemy_targets.start,
+            attacker_formation_id: enemy_form,
+            defender_formation_id: friendly_form,
+            engagement: state.engagement(),
+        },
+    ]
+}
+
 /// Simulate a night battle.
+///
+/// In a combined-fleet battle the player's escort fleet fights, against the
+/// enemy escort while any of it floats and the enemy main fleet otherwise.
 pub(crate) fn simulate_night(
     codex: &Codex,
     input: NightBattleInput,
@@ -260,21 +554,59 @@ pub(crate) fn simulate_night(
     let NightBattleInput {
         mut friendly,
         mut enemy,
+        mut friendly_escort,
+        mut enemy_escort,
         friendly_formation_id,
         enemy_formation_id,
         engagement,
         air_state,
+        friend_fleet,
+        historical_bonuses,
         ..
     } = input;
-    let entry_friendly_nowhps = friendly.iter().map(|ship| ship.hp().max(0)).collect::<Vec<_>>();
-    let entry_friendly_maxhps = friendly.iter().map(|ship| ship.ship.api_maxhp).collect::<Vec<_>>();
-    let entry_enemy_nowhps = enemy.iter().map(|ship| ship.hp().max(0)).collect::<Vec<_>>();
-    let entry_enemy_maxhps = enemy.iter().map(|ship| ship.ship.api_maxhp).collect::<Vec<_>>();
-    let hougeki = night::simulate_night_hougeki(
+    if !historical_bonuses.is_empty() {
+        assign_historical_bonuses(codex, &mut friendly, &historical_bonuses);
+        assign_historical_bonuses(codex, &mut friendly_escort, &historical_bonuses);
+    }
+    let entry_hps = |main: &[BattleRuntimeShip], escort: &[BattleRuntimeShip]| {
+        let ships = main.iter().chain(escort);
+        (
+            ships.clone().map(|ship| ship.hp().max(0)).collect::<Vec<_>>(),
+            ships.map(|ship| ship.ship.api_maxhp).collect::<Vec<_>>(),
+        )
+    };
+    let entry_friendly_hps = entry_hps(&friendly, &friendly_escort);
+    let entry_enemy_hps = entry_hps(&enemy, &enemy_escort);
+
+    let friendly_escort_fights = !friendly_escort.is_empty();
+    let enemy_escort_fights = any_alive(&enemy_escort);
+    let (friendly_fleet, friendly_offset) = if friendly_escort_fights {
+        (&mut friendly_escort, ESCORT_SLOT_OFFSET)
+    } else {
+        (&mut friendly, 0)
+    };
+    let (enemy_fleet, enemy_offset) = if enemy_escort_fights {
+        (&mut enemy_escort, ESCORT_SLOT_OFFSET)
+    } else {
+        (&mut enemy, 0)
+    };
+    // The friend fleet strikes first; entry HPs stay those before its attack.
+    let friendly_phase = friend_fleet.filter(|_| any_alive(enemy_fleet)).map(|friend| {
+        friendly::simulate_friend_fleet(
+            codex,
+            rng,
+            friend,
+            enemy_fleet,
+            enemy_offset,
+            enemy_formation_id,
+            engagement,
+        )
+    });
+    let mut hougeki = night::simulate_night_hougeki(
         codex,
         rng,
-        &mut friendly,
-        &mut enemy,
+        friendly_fleet,
+        enemy_fleet,
         &crate::types::NightBattleParams {
             friendly_formation_id,
             enemy_formation_id,
@@ -282,23 +614,24 @@ pub(crate) fn simulate_night(
             air_state: air_state.as_ref(),
         },
     );
+    if let Some(hougeki) = hougeki.as_mut() {
+        hougeki.offset_indices(friendly_offset, enemy_offset);
+    }
 
     // Build a minimal state for finalization
-    let state = BattleState::for_night(
-        friendly,
-        enemy,
+    let mut state = BattleState::for_night(
+        (friendly, friendly_escort),
+        (enemy, enemy_escort),
         friendly_formation_id,
         enemy_formation_id,
         engagement,
     );
+    if let Some(phase) = friendly_phase {
+        state.set_friendly_phase(phase);
+    }
 
-    state.finalize_night(
-        entry_friendly_nowhps,
-        entry_friendly_maxhps,
-        entry_enemy_nowhps,
-        entry_enemy_maxhps,
-        hougeki,
-    )
+    let active_deck = [1 + i64::from(friendly_escort_fights), 1 + i64::from(enemy_escort_fights)];
+    state.finalize_night(entry_friendly_hps, entry_enemy_hps, active_deck, hougeki)
 }
 
 #[cfg(test)]
@@ -334,6 +667,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -365,6 +702,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![carrier],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -396,6 +737,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -435,6 +780,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![carrier],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -471,6 +820,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -510,6 +863,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -571,6 +928,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![dd],
                 enemy_ships: vec![enemy1, enemy2],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -628,6 +989,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships,
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -671,6 +1036,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -706,6 +1075,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -741,6 +1114,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -775,6 +1152,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -809,6 +1190,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -843,6 +1228,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -886,6 +1275,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy_bb, enemy_dd],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -937,6 +1330,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -991,6 +1388,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
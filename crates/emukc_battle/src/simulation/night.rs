@@ -8,6 +8,7 @@ use emukc_model::{
     kc2::{KcShipType, KcSlotItemType3},
 };
 
+use crate::accuracy::{AttackKind, HitCheck, roll_hit};
 use crate::damage::{calculate_night_damage, calculate_scratch_damage};
 use crate::random::BattleRng;
 use crate::targeting::{
@@ -808,8 +809,16 @@ pub(crate) fn simulate_night_hougeki(
         let mut hit_cls = Vec::new();
         let mut total_dealt = 0i64;
 
+        let check = HitCheck::new(
+            AttackKind::Night,
+            params.friendly_formation_id,
+            params.enemy_formation_id,
+        );
         for _ in 0..hits {
-            let raw = if is_submarine {
+            let hit = roll_hit(codex, rng, ship, &enemy[target_idx], &check);
+            let raw = if hit.is_miss() {
+                0
+            } else if is_submarine {
                 calculate_scratch_damage(rng, enemy[target_idx].hp().max(1))
             } else {
                 calculate_night_damage(
@@ -823,13 +832,14 @@ pub(crate) fn simulate_night_hougeki(
                     } else {
                         None
                     },
+                    hit,
                 )
             };
             let (raw_dmg, dealt) = enemy[target_idx].apply_damage(rng, raw, target_idx);
             total_dealt += dealt;
             let display = crate::targeting::display_damage(&enemy[target_idx], raw_dmg, dealt);
             hit_damages.push(display);
-            hit_cls.push(1i64);
+            hit_cls.push(hit.api_cl());
         }
         ship.damage_dealt += total_dealt;
 
@@ -886,8 +896,16 @@ pub(crate) fn simulate_night_hougeki(
         let mut hit_cls = Vec::new();
         let mut total_dealt = 0i64;
 
+        let check = HitCheck::new(
+            AttackKind::Night,
+            params.enemy_formation_id,
+            params.friendly_formation_id,
+        );
         for _ in 0..hits {
-            let raw = if is_submarine {
+            let hit = roll_hit(codex, rng, ship, &friendly[target_idx], &check);
+            let raw = if hit.is_miss() {
+                0
+            } else if is_submarine {
                 calculate_scratch_damage(rng, friendly[target_idx].hp().max(1))
             } else {
                 calculate_night_damage(
@@ -901,13 +919,14 @@ pub(crate) fn simulate_night_hougeki(
                     } else {
                         None
                     },
+                    hit,
                 )
             };
             let (raw_dmg, dealt) = friendly[target_idx].apply_damage(rng, raw, target_idx);
             total_dealt += dealt;
             let display = crate::targeting::display_damage(&friendly[target_idx], raw_dmg, dealt);
             hit_damages.push(display);
-            hit_cls.push(1i64);
+            hit_cls.push(hit.api_cl());
         }
         ship.damage_dealt += total_dealt;
 
@@ -1198,10 +1217,14 @@ mod tests {
             crate::types::NightBattleInput {
                 friendly: vec![BattleRuntimeShip::from(carrier)],
                 enemy: vec![BattleRuntimeShip::from(enemy)],
+                friendly_escort: Vec::new(),
+                enemy_escort: Vec::new(),
                 friendly_formation_id: 1,
                 enemy_formation_id: 1,
                 engagement: EngagementType::SameCourse,
                 air_state: None,
+                friend_fleet: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -1590,12 +1613,26 @@ mod tests {
         let mut rng = crate::random::SeededRng::new(42);
 
         // Damage with 2.0x CI multiplier (MainMainMain)
-        let dmg_with_ci =
-            calculate_night_damage(&codex, &mut rng, &rt_attacker, &rt_defender, None, Some(2.0));
+        let dmg_with_ci = calculate_night_damage(
+            &codex,
+            &mut rng,
+            &rt_attacker,
+            &rt_defender,
+            None,
+            Some(2.0),
+            crate::accuracy::HitResult::Hit,
+        );
 
         // Damage without CI multiplier
-        let dmg_normal =
-            calculate_night_damage(&codex, &mut rng, &rt_attacker, &rt_defender, None, None);
+        let dmg_normal = calculate_night_damage(
+            &codex,
+            &mut rng,
+            &rt_attacker,
+            &rt_defender,
+            None,
+            None,
+            crate::accuracy::HitResult::Hit,
+        );
 
         // With pre-cap: apply_cap(455*2.0, 360) = 360 + sqrt(550) ≈ 383
         // capped_power ≈ 383, defense ≈ 0, so damage ≈ 383
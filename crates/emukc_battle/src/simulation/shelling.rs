@@ -2,6 +2,7 @@
 
 use emukc_model::codex::Codex;
 
+use crate::accuracy::{AttackKind, HitCheck, HitResult, roll_hit};
 use crate::damage::{calculate_asw_damage, calculate_shelling_damage};
 use crate::random::BattleRng;
 use crate::simulation::day_cutin::{DayAttackType, carrier_ci_display_ids, resolve_day_attack};
@@ -85,8 +86,18 @@ pub(crate) fn simulate_shelling_side(
             None => false,
         };
         let is_asw_attack = target_class(codex, &defenders[target_idx]).is_submarine();
+        let check = HitCheck::new(
+            if is_asw_attack {
+                AttackKind::Asw
+            } else {
+                AttackKind::Shelling
+            },
+            params.formation_id,
+            params.defender_formation_id,
+        );
 
         if is_asw_attack {
+            let hit = roll_hit(codex, rng, ship, &defenders[target_idx], &check);
             let raw = calculate_asw_damage(
                 codex,
                 rng,
@@ -94,6 +105,7 @@ pub(crate) fn simulate_shelling_side(
                 &defenders[target_idx],
                 params.formation_id,
                 params.engagement,
+                hit,
             );
             let (raw_dmg, dealt) = defenders[target_idx].apply_damage(rng, raw, target_idx);
             if !params.attacker_is_enemy {
@@ -115,6 +127,7 @@ pub(crate) fn simulate_shelling_side(
                 vec![target_idx as i64],
                 SiListId::num_from_i64(&day_attack_display_ids(codex, ship, true)),
                 vec![display],
+                vec![hit],
                 shield,
             );
         } else {
@@ -129,7 +142,9 @@ pub(crate) fn simulate_shelling_side(
             if resolved.hit_count == 2 {
                 // DoubleAttack: 2 hits on the same target
                 let mut damages = Vec::with_capacity(2);
+                let mut hits = Vec::with_capacity(2);
                 for _ in 0..2 {
+                    let hit = roll_hit(codex, rng, ship, &defenders[target_idx], &check);
                     let raw = calculate_shelling_damage(
                         codex,
                         rng,
@@ -138,6 +153,7 @@ pub(crate) fn simulate_shelling_side(
                         params.formation_id,
                         params.engagement,
                         ci_mult,
+                        hit,
                     );
                     let (raw_dmg, dealt) = defenders[target_idx].apply_damage(rng, raw, target_idx);
                     if !params.attacker_is_enemy {
@@ -148,6 +164,7 @@ pub(crate) fn simulate_shelling_side(
                         raw_dmg,
                         dealt,
                     ));
+                    hits.push(hit);
                 }
                 push_attack(
                     &mut at_eflag,
@@ -163,9 +180,11 @@ pub(crate) fn simulate_shelling_side(
                     vec![target_idx as i64; 2],
                     SiListId::text_from_i64(&day_attack_display_ids(codex, ship, false)),
                     damages,
+                    hits,
                     shield,
                 );
             } else {
+                let hit = roll_hit(codex, rng, ship, &defenders[target_idx], &check);
                 let raw = calculate_shelling_damage(
                     codex,
                     rng,
@@ -174,6 +193,7 @@ pub(crate) fn simulate_shelling_side(
                     params.formation_id,
                     params.engagement,
                     ci_mult,
+                    hit,
                 );
                 let (raw_dmg, dealt) = defenders[target_idx].apply_damage(rng, raw, target_idx);
                 if !params.attacker_is_enemy {
@@ -207,6 +227,7 @@ pub(crate) fn simulate_shelling_side(
                     vec![target_idx as i64],
                     display_ids,
                     vec![display],
+                    vec![hit],
                     shield,
                 );
             }
@@ -239,14 +260,16 @@ fn push_attack(
     targets: Vec<i64>,
     display_ids: Vec<SiListId>,
     damages: Vec<i64>,
+    hits: Vec<HitResult>,
     shield: bool,
 ) {
+    debug_assert_eq!(damages.len(), hits.len(), "one hit result per damage cell");
     at_eflag.push(i64::from(attacker_is_enemy));
     at_list.push(attacker_idx as i64);
     at_type.push(attack_type);
     df_list.push(targets);
     si_list.push(display_ids);
-    cl_list.push(vec![1; damages.len()]);
+    cl_list.push(hits.into_iter().map(HitResult::api_cl).collect());
     // 旗艦援護: an intercepted hit carries the `.1` shield flag (DamageCell::Shielded).
     damage.push(damages.into_iter().map(|d| damage_cell(d, shield)).collect());
 }
@@ -386,6 +409,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![carrier, bb],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut crate::random::SeededRng::new(1),
         );
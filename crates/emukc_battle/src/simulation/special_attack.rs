@@ -6,6 +6,7 @@
 
 use emukc_model::{codex::Codex, kc2::KcShipType};
 
+use crate::accuracy::{AttackKind, HitCheck, roll_hit};
 use crate::damage::calculate_shelling_damage;
 use crate::random::BattleRng;
 use crate::targeting::{can_shell_day_ship, select_random_target_index, ship_type, target_class};
@@ -670,8 +671,11 @@ pub(crate) fn execute_special_attack(
         let mut hit_damages = Vec::with_capacity(num_hits);
         let mut hit_cls = Vec::with_capacity(num_hits);
 
+        let check =
+            HitCheck::new(AttackKind::Shelling, params.formation_id, params.defender_formation_id);
         for _ in 0..num_hits {
             let ci_mult = Some(total_mult * equip_mult);
+            let hit = roll_hit(codex, rng, attacker, &defenders[target_idx], &check);
             let raw = calculate_shelling_damage(
                 codex,
                 rng,
@@ -680,6 +684,7 @@ pub(crate) fn execute_special_attack(
                 params.formation_id,
                 params.engagement,
                 ci_mult,
+                hit,
             );
             let (raw_dmg, dealt) = defenders[target_idx].apply_damage(rng, raw, target_idx);
             if !is_enemy {
@@ -687,7 +692,7 @@ pub(crate) fn execute_special_attack(
             }
             let display = crate::targeting::display_damage(&defenders[target_idx], raw_dmg, dealt);
             hit_damages.push(display);
-            hit_cls.push(1);
+            hit_cls.push(hit.api_cl());
         }
 
         at_eflag.push(i64::from(is_enemy));
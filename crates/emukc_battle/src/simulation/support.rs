@@ -0,0 +1,483 @@
+//! Support expedition (支援艦隊) phase simulation.
+//!
+//! A fleet on a node- or boss-support expedition may join the day battle right
+//! after kouku. Its composition selects a single attack: an air strike, an ASW
+//! air strike, shelling or long-range torpedo. Support fire is one-sided: the
+//! enemy never fires back and only the enemy side takes damage.
+
+use emukc_model::{
+    codex::Codex,
+    kc2::{KcShipType, KcSlotItemType3, start2::ApiMstSlotitem},
+};
+
+use crate::accuracy::{AttackKind, HitCheck, HitResult, roll_hit, roll_squadron_hit};
+use crate::damage::{apply_cap, calculate_defense_power, resolve_damage};
+use crate::random::BattleRng;
+use crate::simulation::{anti_air, kouku};
+use crate::targeting::{display_damage, is_airstrike_attack_type, ship_type, target_class};
+use crate::types::{
+    AirState, BattleAirBaseStage3, BattleKoukuStage1, BattleKoukuStage2, BattleRuntimeShip,
+    BattleSupportAirAttack, BattleSupportHourai, BattleSupportInfo, DamageCell, EngagementType,
+};
+
+/// Arrival rate (percent) of node support.
+const NODE_SUPPORT_RATE: i64 = 50;
+/// Arrival rate (percent) of boss support.
+const BOSS_SUPPORT_RATE: i64 = 85;
+/// Extra arrival rate of a sparkled flagship.
+const FLAGSHIP_SPARKLE_BONUS: i64 = 15;
+/// Extra arrival rate of every other sparkled ship.
+const SHIP_SPARKLE_BONUS: i64 = 5;
+/// Morale from which a ship counts as sparkled (キラキラ).
+const SPARKLE_COND: i64 = 50;
+
+/// Soft cap of every support attack.
+const SUPPORT_POWER_CAP: f64 = 170.0;
+/// Flat power added to the firepower of a shelling support ship.
+const SHELLING_POWER_BONUS: f64 = 4.0;
+/// Flat power added to the torpedo stat of a long-range torpedo ship.
+const TORPEDO_POWER_BONUS: f64 = 8.0;
+/// Flat power added to a support plane slot.
+const AIR_POWER_BONUS: f64 = 3.0;
+/// Multiplier of a support plane slot's power.
+const AIR_POWER_MODIFIER: f64 = 1.35;
+
+/// Support fleets always sail in line ahead.
+const SUPPORT_FORMATION_ID: i64 = 1;
+
+const CARRIER_TYPES: &[KcShipType] =
+    &[KcShipType::CV, KcShipType::CVL, KcShipType::CVB, KcShipType::AV, KcShipType::LHA];
+const GUNSHIP_TYPES: &[KcShipType] =
+    &[KcShipType::FBB, KcShipType::BB, KcShipType::BBV, KcShipType::CA, KcShipType::CAV];
+
+/// Kind of support attack (`api_support_flag`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SupportType {
+    Air,
+    Shelling,
+    Torpedo,
+    Asw,
+}
+
+impl SupportType {
+    pub(crate) const fn api_flag(self) -> i64 {
+        match self {
+            Self::Air => 1,
+            Self::Shelling => 2,
+            Self::Torpedo => 3,
+            Self::Asw => 4,
+        }
+    }
+}
+
+/// Fleet on a support expedition, as it enters the battle.
+#[derive(Debug, Clone)]
+pub(crate) struct SupportFleet {
+    pub deck_id: i64,
+    pub ships: Vec<BattleRuntimeShip>,
+    pub boss: bool,
+}
+
+/// Support attack from the fleet composition; `None` below two destroyers.
+///
+/// Two battleships or heavy cruisers shell unless three carriers outnumber
+/// them, two carriers strike from the air, and any other fleet fires
+/// long-range torpedoes. An air strike against a fleet of only submarines
+/// turns into ASW support when a plane can attack them.
+pub(crate) fn support_type(
+    codex: &Codex,
+    ships: &[BattleRuntimeShip],
+    enemy: &[BattleRuntimeShip],
+) -> Option<SupportType> {
+    let count = |types: &[KcShipType]| {
+        ships
+            .iter()
+            .filter(|ship| ship_type(codex, ship).is_some_and(|st| types.contains(&st)))
+            .count()
+    };
+    if count(&[KcShipType::DD]) < 2 {
+        return None;
+    }
+    let carriers = count(CARRIER_TYPES);
+    let gunships = count(GUNSHIP_TYPES);
+    if gunships >= 2 && carriers < 3 {
+        return Some(SupportType::Shelling);
+    }
+    if carriers < 2 {
+        return Some(SupportType::Torpedo);
+    }
+    let only_submarines = enemy
+        .iter()
+        .filter(|ship| ship.is_alive())
+        .all(|ship| target_class(codex, ship).is_submarine());
+    let has_asw_planes = ships
+        .iter()
+        .any(|ship| support_planes(codex, ship).any(|(_, mst, _)| is_asw_support_plane(mst)));
+    if only_submarines && has_asw_planes {
+        Some(SupportType::Asw)
+    } else {
+        Some(SupportType::Air)
+    }
+}
+
+/// Arrival rate in percent: node 50 / boss 85, raised by sparkled ships.
+pub(crate) fn arrival_rate(ships: &[BattleRuntimeShip], boss: bool) -> i64 {
+    let base = if boss {
+        BOSS_SUPPORT_RATE
+    } else {
+        NODE_SUPPORT_RATE
+    };
+    let bonus: i64 = ships
+        .iter()
+        .enumerate()
+        .filter(|(_, ship)| ship.ship.api_cond >= SPARKLE_COND)
+        .map(|(idx, _)| {
+            if idx == 0 {
+                FLAGSHIP_SPARKLE_BONUS
+            } else {
+                SHIP_SPARKLE_BONUS
+            }
+        })
+        .sum();
+    (base + bonus).min(100)
+}
+
+/// Run the support phase against the whole enemy side.
+///
+/// `enemy_slots` is the enemy main fleet size and its gap to the escort
+/// slots. Returns `None` when the fleet cannot support or does not arrive.
+pub(crate) fn simulate_support(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    support: &mut SupportFleet,
+    enemy: &mut [BattleRuntimeShip],
+    enemy_slots: (usize, usize),
+    enemy_formation_id: i64,
+    engagement: EngagementType,
+) -> Option<(SupportType, BattleSupportInfo)> {
+    let kind = support_type(codex, &support.ships, enemy)?;
+    if rng.roll_range(0, 100) >= arrival_rate(&support.ships, support.boss) {
+        return None;
+    }
+    let info = match kind {
+        SupportType::Air | SupportType::Asw => BattleSupportInfo {
+            api_support_airatack: Some(air_support(
+                codex,
+                rng,
+                support,
+                enemy,
+                enemy_slots,
+                enemy_formation_id,
+                kind,
+            )),
+            api_support_hourai: None,
+        },
+        SupportType::Shelling | SupportType::Torpedo => BattleSupportInfo {
+            api_support_airatack: None,
+            api_support_hourai: Some(hourai_support(
+                codex,
+                rng,
+                support,
+                enemy,
+                enemy_slots,
+                enemy_formation_id,
+                engagement,
+                kind,
+            )),
+        },
+    };
+    Some((kind, info))
+}
+
+/// Slot of enemy ship `idx`: escorts move past a short main fleet.
+fn enemy_slot(idx: usize, (main_len, gap): (usize, usize)) -> usize {
+    if idx >= main_len {
+        idx + gap
+    } else {
+        idx
+    }
+}
+
+fn ship_ids(support: &SupportFleet) -> Vec<i64> {
+    support.ships.iter().map(|ship| ship.ship.api_id).collect()
+}
+
+/// 1 for ships shown damaged (中破 or worse).
+fn undressing_flags(support: &SupportFleet) -> Vec<i64> {
+    support.ships.iter().map(|ship| i64::from(ship.hp() * 2 <= ship.ship.api_maxhp)).collect()
+}
+
+/// Random alive enemy accepted by `eligible`.
+fn pick_target(
+    rng: &mut impl BattleRng,
+    enemy: &[BattleRuntimeShip],
+    eligible: impl Fn(&BattleRuntimeShip) -> bool,
+) -> Option<usize> {
+    let targets: Vec<usize> = enemy
+        .iter()
+        .enumerate()
+        .filter(|(_, ship)| ship.is_alive() && eligible(ship))
+        .map(|(idx, _)| idx)
+        .collect();
+    rng.choose_index(targets.len()).map(|pick| targets[pick])
+}
+
+/// Capped support power against `defender`.
+fn support_damage(
+    rng: &mut impl BattleRng,
+    basic_power: f64,
+    defender: &BattleRuntimeShip,
+    hit: HitResult,
+) -> i64 {
+    if hit.is_miss() || basic_power <= 0.0 {
+        return 0;
+    }
+    let capped = apply_cap(basic_power, SUPPORT_POWER_CAP) as f64 * hit.damage_multiplier();
+    let defense = calculate_defense_power(rng, defender.ship.api_soukou[0]);
+    resolve_damage(rng, capped, defense, defender.hp())
+}
+
+// ---------------------------------------------------------------------------
+// Shelling and long-range torpedo support
+// ---------------------------------------------------------------------------
+
+#[allow(clippy::too_many_arguments)]
+fn hourai_support(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    support: &SupportFleet,
+    enemy: &mut [BattleRuntimeShip],
+    enemy_slots: (usize, usize),
+    enemy_formation_id: i64,
+    engagement: EngagementType,
+    kind: SupportType,
+) -> BattleSupportHourai {
+    let len = enemy.len() + enemy_slots.1;
+    let mut api_cl_list = vec![0; len];
+    let mut api_damage = vec![DamageCell::Plain(0); len];
+    let check = HitCheck::new(AttackKind::Support, SUPPORT_FORMATION_ID, enemy_formation_id);
+
+    for ship in support.ships.iter().filter(|ship| ship.is_alive()) {
+        let basic_power = if kind == SupportType::Torpedo {
+            let torpedo = ship.ship.api_raisou[0];
+            if torpedo <= 0 {
+                continue;
+            }
+            torpedo as f64 + TORPEDO_POWER_BONUS
+        } else {
+            if target_class(codex, ship).is_submarine() {
+                continue;
+            }
+            ship.ship.api_karyoku[0].max(0) as f64 + SHELLING_POWER_BONUS
+        };
+        let Some(target) = pick_target(rng, enemy, |s| target_class(codex, s).is_surface_like())
+        else {
+            break;
+        };
+        let hit = roll_hit(codex, rng, ship, &enemy[target], &check);
+        let damage = support_damage(rng, basic_power * engagement.modifier(), &enemy[target], hit);
+        let (raw, dealt) = enemy[target].apply_damage(rng, damage, target);
+        let slot = enemy_slot(target, enemy_slots);
+        api_damage[slot] = DamageCell::Plain(
+            api_damage[slot].amount() + display_damage(&enemy[target], raw, dealt),
+        );
+        api_cl_list[slot] = api_cl_list[slot].max(hit.api_cl());
+    }
+
+    BattleSupportHourai {
+        api_deck_id: support.deck_id,
+        api_ship_id: ship_ids(support),
+        api_undressing_flag: undressing_flags(support),
+        api_cl_list,
+        api_damage,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Air and ASW support
+// ---------------------------------------------------------------------------
+
+/// Planes that attack submarines in ASW support.
+fn is_asw_support_plane(mst: &ApiMstSlotitem) -> bool {
+    mst.api_tais > 0
+        && matches!(
+            KcSlotItemType3::n(mst.api_type[2]),
+            Some(
+                KcSlotItemType3::CarrierBasedDiveBomber
+                    | KcSlotItemType3::CarrierBasedTorpedoBomber
+                    | KcSlotItemType3::SeaBasedBomber
+                    | KcSlotItemType3::AutoGyro
+                    | KcSlotItemType3::AntiSubmarinePatrol
+                    | KcSlotItemType3::LargeFlyingBoat
+            )
+        )
+}
+
+/// Loaded plane slots of a ship: slot index, master data and plane count.
+fn support_planes<'a>(
+    codex: &'a Codex,
+    ship: &'a BattleRuntimeShip,
+) -> impl Iterator<Item = (usize, &'a ApiMstSlotitem, i64)> + 'a {
+    ship.slot_items.iter().enumerate().filter_map(|(slot_idx, slot_item)| {
+        let onslot = ship.ship.api_onslot.get(slot_idx).copied().unwrap_or(0);
+        if onslot <= 0 {
+            return None;
+        }
+        let mst = codex.find::<ApiMstSlotitem>(&slot_item.api_slotitem_id).ok()?;
+        Some((slot_idx, mst, onslot))
+    })
+}
+
+fn air_support(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    support: &mut SupportFleet,
+    enemy: &mut [BattleRuntimeShip],
+    enemy_slots: (usize, usize),
+    enemy_formation_id: i64,
+    kind: SupportType,
+) -> BattleSupportAirAttack {
+    let f_count = kouku::total_plane_count(codex, &support.ships);
+    let e_count = kouku::total_plane_count(codex, enemy);
+    let api_plane_from = [Some(kouku::attack_plane_from(codex, &support.ships)), None];
+
+    // Stage 1: fighter combat.
+    let air_state = AirState::from_power(
+        kouku::calculate_fighter_power(codex, &support.ships),
+        kouku::calculate_fighter_power(codex, enemy),
+    );
+    let (f_loss_min, f_loss_max) = air_state.stage1_friendly_loss_ratio();
+    let (e_loss_min, e_loss_max) = air_state.stage1_enemy_loss_ratio();
+    let stage1_f_lost =
+        (f_count as f64 * rng.random_f64_range(f_loss_min, f_loss_max)).floor() as i64;
+    let stage1_e_lost =
+        (e_count as f64 * rng.random_f64_range(e_loss_min, e_loss_max)).floor() as i64;
+    kouku::apply_plane_losses(codex, &mut support.ships, stage1_f_lost);
+    kouku::apply_plane_losses(codex, enemy, stage1_e_lost);
+
+    // Stage 2: enemy anti-air fire at the bombers.
+    let f_count_after_s1 = kouku::total_plane_count(codex, &support.ships);
+    let stage2_f_lost =
+        anti_air::shoot_down(codex, rng, &mut support.ships, enemy, enemy_formation_id, None);
+
+    // Stage 3: each attacking slot strikes one target.
+    let len = enemy.len() + enemy_slots.1;
+    let mut api_erai_flag = vec![0; len];
+    let mut api_ebak_flag = vec![0; len];
+    let mut api_ecl_flag = vec![0; len];
+    let mut api_edam = vec![0; len];
+    for ship in &support.ships {
+        for (_, mst, onslot) in support_planes(codex, ship) {
+            let (stat, eligible): (i64, fn(&Codex, &BattleRuntimeShip) -> bool) = match kind {
+                SupportType::Asw if is_asw_support_plane(mst) => {
+                    (mst.api_tais, |codex, s| target_class(codex, s).is_submarine())
+                }
+                SupportType::Air if is_airstrike_attack_type(mst.api_type[2]) => {
+                    let is_torpedo = KcSlotItemType3::n(mst.api_type[2])
+                        == Some(KcSlotItemType3::CarrierBasedTorpedoBomber);
+                    let stat = if is_torpedo {
+                        mst.api_raig
+                    } else {
+                        mst.api_baku
+                    };
+                    (stat, |codex, s| target_class(codex, s).is_surface_like())
+                }
+                _ => continue,
+            };
+            let Some(target) = pick_target(rng, enemy, |s| eligible(codex, s)) else {
+                continue;
+            };
+            let hit = roll_squadron_hit(rng, mst.api_houm, &enemy[target], enemy_formation_id);
+            let basic_power = (stat.max(0) as f64 * (onslot as f64).sqrt() + AIR_POWER_BONUS)
+                * AIR_POWER_MODIFIER;
+            let damage = support_damage(rng, basic_power, &enemy[target], hit);
+            let (raw, dealt) = enemy[target].apply_damage(rng, damage, target);
+            let slot = enemy_slot(target, enemy_slots);
+            api_edam[slot] += display_damage(&enemy[target], raw, dealt);
+            if KcSlotItemType3::n(mst.api_type[2])
+                == Some(KcSlotItemType3::CarrierBasedTorpedoBomber)
+            {
+                api_erai_flag[slot] = 1;
+            } else {
+                api_ebak_flag[slot] = 1;
+            }
+            if hit.is_critical() {
+                api_ecl_flag[slot] = 1;
+            }
+        }
+    }
+
+    BattleSupportAirAttack {
+        api_deck_id: support.deck_id,
+        api_ship_id: ship_ids(support),
+        api_undressing_flag: undressing_flags(support),
+        api_stage_flag: [1, 1, 1],
+        api_plane_from,
+        api_stage1: BattleKoukuStage1 {
+            api_f_count: f_count,
+            api_f_lostcount: stage1_f_lost,
+            api_e_count: e_count,
+            api_e_lostcount: stage1_e_lost,
+            api_disp_seiku: air_state.api_disp_seiku(),
+            api_touch_plane: [-1, -1],
+        },
+        api_stage2: BattleKoukuStage2 {
+            api_f_count: f_count_after_s1,
+            api_f_lostcount: stage2_f_lost,
+            api_e_count: 0,
+            api_e_lostcount: 0,
+            api_air_fire: None,
+        },
+        api_stage3: BattleAirBaseStage3 {
+            api_erai_flag,
+            api_ebak_flag,
+            api_ecl_flag,
+            api_edam,
+            api_e_sp_list: vec![None; len],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_api_ship;
+    use crate::types::BattleShipInput;
+
+    fn ship_with_cond(cond: i64) -> BattleRuntimeShip {
+        let mut ship = test_api_ship(30, 30);
+        ship.api_cond = cond;
+        BattleRuntimeShip::new(
+            BattleShipInput {
+                ship,
+                slot_items: vec![],
+                effect_list: vec![],
+                married: false,
+            },
+            true,
+            true,
+        )
+    }
+
+    #[test]
+    fn arrival_rate_rises_with_sparkled_ships() {
+        let plain = vec![ship_with_cond(49); 6];
+        assert_eq!(arrival_rate(&plain, false), NODE_SUPPORT_RATE);
+        assert_eq!(arrival_rate(&plain, true), BOSS_SUPPORT_RATE);
+
+        let mut sparkled_flagship = plain.clone();
+        sparkled_flagship[0] = ship_with_cond(53);
+        assert_eq!(arrival_rate(&sparkled_flagship, false), 65);
+
+        let all_sparkled = vec![ship_with_cond(85); 6];
+        assert_eq!(arrival_rate(&all_sparkled, false), 90);
+        assert_eq!(arrival_rate(&all_sparkled, true), 100);
+    }
+
+    #[test]
+    fn escort_slots_skip_the_short_main_fleet_gap() {
+        assert_eq!(enemy_slot(3, (4, 2)), 3);
+        assert_eq!(enemy_slot(4, (4, 2)), 6);
+        assert_eq!(enemy_slot(5, (6, 0)), 5);
+    }
+}
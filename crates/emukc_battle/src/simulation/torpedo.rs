@@ -2,6 +2,7 @@
 
 use emukc_model::codex::Codex;
 
+use crate::accuracy::{AttackKind, HitCheck, roll_hit};
 use crate::damage::calculate_torpedo_damage;
 use crate::random::BattleRng;
 use crate::targeting::{
@@ -13,104 +14,185 @@ use crate::types::{
     TorpedoAttackerSide, TorpedoHit,
 };
 
-/// Simulate the opening torpedo phase.
-pub(crate) fn simulate_opening_torpedo(
-    codex: &Codex,
-    rng: &mut impl BattleRng,
-    friendly: &mut [BattleRuntimeShip],
-    enemy: &mut [BattleRuntimeShip],
-    friendly_formation_id: i64,
-    enemy_formation_id: i64,
-    engagement: EngagementType,
-) -> Option<BattleOpeningAttack> {
-    let fleet_size = friendly.len().max(enemy.len());
-    let mut payload = BattleOpeningAttack::blank(fleet_size);
-    let mut happened = false;
+/// One direction of a torpedo phase: a fleet (slice) firing at the ships it
+/// may target.
+pub(crate) struct TorpedoSalvo {
+    pub side: TorpedoAttackerSide,
+    /// Position of the attacker slice within its side's payload slots.
+    pub attacker_offset: usize,
+    /// Position of the defender slice within its side's payload slots.
+    pub defender_offset: usize,
+    pub attacker_formation_id: i64,
+    pub defender_formation_id: i64,
+    pub engagement: EngagementType,
+}
 
-    for (idx, ship) in friendly.iter_mut().enumerate() {
-        if !can_opening_torpedo_ship(codex, ship) {
-            continue;
-        }
-        let Some(mut target_idx) =
-            select_random_target_index(codex, rng, ship, enemy, BattlePhase::OpeningTorpedo)
-        else {
-            continue;
-        };
-        // 旗艦援護 (かばう): a healthy escort may intercept a flagship-targeted hit.
-        let shield = match select_escort_shield(codex, rng, enemy, target_idx, enemy_formation_id) {
-            Some(escort) => {
-                target_idx = escort;
-                true
-            }
-            None => false,
-        };
-        let raw = calculate_torpedo_damage(
-            codex,
-            rng,
-            ship,
-            &enemy[target_idx],
-            friendly_formation_id,
+impl TorpedoSalvo {
+    /// Single-fleet salvo: both slices start at slot 0.
+    fn single(
+        side: TorpedoAttackerSide,
+        attacker_formation_id: i64,
+        defender_formation_id: i64,
+        engagement: EngagementType,
+    ) -> Self {
+        Self {
+            side,
+            attacker_offset: 0,
+            defender_offset: 0,
+            attacker_formation_id,
+            defender_formation_id,
             engagement,
-            BattlePhase::OpeningTorpedo,
-        );
-        let (raw_dmg, dealt) = enemy[target_idx].apply_damage(rng, raw, target_idx);
-        ship.damage_dealt += dealt;
-        let display = crate::targeting::display_damage(&enemy[target_idx], raw_dmg, dealt);
-        payload.record_torpedo_hit(
-            TorpedoAttackerSide::Friendly,
-            TorpedoHit {
-                attacker_index: idx,
-                defender_index: target_idx,
-                damage: display,
-                shield,
-            },
-        );
-        happened = true;
+        }
     }
+}
 
-    for (idx, ship) in enemy.iter_mut().enumerate() {
-        if !can_opening_torpedo_ship(codex, ship) {
+/// Fire one side's torpedoes in `phase`, recording each hit through `record`.
+/// Returns whether any ship fired.
+fn fire_salvo(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    attackers: &mut [BattleRuntimeShip],
+    defenders: &mut [BattleRuntimeShip],
+    salvo: &TorpedoSalvo,
+    phase: BattlePhase,
+    mut record: impl FnMut(TorpedoHit),
+) -> bool {
+    let mut happened = false;
+    for (idx, ship) in attackers.iter_mut().enumerate() {
+        let can_fire = if phase == BattlePhase::OpeningTorpedo {
+            can_opening_torpedo_ship(codex, ship)
+        } else {
+            can_closing_torpedo_ship(codex, ship)
+        };
+        if !can_fire {
             continue;
         }
-        let Some(mut target_idx) =
-            select_random_target_index(codex, rng, ship, friendly, BattlePhase::OpeningTorpedo)
+        let Some(mut target_idx) = select_random_target_index(codex, rng, ship, defenders, phase)
         else {
             continue;
         };
-        // 旗艦援護 (かばう): a healthy escort may intercept a flagship-targeted hit.
-        let shield =
-            match select_escort_shield(codex, rng, friendly, target_idx, friendly_formation_id) {
+        // 旗艦援護 (かばう): a healthy escort may intercept a flagship-targeted
+        // hit. Closing torpedo is out of scope for かばう.
+        let shield = if phase == BattlePhase::OpeningTorpedo {
+            match select_escort_shield(
+                codex,
+                rng,
+                defenders,
+                target_idx,
+                salvo.defender_formation_id,
+            ) {
                 Some(escort) => {
                     target_idx = escort;
                     true
                 }
                 None => false,
-            };
+            }
+        } else {
+            false
+        };
+        let hit = roll_hit(
+            codex,
+            rng,
+            ship,
+            &defenders[target_idx],
+            &HitCheck::new(
+                AttackKind::Torpedo,
+                salvo.attacker_formation_id,
+                salvo.defender_formation_id,
+            ),
+        );
         let raw = calculate_torpedo_damage(
             codex,
             rng,
             ship,
-            &friendly[target_idx],
-            enemy_formation_id,
-            engagement,
-            BattlePhase::OpeningTorpedo,
+            &defenders[target_idx],
+            salvo.attacker_formation_id,
+            salvo.engagement,
+            phase,
+            hit,
         );
-        let (raw_dmg, dealt) = friendly[target_idx].apply_damage(rng, raw, target_idx);
+        let (raw_dmg, dealt) = defenders[target_idx].apply_damage(rng, raw, target_idx);
         ship.damage_dealt += dealt;
-        let display = crate::targeting::display_damage(&friendly[target_idx], raw_dmg, dealt);
-        payload.record_torpedo_hit(
-            TorpedoAttackerSide::Enemy,
-            TorpedoHit {
-                attacker_index: idx,
-                defender_index: target_idx,
-                damage: display,
-                shield,
-            },
-        );
+        let display = crate::targeting::display_damage(&defenders[target_idx], raw_dmg, dealt);
+        record(TorpedoHit {
+            attacker_index: salvo.attacker_offset + idx,
+            defender_index: salvo.defender_offset + target_idx,
+            damage: display,
+            hit,
+            shield,
+        });
         happened = true;
     }
+    happened
+}
 
-    happened.then_some(payload)
+/// Fire one side's opening torpedoes into a shared payload.
+pub(crate) fn opening_torpedo_salvo(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    attackers: &mut [BattleRuntimeShip],
+    defenders: &mut [BattleRuntimeShip],
+    salvo: &TorpedoSalvo,
+    payload: &mut BattleOpeningAttack,
+) -> bool {
+    fire_salvo(codex, rng, attackers, defenders, salvo, BattlePhase::OpeningTorpedo, |hit| {
+        payload.record_torpedo_hit(salvo.side, hit);
+    })
+}
+
+/// Fire one side's closing torpedoes into a shared payload.
+pub(crate) fn raigeki_salvo(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    attackers: &mut [BattleRuntimeShip],
+    defenders: &mut [BattleRuntimeShip],
+    salvo: &TorpedoSalvo,
+    payload: &mut BattleRaigeki,
+) -> bool {
+    fire_salvo(codex, rng, attackers, defenders, salvo, BattlePhase::ClosingTorpedo, |hit| {
+        payload.record_torpedo_hit(salvo.side, hit);
+    })
+}
+
+/// Simulate the opening torpedo phase.
+pub(crate) fn simulate_opening_torpedo(
+    codex: &Codex,
+    rng: &mut impl BattleRng,
+    friendly: &mut [BattleRuntimeShip],
+    enemy: &mut [BattleRuntimeShip],
+    friendly_formation_id: i64,
+    enemy_formation_id: i64,
+    engagement: EngagementType,
+) -> Option<BattleOpeningAttack> {
+    let fleet_size = friendly.len().max(enemy.len());
+    let mut payload = BattleOpeningAttack::blank(fleet_size);
+    let friendly_fired = opening_torpedo_salvo(
+        codex,
+        rng,
+        friendly,
+        enemy,
+        &TorpedoSalvo::single(
+            TorpedoAttackerSide::Friendly,
+            friendly_formation_id,
+            enemy_formation_id,
+            engagement,
+        ),
+        &mut payload,
+    );
+    let enemy_fired = opening_torpedo_salvo(
+        codex,
+        rng,
+        enemy,
+        friendly,
+        &TorpedoSalvo::single(
+            TorpedoAttackerSide::Enemy,
+            enemy_formation_id,
+            friendly_formation_id,
+            engagement,
+        ),
+        &mut payload,
+    );
+    (friendly_fired || enemy_fired).then_some(payload)
 }
 
 /// Simulate the closing torpedo (raigeki) phase.
@@ -125,75 +207,33 @@ pub(crate) fn simulate_raigeki(
 ) -> Option<BattleRaigeki> {
     let fleet_size = friendly.len().max(enemy.len());
     let mut payload = BattleRaigeki::blank(fleet_size);
-    let mut happened = false;
-
-    for (idx, ship) in friendly.iter_mut().enumerate() {
-        if !can_closing_torpedo_ship(codex, ship) {
-            continue;
-        }
-        let Some(target_idx) =
-            select_random_target_index(codex, rng, ship, enemy, BattlePhase::ClosingTorpedo)
-        else {
-            continue;
-        };
-        let raw = calculate_torpedo_damage(
-            codex,
-            rng,
-            ship,
-            &enemy[target_idx],
-            friendly_formation_id,
-            engagement,
-            BattlePhase::ClosingTorpedo,
-        );
-        let (raw_dmg, dealt) = enemy[target_idx].apply_damage(rng, raw, target_idx);
-        ship.damage_dealt += dealt;
-        let display = crate::targeting::display_damage(&enemy[target_idx], raw_dmg, dealt);
-        payload.record_torpedo_hit(
+    let friendly_fired = raigeki_salvo(
+        codex,
+        rng,
+        friendly,
+        enemy,
+        &TorpedoSalvo::single(
             TorpedoAttackerSide::Friendly,
-            TorpedoHit {
-                attacker_index: idx,
-                defender_index: target_idx,
-                damage: display,
-                shield: false, // closing torpedo is out of scope for かばう
-            },
-        );
-        happened = true;
-    }
-
-    for (idx, ship) in enemy.iter_mut().enumerate() {
-        if !can_closing_torpedo_ship(codex, ship) {
-            continue;
-        }
-        let Some(target_idx) =
-            select_random_target_index(codex, rng, ship, friendly, BattlePhase::ClosingTorpedo)
-        else {
-            continue;
-        };
-        let raw = calculate_torpedo_damage(
-            codex,
-            rng,
-            ship,
-            &friendly[target_idx],
+            friendly_formation_id,
             enemy_formation_id,
             engagement,
-            BattlePhase::ClosingTorpedo,
-        );
-        let (raw_dmg, dealt) = friendly[target_idx].apply_damage(rng, raw, target_idx);
-        ship.damage_dealt += dealt;
-        let display = crate::targeting::display_damage(&friendly[target_idx], raw_dmg, dealt);
-        payload.record_torpedo_hit(
+        ),
+        &mut payload,
+    );
+    let enemy_fired = raigeki_salvo(
+        codex,
+        rng,
+        enemy,
+        friendly,
+        &TorpedoSalvo::single(
             TorpedoAttackerSide::Enemy,
-            TorpedoHit {
-                attacker_index: idx,
-                defender_index: target_idx,
-                damage: display,
-                shield: false, // closing torpedo is out of scope for かばう
-            },
-        );
-        happened = true;
-    }
-
-    happened.then_some(payload)
+            enemy_formation_id,
+            friendly_formation_id,
+            engagement,
+        ),
+        &mut payload,
+    );
+    (friendly_fired || enemy_fired).then_some(payload)
 }
 
 #[cfg(test)]
@@ -227,6 +267,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![dd, clt],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut crate::random::SeededRng::new(1),
         );
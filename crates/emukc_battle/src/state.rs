@@ -1,9 +1,14 @@
 use crate::outcome::{calculate_mvp, calculate_win_rank, verify_protected_ships_alive};
+use crate::simulation::friendly::FriendlyPhase;
+use crate::simulation::support::SupportFleet;
 use crate::targeting::any_alive;
+use std::ops::Range;
+
 use crate::types::{
-    BattleContext, BattleHougeki, BattleKouku, BattleOpeningAttack, BattleOutcome, BattlePacket,
-    BattleRaigeki, BattleRuntimeShip, BattleSimulation, BattleType, NightBattlePacket,
-    NightBattleSimulation,
+    BattleAirBaseAttack, BattleAirBaseWave, BattleContext, BattleHougeki, BattleKouku,
+    BattleOpeningAttack, BattleOutcome, BattlePacket, BattleRaigeki, BattleRuntimeShip,
+    BattleShipInput, BattleSimulation, BattleSupportInfo, BattleType, CombinedFleetType,
+    ESCORT_SLOT_OFFSET, NightBattlePacket, NightBattleSimulation, enemy_combined_power_bonus,
 };
 
 /// All mutable state for a single battle simulation.
@@ -14,20 +19,35 @@ use crate::types::{
 ///
 /// `friendly` and `enemy` are `pub(crate)` because every phase function needs
 /// `&mut` access to them. All other fields are private with setters.
+///
+/// In a combined-fleet battle each side holds its main fleet followed by its
+/// escort fleet; `*_main_len` marks where the escort starts.
 pub(crate) struct BattleState {
     pub(crate) friendly: Vec<BattleRuntimeShip>,
     pub(crate) enemy: Vec<BattleRuntimeShip>,
+    friendly_main_len: usize,
+    enemy_main_len: usize,
+    friendly_type: Option<CombinedFleetType>,
 
     battle_type: BattleType,
     friendly_formation_id: i64,
     enemy_formation_id: i64,
     engagement: super::types::EngagementType,
 
+    /// Land-based air waves; `pub(crate)` so the phase can update plane counts.
+    pub(crate) air_base_waves: Vec<BattleAirBaseWave>,
+    /// Support expedition fleet; `pub(crate)` so the phase can update plane counts.
+    pub(crate) support: Option<SupportFleet>,
+    air_base_attack: Vec<BattleAirBaseAttack>,
     kouku: Option<BattleKouku>,
+    support_flag: i64,
+    support_info: Option<BattleSupportInfo>,
+    friendly_phase: Option<FriendlyPhase>,
     opening_attack: Option<BattleOpeningAttack>,
     opening_taisen: Option<BattleHougeki>,
     hougeki1: Option<BattleHougeki>,
     hougeki2: Option<BattleHougeki>,
+    hougeki3: Option<BattleHougeki>,
     raigeki: Option<BattleRaigeki>,
 
     stage_flag: [i64; 3],
@@ -40,29 +60,66 @@ impl BattleState {
     /// Build initial state from a battle context.
     pub fn from_context(context: BattleContext) -> Self {
         let is_sortie = context.is_sortie;
-        let friendly = context
-            .friend_ships
-            .into_iter()
-            .map(|s| BattleRuntimeShip::new(s, true, is_sortie))
-            .collect::<Vec<_>>();
-        let enemy = context
-            .enemy_ships
-            .into_iter()
-            .map(|s| BattleRuntimeShip::new(s, false, is_sortie))
-            .collect::<Vec<_>>();
+        let (friendly_type, friend_escort, enemy_escort) = match context.combined {
+            Some(combined) => {
+                let friendly_type =
+                    combined.friendly_type.filter(|_| !combined.friend_escort_ships.is_empty());
+                let friend_escort = if friendly_type.is_some() {
+                    combined.friend_escort_ships
+                } else {
+                    Vec::new()
+                };
+                (friendly_type, friend_escort, combined.enemy_escort_ships)
+            }
+            None => (None, Vec::new(), Vec::new()),
+        };
+        let enemy_combined = !enemy_escort.is_empty();
+        let friendly_main_len = context.friend_ships.len();
+        let enemy_main_len = context.enemy_ships.len();
+
+        let friendly =
+            runtime_fleet(context.friend_ships, friend_escort, true, is_sortie, |is_escort| {
+                friendly_type.map_or(0, |ty| ty.shelling_power_bonus(is_escort, enemy_combined))
+            });
+        let enemy =
+            runtime_fleet(context.enemy_ships, enemy_escort, false, is_sortie, |is_escort| {
+                if enemy_combined {
+                    enemy_combined_power_bonus(is_escort)
+                } else {
+                    0
+                }
+            });
 
         Self {
             friendly,
             enemy,
+            friendly_main_len,
+            enemy_main_len,
+            friendly_type,
             battle_type: context.battle_type,
             friendly_formation_id: context.friendly_formation_id,
             enemy_formation_id: context.enemy_formation_id,
             engagement: context.engagement,
+            air_base_waves: context.air_base_waves,
+            support: context.support.map(|support| SupportFleet {
+                deck_id: support.deck_id,
+                ships: support
+                    .ships
+                    .into_iter()
+                    .map(|input| BattleRuntimeShip::new(input, true, is_sortie))
+                    .collect(),
+                boss: support.boss,
+            }),
+            air_base_attack: Vec::new(),
             kouku: None,
+            support_flag: 0,
+            support_info: None,
+            friendly_phase: None,
             opening_attack: None,
             opening_taisen: None,
             hougeki1: None,
             hougeki2: None,
+            hougeki3: None,
             raigeki: None,
             stage_flag: [0, 0, 0],
             hourai_flag: [0, 0, 0, 0],
@@ -75,24 +132,45 @@ impl BattleState {
     /// Avoids the full `BattleContext` → runtime-ship pipeline when the ships
     /// have already been mutated by day battle phases.
     pub fn for_night(
-        friendly: Vec<BattleRuntimeShip>,
-        enemy: Vec<BattleRuntimeShip>,
+        (mut friendly, friendly_escort): (Vec<BattleRuntimeShip>, Vec<BattleRuntimeShip>),
+        (mut enemy, enemy_escort): (Vec<BattleRuntimeShip>, Vec<BattleRuntimeShip>),
         friendly_formation_id: i64,
         enemy_formation_id: i64,
         engagement: super::types::EngagementType,
     ) -> Self {
+        let friendly_main_len = friendly.len();
+        let enemy_main_len = enemy.len();
+        friendly.extend(friendly_escort);
+        enemy.extend(enemy_escort);
+        // Escorts built outside a day battle (`sp_midnight`) lack the flag.
+        for (fleet, main_len) in [(&mut friendly, friendly_main_len), (&mut enemy, enemy_main_len)]
+        {
+            if let Some(flagship) = fleet.get_mut(main_len) {
+                flagship.is_escort_flagship = true;
+            }
+        }
         Self {
             friendly,
             enemy,
+            friendly_main_len,
+            enemy_main_len,
+            friendly_type: None,
             battle_type: BattleType::Normal,
             friendly_formation_id,
             enemy_formation_id,
             engagement,
+            air_base_waves: Vec::new(),
+            support: None,
+            air_base_attack: Vec::new(),
             kouku: None,
+            support_flag: 0,
+            support_info: None,
+            friendly_phase: None,
             opening_attack: None,
             opening_taisen: None,
             hougeki1: None,
             hougeki2: None,
+            hougeki3: None,
             raigeki: None,
             stage_flag: [0, 0, 0],
             hourai_flag: [0, 0, 0, 0],
@@ -119,12 +197,82 @@ impl BattleState {
         self.engagement
     }
 
+    pub(crate) fn friendly_type(&self) -> Option<CombinedFleetType> {
+        self.friendly_type
+    }
+
+    /// Whether either side fields an escort fleet.
+    pub(crate) fn is_combined(&self) -> bool {
+        self.friendly_main_len < self.friendly.len() || self.enemy_main_len < self.enemy.len()
+    }
+
+    pub(crate) fn is_enemy_combined(&self) -> bool {
+        self.enemy_main_len < self.enemy.len()
+    }
+
+    pub(crate) fn friendly_main(&self) -> Range<usize> {
+        0..self.friendly_main_len
+    }
+
+    pub(crate) fn enemy_main(&self) -> Range<usize> {
+        0..self.enemy_main_len
+    }
+
+    /// The friendly escort, or the main fleet when the player has no escort.
+    /// This fleet fires torpedoes and fights the escort shelling phase.
+    pub(crate) fn friendly_escort_or_main(&self) -> Range<usize> {
+        if self.friendly_main_len < self.friendly.len() {
+            self.friendly_main_len..self.friendly.len()
+        } else {
+            self.friendly_main()
+        }
+    }
+
+    /// The enemy escort, or the enemy main fleet when it has no escort.
+    pub(crate) fn enemy_escort_or_main(&self) -> Range<usize> {
+        if self.enemy_main_len < self.enemy.len() {
+            self.enemy_main_len..self.enemy.len()
+        } else {
+            self.enemy_main()
+        }
+    }
+
+    /// Each side's main fleet size and how many API slots it falls short of
+    /// the escort slots; the gap is 0 for a side without an escort.
+    pub(crate) fn escort_slot_gaps(&self) -> [(usize, usize); 2] {
+        let gap = |main_len: usize, len: usize| {
+            let gap = if main_len < len {
+                ESCORT_SLOT_OFFSET.saturating_sub(main_len)
+            } else {
+                0
+            };
+            (main_len, gap)
+        };
+        [
+            gap(self.friendly_main_len, self.friendly.len()),
+            gap(self.enemy_main_len, self.enemy.len()),
+        ]
+    }
+
     // -- Setters (for phase functions to write outputs) --
 
+    pub(crate) fn push_air_base_attack(&mut self, attack: BattleAirBaseAttack) {
+        self.air_base_attack.push(attack);
+    }
+
     pub(crate) fn set_kouku(&mut self, kouku: BattleKouku) {
         self.kouku = Some(kouku);
     }
 
+    pub(crate) fn set_support(&mut self, flag: i64, info: BattleSupportInfo) {
+        self.support_flag = flag;
+        self.support_info = Some(info);
+    }
+
+    pub(crate) fn set_friendly_phase(&mut self, phase: FriendlyPhase) {
+        self.friendly_phase = Some(phase);
+    }
+
     pub(crate) fn set_opening_attack(&mut self, attack: Option<BattleOpeningAttack>) {
         self.opening_attack = attack;
     }
@@ -145,6 +293,10 @@ impl BattleState {
         self.hougeki2 = hougeki;
     }
 
+    pub(crate) fn set_hougeki3(&mut self, hougeki: Option<BattleHougeki>) {
+        self.hougeki3 = hougeki;
+    }
+
     pub(crate) fn set_raigeki(&mut self, raigeki: Option<BattleRaigeki>) {
         self.raigeki = raigeki;
     }
@@ -173,21 +325,40 @@ impl BattleState {
     // -- Finalizers --
 
     /// Consume state, verify invariants, produce the day battle simulation result.
-    pub fn finalize_day(self) -> BattleSimulation {
+    pub fn finalize_day(mut self) -> BattleSimulation {
         verify_protected_ships_alive(&self.friendly);
 
         let can_midnight = matches!(self.battle_type, BattleType::Normal | BattleType::AirBattle)
             && any_alive(&self.friendly)
             && any_alive(&self.enemy);
 
+        if self.is_combined()
+            && let Some(kouku) = self.kouku.as_mut()
+        {
+            kouku.api_stage3_combined = Some(
+                kouku.api_stage3.split_off_escorts(self.friendly_main_len, self.enemy_main_len),
+            );
+        }
+
+        let outcome = BattleOutcome {
+            win_rank: calculate_win_rank(&self.friendly, &self.enemy),
+            mvp: calculate_mvp(&self.friendly[..self.friendly_main_len]),
+            can_midnight,
+        };
+
+        let friendly_escort = self.friendly.split_off(self.friendly_main_len);
+        let enemy_escort = self.enemy.split_off(self.enemy_main_len);
         let packet = BattlePacket {
             formation: [
                 self.friendly_formation_id,
                 self.enemy_formation_id,
                 self.engagement.api_id(),
             ],
-            friendly_nowhps: self.friendly.iter().map(|ship| ship.hp().max(0)).collect(),
-            enemy_nowhps: self.enemy.iter().map(|ship| ship.hp().max(0)).collect(),
+            friendly_nowhps: nowhps(&self.friendly),
+            enemy_nowhps: nowhps(&self.enemy),
+            friendly_nowhps_combined: (!friendly_escort.is_empty())
+                .then(|| nowhps(&friendly_escort)),
+            enemy_nowhps_combined: (!enemy_escort.is_empty()).then(|| nowhps(&enemy_escort)),
             smoke_type: 0,
             balloon_cell: 0,
             atoll_cell: 0,
@@ -202,47 +373,60 @@ impl BattleState {
             hourai_flag: self.hourai_flag,
             hougeki1: self.hougeki1,
             hougeki2: self.hougeki2,
-            hougeki3: None,
+            hougeki3: self.hougeki3,
             raigeki: self.raigeki,
-        };
-
-        let outcome = BattleOutcome {
-            win_rank: calculate_win_rank(&self.friendly, &self.enemy),
-            mvp: calculate_mvp(&self.friendly),
-            can_midnight,
+            air_base_attack: (!self.air_base_attack.is_empty()).then_some(self.air_base_attack),
+            support_flag: self.support_flag,
+            support_info: self.support_info,
         };
 
         BattleSimulation {
             friendly: self.friendly,
             enemy: self.enemy,
+            friendly_escort,
+            enemy_escort,
             packet,
             outcome,
+            air_base_waves: self.air_base_waves,
         }
     }
 
     /// Consume state, verify invariants, produce the night battle simulation result.
+    ///
+    /// The HP arrays are the entry values of the night battle; in a
+    /// combined-fleet battle each is the main fleet's array followed by the
+    /// escort's.
     pub fn finalize_night(
-        self,
-        friendly_nowhps: Vec<i64>,
-        friendly_maxhps: Vec<i64>,
-        enemy_nowhps: Vec<i64>,
-        enemy_maxhps: Vec<i64>,
+        mut self,
+        friendly_hps: (Vec<i64>, Vec<i64>),
+        enemy_hps: (Vec<i64>, Vec<i64>),
+        active_deck: [i64; 2],
         hougeki: Option<crate::types::BattleNightHougeki>,
     ) -> NightBattleSimulation {
         verify_protected_ships_alive(&self.friendly);
 
         let outcome = BattleOutcome {
             win_rank: calculate_win_rank(&self.friendly, &self.enemy),
-            mvp: calculate_mvp(&self.friendly),
+            mvp: calculate_mvp(&self.friendly[..self.friendly_main_len]),
             can_midnight: false,
         };
 
-        let packet = NightBattlePacket {
+        let (mut friendly_nowhps, mut friendly_maxhps) = friendly_hps;
+        let (mut enemy_nowhps, mut enemy_maxhps) = enemy_hps;
+        let escort_hps = |hps: &mut Vec<i64>, main_len: usize| {
+            (hps.len() > main_len).then(|| hps.split_off(main_len))
+        };
+        let mut packet = NightBattlePacket {
             formation: [
                 self.friendly_formation_id,
                 self.enemy_formation_id,
                 self.engagement.api_id(),
             ],
+            active_deck,
+            friendly_nowhps_combined: escort_hps(&mut friendly_nowhps, self.friendly_main_len),
+            friendly_maxhps_combined: escort_hps(&mut friendly_maxhps, self.friendly_main_len),
+            enemy_nowhps_combined: escort_hps(&mut enemy_nowhps, self.enemy_main_len),
+            enemy_maxhps_combined: escort_hps(&mut enemy_maxhps, self.enemy_main_len),
             friendly_nowhps,
             friendly_maxhps,
             enemy_nowhps,
@@ -250,13 +434,53 @@ impl BattleState {
             touch_plane: [-1, -1],
             flare_pos: [-1, -1],
             hougeki,
+            friendly_info: None,
+            friendly_kouku: None,
+            friendly_battle: None,
         };
+        if let Some(phase) = self.friendly_phase.take() {
+            packet.friendly_info = Some(phase.info);
+            packet.friendly_kouku = phase.kouku;
+            packet.friendly_battle = phase.battle;
+        }
 
+        let friendly_escort = self.friendly.split_off(self.friendly_main_len);
+        let enemy_escort = self.enemy.split_off(self.enemy_main_len);
         NightBattleSimulation {
             friendly: self.friendly,
             enemy: self.enemy,
+            friendly_escort,
+            enemy_escort,
             packet,
             outcome,
         }
     }
 }
+
+/// Build one side's runtime ships: main fleet, then escort fleet.
+///
+/// `power_bonus` maps "is escort" to the combined-fleet shelling offset.
+fn runtime_fleet(
+    main: Vec<BattleShipInput>,
+    escort: Vec<BattleShipInput>,
+    is_friendly: bool,
+    is_sortie: bool,
+    power_bonus: impl Fn(bool) -> i64,
+) -> Vec<BattleRuntimeShip> {
+    let main_len = main.len();
+    main.into_iter()
+        .chain(escort)
+        .enumerate()
+        .map(|(idx, input)| {
+            let is_escort = idx >= main_len;
+            let mut ship = BattleRuntimeShip::new(input, is_friendly, is_sortie);
+            ship.is_escort_flagship = idx == main_len;
+            ship.combined_power_bonus = power_bonus(is_escort);
+            ship
+        })
+        .collect()
+}
+
+fn nowhps(ships: &[BattleRuntimeShip]) -> Vec<i64> {
+    ships.iter().map(|ship| ship.hp().max(0)).collect()
+}
@@ -144,14 +144,14 @@ pub(crate) fn select_submarine_target(
 /// Interception probability for a defending fleet's formation, as a percentage.
 ///
 /// Rates from the official wiki (`攻撃対象の選択`): 単縦陣 45%, 複縦/梯形/単横 60%,
-/// 輪形/警戒 75%. Combined-fleet formation IDs (11–14) and any unknown ID return
-/// `None` — combined-fleet interception is out of scope until combined sortie
-/// exists, and an unknown formation never intercepts.
+/// 輪形/警戒 75%. Combined-fleet formations follow the single formation they
+/// resemble: 第一/第二警戒航行序列 60%, 第三 75%, 第四 45%. An unknown formation
+/// returns `None` and never intercepts.
 fn escort_shield_rate(formation_id: i64) -> Option<i64> {
     match formation_id {
-        1 => Some(45),         // 単縦陣
-        2 | 4 | 5 => Some(60), // 複縦陣 / 梯形陣 / 単横陣
-        3 | 6 => Some(75),     // 輪形陣 / 警戒陣
+        1 | 14 => Some(45),              // 単縦陣 / 第四警戒航行序列
+        2 | 4 | 5 | 11 | 12 => Some(60), // 複縦陣 / 梯形陣 / 単横陣 / 第一・第二
+        3 | 6 | 13 => Some(75),          // 輪形陣 / 警戒陣 / 第三警戒航行序列
         _ => None,
     }
 }
@@ -1226,10 +1226,11 @@ mod tests {
         assert_eq!(escort_shield_rate(5), Some(60)); // 単横陣
         assert_eq!(escort_shield_rate(3), Some(75)); // 輪形陣
         assert_eq!(escort_shield_rate(6), Some(75)); // 警戒陣
-        // Combined-fleet (11-14) and unknown formations are out of scope.
+        assert_eq!(escort_shield_rate(11), Some(60)); // 第一警戒航行序列
+        assert_eq!(escort_shield_rate(12), Some(60)); // 第二警戒航行序列
+        assert_eq!(escort_shield_rate(13), Some(75)); // 第三警戒航行序列
+        assert_eq!(escort_shield_rate(14), Some(45)); // 第四警戒航行序列
         assert_eq!(escort_shield_rate(0), None);
-        assert_eq!(escort_shield_rate(11), None);
-        assert_eq!(escort_shield_rate(14), None);
     }
 
     /// Covers AE2. Interception fires at the formation rate (単縦 45%, 輪形 75%),
@@ -20,6 +20,7 @@ pub fn render_day_battle(sim: &BattleSimulation) -> String {
     let mut out = String::new();
     let _ = writeln!(out, "== Day Battle ==");
     render_formation(&sim.packet.formation, &mut out);
+    render_historical_bonuses(&sim.friendly, &sim.friendly_escort, &mut out);
 
     if let Some(kouku) = &sim.packet.kouku {
         render_kouku(kouku, &mut out);
@@ -49,8 +50,18 @@ pub fn render_day_battle(sim: &BattleSimulation) -> String {
     let f_max: Vec<i64> = sim.friendly.iter().map(|s| s.ship.api_maxhp).collect();
     let e_before: Vec<i64> = sim.enemy.iter().map(|s| s.entry_hp).collect();
     let e_max: Vec<i64> = sim.enemy.iter().map(|s| s.ship.api_maxhp).collect();
-    render_fleet('F', "friendly", &sim.friendly, &f_before, &f_max, &mut out);
-    render_fleet('E', "enemy", &sim.enemy, &e_before, &e_max, &mut out);
+    render_fleet('F', "friendly", &sim.friendly, 0, &f_before, &f_max, &mut out);
+    render_fleet('E', "enemy", &sim.enemy, 0, &e_before, &e_max, &mut out);
+    for (prefix, label, main, escort) in [
+        ('F', "friendly escort", &sim.friendly, &sim.friendly_escort),
+        ('E', "enemy escort", &sim.enemy, &sim.enemy_escort),
+    ] {
+        if !escort.is_empty() {
+            let before: Vec<i64> = escort.iter().map(|s| s.entry_hp).collect();
+            let max: Vec<i64> = escort.iter().map(|s| s.ship.api_maxhp).collect();
+            render_fleet(prefix, label, escort, main.len(), &before, &max, &mut out);
+        }
+    }
 
     out
 }
@@ -60,6 +71,7 @@ pub fn render_night_battle(sim: &NightBattleSimulation) -> String {
     let mut out = String::new();
     let _ = writeln!(out, "== Night Battle ==");
     render_formation(&sim.packet.formation, &mut out);
+    render_historical_bonuses(&sim.friendly, &sim.friendly_escort, &mut out);
 
     if let Some(h) = &sim.packet.hougeki {
         render_night_hougeki("midnight", h, &mut out);
@@ -73,6 +85,7 @@ pub fn render_night_battle(sim: &NightBattleSimulation) -> String {
         'F',
         "friendly",
         &sim.friendly,
+        0,
         &sim.packet.friendly_nowhps,
         &sim.packet.friendly_maxhps,
         &mut out,
@@ -81,10 +94,35 @@ pub fn render_night_battle(sim: &NightBattleSimulation) -> String {
         'E',
         "enemy",
         &sim.enemy,
+        0,
         &sim.packet.enemy_nowhps,
         &sim.packet.enemy_maxhps,
         &mut out,
     );
+    for (prefix, label, main, escort, nowhps, maxhps) in [
+        (
+            'F',
+            "friendly escort",
+            &sim.friendly,
+            &sim.friendly_escort,
+            &sim.packet.friendly_nowhps_combined,
+            &sim.packet.friendly_maxhps_combined,
+        ),
+        (
+            'E',
+            "enemy escort",
+            &sim.enemy,
+            &sim.enemy_escort,
+            &sim.packet.enemy_nowhps_combined,
+            &sim.packet.enemy_maxhps_combined,
+        ),
+    ] {
+        if !escort.is_empty() {
+            let nowhps = nowhps.as_deref().unwrap_or_default();
+            let maxhps = maxhps.as_deref().unwrap_or_default();
+            render_fleet(prefix, label, escort, main.len(), nowhps, maxhps, &mut out);
+        }
+    }
 
     out
 }
@@ -126,6 +164,37 @@ fn render_formation(formation: &[i64; 3], out: &mut String) {
     );
 }
 
+/// Lists the historical bonuses of the player's ships; nothing when none has one.
+fn render_historical_bonuses(
+    main: &[BattleRuntimeShip],
+    escort: &[BattleRuntimeShip],
+    out: &mut String,
+) {
+    let ships = main.iter().chain(escort).enumerate();
+    if !ships.clone().any(|(_, ship)| !ship.historical_bonuses.is_empty()) {
+        return;
+    }
+    let _ = writeln!(out, "\n[historical bonus]");
+    for (i, ship) in ships {
+        for bonus in &ship.historical_bonuses {
+            let against = if bonus.enemy_ids.is_empty() {
+                String::new()
+            } else {
+                let ids: Vec<String> =
+                    bonus.enemy_ids.iter().map(|id| format!("ship{id}")).collect();
+                format!(" vs {}", ids.join(","))
+            };
+            let _ = writeln!(
+                out,
+                "  F{} ship{}: x{}{against}",
+                i + 1,
+                ship.ship.api_ship_id,
+                bonus.multiplier
+            );
+        }
+    }
+}
+
 fn render_kouku(k: &BattleKouku, out: &mut String) {
     let s1 = &k.api_stage1;
     let _ = writeln!(out, "\n[aerial]");
@@ -144,6 +213,9 @@ fn render_kouku(k: &BattleKouku, out: &mut String) {
         s1.api_e_count - s1.api_e_lostcount,
         s1.api_e_lostcount
     );
+    if let Some(fire) = &k.api_stage2.api_air_fire {
+        let _ = writeln!(out, "  anti-air cut-in: F{} kind {}", fire.api_idx + 1, fire.api_kind);
+    }
     let _ = writeln!(out, "  bombing dmg to enemy: {:?}", k.api_stage3.api_edam);
     let _ = writeln!(out, "  bombing dmg to friendly: {:?}", k.api_stage3.api_fdam);
 }
@@ -313,10 +385,12 @@ fn render_outcome(o: &BattleOutcome, out: &mut String) {
     let _ = writeln!(out, "\nresult: rank {:?}, mvp {mvp}, midnight {midnight}", o.win_rank);
 }
 
+/// `offset` numbers an escort fleet after its main fleet (F7, F8, ...).
 fn render_fleet(
     prefix: char,
     label: &str,
     ships: &[BattleRuntimeShip],
+    offset: usize,
     befores: &[i64],
     maxes: &[i64],
     out: &mut String,
@@ -334,7 +408,7 @@ fn render_fleet(
         let _ = writeln!(
             out,
             "  {prefix}{} ship{}: {before} -> {after} (max {max}){sunk}",
-            i + 1,
+            offset + i + 1,
             ship.ship.api_ship_id
         );
     }
@@ -348,7 +422,7 @@ mod tests {
         BattleKoukuStage1, BattleKoukuStage2, BattleKoukuStage3, BattleOutcome, BattlePacket,
         NightBattlePacket,
     };
-    use emukc_model::kc2::KcSortieResultRank;
+    use emukc_model::{codex::historical_bonus::HistoricalBonus, kc2::KcSortieResultRank};
 
     fn ship(
         master_id: i64,
@@ -401,6 +475,7 @@ mod tests {
                 api_f_lostcount: 0,
                 api_e_count: 0,
                 api_e_lostcount: 0,
+                api_air_fire: None,
             },
             api_stage3: BattleKoukuStage3 {
                 api_frai: vec![],
@@ -418,6 +493,7 @@ mod tests {
                 api_f_sp_list: vec![None],
                 api_e_sp_list: vec![None],
             },
+            api_stage3_combined: None,
         }
     }
 
@@ -426,6 +502,8 @@ mod tests {
             formation: [1, 1, 1],
             friendly_nowhps: vec![80],
             enemy_nowhps: vec![0],
+            friendly_nowhps_combined: None,
+            enemy_nowhps_combined: None,
             smoke_type: 0,
             balloon_cell: 0,
             atoll_cell: 0,
@@ -451,6 +529,9 @@ mod tests {
                 api_edam: vec![60],
                 api_eydam: vec![DamageCell::Plain(0)],
             }),
+            air_base_attack: None,
+            support_flag: 0,
+            support_info: None,
         }
     }
 
@@ -458,12 +539,15 @@ mod tests {
         BattleSimulation {
             friendly: vec![ship(123, 80, 80, 80, true)],
             enemy: vec![ship(456, 90, 0, 90, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: day_packet(kouku),
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::S,
                 mvp: 1,
                 can_midnight: false,
             },
+            air_base_waves: Vec::new(),
         }
     }
 
@@ -481,15 +565,25 @@ mod tests {
         NightBattleSimulation {
             friendly: vec![ship(123, 80, 80, 80, true)],
             enemy: vec![ship(456, 90, 0, 90, false)],
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             packet: NightBattlePacket {
                 formation: [1, 1, 1],
+                active_deck: [1, 1],
                 friendly_nowhps: vec![80],
                 friendly_maxhps: vec![80],
                 enemy_nowhps: vec![90],
                 enemy_maxhps: vec![90],
+                friendly_nowhps_combined: None,
+                friendly_maxhps_combined: None,
+                enemy_nowhps_combined: None,
+                enemy_maxhps_combined: None,
                 touch_plane: [-1, -1],
                 flare_pos: [-1, -1],
                 hougeki: Some(hougeki),
+                friendly_info: None,
+                friendly_kouku: None,
+                friendly_battle: None,
             },
             outcome: BattleOutcome {
                 win_rank: KcSortieResultRank::S,
@@ -526,6 +620,27 @@ mod tests {
         assert!(text.contains("[shelling 1]"));
     }
 
+    #[test]
+    fn historical_bonuses_are_listed_per_ship() {
+        let mut sim = day_fixture(None);
+        assert!(!render_day_battle(&sim).contains("[historical bonus]"));
+
+        sim.friendly[0].historical_bonuses = vec![HistoricalBonus {
+            cells: vec![],
+            enemy_ids: vec![456, 457],
+            ship_ids: vec![123],
+            ship_classes: vec![],
+            equipment: vec![],
+            multiplier: 1.25,
+        }];
+        let text = render_day_battle(&sim);
+        assert!(text.contains("[historical bonus]"), "bonus section missing:\n{text}");
+        assert!(
+            text.contains("F1 ship123: x1.25 vs ship456,ship457"),
+            "bonus line missing:\n{text}"
+        );
+    }
+
     #[test]
     fn night_battle_renders_midnight_and_cutin() {
         let text = render_night_battle(&night_fixture());
@@ -1,5 +1,8 @@
 //! Domain enums, value objects, and parameter structs.
-//! No Serialize derivations — these are pure computation types.
+//! No Serialize derivations — these are pure computation types, except
+//! [`AirState`], which a persisted practice session carries.
+
+use crate::accuracy::HitResult;
 
 /// Controls which phases execute in a day battle simulation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +17,60 @@ pub enum BattleType {
     LdShooting,
 }
 
+/// Player combined fleet type (連合艦隊), matching `api_combined_flag`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinedFleetType {
+    /// 空母機動部隊
+    CarrierTaskForce,
+    /// 水上打撃部隊
+    SurfaceTaskForce,
+    /// 輸送護衛部隊
+    TransportEscort,
+}
+
+impl CombinedFleetType {
+    pub const fn api_id(self) -> i64 {
+        match self {
+            Self::CarrierTaskForce => 1,
+            Self::SurfaceTaskForce => 2,
+            Self::TransportEscort => 3,
+        }
+    }
+
+    /// Parse from `KanColle` API `api_combined_flag` (1–3); 0 means a single fleet.
+    pub const fn from_api_id(api_id: i64) -> Option<Self> {
+        match api_id {
+            1 => Some(Self::CarrierTaskForce),
+            2 => Some(Self::SurfaceTaskForce),
+            3 => Some(Self::TransportEscort),
+            _ => None,
+        }
+    }
+
+    /// Shelling basic-power bonus for this fleet type's main or escort fleet,
+    /// replacing the single-fleet `+5` difference.
+    ///
+    /// Table from the wiki (`連合艦隊`): the offsets against a single enemy
+    /// fleet differ from those against an enemy combined fleet.
+    pub(crate) const fn shelling_power_bonus(self, is_escort: bool, enemy_combined: bool) -> i64 {
+        match (self, is_escort, enemy_combined) {
+            (Self::CarrierTaskForce, false, _) | (Self::SurfaceTaskForce, false, true) => 2,
+            (Self::CarrierTaskForce | Self::TransportEscort, true, false)
+            | (Self::SurfaceTaskForce, false, false) => 10,
+            _ => -5,
+        }
+    }
+}
+
+/// Shelling basic-power bonus for an enemy combined fleet's main or escort fleet.
+pub(crate) const fn enemy_combined_power_bonus(is_escort: bool) -> i64 {
+    if is_escort {
+        5
+    } else {
+        10
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EngagementType {
     SameCourse,
@@ -54,7 +111,7 @@ impl EngagementType {
 }
 
 /// Air superiority state after the kouku (aerial combat) phase.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum AirState {
     Supremacy,
     Superiority,
@@ -171,6 +228,8 @@ pub(crate) struct TorpedoHit {
     pub(crate) attacker_index: usize,
     pub(crate) defender_index: usize,
     pub(crate) damage: i64,
+    /// Hit-check result, emitted as the `api_fcl` / `api_ecl` flag.
+    pub(crate) hit: HitResult,
     /// True when an escort intercepted this hit (旗艦援護) — the damage cell is
     /// emitted as `DamageCell::Shielded` so the client plays the shield animation.
     pub(crate) shield: bool,
@@ -195,6 +254,8 @@ pub(crate) struct AirstrikeOutput<'a> {
     pub rai_targets: &'a mut [i64],
     pub bak_flags: &'a mut [i64],
     pub rai_flags: &'a mut [i64],
+    /// Per-target critical flag (`api_*cl_flag`): 0 = miss or hit, 1 = critical.
+    pub cl_flags: &'a mut [i64],
 }
 
 /// Parameters for night battle shelling simulation.
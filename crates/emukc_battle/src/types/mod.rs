@@ -5,25 +5,32 @@ pub(crate) mod packet;
 mod runtime;
 
 // Re-export everything that was previously in types.rs
-pub use domain::{AirState, BattleType, EngagementType};
+pub use domain::{AirState, BattleType, CombinedFleetType, EngagementType};
 pub(crate) use domain::{
     AirstrikeOutput, AttackCapability, BattlePhase, NightBattleParams, ShellingParams, TargetClass,
-    TorpedoAttackerSide, TorpedoHit,
+    TorpedoAttackerSide, TorpedoHit, enemy_combined_power_bonus,
 };
+pub(crate) use packet::ESCORT_SLOT_OFFSET;
 pub use packet::SiListId;
 pub use packet::{
-    BattleHougeki, BattleKouku, BattleKoukuStage1, BattleKoukuStage2, BattleKoukuStage3,
-    BattleNightHougeki, BattleOpeningAttack, BattleRaigeki, DamageCell,
+    BattleAirBaseAttack, BattleAirBaseStage3, BattleAirFire, BattleAirRaid, BattleAirRaidAttack,
+    BattleAirRaidStage3, BattleFriendlyBattle, BattleFriendlyInfo, BattleHougeki, BattleKouku,
+    BattleKoukuStage1, BattleKoukuStage2, BattleKoukuStage3, BattleNightHougeki,
+    BattleOpeningAttack, BattleRaigeki, BattleSquadronPlane, BattleSupportAirAttack,
+    BattleSupportHourai, BattleSupportInfo, DamageCell,
 };
 pub use runtime::{
-    BattleContext, BattleOutcome, BattlePacket, BattleRuntimeShip, BattleShipInput,
-    BattleSimulation, NightBattleInput, NightBattlePacket, NightBattleSimulation,
+    AirRaidInput, AirRaidSimulation, BattleAirBaseSquadron, BattleAirBaseWave, BattleContext,
+    BattleOutcome, BattlePacket, BattleRuntimeShip, BattleShipInput, BattleSimulation,
+    BattleSupportInput, CombinedFleetInput, FriendFleetInput, NightBattleInput, NightBattlePacket,
+    NightBattleSimulation,
 };
 
 #[cfg(test)]
 mod tests {
     use super::domain::*;
     use super::packet::*;
+    use crate::accuracy::HitResult;
     use crate::test_utils::make_test_ship_ctx;
 
     // ── AirState tests ──────────────────────────────────────────────
@@ -94,6 +101,16 @@ mod tests {
         assert_eq!(raw, 999);
     }
 
+    #[test]
+    fn escort_flagship_survives_even_when_taiha() {
+        let mut rng = crate::random::SeededRng::new(42);
+        let mut ship = make_test_ship_ctx(5, 5, 5, 40, true, true);
+        ship.is_escort_flagship = true;
+        // Escort slot 7 of a combined fleet.
+        ship.apply_damage(&mut rng, 999, 6);
+        assert!(ship.hp() >= 1, "escort flagship must always survive");
+    }
+
     #[test]
     fn taiha_advance_ship_can_be_sunk() {
         let mut rng = crate::random::SeededRng::new(42);
@@ -250,6 +267,7 @@ mod tests {
                 attacker_index: 1,
                 defender_index: 0,
                 damage: 21,
+                hit: HitResult::Hit,
                 shield: false,
             },
         );
@@ -259,12 +277,15 @@ mod tests {
                 attacker_index: 0,
                 defender_index: 1,
                 damage: 34,
+                hit: HitResult::Critical,
                 shield: false,
             },
         );
         let opening = payload;
 
         assert_eq!(opening.api_frai_list_items[1], Some(vec![0]));
+        assert_eq!(opening.api_fcl_list_items[1], Some(vec![1]));
+        assert_eq!(opening.api_ecl_list_items[0], Some(vec![2]));
         assert_eq!(opening.api_fydam_list_items[1], Some(vec![DamageCell::Plain(21)]));
         assert_eq!(opening.api_eydam_list_items[1], None);
         assert_eq!(opening.api_edam[0], 21);
@@ -283,6 +304,7 @@ mod tests {
                 attacker_index: 1,
                 defender_index: 0,
                 damage: 21,
+                hit: HitResult::Hit,
                 shield: false,
             },
         );
@@ -292,12 +314,15 @@ mod tests {
                 attacker_index: 0,
                 defender_index: 1,
                 damage: 34,
+                hit: HitResult::Critical,
                 shield: false,
             },
         );
         let raigeki = payload;
 
         assert_eq!(raigeki.api_frai[1], 0);
+        assert_eq!(raigeki.api_fcl[1], 1);
+        assert_eq!(raigeki.api_ecl[0], 2);
         assert_eq!(raigeki.api_fydam[1], DamageCell::Plain(21));
         assert_eq!(raigeki.api_eydam[1], DamageCell::Plain(0));
         assert_eq!(raigeki.api_edam[0], 21);
@@ -1,11 +1,18 @@
-//! API packet types with Serialize derivations.
-//! These directly serialize to JSON for the `KanColle` API response.
+//! API packet types with serde derivations.
+//! These directly serialize to JSON for the `KanColle` API response, and read
+//! back from it when a pending battle is restored.
 
-use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::domain::TorpedoAttackerSide;
 use super::domain::TorpedoHit;
 
+/// API slot of a combined fleet's escort flagship: escort ships are always
+/// addressed as 6–11, however many ships the main fleet has.
+pub(crate) const ESCORT_SLOT_OFFSET: usize = 6;
+
 /// A single equipment ID in `api_si_list`.
 ///
 /// The KC API uses JSON value type to distinguish attack rendering paths:
@@ -14,7 +21,7 @@ use super::domain::TorpedoHit;
 /// The `-1` sentinel (no equipment) is always an integer.
 ///
 /// `#[serde(untagged)]` ensures clean JSON output without type tags.
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum SiListId {
     /// Integer-valued ID (normal attacks, sentinels).
@@ -87,6 +94,20 @@ impl From<i64> for DamageCell {
     }
 }
 
+/// Insert `gap` blank entries at `at`, so entries past the main fleet move to
+/// the escort slots.
+fn insert_gap<T: Clone>(list: &mut Vec<T>, at: usize, gap: usize, blank: T) {
+    let at = at.min(list.len());
+    list.splice(at..at, std::iter::repeat_n(blank, gap));
+}
+
+/// Shift a target index past the main fleet by `gap`.
+fn shift_target(target: &mut i64, main_len: usize, gap: usize) {
+    if *target >= main_len as i64 {
+        *target += gap as i64;
+    }
+}
+
 /// Damage cell for a torpedo hit — `Shielded` when an escort intercepted it.
 fn torpedo_cell(hit: TorpedoHit) -> DamageCell {
     if hit.shield {
@@ -107,15 +128,31 @@ impl Serialize for DamageCell {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl<'de> Deserialize<'de> for DamageCell {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // any fraction marks a shielded hit, as on the client
+        let value = f64::deserialize(deserializer)?;
+        let amount = value.floor();
+        Ok(if value > amount {
+            Self::Shielded(amount as i64)
+        } else {
+            Self::Plain(amount as i64)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleKouku {
     pub api_plane_from: [Vec<i64>; 2],
     pub api_stage1: BattleKoukuStage1,
     pub api_stage2: BattleKoukuStage2,
     pub api_stage3: BattleKoukuStage3,
+    /// Stage 3 against the escort fleets of a combined-fleet battle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_stage3_combined: Option<BattleKoukuStage3>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleKoukuStage1 {
     pub api_f_count: i64,
     pub api_f_lostcount: i64,
@@ -125,15 +162,29 @@ pub struct BattleKoukuStage1 {
     pub api_touch_plane: [i64; 2],
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleKoukuStage2 {
     pub api_f_count: i64,
     pub api_f_lostcount: i64,
     pub api_e_count: i64,
     pub api_e_lostcount: i64,
+    /// Anti-air cut-in fired by the friendly fleet; omitted when none triggered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_air_fire: Option<BattleAirFire>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Anti-air cut-in (対空カットイン) entry of kouku stage 2.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirFire {
+    /// 0-based index of the firing ship.
+    pub api_idx: i64,
+    /// Cut-in kind.
+    pub api_kind: i64,
+    /// Equipment master ids shown in the cut-in animation.
+    pub api_use_items: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleKoukuStage3 {
     pub api_frai: Vec<i64>,
     pub api_erai: Vec<i64>,
@@ -151,7 +202,128 @@ pub struct BattleKoukuStage3 {
     pub api_e_sp_list: Vec<Option<i64>>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl BattleKoukuStage3 {
+    /// Split a stage 3 computed over main + escort fleets: `self` keeps the
+    /// main fleets' entries and the escorts' entries are returned.
+    pub(crate) fn split_off_escorts(
+        &mut self,
+        friendly_main_len: usize,
+        enemy_main_len: usize,
+    ) -> Self {
+        fn tail<T>(list: &mut Vec<T>, at: usize) -> Vec<T> {
+            list.split_off(at.min(list.len()))
+        }
+        Self {
+            api_frai: tail(&mut self.api_frai, friendly_main_len),
+            api_erai: tail(&mut self.api_erai, enemy_main_len),
+            api_fbak: tail(&mut self.api_fbak, friendly_main_len),
+            api_ebak: tail(&mut self.api_ebak, enemy_main_len),
+            api_frai_flag: tail(&mut self.api_frai_flag, friendly_main_len),
+            api_erai_flag: tail(&mut self.api_erai_flag, enemy_main_len),
+            api_fbak_flag: tail(&mut self.api_fbak_flag, friendly_main_len),
+            api_ebak_flag: tail(&mut self.api_ebak_flag, enemy_main_len),
+            api_fcl_flag: tail(&mut self.api_fcl_flag, friendly_main_len),
+            api_ecl_flag: tail(&mut self.api_ecl_flag, enemy_main_len),
+            api_fdam: tail(&mut self.api_fdam, friendly_main_len),
+            api_edam: tail(&mut self.api_edam, enemy_main_len),
+            api_f_sp_list: tail(&mut self.api_f_sp_list, friendly_main_len),
+            api_e_sp_list: tail(&mut self.api_e_sp_list, enemy_main_len),
+        }
+    }
+}
+
+/// Plane count of one squadron as it enters a land-based air wave.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleSquadronPlane {
+    pub api_mst_id: i64,
+    pub api_count: i64,
+}
+
+/// One land-based air wave (`api_air_base_attack` entry).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirBaseAttack {
+    pub api_base_id: i64,
+    pub api_stage_flag: [i64; 3],
+    /// Airbases launch from no ship, so the friendly side is always `null`.
+    pub api_plane_from: [Option<Vec<i64>>; 2],
+    pub api_squadron_plane: Vec<BattleSquadronPlane>,
+    pub api_stage1: BattleKoukuStage1,
+    pub api_stage2: BattleKoukuStage2,
+    pub api_stage3: BattleAirBaseStage3,
+}
+
+/// Stage 3 of a land-based air wave; only the enemy fleet takes damage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirBaseStage3 {
+    pub api_erai_flag: Vec<i64>,
+    pub api_ebak_flag: Vec<i64>,
+    pub api_ecl_flag: Vec<i64>,
+    pub api_edam: Vec<i64>,
+    pub api_e_sp_list: Vec<Option<i64>>,
+}
+
+/// Enemy air raid on the player's airbases (`api_destruction_battle` body).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirRaid {
+    pub api_f_nowhps: Vec<i64>,
+    pub api_f_maxhps: Vec<i64>,
+    pub api_air_base_attack: BattleAirRaidAttack,
+    /// 1: resources lost, 2: resources and planes lost, 3: planes lost, 4: no damage.
+    pub api_lost_kind: i64,
+}
+
+/// Aerial combat of an air raid; the airbases are the friendly side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirRaidAttack {
+    pub api_stage_flag: [i64; 3],
+    pub api_plane_from: [Option<Vec<i64>>; 2],
+    pub api_map_squadron_plane: BTreeMap<String, Vec<BattleSquadronPlane>>,
+    pub api_stage1: BattleKoukuStage1,
+    pub api_stage3: BattleAirRaidStage3,
+}
+
+/// Stage 3 of an air raid; only the airbases take damage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirRaidStage3 {
+    pub api_frai_flag: Vec<i64>,
+    pub api_fbak_flag: Vec<i64>,
+    pub api_fcl_flag: Vec<i64>,
+    pub api_fdam: Vec<i64>,
+}
+
+/// `api_support_info`: exactly one of the two attacks is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleSupportInfo {
+    /// Air (`api_support_flag` 1) and ASW (4) support.
+    pub api_support_airatack: Option<BattleSupportAirAttack>,
+    /// Shelling (2) and long-range torpedo (3) support.
+    pub api_support_hourai: Option<BattleSupportHourai>,
+}
+
+/// Air or ASW support strike; stage 3 only hits the enemy side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleSupportAirAttack {
+    pub api_deck_id: i64,
+    pub api_ship_id: Vec<i64>,
+    pub api_undressing_flag: Vec<i64>,
+    pub api_stage_flag: [i64; 3],
+    pub api_plane_from: [Option<Vec<i64>>; 2],
+    pub api_stage1: BattleKoukuStage1,
+    pub api_stage2: BattleKoukuStage2,
+    pub api_stage3: BattleAirBaseStage3,
+}
+
+/// Shelling or long-range torpedo support, indexed by enemy slot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleSupportHourai {
+    pub api_deck_id: i64,
+    pub api_ship_id: Vec<i64>,
+    pub api_undressing_flag: Vec<i64>,
+    pub api_cl_list: Vec<i64>,
+    pub api_damage: Vec<DamageCell>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleOpeningAttack {
     pub api_frai_list_items: Vec<Option<Vec<i64>>>,
     pub api_fcl_list_items: Vec<Option<Vec<i64>>>,
@@ -186,22 +358,46 @@ impl BattleOpeningAttack {
             TorpedoAttackerSide::Friendly => {
                 self.api_frai_list_items[hit.attacker_index] =
                     Some(vec![hit.defender_index as i64]);
-                self.api_fcl_list_items[hit.attacker_index] = Some(vec![1]);
+                self.api_fcl_list_items[hit.attacker_index] = Some(vec![hit.hit.api_cl()]);
                 self.api_fydam_list_items[hit.attacker_index] = Some(vec![torpedo_cell(hit)]);
                 self.api_edam[hit.defender_index] += hit.damage;
             }
             TorpedoAttackerSide::Enemy => {
                 self.api_erai_list_items[hit.attacker_index] =
                     Some(vec![hit.defender_index as i64]);
-                self.api_ecl_list_items[hit.attacker_index] = Some(vec![1]);
+                self.api_ecl_list_items[hit.attacker_index] = Some(vec![hit.hit.api_cl()]);
                 self.api_eydam_list_items[hit.attacker_index] = Some(vec![torpedo_cell(hit)]);
                 self.api_fdam[hit.defender_index] += hit.damage;
             }
         }
     }
+
+    /// Move escort entries recorded right after a short main fleet to the
+    /// escort slots. Each side is given as its main fleet size and how many
+    /// slots that falls short of [`ESCORT_SLOT_OFFSET`].
+    pub(crate) fn pad_escort_slots(
+        &mut self,
+        (friendly_main_len, friendly_gap): (usize, usize),
+        (enemy_main_len, enemy_gap): (usize, usize),
+    ) {
+        for targets in self.api_frai_list_items.iter_mut().flatten() {
+            targets.iter_mut().for_each(|t| shift_target(t, enemy_main_len, enemy_gap));
+        }
+        for targets in self.api_erai_list_items.iter_mut().flatten() {
+            targets.iter_mut().for_each(|t| shift_target(t, friendly_main_len, friendly_gap));
+        }
+        insert_gap(&mut self.api_frai_list_items, friendly_main_len, friendly_gap, None);
+        insert_gap(&mut self.api_fcl_list_items, friendly_main_len, friendly_gap, None);
+        insert_gap(&mut self.api_fydam_list_items, friendly_main_len, friendly_gap, None);
+        insert_gap(&mut self.api_fdam, friendly_main_len, friendly_gap, 0);
+        insert_gap(&mut self.api_erai_list_items, enemy_main_len, enemy_gap, None);
+        insert_gap(&mut self.api_ecl_list_items, enemy_main_len, enemy_gap, None);
+        insert_gap(&mut self.api_eydam_list_items, enemy_main_len, enemy_gap, None);
+        insert_gap(&mut self.api_edam, enemy_main_len, enemy_gap, 0);
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleHougeki {
     pub api_at_eflag: Vec<i64>,
     pub api_at_list: Vec<i64>,
@@ -212,7 +408,36 @@ pub struct BattleHougeki {
     pub api_damage: Vec<Vec<DamageCell>>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl BattleHougeki {
+    /// Shift ship indices of a hougeki simulated on fleet slices so they
+    /// address the combined 0–11 slots (escorts start at [`ESCORT_SLOT_OFFSET`]).
+    pub(crate) fn offset_indices(&mut self, friendly_offset: usize, enemy_offset: usize) {
+        for (idx, eflag) in self.api_at_eflag.iter().enumerate() {
+            let (at_offset, df_offset) = if *eflag == 0 {
+                (friendly_offset, enemy_offset)
+            } else {
+                (enemy_offset, friendly_offset)
+            };
+            self.api_at_list[idx] += at_offset as i64;
+            for target in &mut self.api_df_list[idx] {
+                *target += df_offset as i64;
+            }
+        }
+    }
+
+    /// Append another hougeki's attacks after this one's.
+    pub(crate) fn append(&mut self, mut other: Self) {
+        self.api_at_eflag.append(&mut other.api_at_eflag);
+        self.api_at_list.append(&mut other.api_at_list);
+        self.api_at_type.append(&mut other.api_at_type);
+        self.api_df_list.append(&mut other.api_df_list);
+        self.api_si_list.append(&mut other.api_si_list);
+        self.api_cl_list.append(&mut other.api_cl_list);
+        self.api_damage.append(&mut other.api_damage);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleNightHougeki {
     pub api_at_eflag: Vec<i64>,
     pub api_at_list: Vec<i64>,
@@ -224,7 +449,51 @@ pub struct BattleNightHougeki {
     pub api_damage: Vec<Vec<DamageCell>>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl BattleNightHougeki {
+    /// Shift ship indices so escort fleets address the combined slots.
+    pub(crate) fn offset_indices(&mut self, friendly_offset: usize, enemy_offset: usize) {
+        for (idx, eflag) in self.api_at_eflag.iter().enumerate() {
+            let (at_offset, df_offset) = if *eflag == 0 {
+                (friendly_offset, enemy_offset)
+            } else {
+                (enemy_offset, friendly_offset)
+            };
+            self.api_at_list[idx] += at_offset as i64;
+            for target in &mut self.api_df_list[idx] {
+                *target += df_offset as i64;
+            }
+        }
+    }
+}
+
+/// Friend fleet (友軍艦隊) joining the night battle (`api_friendly_info`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleFriendlyInfo {
+    pub api_production_type: i64,
+    pub api_ship_id: Vec<i64>,
+    pub api_ship_lv: Vec<i64>,
+    pub api_nowhps: Vec<i64>,
+    pub api_maxhps: Vec<i64>,
+    #[serde(rename = "api_Slot")]
+    pub api_slot: Vec<[i64; 5]>,
+    pub api_slot_ex: Vec<i64>,
+    #[serde(rename = "api_Param")]
+    pub api_param: Vec<[i64; 4]>,
+    pub api_voice_id: Vec<i64>,
+    pub api_voice_p_no: Vec<i64>,
+}
+
+/// Friend fleet night attack ahead of the player's (`api_friendly_battle`).
+///
+/// `api_at_eflag` 0 entries are friend fleet ships; their indices address
+/// `api_friendly_info`, not the player's fleet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleFriendlyBattle {
+    pub api_flare_pos: [i64; 2],
+    pub api_hougeki: BattleNightHougeki,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleRaigeki {
     pub api_frai: Vec<i64>,
     pub api_fcl: Vec<i64>,
@@ -258,18 +527,37 @@ impl BattleRaigeki {
         match attacker_side {
             TorpedoAttackerSide::Friendly => {
                 self.api_frai[hit.attacker_index] = hit.defender_index as i64;
-                self.api_fcl[hit.attacker_index] = 1;
+                self.api_fcl[hit.attacker_index] = hit.hit.api_cl();
                 self.api_fydam[hit.attacker_index] = torpedo_cell(hit);
                 self.api_edam[hit.defender_index] += hit.damage;
             }
             TorpedoAttackerSide::Enemy => {
                 self.api_erai[hit.attacker_index] = hit.defender_index as i64;
-                self.api_ecl[hit.attacker_index] = 1;
+                self.api_ecl[hit.attacker_index] = hit.hit.api_cl();
                 self.api_eydam[hit.attacker_index] = torpedo_cell(hit);
                 self.api_fdam[hit.defender_index] += hit.damage;
             }
         }
     }
+
+    /// Move escort entries to the escort slots, as
+    /// [`BattleOpeningAttack::pad_escort_slots`] does.
+    pub(crate) fn pad_escort_slots(
+        &mut self,
+        (friendly_main_len, friendly_gap): (usize, usize),
+        (enemy_main_len, enemy_gap): (usize, usize),
+    ) {
+        self.api_frai.iter_mut().for_each(|t| shift_target(t, enemy_main_len, enemy_gap));
+        self.api_erai.iter_mut().for_each(|t| shift_target(t, friendly_main_len, friendly_gap));
+        insert_gap(&mut self.api_frai, friendly_main_len, friendly_gap, -1);
+        insert_gap(&mut self.api_fcl, friendly_main_len, friendly_gap, 0);
+        insert_gap(&mut self.api_fdam, friendly_main_len, friendly_gap, 0);
+        insert_gap(&mut self.api_fydam, friendly_main_len, friendly_gap, DamageCell::Plain(0));
+        insert_gap(&mut self.api_erai, enemy_main_len, enemy_gap, -1);
+        insert_gap(&mut self.api_ecl, enemy_main_len, enemy_gap, 0);
+        insert_gap(&mut self.api_edam, enemy_main_len, enemy_gap, 0);
+        insert_gap(&mut self.api_eydam, enemy_main_len, enemy_gap, DamageCell::Plain(0));
+    }
 }
 
 #[cfg(test)]
@@ -282,6 +570,14 @@ mod tests {
         assert_eq!(json, "161");
     }
 
+    #[test]
+    fn damage_cell_roundtrips_through_json() {
+        for cell in [DamageCell::Plain(0), DamageCell::Plain(55), DamageCell::Shielded(198)] {
+            let json = serde_json::to_string(&cell).unwrap();
+            assert_eq!(serde_json::from_str::<DamageCell>(&json).unwrap(), cell);
+        }
+    }
+
     #[test]
     fn damage_cell_plain_serializes_as_json_integer() {
         assert_eq!(serde_json::to_string(&DamageCell::Plain(55)).unwrap(), "55");
@@ -346,6 +642,65 @@ mod tests {
         assert_eq!(ids[1], SiListId::Num(-1));
     }
 
+    #[test]
+    fn hougeki_offset_shifts_attacker_and_target_by_side() {
+        let mut hougeki = BattleHougeki {
+            api_at_eflag: vec![0, 1],
+            api_at_list: vec![0, 2],
+            api_at_type: vec![0, 0],
+            api_df_list: vec![vec![1], vec![3, 3]],
+            api_si_list: vec![vec![SiListId::Num(-1)], vec![SiListId::Num(-1); 2]],
+            api_cl_list: vec![vec![1], vec![1, 0]],
+            api_damage: vec![vec![DamageCell::Plain(5)], vec![DamageCell::Plain(7); 2]],
+        };
+        // Friendly escort (offset 6) against the enemy main fleet (offset 0).
+        hougeki.offset_indices(6, 0);
+
+        assert_eq!(hougeki.api_at_list, vec![6, 2]);
+        assert_eq!(hougeki.api_df_list, vec![vec![1], vec![9, 9]]);
+    }
+
+    #[test]
+    fn raigeki_pad_moves_escort_past_short_main_fleet() {
+        // Friendly: 4-ship main fleet + 2 escorts; enemy: single fleet of 6.
+        let mut raigeki = BattleRaigeki::blank(6);
+        raigeki.api_frai[4] = 2;
+        raigeki.api_fdam[5] = 30;
+        raigeki.api_erai[1] = 5;
+        raigeki.api_erai[3] = 0;
+
+        raigeki.pad_escort_slots((4, ESCORT_SLOT_OFFSET - 4), (6, 0));
+
+        assert_eq!(raigeki.api_frai, vec![-1, -1, -1, -1, -1, -1, 2, -1]);
+        assert_eq!(raigeki.api_fdam[7], 30);
+        assert_eq!(raigeki.api_erai, vec![-1, 7, -1, 0, -1, -1]);
+    }
+
+    #[test]
+    fn kouku_stage3_split_keeps_main_fleet_slots() {
+        let mut stage3 = BattleKoukuStage3 {
+            api_frai: vec![0; 8],
+            api_erai: vec![0; 6],
+            api_fbak: vec![0; 8],
+            api_ebak: vec![0; 6],
+            api_frai_flag: vec![0; 8],
+            api_erai_flag: vec![0; 6],
+            api_fbak_flag: vec![0; 8],
+            api_ebak_flag: vec![0; 6],
+            api_fcl_flag: vec![0; 8],
+            api_ecl_flag: vec![0; 6],
+            api_fdam: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            api_edam: vec![0; 6],
+            api_f_sp_list: vec![None; 8],
+            api_e_sp_list: vec![None; 6],
+        };
+        let escort = stage3.split_off_escorts(6, 6);
+
+        assert_eq!(stage3.api_fdam, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(escort.api_fdam, vec![7, 8]);
+        assert!(escort.api_edam.is_empty(), "single enemy fleet has no escort slots");
+    }
+
     #[test]
     fn hougeki_si_list_mixed_types_serialize_correctly() {
         let hougeki = BattleHougeki {
@@ -383,4 +738,23 @@ mod tests {
         );
         assert!(json.contains("161"), "161 must appear in JSON: {json}");
     }
+
+    #[test]
+    fn hougeki_roundtrips_through_json() {
+        let hougeki = BattleHougeki {
+            api_at_eflag: vec![0, 1],
+            api_at_list: vec![0, 0],
+            api_at_type: vec![7, 0],
+            api_df_list: vec![vec![0], vec![1]],
+            api_si_list: vec![SiListId::text_from_i64(&[22, -1]), SiListId::num_from_i64(&[161])],
+            api_cl_list: vec![vec![1], vec![2]],
+            api_damage: vec![vec![DamageCell::Plain(150)], vec![DamageCell::Shielded(12)]],
+        };
+        let json = serde_json::to_string(&hougeki).unwrap();
+        let back: BattleHougeki = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back.api_si_list, hougeki.api_si_list);
+        assert_eq!(back.api_damage, hougeki.api_damage);
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
 }
@@ -1,11 +1,17 @@
 //! Runtime battle types — ship state, battle context, and simulation output.
 //! These types carry mutable battle state and top-level simulation results.
+//! Those a pending battle holds derive serde so the session can be persisted.
 
-use emukc_model::kc2::{KcApiShip, KcApiSlotItem, KcSortieResultRank};
+use emukc_model::{
+    codex::historical_bonus::HistoricalBonus,
+    kc2::{KcApiShip, KcApiSlotItem, KcSortieResultRank},
+};
+use serde::{Deserialize, Serialize};
 
-use super::domain::{AirState, BattleType, EngagementType};
+use super::domain::{AirState, BattleType, CombinedFleetType, EngagementType};
 use super::packet::{
-    BattleHougeki, BattleKouku, BattleNightHougeki, BattleOpeningAttack, BattleRaigeki,
+    BattleAirBaseAttack, BattleAirRaid, BattleFriendlyBattle, BattleFriendlyInfo, BattleHougeki,
+    BattleKouku, BattleNightHougeki, BattleOpeningAttack, BattleRaigeki, BattleSupportInfo,
 };
 use crate::random::BattleRng;
 
@@ -17,7 +23,7 @@ pub struct BattleShipInput {
     pub married: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleRuntimeShip {
     pub ship: KcApiShip,
     pub slot_items: Vec<KcApiSlotItem>,
@@ -34,6 +40,14 @@ pub struct BattleRuntimeShip {
     /// Sinking protection only applies during sorties.
     pub(crate) is_sortie: bool,
     pub married: bool,
+    /// Flagship of a combined fleet's escort, protected from sinking like the
+    /// main flagship.
+    pub(crate) is_escort_flagship: bool,
+    /// Combined-fleet shelling power offset (連合艦隊補正); 0 for single fleets.
+    pub(crate) combined_power_bonus: i64,
+    /// Historical bonuses (特効) boosting this ship at the battle node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) historical_bonuses: Vec<HistoricalBonus>,
 }
 
 impl BattleRuntimeShip {
@@ -49,6 +63,9 @@ impl BattleRuntimeShip {
             is_friendly,
             is_sortie,
             married: input.married,
+            is_escort_flagship: false,
+            combined_power_bonus: 0,
+            historical_bonuses: Vec::new(),
         }
     }
 
@@ -77,7 +94,8 @@ impl BattleRuntimeShip {
     /// - Friendly ships that were **not** in taiha (HP <= 25% max) at the start of
     ///   the battle node cannot be sunk. Lethal damage is replaced with
     ///   proportional damage: `floor(0.5 * H + 0.3 * rand(0..H))`.
-    /// - The flagship (index 0) can **never** be sunk regardless of HP state.
+    /// - The flagship (index 0) can **never** be sunk regardless of HP state;
+    ///   neither can a combined fleet's escort flagship.
     /// - Protection only applies to friendly ships during sorties (not practice).
     ///
     /// Returns `(raw_damage, effective_damage)` where raw is the input damage
@@ -106,7 +124,7 @@ impl BattleRuntimeShip {
 
         // Sinking protection only applies to friendly ships during sorties.
         if self.is_friendly && self.is_sortie && effective >= self.current_hp {
-            let is_flagship = ship_index == 0;
+            let is_flagship = ship_index == 0 || self.is_escort_flagship;
             // Taiha threshold: HP <= 25% of max at node entry.
             let was_taiha_at_entry = self.entry_hp * 4 <= self.ship.api_maxhp;
             let is_protected = is_flagship || !was_taiha_at_entry;
@@ -143,6 +161,24 @@ impl From<BattleShipInput> for BattleRuntimeShip {
     }
 }
 
+/// One squadron of a land-based air wave.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirBaseSquadron {
+    pub slot_item: KcApiSlotItem,
+    /// Planes in the squadron; the simulation leaves the survivors here.
+    pub count: i64,
+}
+
+/// One wave of a land-based air corps (基地航空隊).
+///
+/// A sortied airbase sends two waves; later waves with the same `base_id`
+/// start from the planes the earlier wave brought back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleAirBaseWave {
+    pub base_id: i64,
+    pub squadrons: Vec<BattleAirBaseSquadron>,
+}
+
 /// Input parameters for a day battle simulation.
 #[derive(Debug, Clone)]
 pub struct BattleContext {
@@ -155,23 +191,84 @@ pub struct BattleContext {
     pub engagement: EngagementType,
     pub friend_ships: Vec<BattleShipInput>,
     pub enemy_ships: Vec<BattleShipInput>,
+    /// Land-based air waves striking this node, in launch order.
+    pub air_base_waves: Vec<BattleAirBaseWave>,
+    /// Escort fleets when either side is a combined fleet.
+    pub combined: Option<CombinedFleetInput>,
+    /// Fleet on a support expedition covering this node.
+    pub support: Option<BattleSupportInput>,
+    /// Historical bonuses (特効) in effect at this node.
+    pub historical_bonuses: Vec<HistoricalBonus>,
+}
+
+/// A fleet sent on a support expedition (支援遠征) to the battle node.
+#[derive(Debug, Clone)]
+pub struct BattleSupportInput {
+    /// Fleet number reported as `api_deck_id`.
+    pub deck_id: i64,
+    pub ships: Vec<BattleShipInput>,
+    /// Boss support (艦隊決戦支援) rather than node support (前衛支援).
+    pub boss: bool,
+}
+
+/// Escort fleets of a combined-fleet battle (連合艦隊戦).
+///
+/// `friend_ships` / `enemy_ships` of the [`BattleContext`] stay the main fleets.
+#[derive(Debug, Clone)]
+pub struct CombinedFleetInput {
+    /// Player combined fleet type; `None` when only the enemy is combined.
+    pub friendly_type: Option<CombinedFleetType>,
+    /// Player escort fleet (第二艦隊); empty for a single player fleet.
+    pub friend_escort_ships: Vec<BattleShipInput>,
+    /// Enemy escort fleet; empty for a single enemy fleet.
+    pub enemy_escort_ships: Vec<BattleShipInput>,
+}
+
+/// Input parameters for [`execute_air_raid`](crate::execute_air_raid).
+#[derive(Debug, Clone)]
+pub struct AirRaidInput {
+    /// Airbases of the raided area; only their squadrons intercept.
+    pub bases: Vec<BattleAirBaseWave>,
+    pub enemy_ships: Vec<BattleShipInput>,
 }
 
 /// Input parameters for [`execute_night`](crate::execute_night).
 pub struct NightBattleInput {
     pub friendly: Vec<BattleRuntimeShip>,
     pub enemy: Vec<BattleRuntimeShip>,
+    /// Player escort fleet; when present it fights the night battle instead
+    /// of the main fleet.
+    pub friendly_escort: Vec<BattleRuntimeShip>,
+    /// Enemy escort fleet; it fights the night battle while any of it floats.
+    pub enemy_escort: Vec<BattleRuntimeShip>,
     pub friendly_formation_id: i64,
     pub enemy_formation_id: i64,
     pub engagement: EngagementType,
     pub air_state: Option<AirState>,
+    /// Friend fleet joining ahead of the player's night attack.
+    pub friend_fleet: Option<FriendFleetInput>,
+    /// Historical bonuses (特効) in effect at this node; when empty the
+    /// player's ships keep those they carry from the day battle.
+    pub historical_bonuses: Vec<HistoricalBonus>,
 }
 
+/// A friend fleet (友軍艦隊) answering the player's request.
 #[derive(Debug, Clone)]
+pub struct FriendFleetInput {
+    /// `api_production_type` of the arrival cut-in.
+    pub production_type: i64,
+    pub ships: Vec<BattleShipInput>,
+    /// Arrival voice per ship, `0` for none.
+    pub voice_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattlePacket {
     pub formation: [i64; 3],
     pub friendly_nowhps: Vec<i64>,
     pub enemy_nowhps: Vec<i64>,
+    pub friendly_nowhps_combined: Option<Vec<i64>>,
+    pub enemy_nowhps_combined: Option<Vec<i64>>,
     pub smoke_type: i64,
     pub balloon_cell: i64,
     pub atoll_cell: i64,
@@ -188,10 +285,15 @@ pub struct BattlePacket {
     pub hougeki2: Option<BattleHougeki>,
     pub hougeki3: Option<BattleHougeki>,
     pub raigeki: Option<BattleRaigeki>,
+    pub air_base_attack: Option<Vec<BattleAirBaseAttack>>,
+    /// `api_support_flag`: 0 = none, 1 = air, 2 = shelling, 3 = long-range
+    /// torpedo, 4 = ASW.
+    pub support_flag: i64,
+    pub support_info: Option<BattleSupportInfo>,
 }
 
 /// Battle result: win rank, MVP ship index, and midnight eligibility.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BattleOutcome {
     pub win_rank: KcSortieResultRank,
     pub mvp: i64,
@@ -202,26 +304,53 @@ pub struct BattleOutcome {
 pub struct BattleSimulation {
     pub friendly: Vec<BattleRuntimeShip>,
     pub enemy: Vec<BattleRuntimeShip>,
+    /// Escort fleets of a combined-fleet battle; empty otherwise.
+    pub friendly_escort: Vec<BattleRuntimeShip>,
+    pub enemy_escort: Vec<BattleRuntimeShip>,
     pub packet: BattlePacket,
     pub outcome: BattleOutcome,
+    /// Land-based air waves with the plane counts each brought back.
+    pub air_base_waves: Vec<BattleAirBaseWave>,
 }
 
 #[derive(Debug, Clone)]
 pub struct NightBattlePacket {
     pub formation: [i64; 3],
+    /// Fleets fighting the night battle (`api_active_deck`): 1 = main, 2 = escort.
+    pub active_deck: [i64; 2],
     pub friendly_nowhps: Vec<i64>,
     pub friendly_maxhps: Vec<i64>,
     pub enemy_nowhps: Vec<i64>,
     pub enemy_maxhps: Vec<i64>,
+    pub friendly_nowhps_combined: Option<Vec<i64>>,
+    pub friendly_maxhps_combined: Option<Vec<i64>>,
+    pub enemy_nowhps_combined: Option<Vec<i64>>,
+    pub enemy_maxhps_combined: Option<Vec<i64>>,
     pub touch_plane: [i64; 2],
     pub flare_pos: [i64; 2],
     pub hougeki: Option<BattleNightHougeki>,
+    pub friendly_info: Option<BattleFriendlyInfo>,
+    /// Friend fleet carriers' air attack (`api_friendly_kouku`).
+    pub friendly_kouku: Option<BattleKouku>,
+    pub friendly_battle: Option<BattleFriendlyBattle>,
 }
 
 #[derive(Debug, Clone)]
 pub struct NightBattleSimulation {
     pub friendly: Vec<BattleRuntimeShip>,
     pub enemy: Vec<BattleRuntimeShip>,
+    pub friendly_escort: Vec<BattleRuntimeShip>,
+    pub enemy_escort: Vec<BattleRuntimeShip>,
     pub packet: NightBattlePacket,
     pub outcome: BattleOutcome,
 }
+
+/// Result of an enemy air raid on the player's airbases.
+#[derive(Debug, Clone)]
+pub struct AirRaidSimulation {
+    pub packet: BattleAirRaid,
+    /// Airbases with the plane counts left after the raid.
+    pub bases: Vec<BattleAirBaseWave>,
+    /// Damage each airbase took, in `bases` order.
+    pub base_damage: Vec<i64>,
+}
@@ -119,6 +119,10 @@ fn day_context(codex: &Codex) -> BattleContext {
         engagement: EngagementType::SameCourse,
         friend_ships: vec![attacker(codex), attacker(codex)],
         enemy_ships: vec![target(codex), target(codex)],
+        air_base_waves: Vec::new(),
+        combined: None,
+        support: None,
+        historical_bonuses: Vec::new(),
     }
 }
 
@@ -132,10 +136,14 @@ fn night_input(codex: &Codex) -> NightBattleInput {
             BattleRuntimeShip::new(target(codex), false, true),
             BattleRuntimeShip::new(target(codex), false, true),
         ],
+        friendly_escort: Vec::new(),
+        enemy_escort: Vec::new(),
         friendly_formation_id: 1,
         enemy_formation_id: 1,
         engagement: EngagementType::SameCourse,
         air_state: None,
+        friend_fleet: None,
+        historical_bonuses: Vec::new(),
     }
 }
 
@@ -1,6 +1,9 @@
 //! Bootstrap database
 
+use std::path::{Path, PathBuf};
+
 use emukc_db::entity::bootstrap;
+use emukc_db::migration::{self, PendingMigration};
 use emukc_db::sea_orm::{self, Database, DbConn};
 use thiserror::Error;
 
@@ -20,7 +23,7 @@ pub enum DbBootstrapError {
     Db(#[from] sea_orm::error::DbErr),
 }
 
-/// Prepare the database
+/// Prepare the database, migrating its schema if needed
 ///
 /// # Arguments
 ///
@@ -42,15 +45,90 @@ pub async fn prepare(
         std::fs::remove_file(path)?;
     }
 
-    let path = clean_db_path(path);
-
-    let sqlite_url = format!("sqlite:{path}?mode=rwc");
-    let db = Database::connect(&sqlite_url).await?;
-    bootstrap(&db).await?;
+    let db = connect_db(path).await?;
+    migrate_db(&db, path, true).await?;
 
     Ok(db)
 }
 
+/// Open the database without touching its schema
+///
+/// # Arguments
+///
+/// * `path` - The path to the database, created if missing
+pub async fn connect_db(path: impl AsRef<Path>) -> Result<DbConn, DbBootstrapError> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        return Err(DbBootstrapError::InvalidPath(path.to_string_lossy().to_string()));
+    }
+
+    let sqlite_url = format!("sqlite:{}?mode=rwc", clean_db_path(path));
+    Ok(Database::connect(&sqlite_url).await?)
+}
+
+/// Outcome of [`migrate_db`]
+#[derive(Debug, Clone, Default)]
+pub struct DbMigrationReport {
+    /// Copy of the database taken before migrating
+    pub backup: Option<PathBuf>,
+
+    /// Migrations that were applied
+    pub applied: Vec<PendingMigration>,
+}
+
+/// Apply pending schema migrations
+///
+/// If existing data is about to change and `backup` is set, a copy of the
+/// database is written next to it first, named after the version it had.
+///
+/// # Arguments
+///
+/// * `db` - The database connection
+/// * `path` - The path to the database
+/// * `backup` - Whether to back the database up before migrating
+pub async fn migrate_db(
+    db: &DbConn,
+    path: impl AsRef<Path>,
+    backup: bool,
+) -> Result<DbMigrationReport, DbBootstrapError> {
+    let status = migration::status(db).await?;
+    let backup = if backup && status.needs_migration() {
+        let backup_path = backup_path(path.as_ref(), status.current.unwrap_or_default());
+        migration::backup(db, &clean_db_path(&backup_path)).await?;
+        info!("database backed up to {}", backup_path.display());
+        Some(backup_path)
+    } else {
+        None
+    };
+
+    bootstrap(db).await?;
+    let applied = if status.fresh {
+        Vec::new()
+    } else {
+        status.pending
+    };
+    for pending in &applied {
+        info!("applied schema migration {} `{}`", pending.version, pending.name);
+    }
+
+    Ok(DbMigrationReport {
+        backup,
+        applied,
+    })
+}
+
+/// `emukc.db` at version 3 becomes `emukc.db.v3-<unix time>.bak`.
+fn backup_path(path: &Path, version: i64) -> PathBuf {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{version}-{stamp}.bak"));
+
+    path.with_file_name(name)
+}
+
 #[cfg(not(target_os = "windows"))]
 fn clean_db_path(path: &std::path::Path) -> String {
     path.to_str().unwrap().to_string()
@@ -60,3 +138,47 @@ fn clean_db_path(path: &std::path::Path) -> String {
 fn clean_db_path(path: &std::path::Path) -> String {
     path.to_str().unwrap().replace("\\\\?\\", "").to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use emukc_db::migration::latest_version;
+    use emukc_db::sea_orm::{ConnectionTrait, Statement};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn legacy_database_is_backed_up_before_migrating() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emukc.db");
+
+        let db = connect_db(&path).await.unwrap();
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"CREATE TABLE "map_record" ("id" integer NOT NULL PRIMARY KEY, "variant_key" text NULL)"#
+                .to_string(),
+        ))
+        .await
+        .unwrap();
+
+        let report = migrate_db(&db, &path, true).await.unwrap();
+        assert_eq!(report.applied.len() as i64, latest_version());
+        let backup = report.backup.unwrap();
+        assert!(backup.exists());
+        assert!(backup.file_name().unwrap().to_string_lossy().starts_with("emukc.db.v0-"));
+
+        let again = migrate_db(&db, &path, true).await.unwrap();
+        assert!(again.applied.is_empty());
+        assert!(again.backup.is_none());
+    }
+
+    #[tokio::test]
+    async fn fresh_database_needs_no_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emukc.db");
+
+        let db = connect_db(&path).await.unwrap();
+        let report = migrate_db(&db, &path, true).await.unwrap();
+        assert!(report.applied.is_empty());
+        assert!(report.backup.is_none());
+    }
+}
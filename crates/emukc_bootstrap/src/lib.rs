@@ -47,7 +47,7 @@ pub mod prelude {
         repo_battle_resource_rules_path, repo_battle_slot_resource_triggers_path,
         validate_day_battle_response, validate_night_battle_response,
     };
-    pub use crate::db::{DbBootstrapError, prepare};
+    pub use crate::db::{DbBootstrapError, DbMigrationReport, connect_db, migrate_db, prepare};
     pub use crate::download::BootstrapDownloadError;
     pub use crate::download::download_all;
     pub use crate::download::download_web_assets;
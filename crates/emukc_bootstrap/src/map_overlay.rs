@@ -135,6 +135,7 @@ mod tests {
                                 node_label: Some("Start".to_string()),
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                             emukc_model::codex::map::MapCellDefinition {
                                 cell_no: 1,
@@ -145,6 +146,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                             emukc_model::codex::map::MapCellDefinition {
                                 cell_no: 2,
@@ -155,6 +157,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                             emukc_model::codex::map::MapCellDefinition {
                                 cell_no: 3,
@@ -165,6 +168,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                         ],
                         routing_rules: BTreeMap::new(),
@@ -173,8 +177,10 @@ mod tests {
                         required_defeat_count: None,
                         clear_to_variant_key: None,
                         parse_warnings: Vec::new(),
+                        boss_debuff: Default::default(),
                     },
                 )]),
+                gimmicks: Vec::new(),
             },
         );
         catalog
@@ -191,6 +197,7 @@ mod tests {
                 node_label: None,
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             }
         }
 
@@ -209,6 +216,7 @@ mod tests {
                 required_defeat_count: None,
                 clear_to_variant_key: clear_to_variant_key.map(ToOwned::to_owned),
                 parse_warnings: Vec::new(),
+                boss_debuff: Default::default(),
             }
         }
 
@@ -238,6 +246,7 @@ mod tests {
                     ),
                     ("post_p_unlock".to_string(), variant("post_p_unlock", 0..=16, None)),
                 ]),
+                gimmicks: Vec::new(),
             },
         );
         catalog
@@ -357,6 +366,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             })
                             .collect(),
                         routing_rules: BTreeMap::new(),
@@ -365,8 +375,10 @@ mod tests {
                         required_defeat_count: None,
                         clear_to_variant_key: None,
                         parse_warnings: Vec::new(),
+                        boss_debuff: Default::default(),
                     },
                 )]),
+                gimmicks: Vec::new(),
             },
         );
         let asset = RealMapStartAsset::new(
@@ -415,6 +427,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             })
                             .collect(),
                         ..Default::default()
@@ -434,12 +447,14 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             })
                             .collect(),
                         ..Default::default()
                     },
                 ),
             ]),
+            gimmicks: Vec::new(),
         };
         let asset = RealMapStartAsset::new(
             "map_7-3.json",
@@ -142,6 +142,7 @@ fn merge_capture_into_overlay(
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         }
     });
 
@@ -191,6 +192,7 @@ fn merge_capture_into_overlay(
                         node_label: None,
                         master_cell_id: Some(captured_cell.master_cell_id),
                         distance: captured_cell.distance,
+                        select_cells: Vec::new(),
                     },
                 );
             }
@@ -238,6 +240,7 @@ mod tests {
                             node_label: Some("Start".to_string()),
                             master_cell_id: None,
                             distance: None,
+                            select_cells: Vec::new(),
                         },
                         emukc_model::codex::map::MapCellDefinition {
                             cell_no: 1,
@@ -248,6 +251,7 @@ mod tests {
                             node_label: None,
                             master_cell_id: None,
                             distance: None,
+                            select_cells: Vec::new(),
                         },
                         emukc_model::codex::map::MapCellDefinition {
                             cell_no: 2,
@@ -258,6 +262,7 @@ mod tests {
                             node_label: None,
                             master_cell_id: None,
                             distance: None,
+                            select_cells: Vec::new(),
                         },
                         emukc_model::codex::map::MapCellDefinition {
                             cell_no: 3,
@@ -268,6 +273,7 @@ mod tests {
                             node_label: None,
                             master_cell_id: None,
                             distance: None,
+                            select_cells: Vec::new(),
                         },
                     ],
                     routing_rules: BTreeMap::new(),
@@ -276,8 +282,10 @@ mod tests {
                     required_defeat_count: None,
                     clear_to_variant_key: None,
                     parse_warnings: Vec::new(),
+                    boss_debuff: Default::default(),
                 },
             )]),
+            gimmicks: Vec::new(),
         }
     }
 
@@ -71,6 +71,13 @@ pub(super) fn assemble_final_map_catalog(
         definition.normalize_p_unlock_variants();
     }
 
+    // Wiki routes the player picks (能動分岐) carry no parseable condition; turn them into
+    // selectable branches so `api_req_map/next` offers the choice instead of rolling.
+    for variant in catalog.maps.values_mut().flat_map(|definition| definition.variants.values_mut())
+    {
+        variant.derive_select_cells();
+    }
+
     let output_map_count = catalog.maps.len();
 
     // Topology validation — warn during bootstrap, not at runtime codex load.
@@ -410,6 +417,7 @@ mod tests {
             node_label: Some(format!("C{cell_no}")),
             master_cell_id: None,
             distance: None,
+            select_cells: Vec::new(),
         }
     }
 
@@ -519,6 +527,7 @@ mod tests {
                 ship_id: 1,
                 raw_ship_name: "Mutsuki".to_string(),
                 tags: Vec::new(),
+                weights: None,
             }],
         )]);
         let wikiwiki = make_catalog(14, vec![wikiwiki_variant]);
@@ -553,6 +562,7 @@ mod tests {
                 ship_id: 1,
                 raw_ship_name: "Mutsuki".to_string(),
                 tags: Vec::new(),
+                weights: None,
             }],
         )]);
         let wikiwiki = make_catalog(14, vec![wikiwiki_variant]);
@@ -170,6 +170,7 @@ fn build_variant_from_kcdata(data: &KcDataMapData) -> MapVariantDefinition {
             node_label,
             master_cell_id: None,
             distance: None,
+            select_cells: Vec::new(),
         });
     }
 
@@ -183,6 +184,7 @@ fn build_variant_from_kcdata(data: &KcDataMapData) -> MapVariantDefinition {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     }
 }
 
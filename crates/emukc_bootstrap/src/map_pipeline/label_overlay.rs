@@ -306,6 +306,7 @@ mod tests {
             },
             master_cell_id: None,
             distance: None,
+            select_cells: Vec::new(),
         }
     }
 
@@ -734,6 +735,7 @@ mod tests {
                     node_label: None, // unlabeled
                     master_cell_id: None,
                     distance: None,
+                    select_cells: Vec::new(),
                 },
             ],
             routing_rules: BTreeMap::from([(
@@ -879,6 +881,7 @@ mod tests {
                     node_label: None,
                     master_cell_id: None,
                     distance: None,
+                    select_cells: Vec::new(),
                 },
             ],
             enemy_fleets: BTreeMap::from([(5, make_fleet(5))]),
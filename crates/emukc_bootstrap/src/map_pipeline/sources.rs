@@ -258,6 +258,7 @@ fn parse_stat_json(raw: &str) -> Result<MapCatalog, String> {
                 node_label: Some(label),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             });
         }
 
@@ -274,6 +275,7 @@ fn parse_stat_json(raw: &str) -> Result<MapCatalog, String> {
                 required_defeat_count: None,
                 clear_to_variant_key: None,
                 parse_warnings: Vec::new(),
+                boss_debuff: Default::default(),
             },
         )]
         .into_iter()
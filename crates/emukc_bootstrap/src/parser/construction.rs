@@ -0,0 +1,31 @@
+use emukc_model::codex::construction::ConstructionTable;
+
+use super::error::ParseError;
+
+const EMBEDDED_CONSTRUCTION_TABLE_JSON: &str = include_str!("../../assets/construction_table.json");
+
+pub fn get() -> Result<ConstructionTable, ParseError> {
+    let table: ConstructionTable = serde_json::from_str(EMBEDDED_CONSTRUCTION_TABLE_JSON)?;
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_table_gates_big_ships_behind_recipes() {
+        let table = get().unwrap();
+        assert!(!table.normal.type_rules.is_empty());
+        assert!(!table.large.type_rules.is_empty());
+
+        // Battleships need more than the minimum normal recipe.
+        let battleship =
+            table.normal.type_rules.iter().find(|rule| rule.stypes.contains(&9)).unwrap();
+        assert!(battleship.min_recipe.iter().any(|min| *min > 30));
+
+        // The Yamato class is gated behind a dedicated large construction rule.
+        assert!(table.large.ship_rules.iter().any(|rule| rule.ship_id == 131));
+    }
+}
@@ -0,0 +1,28 @@
+use emukc_model::codex::development::DevelopmentTable;
+
+use super::error::ParseError;
+
+const EMBEDDED_DEVELOPMENT_TABLE_JSON: &str = include_str!("../../assets/development_table.json");
+
+pub fn get() -> Result<DevelopmentTable, ParseError> {
+    let table: DevelopmentTable = serde_json::from_str(EMBEDDED_DEVELOPMENT_TABLE_JSON)?;
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_table_pools_fit_weight_total() {
+        use emukc_model::codex::development::DEVELOPMENT_WEIGHT_TOTAL;
+
+        let table = get().unwrap();
+        assert!(!table.pools.is_empty());
+        for pool in &table.pools {
+            let total: u64 = pool.entries.iter().map(|entry| entry.weight).sum();
+            assert!(total <= DEVELOPMENT_WEIGHT_TOTAL, "{:?} pool overflows: {total}", pool.focus);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Embedded friend fleet table.
+//!
+//! Only map 425 carries entries so far: a sample pair of battleship fleets,
+//! 長門/陸奥 for a normal request and 大和/武蔵 for a strong one. They show the
+//! table layout rather than the event's recorded fleets; real fleets are added
+//! per event map the same way.
+
+use emukc_model::codex::friend_fleet::FriendFleetTable;
+
+use super::error::ParseError;
+
+const EMBEDDED_FRIEND_FLEET_TABLE_JSON: &str = include_str!("../../assets/friend_fleet_table.json");
+
+pub fn get() -> Result<FriendFleetTable, ParseError> {
+    let table: FriendFleetTable = serde_json::from_str(EMBEDDED_FRIEND_FLEET_TABLE_JSON)?;
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_sample_map_has_a_fleet_per_request_type() {
+        let table = get().unwrap();
+
+        for request_type in [0, 1] {
+            let candidates = table.candidates(425, 1, request_type);
+            assert_eq!(candidates.len(), 1, "request type {request_type}");
+            let (fleet, _) = candidates[0];
+            assert!((1..=6).contains(&fleet.ships.len()));
+            assert!(fleet.ships.iter().all(|ship| ship.slots.len() <= 5));
+        }
+        assert!(table.candidates(11, 1, 0).is_empty());
+    }
+}
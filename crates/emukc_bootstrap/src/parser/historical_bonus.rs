@@ -0,0 +1,12 @@
+use emukc_model::codex::historical_bonus::HistoricalBonusTable;
+
+use super::error::ParseError;
+
+const EMBEDDED_HISTORICAL_BONUS_TABLE_JSON: &str =
+    include_str!("../../assets/historical_bonus_table.json");
+
+pub fn get() -> Result<HistoricalBonusTable, ParseError> {
+    let table: HistoricalBonusTable = serde_json::from_str(EMBEDDED_HISTORICAL_BONUS_TABLE_JSON)?;
+
+    Ok(table)
+}
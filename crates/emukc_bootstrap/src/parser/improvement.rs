@@ -0,0 +1,206 @@
+use emukc_model::{
+    codex::improvement::{
+        ImprovementHelper, ImprovementItemCost, ImprovementRecipe, ImprovementStage,
+        ImprovementTable, ImprovementUpgrade, MAX_IMPROVEMENT_LEVEL, Weekday,
+    },
+    prelude::*,
+};
+
+/// Build the improvement table from the parsed slot item improvements.
+///
+/// An equipment gets one recipe for its plain improvement and one per upgrade
+/// target, each with the assistant ships listed for it. Recipe ids follow the
+/// slot item order.
+///
+/// The kcwiki data carries the same assistant ships, weekdays and costs as
+/// Akashi-List and is already downloaded for `slotitem_extra_info`, so no
+/// Akashi-List resource is fetched.
+///
+/// # Arguments
+///
+/// * `slotitems` - The kcwiki slot item map.
+pub fn build(slotitems: &Kc3rdSlotItemMap) -> ImprovementTable {
+    let mut recipes = Vec::new();
+
+    for (slotitem_id, item) in slotitems {
+        let Some(improvement) = &item.improvement else {
+            continue;
+        };
+
+        let variants =
+            improvement.level_consumption.iter().map(|requirements| (requirements, None)).chain(
+                improvement.remodel_variants.iter().flatten().map(|variant| {
+                    (
+                        &variant.requirements,
+                        Some(ImprovementUpgrade {
+                            slotitem_id: variant.slot_item_id,
+                            level: variant.initial_stars,
+                        }),
+                    )
+                }),
+            );
+
+        for (requirements, upgrade) in variants {
+            let mut stages = Vec::new();
+            for (from_level, to_level, consumption) in [
+                (0, 5, &requirements.first_half),
+                (6, MAX_IMPROVEMENT_LEVEL - 1, &requirements.second_half),
+                (MAX_IMPROVEMENT_LEVEL, MAX_IMPROVEMENT_LEVEL, &requirements.remodel),
+            ] {
+                if let Some(consumption) = consumption {
+                    stages.push(stage(from_level, to_level, consumption));
+                }
+            }
+            if upgrade.is_none() {
+                stages.retain(|stage| stage.from_level < MAX_IMPROVEMENT_LEVEL);
+            }
+
+            recipes.push(ImprovementRecipe {
+                id: recipes.len() as i64 + 1,
+                slotitem_id: *slotitem_id,
+                fuel: improvement.base_consumption.fuel,
+                ammo: improvement.base_consumption.ammo,
+                steel: improvement.base_consumption.steel,
+                bauxite: improvement.base_consumption.bauxite,
+                helpers: requirements.secretary.iter().map(helper).collect(),
+                stages,
+                upgrade,
+            });
+        }
+    }
+
+    ImprovementTable {
+        recipes,
+    }
+}
+
+fn stage(
+    from_level: i64,
+    to_level: i64,
+    consumption: &Kc3rdSlotItemImprovePerLevelConsumption,
+) -> ImprovementStage {
+    let costs = |items: &Option<Vec<Kc3rdSlotItemImproveItemConsumption>>| {
+        items
+            .iter()
+            .flatten()
+            .map(|item| ImprovementItemCost {
+                id: item.id,
+                count: item.count,
+            })
+            .collect()
+    };
+
+    ImprovementStage {
+        from_level,
+        to_level,
+        devmat: consumption.dev_mat_min,
+        certain_devmat: consumption.dev_mat_max.max(consumption.dev_mat_min),
+        screw: consumption.screw_min,
+        certain_screw: consumption.screw_max.max(consumption.screw_min),
+        slot_items: costs(&consumption.slot_item_consumption),
+        use_items: costs(&consumption.use_item_consumption),
+    }
+}
+
+fn helper(secretary: &Kc3rdSlotItemImproveSecretary) -> ImprovementHelper {
+    let weekdays = [
+        (secretary.monday, Weekday::Mon),
+        (secretary.tuesday, Weekday::Tue),
+        (secretary.wednesday, Weekday::Wed),
+        (secretary.thursday, Weekday::Thu),
+        (secretary.friday, Weekday::Fri),
+        (secretary.saturday, Weekday::Sat),
+        (secretary.sunday, Weekday::Sun),
+    ];
+
+    ImprovementHelper {
+        ship_id: secretary.id,
+        weekdays: weekdays.into_iter().filter(|(on, _)| *on).map(|(_, day)| day).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consumption(devmat: i64) -> Kc3rdSlotItemImprovePerLevelConsumption {
+        Kc3rdSlotItemImprovePerLevelConsumption {
+            dev_mat_min: devmat,
+            dev_mat_max: devmat + 1,
+            screw_min: 1,
+            screw_max: 2,
+            slot_item_consumption: Some(vec![Kc3rdSlotItemImproveItemConsumption {
+                id: 2,
+                count: 1,
+            }]),
+            use_item_consumption: None,
+        }
+    }
+
+    fn secretary(id: i64) -> Kc3rdSlotItemImproveSecretary {
+        Kc3rdSlotItemImproveSecretary {
+            id,
+            monday: true,
+            tuesday: false,
+            wednesday: false,
+            thursday: true,
+            friday: false,
+            saturday: false,
+            sunday: false,
+        }
+    }
+
+    #[test]
+    fn build_splits_plain_improvement_and_upgrades() {
+        let requirements = |remodel| Kc3rdSlotItemImproveRequirements {
+            first_half: Some(consumption(1)),
+            second_half: Some(consumption(2)),
+            remodel,
+            secretary: vec![secretary(0)],
+        };
+        let item = Kc3rdSlotItem {
+            api_id: 2,
+            name: String::new(),
+            info: String::new(),
+            craftable: true,
+            can_attack_installations: false,
+            stars: None,
+            flight_cost: None,
+            flight_range: None,
+            asw_damage_type: None,
+            improvement: Some(Kc3rdSlotItemImprovement {
+                base_consumption: Kc3rdSlotItemImproveBaseConsumption {
+                    fuel: 10,
+                    ammo: 30,
+                    steel: 60,
+                    bauxite: 0,
+                },
+                level_consumption: Some(requirements(None)),
+                remodel_variants: Some(vec![Kc3rdSlotItemRemodelVariant {
+                    slot_item_id: 63,
+                    initial_stars: 0,
+                    requirements: Kc3rdSlotItemImproveRequirements {
+                        secretary: vec![secretary(182)],
+                        ..requirements(Some(consumption(3)))
+                    },
+                }]),
+            }),
+        };
+
+        let table = build(&Kc3rdSlotItemMap::from([(2, item)]));
+
+        assert_eq!(table.recipes.len(), 2);
+        let plain = table.recipe(1).unwrap();
+        assert_eq!((plain.fuel, plain.ammo, plain.steel), (10, 30, 60));
+        assert!(plain.upgrade.is_none());
+        assert_eq!(plain.stages.len(), 2);
+        assert_eq!(plain.stages[1].from_level, 6);
+        assert_eq!(plain.stages[1].certain_devmat, 3);
+        assert_eq!(plain.helpers[0].weekdays, vec![Weekday::Mon, Weekday::Thu]);
+
+        let upgrade = table.recipe(2).unwrap();
+        assert_eq!(upgrade.upgrade.as_ref().unwrap().slotitem_id, 63);
+        assert_eq!(upgrade.stage(MAX_IMPROVEMENT_LEVEL).unwrap().devmat, 3);
+        assert_eq!(upgrade.helpers[0].ship_id, 182);
+    }
+}
@@ -1,6 +1,11 @@
 //! Parsers for various data sources.
 
+pub mod construction;
+pub mod development;
 pub mod error;
+pub mod friend_fleet;
+pub mod historical_bonus;
+pub mod improvement;
 pub mod kc3kai;
 pub mod kcanotify;
 pub mod kccp;
@@ -101,6 +106,11 @@ pub fn parse_partial_codex(dir: impl AsRef<std::path::Path>) -> Result<Codex, Pa
     };
 
     let music_list = music::get()?;
+    let development = development::get()?;
+    let construction = construction::get()?;
+    let friend_fleets = friend_fleet::get()?;
+    let historical_bonuses = historical_bonus::get()?;
+    let improvements = improvement::build(&kcwiki.slotitem_map);
 
     let mut cache_source = CacheSource::default();
     {
@@ -133,6 +143,11 @@ pub fn parse_partial_codex(dir: impl AsRef<std::path::Path>) -> Result<Codex, Pa
         game_cfg: GameConfig::default(),
         music_list,
         maps,
+        development,
+        construction,
+        friend_fleets,
+        historical_bonuses,
+        improvements,
         cache_source: Some(cache_source),
     })
 }
@@ -178,8 +193,10 @@ mod tests {
                         required_defeat_count: Some(3),
                         clear_to_variant_key: Some("post_p_unlock".to_string()),
                         parse_warnings: Vec::new(),
+                        boss_debuff: Default::default(),
                     },
                 )]),
+                gimmicks: Vec::new(),
             },
         );
         wikiwiki_catalog.maps.insert(
@@ -212,8 +229,10 @@ mod tests {
                         required_defeat_count: None,
                         clear_to_variant_key: None,
                         parse_warnings: Vec::new(),
+                        boss_debuff: Default::default(),
                     },
                 )]),
+                gimmicks: Vec::new(),
             },
         );
 
@@ -267,6 +286,7 @@ mod tests {
                                 node_label: Some("Start".to_string()),
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                             MapCellDefinition {
                                 cell_no: 1,
@@ -277,6 +297,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                             MapCellDefinition {
                                 cell_no: 2,
@@ -287,6 +308,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                             MapCellDefinition {
                                 cell_no: 3,
@@ -297,6 +319,7 @@ mod tests {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                         ],
                         routing_rules: BTreeMap::new(),
@@ -305,8 +328,10 @@ mod tests {
                         required_defeat_count: None,
                         clear_to_variant_key: None,
                         parse_warnings: Vec::new(),
+                        boss_debuff: Default::default(),
                     },
                 )]),
+                gimmicks: Vec::new(),
             },
         );
 
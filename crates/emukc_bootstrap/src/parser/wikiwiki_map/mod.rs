@@ -159,6 +159,7 @@ impl WikiwikiMapCatalog {
                         node_label: Some(ENTRY_NODE_LABEL.to_string()),
                         master_cell_id: None,
                         distance: None,
+                        select_cells: Vec::new(),
                     });
 
                     let boss_cell_no = variant
@@ -193,6 +194,7 @@ impl WikiwikiMapCatalog {
                             node_label: Some(node.label.clone()),
                             master_cell_id: None,
                             distance: None,
+                            select_cells: Vec::new(),
                         });
                     }
 
@@ -228,6 +230,7 @@ impl WikiwikiMapCatalog {
                             required_defeat_count: variant.required_defeat_count,
                             clear_to_variant_key: variant.clear_to_variant_key,
                             parse_warnings,
+                            boss_debuff: Default::default(),
                         },
                     )
                 })
@@ -254,6 +257,7 @@ impl WikiwikiMapCatalog {
                     default_variant,
                     rank_stage_ids: BTreeMap::new(),
                     variants,
+                    gimmicks: Vec::new(),
                 },
             );
 
@@ -0,0 +1,414 @@
+//! Offline cache bundles.
+//!
+//! A bundle is a zip archive holding a subset of the cache root under
+//! `files/` and a [`BundleManifest`] as `manifest.json`, which lists each
+//! file's version, size and MD5 hash. Unpacking it into another cache writes
+//! the files, their versions and their digests, so a machine without network
+//! access can serve them.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{Kache, QUARANTINE_DIR, error::Error, unified_rel_path, ver::cmp_version};
+
+/// Name of the manifest inside a bundle.
+pub const BUNDLE_MANIFEST: &str = "manifest.json";
+
+/// Current bundle format.
+pub const BUNDLE_FORMAT: u32 = 1;
+
+/// Directory of the cached files inside a bundle.
+const BUNDLE_FILES_DIR: &str = "files";
+
+/// Extensions of already compressed files, stored without deflating.
+const STORED_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "mp3", "ogg", "mp4", "woff", "woff2"];
+
+/// What a bundle holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Bundle format, see [`BUNDLE_FORMAT`].
+    pub format: u32,
+
+    /// Bundled files.
+    pub entries: Vec<BundleEntry>,
+}
+
+/// A bundled file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    /// Path relative to the cache root.
+    pub path: String,
+
+    /// Recorded version, if any.
+    pub version: Option<String>,
+
+    /// File size in bytes.
+    pub size: u64,
+
+    /// MD5 hex digest of the content.
+    pub md5: String,
+}
+
+/// Which cache entries go into a bundle.
+///
+/// An entry is packed when it passes every criterion that is set.
+#[derive(Debug, Clone, Default)]
+pub struct BundleFilter {
+    /// Path prefixes to keep; empty keeps all.
+    pub prefixes: Vec<String>,
+
+    /// Paths to keep, e.g. from a `make_list` manifest.
+    pub paths: Option<HashSet<String>>,
+
+    /// Sea areas whose map and gauge resources are kept; empty keeps all.
+    pub areas: Vec<i64>,
+}
+
+impl BundleFilter {
+    /// Whether a cached path goes into the bundle.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path relative to the cache root.
+    pub fn matches(&self, path: &str) -> bool {
+        if !self.prefixes.is_empty()
+            && !self
+                .prefixes
+                .iter()
+                .any(|prefix| path.starts_with(unified_rel_path(prefix).as_str()))
+        {
+            return false;
+        }
+
+        if self.paths.as_ref().is_some_and(|paths| !paths.contains(path)) {
+            return false;
+        }
+
+        if !self.areas.is_empty()
+            && let Some(area) = sea_area_of(path)
+        {
+            return self.areas.contains(&area);
+        }
+
+        true
+    }
+}
+
+/// Sea area of a map or gauge resource.
+///
+/// Maps live in `kcs2/resources/map/{area:03}/`, gauges are named
+/// `kcs2/resources/gauge/0{area:02}{map:02}`.
+fn sea_area_of(path: &str) -> Option<i64> {
+    if let Some(rest) = path.strip_prefix("kcs2/resources/map/") {
+        return rest.split('/').next()?.parse().ok();
+    }
+    if let Some(rest) = path.strip_prefix("kcs2/resources/gauge/") {
+        return rest.get(1..3)?.parse().ok();
+    }
+    None
+}
+
+/// Result of [`Kache::pack`]. Paths are relative to the cache root.
+#[derive(Debug, Clone, Default)]
+pub struct PackReport {
+    /// Files written to the bundle.
+    pub packed: Vec<String>,
+
+    /// Selected entries whose file is gone.
+    pub missing: Vec<String>,
+
+    /// Selected entries whose file does not match its recorded digest.
+    pub corrupted: Vec<String>,
+
+    /// Total size of the packed files in bytes.
+    pub bytes: u64,
+}
+
+/// Result of [`Kache::unpack`]. Paths are relative to the cache root.
+#[derive(Debug, Clone, Default)]
+pub struct UnpackReport {
+    /// Files written to the cache.
+    pub installed: Vec<String>,
+
+    /// Files already cached with the same content.
+    pub unchanged: Vec<String>,
+
+    /// Files kept because the cache holds a newer version.
+    pub kept: Vec<String>,
+
+    /// Bundled files with an unsafe path or a content not matching the manifest.
+    pub rejected: Vec<String>,
+}
+
+impl Kache {
+    /// Write the selected cache entries to a bundle.
+    ///
+    /// Entries whose file is missing or fails its recorded digest are left
+    /// out and reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The bundle file to create.
+    /// * `filter` - Which entries to pack.
+    pub async fn pack(&self, output: &Path, filter: &BundleFilter) -> Result<PackReport, Error> {
+        let entries: Vec<_> =
+            self.export().await?.into_iter().filter(|(path, _)| filter.matches(path)).collect();
+        let digests = self.export_digests()?;
+        let cache_root = self.cache_root.clone();
+        let output = output.to_path_buf();
+
+        tokio::task::spawn_blocking(move || pack_files(&cache_root, &output, entries, &digests))
+            .await
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?
+    }
+
+    /// Load a bundle into the cache.
+    ///
+    /// A file is written only when its content matches the manifest, and
+    /// never over a newer cached version.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The bundle file.
+    pub async fn unpack(&self, input: &Path) -> Result<UnpackReport, Error> {
+        let input = input.to_path_buf();
+        let manifest = {
+            let input = input.clone();
+            tokio::task::spawn_blocking(move || read_manifest(&input))
+                .await
+                .map_err(|e| Error::Io(std::io::Error::other(e)))??
+        };
+        if manifest.format > BUNDLE_FORMAT {
+            return Err(Error::InvalidBundle(format!(
+                "unsupported bundle format {}",
+                manifest.format
+            )));
+        }
+
+        let mut report = UnpackReport::default();
+        let digests = self.export_digests()?;
+        let mut wanted = Vec::new();
+        for entry in manifest.entries {
+            if !is_safe_rel_path(&entry.path) {
+                warn!("🚫 unsafe bundle path {}", entry.path);
+                report.rejected.push(entry.path);
+                continue;
+            }
+
+            let local_version = self.read_version_from_db(&entry.path).await?;
+            let cached = self.cache_root.join(&entry.path).is_file();
+            let newer_local = cached
+                && local_version
+                    .as_deref()
+                    .zip(entry.version.as_deref())
+                    .is_some_and(|(l, b)| cmp_version(l, b) == std::cmp::Ordering::Greater);
+            if newer_local {
+                report.kept.push(entry.path);
+            } else if cached
+                && local_version == entry.version
+                && digests.get(&entry.path) == Some(&(entry.size, entry.md5.clone()))
+            {
+                report.unchanged.push(entry.path);
+            } else {
+                wanted.push(entry);
+            }
+        }
+
+        let cache_root = self.cache_root.clone();
+        let (written, rejected) =
+            tokio::task::spawn_blocking(move || extract_files(&input, &cache_root, wanted))
+                .await
+                .map_err(|e| Error::Io(std::io::Error::other(e)))??;
+        report.rejected.extend(rejected);
+
+        for entry in written {
+            self.set_version(&entry.path, entry.version.as_deref()).await?;
+            self.record_digest(&entry.path).await?;
+            report.installed.push(entry.path);
+        }
+
+        Ok(report)
+    }
+}
+
+fn pack_files(
+    cache_root: &Path,
+    output: &Path,
+    entries: Vec<(String, Option<String>)>,
+    digests: &HashMap<String, (u64, String)>,
+) -> Result<PackReport, Error> {
+    let mut report = PackReport::default();
+    let mut manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        entries: Vec::new(),
+    };
+    let mut zip = ZipWriter::new(std::fs::File::create(output)?);
+
+    for (path, version) in entries {
+        let content = match std::fs::read(cache_root.join(&path)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                report.missing.push(path);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let size = content.len() as u64;
+        let md5 = md5_hex(&content);
+        if digests.get(&path).is_some_and(|(s, h)| *s != size || *h != md5) {
+            warn!("💔 not packing corrupted {}", path);
+            report.corrupted.push(path);
+            continue;
+        }
+
+        zip.start_file(format!("{BUNDLE_FILES_DIR}/{path}"), file_options(&path, size))?;
+        zip.write_all(&content)?;
+
+        report.bytes += size;
+        report.packed.push(path.clone());
+        manifest.entries.push(BundleEntry {
+            path,
+            version,
+            size,
+            md5,
+        });
+    }
+
+    zip.start_file(BUNDLE_MANIFEST, SimpleFileOptions::default())?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    zip.finish()?;
+
+    Ok(report)
+}
+
+fn file_options(path: &str, size: u64) -> SimpleFileOptions {
+    let stored = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| STORED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+
+    SimpleFileOptions::default()
+        .compression_method(if stored {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        })
+        .large_file(size >= u64::from(u32::MAX))
+}
+
+fn read_manifest(input: &Path) -> Result<BundleManifest, Error> {
+    let mut zip = ZipArchive::new(std::fs::File::open(input)?)?;
+    let manifest = zip
+        .by_name(BUNDLE_MANIFEST)
+        .map_err(|_| Error::InvalidBundle(format!("{BUNDLE_MANIFEST} not found")))?;
+
+    Ok(serde_json::from_reader(manifest)?)
+}
+
+/// Extract the wanted entries, returning the written and the rejected ones.
+fn extract_files(
+    input: &Path,
+    cache_root: &Path,
+    entries: Vec<BundleEntry>,
+) -> Result<(Vec<BundleEntry>, Vec<String>), Error> {
+    let mut zip = ZipArchive::new(std::fs::File::open(input)?)?;
+    let mut written = Vec::new();
+    let mut rejected = Vec::new();
+
+    for entry in entries {
+        let mut content = Vec::new();
+        match zip.by_name(&format!("{BUNDLE_FILES_DIR}/{}", entry.path)) {
+            Ok(mut file) => {
+                file.read_to_end(&mut content)?;
+            }
+            Err(zip::result::ZipError::FileNotFound) => {
+                warn!("🚫 {} is listed but not bundled", entry.path);
+                rejected.push(entry.path);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let md5 = md5_hex(&content);
+        if content.len() as u64 != entry.size || md5 != entry.md5 {
+            warn!("💔 {} does not match the bundle manifest", entry.path);
+            rejected.push(entry.path);
+            continue;
+        }
+
+        let target = cache_root.join(&entry.path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let staging = staging_path(&target);
+        std::fs::write(&staging, &content)?;
+        std::fs::rename(&staging, &target)?;
+
+        written.push(entry);
+    }
+
+    Ok((written, rejected))
+}
+
+fn md5_hex(content: &[u8]) -> String {
+    format!("{:x}", md5::compute(content))
+}
+
+/// Where a file is written before it replaces the cached one.
+fn staging_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".unpacking");
+    target.with_file_name(name)
+}
+
+/// Whether a bundled path stays inside the cache root and out of the
+/// database and quarantine locations.
+fn is_safe_rel_path(path: &str) -> bool {
+    let rel = Path::new(path);
+    unified_rel_path(path) == path
+        && rel.components().count() > 1
+        && rel.components().all(|c| matches!(c, Component::Normal(_)))
+        && !path.starts_with(&format!("{QUARANTINE_DIR}/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_combines_prefixes_paths_and_areas() {
+        let filter = BundleFilter {
+            areas: vec![1],
+            ..Default::default()
+        };
+        assert!(filter.matches("kcs2/resources/map/001/01_image.png"));
+        assert!(!filter.matches("kcs2/resources/map/002/01_image.png"));
+        assert!(filter.matches("kcs2/resources/gauge/00101.json"));
+        assert!(!filter.matches("kcs2/resources/gauge/04101_1.json"));
+        assert!(filter.matches("kcs2/resources/ship/full/0001_1234.png"));
+
+        let filter = BundleFilter {
+            prefixes: vec!["/kcs2/img/".to_string(), "kcs2/js/".to_string()],
+            paths: Some(HashSet::from(["kcs2/img/title.png".to_string()])),
+            ..Default::default()
+        };
+        assert!(filter.matches("kcs2/img/title.png"));
+        assert!(!filter.matches("kcs2/img/port.png"));
+        assert!(!filter.matches("kcs2/js/main.js"));
+    }
+
+    #[test]
+    fn unsafe_paths_are_refused() {
+        assert!(is_safe_rel_path("kcs2/js/main.js"));
+        assert!(!is_safe_rel_path("kache.redb"));
+        assert!(!is_safe_rel_path("kcs2/../../etc/passwd"));
+        assert!(!is_safe_rel_path("/etc/passwd"));
+        assert!(!is_safe_rel_path("kcs2\\js\\main.js"));
+        assert!(!is_safe_rel_path(".quarantine/kcs2/js/main.js"));
+    }
+}
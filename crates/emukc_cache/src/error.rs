@@ -23,6 +23,10 @@ pub enum Error {
     #[error("file version not matched: {0}")]
     InvalidFileVersion(String),
 
+    /// File content does not match its recorded size or hash.
+    #[error("file corrupted: {0}")]
+    Corrupted(String),
+
     /// IO error.
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -59,6 +63,22 @@ pub enum Error {
     #[error("failed on all CDN")]
     FailedOnAllCdn,
 
+    /// Mod pack not found.
+    #[error("mod not found: {0}")]
+    ModNotFound(String),
+
+    /// Bundle error.
+    #[error("invalid bundle: {0}")]
+    InvalidBundle(String),
+
+    /// Bundle archive error.
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
+    /// Bundle manifest error.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
     /// Reqwest error.
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
@@ -1,9 +1,11 @@
 //! Kache is for `KanColle` Cache, a simple cache system.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use emukc_crypto::rng;
+use dashmap::{DashMap, DashSet};
+use emukc_crypto::{md5_file_async, rng};
 use redb::{Database, ReadableDatabase, TableDefinition};
 use tokio::io::AsyncReadExt;
 
@@ -12,6 +14,7 @@ use emukc_network::{client::new_reqwest_client, download, reqwest};
 use crate::{
     download_lock::DownloadLock,
     error::Error,
+    mods::{KACHE_MOD_TOGGLE_TABLE, ModPack, scan_mods},
     opt::GetOption,
     unified_rel_path,
     ver::{IntoVersion, cmp_version},
@@ -21,14 +24,24 @@ use crate::{
 pub(crate) const KACHE_TABLE: TableDefinition<&str, Option<&str>> =
     TableDefinition::new("kache_entry");
 
+/// Size and MD5 hex digest of each cached file, recorded when it is stored.
+pub(crate) const KACHE_DIGEST_TABLE: TableDefinition<&str, (u64, &str)> =
+    TableDefinition::new("kache_digest");
+
 /// The `Kache` struct is the main struct for the `KanColle` CDN file cache utilities.
 #[derive(Debug, Clone)]
 pub struct Kache {
     /// Root directory for the cache.
-    cache_root: PathBuf,
+    pub(crate) cache_root: PathBuf,
 
     /// Root directory for the mods.
-    mods_root: Option<PathBuf>,
+    pub(crate) mods_root: Option<PathBuf>,
+
+    /// Mod packs found in the mods root, highest priority first.
+    pub(crate) mod_packs: Arc<Vec<ModPack>>,
+
+    /// Per-profile mod toggles read from the database.
+    pub(crate) mod_toggle_cache: Arc<DashMap<i64, Arc<HashMap<String, bool>>>>,
 
     /// CDN URLs for downloading gadgets.
     gadgets_cdn: Vec<String>,
@@ -47,6 +60,9 @@ pub struct Kache {
 
     /// Download lock to prevent concurrent downloads.
     download_lock: Arc<DownloadLock>,
+
+    /// Paths whose content hash matched since the cache was opened.
+    verified: Arc<DashSet<String>>,
 }
 
 /// The `Builder` struct is the builder for the `Kache` struct.
@@ -149,22 +165,28 @@ impl Builder {
         let write_txn = db.begin_write()?;
         {
             let _ = write_txn.open_table(KACHE_TABLE)?;
+            let _ = write_txn.open_table(KACHE_DIGEST_TABLE)?;
+            let _ = write_txn.open_table(KACHE_MOD_TOGGLE_TABLE)?;
         }
         write_txn.commit()?;
 
         let db = Arc::new(db);
         let version_cache = Arc::new(VersionCache::new(1000));
         let download_lock = Arc::new(DownloadLock::new());
+        let mod_packs = self.mods_root.as_deref().map(scan_mods).unwrap_or_default();
 
         Ok(Kache {
             cache_root,
             mods_root: self.mods_root,
+            mod_packs: Arc::new(mod_packs),
+            mod_toggle_cache: Arc::new(DashMap::new()),
             gadgets_cdn,
             content_cdn,
             client,
             db,
             version_cache,
             download_lock,
+            verified: Arc::new(DashSet::new()),
         })
     }
 }
@@ -224,8 +246,7 @@ impl Kache {
         }
 
         if opt.enable_mod
-            && self.mods_root.is_some()
-            && let Some(f) = self.find_in_mods(path).await
+            && let Some(f) = self.find_in_mods(path, opt.profile_id).await?
         {
             debug!("✅ {log_tail}");
             return Ok(f);
@@ -248,6 +269,9 @@ impl Kache {
                     Error::FileExpired(_) => {
                         warn!("🥀 expired: {log_tail}");
                     }
+                    Error::Corrupted(_) => {
+                        warn!("💔 corrupted: {log_tail}");
+                    }
                     Error::InvalidFileVersion(_) => {
                         // The local cache is NEWER than the requested version (a rollback
                         // request, e.g. populating from a stale list). Serve the newer local
@@ -345,31 +369,6 @@ impl Kache {
         format!("{cdn}/{remote_path}{ver}")
     }
 
-    /// Find the file in the mods.
-    /// Version will be ignored.
-    #[instrument(skip(self))]
-    async fn find_in_mods(&self, path: &str) -> Option<tokio::fs::File> {
-        let mod_path = self.mods_root.as_ref()?;
-
-        let local_path = mod_path.join(path);
-        if local_path.exists() {
-            info!("👻 mod found {:?}", local_path);
-            tokio::fs::File::open(local_path).await.ok()
-        } else {
-            // check for wildcard
-            let ext = local_path.extension()?.to_str()?;
-            let parent_dir = local_path.parent()?;
-            let wildcard_file = parent_dir.join(format!("wildcard.{ext}"));
-
-            if wildcard_file.exists() {
-                info!("👻 wildcard mod found {wildcard_file:?}");
-                tokio::fs::File::open(wildcard_file).await.ok()
-            } else {
-                None
-            }
-        }
-    }
-
     async fn find_in_local(
         &self,
         rel_path: &str,
@@ -393,6 +392,8 @@ impl Kache {
             return Err(Error::FileNotFound(local_path.display().to_string()));
         }
 
+        self.check_digest(rel_path, local_path, metadata.len()).await?;
+
         // No specific version requested — serve whatever is local
         if version.is_empty() {
             trace!("no version requested, serving local file");
@@ -432,7 +433,10 @@ impl Kache {
         }
     }
 
-    async fn read_version_from_db(&self, rel_path: &str) -> Result<Option<String>, Error> {
+    pub(crate) async fn read_version_from_db(
+        &self,
+        rel_path: &str,
+    ) -> Result<Option<String>, Error> {
         let rel_path = unified_rel_path(rel_path);
 
         // Check cache first
@@ -459,6 +463,109 @@ impl Kache {
         Ok(version)
     }
 
+    /// Check a local file against its recorded digest.
+    ///
+    /// The size is checked on every call, the content hash only until it has
+    /// matched once since the cache was opened. Files stored before digests
+    /// were recorded pass unchecked.
+    async fn check_digest(
+        &self,
+        rel_path: &str,
+        local_path: &std::path::Path,
+        size: u64,
+    ) -> Result<(), Error> {
+        let rel_path = unified_rel_path(rel_path);
+        let Some((recorded_size, recorded_hash)) = self.read_digest(&rel_path).await? else {
+            return Ok(());
+        };
+
+        if size != recorded_size {
+            trace!("size {} does not match the recorded {}", size, recorded_size);
+            return Err(Error::Corrupted(rel_path));
+        }
+
+        if self.verified.contains(&rel_path) {
+            return Ok(());
+        }
+
+        if md5_file_async(local_path).await? != recorded_hash {
+            trace!("hash does not match the recorded {}", recorded_hash);
+            return Err(Error::Corrupted(rel_path));
+        }
+
+        self.verified.insert(rel_path);
+        Ok(())
+    }
+
+    /// Read the recorded size and hash of a cached resource.
+    pub(crate) async fn read_digest(&self, rel_path: &str) -> Result<Option<(u64, String)>, Error> {
+        let db = self.db.clone();
+        let rel_path = unified_rel_path(rel_path);
+
+        tokio::task::spawn_blocking(move || -> Result<Option<(u64, String)>, Error> {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(KACHE_DIGEST_TABLE)?;
+            let entry = table.get(rel_path.as_str())?;
+            Ok(entry.map(|v| {
+                let (size, hash) = v.value();
+                (size, hash.to_owned())
+            }))
+        })
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))?
+    }
+
+    /// Record the size and content hash of a cached resource.
+    ///
+    /// Files stored by [`Kache::get`] are recorded automatically; call this
+    /// after placing a file under the cache root by other means.
+    pub async fn record_digest(&self, rel_path: &str) -> Result<(), Error> {
+        let rel_path = unified_rel_path(rel_path);
+        let local_path = self.cache_root.join(&rel_path);
+        let size = tokio::fs::metadata(&local_path).await?.len();
+        let hash = md5_file_async(&local_path).await?;
+
+        let db = self.db.clone();
+        let rel_path_clone = rel_path.clone();
+        tokio::task::spawn_blocking(move || -> Result<(), Error> {
+            let write_txn = db.begin_write()?;
+            {
+                let mut table = write_txn.open_table(KACHE_DIGEST_TABLE)?;
+                table.insert(rel_path_clone.as_str(), (size, hash.as_str()))?;
+            }
+            write_txn.commit()?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))??;
+
+        self.verified.insert(rel_path);
+        Ok(())
+    }
+
+    /// Drop the version and digest records of a cached resource.
+    pub(crate) async fn remove_entry(&self, rel_path: &str) -> Result<(), Error> {
+        let rel_path = unified_rel_path(rel_path);
+        let db = self.db.clone();
+        let rel_path_clone = rel_path.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), Error> {
+            let write_txn = db.begin_write()?;
+            {
+                write_txn.open_table(KACHE_TABLE)?.remove(rel_path_clone.as_str())?;
+                write_txn.open_table(KACHE_DIGEST_TABLE)?.remove(rel_path_clone.as_str())?;
+            }
+            write_txn.commit()?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))??;
+
+        self.version_cache.remove(&rel_path);
+        self.verified.remove(&rel_path);
+        Ok(())
+    }
+
     /// Get the cached version for a resource path, if any.
     pub async fn get_cached_version(&self, path: &str) -> Result<Option<String>, Error> {
         self.read_version_from_db(path).await
@@ -524,13 +631,14 @@ impl Kache {
             return Err(Error::InvalidFile(local_path.display().to_string()));
         }
 
+        self.record_digest(rel_path).await?;
         let v = version.into_version();
         self.set_version(rel_path, v.as_deref()).await?;
 
         Ok(tokio::fs::File::open(local_path).await?)
     }
 
-    async fn fetch_from_remote(
+    pub(crate) async fn fetch_from_remote(
         &self,
         path: &str,
         local_path: &PathBuf,
@@ -590,7 +698,7 @@ impl Kache {
     /// # Arguments
     ///
     /// * `path` - The file path.
-    async fn is_valid(path: &std::path::Path) -> bool {
+    pub(crate) async fn is_valid(path: &std::path::Path) -> bool {
         if !path.exists() || !path.is_file() {
             trace!("File does not exist or is not a file: {:?}", path);
             return false;
@@ -669,6 +777,7 @@ mod tests {
             enable_remote: false,
             enable_mod: false,
             enable_shuffle: false,
+            profile_id: None,
         };
         let file = kache.get_with_opt(rel, "6.2.0.0", &opt).await;
         assert!(file.is_ok(), "rollback request must serve the newer local file, got {file:?}");
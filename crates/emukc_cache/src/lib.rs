@@ -7,19 +7,28 @@
 #[macro_use]
 extern crate tracing;
 
+mod bundle;
 mod download_lock;
 mod error;
 mod export;
 mod kache;
+mod mods;
 mod opt;
 mod ver;
+mod verify;
 mod version_cache;
 
+pub use bundle::{
+    BUNDLE_FORMAT, BUNDLE_MANIFEST, BundleEntry, BundleFilter, BundleManifest, PackReport,
+    UnpackReport,
+};
 pub use error::Error as KacheError;
 pub use kache::Builder as KacheBuilder;
 pub use kache::Kache;
+pub use mods::{LOOSE_MODS, MOD_MANIFEST, ModConflict, ModManifest, ModPack, ModStatus};
 pub use opt::GetOption;
 pub use ver::{IntoVersion, NoVersion, cmp_version};
+pub use verify::{QUARANTINE_DIR, VerifyReport};
 
 /// Convert a path to a unified relative path.
 ///
@@ -35,10 +44,13 @@ pub fn unified_rel_path(path: &str) -> String {
 /// This module re-exports the core types and traits of the crate
 /// for convenient importing with a global import: `use emukc_cache::prelude::*;`
 pub mod prelude {
+    pub use crate::BundleFilter;
     pub use crate::GetOption;
     pub use crate::IntoVersion;
     pub use crate::Kache;
     pub use crate::KacheBuilder;
     pub use crate::KacheError;
+    pub use crate::ModStatus;
     pub use crate::NoVersion;
+    pub use crate::VerifyReport;
 }
@@ -0,0 +1,409 @@
+//! Layered resource mods.
+//!
+//! Each subdirectory of the mods root holding a [`MOD_MANIFEST`] is a mod pack,
+//! laid out like the cache root. Packs are searched from the highest priority
+//! down, and the first one holding a path, or a `wildcard.<ext>` next to it,
+//! serves it. Files outside any pack are searched last, as [`LOOSE_MODS`].
+//!
+//! A pack is active when it is enabled, by its manifest or by a per-profile
+//! toggle, and the cached client version lies in its version range. Packs are
+//! discovered when the cache is built.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use redb::{ReadableDatabase, TableDefinition};
+use serde::{Deserialize, Serialize};
+
+use crate::{Kache, error::Error, unified_rel_path, ver::cmp_version};
+
+/// Name of the manifest marking a mod pack directory.
+pub const MOD_MANIFEST: &str = "mod.json";
+
+/// Id reported for files placed in the mods root outside any pack.
+pub const LOOSE_MODS: &str = "(loose)";
+
+/// Cached file whose version is the game client version.
+const CLIENT_VERSION_PATH: &str = "kcs2/js/main.js";
+
+/// Per-profile mod toggles, keyed by profile ID and mod pack ID.
+pub(crate) const KACHE_MOD_TOGGLE_TABLE: TableDefinition<(i64, &str), bool> =
+    TableDefinition::new("kache_mod_toggle");
+
+/// The `mod.json` of a mod pack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModManifest {
+    /// Display name.
+    pub name: String,
+
+    /// Packs with a higher priority win conflicts.
+    #[serde(default)]
+    pub priority: i64,
+
+    /// Whether the pack is served to profiles without a toggle.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Oldest supported client version, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<String>,
+
+    /// Newest supported client version, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_version: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ModManifest {
+    /// Whether the pack supports a client version.
+    ///
+    /// An unknown client version is supported by every pack.
+    pub fn supports(&self, client_version: Option<&str>) -> bool {
+        let Some(version) = client_version else {
+            return true;
+        };
+
+        self.min_version.as_deref().is_none_or(|min| cmp_version(version, min) != Ordering::Less)
+            && self
+                .max_version
+                .as_deref()
+                .is_none_or(|max| cmp_version(version, max) != Ordering::Greater)
+    }
+}
+
+/// A mod pack found in the mods root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModPack {
+    /// Directory name, used to toggle the pack.
+    pub id: String,
+
+    /// Pack directory.
+    pub root: PathBuf,
+
+    /// The pack's manifest.
+    pub manifest: ModManifest,
+}
+
+/// A mod pack as seen by one profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModStatus {
+    /// The pack.
+    pub pack: ModPack,
+
+    /// Whether the pack is enabled.
+    pub enabled: bool,
+
+    /// Whether `enabled` comes from a per-profile toggle rather than the manifest.
+    pub toggled: bool,
+
+    /// Whether the cached client version lies in the pack's version range.
+    pub compatible: bool,
+}
+
+impl ModStatus {
+    /// Whether the pack serves files.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.compatible
+    }
+}
+
+/// A path provided by more than one active mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModConflict {
+    /// Path relative to the pack roots.
+    pub path: String,
+
+    /// ID of the pack serving the path.
+    pub winner: String,
+
+    /// IDs of the packs it shadows, highest priority first.
+    pub shadowed: Vec<String>,
+}
+
+impl Kache {
+    /// The mod packs found in the mods root, highest priority first.
+    pub fn mod_packs(&self) -> &[ModPack] {
+        &self.mod_packs
+    }
+
+    /// List the mod packs as seen by a profile, highest priority first.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile whose toggles apply, manifests only if `None`.
+    pub async fn mods(&self, profile_id: Option<i64>) -> Result<Vec<ModStatus>, Error> {
+        let client_version = self.read_version_from_db(CLIENT_VERSION_PATH).await?;
+        let toggles = match profile_id {
+            Some(profile_id) => self.mod_toggles(profile_id).await?,
+            None => Arc::default(),
+        };
+
+        Ok(self
+            .mod_packs
+            .iter()
+            .map(|pack| {
+                let toggle = toggles.get(&pack.id).copied();
+                ModStatus {
+                    pack: pack.clone(),
+                    enabled: toggle.unwrap_or(pack.manifest.enabled),
+                    toggled: toggle.is_some(),
+                    compatible: pack.manifest.supports(client_version.as_deref()),
+                }
+            })
+            .collect())
+    }
+
+    /// Report the paths provided by more than one active mod.
+    ///
+    /// Wildcards are compared by their own path, not by the paths they cover.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile whose toggles apply, manifests only if `None`.
+    pub async fn mod_conflicts(&self, profile_id: Option<i64>) -> Result<Vec<ModConflict>, Error> {
+        let Some(mods_root) = self.mods_root.clone() else {
+            return Ok(Vec::new());
+        };
+        let active: Vec<_> = self
+            .mods(profile_id)
+            .await?
+            .into_iter()
+            .filter(ModStatus::is_active)
+            .map(|status| (status.pack.id, status.pack.root))
+            .collect();
+        let pack_roots: Vec<_> = self.mod_packs.iter().map(|pack| pack.root.clone()).collect();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<ModConflict>, Error> {
+            let mut providers: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for (id, root) in &active {
+                for path in list_files(root, &[])? {
+                    if path != MOD_MANIFEST {
+                        providers.entry(path).or_default().push(id.clone());
+                    }
+                }
+            }
+            for path in list_files(&mods_root, &pack_roots)? {
+                if path.contains('/') {
+                    providers.entry(path).or_default().push(LOOSE_MODS.to_owned());
+                }
+            }
+
+            Ok(providers
+                .into_iter()
+                .filter(|(_, ids)| ids.len() > 1)
+                .map(|(path, mut ids)| {
+                    let winner = ids.remove(0);
+                    ModConflict {
+                        path,
+                        winner,
+                        shadowed: ids,
+                    }
+                })
+                .collect())
+        })
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))?
+    }
+
+    /// Enable or disable a mod pack for a profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile ID.
+    /// * `mod_id` - The pack's directory name.
+    /// * `enabled` - The toggle, `None` to follow the manifest again.
+    pub async fn set_mod_enabled(
+        &self,
+        profile_id: i64,
+        mod_id: &str,
+        enabled: Option<bool>,
+    ) -> Result<(), Error> {
+        if !self.mod_packs.iter().any(|pack| pack.id == mod_id) {
+            return Err(Error::ModNotFound(mod_id.to_owned()));
+        }
+
+        let db = self.db.clone();
+        let mod_id = mod_id.to_owned();
+        tokio::task::spawn_blocking(move || -> Result<(), Error> {
+            let write_txn = db.begin_write()?;
+            {
+                let mut table = write_txn.open_table(KACHE_MOD_TOGGLE_TABLE)?;
+                match enabled {
+                    Some(enabled) => {
+                        table.insert((profile_id, mod_id.as_str()), enabled)?;
+                    }
+                    None => {
+                        table.remove((profile_id, mod_id.as_str()))?;
+                    }
+                }
+            }
+            write_txn.commit()?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))??;
+
+        self.mod_toggle_cache.remove(&profile_id);
+        Ok(())
+    }
+
+    /// Find a file in the active mods, highest priority first.
+    /// Version will be ignored.
+    #[instrument(skip(self))]
+    pub(crate) async fn find_in_mods(
+        &self,
+        path: &str,
+        profile_id: Option<i64>,
+    ) -> Result<Option<tokio::fs::File>, Error> {
+        let Some(mods_root) = self.mods_root.as_ref() else {
+            return Ok(None);
+        };
+
+        if !self.mod_packs.is_empty() {
+            for status in self.mods(profile_id).await? {
+                if status.is_active()
+                    && let Some(f) = find_in_dir(&status.pack.root, path).await
+                {
+                    info!("👻 mod {} serves {}", status.pack.id, path);
+                    return Ok(Some(f));
+                }
+            }
+        }
+
+        Ok(find_in_dir(mods_root, path).await)
+    }
+
+    /// Read the mod toggles of a profile, keyed by mod pack ID.
+    async fn mod_toggles(&self, profile_id: i64) -> Result<Arc<HashMap<String, bool>>, Error> {
+        if let Some(toggles) = self.mod_toggle_cache.get(&profile_id) {
+            return Ok(toggles.clone());
+        }
+
+        let db = self.db.clone();
+        let toggles = tokio::task::spawn_blocking(move || -> Result<HashMap<_, _>, Error> {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(KACHE_MOD_TOGGLE_TABLE)?;
+
+            let mut toggles = HashMap::new();
+            for kv in table.range((profile_id, "")..)? {
+                let (k, v) = kv?;
+                let (id, mod_id) = k.value();
+                if id != profile_id {
+                    break;
+                }
+                toggles.insert(mod_id.to_owned(), v.value());
+            }
+            Ok(toggles)
+        })
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))??;
+
+        let toggles = Arc::new(toggles);
+        self.mod_toggle_cache.insert(profile_id, toggles.clone());
+        Ok(toggles)
+    }
+}
+
+/// Find the mod packs in a mods root, highest priority first.
+///
+/// Directories whose manifest cannot be read are skipped.
+pub(crate) fn scan_mods(mods_root: &Path) -> Vec<ModPack> {
+    let Ok(entries) = std::fs::read_dir(mods_root) else {
+        return Vec::new();
+    };
+
+    let mut packs: Vec<_> = entries
+        .filter_map(|entry| {
+            let root = entry.ok()?.path();
+            let raw = std::fs::read(root.join(MOD_MANIFEST)).ok()?;
+            let id = root.file_name()?.to_string_lossy().into_owned();
+            match serde_json::from_slice::<ModManifest>(&raw) {
+                Ok(manifest) => Some(ModPack {
+                    id,
+                    root,
+                    manifest,
+                }),
+                Err(e) => {
+                    warn!("❌ invalid {} of mod {}: {}", MOD_MANIFEST, id, e);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    packs.sort_by(|a, b| b.manifest.priority.cmp(&a.manifest.priority).then(a.id.cmp(&b.id)));
+    packs
+}
+
+/// Find a file, or a `wildcard.<ext>` next to it, below a directory.
+async fn find_in_dir(root: &Path, path: &str) -> Option<tokio::fs::File> {
+    let local_path = root.join(path);
+    if local_path.is_file() {
+        info!("👻 mod found {:?}", local_path);
+        return tokio::fs::File::open(local_path).await.ok();
+    }
+
+    // check for wildcard
+    let ext = local_path.extension()?.to_str()?;
+    let wildcard_file = local_path.parent()?.join(format!("wildcard.{ext}"));
+    if wildcard_file.is_file() {
+        info!("👻 wildcard mod found {wildcard_file:?}");
+        tokio::fs::File::open(wildcard_file).await.ok()
+    } else {
+        None
+    }
+}
+
+/// List the files below a directory, relative to it, skipping `excluded` directories.
+fn list_files(root: &Path, excluded: &[PathBuf]) -> Result<Vec<String>, Error> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                if !excluded.contains(&entry.path()) {
+                    dirs.push(entry.path());
+                }
+            } else if file_type.is_file()
+                && let Ok(rel_path) = entry.path().strip_prefix(root)
+            {
+                files.push(unified_rel_path(&rel_path.to_string_lossy()));
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_range_is_inclusive() {
+        let manifest = ModManifest {
+            name: "patch".to_owned(),
+            priority: 0,
+            enabled: true,
+            min_version: Some("6.0.0.0".to_owned()),
+            max_version: Some("6.2.9.0".to_owned()),
+        };
+        assert!(manifest.supports(None));
+        assert!(manifest.supports(Some("6.0.0.0")));
+        assert!(manifest.supports(Some("6.2.9.0")));
+        assert!(!manifest.supports(Some("5.9.9.9")));
+        assert!(!manifest.supports(Some("6.2.10.0")));
+
+        let manifest: ModManifest = serde_json::from_str(r#"{ "name": "skins" }"#).unwrap();
+        assert!(manifest.enabled);
+        assert!(manifest.supports(Some("1.0.0.0")));
+    }
+}
@@ -14,6 +14,9 @@ pub struct GetOption {
 
     /// Whether to enable the shuffle, picking CDN randomly.
     pub enable_shuffle: bool,
+
+    /// The profile whose mod toggles apply, manifests only if `None`.
+    pub profile_id: Option<i64>,
 }
 
 impl Default for GetOption {
@@ -23,6 +26,7 @@ impl Default for GetOption {
             enable_remote: true,
             enable_mod: true,
             enable_shuffle: true,
+            profile_id: None,
         }
     }
 }
@@ -40,6 +44,7 @@ impl GetOption {
             enable_remote: false,
             enable_mod: true,
             enable_shuffle: false,
+            profile_id: None,
         }
     }
 
@@ -50,6 +55,7 @@ impl GetOption {
             enable_remote: true,
             enable_mod: false,
             enable_shuffle: true,
+            profile_id: None,
         }
     }
 
@@ -60,6 +66,7 @@ impl GetOption {
             enable_remote: true,
             enable_mod: false,
             enable_shuffle: true,
+            profile_id: None,
         }
     }
 
@@ -87,6 +94,12 @@ impl GetOption {
         self
     }
 
+    /// Serves the mods enabled for a profile.
+    pub fn with_profile(mut self, profile_id: Option<i64>) -> Self {
+        self.profile_id = profile_id;
+        self
+    }
+
     /// Executes the `get` method of `Kache` with the given options.
     ///
     /// # Arguments
@@ -0,0 +1,234 @@
+//! Integrity check of the cache root against the recorded entries.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use emukc_crypto::md5_file_async;
+use redb::{ReadableDatabase, ReadableTable};
+
+use crate::{Kache, error::Error, kache::KACHE_DIGEST_TABLE, unified_rel_path};
+
+/// Directory under the cache root that damaged files are moved to.
+pub const QUARANTINE_DIR: &str = ".quarantine";
+
+/// Result of [`Kache::verify`]. Paths are relative to the cache root.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    /// Number of recorded entries checked.
+    pub checked: usize,
+
+    /// Entries whose file is gone.
+    pub missing: Vec<String>,
+
+    /// Entries whose file size differs from the recorded one.
+    pub truncated: Vec<String>,
+
+    /// Entries whose content hash differs, or that hold an error page.
+    pub corrupted: Vec<String>,
+
+    /// Entries stored before digests were recorded.
+    pub unrecorded: Vec<String>,
+
+    /// Files under the cache root without an entry.
+    pub orphaned: Vec<String>,
+
+    /// Entries fetched again from the CDN.
+    pub refetched: Vec<String>,
+
+    /// Files moved to the quarantine directory.
+    pub quarantined: Vec<String>,
+
+    /// Entries and orphans whose digest was recorded.
+    pub recorded: Vec<String>,
+}
+
+impl VerifyReport {
+    /// Whether no damaged or orphaned file was found.
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty()
+            && self.truncated.is_empty()
+            && self.corrupted.is_empty()
+            && self.orphaned.is_empty()
+    }
+}
+
+impl Kache {
+    /// Check every cached file against its recorded size and hash, and look
+    /// for files the database does not know.
+    ///
+    /// With `repair`, a damaged entry is fetched again at its recorded
+    /// version; when that fails, the file is moved to [`QUARANTINE_DIR`] and
+    /// the entry dropped. Orphans that look sane are adopted as unversioned
+    /// entries, the others are quarantined, and entries stored before digests
+    /// were recorded get one.
+    ///
+    /// # Arguments
+    ///
+    /// * `repair` - Whether to fix what is found.
+    pub async fn verify(&self, repair: bool) -> Result<VerifyReport, Error> {
+        let mut report = VerifyReport::default();
+        let entries = self.export().await?;
+        let digests = self.export_digests()?;
+
+        for (rel_path, version) in &entries {
+            report.checked += 1;
+            let local_path = self.cache_root.join(rel_path);
+
+            let damaged = if !local_path.is_file() {
+                report.missing.push(rel_path.clone());
+                true
+            } else if let Some((size, hash)) = digests.get(rel_path) {
+                if local_path.metadata()?.len() != *size {
+                    report.truncated.push(rel_path.clone());
+                    true
+                } else if md5_file_async(&local_path).await? != *hash {
+                    report.corrupted.push(rel_path.clone());
+                    true
+                } else {
+                    false
+                }
+            } else if !Self::is_valid(&local_path).await {
+                report.corrupted.push(rel_path.clone());
+                true
+            } else {
+                report.unrecorded.push(rel_path.clone());
+                if repair {
+                    self.record_digest(rel_path).await?;
+                    report.recorded.push(rel_path.clone());
+                }
+                false
+            };
+
+            if damaged && repair {
+                self.repair_entry(rel_path, version.as_deref(), &mut report).await?;
+            }
+        }
+
+        let known: HashSet<&str> = entries.iter().map(|(path, _)| path.as_str()).collect();
+        for rel_path in scan_files(&self.cache_root).await? {
+            if known.contains(rel_path.as_str()) {
+                continue;
+            }
+            report.orphaned.push(rel_path.clone());
+            if !repair {
+                continue;
+            }
+
+            if Self::is_valid(&self.cache_root.join(&rel_path)).await {
+                self.set_version(&rel_path, None).await?;
+                self.record_digest(&rel_path).await?;
+                report.recorded.push(rel_path);
+            } else {
+                self.quarantine(&rel_path).await?;
+                report.quarantined.push(rel_path);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Fetch a damaged entry again, quarantining the file when that fails.
+    async fn repair_entry(
+        &self,
+        rel_path: &str,
+        version: Option<&str>,
+        report: &mut VerifyReport,
+    ) -> Result<(), Error> {
+        let local_path = self.cache_root.join(rel_path);
+        let parked = if local_path.is_file() {
+            Some(self.quarantine(rel_path).await?)
+        } else {
+            None
+        };
+
+        match self
+            .fetch_from_remote(rel_path, &local_path, version.unwrap_or_default(), false)
+            .await
+        {
+            Ok(_) => {
+                if let Some(parked) = parked {
+                    tokio::fs::remove_file(parked).await?;
+                }
+                report.refetched.push(rel_path.to_owned());
+            }
+            Err(e) => {
+                warn!("💔 could not fetch {} again: {}", rel_path, e);
+                self.remove_entry(rel_path).await?;
+                if parked.is_some() {
+                    report.quarantined.push(rel_path.to_owned());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move a cached file into the quarantine directory.
+    ///
+    /// Return the quarantined file path.
+    async fn quarantine(&self, rel_path: &str) -> Result<PathBuf, Error> {
+        let target = self.cache_root.join(QUARANTINE_DIR).join(rel_path);
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::rename(self.cache_root.join(rel_path), &target).await?;
+        info!("🧪 quarantined {}", rel_path);
+
+        Ok(target)
+    }
+
+    /// Export all recorded digests, keyed by relative path.
+    pub(crate) fn export_digests(&self) -> Result<HashMap<String, (u64, String)>, Error> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(KACHE_DIGEST_TABLE)?;
+
+        let digests = table
+            .iter()?
+            .filter_map(|kv| {
+                let (k, v) = kv.ok()?;
+                let (size, hash) = v.value();
+                Some((k.value().to_owned(), (size, hash.to_owned())))
+            })
+            .collect();
+
+        Ok(digests)
+    }
+}
+
+/// List the files below the cache root, relative to it.
+///
+/// Files directly in the root (the database, dumped lists) and the quarantine
+/// directory are skipped.
+async fn scan_files(cache_root: &Path) -> Result<Vec<String>, Error> {
+    let cache_root = cache_root.to_path_buf();
+
+    tokio::task::spawn_blocking(move || -> Result<Vec<String>, Error> {
+        let mut files = Vec::new();
+        let mut dirs = Vec::new();
+        for entry in std::fs::read_dir(&cache_root)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() && entry.file_name() != QUARANTINE_DIR {
+                dirs.push(entry.path());
+            }
+        }
+
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir)? {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                if file_type.is_dir() {
+                    dirs.push(entry.path());
+                } else if file_type.is_file()
+                    && let Ok(rel_path) = entry.path().strip_prefix(&cache_root)
+                {
+                    files.push(unified_rel_path(&rel_path.to_string_lossy()));
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    })
+    .await
+    .map_err(|e| Error::Io(std::io::Error::other(e)))?
+}
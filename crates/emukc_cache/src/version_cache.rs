@@ -27,6 +27,11 @@ impl VersionCache {
     pub fn put(&self, key: String, value: Option<String>) {
         self.cache.lock().unwrap().put(key, value);
     }
+
+    /// Drop a cached version string.
+    pub fn remove(&self, key: &str) {
+        self.cache.lock().unwrap().pop(key);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,137 @@
+//! Bundle pack and unpack tests for Kache.
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use emukc_cache::{
+    BUNDLE_FORMAT, BUNDLE_MANIFEST, BundleEntry, BundleFilter, BundleManifest, Kache,
+};
+use tempfile::TempDir;
+
+fn setup_test_cache(temp_dir: &TempDir) -> Kache {
+    Kache::builder()
+        .with_cache_root(temp_dir.path().to_path_buf())
+        .with_content_cdn("http://127.0.0.1:9".to_string())
+        .with_gadgets_cdn("http://127.0.0.1:9".to_string())
+        .build()
+        .unwrap()
+}
+
+async fn store(cache: &Kache, temp_dir: &TempDir, rel_path: &str, version: &str, content: &[u8]) {
+    let path = temp_dir.path().join(rel_path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+    cache.set_version(rel_path, Some(version).filter(|v| !v.is_empty())).await.unwrap();
+    cache.record_digest(rel_path).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pack_and_unpack_selected_entries() {
+    let src_dir = TempDir::new().unwrap();
+    let src = setup_test_cache(&src_dir);
+    store(&src, &src_dir, "kcs2/js/main.js", "6.2.9.0", b"var a = 1;").await;
+    store(&src, &src_dir, "kcs2/resources/map/001/01_image.png", "", b"PNG-1-1").await;
+    store(&src, &src_dir, "kcs2/resources/map/002/01_image.png", "", b"PNG-2-1").await;
+    store(&src, &src_dir, "kcs2/resources/gauge/00101.json", "", b"{}").await;
+    store(&src, &src_dir, "kcs2/img/title.png", "1", b"broken").await;
+    std::fs::write(src_dir.path().join("kcs2/img/title.png"), b"broke").unwrap();
+    src.set_version("kcs2/img/gone.png", Some("1")).await.unwrap();
+
+    let bundle = src_dir.path().join("bundle.zip");
+    let filter = BundleFilter {
+        prefixes: vec!["kcs2/".to_string()],
+        areas: vec![1],
+        ..Default::default()
+    };
+    let report = src.pack(&bundle, &filter).await.unwrap();
+    assert_eq!(
+        report.packed,
+        vec![
+            "kcs2/js/main.js",
+            "kcs2/resources/gauge/00101.json",
+            "kcs2/resources/map/001/01_image.png",
+        ]
+    );
+    assert_eq!(report.missing, vec!["kcs2/img/gone.png"]);
+    assert_eq!(report.corrupted, vec!["kcs2/img/title.png"]);
+
+    let dst_dir = TempDir::new().unwrap();
+    let dst = setup_test_cache(&dst_dir);
+    store(&dst, &dst_dir, "kcs2/resources/gauge/00101.json", "", b"{}").await;
+
+    let report = dst.unpack(&bundle).await.unwrap();
+    assert_eq!(report.installed, vec!["kcs2/js/main.js", "kcs2/resources/map/001/01_image.png"]);
+    assert_eq!(report.unchanged, vec!["kcs2/resources/gauge/00101.json"]);
+    assert!(report.rejected.is_empty());
+
+    assert_eq!(
+        dst.get_cached_version("kcs2/js/main.js").await.unwrap().as_deref(),
+        Some("6.2.9.0")
+    );
+    let opt = emukc_cache::GetOption::new().disable_remote();
+    assert!(dst.get_with_opt("kcs2/js/main.js", "6.2.9.0", &opt).await.is_ok());
+    assert!(dst.verify(false).await.unwrap().is_clean());
+}
+
+#[tokio::test]
+async fn test_pack_by_path_list() {
+    let src_dir = TempDir::new().unwrap();
+    let src = setup_test_cache(&src_dir);
+    store(&src, &src_dir, "kcs2/js/main.js", "1", b"var a = 1;").await;
+    store(&src, &src_dir, "kcs2/img/title.png", "1", b"PNG").await;
+
+    let bundle = src_dir.path().join("bundle.zip");
+    let filter = BundleFilter {
+        paths: Some(HashSet::from(["kcs2/img/title.png".to_string()])),
+        ..Default::default()
+    };
+    let report = src.pack(&bundle, &filter).await.unwrap();
+    assert_eq!(report.packed, vec!["kcs2/img/title.png"]);
+    assert_eq!(report.bytes, 3);
+}
+
+#[tokio::test]
+async fn test_unpack_keeps_newer_and_rejects_bad_entries() {
+    let dir = TempDir::new().unwrap();
+    let bundle = dir.path().join("bundle.zip");
+    let entry = |path: &str, version: &str, content: &[u8]| BundleEntry {
+        path: path.to_string(),
+        version: Some(version.to_string()),
+        size: content.len() as u64,
+        md5: format!("{:x}", md5::compute(content)),
+    };
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        entries: vec![
+            entry("kcs2/js/main.js", "1", b"old"),
+            entry("kcs2/img/title.png", "1", b"PNG"),
+            entry("kcs2/../../evil.js", "1", b"evil"),
+        ],
+    };
+    {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&bundle).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, content) in [
+            ("files/kcs2/js/main.js", &b"old"[..]),
+            ("files/kcs2/img/title.png", b"PNG-tampered"),
+            ("files/kcs2/../../evil.js", b"evil"),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.start_file(BUNDLE_MANIFEST, options).unwrap();
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap()).unwrap();
+        zip.finish().unwrap();
+    }
+
+    let cache_dir = TempDir::new().unwrap();
+    let cache = setup_test_cache(&cache_dir);
+    store(&cache, &cache_dir, "kcs2/js/main.js", "2", b"new").await;
+
+    let report = cache.unpack(&bundle).await.unwrap();
+    assert!(report.installed.is_empty());
+    assert_eq!(report.kept, vec!["kcs2/js/main.js"]);
+    assert_eq!(report.rejected, vec!["kcs2/../../evil.js", "kcs2/img/title.png"]);
+    assert_eq!(std::fs::read(cache_dir.path().join("kcs2/js/main.js")).unwrap(), b"new");
+    assert!(!cache_dir.path().join("kcs2/img/title.png").exists());
+}
@@ -0,0 +1,100 @@
+//! Content digest and verify tests for Kache.
+
+use emukc_cache::{GetOption, Kache, KacheError, QUARANTINE_DIR};
+use tempfile::TempDir;
+
+/// A cache whose CDN refuses connections, so re-fetches fail without the network.
+fn setup_test_cache(temp_dir: &TempDir) -> Kache {
+    Kache::builder()
+        .with_cache_root(temp_dir.path().to_path_buf())
+        .with_content_cdn("http://127.0.0.1:9".to_string())
+        .with_gadgets_cdn("http://127.0.0.1:9".to_string())
+        .build()
+        .unwrap()
+}
+
+fn write_file(temp_dir: &TempDir, rel_path: &str, content: &[u8]) {
+    let path = temp_dir.path().join(rel_path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+#[tokio::test]
+async fn test_truncated_file_is_not_served() {
+    let temp = TempDir::new().unwrap();
+    let cache = setup_test_cache(&temp);
+
+    write_file(&temp, "kcs2/img/test.png", b"PNGDATA-complete");
+    cache.set_version("kcs2/img/test.png", Some("1")).await.unwrap();
+    cache.record_digest("kcs2/img/test.png").await.unwrap();
+
+    let opt = GetOption::new().disable_remote();
+    assert!(cache.get_with_opt("kcs2/img/test.png", "1", &opt).await.is_ok());
+
+    write_file(&temp, "kcs2/img/test.png", b"PNGDATA");
+    let result = cache.get_with_opt("kcs2/img/test.png", "1", &opt).await;
+    assert!(matches!(result, Err(KacheError::FileNotFound(_))), "got {result:?}");
+}
+
+#[tokio::test]
+async fn test_corrupted_file_is_not_served_after_restart() {
+    let temp = TempDir::new().unwrap();
+    {
+        let cache = setup_test_cache(&temp);
+        write_file(&temp, "kcs2/img/test.png", b"PNGDATA-one");
+        cache.record_digest("kcs2/img/test.png").await.unwrap();
+    }
+
+    // same size, different content
+    write_file(&temp, "kcs2/img/test.png", b"PNGDATA-two");
+    let cache = setup_test_cache(&temp);
+    let opt = GetOption::new().disable_remote();
+    let result = cache.get_with_opt("kcs2/img/test.png", "", &opt).await;
+    assert!(matches!(result, Err(KacheError::FileNotFound(_))), "got {result:?}");
+}
+
+#[tokio::test]
+async fn test_verify_reports_and_repairs() {
+    let temp = TempDir::new().unwrap();
+    let cache = setup_test_cache(&temp);
+
+    write_file(&temp, "kcs2/img/good.png", b"good");
+    cache.set_version("kcs2/img/good.png", Some("1")).await.unwrap();
+    cache.record_digest("kcs2/img/good.png").await.unwrap();
+
+    write_file(&temp, "kcs2/img/short.png", b"complete");
+    cache.set_version("kcs2/img/short.png", Some("1")).await.unwrap();
+    cache.record_digest("kcs2/img/short.png").await.unwrap();
+    write_file(&temp, "kcs2/img/short.png", b"comp");
+
+    write_file(&temp, "kcs2/img/old.png", b"old");
+    cache.set_version("kcs2/img/old.png", None).await.unwrap();
+
+    cache.set_version("kcs2/img/gone.png", Some("1")).await.unwrap();
+
+    write_file(&temp, "kcs2/js/main.js", b"var a = 1;");
+    write_file(&temp, "kcs2/img/error.png", b"<!DOCTYPE html><html>502</html>");
+
+    let report = cache.verify(false).await.unwrap();
+    assert_eq!(report.checked, 4);
+    assert_eq!(report.truncated, vec!["kcs2/img/short.png"]);
+    assert_eq!(report.missing, vec!["kcs2/img/gone.png"]);
+    assert_eq!(report.unrecorded, vec!["kcs2/img/old.png"]);
+    assert_eq!(report.orphaned, vec!["kcs2/img/error.png", "kcs2/js/main.js"]);
+    assert!(report.corrupted.is_empty());
+    assert!(report.quarantined.is_empty());
+    assert!(!report.is_clean());
+
+    let report = cache.verify(true).await.unwrap();
+    assert!(report.refetched.is_empty());
+    assert_eq!(report.quarantined, vec!["kcs2/img/short.png", "kcs2/img/error.png"]);
+    assert_eq!(report.recorded, vec!["kcs2/img/old.png", "kcs2/js/main.js"]);
+    assert!(temp.path().join(QUARANTINE_DIR).join("kcs2/img/short.png").is_file());
+    assert!(!temp.path().join("kcs2/img/error.png").exists());
+
+    let report = cache.verify(false).await.unwrap();
+    assert!(report.is_clean(), "got {report:?}");
+    assert!(report.unrecorded.is_empty());
+    assert_eq!(report.checked, 3);
+    assert!(cache.get_cached_version("kcs2/img/short.png").await.unwrap().is_none());
+}
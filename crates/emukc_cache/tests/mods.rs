@@ -0,0 +1,124 @@
+//! Layered mod pack tests for Kache.
+
+use std::path::Path;
+
+use emukc_cache::{GetOption, Kache, KacheError, LOOSE_MODS, MOD_MANIFEST, ModConflict};
+use tempfile::TempDir;
+use tokio::io::AsyncReadExt;
+
+fn setup_test_cache(cache_dir: &TempDir, mods_dir: &TempDir) -> Kache {
+    Kache::builder()
+        .with_cache_root(cache_dir.path().to_path_buf())
+        .with_mods_root(Some(mods_dir.path().to_path_buf()))
+        .with_content_cdn("http://127.0.0.1:9".to_string())
+        .with_gadgets_cdn("http://127.0.0.1:9".to_string())
+        .build()
+        .unwrap()
+}
+
+fn write(root: &Path, rel_path: &str, content: &str) {
+    let path = root.join(rel_path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn add_pack(mods_dir: &TempDir, id: &str, manifest: &str, files: &[(&str, &str)]) {
+    let root = mods_dir.path().join(id);
+    write(&root, MOD_MANIFEST, manifest);
+    for (rel_path, content) in files {
+        write(&root, rel_path, content);
+    }
+}
+
+async fn read(
+    cache: &Kache,
+    rel_path: &str,
+    profile_id: Option<i64>,
+) -> Result<String, KacheError> {
+    let opt = GetOption::new().disable_remote().with_profile(profile_id);
+    let mut f = cache.get_with_opt(rel_path, "", &opt).await?;
+    let mut content = String::new();
+    f.read_to_string(&mut content).await?;
+    Ok(content)
+}
+
+#[tokio::test]
+async fn test_mods_resolve_by_priority_and_profile() {
+    let cache_dir = TempDir::new().unwrap();
+    let mods_dir = TempDir::new().unwrap();
+    add_pack(
+        &mods_dir,
+        "english",
+        r#"{ "name": "English Patch", "priority": 10 }"#,
+        &[("kcs2/img/title.png", "english"), ("kcs2/js/main.js", "english")],
+    );
+    add_pack(
+        &mods_dir,
+        "skins",
+        r#"{ "name": "Skins", "priority": 5, "enabled": false }"#,
+        &[("kcs2/img/title.png", "skins"), ("kcs2/resources/ship/full/wildcard.png", "skin")],
+    );
+    write(mods_dir.path(), "kcs2/img/title.png", "loose");
+    write(mods_dir.path(), "kcs2/img/port.png", "loose");
+
+    let cache = setup_test_cache(&cache_dir, &mods_dir);
+    let ids: Vec<_> = cache.mod_packs().iter().map(|pack| pack.id.as_str()).collect();
+    assert_eq!(ids, vec!["english", "skins"]);
+
+    assert_eq!(read(&cache, "kcs2/img/title.png", None).await.unwrap(), "english");
+    assert_eq!(read(&cache, "kcs2/img/port.png", None).await.unwrap(), "loose");
+    assert!(read(&cache, "kcs2/resources/ship/full/0001_1234.png", None).await.is_err());
+
+    cache.set_mod_enabled(1, "english", Some(false)).await.unwrap();
+    cache.set_mod_enabled(1, "skins", Some(true)).await.unwrap();
+    assert_eq!(read(&cache, "kcs2/img/title.png", Some(1)).await.unwrap(), "skins");
+    assert_eq!(
+        read(&cache, "kcs2/resources/ship/full/0001_1234.png", Some(1)).await.unwrap(),
+        "skin"
+    );
+    assert_eq!(read(&cache, "kcs2/img/title.png", Some(2)).await.unwrap(), "english");
+
+    let conflicts = cache.mod_conflicts(Some(1)).await.unwrap();
+    assert_eq!(
+        conflicts,
+        vec![ModConflict {
+            path: "kcs2/img/title.png".to_string(),
+            winner: "skins".to_string(),
+            shadowed: vec![LOOSE_MODS.to_string()],
+        }]
+    );
+
+    cache.set_mod_enabled(1, "english", None).await.unwrap();
+    let status = cache.mods(Some(1)).await.unwrap();
+    assert!(status[0].enabled && !status[0].toggled);
+    assert!(status[1].enabled && status[1].toggled);
+    assert_eq!(read(&cache, "kcs2/img/title.png", Some(1)).await.unwrap(), "english");
+
+    assert!(matches!(
+        cache.set_mod_enabled(1, "missing", Some(true)).await,
+        Err(KacheError::ModNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_mods_outside_version_range_are_inactive() {
+    let cache_dir = TempDir::new().unwrap();
+    let mods_dir = TempDir::new().unwrap();
+    add_pack(
+        &mods_dir,
+        "old",
+        r#"{ "name": "Old Patch", "max_version": "5.9.9.9" }"#,
+        &[("kcs2/img/title.png", "old")],
+    );
+    std::fs::create_dir_all(mods_dir.path().join("broken")).unwrap();
+    write(mods_dir.path(), &format!("broken/{MOD_MANIFEST}"), "{");
+
+    let cache = setup_test_cache(&cache_dir, &mods_dir);
+    assert_eq!(cache.mod_packs().len(), 1);
+    assert_eq!(read(&cache, "kcs2/img/title.png", None).await.unwrap(), "old");
+
+    cache.set_version("kcs2/js/main.js", Some("6.2.9.0")).await.unwrap();
+    let status = cache.mods(None).await.unwrap();
+    assert!(!status[0].compatible);
+    assert!(read(&cache, "kcs2/img/title.png", None).await.is_err());
+}
@@ -3,8 +3,16 @@ pub mod profile;
 /// Entities for `EmuKC` user related stuff.
 pub mod user;
 
-/// Bootstrap the database with the necessary tables
+/// Bootstrap the database: apply pending schema migrations and create the
+/// tables that do not exist yet.
 pub async fn bootstrap(db: &sea_orm::DbConn) -> Result<(), sea_orm::error::DbErr> {
+    crate::migration::migrate(db).await?;
+
+    Ok(())
+}
+
+/// Create the tables of every entity that does not exist yet.
+pub(crate) async fn create_tables(db: &sea_orm::DbConn) -> Result<(), sea_orm::error::DbErr> {
     // user
     user::bootstrap(db).await?;
     // profile
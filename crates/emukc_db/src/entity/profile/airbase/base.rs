@@ -3,10 +3,22 @@
 
 use emukc_model::profile::airbase::{Airbase, AirbaseAction};
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
 #[derive(
-    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum, enumn::N,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    enumn::N,
+    Serialize,
+    Deserialize,
 )]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum Action {
@@ -23,7 +35,9 @@ pub enum Action {
 }
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "airbase")]
 pub struct Model {
     /// Instance ID
@@ -1,12 +1,25 @@
 //! Aircrafts belonging to an airbase.
 #![allow(missing_docs)]
 
+use chrono::{DateTime, Utc};
 use emukc_model::profile::airbase::{PlaneInfo, PlaneState};
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
 #[derive(
-    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum, enumn::N,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    enumn::N,
+    Serialize,
+    Deserialize,
 )]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum Status {
@@ -24,7 +37,9 @@ pub enum Status {
 }
 
 #[expect(missing_docs)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "plane_info")]
 pub struct Model {
     /// Slot id, slot item instance id
@@ -54,6 +69,12 @@ pub struct Model {
 
     /// Max count
     pub max_count: i64,
+
+    /// Relocation completion time, while `state` is `Reassigning`
+    pub relocate_until: Option<DateTime<Utc>>,
+
+    /// Last time `condition` changed
+    pub condition_at: Option<DateTime<Utc>>,
 }
 
 /// Relation
@@ -108,6 +129,8 @@ impl From<PlaneInfo> for ActiveModel {
             condition: ActiveValue::Set(value.condition),
             count: ActiveValue::Set(value.count),
             max_count: ActiveValue::Set(value.max_count),
+            relocate_until: ActiveValue::Set(value.relocate_until),
+            condition_at: ActiveValue::Set(value.condition_at),
         }
     }
 }
@@ -124,6 +147,8 @@ impl From<Model> for PlaneInfo {
             condition: value.condition,
             count: value.count,
             max_count: value.max_count,
+            relocate_until: value.relocate_until,
+            condition_at: value.condition_at,
         }
     }
 }
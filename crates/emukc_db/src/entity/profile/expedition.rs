@@ -3,9 +3,22 @@
 use chrono::{DateTime, Utc};
 use emukc_model::profile::expedition::ExpeditionState;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum Status {
     /// Not started
@@ -22,7 +35,9 @@ pub enum Status {
 }
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "expedition")]
 pub struct Model {
     /// Instance ID
@@ -3,9 +3,22 @@
 use chrono::{DateTime, Utc};
 use emukc_model::profile::fleet::{Fleet, FleetMissionContext, FleetMissionStatus};
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum MissionStatus {
     /// Idle
@@ -26,7 +39,9 @@ pub enum MissionStatus {
 }
 
 #[expect(missing_docs)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "fleet")]
 pub struct Model {
     /// Instance ID
@@ -2,9 +2,12 @@
 
 use emukc_model::profile::furniture::FurnitureConfig;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "furniture_config")]
 pub struct Model {
     /// Profile ID
@@ -1,9 +1,12 @@
 //! Furniture inventory entity
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "furniture_record")]
 pub struct Model {
     /// Instance ID
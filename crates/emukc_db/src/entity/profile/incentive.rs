@@ -3,10 +3,22 @@
 
 use emukc_model::kc2::{KcApiIncentiveMode, KcApiIncentiveType};
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Incentive type
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum, enumn::N,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    enumn::N,
+    Serialize,
+    Deserialize,
 )]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum IncentiveType {
@@ -33,7 +45,18 @@ pub enum IncentiveType {
 
 #[expect(missing_docs)]
 #[derive(
-    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum, enumn::N,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    enumn::N,
+    Serialize,
+    Deserialize,
 )]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum IncentiveMode {
@@ -46,7 +69,9 @@ pub enum IncentiveMode {
 }
 
 #[expect(missing_docs)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "incentive")]
 pub struct Model {
     /// Instance ID
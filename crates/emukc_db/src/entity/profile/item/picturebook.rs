@@ -2,9 +2,12 @@
 
 use emukc_model::profile::picture_book::PictureBookSlotItem;
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "slotitem_record")]
 pub struct Model {
     /// Instance ID
@@ -2,9 +2,12 @@
 
 use emukc_model::{kc2::KcApiSlotItem, profile::slot_item::SlotItem};
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "slot_item")]
 pub struct Model {
     /// Instance ID
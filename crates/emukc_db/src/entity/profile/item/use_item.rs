@@ -2,9 +2,12 @@
 
 use emukc_model::profile::user_item::UserItem;
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "use_item")]
 pub struct Model {
     /// instance ID
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 use emukc_model::{kc2::UserHQRank, profile::Profile};
 
@@ -20,7 +21,9 @@ pub mod settings;
 pub mod ship;
 
 #[expect(missing_docs)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "profile")]
 pub struct Model {
     /// Profile ID
@@ -1,9 +1,12 @@
 //! Practice rival details entities
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "rival_detail")]
 pub struct Model {
     /// instance id
@@ -2,9 +2,12 @@
 
 use emukc_model::profile::practice::RivalShip;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "rival_ship")]
 pub struct Model {
     /// ship instance ID
@@ -25,6 +28,10 @@ pub struct Model {
 
     /// Ship star, indicates the modernization level
     pub star: i64,
+
+    /// The ship this one mirrors, if the rival is a local profile
+    #[serde(default)]
+    pub source_ship_id: Option<i64>,
 }
 
 /// Relation
@@ -1,8 +1,9 @@
 //! Preset development item.
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Preset development item.
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "preset_dev_item")]
 pub struct Model {
     /// primary key
@@ -2,13 +2,26 @@
 use emukc_model::prelude::Kc3rdQuestPeriod;
 use emukc_time::KcTime;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub mod oneshot;
 pub mod periodic;
 pub mod progress;
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum Period {
     /// Oneshot
@@ -88,12 +101,12 @@ pub trait HasTimestampAndPeriod {
 
 /// Trait for entities that can be reset
 pub trait ShouldReset {
-    /// Check if the quest should be reset.
-    fn should_reset(&self) -> bool;
+    /// Check if the quest should be reset by `now`.
+    fn should_reset(&self, now: &chrono::DateTime<chrono::Utc>) -> bool;
 }
 
 impl<T: HasTimestampAndPeriod> ShouldReset for T {
-    fn should_reset(&self) -> bool {
+    fn should_reset(&self, now: &chrono::DateTime<chrono::Utc>) -> bool {
         let start_time = &self.timestamp();
         let reset_time = match self.period() {
             Period::Oneshot => return false,
@@ -106,7 +119,7 @@ impl<T: HasTimestampAndPeriod> ShouldReset for T {
             Period::Annually => KcTime::jst_next_year_day_one_0500(start_time),
         };
 
-        chrono::Utc::now() > reset_time
+        *now > reset_time
     }
 }
 
@@ -134,9 +147,22 @@ pub async fn bootstrap(db: &sea_orm::DatabaseConnection) -> Result<(), sea_orm::
 
 #[cfg(test)]
 mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
     use emukc_model::prelude::Kc3rdQuestPeriod;
 
-    use super::Period;
+    use super::{HasTimestampAndPeriod, Period, ShouldReset};
+
+    struct Record(DateTime<Utc>, Period);
+
+    impl HasTimestampAndPeriod for Record {
+        fn timestamp(&self) -> DateTime<Utc> {
+            self.0
+        }
+
+        fn period(&self) -> Period {
+            self.1
+        }
+    }
 
     #[test]
     fn try_from_all_known_variants_succeeds() {
@@ -175,4 +201,29 @@ mod tests {
             assert_eq!(period, back);
         }
     }
+
+    #[test]
+    fn should_reset_follows_the_given_time() {
+        // Wednesday 2024-05-15 12:00 JST
+        let start = Utc.with_ymd_and_hms(2024, 5, 15, 3, 0, 0).unwrap();
+
+        let daily = Record(start, Period::Daily);
+        assert!(!daily.should_reset(&(start + Duration::hours(10))));
+        assert!(daily.should_reset(&(start + Duration::hours(18))));
+
+        let weekly = Record(start, Period::Weekly);
+        assert!(!weekly.should_reset(&(start + Duration::days(4))));
+        assert!(weekly.should_reset(&(start + Duration::days(5))));
+
+        let monthly = Record(start, Period::Monthly);
+        assert!(!monthly.should_reset(&(start + Duration::days(16))));
+        assert!(monthly.should_reset(&(start + Duration::days(17))));
+
+        let quarterly = Record(start, Period::Quarterly);
+        assert!(!quarterly.should_reset(&(start + Duration::days(40))));
+        assert!(quarterly.should_reset(&(start + Duration::days(48))));
+
+        let oneshot = Record(start, Period::Oneshot);
+        assert!(!oneshot.should_reset(&(start + Duration::days(365))));
+    }
 }
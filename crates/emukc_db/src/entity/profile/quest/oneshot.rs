@@ -3,9 +3,12 @@
 use chrono::{DateTime, Utc};
 use emukc_model::profile::quest::QuestOneshotRecord;
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "quest_record_oneshot")]
 pub struct Model {
     /// Instance ID
@@ -3,11 +3,14 @@
 use chrono::{DateTime, Utc};
 use emukc_model::profile::quest::QuestPeriodicRecord;
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 use super::{HasTimestampAndPeriod, Period};
 
 #[expect(missing_docs)]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "quest_record_periodic")]
 pub struct Model {
     /// Instance ID
@@ -1,13 +1,16 @@
 //! Ship related entities
 use emukc_model::kc2::KcApiShip;
 use sea_orm::{ActiveValue, entity::prelude::*};
+use serde::{Deserialize, Serialize};
 
 pub mod morale_timer;
 pub mod picturebook;
 pub mod sp_effect_item;
 
 #[expect(missing_docs)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel)]
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, DeriveEntityModel, Serialize, Deserialize,
+)]
 #[sea_orm(table_name = "ship")]
 pub struct Model {
     /// Instance ID
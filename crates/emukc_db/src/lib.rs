@@ -3,6 +3,9 @@
 /// Entry point for the `emukc_db` crate
 pub mod entity;
 
+/// Versioned schema migrations
+pub mod migration;
+
 #[doc(hidden)]
 mod mem;
 
@@ -0,0 +1,326 @@
+//! Versioned schema migrations.
+//!
+//! The schema version is the number of migrations recorded in the
+//! `schema_migration` table. On every start pending migrations run in order,
+//! each in its own transaction, and then tables of entities added since are
+//! created from their models.
+//!
+//! A fresh database gets its tables straight from the entities and is stamped
+//! with the latest version. A database from before this table existed counts
+//! as version 0, so the early migrations check the columns they change.
+//!
+//! Migrations only change tables that already existed at the previous
+//! version; brand new tables come from the entities.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveValue, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
+    QueryOrder, Statement, TransactionTrait,
+};
+
+use crate::entity::create_tables;
+
+/// Applied migration entity
+pub mod record;
+
+mod steps;
+
+use steps::MIGRATIONS;
+
+/// A schema migration.
+#[async_trait]
+pub trait Migration: Send + Sync {
+    /// Name recorded in the `schema_migration` table.
+    fn name(&self) -> &'static str;
+
+    /// Apply the migration.
+    async fn up(&self, c: &DatabaseTransaction) -> Result<(), DbErr>;
+}
+
+/// A migration that has not been applied yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMigration {
+    /// Schema version reached by the migration
+    pub version: i64,
+
+    /// Migration name
+    pub name: &'static str,
+}
+
+/// Schema state of a database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaStatus {
+    /// Current version; `None` if the database predates versioning
+    pub current: Option<i64>,
+
+    /// Version this build migrates to
+    pub latest: i64,
+
+    /// Whether the database has no tables yet
+    pub fresh: bool,
+
+    /// Migrations already applied
+    pub applied: Vec<record::Model>,
+
+    /// Migrations still to apply
+    pub pending: Vec<PendingMigration>,
+}
+
+impl SchemaStatus {
+    /// Whether migrating would change existing data.
+    pub fn needs_migration(&self) -> bool {
+        !self.fresh && !self.pending.is_empty()
+    }
+}
+
+/// The schema version this build migrates to.
+pub fn latest_version() -> i64 {
+    MIGRATIONS.len() as i64
+}
+
+/// Read the schema state without changing anything.
+///
+/// # Arguments
+///
+/// * `db` - The database connection.
+pub async fn status(db: &DatabaseConnection) -> Result<SchemaStatus, DbErr> {
+    let tables = table_names(db).await?;
+    let versioned = tables.iter().any(|table| table == SCHEMA_MIGRATION_TABLE);
+    let fresh = tables.iter().all(|table| table == SCHEMA_MIGRATION_TABLE);
+
+    let applied = if versioned {
+        record::Entity::find().order_by_asc(record::Column::Version).all(db).await?
+    } else {
+        Vec::new()
+    };
+    let current = versioned.then(|| applied.last().map_or(0, |m| m.version));
+    let done = current.unwrap_or_default();
+
+    let pending = (done + 1..=latest_version())
+        .map(|version| PendingMigration {
+            version,
+            name: MIGRATIONS[version as usize - 1].name(),
+        })
+        .collect();
+
+    Ok(SchemaStatus {
+        current,
+        latest: latest_version(),
+        fresh,
+        applied,
+        pending,
+    })
+}
+
+/// Bring the schema up to date.
+///
+/// # Arguments
+///
+/// * `db` - The database connection.
+///
+/// # Returns
+///
+/// The migrations that were applied; empty for fresh databases.
+pub async fn migrate(db: &DatabaseConnection) -> Result<Vec<PendingMigration>, DbErr> {
+    let status = status(db).await?;
+    create_schema_migration_table(db).await?;
+
+    if status.fresh {
+        create_tables(db).await?;
+        let txn = db.begin().await?;
+        for pending in &status.pending {
+            record_migration(&txn, pending).await?;
+        }
+        txn.commit().await?;
+
+        return Ok(Vec::new());
+    }
+
+    for pending in &status.pending {
+        let txn = db.begin().await?;
+        MIGRATIONS[pending.version as usize - 1].up(&txn).await?;
+        record_migration(&txn, pending).await?;
+        txn.commit().await?;
+    }
+    create_tables(db).await?;
+
+    Ok(status.pending)
+}
+
+/// Write a consistent copy of the database to `path`.
+///
+/// # Arguments
+///
+/// * `db` - The database connection.
+/// * `path` - The backup file, which must not exist.
+pub async fn backup(db: &DatabaseConnection, path: &str) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "VACUUM INTO ?",
+        [path.into()],
+    ))
+    .await?;
+
+    Ok(())
+}
+
+const SCHEMA_MIGRATION_TABLE: &str = "schema_migration";
+
+async fn create_schema_migration_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let schema = sea_orm::Schema::new(db.get_database_backend());
+    let stmt = schema.create_table_from_entity(record::Entity).if_not_exists().to_owned();
+    db.execute(db.get_database_backend().build(&stmt)).await?;
+
+    Ok(())
+}
+
+async fn record_migration(
+    c: &DatabaseTransaction,
+    pending: &PendingMigration,
+) -> Result<(), DbErr> {
+    record::Entity::insert(record::ActiveModel {
+        version: ActiveValue::Set(pending.version),
+        name: ActiveValue::Set(pending.name.to_string()),
+        applied_at: ActiveValue::Set(Utc::now()),
+    })
+    .exec(c)
+    .await?;
+
+    Ok(())
+}
+
+async fn table_names<C>(c: &C) -> Result<Vec<String>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    c.query_all(Statement::from_string(
+        c.get_database_backend(),
+        r#"SELECT "name" FROM "sqlite_master" WHERE "type" = 'table' AND "name" NOT LIKE 'sqlite_%'"#
+            .to_string(),
+    ))
+    .await?
+    .into_iter()
+    .map(|row| row.try_get("", "name"))
+    .collect()
+}
+
+/// Column names of `table`, empty if the table does not exist.
+async fn column_names<C>(c: &C, table: &str) -> Result<Vec<String>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    c.query_all(Statement::from_string(
+        c.get_database_backend(),
+        format!(r#"PRAGMA table_info("{table}")"#),
+    ))
+    .await?
+    .into_iter()
+    .map(|row| row.try_get("", "name"))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn empty_db() -> DatabaseConnection {
+        sea_orm::Database::connect("sqlite::memory:").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn fresh_database_is_stamped_latest() {
+        let db = empty_db().await;
+        let before = status(&db).await.unwrap();
+        assert!(before.fresh);
+        assert_eq!(before.current, None);
+        assert!(!before.needs_migration());
+
+        assert!(migrate(&db).await.unwrap().is_empty());
+
+        let after = status(&db).await.unwrap();
+        assert_eq!(after.current, Some(latest_version()));
+        assert_eq!(after.applied.len() as i64, latest_version());
+        assert!(after.pending.is_empty());
+        assert!(column_names(&db, "map_record").await.unwrap().contains(&"unlocked".to_string()));
+    }
+
+    #[tokio::test]
+    async fn unversioned_database_runs_every_migration() {
+        let db = empty_db().await;
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"CREATE TABLE "map_record" ("id" integer NOT NULL PRIMARY KEY, "variant_key" text NULL)"#
+                .to_string(),
+        ))
+        .await
+        .unwrap();
+
+        let before = status(&db).await.unwrap();
+        assert!(!before.fresh);
+        assert_eq!(before.current, None);
+        assert!(before.needs_migration());
+
+        let applied = migrate(&db).await.unwrap();
+        assert_eq!(applied.len() as i64, latest_version());
+        assert_eq!(applied[0].version, 1);
+
+        let columns = column_names(&db, "map_record").await.unwrap();
+        assert!(columns.contains(&"stage_id".to_string()));
+        assert!(columns.contains(&"unlocked".to_string()));
+        assert!(columns.contains(&"gimmick_state".to_string()));
+        assert!(!table_names(&db).await.unwrap().is_empty());
+
+        // a second run has nothing left to do
+        assert!(migrate(&db).await.unwrap().is_empty());
+        assert_eq!(status(&db).await.unwrap().current, Some(latest_version()));
+    }
+
+    #[tokio::test]
+    async fn rival_ship_gains_source_ship_id() {
+        let db = empty_db().await;
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"CREATE TABLE "rival_ship" ("id" integer NOT NULL PRIMARY KEY, "profile_id" integer NOT NULL, "rival_id" integer NOT NULL, "mst_id" integer NOT NULL, "level" integer NOT NULL, "star" integer NOT NULL)"#
+                .to_string(),
+        ))
+        .await
+        .unwrap();
+
+        migrate(&db).await.unwrap();
+
+        let columns = column_names(&db, "rival_ship").await.unwrap();
+        assert!(columns.contains(&"source_ship_id".to_string()));
+    }
+
+    #[tokio::test]
+    async fn plane_info_gains_timers() {
+        use crate::entity::profile::airbase::plane;
+
+        let db = empty_db().await;
+        for sql in [
+            r#"CREATE TABLE "plane_info" ("slot_id" integer NOT NULL PRIMARY KEY, "profile_id" integer NOT NULL, "area_id" integer NOT NULL, "rid" integer NOT NULL, "squadron_id" integer NOT NULL, "state" integer NOT NULL, "condition" integer NOT NULL, "count" integer NOT NULL, "max_count" integer NOT NULL)"#,
+            r#"INSERT INTO "plane_info" VALUES (7, 1, 6, 1, 1, 1, 1, 18, 18)"#,
+        ] {
+            db.execute(Statement::from_string(db.get_database_backend(), sql.to_string()))
+                .await
+                .unwrap();
+        }
+
+        migrate(&db).await.unwrap();
+
+        let planes = plane::Entity::find().all(&db).await.unwrap();
+        assert_eq!(planes.len(), 1);
+        assert_eq!(planes[0].count, 18);
+        assert_eq!(planes[0].relocate_until, None);
+        assert_eq!(planes[0].condition_at, None);
+    }
+
+    #[test]
+    fn migration_names_are_unique() {
+        let mut names: Vec<_> = MIGRATIONS.iter().map(|m| m.name()).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), MIGRATIONS.len());
+    }
+}
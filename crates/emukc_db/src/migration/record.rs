@@ -0,0 +1,26 @@
+//! Applied schema migration entity
+
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[expect(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "schema_migration")]
+pub struct Model {
+    /// Schema version reached by the migration
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub version: i64,
+
+    /// Migration name
+    pub name: String,
+
+    /// When the migration was applied
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Relation
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,160 @@
+//! The migrations, oldest first.
+
+use async_trait::async_trait;
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DbErr, Statement};
+
+use super::{Migration, column_names};
+
+/// Every migration in the order they are applied; version `n` is entry `n - 1`.
+pub(super) const MIGRATIONS: &[&dyn Migration] = &[
+    &MapRecordStageId,
+    &MapRecordUnlocked,
+    &RivalShipSourceShipId,
+    &MapRecordGimmickState,
+    &PlaneInfoTimers,
+];
+
+/// Rename `map_record.variant_key` to `stage_id`.
+struct MapRecordStageId;
+
+#[async_trait]
+impl Migration for MapRecordStageId {
+    fn name(&self) -> &'static str {
+        "map_record_stage_id"
+    }
+
+    async fn up(&self, c: &DatabaseTransaction) -> Result<(), DbErr> {
+        let backend = c.get_database_backend();
+        let columns = column_names(c, "map_record").await?;
+        if columns.is_empty() {
+            return Ok(());
+        }
+
+        if !columns.iter().any(|column| column == "stage_id") {
+            c.execute(Statement::from_string(
+                backend,
+                r#"ALTER TABLE "map_record" ADD COLUMN "stage_id" TEXT"#.to_string(),
+            ))
+            .await?;
+        }
+
+        if columns.iter().any(|column| column == "variant_key") {
+            c.execute(Statement::from_string(
+                backend,
+                r#"UPDATE "map_record" SET "stage_id" = COALESCE("stage_id", "variant_key") WHERE "variant_key" IS NOT NULL"#
+                    .to_string(),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Add `map_record.unlocked`.
+///
+/// Defaults to `true` so existing accounts keep access to their maps.
+struct MapRecordUnlocked;
+
+#[async_trait]
+impl Migration for MapRecordUnlocked {
+    fn name(&self) -> &'static str {
+        "map_record_unlocked"
+    }
+
+    async fn up(&self, c: &DatabaseTransaction) -> Result<(), DbErr> {
+        let columns = column_names(c, "map_record").await?;
+        if columns.is_empty() || columns.iter().any(|column| column == "unlocked") {
+            return Ok(());
+        }
+
+        c.execute(Statement::from_string(
+            c.get_database_backend(),
+            r#"ALTER TABLE "map_record" ADD COLUMN "unlocked" INTEGER NOT NULL DEFAULT 1"#
+                .to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Add `rival_ship.source_ship_id`.
+struct RivalShipSourceShipId;
+
+#[async_trait]
+impl Migration for RivalShipSourceShipId {
+    fn name(&self) -> &'static str {
+        "rival_ship_source_ship_id"
+    }
+
+    async fn up(&self, c: &DatabaseTransaction) -> Result<(), DbErr> {
+        let columns = column_names(c, "rival_ship").await?;
+        if columns.is_empty() || columns.iter().any(|column| column == "source_ship_id") {
+            return Ok(());
+        }
+
+        c.execute(Statement::from_string(
+            c.get_database_backend(),
+            r#"ALTER TABLE "rival_ship" ADD COLUMN "source_ship_id" INTEGER NULL"#.to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Add `map_record.gimmick_state`.
+struct MapRecordGimmickState;
+
+#[async_trait]
+impl Migration for MapRecordGimmickState {
+    fn name(&self) -> &'static str {
+        "map_record_gimmick_state"
+    }
+
+    async fn up(&self, c: &DatabaseTransaction) -> Result<(), DbErr> {
+        let columns = column_names(c, "map_record").await?;
+        if columns.is_empty() || columns.iter().any(|column| column == "gimmick_state") {
+            return Ok(());
+        }
+
+        c.execute(Statement::from_string(
+            c.get_database_backend(),
+            r#"ALTER TABLE "map_record" ADD COLUMN "gimmick_state" TEXT NULL"#.to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Add `plane_info.relocate_until` and `plane_info.condition_at`.
+struct PlaneInfoTimers;
+
+#[async_trait]
+impl Migration for PlaneInfoTimers {
+    fn name(&self) -> &'static str {
+        "plane_info_timers"
+    }
+
+    async fn up(&self, c: &DatabaseTransaction) -> Result<(), DbErr> {
+        let columns = column_names(c, "plane_info").await?;
+        if columns.is_empty() {
+            return Ok(());
+        }
+
+        for column in ["relocate_until", "condition_at"] {
+            if columns.iter().any(|existing| existing == column) {
+                continue;
+            }
+            c.execute(Statement::from_string(
+                c.get_database_backend(),
+                format!(r#"ALTER TABLE "plane_info" ADD COLUMN "{column}" TEXT NULL"#),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -190,6 +190,7 @@ CREATE TABLE "map_record" (
             selected_rank: ActiveValue::Set(entity::profile::map_record::SelectedRank::NotSet),
             event_state: ActiveValue::Set(None),
             unlocked: ActiveValue::Set(true),
+            gimmick_state: ActiveValue::Set(None),
         }
         .insert(&db)
         .await
@@ -1,7 +1,7 @@
 //! Gameplay errors
 
 use emukc_db::sea_orm;
-use emukc_model::thirdparty::reward::RewardError;
+use emukc_model::{codex::equip::EquipError, thirdparty::reward::RewardError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -45,6 +45,12 @@ pub enum GameplayError {
     #[error(transparent)]
     Reward(#[from] RewardError),
 
+    #[error(transparent)]
+    Equip(#[from] EquipError),
+
     #[error("Locked: {0}")]
     Locked(String),
+
+    #[error("Invalid profile archive: {0}")]
+    InvalidArchive(String),
 }
@@ -1,14 +1,43 @@
 use async_trait::async_trait;
+use emukc_battle::{BattleAirBaseSquadron, BattleAirBaseWave};
 use emukc_db::{
     entity::profile::airbase::{base, plane as plane_db},
     sea_orm::{ActiveValue, QueryOrder, TransactionTrait, entity::prelude::*},
 };
-use emukc_model::profile::airbase::Airbase;
+use emukc_model::{
+    codex::Codex,
+    kc2::{KcApiAirBase, KcApiPlaneInfo, KcUseItemType, MaterialCategory},
+    prelude::ApiMstSlotitem,
+    profile::{
+        airbase::{Airbase, PlaneInfo},
+        material::Material,
+    },
+};
+use emukc_time::chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::{err::GameplayError, gameplay::HasContext};
+use crate::{
+    err::GameplayError,
+    game::{
+        map::{active_map_catalog, get_map_records_impl},
+        material::deduct_material_impl,
+        slot_item::find_slot_item_impl,
+        use_item::deduct_use_item_impl,
+    },
+    gameplay::HasContext,
+};
 
 mod plane;
 
+/// An airbase sortied to strike two cells of the next map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AirBaseStrike {
+    /// The airbase ID.
+    pub rid: i64,
+    /// Cells hit by the first and second wave.
+    pub cells: [i64; 2],
+}
+
 /// A trait for airbase related gameplay.
 #[async_trait]
 pub trait AirbaseOps {
@@ -32,6 +61,119 @@ pub trait AirbaseOps {
     ///
     /// - `profile_id`: The profile ID.
     async fn get_airbases(&self, profile_id: i64) -> Result<Vec<Airbase>, GameplayError>;
+
+    /// Get airbases with their squadrons.
+    ///
+    /// Every area with an unlocked map that allows land-based air squadrons
+    /// gets its first airbase. Finished relocations are cleared and squadron
+    /// conditions recovered before building the response.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    async fn get_air_corps(&self, profile_id: i64) -> Result<Vec<KcApiAirBase>, GameplayError>;
+
+    /// Slot item IDs still relocating after leaving a squadron.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    async fn get_relocating_planes(&self, profile_id: i64) -> Result<Vec<i64>, GameplayError>;
+
+    /// Deploy planes to squadrons of an airbase.
+    ///
+    /// A plane already in the squadron starts relocating. Deploying costs
+    /// `squadron size × api_cost` bauxite per plane.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `area_id`: The area ID.
+    /// - `rid`: The airbase ID.
+    /// - `assignments`: `(squadron_id, slot_item_id)` pairs, `-1` removes the plane.
+    async fn set_airbase_planes(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        rid: i64,
+        assignments: &[(i64, i64)],
+    ) -> Result<(KcApiAirBase, Material), GameplayError>;
+
+    /// Set airbase actions.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `area_id`: The area ID.
+    /// - `actions`: `(rid, action kind)` pairs.
+    async fn set_airbase_actions(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        actions: &[(i64, i64)],
+    ) -> Result<(), GameplayError>;
+
+    /// Resupply squadrons of an airbase to their full plane count.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `area_id`: The area ID.
+    /// - `rid`: The airbase ID.
+    /// - `squadron_ids`: The squadrons to resupply.
+    async fn supply_airbase(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        rid: i64,
+        squadron_ids: &[i64],
+    ) -> Result<(KcApiAirBase, Material), GameplayError>;
+
+    /// Rename an airbase.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `area_id`: The area ID.
+    /// - `rid`: The airbase ID.
+    /// - `name`: The new name.
+    async fn rename_airbase(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        rid: i64,
+        name: &str,
+    ) -> Result<(), GameplayError>;
+
+    /// Open the next airbase of an area, consuming a construction corps.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `area_id`: The area ID.
+    async fn expand_airbase(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+    ) -> Result<KcApiAirBase, GameplayError>;
+
+    /// Sortie airbases ahead of the next map start.
+    ///
+    /// Every airbase must be set to attack and have planes. Fuel and ammo for
+    /// the deployed planes are spent now; the strikes apply to the next sortie
+    /// into `area_id`.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `area_id`: The area ID.
+    /// - `strikes`: The sortied airbases and their target cells.
+    async fn start_air_base(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        strikes: &[AirBaseStrike],
+    ) -> Result<Material, GameplayError>;
 }
 
 #[async_trait]
@@ -74,6 +216,225 @@ impl<T: HasContext + ?Sized> AirbaseOps for T {
 
         Ok(airbases)
     }
+
+    async fn get_air_corps(&self, profile_id: i64) -> Result<Vec<KcApiAirBase>, GameplayError> {
+        let codex = self.codex();
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        ensure_area_airbases_impl(&tx, codex, profile_id).await?;
+        settle_planes_impl(&tx, profile_id, self.clock().now(profile_id)).await?;
+
+        let mut corps = Vec::new();
+        for base in get_airbases_impl(&tx, profile_id).await? {
+            corps.push(build_air_base_impl(&tx, base).await?);
+        }
+
+        tx.commit().await?;
+
+        Ok(corps)
+    }
+
+    async fn get_relocating_planes(&self, profile_id: i64) -> Result<Vec<i64>, GameplayError> {
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let planes = settle_planes_impl(&tx, profile_id, self.clock().now(profile_id)).await?;
+
+        tx.commit().await?;
+
+        Ok(planes
+            .iter()
+            .filter(|plane| plane.state == plane_db::Status::Reassigning)
+            .map(|plane| plane.slot_id)
+            .collect())
+    }
+
+    async fn set_airbase_planes(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        rid: i64,
+        assignments: &[(i64, i64)],
+    ) -> Result<(KcApiAirBase, Material), GameplayError> {
+        let codex = self.codex();
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let now = self.clock().now(profile_id);
+        settle_planes_impl(&tx, profile_id, now).await?;
+        let base = find_airbase_impl(&tx, profile_id, area_id, rid).await?;
+
+        let mut bauxite = 0;
+        for (squadron_id, slot_id) in assignments {
+            bauxite +=
+                set_squadron_plane_impl(&tx, codex, &base, *squadron_id, *slot_id, now).await?;
+        }
+        let material =
+            deduct_material_impl(&tx, profile_id, &[(MaterialCategory::Bauxite, bauxite)]).await?;
+
+        let base = update_airbase_distance_impl(&tx, codex, base).await?;
+        let api_base = build_air_base_impl(&tx, base).await?;
+
+        tx.commit().await?;
+
+        Ok((api_base, material.into()))
+    }
+
+    async fn set_airbase_actions(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        actions: &[(i64, i64)],
+    ) -> Result<(), GameplayError> {
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        for (rid, action) in actions {
+            let action = base::Action::n(*action as i32)
+                .ok_or_else(|| GameplayError::WrongType(format!("airbase action {action}")))?;
+            let base = find_airbase_impl(&tx, profile_id, area_id, *rid).await?;
+            let mut am: base::ActiveModel = base.into();
+            am.action = ActiveValue::Set(action);
+            am.update(&tx).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn supply_airbase(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        rid: i64,
+        squadron_ids: &[i64],
+    ) -> Result<(KcApiAirBase, Material), GameplayError> {
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let base = find_airbase_impl(&tx, profile_id, area_id, rid).await?;
+        let planes = get_squadrons_impl(&tx, &base).await?;
+
+        let mut missing = 0;
+        for plane in planes.into_iter().filter(|p| squadron_ids.contains(&p.squadron_id)) {
+            if plane.count >= plane.max_count {
+                continue;
+            }
+            missing += plane.max_count - plane.count;
+            let max_count = plane.max_count;
+            let mut am: plane_db::ActiveModel = plane.into();
+            am.count = ActiveValue::Set(max_count);
+            am.update(&tx).await?;
+        }
+        let material = deduct_material_impl(
+            &tx,
+            profile_id,
+            &[
+                (MaterialCategory::Fuel, missing * plane::SUPPLY_FUEL_PER_PLANE),
+                (MaterialCategory::Bauxite, missing * plane::SUPPLY_BAUXITE_PER_PLANE),
+            ],
+        )
+        .await?;
+
+        let api_base = build_air_base_impl(&tx, base).await?;
+
+        tx.commit().await?;
+
+        Ok((api_base, material.into()))
+    }
+
+    async fn rename_airbase(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        rid: i64,
+        name: &str,
+    ) -> Result<(), GameplayError> {
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let base = find_airbase_impl(&tx, profile_id, area_id, rid).await?;
+        let mut am: base::ActiveModel = base.into();
+        am.name = ActiveValue::Set(name.to_owned());
+        am.update(&tx).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn expand_airbase(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+    ) -> Result<KcApiAirBase, GameplayError> {
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let count = base::Entity::find()
+            .filter(base::Column::ProfileId.eq(profile_id))
+            .filter(base::Column::AreaId.eq(area_id))
+            .count(&tx)
+            .await? as i64;
+        if count == 0 {
+            return Err(GameplayError::EntryNotFound(format!("airbase in area {area_id}")));
+        }
+        if count >= plane::MAX_AIRBASES_PER_AREA {
+            return Err(GameplayError::CapacityExceeded(plane::MAX_AIRBASES_PER_AREA));
+        }
+
+        deduct_use_item_impl(&tx, profile_id, KcUseItemType::ConstCorps as i64, 1).await?;
+        let base = unlock_airbase_impl(&tx, profile_id, area_id, count + 1).await?;
+        let api_base = build_air_base_impl(&tx, base).await?;
+
+        tx.commit().await?;
+
+        Ok(api_base)
+    }
+
+    async fn start_air_base(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        strikes: &[AirBaseStrike],
+    ) -> Result<Material, GameplayError> {
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let mut planes = 0;
+        for strike in strikes {
+            let base = find_airbase_impl(&tx, profile_id, area_id, strike.rid).await?;
+            if base.action != base::Action::Attack {
+                return Err(GameplayError::WrongType(format!(
+                    "airbase {area_id}-{} is not set to attack",
+                    strike.rid
+                )));
+            }
+            let count: i64 = get_squadrons_impl(&tx, &base).await?.iter().map(|p| p.count).sum();
+            if count <= 0 {
+                return Err(GameplayError::WrongType(format!(
+                    "airbase {area_id}-{} has no planes",
+                    strike.rid
+                )));
+            }
+            planes += count;
+        }
+        let (fuel, ammo) = plane::sortie_cost(planes);
+        let material = deduct_material_impl(
+            &tx,
+            profile_id,
+            &[(MaterialCategory::Fuel, fuel), (MaterialCategory::Ammo, ammo)],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        self.sortie_store().insert_air_base_strikes(profile_id, area_id, strikes.to_vec());
+
+        Ok(material.into())
+    }
 }
 
 pub(crate) async fn unlock_airbase_impl<C>(
@@ -129,6 +490,375 @@ where
     Ok(models)
 }
 
+pub(crate) async fn find_airbase_impl<C>(
+    c: &C,
+    profile_id: i64,
+    area_id: i64,
+    rid: i64,
+) -> Result<base::Model, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    base::Entity::find()
+        .filter(base::Column::ProfileId.eq(profile_id))
+        .filter(base::Column::AreaId.eq(area_id))
+        .filter(base::Column::Rid.eq(rid))
+        .one(c)
+        .await?
+        .ok_or_else(|| GameplayError::EntryNotFound(format!("airbase {area_id}-{rid}")))
+}
+
+/// Planes deployed in the squadrons of an airbase, ordered by squadron.
+pub(crate) async fn get_squadrons_impl<C>(
+    c: &C,
+    base: &base::Model,
+) -> Result<Vec<plane_db::Model>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let planes = plane_db::Entity::find()
+        .filter(plane_db::Column::ProfileId.eq(base.profile_id))
+        .filter(plane_db::Column::AreaId.eq(base.area_id))
+        .filter(plane_db::Column::Rid.eq(base.rid))
+        .filter(plane_db::Column::State.eq(plane_db::Status::Assigned))
+        .order_by_asc(plane_db::Column::SquadronId)
+        .all(c)
+        .await?;
+
+    Ok(planes)
+}
+
+/// Squadrons of an airbase as they fly into battle.
+async fn battle_squadrons_impl<C>(
+    c: &C,
+    base: &base::Model,
+) -> Result<Vec<BattleAirBaseSquadron>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mut squadrons = Vec::new();
+    for plane in get_squadrons_impl(c, base).await? {
+        let item = find_slot_item_impl(c, plane.slot_id).await?;
+        squadrons.push(BattleAirBaseSquadron {
+            slot_item: item.into(),
+            count: plane.count,
+        });
+    }
+
+    Ok(squadrons)
+}
+
+/// Land-based air waves striking `cell_no`, in launch order.
+pub(crate) async fn build_air_base_waves_impl<C>(
+    c: &C,
+    profile_id: i64,
+    area_id: i64,
+    strikes: &[AirBaseStrike],
+    cell_no: i64,
+) -> Result<Vec<BattleAirBaseWave>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mut waves = Vec::new();
+    for strike in strikes {
+        let launches = strike.cells.iter().filter(|cell| **cell == cell_no).count();
+        if launches == 0 {
+            continue;
+        }
+        let base = find_airbase_impl(c, profile_id, area_id, strike.rid).await?;
+        let squadrons = battle_squadrons_impl(c, &base).await?;
+        for _ in 0..launches {
+            waves.push(BattleAirBaseWave {
+                base_id: strike.rid,
+                squadrons: squadrons.clone(),
+            });
+        }
+    }
+
+    Ok(waves)
+}
+
+/// Airbases of an area under air raid; only air defense squadrons intercept.
+pub(crate) async fn build_air_raid_bases_impl<C>(
+    c: &C,
+    profile_id: i64,
+    area_id: i64,
+) -> Result<Vec<BattleAirBaseWave>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mut bases = Vec::new();
+    for base in get_airbases_impl(c, profile_id).await?.into_iter().filter(|b| b.area_id == area_id)
+    {
+        let squadrons = if base.action == base::Action::Defense {
+            battle_squadrons_impl(c, &base).await?
+        } else {
+            Vec::new()
+        };
+        bases.push(BattleAirBaseWave {
+            base_id: base.rid,
+            squadrons,
+        });
+    }
+
+    Ok(bases)
+}
+
+/// Write back the planes every airbase has left after its last wave.
+pub(crate) async fn settle_air_base_waves_impl<C>(
+    c: &C,
+    profile_id: i64,
+    area_id: i64,
+    waves: &[BattleAirBaseWave],
+) -> Result<(), GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mut settled = Vec::new();
+    for wave in waves.iter().rev() {
+        if settled.contains(&wave.base_id) {
+            continue;
+        }
+        settled.push(wave.base_id);
+        for squadron in &wave.squadrons {
+            let Some(plane) = plane_db::Entity::find()
+                .filter(plane_db::Column::ProfileId.eq(profile_id))
+                .filter(plane_db::Column::AreaId.eq(area_id))
+                .filter(plane_db::Column::Rid.eq(wave.base_id))
+                .filter(plane_db::Column::SlotId.eq(squadron.slot_item.api_id))
+                .filter(plane_db::Column::State.eq(plane_db::Status::Assigned))
+                .one(c)
+                .await?
+            else {
+                continue;
+            };
+            if plane.count == squadron.count {
+                continue;
+            }
+            let mut am: plane_db::ActiveModel = plane.into();
+            am.count = ActiveValue::Set(squadron.count.max(0));
+            am.update(c).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Open the first airbase of every area with an unlocked LBAS map.
+async fn ensure_area_airbases_impl<C>(
+    c: &C,
+    codex: &Codex,
+    profile_id: i64,
+) -> Result<(), GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let unlocked: Vec<i64> = get_map_records_impl(c, profile_id)
+        .await?
+        .into_iter()
+        .filter(|record| record.unlocked)
+        .map(|record| record.map_id)
+        .collect();
+    let catalog = active_map_catalog(codex);
+    let mut areas: Vec<i64> = catalog
+        .known_maps()
+        .into_iter()
+        .filter(|def| def.airbase_count.is_some_and(|n| n > 0) && unlocked.contains(&def.map_id))
+        .map(|def| def.map_id / 10)
+        .collect();
+    areas.sort_unstable();
+    areas.dedup();
+
+    for area_id in areas {
+        unlock_airbase_impl(c, profile_id, area_id, 1).await?;
+    }
+
+    Ok(())
+}
+
+/// Finish due relocations and persist recovered conditions.
+///
+/// # Returns
+///
+/// The remaining planes of the profile.
+pub(crate) async fn settle_planes_impl<C>(
+    c: &C,
+    profile_id: i64,
+    now: DateTime<Utc>,
+) -> Result<Vec<plane_db::Model>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let planes =
+        plane_db::Entity::find().filter(plane_db::Column::ProfileId.eq(profile_id)).all(c).await?;
+
+    let mut remaining = Vec::with_capacity(planes.len());
+    for plane in planes {
+        if plane::relocation_done(&plane, now) {
+            plane.delete(c).await?;
+            continue;
+        }
+        let (condition, condition_at) = plane::recover_condition(&plane, now);
+        if condition == plane.condition {
+            remaining.push(plane);
+            continue;
+        }
+        let mut am: plane_db::ActiveModel = plane.into();
+        am.condition = ActiveValue::Set(condition);
+        am.condition_at = ActiveValue::Set(condition_at);
+        remaining.push(am.update(c).await?);
+    }
+
+    Ok(remaining)
+}
+
+/// Reject a slot item deployed to, or relocating from, an airbase squadron.
+///
+/// A plane whose relocation has finished by `now` is released on the spot.
+pub(crate) async fn ensure_not_deployed_impl<C>(
+    c: &C,
+    slot_id: i64,
+    now: DateTime<Utc>,
+) -> Result<(), GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let Some(plane) = plane_db::Entity::find_by_id(slot_id).one(c).await? else {
+        return Ok(());
+    };
+    if plane::relocation_done(&plane, now) {
+        plane.delete(c).await?;
+        return Ok(());
+    }
+
+    Err(GameplayError::Locked(format!("slot item {slot_id} is deployed to an airbase")))
+}
+
+/// Put a plane into a squadron, relocating the previous one.
+///
+/// # Returns
+///
+/// The bauxite cost of the deployment.
+async fn set_squadron_plane_impl<C>(
+    c: &C,
+    codex: &Codex,
+    base: &base::Model,
+    squadron_id: i64,
+    slot_id: i64,
+    now: DateTime<Utc>,
+) -> Result<i64, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    if !(1..=plane::SQUADRON_COUNT).contains(&squadron_id) {
+        return Err(GameplayError::EntryNotFound(format!("squadron {squadron_id}")));
+    }
+
+    let current = get_squadrons_impl(c, base)
+        .await?
+        .into_iter()
+        .find(|plane| plane.squadron_id == squadron_id);
+    if current.as_ref().is_some_and(|plane| plane.slot_id == slot_id) {
+        return Ok(0);
+    }
+
+    let cost = if slot_id > 0 {
+        let item = find_slot_item_impl(c, slot_id).await?;
+        if item.profile_id != base.profile_id {
+            return Err(GameplayError::EntryNotFound(format!("slot item {slot_id}")));
+        }
+        if !plane::is_deployable(item.type3) {
+            return Err(GameplayError::WrongType(format!("slot item {slot_id} is not a plane")));
+        }
+        if item.equip_on > 0 || plane_db::Entity::find_by_id(slot_id).one(c).await?.is_some() {
+            return Err(GameplayError::Locked(format!("slot item {slot_id} is in use")));
+        }
+        let mst = codex.find::<ApiMstSlotitem>(&item.mst_id)?;
+        let size = plane::squadron_size(item.type3);
+
+        plane_db::ActiveModel {
+            slot_id: ActiveValue::Set(slot_id),
+            profile_id: ActiveValue::Set(base.profile_id),
+            area_id: ActiveValue::Set(base.area_id),
+            rid: ActiveValue::Set(base.rid),
+            squadron_id: ActiveValue::Set(squadron_id),
+            state: ActiveValue::Set(plane_db::Status::Assigned),
+            condition: ActiveValue::Set(plane::CONDITION_NORMAL),
+            count: ActiveValue::Set(size),
+            max_count: ActiveValue::Set(size),
+            relocate_until: ActiveValue::Set(None),
+            condition_at: ActiveValue::Set(None),
+        }
+        .insert(c)
+        .await?;
+
+        size * mst.api_cost.unwrap_or(0)
+    } else {
+        0
+    };
+
+    if let Some(plane) = current {
+        let mut am: plane_db::ActiveModel = plane.into();
+        am.state = ActiveValue::Set(plane_db::Status::Reassigning);
+        am.squadron_id = ActiveValue::Set(0);
+        am.relocate_until = ActiveValue::Set(Some(plane::relocation_deadline(now)));
+        am.update(c).await?;
+    }
+
+    Ok(cost)
+}
+
+/// Recompute the stored range of an airbase from its squadrons.
+async fn update_airbase_distance_impl<C>(
+    c: &C,
+    codex: &Codex,
+    base: base::Model,
+) -> Result<base::Model, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mut ranges = Vec::new();
+    for plane in get_squadrons_impl(c, &base).await? {
+        let item = find_slot_item_impl(c, plane.slot_id).await?;
+        let mst = codex.find::<ApiMstSlotitem>(&item.mst_id)?;
+        ranges.push((item.type3, mst.api_distance.unwrap_or(0)));
+    }
+    let distance = plane::airbase_distance(&ranges);
+
+    let mut am: base::ActiveModel = base.into();
+    am.base_range = ActiveValue::Set(distance.api_base);
+    am.bonus_range = ActiveValue::Set(distance.api_bonus);
+
+    Ok(am.update(c).await?)
+}
+
+/// Build the API view of an airbase with all four squadrons.
+pub(crate) async fn build_air_base_impl<C>(
+    c: &C,
+    base: base::Model,
+) -> Result<KcApiAirBase, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let planes = get_squadrons_impl(c, &base).await?;
+    let mut api_base: KcApiAirBase = Airbase::from(base).into();
+    api_base.api_plane_info = (1..=plane::SQUADRON_COUNT)
+        .map(|squadron_id| match planes.iter().find(|p| p.squadron_id == squadron_id) {
+            Some(plane) => PlaneInfo::from(plane.clone()).into(),
+            None => KcApiPlaneInfo {
+                api_cond: None,
+                api_count: None,
+                api_max_count: None,
+                api_slotid: 0,
+                api_squadron_id: squadron_id,
+                api_state: 0,
+            },
+        })
+        .collect();
+
+    Ok(api_base)
+}
+
 pub(super) async fn init<C>(_c: &C, _profile_id: i64) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
@@ -148,3 +878,174 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use emukc_model::{
+        kc2::KcSlotItemType3,
+        prelude::{ApiMstShip, ApiMstStype, Kc3rdShip},
+    };
+    use emukc_time::chrono::Duration;
+
+    use super::*;
+    use crate::{
+        game::{MaterialOps, ShipOps, SlotItemOps, use_item::add_use_item_impl},
+        user::{AccountOps, ProfileOps},
+    };
+
+    const CARRIER_ID: i64 = 1;
+    const ATTACKER_ID: i64 = 2;
+    const TORPEDO_BOMBER_ID: i64 = 3;
+
+    fn codex() -> Codex {
+        let mut codex = Codex::default();
+        codex.manifest.api_mst_stype.push(ApiMstStype {
+            api_id: 7,
+            api_equip_type: [((KcSlotItemType3::CarrierBasedTorpedoBomber as i64).to_string(), 1)]
+                .into(),
+            api_kcnt: 0,
+            api_name: String::new(),
+            api_scnt: 0,
+            api_sortno: 0,
+        });
+        codex.manifest.api_mst_ship.push(ApiMstShip {
+            api_id: CARRIER_ID,
+            api_stype: 7,
+            api_slot_num: 2,
+            api_taik: Some([40, 60]),
+            api_maxeq: Some([18, 12, 0, 0, 0]),
+            ..Default::default()
+        });
+        codex.ship_extra.insert(
+            CARRIER_ID,
+            Kc3rdShip {
+                api_id: CARRIER_ID,
+                kaih: [0, 0],
+                tais: [0, 0],
+                saku: [0, 0],
+                luck: [0, 0],
+                luck_bonus: 0.0,
+                armor_bonus: 0,
+                cnum: 1,
+                buildable: false,
+                buildable_lsc: false,
+                slots: Vec::new(),
+                remodel: None,
+                remodel_back_to: None,
+                remodel_back_requirement: None,
+            },
+        );
+        for (api_id, type3, distance) in [
+            (ATTACKER_ID, KcSlotItemType3::LandBasedAttacker, 6),
+            (TORPEDO_BOMBER_ID, KcSlotItemType3::CarrierBasedTorpedoBomber, 4),
+        ] {
+            codex.manifest.api_mst_slotitem.push(ApiMstSlotitem {
+                api_id,
+                api_broken: [1, 1, 1, 1],
+                api_type: [3, 5, type3 as i64, 8, 8],
+                api_cost: Some(10),
+                api_distance: Some(distance),
+                ..Default::default()
+            });
+        }
+        codex
+    }
+
+    async fn new_context() -> ((emukc_db::sea_orm::DbConn, Codex), i64) {
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex());
+        let account = context.sign_up("airbase", "1234567").await.unwrap();
+        let profile_id =
+            context.new_profile(&account.access_token.token, "airbase").await.unwrap().profile.id;
+        context.unlock_airbase(profile_id, 6, 1).await.unwrap();
+
+        (context, profile_id)
+    }
+
+    #[tokio::test]
+    async fn set_plane_deploys_and_relocates() {
+        let (context, pid) = new_context().await;
+        let first = context.add_slot_item(pid, TORPEDO_BOMBER_ID, 0, 0).await.unwrap().api_id;
+        let second = context.add_slot_item(pid, ATTACKER_ID, 0, 0).await.unwrap().api_id;
+        let ship = context.add_ship(pid, CARRIER_ID).await.unwrap();
+
+        let (base, material) = context.set_airbase_planes(pid, 6, 1, &[(1, first)]).await.unwrap();
+        assert_eq!(base.api_plane_info[0].api_slotid, first);
+        assert_eq!(base.api_plane_info[0].api_count, Some(18));
+        assert_eq!(base.api_distance.api_base, 4);
+        assert_eq!(material.bauxite, 1000 - 18 * 10);
+
+        // the deployed plane can be neither deployed twice, equipped nor scrapped
+        assert!(matches!(
+            context.set_airbase_planes(pid, 6, 1, &[(2, first)]).await,
+            Err(GameplayError::Locked(_))
+        ));
+        assert!(matches!(
+            context.set_slot_item(ship.api_id, 0, first).await,
+            Err(GameplayError::Locked(_))
+        ));
+        assert!(matches!(
+            context.destroy_items(pid, &[first]).await,
+            Err(GameplayError::Locked(_))
+        ));
+
+        let (base, _) = context.set_airbase_planes(pid, 6, 1, &[(1, second)]).await.unwrap();
+        assert_eq!(base.api_plane_info[0].api_slotid, second);
+        assert_eq!(context.get_relocating_planes(pid).await.unwrap(), vec![first]);
+
+        // a relocating plane stays off the ships
+        assert!(matches!(
+            context.set_slot_item(ship.api_id, 0, first).await,
+            Err(GameplayError::Locked(_))
+        ));
+
+        // once the relocation is over the plane is free, settled or not
+        let mut am: plane_db::ActiveModel =
+            plane_db::Entity::find_by_id(first).one(&context.0).await.unwrap().unwrap().into();
+        am.relocate_until = ActiveValue::Set(Some(Utc::now() - Duration::minutes(1)));
+        am.update(&context.0).await.unwrap();
+        context.set_slot_item(ship.api_id, 0, first).await.unwrap();
+        assert!(plane_db::Entity::find_by_id(first).one(&context.0).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn supply_refills_missing_planes() {
+        let (context, pid) = new_context().await;
+        let slot_id = context.add_slot_item(pid, ATTACKER_ID, 0, 0).await.unwrap().api_id;
+        context.set_airbase_planes(pid, 6, 1, &[(1, slot_id)]).await.unwrap();
+
+        let plane = plane_db::Entity::find_by_id(slot_id).one(&context.0).await.unwrap().unwrap();
+        let mut am: plane_db::ActiveModel = plane.into();
+        am.count = ActiveValue::Set(12);
+        am.update(&context.0).await.unwrap();
+        let before = context.get_materials(pid).await.unwrap();
+
+        let (base, material) = context.supply_airbase(pid, 6, 1, &[1]).await.unwrap();
+        assert_eq!(base.api_plane_info[0].api_count, Some(18));
+        assert_eq!(material.fuel, before.fuel - 6 * plane::SUPPLY_FUEL_PER_PLANE);
+        assert_eq!(material.bauxite, before.bauxite - 6 * plane::SUPPLY_BAUXITE_PER_PLANE);
+
+        // nothing is charged for a full squadron
+        let (_, material) = context.supply_airbase(pid, 6, 1, &[1]).await.unwrap();
+        assert_eq!(material.bauxite, before.bauxite - 6 * plane::SUPPLY_BAUXITE_PER_PLANE);
+    }
+
+    #[tokio::test]
+    async fn expand_consumes_construction_corps() {
+        let (context, pid) = new_context().await;
+
+        assert!(context.expand_airbase(pid, 6).await.is_err());
+        assert!(matches!(
+            context.expand_airbase(pid, 7).await,
+            Err(GameplayError::EntryNotFound(_))
+        ));
+
+        add_use_item_impl(&context.0, pid, KcUseItemType::ConstCorps as i64, 3).await.unwrap();
+        assert_eq!(context.expand_airbase(pid, 6).await.unwrap().api_rid, 2);
+        assert_eq!(context.expand_airbase(pid, 6).await.unwrap().api_rid, 3);
+        assert!(matches!(
+            context.expand_airbase(pid, 6).await,
+            Err(GameplayError::CapacityExceeded(plane::MAX_AIRBASES_PER_AREA))
+        ));
+        assert_eq!(context.get_airbases(pid).await.unwrap().len(), 3);
+    }
+}
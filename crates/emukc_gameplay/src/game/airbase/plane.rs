@@ -1 +1,206 @@
+//! Squadron rules shared by the airbase operations.
 
+use emukc_db::entity::profile::airbase::plane as plane_db;
+use emukc_model::kc2::{KcApiDistance, KcSlotItemType3};
+use emukc_time::chrono::{DateTime, Duration, Utc};
+
+/// Squadrons per airbase.
+pub(super) const SQUADRON_COUNT: i64 = 4;
+
+/// Airbases an area can be expanded to.
+pub(super) const MAX_AIRBASES_PER_AREA: i64 = 3;
+
+/// Time a removed or replaced plane spends relocating.
+pub(super) const RELOCATION_MINUTES: i64 = 4;
+
+/// Time for a fatigued squadron to recover one condition step.
+pub(super) const CONDITION_RECOVERY_MINUTES: i64 = 15;
+
+/// Normal squadron condition.
+pub(super) const CONDITION_NORMAL: i64 = 1;
+
+/// Fuel spent per plane when resupplying a squadron.
+pub(super) const SUPPLY_FUEL_PER_PLANE: i64 = 3;
+
+/// Bauxite spent per plane when resupplying a squadron.
+pub(super) const SUPPLY_BAUXITE_PER_PLANE: i64 = 5;
+
+/// Fuel spent per two planes sortied, rounded up.
+pub(super) const SORTIE_FUEL_PER_TWO_PLANES: i64 = 3;
+
+/// Ammo spent per ten planes sortied, rounded up.
+pub(super) const SORTIE_AMMO_PER_TEN_PLANES: i64 = 7;
+
+/// Whether a slot item type can be deployed to an airbase.
+pub(super) fn is_deployable(type3: i64) -> bool {
+    matches!(
+        KcSlotItemType3::n(type3),
+        Some(
+            KcSlotItemType3::CarrierBasedFighter
+                | KcSlotItemType3::CarrierBasedDiveBomber
+                | KcSlotItemType3::CarrierBasedTorpedoBomber
+                | KcSlotItemType3::CarrierBasedRecon
+                | KcSlotItemType3::CarrierBasedRecon2
+                | KcSlotItemType3::SeaBasedRecon
+                | KcSlotItemType3::SeaBasedBomber
+                | KcSlotItemType3::SeaplaneFighter
+                | KcSlotItemType3::LargeFlyingBoat
+                | KcSlotItemType3::LandBasedAttacker
+                | KcSlotItemType3::LocalFighter
+                | KcSlotItemType3::LandBasedRecon
+                | KcSlotItemType3::LargeLandBasedAircraft
+                | KcSlotItemType3::JetFighter
+                | KcSlotItemType3::JetFighterBomber
+                | KcSlotItemType3::JetAttacker
+                | KcSlotItemType3::JetRecon
+        )
+    )
+}
+
+/// Whether a slot item type only extends the airbase range as a recon.
+pub(super) fn is_recon(type3: i64) -> bool {
+    matches!(
+        KcSlotItemType3::n(type3),
+        Some(
+            KcSlotItemType3::CarrierBasedRecon
+                | KcSlotItemType3::CarrierBasedRecon2
+                | KcSlotItemType3::SeaBasedRecon
+                | KcSlotItemType3::LargeFlyingBoat
+                | KcSlotItemType3::LandBasedRecon
+                | KcSlotItemType3::JetRecon
+        )
+    )
+}
+
+/// Planes in a full squadron of this slot item type.
+pub(super) fn squadron_size(type3: i64) -> i64 {
+    if is_recon(type3) {
+        4
+    } else if type3 == KcSlotItemType3::LargeLandBasedAircraft as i64 {
+        9
+    } else {
+        18
+    }
+}
+
+/// Airbase range from `(type3, api_distance)` of every deployed plane.
+///
+/// The base range is the shortest plane range. A recon reaching further than
+/// that extends it by `min(3, round(sqrt(recon - base)))`.
+pub(super) fn airbase_distance(planes: &[(i64, i64)]) -> KcApiDistance {
+    let Some(base) = planes.iter().map(|(_, distance)| *distance).min() else {
+        return KcApiDistance {
+            api_base: 0,
+            api_bonus: 0,
+        };
+    };
+    let bonus = planes
+        .iter()
+        .filter(|(type3, distance)| is_recon(*type3) && *distance > base)
+        .map(|(_, distance)| ((distance - base) as f64).sqrt().round() as i64)
+        .max()
+        .unwrap_or(0)
+        .min(3);
+
+    KcApiDistance {
+        api_base: base,
+        api_bonus: bonus,
+    }
+}
+
+/// Fuel and ammo spent to sortie `count` planes.
+pub(super) fn sortie_cost(count: i64) -> (i64, i64) {
+    let count = count.max(0);
+    ((count * SORTIE_FUEL_PER_TWO_PLANES + 1) / 2, (count * SORTIE_AMMO_PER_TEN_PLANES + 9) / 10)
+}
+
+/// Condition and its change time after recovering until `now`.
+///
+/// Partial progress towards the next step is kept by advancing `condition_at`
+/// by whole recovery steps only.
+pub(super) fn recover_condition(
+    plane: &plane_db::Model,
+    now: DateTime<Utc>,
+) -> (i64, Option<DateTime<Utc>>) {
+    let (Some(since), true) = (plane.condition_at, plane.condition > CONDITION_NORMAL) else {
+        return (plane.condition, plane.condition_at);
+    };
+    let steps = ((now - since).num_minutes() / CONDITION_RECOVERY_MINUTES)
+        .min(plane.condition - CONDITION_NORMAL);
+    (plane.condition - steps, Some(since + Duration::minutes(steps * CONDITION_RECOVERY_MINUTES)))
+}
+
+/// Whether a relocating plane has finished relocating.
+pub(super) fn relocation_done(plane: &plane_db::Model, now: DateTime<Utc>) -> bool {
+    plane.state == plane_db::Status::Reassigning
+        && plane.relocate_until.is_none_or(|until| until <= now)
+}
+
+/// Relocation completion time for a plane leaving a squadron now.
+pub(super) fn relocation_deadline(now: DateTime<Utc>) -> DateTime<Utc> {
+    now + Duration::minutes(RELOCATION_MINUTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plane(condition: i64, condition_at: Option<DateTime<Utc>>) -> plane_db::Model {
+        plane_db::Model {
+            slot_id: 1,
+            profile_id: 1,
+            area_id: 6,
+            rid: 1,
+            squadron_id: 1,
+            state: plane_db::Status::Assigned,
+            condition,
+            count: 18,
+            max_count: 18,
+            relocate_until: None,
+            condition_at,
+        }
+    }
+
+    #[test]
+    fn recon_extends_range_up_to_three() {
+        let attacker = KcSlotItemType3::LandBasedAttacker as i64;
+        let recon = KcSlotItemType3::LargeFlyingBoat as i64;
+
+        let d = airbase_distance(&[(attacker, 6), (recon, 20)]);
+        assert_eq!((d.api_base, d.api_bonus), (6, 3));
+        let d = airbase_distance(&[(attacker, 6), (recon, 8)]);
+        assert_eq!((d.api_base, d.api_bonus), (6, 1));
+        let d = airbase_distance(&[(attacker, 6), (attacker, 9)]);
+        assert_eq!((d.api_base, d.api_bonus), (6, 0));
+        let d = airbase_distance(&[]);
+        assert_eq!((d.api_base, d.api_bonus), (0, 0));
+    }
+
+    #[test]
+    fn squadron_sizes() {
+        assert_eq!(squadron_size(KcSlotItemType3::LandBasedAttacker as i64), 18);
+        assert_eq!(squadron_size(KcSlotItemType3::LandBasedRecon as i64), 4);
+        assert_eq!(squadron_size(KcSlotItemType3::LargeLandBasedAircraft as i64), 9);
+        assert!(!is_deployable(KcSlotItemType3::SmallCaliberMainGun as i64));
+    }
+
+    #[test]
+    fn sortie_cost_rounds_up() {
+        assert_eq!(sortie_cost(18), (27, 13));
+        assert_eq!(sortie_cost(9), (14, 7));
+        assert_eq!(sortie_cost(0), (0, 0));
+    }
+
+    #[test]
+    fn condition_recovers_stepwise() {
+        let now = Utc::now();
+        let since = now - Duration::minutes(CONDITION_RECOVERY_MINUTES + 5);
+        let (condition, at) = recover_condition(&plane(3, Some(since)), now);
+        assert_eq!(condition, 2);
+        assert_eq!(at, Some(since + Duration::minutes(CONDITION_RECOVERY_MINUTES)));
+
+        let since = now - Duration::minutes(CONDITION_RECOVERY_MINUTES * 5);
+        assert_eq!(recover_condition(&plane(3, Some(since)), now).0, CONDITION_NORMAL);
+        assert_eq!(recover_condition(&plane(2, None), now).0, 2);
+    }
+}
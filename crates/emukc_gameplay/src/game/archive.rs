@@ -0,0 +1,820 @@
+//! Portable profile archives.
+//!
+//! A [`ProfileArchive`] holds every database row of one profile. Instance IDs
+//! (ships, slot items, rivals, ...) are server-wide, so importing allocates new
+//! ones and rewrites every reference to them.
+
+use std::collections::{BTreeSet, HashMap};
+
+use emukc_db::{
+    entity::profile::{
+        self, airbase, expedition, fleet, furniture, incentive,
+        item::{pay_item, picturebook as slot_item_record, slot_item, use_item},
+        kdock, map_record, material, ndock, practice,
+        preset::{preset_caps, preset_deck, preset_dev_item, preset_slot},
+        quest, settings,
+        ship::{self, morale_timer, picturebook as ship_record, sp_effect_item},
+    },
+    sea_orm::{ActiveValue, IntoActiveModel, QueryOrder, entity::prelude::*},
+};
+use emukc_model::codex::Codex;
+use emukc_time::chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::err::GameplayError;
+
+use super::basic::find_profile;
+
+/// Format tag of a profile archive.
+pub const PROFILE_ARCHIVE_FORMAT: &str = "emukc-profile";
+
+/// Current profile archive version.
+///
+/// Bump it whenever a change to the archived tables cannot be read by the
+/// previous importer.
+pub const PROFILE_ARCHIVE_VERSION: i64 = 1;
+
+/// At most this many problems are reported when an archive is rejected.
+const MAX_REPORTED_PROBLEMS: usize = 10;
+
+/// The complete state of one profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileArchive {
+    /// Always [`PROFILE_ARCHIVE_FORMAT`].
+    pub format: String,
+
+    /// The archive version, see [`PROFILE_ARCHIVE_VERSION`].
+    pub version: i64,
+
+    /// Game time of the export.
+    pub exported_at: DateTime<Utc>,
+
+    pub profile: profile::Model,
+
+    pub material: Option<material::Model>,
+
+    pub game_settings: Option<settings::game::Model>,
+
+    pub option_settings: Option<settings::option::Model>,
+
+    pub oss_settings: Option<settings::oss::Model>,
+
+    #[serde(default)]
+    pub ships: Vec<ship::Model>,
+
+    #[serde(default)]
+    pub ship_sp_effect_items: Vec<sp_effect_item::Model>,
+
+    pub ship_morale_timer: Option<morale_timer::Model>,
+
+    #[serde(default)]
+    pub ship_records: Vec<ship_record::Model>,
+
+    #[serde(default)]
+    pub slot_items: Vec<slot_item::Model>,
+
+    #[serde(default)]
+    pub slot_item_records: Vec<slot_item_record::Model>,
+
+    #[serde(default)]
+    pub use_items: Vec<use_item::Model>,
+
+    #[serde(default)]
+    pub pay_items: Vec<pay_item::Model>,
+
+    #[serde(default)]
+    pub fleets: Vec<fleet::Model>,
+
+    #[serde(default)]
+    pub kdocks: Vec<kdock::Model>,
+
+    #[serde(default)]
+    pub ndocks: Vec<ndock::Model>,
+
+    #[serde(default)]
+    pub expeditions: Vec<expedition::Model>,
+
+    #[serde(default)]
+    pub quest_progress: Vec<quest::progress::Model>,
+
+    #[serde(default)]
+    pub quest_oneshot_records: Vec<quest::oneshot::Model>,
+
+    #[serde(default)]
+    pub quest_periodic_records: Vec<quest::periodic::Model>,
+
+    #[serde(default)]
+    pub map_records: Vec<map_record::Model>,
+
+    pub preset_caps: Option<preset_caps::Model>,
+
+    #[serde(default)]
+    pub preset_decks: Vec<preset_deck::Model>,
+
+    #[serde(default)]
+    pub preset_slots: Vec<preset_slot::Model>,
+
+    #[serde(default)]
+    pub preset_dev_items: Vec<preset_dev_item::Model>,
+
+    #[serde(default)]
+    pub furniture: Vec<furniture::record::Model>,
+
+    pub furniture_config: Option<furniture::config::Model>,
+
+    pub practice_config: Option<practice::config::Model>,
+
+    #[serde(default)]
+    pub rivals: Vec<practice::rival::Model>,
+
+    #[serde(default)]
+    pub rival_details: Vec<practice::detail::Model>,
+
+    #[serde(default)]
+    pub rival_ships: Vec<practice::rival_ship::Model>,
+
+    #[serde(default)]
+    pub airbases: Vec<airbase::base::Model>,
+
+    #[serde(default)]
+    pub airbase_planes: Vec<airbase::plane::Model>,
+
+    #[serde(default)]
+    pub incentives: Vec<incentive::Model>,
+}
+
+/// Collect the archive of a profile.
+///
+/// # Parameters
+///
+/// - `c`: The database connection.
+/// - `profile_id`: The profile ID.
+/// - `now`: The game time, recorded as the export time.
+pub(crate) async fn export_profile_impl<C>(
+    c: &C,
+    profile_id: i64,
+    now: DateTime<Utc>,
+) -> Result<ProfileArchive, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let profile = find_profile(c, profile_id).await?;
+
+    Ok(ProfileArchive {
+        format: PROFILE_ARCHIVE_FORMAT.to_string(),
+        version: PROFILE_ARCHIVE_VERSION,
+        exported_at: now,
+        profile,
+        material: material::Entity::find_by_id(profile_id).one(c).await?,
+        game_settings: settings::game::Entity::find_by_id(profile_id).one(c).await?,
+        option_settings: settings::option::Entity::find_by_id(profile_id).one(c).await?,
+        oss_settings: settings::oss::Entity::find_by_id(profile_id).one(c).await?,
+        ships: ship::Entity::find()
+            .filter(ship::Column::ProfileId.eq(profile_id))
+            .order_by_asc(ship::Column::Id)
+            .all(c)
+            .await?,
+        ship_sp_effect_items: sp_effect_item::Entity::find()
+            .filter(sp_effect_item::Column::ProfileId.eq(profile_id))
+            .order_by_asc(sp_effect_item::Column::Id)
+            .all(c)
+            .await?,
+        ship_morale_timer: morale_timer::Entity::find_by_id(profile_id).one(c).await?,
+        ship_records: ship_record::Entity::find()
+            .filter(ship_record::Column::ProfileId.eq(profile_id))
+            .order_by_asc(ship_record::Column::Id)
+            .all(c)
+            .await?,
+        slot_items: slot_item::Entity::find()
+            .filter(slot_item::Column::ProfileId.eq(profile_id))
+            .order_by_asc(slot_item::Column::Id)
+            .all(c)
+            .await?,
+        slot_item_records: slot_item_record::Entity::find()
+            .filter(slot_item_record::Column::ProfileId.eq(profile_id))
+            .order_by_asc(slot_item_record::Column::Id)
+            .all(c)
+            .await?,
+        use_items: use_item::Entity::find()
+            .filter(use_item::Column::ProfileId.eq(profile_id))
+            .order_by_asc(use_item::Column::Id)
+            .all(c)
+            .await?,
+        pay_items: pay_item::Entity::find()
+            .filter(pay_item::Column::ProfileId.eq(profile_id))
+            .order_by_asc(pay_item::Column::Id)
+            .all(c)
+            .await?,
+        fleets: fleet::Entity::find()
+            .filter(fleet::Column::ProfileId.eq(profile_id))
+            .order_by_asc(fleet::Column::Index)
+            .all(c)
+            .await?,
+        kdocks: kdock::Entity::find()
+            .filter(kdock::Column::ProfileId.eq(profile_id))
+            .order_by_asc(kdock::Column::Index)
+            .all(c)
+            .await?,
+        ndocks: ndock::Entity::find()
+            .filter(ndock::Column::ProfileId.eq(profile_id))
+            .order_by_asc(ndock::Column::Index)
+            .all(c)
+            .await?,
+        expeditions: expedition::Entity::find()
+            .filter(expedition::Column::ProfileId.eq(profile_id))
+            .order_by_asc(expedition::Column::MissionId)
+            .all(c)
+            .await?,
+        quest_progress: quest::progress::Entity::find()
+            .filter(quest::progress::Column::ProfileId.eq(profile_id))
+            .order_by_asc(quest::progress::Column::QuestId)
+            .all(c)
+            .await?,
+        quest_oneshot_records: quest::oneshot::Entity::find()
+            .filter(quest::oneshot::Column::ProfileId.eq(profile_id))
+            .order_by_asc(quest::oneshot::Column::QuestId)
+            .all(c)
+            .await?,
+        quest_periodic_records: quest::periodic::Entity::find()
+            .filter(quest::periodic::Column::ProfileId.eq(profile_id))
+            .order_by_asc(quest::periodic::Column::QuestId)
+            .all(c)
+            .await?,
+        map_records: map_record::Entity::find()
+            .filter(map_record::Column::ProfileId.eq(profile_id))
+            .order_by_asc(map_record::Column::MapId)
+            .all(c)
+            .await?,
+        preset_caps: preset_caps::Entity::find_by_id(profile_id).one(c).await?,
+        preset_decks: preset_deck::Entity::find()
+            .filter(preset_deck::Column::ProfileId.eq(profile_id))
+            .order_by_asc(preset_deck::Column::Index)
+            .all(c)
+            .await?,
+        preset_slots: preset_slot::Entity::find()
+            .filter(preset_slot::Column::ProfileId.eq(profile_id))
+            .order_by_asc(preset_slot::Column::Index)
+            .all(c)
+            .await?,
+        preset_dev_items: preset_dev_item::Entity::find()
+            .filter(preset_dev_item::Column::ProfileId.eq(profile_id))
+            .order_by_asc(preset_dev_item::Column::Index)
+            .all(c)
+            .await?,
+        furniture: furniture::record::Entity::find()
+            .filter(furniture::record::Column::ProfileId.eq(profile_id))
+            .order_by_asc(furniture::record::Column::FurnitureId)
+            .all(c)
+            .await?,
+        furniture_config: furniture::config::Entity::find_by_id(profile_id).one(c).await?,
+        practice_config: practice::config::Entity::find_by_id(profile_id).one(c).await?,
+        rivals: practice::rival::Entity::find()
+            .filter(practice::rival::Column::ProfileId.eq(profile_id))
+            .order_by_asc(practice::rival::Column::Index)
+            .all(c)
+            .await?,
+        rival_details: practice::detail::Entity::find()
+            .filter(practice::detail::Column::ProfileId.eq(profile_id))
+            .order_by_asc(practice::detail::Column::Id)
+            .all(c)
+            .await?,
+        rival_ships: practice::rival_ship::Entity::find()
+            .filter(practice::rival_ship::Column::ProfileId.eq(profile_id))
+            .order_by_asc(practice::rival_ship::Column::Id)
+            .all(c)
+            .await?,
+        airbases: airbase::base::Entity::find()
+            .filter(airbase::base::Column::ProfileId.eq(profile_id))
+            .order_by_asc(airbase::base::Column::Id)
+            .all(c)
+            .await?,
+        airbase_planes: airbase::plane::Entity::find()
+            .filter(airbase::plane::Column::ProfileId.eq(profile_id))
+            .order_by_asc(airbase::plane::Column::SlotId)
+            .all(c)
+            .await?,
+        incentives: incentive::Entity::find()
+            .filter(incentive::Column::ProfileId.eq(profile_id))
+            .order_by_asc(incentive::Column::Id)
+            .all(c)
+            .await?,
+    })
+}
+
+/// Check an archive before importing it.
+///
+/// Returns the problems found, empty if the archive can be imported.
+///
+/// # Parameters
+///
+/// - `codex`: The codex every manifest ID must exist in.
+/// - `archive`: The archive.
+pub fn validate_profile_archive(codex: &Codex, archive: &ProfileArchive) -> Vec<String> {
+    let mut problems = check_archive_references(archive);
+    let manifest = &codex.manifest;
+
+    for ship in &archive.ships {
+        if manifest.find_ship(ship.mst_id).is_none() {
+            problems.push(format!("ship {} has unknown manifest ID {}", ship.id, ship.mst_id));
+        }
+    }
+    for item in &archive.slot_items {
+        if manifest.find_slotitem(item.mst_id).is_none() {
+            problems.push(format!("slot item {} has unknown manifest ID {}", item.id, item.mst_id));
+        }
+    }
+    for item in &archive.use_items {
+        if manifest.find_useitem(item.mst_id).is_none() {
+            problems.push(format!("unknown use item {}", item.mst_id));
+        }
+    }
+    for item in &archive.pay_items {
+        if manifest.find_payitem(item.mst_id).is_none() {
+            problems.push(format!("unknown pay item {}", item.mst_id));
+        }
+    }
+    for record in &archive.furniture {
+        if manifest.find_furniture(record.furniture_id).is_none() {
+            problems.push(format!("unknown furniture {}", record.furniture_id));
+        }
+    }
+
+    let map_ids: BTreeSet<i64> = manifest.api_mst_mapinfo.iter().map(|map| map.api_id).collect();
+    for record in &archive.map_records {
+        if !map_ids.contains(&record.map_id) {
+            problems.push(format!("unknown map {}", record.map_id));
+        }
+    }
+
+    let quest_ids = archive
+        .quest_progress
+        .iter()
+        .map(|quest| quest.quest_id)
+        .chain(archive.quest_oneshot_records.iter().map(|quest| quest.quest_id))
+        .chain(archive.quest_periodic_records.iter().map(|quest| quest.quest_id));
+    for quest_id in quest_ids.collect::<BTreeSet<_>>() {
+        if !codex.quest.contains_key(&quest_id) {
+            problems.push(format!("unknown quest {quest_id}"));
+        }
+    }
+
+    problems
+}
+
+/// Check the archive header and that every instance reference resolves
+/// inside the archive.
+fn check_archive_references(archive: &ProfileArchive) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if archive.format != PROFILE_ARCHIVE_FORMAT {
+        problems.push(format!("unexpected archive format `{}`", archive.format));
+    }
+    if !(1..=PROFILE_ARCHIVE_VERSION).contains(&archive.version) {
+        problems.push(format!(
+            "unsupported archive version {}, this server reads up to {PROFILE_ARCHIVE_VERSION}",
+            archive.version
+        ));
+    }
+
+    let ship_ids: BTreeSet<i64> = archive.ships.iter().map(|ship| ship.id).collect();
+    let item_ids: BTreeSet<i64> = archive.slot_items.iter().map(|item| item.id).collect();
+    let rival_ids: BTreeSet<i64> = archive.rivals.iter().map(|rival| rival.id).collect();
+
+    let mut check = |kind: &str, id: i64, ids: &BTreeSet<i64>, owner: String| {
+        if id > 0 && !ids.contains(&id) {
+            problems.push(format!("{owner} refers to missing {kind} {id}"));
+        }
+    };
+
+    for ship in &archive.ships {
+        for slot in ship_slot_ids(ship) {
+            check("slot item", slot, &item_ids, format!("ship {}", ship.id));
+        }
+    }
+    for item in &archive.slot_items {
+        check("ship", item.equip_on, &ship_ids, format!("slot item {}", item.id));
+    }
+    for fleet in &archive.fleets {
+        for ship_id in fleet_ship_ids(fleet) {
+            check("ship", ship_id, &ship_ids, format!("fleet {}", fleet.index));
+        }
+    }
+    for dock in &archive.ndocks {
+        check("ship", dock.ship_id, &ship_ids, format!("repair dock {}", dock.index));
+    }
+    for deck in &archive.preset_decks {
+        for ship_id in preset_deck_ship_ids(deck) {
+            check("ship", ship_id, &ship_ids, format!("preset deck {}", deck.index));
+        }
+    }
+    for item in &archive.ship_sp_effect_items {
+        check("ship", item.ship_id, &ship_ids, format!("sp effect item {}", item.id));
+    }
+    for plane in &archive.airbase_planes {
+        check("slot item", plane.slot_id, &item_ids, format!("airbase {} plane", plane.rid));
+    }
+    for detail in &archive.rival_details {
+        check("rival", detail.id, &rival_ids, "rival detail".to_string());
+    }
+    for ship in &archive.rival_ships {
+        check("rival", ship.rival_id, &rival_ids, format!("rival ship {}", ship.id));
+    }
+
+    problems
+}
+
+/// Import an archive as a new profile of an account.
+///
+/// The archive must pass [`validate_profile_archive`].
+///
+/// # Parameters
+///
+/// - `c`: The database connection.
+/// - `codex`: The codex.
+/// - `account_id`: The account that owns the new profile.
+/// - `profile_name`: The new profile's name, the archived name if `None`.
+/// - `archive`: The archive.
+pub(crate) async fn import_profile_impl<C>(
+    c: &C,
+    codex: &Codex,
+    account_id: i64,
+    profile_name: Option<&str>,
+    archive: &ProfileArchive,
+) -> Result<profile::Model, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let problems = validate_profile_archive(codex, archive);
+    if !problems.is_empty() {
+        let mut message =
+            problems.iter().take(MAX_REPORTED_PROBLEMS).cloned().collect::<Vec<_>>().join("; ");
+        if problems.len() > MAX_REPORTED_PROBLEMS {
+            message.push_str(&format!("; and {} more", problems.len() - MAX_REPORTED_PROBLEMS));
+        }
+        return Err(GameplayError::InvalidArchive(message));
+    }
+
+    // profile
+    let mut am = archive.profile.clone().into_active_model().reset_all();
+    am.id = ActiveValue::NotSet;
+    am.account_id = ActiveValue::Set(account_id);
+    if let Some(name) = profile_name {
+        am.name = ActiveValue::Set(name.to_string());
+    }
+    let new_profile = am.insert(c).await?;
+    let pid = new_profile.id;
+
+    // per-profile singletons
+    if let Some(m) = &archive.material {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.profile_id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    if let Some(m) = &archive.game_settings {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.profile_id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    if let Some(m) = &archive.option_settings {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.profile_id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    if let Some(m) = &archive.oss_settings {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.profile_id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    if let Some(m) = &archive.ship_morale_timer {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    if let Some(m) = &archive.preset_caps {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    if let Some(m) = &archive.furniture_config {
+        let mut am = (*m).into_active_model().reset_all();
+        am.id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    if let Some(m) = &archive.practice_config {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+
+    // slot items first, their ships are patched in once the ships exist
+    let mut item_ids = HashMap::new();
+    for m in &archive.slot_items {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::NotSet;
+        am.profile_id = ActiveValue::Set(pid);
+        am.equip_on = ActiveValue::Set(0);
+        let inserted = am.insert(c).await?;
+        item_ids.insert(m.id, inserted.id);
+    }
+
+    let mut ship_ids = HashMap::new();
+    for m in &archive.ships {
+        let mut am = (*m).into_active_model().reset_all();
+        am.id = ActiveValue::NotSet;
+        am.profile_id = ActiveValue::Set(pid);
+        am.slot_1 = ActiveValue::Set(remap(&item_ids, m.slot_1));
+        am.slot_2 = ActiveValue::Set(remap(&item_ids, m.slot_2));
+        am.slot_3 = ActiveValue::Set(remap(&item_ids, m.slot_3));
+        am.slot_4 = ActiveValue::Set(remap(&item_ids, m.slot_4));
+        am.slot_5 = ActiveValue::Set(remap(&item_ids, m.slot_5));
+        am.slot_ex = ActiveValue::Set(remap(&item_ids, m.slot_ex));
+        let inserted = am.insert(c).await?;
+        ship_ids.insert(m.id, inserted.id);
+    }
+
+    for m in archive.slot_items.iter().filter(|m| m.equip_on > 0) {
+        let mut am = slot_item::ActiveModel {
+            id: ActiveValue::Unchanged(remap(&item_ids, m.id)),
+            ..Default::default()
+        };
+        am.equip_on = ActiveValue::Set(remap(&ship_ids, m.equip_on));
+        am.update(c).await?;
+    }
+
+    for m in &archive.ship_sp_effect_items {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::NotSet;
+        am.profile_id = ActiveValue::Set(pid);
+        am.ship_id = ActiveValue::Set(remap(&ship_ids, m.ship_id));
+        am.insert(c).await?;
+    }
+
+    for m in &archive.fleets {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::NotSet;
+        am.profile_id = ActiveValue::Set(pid);
+        am.ship_1 = ActiveValue::Set(remap(&ship_ids, m.ship_1));
+        am.ship_2 = ActiveValue::Set(remap(&ship_ids, m.ship_2));
+        am.ship_3 = ActiveValue::Set(remap(&ship_ids, m.ship_3));
+        am.ship_4 = ActiveValue::Set(remap(&ship_ids, m.ship_4));
+        am.ship_5 = ActiveValue::Set(remap(&ship_ids, m.ship_5));
+        am.ship_6 = ActiveValue::Set(remap(&ship_ids, m.ship_6));
+        am.insert(c).await?;
+    }
+
+    for m in &archive.ndocks {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::NotSet;
+        am.profile_id = ActiveValue::Set(pid);
+        am.ship_id = ActiveValue::Set(remap(&ship_ids, m.ship_id));
+        am.insert(c).await?;
+    }
+
+    for m in &archive.preset_decks {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::NotSet;
+        am.profile_id = ActiveValue::Set(pid);
+        am.ship_1 = ActiveValue::Set(remap(&ship_ids, m.ship_1));
+        am.ship_2 = ActiveValue::Set(remap(&ship_ids, m.ship_2));
+        am.ship_3 = ActiveValue::Set(remap(&ship_ids, m.ship_3));
+        am.ship_4 = ActiveValue::Set(remap(&ship_ids, m.ship_4));
+        am.ship_5 = ActiveValue::Set(remap(&ship_ids, m.ship_5));
+        am.ship_6 = ActiveValue::Set(remap(&ship_ids, m.ship_6));
+        am.insert(c).await?;
+    }
+
+    for m in &archive.airbase_planes {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.slot_id = ActiveValue::Set(remap(&item_ids, m.slot_id));
+        am.profile_id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+
+    // rivals carry server-wide IDs as well
+    let next_rival_id = practice::rival::Entity::find()
+        .order_by_desc(practice::rival::Column::Id)
+        .one(c)
+        .await?
+        .map_or(0, |rival| rival.id)
+        + 1;
+    let mut rival_ids = HashMap::new();
+    for (i, m) in archive.rivals.iter().enumerate() {
+        let new_id = next_rival_id + i as i64;
+        rival_ids.insert(m.id, new_id);
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::Set(new_id);
+        am.profile_id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    for m in &archive.rival_details {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::Set(remap(&rival_ids, m.id));
+        am.profile_id = ActiveValue::Set(pid);
+        am.insert(c).await?;
+    }
+    let next_rival_ship_id = practice::rival_ship::Entity::find()
+        .order_by_desc(practice::rival_ship::Column::Id)
+        .one(c)
+        .await?
+        .map_or(0, |ship| ship.id)
+        + 1;
+    for (i, m) in archive.rival_ships.iter().enumerate() {
+        let mut am = m.clone().into_active_model().reset_all();
+        am.id = ActiveValue::Set(next_rival_ship_id + i as i64);
+        am.profile_id = ActiveValue::Set(pid);
+        am.rival_id = ActiveValue::Set(remap(&rival_ids, m.rival_id));
+        // the mirrored ship lives on the exporting server
+        am.source_ship_id = ActiveValue::Set(None);
+        am.insert(c).await?;
+    }
+
+    // plain per-profile rows
+    macro_rules! insert_rows {
+        ($($rows:expr),* $(,)?) => {
+            $(
+                for m in $rows {
+                    let mut am = m.clone().into_active_model().reset_all();
+                    am.id = ActiveValue::NotSet;
+                    am.profile_id = ActiveValue::Set(pid);
+                    am.insert(c).await?;
+                }
+            )*
+        };
+    }
+    insert_rows!(
+        &archive.ship_records,
+        &archive.slot_item_records,
+        &archive.use_items,
+        &archive.pay_items,
+        &archive.kdocks,
+        &archive.expeditions,
+        &archive.quest_progress,
+        &archive.quest_oneshot_records,
+        &archive.quest_periodic_records,
+        &archive.map_records,
+        &archive.preset_slots,
+        &archive.preset_dev_items,
+        &archive.furniture,
+        &archive.airbases,
+        &archive.incentives,
+    );
+
+    Ok(new_profile)
+}
+
+/// Map an archived instance ID to the imported one; empty slots (`<= 0`) are kept.
+fn remap(ids: &HashMap<i64, i64>, id: i64) -> i64 {
+    if id <= 0 {
+        return id;
+    }
+
+    ids.get(&id).copied().unwrap_or(-1)
+}
+
+fn ship_slot_ids(ship: &ship::Model) -> [i64; 6] {
+    [ship.slot_1, ship.slot_2, ship.slot_3, ship.slot_4, ship.slot_5, ship.slot_ex]
+}
+
+fn fleet_ship_ids(fleet: &fleet::Model) -> [i64; 6] {
+    [fleet.ship_1, fleet.ship_2, fleet.ship_3, fleet.ship_4, fleet.ship_5, fleet.ship_6]
+}
+
+fn preset_deck_ship_ids(deck: &preset_deck::Model) -> [i64; 6] {
+    [deck.ship_1, deck.ship_2, deck.ship_3, deck.ship_4, deck.ship_5, deck.ship_6]
+}
+
+#[cfg(test)]
+mod tests {
+    use emukc_db::sea_orm::TryIntoModel;
+
+    use super::*;
+
+    fn archive() -> ProfileArchive {
+        ProfileArchive {
+            format: PROFILE_ARCHIVE_FORMAT.to_string(),
+            version: PROFILE_ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            profile: {
+                let mut am = profile::default_active_model(1, "archived");
+                am.id = ActiveValue::Set(1);
+                am.try_into_model().unwrap()
+            },
+            material: None,
+            game_settings: None,
+            option_settings: None,
+            oss_settings: None,
+            ships: Vec::new(),
+            ship_sp_effect_items: Vec::new(),
+            ship_morale_timer: None,
+            ship_records: Vec::new(),
+            slot_items: Vec::new(),
+            slot_item_records: Vec::new(),
+            use_items: Vec::new(),
+            pay_items: Vec::new(),
+            fleets: Vec::new(),
+            kdocks: Vec::new(),
+            ndocks: Vec::new(),
+            expeditions: Vec::new(),
+            quest_progress: Vec::new(),
+            quest_oneshot_records: Vec::new(),
+            quest_periodic_records: Vec::new(),
+            map_records: Vec::new(),
+            preset_caps: None,
+            preset_decks: Vec::new(),
+            preset_slots: Vec::new(),
+            preset_dev_items: Vec::new(),
+            furniture: Vec::new(),
+            furniture_config: None,
+            practice_config: None,
+            rivals: Vec::new(),
+            rival_details: Vec::new(),
+            rival_ships: Vec::new(),
+            airbases: Vec::new(),
+            airbase_planes: Vec::new(),
+            incentives: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn archive_references_must_resolve() {
+        let mut archive = archive();
+        assert!(check_archive_references(&archive).is_empty());
+
+        archive.ndocks.push(ndock::Model {
+            id: 1,
+            profile_id: 1,
+            index: 1,
+            status: ndock::Status::Busy,
+            ship_id: 42,
+            complete_time: None,
+            fuel: 0,
+            steel: 0,
+        });
+        let problems = check_archive_references(&archive);
+        assert_eq!(problems, vec!["repair dock 1 refers to missing ship 42".to_string()]);
+    }
+
+    #[test]
+    fn archive_header_is_checked() {
+        let mut archive = archive();
+        archive.format = "something-else".to_string();
+        archive.version = PROFILE_ARCHIVE_VERSION + 1;
+
+        assert_eq!(check_archive_references(&archive).len(), 2);
+    }
+
+    #[test]
+    fn remap_keeps_empty_slots() {
+        let ids = HashMap::from([(10, 110)]);
+
+        assert_eq!(remap(&ids, 10), 110);
+        assert_eq!(remap(&ids, -1), -1);
+        assert_eq!(remap(&ids, 0), 0);
+    }
+
+    #[tokio::test]
+    async fn profile_without_instances_roundtrips() {
+        use emukc_model::kc2::start2::ApiMstFurniture;
+
+        use crate::user::{AccountOps, ProfileOps};
+
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), Codex::default());
+        let owner = context.sign_up("archive-owner", "1234567").await.unwrap();
+        let other = context.sign_up("archive-other", "1234567").await.unwrap();
+        let source =
+            context.new_profile(&owner.access_token.token, "archived").await.unwrap().profile;
+
+        let archive = context.export_profile(&owner.access_token.token, source.id).await.unwrap();
+        assert!(!archive.fleets.is_empty());
+        assert!(context.export_profile(&other.access_token.token, source.id).await.is_err());
+
+        // the default profile comes with furniture the empty codex lacks
+        let mut codex = Codex::default();
+        codex.manifest.api_mst_furniture = archive
+            .furniture
+            .iter()
+            .map(|record| ApiMstFurniture {
+                api_id: record.furniture_id,
+                ..Default::default()
+            })
+            .collect();
+        let context = (context.0, codex);
+
+        let imported = context
+            .import_profile(&other.access_token.token, &archive, Some("copy"))
+            .await
+            .unwrap()
+            .profile;
+        assert_ne!(imported.id, source.id);
+        assert_eq!(imported.account_id, other.account.uid);
+        assert_eq!(imported.name, "copy");
+
+        let copy = context.export_profile(&other.access_token.token, imported.id).await.unwrap();
+        assert_eq!(copy.fleets.len(), archive.fleets.len());
+        assert_eq!(copy.ndocks.len(), archive.ndocks.len());
+        assert_eq!(copy.kdocks.len(), archive.kdocks.len());
+        assert!(copy.fleets.iter().all(|fleet| fleet.profile_id == imported.id));
+        assert_eq!(copy.material.map(|m| m.profile_id), Some(imported.id));
+        assert_eq!(copy.preset_caps.map(|caps| caps.id), Some(imported.id));
+    }
+}
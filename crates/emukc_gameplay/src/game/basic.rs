@@ -12,8 +12,7 @@ use emukc_model::{
 use crate::{err::GameplayError, gameplay::HasContext};
 
 use super::{
-    fleet::get_fleets_impl, furniture::get_furniture_config_impl, kdock::get_kdocks_impl,
-    use_item::find_use_item_impl,
+    fleet::find_fleets, furniture::get_furniture_config_impl, use_item::find_use_item_impl,
 };
 
 /// A trait for furniture related gameplay.
@@ -170,12 +169,14 @@ where
     // furniture
     let (_, furniture_cfg) = get_furniture_config_impl(c, profile_id).await?;
     // fleets
-    let fleets = get_fleets_impl(c, profile_id).await?;
+    let fleets = find_fleets(c, profile_id).await?;
     let api_count_deck = fleets.len() as i64;
     // construction docks
-    let kdocks = get_kdocks_impl(c, profile_id).await?;
-    let api_count_kdock =
-        kdocks.iter().filter(|x| x.status != kdock::Status::Locked).count() as i64;
+    let api_count_kdock = kdock::Entity::find()
+        .filter(kdock::Column::ProfileId.eq(profile_id))
+        .filter(kdock::Column::Status.ne(kdock::Status::Locked))
+        .count(c)
+        .await? as i64;
     // repair docks
     let api_count_ndock = ndock::Entity::find()
         .filter(ndock::Column::ProfileId.eq(profile_id))
@@ -1,14 +1,15 @@
 #![allow(non_snake_case)]
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use emukc_model::{
     kc2::KcSortieResultRank, profile::practice::Rival, thirdparty::FleetShipSnapshot,
 };
 
 use emukc_battle::{
-    AirState, BattleHougeki, BattleKouku, BattleNightHougeki, BattleOpeningAttack, BattleOutcome,
-    BattleRaigeki, BattleRuntimeShip, BattleShipInput,
+    AirState, BattleAirBaseAttack, BattleHougeki, BattleKouku, BattleNightHougeki,
+    BattleOpeningAttack, BattleOutcome, BattleRaigeki, BattleRuntimeShip, BattleShipInput,
+    BattleSupportInfo,
 };
 
 pub(crate) mod exp;
@@ -50,6 +51,8 @@ pub struct PracticeBattleResponse {
     pub api_eSlot: Vec<[i64; 5]>,
     pub api_eParam: Vec<[i64; 4]>,
     pub api_e_effect_list: Vec<Vec<i64>>,
+    #[serde(flatten)]
+    pub combined: BattleCombinedArrays,
     pub api_smoke_type: i64,
     pub api_balloon_cell: i64,
     pub api_atoll_cell: i64,
@@ -57,7 +60,11 @@ pub struct PracticeBattleResponse {
     pub api_search: [i64; 2],
     pub api_stage_flag: [i64; 3],
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_air_base_attack: Option<Vec<BattleAirBaseAttack>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub api_kouku: Option<BattleKouku>,
+    pub api_support_flag: i64,
+    pub api_support_info: Option<BattleSupportInfo>,
     pub api_opening_taisen_flag: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_opening_taisen: Option<BattleHougeki>,
@@ -75,7 +82,32 @@ pub struct PracticeBattleResponse {
     pub api_raigeki: Option<BattleRaigeki>,
 }
 
-#[derive(Debug, Clone)]
+/// Escort-fleet arrays of a combined-fleet battle (the `*_combined` keys).
+///
+/// Each side is present only when that side fields an escort fleet.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BattleCombinedArrays {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_f_nowhps_combined: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_f_maxhps_combined: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_fParam_combined: Option<Vec<[i64; 4]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_ship_ke_combined: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_ship_lv_combined: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_e_nowhps_combined: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_e_maxhps_combined: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_eSlot_combined: Option<Vec<[i64; 5]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_eParam_combined: Option<Vec<[i64; 4]>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PracticeBattleResultSnapshot {
     pub deck_id: i64,
     pub enemy_id: i64,
@@ -96,7 +128,7 @@ pub struct PracticeBattleResultSnapshot {
     pub enemy_deck_name: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PracticeBattleSession {
     pub profile_id: i64,
     pub deck_id: i64,
@@ -12,8 +12,8 @@ use super::super::practice_repository::PracticeRepository;
 use super::exp::{calculate_admiral_exp, calculate_ship_exp};
 use super::response::{build_night_response, calculate_base_exp, enemy_slot_ids};
 use super::{
-    PracticeBattleInput, PracticeBattleResponse, PracticeBattleResultSnapshot,
-    PracticeBattleSession, PracticeNightBattleResponse,
+    BattleCombinedArrays, PracticeBattleInput, PracticeBattleResponse,
+    PracticeBattleResultSnapshot, PracticeBattleSession, PracticeNightBattleResponse,
 };
 
 /// Run a practice day battle and produce response + result snapshot.
@@ -39,6 +39,10 @@ pub fn run_day_battle(
             engagement: EngagementType::SameCourse,
             friend_ships: input.friend_ships,
             enemy_ships: input.enemy_ships,
+            air_base_waves: Vec::new(),
+            combined: None,
+            support: None,
+            historical_bonuses: Vec::new(),
         },
         rng,
     );
@@ -110,13 +114,17 @@ pub fn run_day_battle(
                 }
             })
             .collect(),
+        combined: BattleCombinedArrays::default(),
         api_smoke_type: simulation.packet.smoke_type,
         api_balloon_cell: simulation.packet.balloon_cell,
         api_atoll_cell: simulation.packet.atoll_cell,
         api_midnight_flag: simulation.packet.midnight_flag,
         api_search: simulation.packet.search,
         api_stage_flag: simulation.packet.stage_flag,
+        api_air_base_attack: None,
         api_kouku: simulation.packet.kouku,
+        api_support_flag: 0,
+        api_support_info: None,
         api_opening_taisen_flag: simulation.packet.opening_taisen_flag,
         api_opening_taisen: simulation.packet.opening_taisen,
         api_opening_flag: simulation.packet.opening_flag,
@@ -204,10 +212,14 @@ pub fn run_night_battle(
         NightBattleInput {
             friendly: session.friendly.clone(),
             enemy: session.enemy.clone(),
+            friendly_escort: Vec::new(),
+            enemy_escort: Vec::new(),
             friendly_formation_id: session.formation[0],
             enemy_formation_id: session.formation[1],
             engagement,
             air_state: session.air_state,
+            friend_fleet: None,
+            historical_bonuses: Vec::new(),
         },
         rng,
     );
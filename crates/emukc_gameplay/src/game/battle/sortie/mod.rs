@@ -1,7 +1,9 @@
 use emukc_battle::{
-    BattleContext, BattleOutcome, BattlePacket, BattleRuntimeShip, BattleSimulation,
-    NightBattlePacket,
+    BattleAirBaseWave, BattleContext, BattleOutcome, BattlePacket, BattleRuntimeShip,
+    BattleSimulation, NightBattlePacket,
 };
+use emukc_model::codex::{Codex, historical_bonus::HistoricalBonus};
+use serde::{Deserialize, Serialize};
 
 pub(crate) mod orchestrate;
 pub(crate) mod response;
@@ -23,7 +25,7 @@ pub struct SortieBattleInput {
     pub context: BattleContext,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SortieBattleSession {
     pub profile_id: i64,
     pub deck_id: i64,
@@ -33,8 +35,13 @@ pub struct SortieBattleSession {
     pub enemy_ship_ids: Vec<i64>,
     pub friendly: Vec<BattleRuntimeShip>,
     pub enemy: Vec<BattleRuntimeShip>,
+    /// Escort fleets of a combined-fleet battle; empty otherwise.
+    pub friendly_escort: Vec<BattleRuntimeShip>,
+    pub enemy_escort: Vec<BattleRuntimeShip>,
     pub packet: BattlePacket,
     pub outcome: BattleOutcome,
+    /// Land-based air waves with the planes they brought back.
+    pub air_base_waves: Vec<BattleAirBaseWave>,
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +52,15 @@ pub struct SortieNightBattleSession {
     pub outcome: BattleOutcome,
 }
 
+/// Historical bonuses (特効) in effect at a map cell.
+pub(crate) fn node_historical_bonuses(
+    codex: &Codex,
+    map_id: i64,
+    cell_id: i64,
+) -> Vec<HistoricalBonus> {
+    codex.historical_bonuses.for_node(map_id, cell_id).into_iter().cloned().collect()
+}
+
 pub(crate) fn build_sortie_session(
     profile_id: i64,
     deck_id: i64,
@@ -61,8 +77,11 @@ pub(crate) fn build_sortie_session(
         enemy_ship_ids: simulation.enemy.iter().map(|ship| ship.ship.api_ship_id).collect(),
         friendly: simulation.friendly,
         enemy: simulation.enemy,
+        friendly_escort: simulation.friendly_escort,
+        enemy_escort: simulation.enemy_escort,
         packet: simulation.packet,
         outcome: simulation.outcome,
+        air_base_waves: simulation.air_base_waves,
     }
 }
 
@@ -105,6 +124,10 @@ mod tests {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![sample_ship(&codex, 89, 99)],
                 enemy_ships: vec![sample_ship(&codex, 412, 99)],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
             &mut rng,
         );
@@ -1,8 +1,8 @@
 //! Sortie battle orchestration — build context → call `emukc_battle` → persist.
 
 use emukc_battle::{
-    BattleOutcome, BattlePacket, BattleRng, BattleRuntimeShip, EngagementType, NightBattleInput,
-    execute_day, execute_night,
+    BattleOutcome, BattlePacket, BattleRng, BattleRuntimeShip, EngagementType, FriendFleetInput,
+    NightBattleInput, NightBattleSimulation, execute_day, execute_night,
 };
 use emukc_model::codex::Codex;
 use emukc_model::kc2::KcSortieResultRank;
@@ -10,6 +10,7 @@ use emukc_model::kc2::KcSortieResultRank;
 use super::super::repository::SortieRepository;
 use super::{
     SortieBattleInput, SortieBattleSession, SortieNightBattleSession, build_sortie_session,
+    node_historical_bonuses,
 };
 
 /// Run a day battle, store the session, return it.
@@ -49,6 +50,7 @@ pub fn pending_battle(
 }
 
 /// Run a night battle following a day battle, update the stored session.
+#[allow(clippy::too_many_arguments)]
 pub fn run_night_battle(
     store: &dyn SortieRepository,
     codex: &Codex,
@@ -56,6 +58,7 @@ pub fn run_night_battle(
     friendly_formation_id: i64,
     enemy_formation_id: i64,
     engagement: EngagementType,
+    friend_fleet: Option<FriendFleetInput>,
     rng: &mut impl BattleRng,
 ) -> Option<SortieNightBattleSession> {
     use emukc_battle::AirState;
@@ -71,19 +74,18 @@ pub fn run_night_battle(
         NightBattleInput {
             friendly: session.friendly.clone(),
             enemy: session.enemy.clone(),
+            friendly_escort: session.friendly_escort.clone(),
+            enemy_escort: session.enemy_escort.clone(),
             friendly_formation_id,
             enemy_formation_id,
             engagement,
             air_state,
+            friend_fleet,
+            historical_bonuses: node_historical_bonuses(codex, session.map_id, session.cell_id),
         },
         rng,
     );
-    session.friendly = simulation.friendly.clone();
-    session.enemy = simulation.enemy.clone();
-    session.outcome = simulation.outcome.clone();
-    session.packet.friendly_nowhps = simulation.packet.friendly_nowhps.clone();
-    session.packet.enemy_nowhps = simulation.packet.enemy_nowhps.clone();
-    session.packet.midnight_flag = 0;
+    apply_night_result(&mut session, &simulation);
     store.insert_pending_battle(profile_id, session);
 
     Some(SortieNightBattleSession {
@@ -102,6 +104,7 @@ pub fn run_sp_midnight_battle(
     codex: &Codex,
     input: SortieBattleInput,
     enemy_formation_id: i64,
+    friend_fleet: Option<FriendFleetInput>,
     rng: &mut impl BattleRng,
 ) -> (SortieBattleSession, SortieNightBattleSession) {
     let SortieBattleInput {
@@ -114,10 +117,28 @@ pub fn run_sp_midnight_battle(
 
     let friendly_formation_id = context.friendly_formation_id;
     let engagement = context.engagement;
+    let historical_bonuses = context.historical_bonuses;
     let friendly: Vec<BattleRuntimeShip> =
         context.friend_ships.into_iter().map(|s| BattleRuntimeShip::new(s, true, true)).collect();
     let enemy: Vec<BattleRuntimeShip> =
         context.enemy_ships.into_iter().map(|s| BattleRuntimeShip::new(s, false, true)).collect();
+    let (friendly_escort, enemy_escort) = context.combined.map_or_else(
+        || (Vec::new(), Vec::new()),
+        |combined| {
+            (
+                combined
+                    .friend_escort_ships
+                    .into_iter()
+                    .map(|s| BattleRuntimeShip::new(s, true, true))
+                    .collect::<Vec<_>>(),
+                combined
+                    .enemy_escort_ships
+                    .into_iter()
+                    .map(|s| BattleRuntimeShip::new(s, false, true))
+                    .collect::<Vec<_>>(),
+            )
+        },
+    );
 
     // Create a minimal day session to anchor the night battle
     let day_session = SortieBattleSession {
@@ -129,10 +150,14 @@ pub fn run_sp_midnight_battle(
         enemy_ship_ids: enemy.iter().map(|s| s.ship.api_ship_id).collect(),
         friendly: friendly.clone(),
         enemy: enemy.clone(),
+        friendly_escort: friendly_escort.clone(),
+        enemy_escort: enemy_escort.clone(),
         packet: BattlePacket {
             formation: [friendly_formation_id, enemy_formation_id, engagement.api_id()],
             friendly_nowhps: friendly.iter().map(BattleRuntimeShip::hp).collect(),
             enemy_nowhps: enemy.iter().map(BattleRuntimeShip::hp).collect(),
+            friendly_nowhps_combined: escort_hps(&friendly_escort),
+            enemy_nowhps_combined: escort_hps(&enemy_escort),
             smoke_type: 0,
             balloon_cell: 0,
             atoll_cell: 0,
@@ -149,12 +174,16 @@ pub fn run_sp_midnight_battle(
             hougeki2: None,
             hougeki3: None,
             raigeki: None,
+            air_base_attack: None,
+            support_flag: 0,
+            support_info: None,
         },
         outcome: BattleOutcome {
             win_rank: KcSortieResultRank::D,
             mvp: 0,
             can_midnight: true,
         },
+        air_base_waves: Vec::new(),
     };
     store.insert_pending_battle(profile_id, day_session.clone());
 
@@ -164,22 +193,21 @@ pub fn run_sp_midnight_battle(
         NightBattleInput {
             friendly,
             enemy,
+            friendly_escort,
+            enemy_escort,
             friendly_formation_id,
             enemy_formation_id,
             engagement,
             air_state: None,
+            friend_fleet,
+            historical_bonuses,
         },
         rng,
     );
 
     // Update the stored day session with night results
     if let Some(mut stored) = store.take_pending_battle(profile_id) {
-        stored.friendly = night.friendly.clone();
-        stored.enemy = night.enemy.clone();
-        stored.outcome = night.outcome.clone();
-        stored.packet.friendly_nowhps = night.packet.friendly_nowhps.clone();
-        stored.packet.enemy_nowhps = night.packet.enemy_nowhps.clone();
-        stored.packet.midnight_flag = 0;
+        apply_night_result(&mut stored, &night);
         store.insert_pending_battle(profile_id, stored);
     }
 
@@ -191,3 +219,21 @@ pub fn run_sp_midnight_battle(
 
     (day_session, night_session)
 }
+
+/// Carry a night battle's end state back into the stored day session.
+fn apply_night_result(session: &mut SortieBattleSession, night: &NightBattleSimulation) {
+    session.friendly = night.friendly.clone();
+    session.enemy = night.enemy.clone();
+    session.friendly_escort = night.friendly_escort.clone();
+    session.enemy_escort = night.enemy_escort.clone();
+    session.outcome = night.outcome.clone();
+    session.packet.friendly_nowhps = night.packet.friendly_nowhps.clone();
+    session.packet.enemy_nowhps = night.packet.enemy_nowhps.clone();
+    session.packet.friendly_nowhps_combined = night.packet.friendly_nowhps_combined.clone();
+    session.packet.enemy_nowhps_combined = night.packet.enemy_nowhps_combined.clone();
+    session.packet.midnight_flag = 0;
+}
+
+fn escort_hps(escort: &[BattleRuntimeShip]) -> Option<Vec<i64>> {
+    (!escort.is_empty()).then(|| escort.iter().map(BattleRuntimeShip::hp).collect())
+}
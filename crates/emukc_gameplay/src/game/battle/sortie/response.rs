@@ -1,8 +1,9 @@
 //! Sortie battle API response construction.
 
 use emukc_battle::{BattlePacket, BattleShipInput, NightBattlePacket};
+use emukc_model::kc2::KcApiShip;
 
-use super::super::practice::PracticeBattleResponse;
+use super::super::practice::{BattleCombinedArrays, PracticeBattleResponse};
 use super::SortieBattleSession;
 use crate::game::sortie::SortieNightBattleResponse;
 
@@ -24,13 +25,54 @@ pub(crate) fn enemy_slot_ids(ship: &BattleShipInput) -> [i64; 5] {
     slots
 }
 
+/// `api_fParam` / `api_eParam` entry: firepower, torpedo, AA, armor.
+fn ship_param(ship: &KcApiShip) -> [i64; 4] {
+    [ship.api_karyoku[0], ship.api_raisou[0], ship.api_taiku[0], ship.api_soukou[0]]
+}
+
+/// `None` for an absent escort fleet, so the `*_combined` key is omitted.
+fn escort_array<T>(values: Vec<T>) -> Option<Vec<T>> {
+    (!values.is_empty()).then_some(values)
+}
+
 /// Build a sortie day-battle API response.
+///
+/// The escort fleets are empty unless that side is a combined fleet.
 pub fn build_day_response(
     deck_id: i64,
     friend_ships: Vec<BattleShipInput>,
     enemy_ships: Vec<BattleShipInput>,
+    friend_escort: &[BattleShipInput],
+    enemy_escort: &[BattleShipInput],
     packet: BattlePacket,
 ) -> PracticeBattleResponse {
+    let combined = BattleCombinedArrays {
+        api_f_nowhps_combined: escort_array(
+            friend_escort.iter().map(|ship| ship.ship.api_nowhp).collect(),
+        ),
+        api_f_maxhps_combined: escort_array(
+            friend_escort.iter().map(|ship| ship.ship.api_maxhp).collect(),
+        ),
+        api_fParam_combined: escort_array(
+            friend_escort.iter().map(|ship| ship_param(&ship.ship)).collect(),
+        ),
+        api_ship_ke_combined: escort_array(
+            enemy_escort.iter().map(|ship| ship.ship.api_ship_id).collect(),
+        ),
+        api_ship_lv_combined: escort_array(
+            enemy_escort.iter().map(|ship| ship.ship.api_lv).collect(),
+        ),
+        api_e_nowhps_combined: escort_array(
+            enemy_escort.iter().map(|ship| ship.ship.api_nowhp).collect(),
+        ),
+        api_e_maxhps_combined: escort_array(
+            enemy_escort.iter().map(|ship| ship.ship.api_maxhp).collect(),
+        ),
+        api_eSlot_combined: escort_array(enemy_escort.iter().map(enemy_slot_ids).collect()),
+        api_eParam_combined: escort_array(
+            enemy_escort.iter().map(|ship| ship_param(&ship.ship)).collect(),
+        ),
+    };
     PracticeBattleResponse {
         api_deck_id: deck_id,
         api_formation: packet.formation,
@@ -73,13 +115,17 @@ pub fn build_day_response(
                 }
             })
             .collect(),
+        combined,
         api_smoke_type: packet.smoke_type,
         api_balloon_cell: packet.balloon_cell,
         api_atoll_cell: packet.atoll_cell,
         api_midnight_flag: packet.midnight_flag,
         api_search: packet.search,
         api_stage_flag: packet.stage_flag,
+        api_air_base_attack: packet.air_base_attack,
         api_kouku: packet.kouku,
+        api_support_flag: packet.support_flag,
+        api_support_info: packet.support_info,
         api_opening_taisen_flag: packet.opening_taisen_flag,
         api_opening_taisen: packet.opening_taisen,
         api_opening_flag: packet.opening_flag,
@@ -98,9 +144,31 @@ pub fn build_night_response(
     session: &SortieBattleSession,
     packet: NightBattlePacket,
 ) -> SortieNightBattleResponse {
+    let combined = BattleCombinedArrays {
+        api_f_nowhps_combined: packet.friendly_nowhps_combined,
+        api_f_maxhps_combined: packet.friendly_maxhps_combined,
+        api_fParam_combined: escort_array(
+            session.friendly_escort.iter().map(|ship| ship_param(&ship.ship)).collect(),
+        ),
+        api_ship_ke_combined: escort_array(
+            session.enemy_escort.iter().map(|ship| ship.ship.api_ship_id).collect(),
+        ),
+        api_ship_lv_combined: escort_array(
+            session.enemy_escort.iter().map(|ship| ship.ship.api_lv).collect(),
+        ),
+        api_e_nowhps_combined: packet.enemy_nowhps_combined,
+        api_e_maxhps_combined: packet.enemy_maxhps_combined,
+        api_eSlot_combined: escort_array(
+            session.enemy_escort.iter().map(super::super::practice::enemy_slot_ids).collect(),
+        ),
+        api_eParam_combined: escort_array(
+            session.enemy_escort.iter().map(|ship| ship_param(&ship.ship)).collect(),
+        ),
+    };
     SortieNightBattleResponse {
         api_deck_id: deck_id,
         api_formation: packet.formation,
+        api_active_deck: packet.active_deck,
         api_f_nowhps: packet.friendly_nowhps,
         api_f_maxhps: packet.friendly_maxhps,
         api_fParam: session
@@ -132,11 +200,15 @@ pub fn build_night_response(
                 ]
             })
             .collect(),
+        combined,
         api_smoke_type: 0,
         api_balloon_cell: 0,
         api_atoll_cell: 0,
         api_touch_plane: packet.touch_plane,
         api_flare_pos: packet.flare_pos,
         api_hougeki: packet.hougeki,
+        api_friendly_info: packet.friendly_info,
+        api_friendly_kouku: packet.friendly_kouku,
+        api_friendly_battle: packet.friendly_battle,
     }
 }
@@ -0,0 +1,145 @@
+//! Game clock debug operations.
+
+use async_trait::async_trait;
+use emukc_time::{
+    ClockOverride,
+    chrono::{DateTime, Duration, Utc},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{err::GameplayError, gameplay::HasContext};
+
+use super::basic::find_profile;
+
+/// A profile's game clock, as seen by the debug API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockStatus {
+    /// The profile's current game time.
+    pub now: DateTime<Utc>,
+
+    /// Seconds the clock runs ahead of the system time.
+    pub offset_secs: i64,
+
+    /// Whether the clock is frozen.
+    pub frozen: bool,
+}
+
+impl ClockStatus {
+    fn new(now: DateTime<Utc>, clock: ClockOverride) -> Self {
+        Self {
+            now,
+            offset_secs: (now - Utc::now()).num_seconds(),
+            frozen: clock.frozen_at.is_some(),
+        }
+    }
+}
+
+/// A trait for manipulating a profile's game clock.
+///
+/// Timers, morale recovery and periodic resets are evaluated lazily against the
+/// game clock, so they take effect on the next request that reads them.
+#[async_trait]
+pub trait ClockOps {
+    /// Get the game clock of a profile.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    async fn get_clock(&self, profile_id: i64) -> Result<ClockStatus, GameplayError>;
+
+    /// Freeze the game clock of a profile.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `at`: The time to freeze at, the current game time if `None`.
+    async fn freeze_clock(
+        &self,
+        profile_id: i64,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<ClockStatus, GameplayError>;
+
+    /// Let a frozen game clock run again.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    async fn resume_clock(&self, profile_id: i64) -> Result<ClockStatus, GameplayError>;
+
+    /// Jump the game clock of a profile.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `by`: The time to jump, negative to go back.
+    async fn advance_clock(
+        &self,
+        profile_id: i64,
+        by: Duration,
+    ) -> Result<ClockStatus, GameplayError>;
+
+    /// Put the game clock of a profile back on the system time.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    async fn reset_clock(&self, profile_id: i64) -> Result<ClockStatus, GameplayError>;
+}
+
+#[async_trait]
+impl<T: HasContext + ?Sized> ClockOps for T {
+    async fn get_clock(&self, profile_id: i64) -> Result<ClockStatus, GameplayError> {
+        find_profile(self.db(), profile_id).await?;
+
+        let clock = self.clock();
+        Ok(ClockStatus::new(clock.now(profile_id), clock.override_of(profile_id)))
+    }
+
+    async fn freeze_clock(
+        &self,
+        profile_id: i64,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<ClockStatus, GameplayError> {
+        find_profile(self.db(), profile_id).await?;
+
+        let clock = self.clock();
+        let now = clock.freeze(profile_id, at);
+        info!("profile {profile_id} clock frozen at {now}");
+
+        Ok(ClockStatus::new(now, clock.override_of(profile_id)))
+    }
+
+    async fn resume_clock(&self, profile_id: i64) -> Result<ClockStatus, GameplayError> {
+        find_profile(self.db(), profile_id).await?;
+
+        let clock = self.clock();
+        let now = clock.resume(profile_id);
+        info!("profile {profile_id} clock resumed at {now}");
+
+        Ok(ClockStatus::new(now, clock.override_of(profile_id)))
+    }
+
+    async fn advance_clock(
+        &self,
+        profile_id: i64,
+        by: Duration,
+    ) -> Result<ClockStatus, GameplayError> {
+        find_profile(self.db(), profile_id).await?;
+
+        let clock = self.clock();
+        let now = clock.advance(profile_id, by);
+        info!("profile {profile_id} clock advanced by {by} to {now}");
+
+        Ok(ClockStatus::new(now, clock.override_of(profile_id)))
+    }
+
+    async fn reset_clock(&self, profile_id: i64) -> Result<ClockStatus, GameplayError> {
+        find_profile(self.db(), profile_id).await?;
+
+        let clock = self.clock();
+        let now = clock.reset(profile_id);
+        info!("profile {profile_id} clock reset");
+
+        Ok(ClockStatus::new(now, clock.override_of(profile_id)))
+    }
+}
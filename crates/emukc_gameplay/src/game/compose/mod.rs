@@ -181,7 +181,7 @@ impl<T: HasContext + ?Sized> ComposeOps for T {
                 .await?;
         tx.commit().await?;
 
-        let fleets = get_fleets_impl(db, profile_id).await?;
+        let fleets = get_fleets_impl(db, profile_id, self.clock().now(profile_id)).await?;
 
         let unset_slot_items = if let Some(item_types) = result.unset_slot_item_types {
             let types: Vec<i64> = item_types.iter().copied().collect();
@@ -68,6 +68,7 @@ where
     } else {
         Some(params.to_slot_idx)
     };
+    let to_ship_mst_id = to_ship_model.mst_id;
     let to_slot = get_slot_mut(&mut to_ship_model, params.to_ex_slot, to_slot_idx);
     let unset_slot_item_id = *to_slot;
     *to_slot = slot_item_id;
@@ -75,6 +76,12 @@ where
     // update slot item
     {
         let set_slot_item_model = find_slot_item_impl(c, slot_item_id).await?;
+        codex.check_equip(
+            to_ship_mst_id,
+            set_slot_item_model.mst_id,
+            set_slot_item_model.level,
+            params.to_ex_slot,
+        )?;
         let mut am: slot_item::ActiveModel = set_slot_item_model.into();
         am.equip_on = ActiveValue::Set(params.to_ship_id);
         let m = am.update(c).await?;
@@ -63,6 +63,28 @@ pub struct ExpeditionCompletion {
     pub item_rewards: [Option<ExpeditionItemReward>; 2],
 }
 
+/// Sortie support expedition (支援任務) kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupportMissionKind {
+    /// 前衛支援任務: covers every battle node except the boss.
+    Node,
+    /// 艦隊決戦支援任務: covers the boss node.
+    Boss,
+}
+
+impl SupportMissionKind {
+    /// Support kind of a mission, `None` for an ordinary expedition.
+    pub fn of(mission: &ApiMstMission) -> Option<Self> {
+        if mission.api_name.contains("前衛支援") {
+            Some(Self::Node)
+        } else if mission.api_name.contains("決戦支援") {
+            Some(Self::Boss)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct ExpeditionSupplyCost {
     ship_id: i64,
@@ -122,6 +144,14 @@ pub trait ExpeditionOps {
 
     /// Recall an expedition currently in progress.
     async fn recall_expedition(&self, profile_id: i64, fleet_id: i64) -> Result<(), GameplayError>;
+
+    /// Find the fleet on a support expedition of `kind` covering sea area `area_id`.
+    async fn find_support_fleet(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        kind: SupportMissionKind,
+    ) -> Result<Option<fleet::Model>, GameplayError>;
 }
 
 #[async_trait]
@@ -139,7 +169,7 @@ impl<T: HasContext + ?Sized> ExpeditionOps for T {
             .all(&tx)
             .await?;
 
-        let now = Utc::now();
+        let now = self.clock().now(profile_id);
         let next_refresh_time = codex
             .manifest
             .api_mst_mission
@@ -188,10 +218,14 @@ impl<T: HasContext + ?Sized> ExpeditionOps for T {
         let fleet_ships = get_fleet_ships_impl(&tx, profile_id, fleet_id).await?;
         validate_expedition_start(&tx, codex, &fleet_ships, expedition_condition).await?;
         let launch_snapshot = collect_expedition_launch_snapshot(&tx, &fleet_ships).await?;
-        let supply_costs = calculate_expedition_supply_costs(codex, mission_mst, &fleet_ships)?;
-        apply_expedition_supply_costs(&tx, &fleet_ships, &supply_costs).await?;
+        // Support missions pay for their battles instead of the voyage.
+        if SupportMissionKind::of(mission_mst).is_none() {
+            let supply_costs = calculate_expedition_supply_costs(codex, mission_mst, &fleet_ships)?;
+            apply_expedition_supply_costs(&tx, &fleet_ships, &supply_costs).await?;
+        }
 
-        let complete_time = Utc::now() + Duration::minutes(mission_mst.api_time);
+        let now = self.clock().now(profile_id);
+        let complete_time = now + Duration::minutes(mission_mst.api_time);
 
         {
             let mut am = fleet_model.into_active_model();
@@ -206,7 +240,7 @@ impl<T: HasContext + ?Sized> ExpeditionOps for T {
             am.update(&tx).await?;
         }
 
-        mark_expedition_started(&tx, profile_id, mission_mst, Utc::now()).await?;
+        mark_expedition_started(&tx, profile_id, mission_mst, now).await?;
 
         tx.commit().await?;
 
@@ -231,7 +265,7 @@ impl<T: HasContext + ?Sized> ExpeditionOps for T {
             ));
         }
 
-        let now = Utc::now();
+        let now = self.clock().now(profile_id);
         let mission_id = fleet_model.mission_id;
         let mission_mst = find_mission_mst(codex, mission_id)
             .ok_or(GameplayError::ManifestNotFound(mission_id))?;
@@ -240,7 +274,7 @@ impl<T: HasContext + ?Sized> ExpeditionOps for T {
                 GameplayError::BadManifest(format!("expedition condition {mission_id} not found"))
             })?;
         let mission_ships_before_return =
-            get_fleet_ships_with_morale_refresh(&tx, profile_id, fleet_id).await?;
+            get_fleet_ships_with_morale_refresh(&tx, profile_id, fleet_id, now).await?;
         let ship_ids: Vec<i64> = mission_ships_before_return.iter().map(|ship| ship.id).collect();
         let is_recall = fleet_model.mission_status == fleet::MissionStatus::ForceReturning;
         let launch_snapshot = load_expedition_launch_snapshot(&fleet_model).unwrap_or(
@@ -427,7 +461,7 @@ impl<T: HasContext + ?Sized> ExpeditionOps for T {
 
         let mission_mst = find_mission_mst(self.codex(), fleet_model.mission_id)
             .ok_or(GameplayError::ManifestNotFound(fleet_model.mission_id))?;
-        let now = Utc::now();
+        let now = self.clock().now(profile_id);
         let current_return_time = fleet_model.return_time.ok_or_else(|| {
             GameplayError::WrongType(format!("fleet {fleet_id} is missing expedition return time",))
         })?;
@@ -443,6 +477,16 @@ impl<T: HasContext + ?Sized> ExpeditionOps for T {
 
         Ok(())
     }
+
+    async fn find_support_fleet(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        kind: SupportMissionKind,
+    ) -> Result<Option<fleet::Model>, GameplayError> {
+        let now = self.clock().now(profile_id);
+        find_support_fleet_impl(self.db(), self.codex(), profile_id, area_id, kind, now).await
+    }
 }
 
 async fn validate_expedition_start<C>(
@@ -653,6 +697,7 @@ async fn get_fleet_ships_with_morale_refresh<C>(
     c: &C,
     profile_id: i64,
     fleet_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<Vec<ship::Model>, GameplayError>
 where
     C: ConnectionTrait,
@@ -660,7 +705,7 @@ where
     let fleet = find_fleet(c, profile_id, fleet_id).await?;
     let fleet_ship_ids =
         [fleet.ship_1, fleet.ship_2, fleet.ship_3, fleet.ship_4, fleet.ship_5, fleet.ship_6];
-    let (ships, _) = get_ships_impl(c, profile_id).await?;
+    let (ships, _) = get_ships_impl(c, profile_id, now).await?;
 
     let mut mission_ships =
         ships.into_iter().filter(|ship| fleet_ship_ids.contains(&ship.id)).collect::<Vec<_>>();
@@ -675,6 +720,60 @@ fn find_mission_mst(codex: &Codex, mission_id: i64) -> Option<&ApiMstMission> {
     codex.manifest.api_mst_mission.iter().find(|mission| mission.api_id == mission_id)
 }
 
+/// Fleet still out on a `kind` support expedition to sea area `area_id`.
+pub(crate) async fn find_support_fleet_impl<C>(
+    c: &C,
+    codex: &Codex,
+    profile_id: i64,
+    area_id: i64,
+    kind: SupportMissionKind,
+    now: DateTime<Utc>,
+) -> Result<Option<fleet::Model>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let fleets = fleet::Entity::find()
+        .filter(fleet::Column::ProfileId.eq(profile_id))
+        .filter(fleet::Column::MissionStatus.eq(fleet::MissionStatus::InMission))
+        .all(c)
+        .await?;
+
+    Ok(fleets.into_iter().find(|fleet| {
+        fleet.return_time.is_none_or(|return_time| return_time > now)
+            && find_mission_mst(codex, fleet.mission_id).is_some_and(|mission| {
+                mission.api_maparea_id == area_id && SupportMissionKind::of(mission) == Some(kind)
+            })
+    }))
+}
+
+/// Charge a support fleet's fuel and ammo once it has joined a battle.
+///
+/// Support missions skip the launch cost, so the mission's ratios apply
+/// here instead; a ship short of supply is drained to zero.
+pub(crate) async fn charge_support_fleet_impl<C>(
+    c: &C,
+    codex: &Codex,
+    profile_id: i64,
+    support_fleet: &fleet::Model,
+) -> Result<(), GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mission_mst = find_mission_mst(codex, support_fleet.mission_id)
+        .ok_or(GameplayError::ManifestNotFound(support_fleet.mission_id))?;
+    let fleet_ships = get_fleet_ships_impl(c, profile_id, support_fleet.index).await?;
+    let supply_costs = calculate_expedition_supply_costs(codex, mission_mst, &fleet_ships)?;
+
+    for (ship, cost) in fleet_ships.iter().zip(&supply_costs) {
+        let mut am = (*ship).into_active_model();
+        am.fuel = ActiveValue::Set((ship.fuel - cost.fuel).max(0));
+        am.ammo = ActiveValue::Set((ship.ammo - cost.ammo).max(0));
+        am.update(c).await?;
+    }
+
+    Ok(())
+}
+
 fn validate_expedition_full_supply(
     codex: &Codex,
     fleet_ships: &[ship::Model],
@@ -1321,7 +1420,7 @@ where
         return Ok(record);
     }
 
-    let first_day_of_this_month = KcTime::jst_0500_of_nth_day(1);
+    let first_day_of_this_month = KcTime::jst_0500_of_nth_day(&now, 1);
     if now <= first_day_of_this_month {
         return Ok(record);
     }
@@ -1374,7 +1473,103 @@ fn idle_fleet_err(fleet_id: i64, context: &str) -> GameplayError {
 
 #[cfg(test)]
 mod tests {
+    use emukc_model::prelude::Kc3rdShip;
+
     use super::*;
+    use crate::{
+        game::{FleetOps, ShipOps},
+        user::{AccountOps, ProfileOps},
+    };
+
+    #[tokio::test]
+    async fn support_fleet_pays_its_ratios_per_battle() {
+        let mut codex = Codex::default();
+        codex.manifest.api_mst_mission.push(ApiMstMission {
+            api_id: 33,
+            api_name: "前衛支援任務".to_string(),
+            api_use_fuel: 0.5,
+            api_use_bull: 0.8,
+            ..Default::default()
+        });
+        codex.manifest.api_mst_ship.push(ApiMstShip {
+            api_id: 1,
+            api_taik: Some([15, 30]),
+            api_fuel_max: Some(20),
+            api_bull_max: Some(20),
+            ..Default::default()
+        });
+        codex.ship_extra.insert(
+            1,
+            Kc3rdShip {
+                api_id: 1,
+                kaih: [0, 0],
+                tais: [0, 0],
+                saku: [0, 0],
+                luck: [0, 0],
+                luck_bonus: 0.0,
+                armor_bonus: 0,
+                cnum: 1,
+                buildable: false,
+                buildable_lsc: false,
+                slots: Vec::new(),
+                remodel: None,
+                remodel_back_to: None,
+                remodel_back_requirement: None,
+            },
+        );
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex);
+        let account = context.sign_up("support", "1234567").await.unwrap();
+        let profile_id =
+            context.new_profile(&account.access_token.token, "support").await.unwrap().profile.id;
+        let ship = context.add_ship(profile_id, 1).await.unwrap();
+        context
+            .update_fleet_ships(profile_id, 1, &[ship.api_id, -1, -1, -1, -1, -1])
+            .await
+            .unwrap();
+
+        let mut am: fleet::ActiveModel = fleet::Entity::find()
+            .filter(fleet::Column::ProfileId.eq(profile_id))
+            .filter(fleet::Column::Index.eq(1))
+            .one(&context.0)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        am.mission_id = ActiveValue::Set(33);
+        let support_fleet = am.update(&context.0).await.unwrap();
+
+        let supply = |ship: ship::Model| (ship.fuel, ship.ammo);
+        charge_support_fleet_impl(&context.0, &context.1, profile_id, &support_fleet)
+            .await
+            .unwrap();
+        let charged = ship::Entity::find_by_id(ship.api_id).one(&context.0).await.unwrap().unwrap();
+        assert_eq!(supply(charged), (10, 4));
+
+        // a ship short of supply is drained to zero
+        charge_support_fleet_impl(&context.0, &context.1, profile_id, &support_fleet)
+            .await
+            .unwrap();
+        let drained = ship::Entity::find_by_id(ship.api_id).one(&context.0).await.unwrap().unwrap();
+        assert_eq!(supply(drained), (0, 0));
+    }
+
+    #[test]
+    fn support_mission_kind_follows_mission_name() {
+        let mission = |name: &str| ApiMstMission {
+            api_name: name.to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            SupportMissionKind::of(&mission("前衛支援任務")),
+            Some(SupportMissionKind::Node)
+        );
+        assert_eq!(
+            SupportMissionKind::of(&mission("艦隊決戦支援任務")),
+            Some(SupportMissionKind::Boss)
+        );
+        assert_eq!(SupportMissionKind::of(&mission("練習航海")), None);
+    }
 
     #[test]
     fn type1_great_success_rate_is_guaranteed_with_six_sparkled() {
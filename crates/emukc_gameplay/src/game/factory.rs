@@ -1,9 +1,11 @@
 use async_trait::async_trait;
+use emukc_crypto::rng;
 use emukc_db::{
     entity::profile::{kdock, ship},
     sea_orm::{ActiveValue, TransactionTrait, entity::prelude::*},
 };
 use emukc_model::{
+    codex::{Codex, development::DEVELOPMENT_WEIGHT_TOTAL, select_weighted_for_roll},
     kc2::{KcApiShip, KcApiSlotItem, MaterialCategory},
     prelude::ApiMstShip,
     profile::{material::Material, slot_item::SlotItem},
@@ -13,6 +15,8 @@ use emukc_time::chrono;
 use crate::{
     err::GameplayError,
     game::{
+        basic::find_profile,
+        fleet::get_fleet_ships_impl,
         kdock::find_kdock_impl,
         material::{add_material_impl, deduct_material_impl},
         slot_item::{add_slot_item_impl, destroy_items_impl},
@@ -22,42 +26,93 @@ use crate::{
 
 use super::{ship::add_ship_impl, slot_item::find_slot_item_impl};
 
+/// Outcome of a single development attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DevelopedSlotItem {
+    /// Slot item manifest ID, `-1` on failure.
+    pub mst_id: i64,
+    /// Created slot item instance ID, `-1` on failure.
+    pub id: i64,
+}
+
+impl DevelopedSlotItem {
+    /// A failed attempt (penguin).
+    pub const FAILED: Self = Self {
+        mst_id: -1,
+        id: -1,
+    };
+
+    /// Whether the attempt produced an item.
+    pub fn is_success(&self) -> bool {
+        self.id > 0
+    }
+}
+
 /// A trait for factory related gameplay.
 #[async_trait]
 pub trait FactoryOps {
-    /// Create slot items.
+    /// Develop slot items.
+    ///
+    /// Each attempt rolls the codex development table by the recipe's dominant
+    /// resource and the first fleet's flagship type. An attempt fails when the
+    /// roll lands outside the pool or the item's rarity exceeds the HQ level
+    /// gate. Resources are consumed for every attempt, development materials
+    /// only for successful ones.
     ///
     /// # Parameters
     ///
     /// - `profile_id`: The profile ID.
-    /// - `mst_id`: The slot item manifest ID.
-    /// - `consumption`: The materials consumption.
+    /// - `recipe`: `[fuel, ammo, steel, bauxite]` for a single attempt.
+    /// - `attempts`: Number of attempts, 1 or 3 for multiple development.
     async fn create_slotitem(
         &self,
         profile_id: i64,
-        mst_id: &[i64],
-        consumption: &[(MaterialCategory, i64)],
-    ) -> Result<(Vec<i64>, Material), GameplayError>;
+        recipe: [i64; 4],
+        attempts: i64,
+    ) -> Result<(Vec<DevelopedSlotItem>, Material), GameplayError>;
+
+    /// Record already-resolved development attempts.
+    ///
+    /// Consumes materials and adds items exactly as [`FactoryOps::create_slotitem`]
+    /// does, without rolling.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `recipe`: `[fuel, ammo, steel, bauxite]` for a single attempt.
+    /// - `results`: Slot item manifest ID per attempt, `-1` for a failure.
+    async fn record_development(
+        &self,
+        profile_id: i64,
+        recipe: [i64; 4],
+        results: &[i64],
+    ) -> Result<(Vec<DevelopedSlotItem>, Material), GameplayError>;
 
     /// Create a ship.
     ///
+    /// The ship is rolled from the codex construction table by the recipe's
+    /// resource thresholds, the first fleet's flagship type and ship rarity.
+    /// The construction time is the rolled ship's build time.
+    ///
     /// # Parameters
     ///
     /// - `profile_id`: The profile ID.
     /// - `kdock_id`: The construction dock ID.
-    /// - `mst_id`: The ship manifest ID.
+    /// - `recipe`: `[fuel, ammo, steel, bauxite, devmat]` put into the build.
     /// - `large`: Whether it is a large ship construction.
     /// - `fast`: Whether it is a high-speed construction.
-    /// - `consumption`: The materials consumption.
+    ///
+    /// # Returns
+    ///
+    /// The manifest ID of the ship under construction.
     async fn create_ship(
         &self,
         profile_id: i64,
         kdock_id: i64,
-        mst_id: i64,
+        recipe: [i64; 5],
         large: bool,
         fast: bool,
-        consumption: &[(MaterialCategory, i64)],
-    ) -> Result<(), GameplayError>;
+    ) -> Result<i64, GameplayError>;
 
     /// High-speed construction.
     ///
@@ -103,61 +158,96 @@ impl<T: HasContext + ?Sized> FactoryOps for T {
     async fn create_slotitem(
         &self,
         profile_id: i64,
-        mst_id: &[i64],
-        consumption: &[(MaterialCategory, i64)],
-    ) -> Result<(Vec<i64>, Material), GameplayError> {
+        recipe: [i64; 4],
+        attempts: i64,
+    ) -> Result<(Vec<DevelopedSlotItem>, Material), GameplayError> {
         let codex = self.codex();
         let db = self.db();
         let tx = db.begin().await?;
 
-        let mut slot_ids: Vec<i64> = Vec::new();
+        let profile = find_profile(&tx, profile_id).await?;
+        let secretary_stype = match get_fleet_ships_impl(&tx, profile_id, 1).await?.first() {
+            Some(flagship) => codex.find::<ApiMstShip>(&flagship.mst_id)?.api_stype,
+            None => 0,
+        };
 
-        // deduct material consumption
-        let m = deduct_material_impl(&tx, profile_id, consumption).await?;
-        let m: Material = m.into();
-
-        // add items
-        for id in mst_id.iter() {
-            if *id > 0 {
-                let m = add_slot_item_impl(&tx, codex, profile_id, *id, 0, 0).await?;
-                slot_ids.push(m.id);
-            } else {
-                slot_ids.push(*id);
-            }
-        }
+        let results: Vec<i64> = (0..attempts)
+            .map(|_| {
+                let roll = rng::u64(0..DEVELOPMENT_WEIGHT_TOTAL);
+                codex
+                    .roll_development(&recipe, secretary_stype, profile.hq_level, roll)
+                    .unwrap_or(DevelopedSlotItem::FAILED.mst_id)
+            })
+            .collect();
 
-        // Update quest progress for each item
-        for id in mst_id.iter() {
-            if *id > 0 {
-                let event = emukc_model::thirdparty::QuestActionEvent::SlotItemConstructed {
-                    item_mst_id: *id,
-                };
-                crate::game::quest::update::update_quest_progress_for_action(
-                    &tx, codex, profile_id, &event,
-                )
-                .await?;
-            }
-        }
+        let result = record_development_impl(&tx, codex, profile_id, recipe, &results).await?;
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn record_development(
+        &self,
+        profile_id: i64,
+        recipe: [i64; 4],
+        results: &[i64],
+    ) -> Result<(Vec<DevelopedSlotItem>, Material), GameplayError> {
+        let codex = self.codex();
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let result = record_development_impl(&tx, codex, profile_id, recipe, results).await?;
         tx.commit().await?;
 
-        Ok((slot_ids, m))
+        Ok(result)
     }
 
     async fn create_ship(
         &self,
         profile_id: i64,
         kdock_id: i64,
-        mst_id: i64,
+        recipe: [i64; 5],
         large: bool,
         fast: bool,
-        consumption: &[(MaterialCategory, i64)],
-    ) -> Result<(), GameplayError> {
+    ) -> Result<i64, GameplayError> {
         let codex = self.codex();
         let db = self.db();
         let tx = db.begin().await?;
 
+        // roll the ship
+        let secretary_stype = match get_fleet_ships_impl(&tx, profile_id, 1).await?.first() {
+            Some(flagship) => codex.find::<ApiMstShip>(&flagship.mst_id)?.api_stype,
+            None => 0,
+        };
+        let candidates = codex.construction_candidates(&recipe, large, secretary_stype);
+        let total: u64 = candidates.iter().map(|(_, weight)| weight).sum();
+        let mst_id = if total > 0 {
+            select_weighted_for_roll(&candidates, rng::u64(0..total))
+        } else {
+            None
+        }
+        .ok_or_else(|| GameplayError::EntryNotFound(format!("buildable ship for {recipe:?}")))?;
+
         // deduct material consumption
-        deduct_material_impl(&tx, profile_id, consumption).await?;
+        let [fuel, ammo, steel, bauxite, devmat] = recipe;
+        let mut consumption = vec![
+            (MaterialCategory::Fuel, fuel),
+            (MaterialCategory::Ammo, ammo),
+            (MaterialCategory::Steel, steel),
+            (MaterialCategory::Bauxite, bauxite),
+            (MaterialCategory::DevMat, devmat),
+        ];
+        if fast {
+            consumption.push((
+                MaterialCategory::Torch,
+                if large {
+                    10
+                } else {
+                    1
+                },
+            ));
+        }
+        deduct_material_impl(&tx, profile_id, &consumption).await?;
 
         // create ship
         let ship_mst = codex.find::<ApiMstShip>(&mst_id)?;
@@ -167,16 +257,11 @@ impl<T: HasContext + ?Sized> FactoryOps for T {
         let mut kdock_am: kdock::ActiveModel = kdock.into();
         kdock_am.ship_id = ActiveValue::Set(mst_id);
 
-        for (category, amount) in consumption {
-            match category {
-                MaterialCategory::Fuel => kdock_am.fuel = ActiveValue::Set(*amount),
-                MaterialCategory::Ammo => kdock_am.ammo = ActiveValue::Set(*amount),
-                MaterialCategory::Steel => kdock_am.steel = ActiveValue::Set(*amount),
-                MaterialCategory::Bauxite => kdock_am.bauxite = ActiveValue::Set(*amount),
-                MaterialCategory::DevMat => kdock_am.devmat = ActiveValue::Set(*amount),
-                _ => {}
-            }
-        }
+        kdock_am.fuel = ActiveValue::Set(fuel);
+        kdock_am.ammo = ActiveValue::Set(ammo);
+        kdock_am.steel = ActiveValue::Set(steel);
+        kdock_am.bauxite = ActiveValue::Set(bauxite);
+        kdock_am.devmat = ActiveValue::Set(devmat);
 
         kdock_am.is_large = ActiveValue::Set(large);
 
@@ -186,7 +271,7 @@ impl<T: HasContext + ?Sized> FactoryOps for T {
         } else {
             let build_time_in_ms = ship_mst.api_buildtime.unwrap_or(1) * 60 * 1000;
             let complete_time =
-                chrono::Utc::now() + chrono::Duration::milliseconds(build_time_in_ms);
+                self.clock().now(profile_id) + chrono::Duration::milliseconds(build_time_in_ms);
             kdock_am.status = ActiveValue::Set(kdock::Status::Busy);
             kdock_am.complete_time = ActiveValue::Set(Some(complete_time));
         }
@@ -204,7 +289,7 @@ impl<T: HasContext + ?Sized> FactoryOps for T {
         .await?;
         tx.commit().await?;
 
-        Ok(())
+        Ok(mst_id)
     }
 
     async fn speed_up_ship_construction(
@@ -284,7 +369,8 @@ impl<T: HasContext + ?Sized> FactoryOps for T {
                 &tx, codex, profile_id, &event,
             )
             .await?;
-            destroy_items_impl(&tx, codex, profile_id, &slot_ids).await?;
+            destroy_items_impl(&tx, codex, profile_id, &slot_ids, self.clock().now(profile_id))
+                .await?;
         }
 
         let mut scrap_materials = [
@@ -366,3 +452,159 @@ impl<T: HasContext + ?Sized> FactoryOps for T {
         Ok((ship, slot_items))
     }
 }
+
+/// Consume materials for development attempts and add the developed items.
+///
+/// Resources are consumed for every attempt, development materials only for
+/// successful ones. Each successful item advances development quests.
+pub(crate) async fn record_development_impl<C>(
+    c: &C,
+    codex: &Codex,
+    profile_id: i64,
+    recipe: [i64; 4],
+    results: &[i64],
+) -> Result<(Vec<DevelopedSlotItem>, Material), GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let attempts = results.len() as i64;
+    let successes = results.iter().filter(|mst_id| **mst_id > 0).count() as i64;
+
+    // deduct material consumption
+    let [fuel, ammo, steel, bauxite] = recipe;
+    let consumption = [
+        (MaterialCategory::Fuel, fuel * attempts),
+        (MaterialCategory::Ammo, ammo * attempts),
+        (MaterialCategory::Steel, steel * attempts),
+        (MaterialCategory::Bauxite, bauxite * attempts),
+        (MaterialCategory::DevMat, successes),
+    ];
+    let m = deduct_material_impl(c, profile_id, &consumption).await?;
+    let m: Material = m.into();
+
+    // add items
+    let mut developed = Vec::with_capacity(results.len());
+    for &mst_id in results {
+        if mst_id <= 0 {
+            developed.push(DevelopedSlotItem::FAILED);
+            continue;
+        }
+        let item = add_slot_item_impl(c, codex, profile_id, mst_id, 0, 0).await?;
+
+        // Update quest progress for each item
+        let event = emukc_model::thirdparty::QuestActionEvent::SlotItemConstructed {
+            item_mst_id: mst_id,
+        };
+        crate::game::quest::update::update_quest_progress_for_action(c, codex, profile_id, &event)
+            .await?;
+
+        developed.push(DevelopedSlotItem {
+            mst_id,
+            id: item.id,
+        });
+    }
+
+    Ok((developed, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use emukc_model::{
+        codex::construction::{ConstructionPool, ConstructionShipRule, ConstructionTypeRule},
+        prelude::Kc3rdShip,
+    };
+
+    use super::*;
+    use crate::{
+        game::material::MaterialOps,
+        user::{AccountOps, ProfileOps},
+    };
+
+    fn buildable_ship(codex: &mut Codex, api_id: i64, api_stype: i64, backs: i64) {
+        codex.manifest.api_mst_ship.push(ApiMstShip {
+            api_id,
+            api_stype,
+            api_backs: Some(backs),
+            api_buildtime: Some(20),
+            ..Default::default()
+        });
+        codex.ship_extra.insert(
+            api_id,
+            Kc3rdShip {
+                api_id,
+                kaih: [0, 0],
+                tais: [0, 0],
+                saku: [0, 0],
+                luck: [0, 0],
+                luck_bonus: 0.0,
+                armor_bonus: 0,
+                cnum: 1,
+                buildable: true,
+                buildable_lsc: false,
+                slots: Vec::new(),
+                remodel: None,
+                remodel_back_to: None,
+                remodel_back_requirement: None,
+            },
+        );
+    }
+
+    async fn new_context(
+        construction: ConstructionPool,
+    ) -> ((emukc_db::sea_orm::DbConn, Codex), i64) {
+        let mut codex = Codex::default();
+        buildable_ship(&mut codex, 1, 2, 1);
+        buildable_ship(&mut codex, 131, 9, 7);
+        codex.construction.normal = construction;
+
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex);
+        let account = context.sign_up("builder", "1234567").await.unwrap();
+        let profile =
+            context.new_profile(&account.access_token.token, "builder").await.unwrap().profile;
+
+        (context, profile.id)
+    }
+
+    #[tokio::test]
+    async fn create_ship_follows_recipe_gates() {
+        let pool = ConstructionPool {
+            type_rules: vec![
+                ConstructionTypeRule {
+                    stypes: vec![2],
+                    min_recipe: [30, 30, 30, 30, 1],
+                    weight: 100,
+                },
+                ConstructionTypeRule {
+                    stypes: vec![9],
+                    min_recipe: [400, 100, 600, 30, 1],
+                    weight: 40,
+                },
+            ],
+            rarity_weights: vec![100],
+            secretary_bonuses: Vec::new(),
+            ship_rules: vec![ConstructionShipRule {
+                ship_id: 131,
+                min_recipe: [3500, 3500, 6000, 2000, 1],
+            }],
+        };
+        let (context, pid) = new_context(pool).await;
+
+        for _ in 0..4 {
+            let mst_id =
+                context.create_ship(pid, 1, [30, 30, 30, 30, 1], false, false).await.unwrap();
+            assert_eq!(mst_id, 1);
+        }
+
+        let material = context.get_materials(pid).await.unwrap();
+        assert_eq!(material.fuel, 1000 - 4 * 30);
+        assert_eq!(material.devmat, 1);
+    }
+
+    #[tokio::test]
+    async fn create_ship_without_table_builds_nothing() {
+        let (context, pid) = new_context(ConstructionPool::default()).await;
+
+        let result = context.create_ship(pid, 1, [30, 30, 30, 30, 1], false, false).await;
+        assert!(matches!(result, Err(GameplayError::EntryNotFound(_))));
+    }
+}
@@ -4,7 +4,7 @@ use emukc_db::{
     sea_orm::{ActiveValue, QueryOrder, TransactionTrait, TryIntoModel, entity::prelude::*},
 };
 use emukc_model::profile::fleet::Fleet;
-use emukc_time::chrono::Utc;
+use emukc_time::chrono::{DateTime, Utc};
 
 use crate::{err::GameplayError, gameplay::HasContext};
 
@@ -90,14 +90,14 @@ impl<T: HasContext + ?Sized> FleetOps for T {
 
     async fn get_fleet(&self, profile_id: i64, index: i64) -> Result<Fleet, GameplayError> {
         let db = self.db();
-        let fleet = get_fleet_impl(db, profile_id, index).await?;
+        let fleet = get_fleet_impl(db, profile_id, index, self.clock().now(profile_id)).await?;
 
         Ok(fleet)
     }
 
     async fn get_fleets(&self, profile_id: i64) -> Result<Vec<Fleet>, GameplayError> {
         let db = self.db();
-        let fleets = get_fleets_impl(db, profile_id).await?;
+        let fleets = get_fleets_impl(db, profile_id, self.clock().now(profile_id)).await?;
 
         Ok(fleets.into_iter().map(std::convert::Into::into).collect())
     }
@@ -168,6 +168,23 @@ where
     Ok(fleet)
 }
 
+/// All fleets of a profile as stored, without settling returned expeditions.
+pub(crate) async fn find_fleets<C>(
+    c: &C,
+    profile_id: i64,
+) -> Result<Vec<fleet::Model>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let fleets = fleet::Entity::find()
+        .filter(fleet::Column::ProfileId.eq(profile_id))
+        .order_by_asc(fleet::Column::Index)
+        .all(c)
+        .await?;
+
+    Ok(fleets)
+}
+
 /// Unlock new deck port.
 ///
 /// # Parameters
@@ -198,15 +215,18 @@ where
 ///
 /// - `profile_id`: The profile ID.
 /// - `index`: The fleet index, must be one of 1, 2, 3, 4.
+/// - `now`: The profile's game time.
 pub(crate) async fn get_fleet_impl<C>(
     c: &C,
     profile_id: i64,
     index: i64,
+    now: DateTime<Utc>,
 ) -> Result<Fleet, GameplayError>
 where
     C: ConnectionTrait,
 {
-    let fleet = normalize_fleet_mission_status(find_fleet(c, profile_id, index).await?, c).await?;
+    let fleet =
+        normalize_fleet_mission_status(find_fleet(c, profile_id, index).await?, c, now).await?;
 
     Ok(fleet.into())
 }
@@ -216,22 +236,20 @@ where
 /// # Parameters
 ///
 /// - `profile_id`: The profile ID.
+/// - `now`: The profile's game time.
 pub(crate) async fn get_fleets_impl<C>(
     c: &C,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<Vec<fleet::Model>, GameplayError>
 where
     C: ConnectionTrait,
 {
-    let models: Vec<fleet::Model> = fleet::Entity::find()
-        .filter(fleet::Column::ProfileId.eq(profile_id))
-        .order_by_asc(fleet::Column::Index)
-        .all(c)
-        .await?;
+    let models = find_fleets(c, profile_id).await?;
 
     let mut fleets = Vec::with_capacity(models.len());
     for model in models {
-        fleets.push(normalize_fleet_mission_status(model, c).await?);
+        fleets.push(normalize_fleet_mission_status(model, c, now).await?);
     }
 
     Ok(fleets)
@@ -240,12 +258,13 @@ where
 async fn normalize_fleet_mission_status<C>(
     model: fleet::Model,
     c: &C,
+    now: DateTime<Utc>,
 ) -> Result<fleet::Model, GameplayError>
 where
     C: ConnectionTrait,
 {
     if model.mission_status == fleet::MissionStatus::InMission
-        && model.return_time.is_some_and(|return_time| return_time <= Utc::now())
+        && model.return_time.is_some_and(|return_time| return_time <= now)
     {
         let mut am: fleet::ActiveModel = model.into();
         am.mission_status = ActiveValue::Set(fleet::MissionStatus::Returning);
@@ -263,7 +282,7 @@ pub(crate) async fn get_fleet_ships_impl<C>(
 where
     C: ConnectionTrait,
 {
-    let fleet = get_fleet_impl(c, profile_id, index).await?;
+    let fleet = Fleet::from(find_fleet(c, profile_id, index).await?);
     let mut ships = ship::Entity::find().filter(ship::Column::Id.is_in(fleet.ships)).all(c).await?;
     ships.sort_by_key(|ship| fleet.ships.iter().position(|&id| id == ship.id).unwrap());
 
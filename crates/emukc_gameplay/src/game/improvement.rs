@@ -0,0 +1,709 @@
+//! Equipment improvement in Akashi's Arsenal (明石の改修工廠).
+
+use async_trait::async_trait;
+use emukc_crypto::rng;
+use emukc_db::{
+    entity::profile::{airbase::plane as plane_db, item::slot_item},
+    sea_orm::{ActiveValue, QueryOrder, QuerySelect, TransactionTrait, entity::prelude::*},
+};
+use emukc_model::{
+    codex::{
+        Codex,
+        improvement::{
+            ImprovementRecipe, ImprovementStage, MAX_IMPROVEMENT_LEVEL, improvement_success_rate,
+        },
+    },
+    kc2::{KcApiSlotItem, MaterialCategory},
+    prelude::ApiMstSlotitem,
+    profile::{material::Material, slot_item::SlotItem},
+};
+use emukc_time::{
+    KcTime,
+    chrono::{DateTime, Utc},
+};
+
+use crate::{
+    err::GameplayError,
+    game::{
+        fleet::get_fleet_ships_impl,
+        material::deduct_material_impl,
+        picturebook::add_slot_item_to_picturebook_impl,
+        ship::{find_ship_impl, recalculate_ship_status_with_model},
+        slot_item::find_slot_item_impl,
+        use_item::{deduct_use_item_impl, find_use_item_impl},
+    },
+    gameplay::HasContext,
+};
+
+/// Costs of the next improvement of a slot item.
+#[derive(Debug, Clone)]
+pub struct ImprovementDetail {
+    /// Costs of the star range the slot item is in.
+    pub stage: ImprovementStage,
+
+    /// Whether a successful attempt upgrades the slot item into another one.
+    pub upgrade: bool,
+}
+
+/// Outcome of an improvement attempt.
+#[derive(Debug, Clone)]
+pub struct ImprovementResult {
+    /// Whether the attempt succeeded.
+    pub success: bool,
+
+    /// Slot item manifest ID before the attempt.
+    pub before_mst_id: i64,
+
+    /// Slot item manifest ID after the attempt.
+    pub after_mst_id: i64,
+
+    /// The improved slot item, on success.
+    pub slot_item: Option<KcApiSlotItem>,
+
+    /// Instance IDs of the slot items consumed.
+    pub consumed_slot_item_ids: Vec<i64>,
+
+    /// Materials after the attempt.
+    pub material: Material,
+
+    /// Master ID of the assistant ship, `0` if there is none.
+    pub helper_mst_id: i64,
+}
+
+/// A trait for equipment improvement gameplay.
+///
+/// A recipe is offered when the second ship of the first fleet is one of its
+/// assistant ships on the current JST weekday.
+#[async_trait]
+pub trait ImprovementOps {
+    /// Get the improvement recipes offered today.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    async fn get_improvement_list(
+        &self,
+        profile_id: i64,
+    ) -> Result<Vec<ImprovementRecipe>, GameplayError>;
+
+    /// Get the costs of improving a slot item.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `recipe_id`: The improvement recipe ID.
+    /// - `slot_item_id`: The slot item instance ID.
+    async fn get_improvement_detail(
+        &self,
+        profile_id: i64,
+        recipe_id: i64,
+        slot_item_id: i64,
+    ) -> Result<ImprovementDetail, GameplayError>;
+
+    /// Improve a slot item.
+    ///
+    /// Resources, development materials and improvement materials are consumed
+    /// for every attempt, the equipment and use items of the recipe only for
+    /// successful ones. Guaranteed attempts always succeed.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `recipe_id`: The improvement recipe ID.
+    /// - `slot_item_id`: The slot item instance ID.
+    /// - `certain`: Whether to pay for a guaranteed attempt.
+    async fn improve_slot_item(
+        &self,
+        profile_id: i64,
+        recipe_id: i64,
+        slot_item_id: i64,
+        certain: bool,
+    ) -> Result<ImprovementResult, GameplayError>;
+}
+
+#[async_trait]
+impl<T: HasContext + ?Sized> ImprovementOps for T {
+    async fn get_improvement_list(
+        &self,
+        profile_id: i64,
+    ) -> Result<Vec<ImprovementRecipe>, GameplayError> {
+        let codex = self.codex();
+        let db = self.db();
+
+        let helper_mst_id = find_helper_mst_id(db, profile_id).await?;
+        let weekday = KcTime::jst_weekday(&self.clock().now(profile_id));
+
+        Ok(codex
+            .improvements
+            .available_recipes(helper_mst_id, weekday)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    async fn get_improvement_detail(
+        &self,
+        profile_id: i64,
+        recipe_id: i64,
+        slot_item_id: i64,
+    ) -> Result<ImprovementDetail, GameplayError> {
+        let codex = self.codex();
+        let db = self.db();
+        let now = self.clock().now(profile_id);
+
+        let helper_mst_id = find_helper_mst_id(db, profile_id).await?;
+        let recipe = find_offered_recipe(codex, recipe_id, helper_mst_id, now)?;
+        let item = find_improvable_item(db, profile_id, recipe, slot_item_id).await?;
+        let stage = find_stage(recipe, &item)?;
+
+        Ok(ImprovementDetail {
+            stage: stage.clone(),
+            upgrade: item.level >= MAX_IMPROVEMENT_LEVEL,
+        })
+    }
+
+    async fn improve_slot_item(
+        &self,
+        profile_id: i64,
+        recipe_id: i64,
+        slot_item_id: i64,
+        certain: bool,
+    ) -> Result<ImprovementResult, GameplayError> {
+        let codex = self.codex();
+        let db = self.db();
+        let now = self.clock().now(profile_id);
+        let tx = db.begin().await?;
+
+        let result = improve_slot_item_impl(
+            &tx,
+            codex,
+            profile_id,
+            recipe_id,
+            slot_item_id,
+            certain,
+            rng::f64(),
+            now,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(result)
+    }
+}
+
+/// Master ID of the assistant ship, `0` when the first fleet has no second ship.
+async fn find_helper_mst_id<C>(c: &C, profile_id: i64) -> Result<i64, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    Ok(get_fleet_ships_impl(c, profile_id, 1).await?.get(1).map_or(0, |ship| ship.mst_id))
+}
+
+fn find_offered_recipe(
+    codex: &Codex,
+    recipe_id: i64,
+    helper_mst_id: i64,
+    now: DateTime<Utc>,
+) -> Result<&ImprovementRecipe, GameplayError> {
+    let recipe = codex.improvements.recipe(recipe_id).ok_or_else(|| {
+        GameplayError::EntryNotFound(format!("improvement recipe {recipe_id} not found"))
+    })?;
+
+    let weekday = KcTime::jst_weekday(&now);
+    if !recipe.available(helper_mst_id, weekday) {
+        return Err(GameplayError::WrongType(format!(
+            "improvement recipe {recipe_id} is not offered with ship {helper_mst_id} on {weekday}"
+        )));
+    }
+
+    Ok(recipe)
+}
+
+async fn find_improvable_item<C>(
+    c: &C,
+    profile_id: i64,
+    recipe: &ImprovementRecipe,
+    slot_item_id: i64,
+) -> Result<slot_item::Model, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let item = find_slot_item_impl(c, slot_item_id).await?;
+    if item.profile_id != profile_id {
+        return Err(GameplayError::EntryNotFound(format!(
+            "slot item {slot_item_id} does not belong to profile {profile_id}"
+        )));
+    }
+    if item.mst_id != recipe.slotitem_id {
+        return Err(GameplayError::WrongType(format!(
+            "improvement recipe {} does not improve slot item {}",
+            recipe.id, item.mst_id
+        )));
+    }
+
+    Ok(item)
+}
+
+fn find_stage<'a>(
+    recipe: &'a ImprovementRecipe,
+    item: &slot_item::Model,
+) -> Result<&'a ImprovementStage, GameplayError> {
+    recipe.stage(item.level).ok_or_else(|| {
+        GameplayError::WrongType(format!(
+            "slot item {} cannot be improved past level {}",
+            item.id, item.level
+        ))
+    })
+}
+
+/// Pick the slot items an improvement consumes: unlocked, unequipped and not
+/// deployed to an airbase, never the improved item itself, lowest level first.
+pub(crate) async fn find_improvement_fodder_impl<C>(
+    c: &C,
+    profile_id: i64,
+    stage: &ImprovementStage,
+    target_id: i64,
+) -> Result<Vec<i64>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let deployed: Vec<i64> = plane_db::Entity::find()
+        .select_only()
+        .column(plane_db::Column::SlotId)
+        .filter(plane_db::Column::ProfileId.eq(profile_id))
+        .into_tuple()
+        .all(c)
+        .await?;
+
+    let mut fodder = Vec::new();
+    for cost in &stage.slot_items {
+        let candidates = slot_item::Entity::find()
+            .filter(slot_item::Column::ProfileId.eq(profile_id))
+            .filter(slot_item::Column::MstId.eq(cost.id))
+            .filter(slot_item::Column::Id.ne(target_id))
+            .filter(slot_item::Column::EquipOn.lte(0))
+            .filter(slot_item::Column::Locked.eq(false))
+            .filter(slot_item::Column::Id.is_not_in(deployed.iter().copied()))
+            .order_by_asc(slot_item::Column::Level)
+            .order_by_asc(slot_item::Column::Id)
+            .all(c)
+            .await?;
+
+        let picked: Vec<i64> = candidates
+            .iter()
+            .map(|item| item.id)
+            .filter(|id| !fodder.contains(id))
+            .take(cost.count as usize)
+            .collect();
+        if (picked.len() as i64) < cost.count {
+            return Err(GameplayError::Insufficient(format!(
+                "slot item {} for improvement, has:{}, needs:{}",
+                cost.id,
+                picked.len(),
+                cost.count
+            )));
+        }
+        fodder.extend(picked);
+    }
+
+    Ok(fodder)
+}
+
+/// Improve a slot item; a non-guaranteed attempt succeeds when `roll`, drawn
+/// from `[0, 1)`, is below the success rate of the item's level.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn improve_slot_item_impl<C>(
+    c: &C,
+    codex: &Codex,
+    profile_id: i64,
+    recipe_id: i64,
+    slot_item_id: i64,
+    certain: bool,
+    roll: f64,
+    now: DateTime<Utc>,
+) -> Result<ImprovementResult, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let helper_mst_id = find_helper_mst_id(c, profile_id).await?;
+    let recipe = find_offered_recipe(codex, recipe_id, helper_mst_id, now)?;
+    let item = find_improvable_item(c, profile_id, recipe, slot_item_id).await?;
+    let stage = find_stage(recipe, &item)?;
+
+    // everything the recipe asks for must be at hand, even if a failure keeps it
+    let fodder = find_improvement_fodder_impl(c, profile_id, stage, item.id).await?;
+    for cost in &stage.use_items {
+        let owned = find_use_item_impl(c, profile_id, cost.id).await?.count;
+        if owned < cost.count {
+            return Err(GameplayError::Insufficient(format!(
+                "use item {} for improvement, has:{owned}, needs:{}",
+                cost.id, cost.count
+            )));
+        }
+    }
+
+    let (devmat, screw) = if certain {
+        (stage.certain_devmat, stage.certain_screw)
+    } else {
+        (stage.devmat, stage.screw)
+    };
+    let material = deduct_material_impl(
+        c,
+        profile_id,
+        &[
+            (MaterialCategory::Fuel, recipe.fuel),
+            (MaterialCategory::Ammo, recipe.ammo),
+            (MaterialCategory::Steel, recipe.steel),
+            (MaterialCategory::Bauxite, recipe.bauxite),
+            (MaterialCategory::DevMat, devmat),
+            (MaterialCategory::Screw, screw),
+        ],
+    )
+    .await?;
+
+    let before_mst_id = item.mst_id;
+    let success = certain || roll < improvement_success_rate(item.level);
+
+    let mut result = ImprovementResult {
+        success,
+        before_mst_id,
+        after_mst_id: before_mst_id,
+        slot_item: None,
+        consumed_slot_item_ids: Vec::new(),
+        material: material.into(),
+        helper_mst_id,
+    };
+
+    let level = if success {
+        for id in &fodder {
+            slot_item::Entity::delete_by_id(*id).exec(c).await?;
+        }
+        for cost in &stage.use_items {
+            deduct_use_item_impl(c, profile_id, cost.id, cost.count).await?;
+        }
+
+        let equip_on = item.equip_on;
+        let mut am: slot_item::ActiveModel = item.clone().into();
+        match recipe.upgrade.as_ref().filter(|_| item.level >= MAX_IMPROVEMENT_LEVEL) {
+            Some(upgrade) => {
+                let mst = codex.find::<ApiMstSlotitem>(&upgrade.slotitem_id)?;
+                am.mst_id = ActiveValue::Set(mst.api_id);
+                am.type3 = ActiveValue::Set(mst.api_type[2]);
+                am.level = ActiveValue::Set(upgrade.level);
+                am.aircraft_lv = ActiveValue::Set(0);
+                add_slot_item_to_picturebook_impl(c, profile_id, mst.api_sortno).await?;
+            }
+            None => {
+                am.level = ActiveValue::Set(item.level + 1);
+            }
+        }
+        let improved = am.update(c).await?;
+
+        // the ship carrying the item sees its new stats
+        if equip_on > 0
+            && let Some((ship, _)) = find_ship_impl(c, equip_on).await?
+        {
+            recalculate_ship_status_with_model(c, codex, &ship).await?.update(c).await?;
+        }
+
+        result.after_mst_id = improved.mst_id;
+        result.consumed_slot_item_ids = fodder;
+        let level = improved.level;
+        let improved: SlotItem = improved.into();
+        result.slot_item = Some(improved.into());
+        level
+    } else {
+        item.level
+    };
+
+    // Update quest progress, failures count as well
+    let event = emukc_model::thirdparty::QuestActionEvent::SlotItemImproved {
+        item_mst_id: before_mst_id,
+        stars: level,
+    };
+    crate::game::quest::update::update_quest_progress_for_action(c, codex, profile_id, &event)
+        .await?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use emukc_model::codex::improvement::{
+        ImprovementHelper, ImprovementItemCost, ImprovementTable, ImprovementUpgrade, Weekday,
+    };
+    use emukc_time::chrono::TimeZone;
+
+    use super::*;
+    use crate::{
+        game::MaterialOps,
+        user::{AccountOps, ProfileOps},
+    };
+
+    const SLOTITEM_ID: i64 = 2;
+    const UPGRADE_ID: i64 = 4;
+
+    fn codex() -> Codex {
+        Codex {
+            improvements: ImprovementTable {
+                recipes: vec![ImprovementRecipe {
+                    id: 1,
+                    slotitem_id: SLOTITEM_ID,
+                    fuel: 10,
+                    ammo: 10,
+                    steel: 10,
+                    bauxite: 0,
+                    helpers: vec![ImprovementHelper {
+                        ship_id: 0,
+                        weekdays: vec![
+                            Weekday::Mon,
+                            Weekday::Tue,
+                            Weekday::Wed,
+                            Weekday::Thu,
+                            Weekday::Fri,
+                            Weekday::Sat,
+                            Weekday::Sun,
+                        ],
+                    }],
+                    stages: vec![ImprovementStage {
+                        from_level: 0,
+                        to_level: 9,
+                        devmat: 1,
+                        certain_devmat: 2,
+                        screw: 1,
+                        certain_screw: 2,
+                        slot_items: vec![ImprovementItemCost {
+                            id: SLOTITEM_ID,
+                            count: 1,
+                        }],
+                        use_items: Vec::new(),
+                    }],
+                    upgrade: None,
+                }],
+            },
+            ..Default::default()
+        }
+    }
+
+    async fn insert_item<C: ConnectionTrait>(
+        c: &C,
+        profile_id: i64,
+        level: i64,
+        locked: bool,
+    ) -> i64 {
+        slot_item::ActiveModel {
+            profile_id: ActiveValue::Set(profile_id),
+            mst_id: ActiveValue::Set(SLOTITEM_ID),
+            type3: ActiveValue::Set(1),
+            locked: ActiveValue::Set(locked),
+            level: ActiveValue::Set(level),
+            aircraft_lv: ActiveValue::Set(0),
+            equip_on: ActiveValue::Set(0),
+            ..Default::default()
+        }
+        .insert(c)
+        .await
+        .unwrap()
+        .id
+    }
+
+    async fn new_profile(context: &(DbConn, Codex)) -> i64 {
+        let account = context.sign_up("improvement", "1234567").await.unwrap();
+        let profile_id =
+            context.new_profile(&account.access_token.token, "akashi").await.unwrap().profile.id;
+        context
+            .add_material(
+                profile_id,
+                &[(MaterialCategory::DevMat, 10), (MaterialCategory::Screw, 10)],
+            )
+            .await
+            .unwrap();
+        profile_id
+    }
+
+    #[tokio::test]
+    async fn certain_improvement_consumes_fodder_and_materials() {
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex());
+        let profile_id = new_profile(&context).await;
+        let before = context.get_materials(profile_id).await.unwrap();
+
+        let target = insert_item(&context.0, profile_id, 9, true).await;
+        let locked = insert_item(&context.0, profile_id, 0, true).await;
+        assert!(matches!(
+            context.improve_slot_item(profile_id, 1, target, true).await,
+            Err(GameplayError::Insufficient(_))
+        ));
+
+        let fodder = insert_item(&context.0, profile_id, 3, false).await;
+        let list = context.get_improvement_list(profile_id).await.unwrap();
+        assert_eq!(list.len(), 1);
+        let detail = context.get_improvement_detail(profile_id, 1, target).await.unwrap();
+        assert_eq!(detail.stage.certain_screw, 2);
+
+        let result = context.improve_slot_item(profile_id, 1, target, true).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.consumed_slot_item_ids, vec![fodder]);
+        assert_eq!(result.slot_item.unwrap().api_level, 10);
+        assert_eq!(result.material.devmat, before.devmat - 2);
+        assert_eq!(result.material.screw, before.screw - 2);
+        assert_eq!(result.material.fuel, before.fuel - 10);
+        assert!(find_slot_item_impl(&context.0, locked).await.is_ok());
+        assert!(find_slot_item_impl(&context.0, fodder).await.is_err());
+
+        // ★max without an upgrade is as far as it goes
+        assert!(context.get_improvement_detail(profile_id, 1, target).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_roll_keeps_level_and_fodder() {
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex());
+        let profile_id = new_profile(&context).await;
+        let before = context.get_materials(profile_id).await.unwrap();
+
+        let target = insert_item(&context.0, profile_id, 9, true).await;
+        let fodder = insert_item(&context.0, profile_id, 0, false).await;
+
+        // ★9 succeeds 60% of the time
+        let result = improve_slot_item_impl(
+            &context.0,
+            &context.1,
+            profile_id,
+            1,
+            target,
+            false,
+            0.6,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert!(!result.success);
+        assert!(result.slot_item.is_none());
+        assert!(result.consumed_slot_item_ids.is_empty());
+        assert_eq!(result.after_mst_id, SLOTITEM_ID);
+        assert_eq!(result.material.devmat, before.devmat - 1);
+        assert_eq!(result.material.screw, before.screw - 1);
+        assert_eq!(find_slot_item_impl(&context.0, target).await.unwrap().level, 9);
+        assert!(find_slot_item_impl(&context.0, fodder).await.is_ok());
+
+        let result = improve_slot_item_impl(
+            &context.0,
+            &context.1,
+            profile_id,
+            1,
+            target,
+            false,
+            0.59,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+        assert_eq!(result.consumed_slot_item_ids, vec![fodder]);
+    }
+
+    #[tokio::test]
+    async fn recipe_is_rejected_without_its_helper_or_weekday() {
+        let mut codex = codex();
+        codex.improvements.recipes[0].helpers = vec![
+            ImprovementHelper {
+                ship_id: 0,
+                weekdays: vec![Weekday::Tue],
+            },
+            ImprovementHelper {
+                ship_id: 187,
+                weekdays: vec![Weekday::Mon],
+            },
+        ];
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex);
+        let profile_id = new_profile(&context).await;
+
+        let target = insert_item(&context.0, profile_id, 0, true).await;
+        insert_item(&context.0, profile_id, 0, false).await;
+
+        // 09:00 JST on a Monday, with no second ship in the first fleet
+        let monday = Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap();
+        assert!(matches!(
+            improve_slot_item_impl(
+                &context.0, &context.1, profile_id, 1, target, true, 0.0, monday
+            )
+            .await,
+            Err(GameplayError::WrongType(_))
+        ));
+        assert!(
+            context.1.improvements.available_recipes(0, KcTime::jst_weekday(&monday)).is_empty()
+        );
+
+        let tuesday = monday + emukc_time::chrono::Duration::days(1);
+        let result = improve_slot_item_impl(
+            &context.0, &context.1, profile_id, 1, target, true, 0.0, tuesday,
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn max_level_item_turns_into_its_upgrade() {
+        let mut codex = codex();
+        let recipe = &mut codex.improvements.recipes[0];
+        recipe.stages[0].to_level = MAX_IMPROVEMENT_LEVEL;
+        recipe.upgrade = Some(ImprovementUpgrade {
+            slotitem_id: UPGRADE_ID,
+            level: 1,
+        });
+        codex.manifest.api_mst_slotitem.push(ApiMstSlotitem {
+            api_id: UPGRADE_ID,
+            api_sortno: UPGRADE_ID,
+            api_type: [1, 2, 3, 4, 0],
+            ..Default::default()
+        });
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex);
+        let profile_id = new_profile(&context).await;
+
+        let target = insert_item(&context.0, profile_id, MAX_IMPROVEMENT_LEVEL, true).await;
+        insert_item(&context.0, profile_id, 0, false).await;
+
+        let detail = context.get_improvement_detail(profile_id, 1, target).await.unwrap();
+        assert!(detail.upgrade);
+
+        let result = context.improve_slot_item(profile_id, 1, target, true).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.before_mst_id, SLOTITEM_ID);
+        assert_eq!(result.after_mst_id, UPGRADE_ID);
+        let item = find_slot_item_impl(&context.0, target).await.unwrap();
+        assert_eq!(item.mst_id, UPGRADE_ID);
+        assert_eq!(item.type3, 3);
+        assert_eq!(item.level, 1);
+    }
+
+    #[tokio::test]
+    async fn deployed_planes_are_not_fodder() {
+        let context = (emukc_db::prelude::new_mem_db().await.unwrap(), codex());
+        let profile_id = new_profile(&context).await;
+
+        let target = insert_item(&context.0, profile_id, 0, true).await;
+        let deployed = insert_item(&context.0, profile_id, 0, false).await;
+        plane_db::ActiveModel {
+            slot_id: ActiveValue::Set(deployed),
+            profile_id: ActiveValue::Set(profile_id),
+            area_id: ActiveValue::Set(1),
+            rid: ActiveValue::Set(1),
+            squadron_id: ActiveValue::Set(1),
+            state: ActiveValue::Set(plane_db::Status::Assigned),
+            condition: ActiveValue::Set(1),
+            count: ActiveValue::Set(18),
+            max_count: ActiveValue::Set(18),
+            relocate_until: ActiveValue::Set(None),
+            condition_at: ActiveValue::Set(None),
+        }
+        .insert(&context.0)
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            context.improve_slot_item(profile_id, 1, target, true).await,
+            Err(GameplayError::Insufficient(_))
+        ));
+        assert!(find_slot_item_impl(&context.0, deployed).await.is_ok());
+    }
+}
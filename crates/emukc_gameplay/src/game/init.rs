@@ -1,5 +1,6 @@
 use emukc_db::sea_orm::ConnectionTrait;
 use emukc_model::codex::Codex;
+use emukc_time::chrono::{DateTime, Utc};
 
 use crate::err::GameplayError;
 
@@ -15,10 +16,12 @@ use super::{
 /// - `c`: The database connection.
 /// - `codex`: The codex.
 /// - `profile_id`: The profile ID.
+/// - `now`: The profile's game time.
 pub async fn init_profile_game_data<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
@@ -42,7 +45,7 @@ where
     furniture::init(c, profile_id).await?;
 
     // map
-    map::init(c, codex, profile_id).await?;
+    map::init(c, codex, profile_id, now).await?;
 
     // material
     material::init(c, codex, profile_id).await?;
@@ -60,7 +63,7 @@ where
     quest::init(c, codex, profile_id).await?;
 
     // ships
-    ship::init(c, profile_id).await?;
+    ship::init(c, profile_id, now).await?;
 
     // slot items
     slot_item::init(c, profile_id).await?;
@@ -4,6 +4,7 @@ use emukc_db::{
     sea_orm::{ActiveValue, QueryOrder, TransactionTrait, TryIntoModel, entity::prelude::*},
 };
 use emukc_model::{kc2::KcUseItemType, profile::kdock::ConstructionDock};
+use emukc_time::chrono::{DateTime, Utc};
 
 use crate::{err::GameplayError, gameplay::HasContext};
 
@@ -74,14 +75,14 @@ impl<T: HasContext + ?Sized> KDockOps for T {
         index: i64,
     ) -> Result<ConstructionDock, GameplayError> {
         let db = self.db();
-        let dock = get_kdock_impl(db, profile_id, index).await?;
+        let dock = get_kdock_impl(db, profile_id, index, self.clock().now(profile_id)).await?;
 
         Ok(dock)
     }
 
     async fn get_kdocks(&self, profile_id: i64) -> Result<Vec<ConstructionDock>, GameplayError> {
         let db = self.db();
-        let docks = get_kdocks_impl(db, profile_id).await?;
+        let docks = get_kdocks_impl(db, profile_id, self.clock().now(profile_id)).await?;
 
         Ok(docks.into_iter().map(std::convert::Into::into).collect())
     }
@@ -150,15 +151,18 @@ where
 ///
 /// - `profile_id`: The profile ID.
 /// - `index`: The construction dock index, must be one of 1, 2, 3, 4.
+/// - `now`: The profile's game time.
 pub(crate) async fn get_kdock_impl<C>(
     c: &C,
     profile_id: i64,
     index: i64,
+    now: DateTime<Utc>,
 ) -> Result<ConstructionDock, GameplayError>
 where
     C: ConnectionTrait,
 {
     let dock = find_kdock_impl(c, profile_id, index).await?;
+    let dock = settle_kdock_impl(c, dock, now).await?;
 
     Ok(dock.into())
 }
@@ -168,9 +172,11 @@ where
 /// # Parameters
 ///
 /// - `profile_id`: The profile ID.
+/// - `now`: The profile's game time.
 pub(crate) async fn get_kdocks_impl<C>(
     c: &C,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<Vec<kdock::Model>, GameplayError>
 where
     C: ConnectionTrait,
@@ -181,7 +187,31 @@ where
         .all(c)
         .await?;
 
-    Ok(docks)
+    let mut settled = Vec::with_capacity(docks.len());
+    for dock in docks {
+        settled.push(settle_kdock_impl(c, dock, now).await?);
+    }
+
+    Ok(settled)
+}
+
+/// Mark a busy construction dock as completed once its build time has passed.
+async fn settle_kdock_impl<C>(
+    c: &C,
+    dock: kdock::Model,
+    now: DateTime<Utc>,
+) -> Result<kdock::Model, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    if dock.status != kdock::Status::Busy || dock.complete_time.is_none_or(|t| t > now) {
+        return Ok(dock);
+    }
+
+    let mut am: kdock::ActiveModel = dock.into();
+    am.status = ActiveValue::Set(kdock::Status::Completed);
+
+    Ok(am.update(c).await?)
 }
 
 pub(crate) async fn expand_construction_dock_impl<C>(
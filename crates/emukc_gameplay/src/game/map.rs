@@ -18,14 +18,20 @@ use emukc_model::{
     kc2::{KcApiEventmap, KcApiMapInfo},
     profile::map_record::MapSelectRank,
 };
-use emukc_time::{KcTime, chrono::Utc};
+use emukc_time::{
+    KcTime,
+    chrono::{DateTime, Utc},
+};
 
 use crate::{err::GameplayError, gameplay::HasContext};
 
 use super::{
     basic::find_profile,
     fleet::get_fleet_ships_impl,
-    map_progress::{active_stage_for_record, assign_stage_id, select_stage_id_for_rank},
+    map_progress::{
+        active_gimmick_ids, active_stage_for_record, assign_stage_id, gimmick_stage_id,
+        select_stage_id_for_rank,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -79,8 +85,9 @@ impl<T: HasContext + ?Sized> MapOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        ensure_map_records_impl(&tx, codex, profile_id).await?;
-        refresh_all_map_records_impl(&tx, codex, profile_id).await?;
+        let now = self.clock().now(profile_id);
+        ensure_map_records_impl(&tx, codex, profile_id, now).await?;
+        refresh_all_map_records_impl(&tx, codex, profile_id, now).await?;
         let records = get_map_records_impl(&tx, profile_id).await?;
 
         tx.commit().await?;
@@ -92,8 +99,9 @@ impl<T: HasContext + ?Sized> MapOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        ensure_map_records_impl(&tx, codex, profile_id).await?;
-        refresh_all_map_records_impl(&tx, codex, profile_id).await?;
+        let now = self.clock().now(profile_id);
+        ensure_map_records_impl(&tx, codex, profile_id, now).await?;
+        refresh_all_map_records_impl(&tx, codex, profile_id, now).await?;
         let records = get_map_records_impl(&tx, profile_id).await?;
         let infos = build_map_infos(codex, records);
 
@@ -112,8 +120,9 @@ impl<T: HasContext + ?Sized> MapOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        ensure_map_records_impl(&tx, codex, profile_id).await?;
-        refresh_all_map_records_impl(&tx, codex, profile_id).await?;
+        let now = self.clock().now(profile_id);
+        ensure_map_records_impl(&tx, codex, profile_id, now).await?;
+        refresh_all_map_records_impl(&tx, codex, profile_id, now).await?;
         let definition = find_map_definition(codex, maparea_id, mapinfo_no)?;
         if !definition.is_event {
             return Err(GameplayError::WrongType(format!(
@@ -124,9 +133,20 @@ impl<T: HasContext + ?Sized> MapOps for T {
         let selected_rank = map_record::SelectedRank::from(parse_map_select_rank(rank)?);
         let record = find_map_record_impl(&tx, profile_id, definition.map_id).await?;
         let current_hp = record.current_hp;
+        // Gimmick progress is per difficulty; switching starts it over.
+        let rank_changed = record.selected_rank != selected_rank;
+        let stage_id = if rank_changed {
+            select_stage_id_for_rank(&definition, rank)
+        } else {
+            gimmick_stage_id(&definition, &active_gimmick_ids(&definition, &record))
+                .or_else(|| select_stage_id_for_rank(&definition, rank))
+        };
         let mut am = record.into_active_model();
         am.selected_rank = ActiveValue::Set(selected_rank);
-        assign_stage_id(&mut am, select_stage_id_for_rank(&definition, rank));
+        assign_stage_id(&mut am, stage_id);
+        if rank_changed {
+            am.gimmick_state = ActiveValue::Set(None);
+        }
         if definition.max_hp.is_some() && current_hp.is_none() {
             am.current_hp = ActiveValue::Set(definition.max_hp);
             am.gauge_index = ActiveValue::Set(1);
@@ -226,6 +246,7 @@ pub(crate) async fn ensure_map_records_impl<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
@@ -235,7 +256,6 @@ where
         .into_iter()
         .map(|record| record.map_id)
         .collect::<BTreeSet<_>>();
-    let now = Utc::now();
     let catalog = active_map_catalog(codex);
 
     for definition in catalog.known_maps() {
@@ -251,6 +271,7 @@ where
             map_id: ActiveValue::Set(definition.map_id),
             cleared: ActiveValue::Set(false),
             unlocked: ActiveValue::Set(unlocked),
+            gimmick_state: ActiveValue::Set(None),
             last_cleared_at: ActiveValue::Set(None),
             last_reset_at: ActiveValue::Set(Some(now)),
             defeat_count: ActiveValue::Set(definition.required_defeat_count.map(|_| 0)),
@@ -271,12 +292,12 @@ pub(crate) async fn refresh_all_map_records_impl<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
 {
-    let now = Utc::now();
-    let refresh_boundary = KcTime::jst_0500_of_nth_day(1);
+    let refresh_boundary = KcTime::jst_0500_of_nth_day(&now, 1);
     let definitions = active_map_catalog(codex)
         .known_maps()
         .into_iter()
@@ -301,6 +322,7 @@ where
             am.gauge_index = ActiveValue::Set(1);
             assign_stage_id(&mut am, select_stage_id_for_rank(definition, selected_rank));
             am.event_state = ActiveValue::Set(definition.max_hp.map(|_| 1));
+            am.gimmick_state = ActiveValue::Set(None);
             am.update(c).await?;
         }
     }
@@ -445,16 +467,17 @@ pub(crate) async fn clear_and_unlock_map_impl<C>(
     codex: &Codex,
     profile_id: i64,
     map_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
 {
-    ensure_map_records_impl(c, codex, profile_id).await?;
+    ensure_map_records_impl(c, codex, profile_id, now).await?;
     let record = find_map_record_impl(c, profile_id, map_id).await?;
     let mut am = record.into_active_model();
     am.unlocked = ActiveValue::Set(true);
     am.cleared = ActiveValue::Set(true);
-    am.last_cleared_at = ActiveValue::Set(Some(Utc::now()));
+    am.last_cleared_at = ActiveValue::Set(Some(now));
     am.update(c).await?;
     check_and_unlock_dependencies_impl(c, codex, profile_id, map_id).await?;
     Ok(())
@@ -466,11 +489,12 @@ pub(crate) async fn unlock_map_impl<C>(
     codex: &Codex,
     profile_id: i64,
     map_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
 {
-    ensure_map_records_impl(c, codex, profile_id).await?;
+    ensure_map_records_impl(c, codex, profile_id, now).await?;
     let record = find_map_record_impl(c, profile_id, map_id).await?;
     if !record.unlocked {
         let mut am = record.into_active_model();
@@ -507,11 +531,16 @@ fn sally_flag_array(definition: &MapDefinition) -> [i64; 3] {
     result
 }
 
-pub(super) async fn init<C>(c: &C, codex: &Codex, profile_id: i64) -> Result<(), GameplayError>
+pub(super) async fn init<C>(
+    c: &C,
+    codex: &Codex,
+    profile_id: i64,
+    now: DateTime<Utc>,
+) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
 {
-    ensure_map_records_impl(c, codex, profile_id).await
+    ensure_map_records_impl(c, codex, profile_id, now).await
 }
 
 pub(super) async fn wipe<C>(c: &C, profile_id: i64) -> Result<(), GameplayError>
@@ -570,6 +599,7 @@ mod tests {
                     },
                 ),
             ]),
+            gimmicks: Vec::new(),
         }
     }
 
@@ -588,6 +618,7 @@ mod tests {
             stage_id: stage_id.map(ToOwned::to_owned),
             selected_rank: map_record::SelectedRank::NotSet,
             event_state: None,
+            gimmick_state: None,
         }
     }
 
@@ -714,6 +745,7 @@ mod tests {
             default_variant: String::new(),
             rank_stage_ids: BTreeMap::new(),
             variants: BTreeMap::new(),
+            gimmicks: Vec::new(),
         }
     }
 
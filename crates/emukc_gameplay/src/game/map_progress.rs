@@ -1,5 +1,24 @@
-use emukc_db::{entity::profile::map_record, sea_orm::ActiveValue};
-use emukc_model::codex::map::{MapDefinition, MapStageDefinition};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use emukc_db::{
+    entity::profile::map_record,
+    sea_orm::{ActiveModelTrait, ActiveValue, ConnectionTrait, IntoActiveModel},
+};
+use emukc_model::{
+    codex::map::{
+        GimmickCondition, GimmickEffect, MapDefinition, MapGimmickDefinition, MapStageDefinition,
+        RoutePredicate, RouteRule,
+    },
+    kc2::{KcShipType, KcSlotItemType3},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::err::GameplayError;
+
+use super::map::find_map_record_impl;
 
 pub(crate) fn resolve_record_stage_id(
     definition: &MapDefinition,
@@ -32,11 +51,356 @@ pub(crate) fn assign_stage_id(record: &mut map_record::ActiveModel, stage_id: Op
     record.stage_id = ActiveValue::Set(stage_id);
 }
 
+/// Progress of a map record towards its gimmicks, kept as JSON in `gimmick_state`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct GimmickProgress {
+    /// Times each condition has been met, by gimmick and condition index.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub counts: BTreeMap<String, Vec<i64>>,
+
+    /// Gimmicks whose conditions have all been met.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub triggered: BTreeSet<String>,
+}
+
+impl GimmickProgress {
+    pub(crate) fn from_record(record: &map_record::Model) -> Self {
+        let Some(state) = record.gimmick_state.as_deref() else {
+            return Self::default();
+        };
+        serde_json::from_str(state).unwrap_or_else(|e| {
+            tracing::warn!(map_id = record.map_id, "dropping unreadable gimmick state: {e}");
+            Self::default()
+        })
+    }
+
+    pub(crate) fn to_state(&self) -> Option<String> {
+        if *self == Self::default() {
+            return None;
+        }
+        serde_json::to_string(self).ok()
+    }
+}
+
+/// Something that happened in a sortie that gimmick conditions count.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum GimmickEvent<'a> {
+    /// The fleet reached a cell.
+    Visit {
+        cell_no: i64,
+    },
+
+    /// The fleet won the battle at a cell.
+    Win {
+        cell_no: i64,
+        rank: &'a str,
+    },
+}
+
+/// Count `event` towards the gimmicks of `definition` that exist on `gauge_index` and `rank`.
+///
+/// # Returns
+///
+/// The gimmicks triggered by it, including those waiting on a gimmick triggered here.
+pub(crate) fn record_gimmick_event<'a>(
+    definition: &'a MapDefinition,
+    stage: &MapStageDefinition,
+    gauge_index: i64,
+    rank: i64,
+    progress: &mut GimmickProgress,
+    event: GimmickEvent<'_>,
+) -> Vec<&'a MapGimmickDefinition> {
+    let pending = definition
+        .gimmicks
+        .iter()
+        .filter(|gimmick| {
+            gimmick.applies_to(gauge_index, rank)
+                && !progress.triggered.contains(&gimmick.gimmick_id)
+        })
+        .collect::<Vec<_>>();
+
+    for gimmick in &pending {
+        let counts = progress.counts.entry(gimmick.gimmick_id.clone()).or_default();
+        counts.resize(gimmick.conditions.len(), 0);
+        for (condition, count) in gimmick.conditions.iter().zip(counts.iter_mut()) {
+            let met = match (condition, event) {
+                (
+                    GimmickCondition::Visit {
+                        node,
+                        ..
+                    },
+                    GimmickEvent::Visit {
+                        cell_no,
+                    },
+                ) => stage.node_cell_nos(node).contains(&cell_no),
+                (
+                    GimmickCondition::Win {
+                        node,
+                        rank: required,
+                        ..
+                    },
+                    GimmickEvent::Win {
+                        cell_no,
+                        rank,
+                    },
+                ) => {
+                    win_rank_at_least(rank, required)
+                        && stage.node_cell_nos(node).contains(&cell_no)
+                }
+                _ => false,
+            };
+            if met {
+                *count = (*count + 1).min(condition.required_count());
+            }
+        }
+    }
+
+    let mut triggered = Vec::new();
+    while let Some(gimmick) = pending.iter().copied().find(|gimmick| {
+        !progress.triggered.contains(&gimmick.gimmick_id)
+            && gimmick_conditions_met(gimmick, progress)
+    }) {
+        progress.counts.remove(&gimmick.gimmick_id);
+        progress.triggered.insert(gimmick.gimmick_id.clone());
+        triggered.push(gimmick);
+    }
+    triggered
+}
+
+fn gimmick_conditions_met(gimmick: &MapGimmickDefinition, progress: &GimmickProgress) -> bool {
+    let counts = progress.counts.get(&gimmick.gimmick_id);
+    gimmick.conditions.iter().enumerate().all(|(index, condition)| match condition {
+        GimmickCondition::Gimmick {
+            gimmick_id,
+        } => progress.triggered.contains(gimmick_id),
+        _ => {
+            counts.and_then(|counts| counts.get(index)).copied().unwrap_or_default()
+                >= condition.required_count()
+        }
+    })
+}
+
+fn win_rank_at_least(rank: &str, required: &str) -> bool {
+    const ORDER: [&str; 6] = ["S", "A", "B", "C", "D", "E"];
+    let position = |rank: &str| ORDER.iter().position(|r| *r == rank);
+    matches!((position(rank), position(required)), (Some(rank), Some(required)) if rank <= required)
+}
+
+/// Gimmicks of `record` that have triggered and apply to its current gauge and rank.
+pub(crate) fn active_gimmick_ids(
+    definition: &MapDefinition,
+    record: &map_record::Model,
+) -> BTreeSet<String> {
+    let progress = GimmickProgress::from_record(record);
+    let rank = record.selected_rank.clone() as i64;
+    definition
+        .gimmicks
+        .iter()
+        .filter(|gimmick| {
+            gimmick.applies_to(record.gauge_index, rank)
+                && progress.triggered.contains(&gimmick.gimmick_id)
+        })
+        .map(|gimmick| gimmick.gimmick_id.clone())
+        .collect()
+}
+
+/// The stage the last of `gimmick_ids` that switches stages moves the map to.
+pub(crate) fn gimmick_stage_id(
+    definition: &MapDefinition,
+    gimmick_ids: &BTreeSet<String>,
+) -> Option<String> {
+    definition
+        .gimmicks
+        .iter()
+        .filter(|gimmick| gimmick_ids.contains(&gimmick.gimmick_id))
+        .flat_map(|gimmick| &gimmick.effects)
+        .filter_map(|effect| match effect {
+            GimmickEffect::SwitchStage {
+                stage_id,
+            } => Some(stage_id),
+            _ => None,
+        })
+        .rfind(|stage_id| definition.stage(stage_id).is_some())
+        .cloned()
+}
+
+/// `stage` with the boss debuffs and routes of `gimmick_ids` applied.
+///
+/// An opened route replaces the routing rules of its source node, so fleets reaching it
+/// always take the new route.
+pub(crate) fn apply_gimmicks<'a>(
+    definition: &MapDefinition,
+    stage: &'a MapStageDefinition,
+    gimmick_ids: &BTreeSet<String>,
+) -> Cow<'a, MapStageDefinition> {
+    let effects = definition
+        .gimmicks
+        .iter()
+        .filter(|gimmick| gimmick_ids.contains(&gimmick.gimmick_id))
+        .flat_map(|gimmick| &gimmick.effects)
+        .filter(|effect| !matches!(effect, GimmickEffect::SwitchStage { .. }))
+        .collect::<Vec<_>>();
+    if effects.is_empty() {
+        return Cow::Borrowed(stage);
+    }
+
+    let mut stage = stage.clone();
+    for effect in effects {
+        match effect {
+            GimmickEffect::BossDebuff {
+                armor,
+                hp,
+            } => {
+                stage.boss_debuff.armor += armor;
+                stage.boss_debuff.hp += hp;
+            }
+            GimmickEffect::OpenRoute {
+                from,
+                to,
+            } => {
+                let Some(to_cell_no) = stage.node_cell_nos(to).first().copied() else {
+                    tracing::warn!(
+                        map_id = definition.map_id,
+                        to,
+                        "gimmick route target not found"
+                    );
+                    continue;
+                };
+                for from_cell_no in stage.node_cell_nos(from) {
+                    if let Some(cell) = stage.cells.iter_mut().find(|c| c.cell_no == from_cell_no)
+                        && !cell.next_cells.contains(&to_cell_no)
+                    {
+                        cell.next_cells.push(to_cell_no);
+                    }
+                    stage.routing_rules.insert(
+                        from_cell_no,
+                        vec![RouteRule {
+                            from_cell_no,
+                            to_cell_no,
+                            priority: 0,
+                            weight: None,
+                            probability_pct: None,
+                            predicate: RoutePredicate::Always,
+                            raw_text: format!("gimmick route {from} -> {to}"),
+                        }],
+                    );
+                }
+            }
+            GimmickEffect::SwitchStage {
+                ..
+            } => {}
+        }
+    }
+    Cow::Owned(stage)
+}
+
+/// Count `event` towards the gimmicks of the profile's record for `definition`.
+///
+/// A triggered gimmick that switches stages moves the record there at once; the other
+/// effects apply from the next sortie.
+///
+/// # Returns
+///
+/// The ids of the gimmicks triggered by the event.
+pub(crate) async fn record_gimmick_event_impl<C>(
+    c: &C,
+    profile_id: i64,
+    definition: &MapDefinition,
+    stage: &MapStageDefinition,
+    event: GimmickEvent<'_>,
+) -> Result<Vec<String>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    if definition.gimmicks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let record = find_map_record_impl(c, profile_id, definition.map_id).await?;
+    let rank = record.selected_rank.clone() as i64;
+    let mut progress = GimmickProgress::from_record(&record);
+    let triggered =
+        record_gimmick_event(definition, stage, record.gauge_index, rank, &mut progress, event)
+            .into_iter()
+            .map(|gimmick| gimmick.gimmick_id.clone())
+            .collect::<Vec<_>>();
+
+    let state = progress.to_state();
+    if state == record.gimmick_state {
+        return Ok(triggered);
+    }
+    let stage_id = gimmick_stage_id(definition, &triggered.iter().cloned().collect());
+    let mut am = record.into_active_model();
+    am.gimmick_state = ActiveValue::Set(state);
+    if stage_id.is_some() {
+        assign_stage_id(&mut am, stage_id);
+    }
+    am.update(c).await?;
+
+    if !triggered.is_empty() {
+        tracing::info!(
+            profile_id,
+            map_id = definition.map_id,
+            ?triggered,
+            "event gimmicks triggered"
+        );
+    }
+    Ok(triggered)
+}
+
+/// TP a ship carries on a transport operation: a base value for its ship type plus its
+/// cargo equipment.
+pub(crate) fn ship_transport_points(
+    ship_id: i64,
+    ship_type: i64,
+    equip_types: impl IntoIterator<Item = i64>,
+) -> i64 {
+    /// 鬼怒改二 carries a built-in 大発動艇.
+    const KINU_KAI_NI: i64 = 487;
+
+    let base = match KcShipType::n(ship_type) {
+        Some(KcShipType::DD) => 5,
+        Some(KcShipType::CL) => 2,
+        Some(KcShipType::CAV) => 4,
+        Some(KcShipType::BBV | KcShipType::AS) => 7,
+        Some(KcShipType::SSV) => 1,
+        Some(KcShipType::AV) => 9,
+        Some(KcShipType::LHA) => 12,
+        Some(KcShipType::CT) => 6,
+        Some(KcShipType::AO) => 15,
+        _ => 0,
+    };
+    let built_in = if ship_id == KINU_KAI_NI {
+        8
+    } else {
+        0
+    };
+    let cargo = equip_types
+        .into_iter()
+        .map(|equip_type| match KcSlotItemType3::n(equip_type) {
+            Some(KcSlotItemType3::TransportContainer) => 5,
+            Some(KcSlotItemType3::LandingCraft) => 8,
+            Some(KcSlotItemType3::SpecialTypeAmphibiousTank) => 2,
+            Some(KcSlotItemType3::BattleRation) => 1,
+            _ => 0,
+        })
+        .sum::<i64>();
+    base + built_in + cargo
+}
+
+/// How far a boss win lowers a transport gauge: all of the fleet's TP on S, 70% on A.
+pub(crate) fn transport_gauge_damage(transport_points: i64, win_rank: &str) -> i64 {
+    match win_rank {
+        "S" => transport_points,
+        "A" => transport_points * 7 / 10,
+        _ => 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
 
-    use emukc_model::codex::map::{MapDefinition, MapResetPolicy, MapStageDefinition};
+    use emukc_model::codex::map::{MapCellDefinition, MapResetPolicy};
 
     use super::*;
 
@@ -73,6 +437,7 @@ mod tests {
                     },
                 ),
             ]),
+            gimmicks: Vec::new(),
         }
     }
 
@@ -91,6 +456,7 @@ mod tests {
             stage_id: stage_id.map(ToOwned::to_owned),
             selected_rank: map_record::SelectedRank::NotSet,
             event_state: None,
+            gimmick_state: None,
         }
     }
 
@@ -115,6 +481,156 @@ mod tests {
         assert_eq!(resolve_record_stage_id(&definition, &record), Some("legacy".to_string()));
     }
 
+    fn gimmick_definition() -> MapDefinition {
+        let mut definition = sample_definition();
+        definition.variants.get_mut("legacy").unwrap().cells = vec![
+            MapCellDefinition {
+                cell_no: 1,
+                next_cells: vec![2],
+                node_label: Some("A".to_string()),
+                ..Default::default()
+            },
+            MapCellDefinition {
+                cell_no: 2,
+                node_label: Some("B".to_string()),
+                ..Default::default()
+            },
+            MapCellDefinition {
+                cell_no: 3,
+                node_label: Some("C".to_string()),
+                ..Default::default()
+            },
+        ];
+        definition.gimmicks = vec![
+            MapGimmickDefinition {
+                gimmick_id: "debuff".to_string(),
+                gauge_index: Some(1),
+                ranks: vec![],
+                conditions: vec![
+                    GimmickCondition::Visit {
+                        node: "A".to_string(),
+                        count: 2,
+                    },
+                    GimmickCondition::Win {
+                        node: "B".to_string(),
+                        rank: "A".to_string(),
+                        count: 1,
+                    },
+                ],
+                effects: vec![GimmickEffect::BossDebuff {
+                    armor: 20,
+                    hp: 0,
+                }],
+            },
+            MapGimmickDefinition {
+                gimmick_id: "route".to_string(),
+                gauge_index: None,
+                ranks: vec![4],
+                conditions: vec![GimmickCondition::Gimmick {
+                    gimmick_id: "debuff".to_string(),
+                }],
+                effects: vec![
+                    GimmickEffect::OpenRoute {
+                        from: "A".to_string(),
+                        to: "C".to_string(),
+                    },
+                    GimmickEffect::SwitchStage {
+                        stage_id: "current".to_string(),
+                    },
+                ],
+            },
+        ];
+        definition
+    }
+
+    #[test]
+    fn gimmick_triggers_once_every_condition_is_met() {
+        let definition = gimmick_definition();
+        let stage = definition.stage("legacy").unwrap();
+        let mut progress = GimmickProgress::default();
+        let mut record = |event| {
+            record_gimmick_event(&definition, stage, 1, 4, &mut progress, event)
+                .into_iter()
+                .map(|gimmick| gimmick.gimmick_id.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(
+            record(GimmickEvent::Visit {
+                cell_no: 1
+            })
+            .is_empty()
+        );
+        // a B win is not good enough
+        assert!(
+            record(GimmickEvent::Win {
+                cell_no: 2,
+                rank: "B"
+            })
+            .is_empty()
+        );
+        assert!(
+            record(GimmickEvent::Win {
+                cell_no: 2,
+                rank: "S"
+            })
+            .is_empty()
+        );
+        assert_eq!(
+            record(GimmickEvent::Visit {
+                cell_no: 1
+            }),
+            vec!["debuff", "route"]
+        );
+        assert!(
+            record(GimmickEvent::Visit {
+                cell_no: 1
+            })
+            .is_empty()
+        );
+
+        let state = progress.to_state().unwrap();
+        let mut model = sample_record(None);
+        model.gimmick_state = Some(state);
+        model.selected_rank = map_record::SelectedRank::Hard;
+        let ids = active_gimmick_ids(&definition, &model);
+        assert_eq!(ids, BTreeSet::from(["debuff".to_string(), "route".to_string()]));
+        assert_eq!(gimmick_stage_id(&definition, &ids).as_deref(), Some("current"));
+
+        // the route gimmick only exists on 甲, the debuff only on the first gauge
+        model.selected_rank = map_record::SelectedRank::Normal;
+        model.gauge_index = 2;
+        assert!(active_gimmick_ids(&definition, &model).is_empty());
+    }
+
+    #[test]
+    fn apply_gimmicks_debuffs_boss_and_opens_routes() {
+        let definition = gimmick_definition();
+        let stage = definition.stage("legacy").unwrap();
+        assert!(matches!(apply_gimmicks(&definition, stage, &BTreeSet::new()), Cow::Borrowed(_)));
+
+        let ids = BTreeSet::from(["debuff".to_string(), "route".to_string()]);
+        let applied = apply_gimmicks(&definition, stage, &ids);
+        assert_eq!(applied.boss_debuff.armor, 20);
+        assert_eq!(applied.cell(1).unwrap().next_cells, vec![2, 3]);
+        let rules = &applied.routing_rules[&1];
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].to_cell_no, 3);
+    }
+
+    #[test]
+    fn transport_points_count_ship_type_and_cargo() {
+        let drum = KcSlotItemType3::TransportContainer as i64;
+        let daihatsu = KcSlotItemType3::LandingCraft as i64;
+        assert_eq!(ship_transport_points(1, KcShipType::DD as i64, [drum, drum]), 15);
+        assert_eq!(ship_transport_points(1, KcShipType::BB as i64, []), 0);
+        assert_eq!(ship_transport_points(487, KcShipType::CL as i64, [daihatsu]), 18);
+
+        assert_eq!(transport_gauge_damage(47, "S"), 47);
+        assert_eq!(transport_gauge_damage(47, "A"), 32);
+        assert_eq!(transport_gauge_damage(47, "B"), 0);
+    }
+
     #[test]
     fn assign_stage_id_sets_value() {
         let mut record = map_record::ActiveModel {
@@ -123,6 +639,7 @@ mod tests {
             map_id: ActiveValue::Set(1),
             cleared: ActiveValue::Set(false),
             unlocked: ActiveValue::Set(true),
+            gimmick_state: ActiveValue::Set(None),
             last_cleared_at: ActiveValue::Set(None),
             last_reset_at: ActiveValue::Set(None),
             defeat_count: ActiveValue::Set(None),
@@ -144,6 +661,7 @@ mod tests {
             map_id: ActiveValue::Set(1),
             cleared: ActiveValue::Set(false),
             unlocked: ActiveValue::Set(true),
+            gimmick_state: ActiveValue::Set(None),
             last_cleared_at: ActiveValue::Set(None),
             last_reset_at: ActiveValue::Set(None),
             defeat_count: ActiveValue::Set(None),
@@ -7,6 +7,8 @@ use emukc_model::codex::map::{
 
 use crate::err::GameplayError;
 
+pub(crate) const DRUM_CANISTER_MST_ID: i64 = 75;
+
 #[derive(Debug, Clone, Default)]
 pub(crate) struct FleetRouteShipEntry {
     pub(crate) ship_id: i64,
@@ -65,6 +67,135 @@ impl FleetRouteContext {
     }
 }
 
+/// Route-relevant stats of one fleet ship, as [`FleetRouteContext::from_ships`] consumes them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FleetRouteShipStats {
+    pub(crate) ship_id: i64,
+    /// `None` when the ship is missing from the manifest.
+    pub(crate) ship_type: Option<i64>,
+    pub(crate) speed: i64,
+    /// Current `LoS`, base plus equipment.
+    pub(crate) los_now: i64,
+    /// `(type3, master id, LoS)` of each equipped item.
+    pub(crate) equipment: Vec<(i64, i64, i64)>,
+}
+
+impl FleetRouteContext {
+    /// Aggregate the route context of a fleet, flagship first.
+    pub(crate) fn from_ships(ships: &[FleetRouteShipStats], hq_level: i64) -> Self {
+        let mut ship_ids = BTreeSet::new();
+        let mut ship_type_counts = BTreeMap::<i64, i64>::new();
+        let mut ship_entries = Vec::with_capacity(ships.len());
+        let mut min_speed = i64::MAX;
+        let mut los_total = 0;
+        let mut total_drums = 0;
+        let mut flagship_ship_id = None;
+        let mut flagship_ship_type = None;
+        // Accumulators for LoS formulas.
+        let mut los_f1_acc: f64 = 0.0;
+        let mut los_f3_acc: f64 = 0.0;
+
+        for (idx, ship) in ships.iter().enumerate() {
+            ship_ids.insert(ship.ship_id);
+            if let Some(ship_type) = ship.ship_type {
+                *ship_type_counts.entry(ship_type).or_default() += 1;
+                if idx == 0 {
+                    flagship_ship_id = Some(ship.ship_id);
+                    flagship_ship_type = Some(ship_type);
+                }
+                let mut entry = FleetRouteShipEntry {
+                    ship_id: ship.ship_id,
+                    ship_type,
+                    speed: ship.speed,
+                    slotitem_types: BTreeSet::new(),
+                };
+                // Sum equipment LoS for this ship (used in formula 3).
+                let mut ship_equip_saku: i64 = 0;
+                for (type3, mst_id, api_saku) in ship.equipment.iter().copied() {
+                    entry.slotitem_types.insert(type3);
+                    if mst_id == DRUM_CANISTER_MST_ID {
+                        total_drums += 1;
+                    }
+                    ship_equip_saku += api_saku;
+                }
+                ship_entries.push(entry);
+
+                // Formula 1: Σ sqrt(ship.los_now).  Per-equipment sqrt-weighting is
+                // an approximation here; we use the combined value since individual
+                // equipment LoS is not split out in the DB entity.
+                los_f1_acc += (ship.los_now as f64).sqrt();
+
+                // Formula 3: Σ(equip_los × 0.6 + sqrt(ship_base_los)).
+                // ship_base_los = ship.los_now − ship_equip_saku.
+                let ship_base_los = (ship.los_now - ship_equip_saku).max(0) as f64;
+                los_f3_acc += ship_equip_saku as f64 * 0.6 + ship_base_los.sqrt();
+            } else {
+                // Unknown ship — fall back to raw los_now for both formulas.
+                los_f1_acc += (ship.los_now as f64).sqrt();
+                los_f3_acc += (ship.los_now as f64).sqrt();
+            }
+            min_speed = min_speed.min(ship.speed);
+            los_total += ship.los_now;
+        }
+
+        let fleet_size = ships.len() as i64;
+        // Formula 3 HQ penalty: ceil(0.4 × hq_level).
+        let hq_penalty = (0.4 * hq_level as f64).ceil();
+        // Fleet-size bonus: (6 - fleet_size) × 2.
+        let fleet_bonus = ((6 - fleet_size).max(0)) as f64 * 2.0;
+        let los_formula3 = (los_f3_acc - hq_penalty + fleet_bonus).max(0.0);
+
+        Self {
+            fleet_size,
+            visited_cell_ids: BTreeSet::new(),
+            ship_ids,
+            flagship_ship_id,
+            flagship_ship_type,
+            ship_type_counts,
+            ship_entries,
+            min_speed: if min_speed == i64::MAX {
+                0
+            } else {
+                min_speed
+            },
+            los_total,
+            total_drums,
+            los_formula1: los_f1_acc,
+            los_formula3,
+        }
+    }
+}
+
+/// How [`evaluate_route_decision`] picked the next cell.
+#[derive(Debug, Clone)]
+pub(crate) struct RouteDecision<'a> {
+    pub(crate) cell_no: i64,
+    /// Rules whose predicates decided the branch; empty when the cell has no
+    /// rules or the pick fell back to topology.
+    pub(crate) rules: Vec<&'a RouteRule>,
+    /// Whether `Unknown`/`SourceUnknown` rules forced a random pick among the
+    /// cell's next cells.
+    pub(crate) random_fallback: bool,
+}
+
+impl<'a> RouteDecision<'a> {
+    fn plain(cell_no: i64) -> Self {
+        Self {
+            cell_no,
+            rules: Vec::new(),
+            random_fallback: false,
+        }
+    }
+
+    fn random_fallback(cell_no: i64) -> Self {
+        Self {
+            cell_no,
+            rules: Vec::new(),
+            random_fallback: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum RoutePredicateEval {
     Matched,
@@ -85,9 +216,20 @@ pub(crate) fn evaluate_route_destination(
     context: &FleetRouteContext,
     selected_cell_id: Option<i64>,
 ) -> Result<i64, GameplayError> {
+    evaluate_route_decision(current, stage, context, selected_cell_id)
+        .map(|decision| decision.cell_no)
+}
+
+/// [`evaluate_route_destination`], also reporting which rules decided the branch.
+pub(crate) fn evaluate_route_decision<'a>(
+    current: &MapCellDefinition,
+    stage: &'a MapStageDefinition,
+    context: &FleetRouteContext,
+    selected_cell_id: Option<i64>,
+) -> Result<RouteDecision<'a>, GameplayError> {
     let Some(rules) = stage.routing_rules.get(&current.cell_no).filter(|rules| !rules.is_empty())
     else {
-        return select_route_from_cells(current, stage, selected_cell_id);
+        return select_route_from_cells(current, stage, selected_cell_id).map(RouteDecision::plain);
     };
 
     let mut fallback_rules = Vec::<&RouteRule>::new();
@@ -142,16 +284,17 @@ pub(crate) fn evaluate_route_destination(
                 && targets.contains(&selected_cell_id)
                 && current.next_cells.contains(&selected_cell_id)
             {
-                return Ok(selected_cell_id);
+                return Ok(RouteDecision::plain(selected_cell_id));
             }
-            return select_route_from_cells(current, stage, None);
+            return select_route_from_cells(current, stage, None)
+                .map(RouteDecision::random_fallback);
         }
 
         if let Some(selected_cell_id) = selected_cell_id
             && any_indeterminate
             && current.next_cells.contains(&selected_cell_id)
         {
-            return Ok(selected_cell_id);
+            return Ok(RouteDecision::plain(selected_cell_id));
         }
 
         let unconditional_targets = rules
@@ -160,15 +303,29 @@ pub(crate) fn evaluate_route_destination(
             .map(|rule| rule.to_cell_no)
             .collect::<BTreeSet<_>>();
         if any_indeterminate && unconditional_targets.len() == 1 {
-            return unconditional_targets.iter().next().copied().ok_or_else(|| {
+            let cell_no = unconditional_targets.iter().next().copied().ok_or_else(|| {
                 GameplayError::WrongType(format!(
                     "cell {} has no executable route",
                     current.cell_no
                 ))
+            })?;
+            return Ok(RouteDecision {
+                cell_no,
+                rules: rules
+                    .iter()
+                    .filter(|rule| matches!(rule.predicate, RoutePredicate::Always))
+                    .collect(),
+                random_fallback: false,
             });
         }
         if any_indeterminate {
-            return select_route_from_cells(current, stage, selected_cell_id);
+            return select_route_from_cells(current, stage, selected_cell_id).map(|cell_no| {
+                RouteDecision {
+                    cell_no,
+                    rules: Vec::new(),
+                    random_fallback: selected_cell_id.is_none(),
+                }
+            });
         }
         return Err(GameplayError::WrongType(format!(
             "cell {} has no executable routing rule",
@@ -188,8 +345,13 @@ pub(crate) fn evaluate_route_destination(
             next_cells = ?current.next_cells,
             "route rules filtered by topology, falling back to next_cells"
         );
-        return select_route_from_cells(current, stage, selected_cell_id);
+        return select_route_from_cells(current, stage, selected_cell_id).map(RouteDecision::plain);
     }
+    let decided_by = |cell_no: i64| RouteDecision {
+        cell_no,
+        rules: executable.iter().copied().filter(|rule| rule.to_cell_no == cell_no).collect(),
+        random_fallback: false,
+    };
     if let Some(selected_cell_id) = selected_cell_id {
         if !candidate_targets.contains(&selected_cell_id) {
             return Err(GameplayError::WrongType(format!(
@@ -197,10 +359,10 @@ pub(crate) fn evaluate_route_destination(
                 current.cell_no,
             )));
         }
-        return Ok(selected_cell_id);
+        return Ok(decided_by(selected_cell_id));
     }
     if candidate_targets.len() == 1 {
-        return candidate_targets.iter().next().copied().ok_or_else(|| {
+        return candidate_targets.iter().next().copied().map(decided_by).ok_or_else(|| {
             GameplayError::WrongType(format!("cell {} has no executable route", current.cell_no))
         });
     }
@@ -233,17 +395,40 @@ pub(crate) fn evaluate_route_destination(
     });
     let total_weight = weights.values().sum::<u64>();
     if total_weight == 0 {
-        return candidate_targets.iter().next().copied().ok_or_else(|| {
+        return candidate_targets.iter().next().copied().map(decided_by).ok_or_else(|| {
             GameplayError::WrongType(format!("cell {} has no executable route", current.cell_no))
         });
     }
 
     let roll = rng::u64(0..total_weight);
-    select_route_target_for_roll(&weights, roll).ok_or_else(|| {
+    select_route_target_for_roll(&weights, roll).map(decided_by).ok_or_else(|| {
         GameplayError::WrongType(format!("cell {} has no executable route", current.cell_no))
     })
 }
 
+/// Validate the player's pick at a player-chosen branch (能動分岐).
+///
+/// `current.select_cells` must not be empty; the routing rules of such a cell
+/// are not consulted.
+pub(crate) fn select_player_route(
+    current: &MapCellDefinition,
+    selected_cell_id: Option<i64>,
+) -> Result<i64, GameplayError> {
+    let Some(selected_cell_id) = selected_cell_id else {
+        return Err(GameplayError::WrongType(format!(
+            "cell {} requires the player to select a route",
+            current.cell_no,
+        )));
+    };
+    if !current.select_cells.contains(&selected_cell_id) {
+        return Err(GameplayError::WrongType(format!(
+            "cell {selected_cell_id} is not a selectable route from {}",
+            current.cell_no,
+        )));
+    }
+    Ok(selected_cell_id)
+}
+
 fn select_route_from_cells(
     current: &MapCellDefinition,
     stage: &MapStageDefinition,
@@ -539,6 +724,16 @@ pub(crate) fn select_route_target_for_roll(
     weights.keys().last().copied()
 }
 
+/// Human-readable label of a rule: its source text, or its predicate key
+/// when the rule was not parsed from text.
+pub(crate) fn route_rule_label(rule: &RouteRule) -> String {
+    if rule.raw_text.is_empty() {
+        route_predicate_key(&rule.predicate)
+    } else {
+        rule.raw_text.clone()
+    }
+}
+
 fn route_predicate_key(predicate: &RoutePredicate) -> String {
     match predicate {
         RoutePredicate::Always => "always".into(),
@@ -758,6 +953,17 @@ impl RoutePredicateEval {
 mod tests {
     use super::*;
 
+    #[test]
+    fn select_player_route_validates_the_pick() {
+        let cell = MapCellDefinition {
+            select_cells: vec![2, 3],
+            ..make_cell(1, vec![2, 3, 4])
+        };
+        assert_eq!(select_player_route(&cell, Some(3)).unwrap(), 3);
+        assert!(select_player_route(&cell, Some(4)).is_err());
+        assert!(select_player_route(&cell, None).is_err());
+    }
+
     #[test]
     fn cell_has_routing_outgoing_next_cells_only() {
         let stage = MapStageDefinition {
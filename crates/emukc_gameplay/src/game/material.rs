@@ -4,6 +4,7 @@ use emukc_db::{
     sea_orm::{ActiveValue, TransactionTrait, TryIntoModel, entity::prelude::*},
 };
 use emukc_model::{codex::Codex, kc2::MaterialCategory, profile::material::Material};
+use emukc_time::chrono::{DateTime, Utc};
 
 use crate::{err::GameplayError, gameplay::HasContext};
 
@@ -99,7 +100,14 @@ impl<T: HasContext + ?Sized> MaterialOps for T {
 
         let profile = find_profile(&tx, profile_id).await?;
 
-        let m = update_materials_impl(&tx, codex, profile_id, profile.hq_level).await?;
+        let m = update_materials_impl(
+            &tx,
+            codex,
+            profile_id,
+            profile.hq_level,
+            self.clock().now(profile_id),
+        )
+        .await?;
 
         tx.commit().await?;
 
@@ -214,13 +222,14 @@ pub(crate) async fn update_materials_impl<C>(
     codex: &Codex,
     profile_id: i64,
     user_lv: i64,
+    now: DateTime<Utc>,
 ) -> Result<material::Model, GameplayError>
 where
     C: ConnectionTrait,
 {
     let record = get_mat_impl(c, profile_id).await?;
     let mut model: Material = record.into();
-    codex.game_cfg.material.apply_self_replenish(&mut model, user_lv);
+    codex.game_cfg.material.apply_self_replenish(&mut model, user_lv, now);
 
     let am = material::ActiveModel {
         profile_id: ActiveValue::Unchanged(profile_id),
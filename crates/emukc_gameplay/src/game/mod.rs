@@ -2,15 +2,22 @@
 
 pub mod battle;
 
-pub use airbase::AirbaseOps;
+pub use airbase::{AirBaseStrike, AirbaseOps};
+pub use archive::{
+    PROFILE_ARCHIVE_FORMAT, PROFILE_ARCHIVE_VERSION, ProfileArchive, validate_profile_archive,
+};
+pub(crate) use archive::{export_profile_impl, import_profile_impl};
 pub use basic::BasicOps;
+pub use clock::{ClockOps, ClockStatus};
 pub use compose::{ComposeOps, PowerupResp, SlotDepriveParams};
 pub use expedition::{
     ExpeditionCompletion, ExpeditionItemReward, ExpeditionOps, ExpeditionStartInfo,
+    SupportMissionKind,
 };
-pub use factory::FactoryOps;
+pub use factory::{DevelopedSlotItem, FactoryOps};
 pub use fleet::FleetOps;
 pub use furniture::FurnitureOps;
+pub use improvement::{ImprovementDetail, ImprovementOps, ImprovementResult};
 pub use incentive::IncentiveOps;
 pub(crate) use init::{init_profile_game_data, wipe_profile_game_data};
 pub use kdock::KDockOps;
@@ -23,6 +30,11 @@ pub use picturebook::PictureBookOps;
 pub use practice::PracticeOps;
 pub use presets::PresetOps;
 pub use quest::QuestOps;
+pub use route_sim::{
+    RouteSimBranches, RouteSimFailure, RouteSimShip, RouteSimTarget, RouteSimulation,
+    simulate_routes,
+};
+pub use session_journal::DEFAULT_SESSION_TTL;
 pub use settings::SettingsOps;
 pub use ship::ShipOps;
 pub use slot_item::SlotItemOps;
@@ -39,12 +51,15 @@ use crate::gameplay::HasContext;
 // modules
 
 mod airbase;
+mod archive;
 mod basic;
+mod clock;
 mod compose;
 mod expedition;
 mod factory;
 mod fleet;
 mod furniture;
+mod improvement;
 mod incentive;
 mod init;
 mod kdock;
@@ -58,6 +73,8 @@ mod picturebook;
 mod practice;
 mod presets;
 mod quest;
+mod route_sim;
+mod session_journal;
 mod settings;
 mod ship;
 mod slot_item;
@@ -71,11 +88,13 @@ mod use_item;
 pub trait GameOps:
     BasicOps
     + AirbaseOps
+    + ClockOps
     + ComposeOps
     + ExpeditionOps
     + FactoryOps
     + FleetOps
     + FurnitureOps
+    + ImprovementOps
     + SettingsOps
     + IncentiveOps
     + KDockOps
@@ -102,9 +121,10 @@ pub mod ops {
 
     #[doc(hidden)]
     pub use crate::game::{
-        AirbaseOps, BasicOps, ComposeOps, ExpeditionOps, FactoryOps, FleetOps, FurnitureOps,
-        GameOps, IncentiveOps, KDockOps, MapOps, MaterialOps, NDockOps, PayItemOps, PictureBookOps,
-        PracticeOps, PresetOps, QuestOps, SettingsOps, ShipOps, SlotItemOps, SortieOps, UseItemOps,
+        AirbaseOps, BasicOps, ClockOps, ComposeOps, ExpeditionOps, FactoryOps, FleetOps,
+        FurnitureOps, GameOps, ImprovementOps, IncentiveOps, KDockOps, MapOps, MaterialOps,
+        NDockOps, PayItemOps, PictureBookOps, PracticeOps, PresetOps, QuestOps, SettingsOps,
+        ShipOps, SlotItemOps, SortieOps, UseItemOps,
     };
 }
 
@@ -113,8 +133,11 @@ pub mod types {
 
     #[doc(hidden)]
     pub use crate::game::{
-        ExpeditionCompletion, ExpeditionItemReward, ExpeditionStartInfo, PowerupResp,
-        SlotDepriveParams, SortieAirSearch, SortieCellData, SortieEnemyDeckPreview,
-        SortieHappening, SortieItemGet, SortieNextResponse, SortieStartResponse,
+        AirBaseStrike, ClockStatus, DevelopedSlotItem, ExpeditionCompletion, ExpeditionItemReward,
+        ExpeditionStartInfo, ImprovementDetail, ImprovementResult, PROFILE_ARCHIVE_FORMAT,
+        PROFILE_ARCHIVE_VERSION, PowerupResp, ProfileArchive, RouteSimBranches, RouteSimFailure,
+        RouteSimShip, RouteSimTarget, RouteSimulation, SlotDepriveParams, SortieAirSearch,
+        SortieCellData, SortieEnemyDeckPreview, SortieHappening, SortieItemGet, SortieNextResponse,
+        SortieStartResponse, simulate_routes, validate_profile_archive,
     };
 }
@@ -12,7 +12,7 @@ use emukc_model::{
     prelude::ApiMstShip,
     profile::{material::Material, ndock::RepairDock},
 };
-use emukc_time::chrono;
+use emukc_time::chrono::{self, DateTime, Utc};
 
 use crate::{err::GameplayError, gameplay::HasContext};
 
@@ -110,7 +110,7 @@ impl<T: HasContext + ?Sized> NDockOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        let docks = get_ndocks_impl(&tx, codex, profile_id).await?;
+        let docks = get_ndocks_impl(&tx, codex, profile_id, self.clock().now(profile_id)).await?;
 
         tx.commit().await?;
 
@@ -139,8 +139,16 @@ impl<T: HasContext + ?Sized> NDockOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        let m =
-            ndock_start_repair_impl(&tx, codex, profile_id, ndock_id, ship_id, highspeed).await?;
+        let m = ndock_start_repair_impl(
+            &tx,
+            codex,
+            profile_id,
+            ndock_id,
+            ship_id,
+            highspeed,
+            self.clock().now(profile_id),
+        )
+        .await?;
 
         tx.commit().await?;
 
@@ -231,10 +239,12 @@ where
 /// # Parameters
 ///
 /// - `profile_id`: The profile ID.
+/// - `now`: The profile's game time.
 pub(crate) async fn get_ndocks_impl<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<Vec<ndock::Model>, GameplayError>
 where
     C: ConnectionTrait,
@@ -251,7 +261,7 @@ where
         if model.ship_id > 0 {
             let ship_id = model.ship_id;
             if let Some(complete_time) = model.complete_time
-                && complete_time <= chrono::Utc::now()
+                && complete_time <= now
             {
                 let dock_id = model.id;
 
@@ -321,6 +331,7 @@ pub(crate) async fn ndock_start_repair_impl<C>(
     ndock_id: i64,
     ship_id: i64,
     highspeed: bool,
+    now: DateTime<Utc>,
 ) -> Result<Option<material::Model>, GameplayError>
 where
     C: ConnectionTrait,
@@ -395,9 +406,8 @@ where
             am.fuel = ActiveValue::Set(docking_cost.fuel_cost);
             am.steel = ActiveValue::Set(docking_cost.steel_cost);
             am.ship_id = ActiveValue::Set(ship_id);
-            am.complete_time = ActiveValue::Set(Some(
-                chrono::Utc::now() + chrono::Duration::seconds(docking_cost.duration_sec),
-            ));
+            am.complete_time =
+                ActiveValue::Set(Some(now + chrono::Duration::seconds(docking_cost.duration_sec)));
         }
 
         am.update(c).await?;
@@ -1,17 +1,30 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
 use async_trait::async_trait;
 use emukc_crypto::rng;
 use emukc_db::{
     entity::profile::{
-        self,
+        self, fleet, furniture,
+        item::slot_item,
         practice::{self, config::RivalType, rival_ship},
         ship,
     },
-    sea_orm::{ActiveValue, IntoActiveModel, QueryOrder, TransactionTrait, entity::prelude::*},
+    sea_orm::{
+        ActiveValue, IntoActiveModel, PaginatorTrait, QueryOrder, TransactionTrait,
+        entity::prelude::*,
+    },
 };
 use emukc_model::{
     codex::Codex,
-    kc2::{KcSortieResultRank, UserHQRank, level},
-    profile::practice::{PracticeConfig, Rival, RivalDetail, RivalFlag, RivalShip, RivalStatus},
+    kc2::{KcApiShip, KcShipType, KcSortieResultRank, UserHQRank, level},
+    prelude::ApiMstShip,
+    profile::{
+        fleet::Fleet,
+        practice::{
+            PracticeConfig, Rival, RivalDetail, RivalFlag, RivalShip, RivalStatus,
+            RivalType as RivalTypeModel,
+        },
+    },
     thirdparty::QuestActionEvent,
 };
 use emukc_time::{
@@ -91,6 +104,20 @@ pub trait PracticeOps {
         &self,
         profile_id: i64,
     ) -> Result<PracticeNightBattleResponse, GameplayError>;
+
+    /// Choose the rival group practice rivals are drawn from.
+    ///
+    /// The current rivals stay until the list is next refreshed.
+    ///
+    /// # Parameters
+    ///
+    /// - `profile_id`: The profile ID.
+    /// - `kind`: The rival group.
+    async fn change_practice_matching_kind(
+        &self,
+        profile_id: i64,
+        kind: RivalTypeModel,
+    ) -> Result<practice::config::Model, GameplayError>;
 }
 
 #[async_trait]
@@ -100,7 +127,8 @@ impl<T: HasContext + ?Sized> PracticeOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        let rivals = get_practice_rivals_impl(&tx, codex, profile_id).await?;
+        let rivals =
+            get_practice_rivals_impl(&tx, codex, profile_id, self.clock().now(profile_id)).await?;
 
         tx.commit().await?;
 
@@ -140,7 +168,7 @@ impl<T: HasContext + ?Sized> PracticeOps for T {
         }
 
         let friend_ships = build_practice_friend_ships(&tx, &friend_ships).await?;
-        let enemy_ships = build_practice_enemy_ships(codex, &rival)?;
+        let enemy_ships = build_practice_enemy_ships(&tx, codex, &rival).await?;
         let input = PracticeBattleInput {
             profile_id,
             deck_id,
@@ -234,6 +262,18 @@ impl<T: HasContext + ?Sized> PracticeOps for T {
 
         Ok(response)
     }
+
+    async fn change_practice_matching_kind(
+        &self,
+        profile_id: i64,
+        kind: RivalTypeModel,
+    ) -> Result<practice::config::Model, GameplayError> {
+        let db = self.db();
+
+        let config = change_practice_matching_kind_impl(db, profile_id, kind.into()).await?;
+
+        Ok(config)
+    }
 }
 
 fn build_practice_quest_event(
@@ -250,6 +290,7 @@ pub(crate) async fn get_practice_rivals_impl<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<PracticeInfo, GameplayError>
 where
     C: ConnectionTrait,
@@ -269,17 +310,17 @@ where
         .all(c)
         .await?;
 
-    let entry_limit = cal_practice_entry_limit();
+    let entry_limit = cal_practice_entry_limit(now);
 
     let resp = if rivals.is_empty()
-        || KcTime::is_before_or_after_jst_today_hour(&config.last_generated, 3, 15)
+        || KcTime::is_before_or_after_jst_today_hour(&now, &config.last_generated, 3, 15)
     {
         let selected_type = config.selected_type;
         let r = generate_practice_rivals(c, codex, profile_id, selected_type).await?;
 
         // update last generated time
         let mut cfg_am = config.into_active_model();
-        cfg_am.last_generated = ActiveValue::Set(Utc::now());
+        cfg_am.last_generated = ActiveValue::Set(now);
         cfg_am.generated_type = ActiveValue::Set(selected_type);
 
         let cfg = cfg_am.update(c).await?;
@@ -308,6 +349,7 @@ where
             let ships = profile::practice::rival_ship::Entity::find()
                 .filter(profile::practice::rival_ship::Column::ProfileId.eq(profile_id))
                 .filter(profile::practice::rival_ship::Column::RivalId.eq(rival.id))
+                .order_by_asc(profile::practice::rival_ship::Column::Id)
                 .all(c)
                 .await?;
 
@@ -354,6 +396,27 @@ where
     Ok(resp)
 }
 
+pub(crate) async fn change_practice_matching_kind_impl<C>(
+    c: &C,
+    profile_id: i64,
+    kind: RivalType,
+) -> Result<practice::config::Model, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let config =
+        practice::config::Entity::find_by_id(profile_id).one(c).await?.ok_or_else(|| {
+            GameplayError::EntryNotFound(format!(
+                "Practice config not found for profile {profile_id}",
+            ))
+        })?;
+
+    let mut am = config.into_active_model();
+    am.selected_type = ActiveValue::Set(kind);
+
+    Ok(am.update(c).await?)
+}
+
 pub(crate) async fn get_practice_rival_details_impl<C>(
     c: &C,
     profile_id: i64,
@@ -379,6 +442,7 @@ where
     let ships = profile::practice::rival_ship::Entity::find()
         .filter(profile::practice::rival_ship::Column::ProfileId.eq(profile_id))
         .filter(profile::practice::rival_ship::Column::RivalId.eq(rival_id))
+        .order_by_asc(profile::practice::rival_ship::Column::Id)
         .all(c)
         .await?;
 
@@ -409,85 +473,117 @@ where
     Ok(rival)
 }
 
+/// Rivals listed per refresh.
+const RIVAL_COUNT: usize = 5;
+
+/// The fleet a profile defends practice with, the first one as in the game.
+const PRACTICE_FLEET_INDEX: i64 = 1;
+
+/// How far a rival's HQ level may be from the player's.
+const RIVAL_LEVEL_SPREAD: i64 = 15;
+
+/// Compositions the generator picks from, flagship first.
+const GENERATED_FLEETS: &[(&str, &[KcShipType])] = &[
+    (
+        "Battleship Division",
+        &[
+            KcShipType::BB,
+            KcShipType::BB,
+            KcShipType::CA,
+            KcShipType::CA,
+            KcShipType::CL,
+            KcShipType::DD,
+        ],
+    ),
+    (
+        "Carrier Task Force",
+        &[
+            KcShipType::CV,
+            KcShipType::CV,
+            KcShipType::CVL,
+            KcShipType::CA,
+            KcShipType::DD,
+            KcShipType::DD,
+        ],
+    ),
+    (
+        "Cruiser Squadron",
+        &[
+            KcShipType::CA,
+            KcShipType::CA,
+            KcShipType::CAV,
+            KcShipType::CAV,
+            KcShipType::CL,
+            KcShipType::DD,
+        ],
+    ),
+    (
+        "Torpedo Squadron",
+        &[
+            KcShipType::CL,
+            KcShipType::DD,
+            KcShipType::DD,
+            KcShipType::DD,
+            KcShipType::DD,
+            KcShipType::DD,
+        ],
+    ),
+    (
+        "Submarine Flotilla",
+        &[
+            KcShipType::SS,
+            KcShipType::SS,
+            KcShipType::SS,
+            KcShipType::SS,
+            KcShipType::SS,
+            KcShipType::SS,
+        ],
+    ),
+];
+
+const GENERATED_COMMENTS: &[&str] = &[
+    "よろしくお願いします！",
+    "Practice makes perfect.",
+    "Go easy on me!",
+    "Farming exp, thanks for the match.",
+    "My girls are ready.",
+];
+
+/// A rival before it is given IDs.
+struct RivalDraft {
+    rival: Rival,
+
+    /// The ship each of the rival's ships mirrors, empty for generated rivals.
+    sources: Vec<i64>,
+}
+
 /// Generate practice rivals.
 ///
+/// Other profiles in the player's world come first, each fielding its first
+/// fleet. Generated fleets scaled to the player's HQ level fill the rest.
+///
 /// # Parameters
 ///
 /// - `c`: The database connection.
 /// - `codex`: The codex.
 /// - `profile_id`: The profile ID.
-/// - `select_type`: The rival type.
-///
-/// ## TODO
-/// 1. using normal distribution to generate 5 rivals
-/// 2. using player's level as the mean of the distribution
-///
-/// there are a few types of rivals' deck:
-///
-/// a. best formation, tier S ships, since they are farming event or boss.
-/// b. carefully hand picked ships to irradiate you, very difficult to beat.
-/// c. 1 or 2 high level ships, they want to help you to level up.
+/// - `select_type`: The rival group to draw from.
 async fn generate_practice_rivals<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
-    _select_type: RivalType,
+    select_type: RivalType,
 ) -> Result<Vec<Rival>, GameplayError>
 where
     C: ConnectionTrait,
 {
-    let mut api_mst_ship = codex.manifest.api_mst_ship.clone();
-    api_mst_ship.retain(|f| f.api_aftershipid.is_some());
-
-    let last_profile = profile::Entity::find().order_by_desc(profile::Column::Id).one(c).await?;
-    let current_uid = last_profile.map(|p| p.id).unwrap_or(0) + 1;
+    let player = find_profile(c, profile_id).await?;
 
-    let last_ship = rival_ship::Entity::find().order_by_desc(rival_ship::Column::Id).one(c).await?;
-    let current_ship_id = last_ship.map(|s| s.id).unwrap_or(0) + 1;
-
-    let rival_uid_starts_from = current_uid + 10000;
-    let rival_ship_id_starts_from = current_ship_id + 10000;
-
-    let rivals: Vec<Rival> = {
-        (1..6)
-            .map(|i| {
-                let name = format!("Practice Rival {i}");
-                let comment = format!("I am your {i}th rival");
-                let rank = rng::i64_inclusive(1..=10);
-                let flag = rng::i64_inclusive(1..=3);
-                let ship_mst = rng::choose(&api_mst_ship).unwrap();
-
-                Rival {
-                    id: rival_uid_starts_from + i,
-                    index: i,
-                    name,
-                    comment,
-                    level: 120 - i,
-                    rank: UserHQRank::n(rank).unwrap_or(UserHQRank::Admiral),
-                    flag: RivalFlag::n(flag).unwrap_or_default(),
-                    status: RivalStatus::Untouched,
-                    medals: 10 + i,
-                    details: RivalDetail {
-                        exp_now: 0,
-                        exp_next: 1000,
-                        friend: 0,
-                        current_ship_count: 299,
-                        ship_capacity: 300,
-                        current_slot_item_count: 999,
-                        slot_item_capacity: 1000,
-                        furniture: 123,
-                        deck_name: format!("Deck {i}"),
-                        ships: vec![RivalShip {
-                            id: rival_ship_id_starts_from + i,
-                            mst_id: ship_mst.api_id,
-                            level: 180,
-                            star: 10,
-                        }],
-                    },
-                }
-            })
-            .collect()
-    };
+    let mut drafts = local_practice_rivals(c, &player, select_type).await?;
+    let pool = ShipPool::new(codex);
+    while drafts.len() < RIVAL_COUNT {
+        drafts.push(generate_practice_rival(&pool, player.hq_level, select_type));
+    }
 
     // remove old records
 
@@ -506,14 +602,38 @@ where
         .exec(c)
         .await?;
 
+    // rival IDs are server-wide and kept clear of profile IDs
+    let last_profile = profile::Entity::find().order_by_desc(profile::Column::Id).one(c).await?;
+    let last_rival =
+        practice::rival::Entity::find().order_by_desc(practice::rival::Column::Id).one(c).await?;
+    let mut next_rival_id =
+        (last_profile.map_or(0, |p| p.id) + 10000).max(last_rival.map_or(0, |r| r.id)) + 1;
+
+    let last_ship = rival_ship::Entity::find().order_by_desc(rival_ship::Column::Id).one(c).await?;
+    let mut next_ship_id = last_ship.map_or(0, |s| s.id) + 1;
+
+    let mut rivals = Vec::with_capacity(drafts.len());
+
     // insert new records
-    for (i, rival) in rivals.iter().enumerate() {
+    for (i, draft) in drafts.into_iter().enumerate() {
+        let RivalDraft {
+            mut rival,
+            sources,
+        } = draft;
+        rival.id = next_rival_id;
+        rival.index = i as i64 + 1;
+        next_rival_id += 1;
+        for ship in &mut rival.details.ships {
+            ship.id = next_ship_id;
+            next_ship_id += 1;
+        }
+
         // rival
         {
             let am = profile::practice::rival::ActiveModel {
                 id: ActiveValue::Set(rival.id),
                 profile_id: ActiveValue::Set(profile_id),
-                index: ActiveValue::Set(i as i64 + 1),
+                index: ActiveValue::Set(rival.index),
                 name: ActiveValue::Set(rival.name.clone()),
                 comment: ActiveValue::Set(rival.comment.clone()),
                 level: ActiveValue::Set(rival.level),
@@ -545,7 +665,7 @@ where
         }
         // ships
         {
-            for ship in &rival.details.ships {
+            for (j, ship) in rival.details.ships.iter().enumerate() {
                 let am = profile::practice::rival_ship::ActiveModel {
                     id: ActiveValue::Set(ship.id),
                     profile_id: ActiveValue::Set(profile_id),
@@ -553,24 +673,311 @@ where
                     mst_id: ActiveValue::Set(ship.mst_id),
                     level: ActiveValue::Set(ship.level),
                     star: ActiveValue::Set(ship.star),
+                    source_ship_id: ActiveValue::Set(sources.get(j).copied()),
                 };
 
                 am.insert(c).await?;
             }
         }
+
+        rivals.push(rival);
     }
 
     Ok(rivals)
 }
 
-fn cal_practice_entry_limit() -> Option<i64> {
-    let now = Utc::now();
-    let jst_0100 = KcTime::jst_today_hour_utc(1);
+/// Whether an admiral at `rival_level` belongs to `select_type` for a player
+/// at `player_level`: the first group is level with or above the player, the
+/// second below.
+fn in_rival_group(select_type: RivalType, player_level: i64, rival_level: i64) -> bool {
+    match select_type {
+        RivalType::FirstGroup => rival_level >= player_level,
+        RivalType::SecondGroup => rival_level < player_level,
+        RivalType::All => true,
+    }
+}
+
+/// Draw rivals from the other profiles in the player's world.
+async fn local_practice_rivals<C>(
+    c: &C,
+    player: &profile::Model,
+    select_type: RivalType,
+) -> Result<Vec<RivalDraft>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mut candidates = profile::Entity::find()
+        .filter(profile::Column::Id.ne(player.id))
+        .filter(profile::Column::WorldId.eq(player.world_id))
+        .all(c)
+        .await?;
+    candidates.retain(|p| in_rival_group(select_type, player.hq_level, p.hq_level));
+    rng::shuffle(&mut candidates);
+
+    let mut drafts = Vec::new();
+    for candidate in &candidates {
+        if drafts.len() >= RIVAL_COUNT {
+            break;
+        }
+        if let Some(draft) = local_practice_rival(c, candidate).await? {
+            drafts.push(draft);
+        }
+    }
+
+    Ok(drafts)
+}
+
+/// A profile as a rival, `None` if its practice fleet is empty.
+async fn local_practice_rival<C>(
+    c: &C,
+    profile: &profile::Model,
+) -> Result<Option<RivalDraft>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let Some(fleet) = fleet::Entity::find()
+        .filter(fleet::Column::ProfileId.eq(profile.id))
+        .filter(fleet::Column::Index.eq(PRACTICE_FLEET_INDEX))
+        .one(c)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let deck_name = fleet.name.clone();
+    let ship_ids = Fleet::from(fleet).ships;
+    let mut ships = ship::Entity::find()
+        .filter(ship::Column::ProfileId.eq(profile.id))
+        .filter(ship::Column::Id.is_in(ship_ids))
+        .all(c)
+        .await?;
+    if ships.is_empty() {
+        return Ok(None);
+    }
+    ships.sort_by_key(|ship| ship_ids.iter().position(|&id| id == ship.id));
+
+    let current_ship_count =
+        ship::Entity::find().filter(ship::Column::ProfileId.eq(profile.id)).count(c).await? as i64;
+    let current_slot_item_count = slot_item::Entity::find()
+        .filter(slot_item::Column::ProfileId.eq(profile.id))
+        .count(c)
+        .await? as i64;
+    let furniture = furniture::record::Entity::find()
+        .filter(furniture::record::Column::ProfileId.eq(profile.id))
+        .count(c)
+        .await? as i64;
+
+    let rank = UserHQRank::n(profile.hq_rank).unwrap_or_default();
+    let (_, exp_next) = level::exp_to_hq_level(profile.experience);
+
+    Ok(Some(RivalDraft {
+        rival: Rival {
+            id: 0,
+            index: 0,
+            name: profile.name.clone(),
+            comment: profile.comment.clone(),
+            level: profile.hq_level,
+            rank,
+            flag: rival_flag(rank),
+            status: RivalStatus::Untouched,
+            medals: profile.medals,
+            details: RivalDetail {
+                exp_now: profile.experience,
+                exp_next,
+                friend: 0,
+                current_ship_count,
+                ship_capacity: profile.max_ship_capacity,
+                current_slot_item_count,
+                slot_item_capacity: profile.max_equipment_capacity,
+                furniture,
+                deck_name,
+                ships: ships
+                    .iter()
+                    .map(|ship| RivalShip {
+                        id: 0,
+                        mst_id: ship.mst_id,
+                        level: ship.level,
+                        star: ship.srate + 1,
+                    })
+                    .collect(),
+            },
+        },
+        sources: ships.iter().map(|ship| ship.id).collect(),
+    }))
+}
+
+/// Flag shown next to a rival, by HQ rank.
+fn rival_flag(rank: UserHQRank) -> RivalFlag {
+    match rank {
+        UserHQRank::MarshalAdmiral | UserHQRank::Admiral => RivalFlag::Gold,
+        UserHQRank::ViceAdmiral | UserHQRank::RearAdmiral | UserHQRank::Captain => {
+            RivalFlag::Silver
+        }
+        _ => RivalFlag::Bronze,
+    }
+}
+
+/// HQ levels a generated rival of `select_type` is drawn from.
+fn rival_level_range(select_type: RivalType, player_level: i64) -> RangeInclusive<i64> {
+    let low = (player_level - RIVAL_LEVEL_SPREAD).max(1);
+    let high = (player_level + RIVAL_LEVEL_SPREAD).min(level::MAX_HQ_LEVEL);
+    match select_type {
+        RivalType::FirstGroup => player_level.min(high)..=high,
+        RivalType::SecondGroup => low..=(player_level - 1).max(low),
+        RivalType::All => low..=high,
+    }
+}
+
+/// HQ rank a generated rival at `level` plausibly holds.
+fn generated_rival_rank(level: i64) -> UserHQRank {
+    match level {
+        ..=20 => UserHQRank::JuniorLieutenantCommander,
+        21..=40 => UserHQRank::LieutenantCommander,
+        41..=60 => UserHQRank::Commander,
+        61..=80 => UserHQRank::Captain,
+        81..=99 => UserHQRank::RearAdmiral,
+        100..=110 => UserHQRank::ViceAdmiral,
+        _ => UserHQRank::Admiral,
+    }
+}
+
+/// Playable ship forms with the level each is first reached at.
+struct ShipPool<'a> {
+    ships: Vec<&'a ApiMstShip>,
+    required_level: HashMap<i64, i64>,
+}
+
+impl<'a> ShipPool<'a> {
+    fn new(codex: &'a Codex) -> Self {
+        let ships = codex.manifest.friend_ships();
+        let mut required_level: HashMap<i64, i64> = HashMap::new();
+        for mst in &ships {
+            if let (Some(after), Some(lv)) = (Self::after_ship_id(mst), mst.api_afterlv) {
+                let required = required_level.entry(after).or_insert(lv);
+                *required = (*required).min(lv);
+            }
+        }
+
+        Self {
+            ships,
+            required_level,
+        }
+    }
+
+    fn after_ship_id(mst: &ApiMstShip) -> Option<i64> {
+        mst.api_aftershipid.as_deref().and_then(|id| id.parse().ok()).filter(|&id| id > 0)
+    }
+
+    fn required_level(&self, mst_id: i64) -> i64 {
+        self.required_level.get(&mst_id).copied().unwrap_or(1)
+    }
+
+    /// Whether a ship at `level` would already have been remodeled past `mst`.
+    fn outgrown(&self, mst: &ApiMstShip, level: i64) -> bool {
+        let Some(after) = Self::after_ship_id(mst) else {
+            return false;
+        };
+        // converting back and forth does not count
+        mst.api_afterlv.is_some_and(|lv| lv <= level)
+            && self.required_level(after) > self.required_level(mst.api_id)
+    }
+
+    /// A `ship_type` ship in the form it would have at `level`, other than
+    /// the ships in `taken`.
+    fn pick(&self, ship_type: KcShipType, level: i64, taken: &[&str]) -> Option<&'a ApiMstShip> {
+        let candidates: Vec<&ApiMstShip> = self
+            .ships
+            .iter()
+            .copied()
+            .filter(|mst| {
+                KcShipType::n(mst.api_stype) == Some(ship_type)
+                    && self.required_level(mst.api_id) <= level
+                    && !self.outgrown(mst, level)
+                    && !taken.contains(&mst.api_yomi.as_str())
+            })
+            .collect();
+        rng::choose(&candidates).copied()
+    }
+}
+
+/// Generate a rival whose fleet and ship levels fit the player's HQ level.
+fn generate_practice_rival(
+    pool: &ShipPool<'_>,
+    player_level: i64,
+    select_type: RivalType,
+) -> RivalDraft {
+    let hq_level = rng::i64_inclusive(rival_level_range(select_type, player_level));
+    let (deck_name, ship_types) = rng::choose(GENERATED_FLEETS).unwrap();
+    let fleet_size = ((2 + hq_level / 20) as usize).min(ship_types.len());
+    let level_cap = if hq_level >= 100 {
+        level::ship_level_cap(true)
+    } else {
+        level::ship_level_cap(false)
+    };
+
+    let mut taken: Vec<&str> = Vec::new();
+    let mut ships = Vec::with_capacity(fleet_size);
+    for (i, ship_type) in ship_types.iter().take(fleet_size).enumerate() {
+        // flagships are usually the best trained
+        let spread = if i == 0 {
+            rng::f64_range(1.0, 1.2)
+        } else {
+            rng::f64_range(0.7, 1.1)
+        };
+        let ship_level = ((hq_level as f64 * spread).round() as i64).clamp(1, level_cap);
+        let Some(mst) = pool
+            .pick(*ship_type, ship_level, &taken)
+            .or_else(|| pool.pick(KcShipType::DD, ship_level, &taken))
+        else {
+            continue;
+        };
+        taken.push(&mst.api_yomi);
+        ships.push(RivalShip {
+            id: 0,
+            mst_id: mst.api_id,
+            level: ship_level,
+            star: (ship_level / 40 + 1).min(5),
+        });
+    }
+
+    let rank = generated_rival_rank(hq_level);
+    let exp_now = level::hq_level_required_exp(hq_level);
+    let (_, exp_next) = level::exp_to_hq_level(exp_now);
+
+    RivalDraft {
+        rival: Rival {
+            id: 0,
+            index: 0,
+            name: format!("提督{:04}", rng::i64(0..10000)),
+            comment: (*rng::choose(GENERATED_COMMENTS).unwrap()).to_owned(),
+            level: hq_level,
+            rank,
+            flag: rival_flag(rank),
+            status: RivalStatus::Untouched,
+            medals: rng::i64_inclusive(0..=hq_level / 10),
+            details: RivalDetail {
+                exp_now,
+                exp_next,
+                friend: 0,
+                current_ship_count: 100 + hq_level * 2,
+                ship_capacity: 100 + hq_level * 3,
+                current_slot_item_count: 400 + hq_level * 8,
+                slot_item_capacity: 500 + hq_level * 12,
+                furniture: 20 + hq_level,
+                deck_name: (*deck_name).to_owned(),
+                ships,
+            },
+        },
+        sources: Vec::new(),
+    }
+}
+
+fn cal_practice_entry_limit(now: DateTime<Utc>) -> Option<i64> {
+    let jst_0100 = KcTime::jst_today_hour_utc(&now, 1);
 
     if now < jst_0100 {
         return None;
     }
-    let jst_1300 = KcTime::jst_today_hour_utc(13);
+    let jst_1300 = KcTime::jst_today_hour_utc(&now, 13);
     if now < jst_1300 {
         return Some((jst_1300.timestamp_millis() - now.timestamp_millis()) / 1000);
     }
@@ -612,31 +1019,96 @@ where
     Ok(result)
 }
 
-fn build_practice_enemy_ships(
+/// Build the rival's fleet for battle.
+///
+/// Ships mirroring a local profile's fleet fight with that ship's equipment
+/// and modernization, fully repaired and resupplied. Generated ships, and
+/// mirrored ones that have since left their fleet's owner, are built fresh
+/// from the codex.
+async fn build_practice_enemy_ships<C>(
+    c: &C,
     codex: &Codex,
     rival: &Rival,
-) -> Result<Vec<PracticeBattleShipInput>, GameplayError> {
-    rival
-        .details
-        .ships
-        .iter()
-        .map(|ship| {
-            let (mut api_ship, slot_items) =
-                codex.new_ship(ship.mst_id).ok_or(GameplayError::ManifestNotFound(ship.mst_id))?;
-            let exp_now = level::ship_level_required_exp(ship.level);
-            let (_, next_exp) = level::exp_to_ship_level(exp_now);
-            api_ship.api_lv = ship.level;
-            api_ship.api_exp = [exp_now, next_exp, 0];
-            codex.cal_ship_status(&mut api_ship, &slot_items, false)?;
-
-            Ok(PracticeBattleShipInput {
-                ship: api_ship,
-                slot_items,
-                effect_list: vec![0],
-                married: false,
-            })
-        })
-        .collect()
+) -> Result<Vec<PracticeBattleShipInput>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let sources: HashMap<i64, i64> = rival_ship::Entity::find()
+        .filter(rival_ship::Column::RivalId.eq(rival.id))
+        .all(c)
+        .await?
+        .into_iter()
+        .filter_map(|ship| ship.source_ship_id.map(|source| (ship.id, source)))
+        .collect();
+
+    let mut result = Vec::with_capacity(rival.details.ships.len());
+    for ship in &rival.details.ships {
+        let mirrored = match sources.get(&ship.id) {
+            Some(source) => ship::Entity::find_by_id(*source).one(c).await?,
+            None => None,
+        };
+        let input = match mirrored {
+            Some(model) => build_mirrored_enemy_ship(c, codex, &model).await?,
+            None => build_generated_enemy_ship(codex, ship)?,
+        };
+        result.push(input);
+    }
+
+    Ok(result)
+}
+
+async fn build_mirrored_enemy_ship<C>(
+    c: &C,
+    codex: &Codex,
+    model: &ship::Model,
+) -> Result<PracticeBattleShipInput, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let mst = codex.find::<ApiMstShip>(&model.mst_id)?;
+    let slot_ids =
+        [model.slot_1, model.slot_2, model.slot_3, model.slot_4, model.slot_5, model.slot_ex]
+            .into_iter()
+            .filter(|slot_id| *slot_id > 0)
+            .collect::<Vec<_>>();
+    let slot_items = find_slot_items_by_id_impl(c, &slot_ids).await?;
+    let slot_items = slot_items.into_iter().map(std::convert::Into::into).collect();
+
+    let mut api_ship: KcApiShip = (*model).into();
+    api_ship.api_nowhp = api_ship.api_maxhp;
+    api_ship.api_fuel = mst.api_fuel_max.unwrap_or(0);
+    api_ship.api_bull = mst.api_bull_max.unwrap_or(0);
+    if let Some(maxeq) = mst.api_maxeq {
+        api_ship.api_onslot = maxeq;
+    }
+    api_ship.api_cond = api_ship.api_cond.max(49);
+
+    Ok(PracticeBattleShipInput {
+        ship: api_ship,
+        slot_items,
+        effect_list: vec![0],
+        married: model.married,
+    })
+}
+
+fn build_generated_enemy_ship(
+    codex: &Codex,
+    ship: &RivalShip,
+) -> Result<PracticeBattleShipInput, GameplayError> {
+    let (mut api_ship, slot_items) =
+        codex.new_ship(ship.mst_id).ok_or(GameplayError::ManifestNotFound(ship.mst_id))?;
+    let exp_now = level::ship_level_required_exp(ship.level);
+    let (_, next_exp) = level::exp_to_ship_level(exp_now);
+    api_ship.api_lv = ship.level;
+    api_ship.api_exp = [exp_now, next_exp, 0];
+    codex.cal_ship_status(&mut api_ship, &slot_items, false)?;
+
+    Ok(PracticeBattleShipInput {
+        ship: api_ship,
+        slot_items,
+        effect_list: vec![0],
+        married: false,
+    })
 }
 
 async fn update_practice_result_stats<C>(
@@ -808,3 +1280,90 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mst(id: i64, stype: KcShipType, yomi: &str, after: i64, after_lv: i64) -> ApiMstShip {
+        ApiMstShip {
+            api_id: id,
+            api_stype: stype as i64,
+            api_yomi: yomi.to_owned(),
+            api_aftershipid: Some(after.to_string()),
+            api_afterlv: Some(after_lv),
+            ..Default::default()
+        }
+    }
+
+    fn codex() -> Codex {
+        let mut codex = Codex::default();
+        codex.manifest.api_mst_ship = vec![
+            mst(1, KcShipType::DD, "fubuki", 2, 20),
+            mst(2, KcShipType::DD, "fubuki", 0, 0),
+            mst(3, KcShipType::DD, "shirayuki", 0, 0),
+            // converts back and forth once remodeled
+            mst(4, KcShipType::CVL, "chitose", 5, 30),
+            mst(5, KcShipType::CVL, "chitose", 4, 1),
+        ];
+        codex
+    }
+
+    #[test]
+    fn ship_pool_fields_the_form_a_level_reaches() {
+        let codex = codex();
+        let pool = ShipPool::new(&codex);
+        let picks = |ship_type, level, taken: &[&str]| {
+            let mut ids: Vec<i64> = (0..64)
+                .filter_map(|_| pool.pick(ship_type, level, taken))
+                .map(|m| m.api_id)
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids
+        };
+
+        assert_eq!(picks(KcShipType::DD, 10, &[]), vec![1, 3]);
+        assert_eq!(picks(KcShipType::DD, 30, &[]), vec![2, 3]);
+        assert_eq!(picks(KcShipType::DD, 30, &["shirayuki"]), vec![2]);
+        assert_eq!(picks(KcShipType::CVL, 10, &[]), vec![4]);
+        assert_eq!(picks(KcShipType::CVL, 40, &[]), vec![5]);
+        assert!(picks(KcShipType::BB, 99, &[]).is_empty());
+    }
+
+    #[test]
+    fn rival_groups_split_at_player_level() {
+        assert_eq!(rival_level_range(RivalType::FirstGroup, 50), 50..=65);
+        assert_eq!(rival_level_range(RivalType::SecondGroup, 50), 35..=49);
+        assert_eq!(rival_level_range(RivalType::All, 110), 95..=120);
+        assert_eq!(rival_level_range(RivalType::SecondGroup, 1), 1..=1);
+        assert_eq!(rival_level_range(RivalType::FirstGroup, 120), 120..=120);
+
+        assert!(in_rival_group(RivalType::FirstGroup, 50, 50));
+        assert!(!in_rival_group(RivalType::SecondGroup, 50, 50));
+        assert!(in_rival_group(RivalType::All, 50, 1));
+    }
+
+    #[test]
+    fn generated_rival_fits_player_level() {
+        let codex = codex();
+        let pool = ShipPool::new(&codex);
+        for _ in 0..32 {
+            let draft = generate_practice_rival(&pool, 30, RivalType::FirstGroup);
+            let rival = draft.rival;
+            assert!((30..=45).contains(&rival.level));
+            assert!(draft.sources.is_empty());
+            assert_eq!(level::exp_to_hq_level(rival.details.exp_now).0, rival.level);
+            let mut yomi: Vec<_> = rival
+                .details
+                .ships
+                .iter()
+                .map(|ship| codex.manifest.find_ship(ship.mst_id).unwrap().api_yomi.clone())
+                .collect();
+            let count = yomi.len();
+            yomi.sort();
+            yomi.dedup();
+            assert_eq!(yomi.len(), count, "a ship is fielded twice");
+        }
+    }
+}
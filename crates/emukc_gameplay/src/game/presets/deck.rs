@@ -11,7 +11,7 @@ use emukc_model::{
 
 use crate::err::GameplayError;
 
-use super::{deduct_use_item_impl, get_fleets_impl, update_fleet_ships_impl};
+use super::{deduct_use_item_impl, find_fleets, update_fleet_ships_impl};
 
 pub(crate) async fn get_preset_decks_impl<C>(
     c: &C,
@@ -158,7 +158,7 @@ where
             ))
         })?;
 
-    let fleets = get_fleets_impl(c, profile_id).await?;
+    let fleets = find_fleets(c, profile_id).await?;
 
     let other_fleet_ships: Vec<i64> = fleets
         .iter()
@@ -31,7 +31,7 @@ use slot::{
 use crate::{err::GameplayError, gameplay::HasContext};
 
 use super::{
-    fleet::{get_fleets_impl, update_fleet_ships_impl},
+    fleet::{find_fleets, update_fleet_ships_impl},
     slot_item::get_unset_slot_items_impl,
     use_item::deduct_use_item_impl,
 };
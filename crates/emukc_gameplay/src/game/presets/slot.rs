@@ -251,6 +251,9 @@ where
                 unreachable!()
             }
         };
+        if target_mst_id > 0 {
+            codex.check_equip(ship.mst_id, target_mst_id, target_stars, false)?;
+        }
 
         let Some(available) = unset_mst_lv_lookup.get(&target_mst_id) else {
             continue;
@@ -287,6 +290,10 @@ where
 
     let ship_has_exslot = ship.slot_ex != 0;
     let (ex_mst_id, ex_level) = (record.mst_id_ex, record.stars_ex);
+    if ship_has_exslot && let Some(ex_available) = unset_mst_lv_lookup.get_mut(&ex_mst_id) {
+        // an extra slot grant may need an improvement level, so offer only the items that fit
+        ex_available.retain(|m| codex.check_equip(ship.mst_id, m.mst_id, m.level, true).is_ok());
+    }
     let new_ex_id = if let Some(ex_available) = unset_mst_lv_lookup.get(&ex_mst_id) {
         if ex_mst_id > 0 && ship_has_exslot {
             match mode {
@@ -187,14 +187,15 @@ impl<T: HasContext + ?Sized> QuestOps for T {
         let db = self.db();
         let mut tx = db.begin().await?;
 
-        if update_quests_impl(&tx, codex, profile_id).await? {
+        if update_quests_impl(&tx, codex, profile_id, self.clock().now(profile_id)).await? {
             tx.commit().await?;
 
             tx = db.begin().await?;
         }
 
         // Validate composition quests
-        update::validate_composition_quests(&tx, codex, profile_id).await?;
+        update::validate_composition_quests(&tx, codex, profile_id, self.clock().now(profile_id))
+            .await?;
         tx.commit().await?;
 
         tx = db.begin().await?;
@@ -234,7 +235,7 @@ impl<T: HasContext + ?Sized> QuestOps for T {
             period: ActiveValue::Set(
                 quest_manifest.period.try_into().expect("unknown period filtered upstream"),
             ),
-            start_since: ActiveValue::Set(chrono::Utc::now()),
+            start_since: ActiveValue::Set(self.clock().now(profile_id)),
             requirements: ActiveValue::Set(serde_json::to_value(requirements).unwrap()),
             requirement_type: ActiveValue::Set(typ),
         };
@@ -252,7 +253,8 @@ impl<T: HasContext + ?Sized> QuestOps for T {
         let tx = db.begin().await?;
 
         update_quest_status(&tx, profile_id, quest_id, Status::Activated, Some(codex)).await?;
-        update::validate_composition_quests(&tx, codex, profile_id).await?;
+        update::validate_composition_quests(&tx, codex, profile_id, self.clock().now(profile_id))
+            .await?;
 
         tx.commit().await?;
 
@@ -302,7 +304,8 @@ impl<T: HasContext + ?Sized> QuestOps for T {
         }
 
         // mark as completed
-        mark_quest_as_completed(&tx, profile_id, quest_id, quest.period).await?;
+        let now = self.clock().now(profile_id);
+        mark_quest_as_completed(&tx, profile_id, quest_id, quest.period, now).await?;
 
         // remove quest progress
         {
@@ -315,7 +318,7 @@ impl<T: HasContext + ?Sized> QuestOps for T {
         // reconstruct quest tree
         // this will be called by mainjs, but we do it here to ensure consistency
         let codex = self.codex();
-        update_quests_impl(&tx, codex, profile_id).await?;
+        update_quests_impl(&tx, codex, profile_id, now).await?;
 
         let quest_mst = Kc3rdQuest::find_in_codex(codex, &quest_id)?;
         // deduct requirements
@@ -452,7 +455,8 @@ pub(super) async fn init<C>(c: &C, codex: &Codex, profile_id: i64) -> Result<(),
 where
     C: ConnectionTrait,
 {
-    update_quests_impl(c, codex, profile_id).await?;
+    // a new profile starts on the system time
+    update_quests_impl(c, codex, profile_id, chrono::Utc::now()).await?;
 
     Ok(())
 }
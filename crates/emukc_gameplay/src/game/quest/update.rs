@@ -12,7 +12,7 @@ use emukc_model::{
     profile::quest::QuestProgressStatus,
     thirdparty::QuestActionEvent,
 };
-use emukc_time::chrono;
+use emukc_time::chrono::{DateTime, Utc};
 
 use crate::err::GameplayError;
 
@@ -20,6 +20,7 @@ pub(crate) async fn update_quests_impl<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<bool, GameplayError>
 where
     C: ConnectionTrait,
@@ -40,7 +41,7 @@ where
         periodic::Entity::find().filter(periodic::Column::ProfileId.eq(profile_id)).all(c).await?;
 
     for quest in periodic_quests {
-        if quest.should_reset() {
+        if quest.should_reset(&now) {
             should_commit = true;
             quest.delete(c).await?;
         } else {
@@ -55,7 +56,7 @@ where
     let mut in_progress_quest_id: Vec<i64> = Vec::new();
 
     for quest in in_progress_quests.iter() {
-        if quest.should_reset() {
+        if quest.should_reset(&now) {
             should_commit = true;
             progress::Entity::delete_by_id(quest.id).exec(c).await?;
         } else {
@@ -70,7 +71,7 @@ where
 
     // reconstruct quest tree
     let new_quests =
-        reconstruct_quest_tree(codex, profile_id, &completed_quest_id, &in_progress_quest_id)
+        reconstruct_quest_tree(codex, profile_id, &completed_quest_id, &in_progress_quest_id, now)
             .await?;
 
     if !new_quests.is_empty() {
@@ -128,6 +129,7 @@ async fn reconstruct_quest_tree(
     profile_id: i64,
     completed_quest_id: &[i64],
     in_progress_quest_id: &[i64],
+    now: DateTime<Utc>,
 ) -> Result<Vec<progress::ActiveModel>, GameplayError> {
     let new_quests: Vec<progress::ActiveModel> = codex
         .quest
@@ -160,7 +162,7 @@ async fn reconstruct_quest_tree(
                 period: ActiveValue::Set(
                     quest.period.try_into().expect("unknown period filtered upstream"),
                 ),
-                start_since: ActiveValue::Set(chrono::Utc::now()),
+                start_since: ActiveValue::Set(now),
                 requirement_type: ActiveValue::Set(requirement_type),
                 requirements: ActiveValue::Set(serde_json::to_value(conditions).unwrap()),
             })
@@ -344,11 +346,12 @@ pub(crate) async fn validate_composition_quests<C>(
     c: &C,
     codex: &Codex,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
 {
-    use crate::game::fleet::get_fleets_impl;
+    use crate::game::fleet::find_fleets;
     use crate::game::ship::get_ships_impl;
     use emukc_model::thirdparty::composition::{ShipInstance, validate_composition};
 
@@ -360,8 +363,8 @@ where
         .await?;
 
     // Load fleets and ships
-    let fleets = get_fleets_impl(c, profile_id).await?;
-    let (ships, _) = get_ships_impl(c, profile_id).await?;
+    let fleets = find_fleets(c, profile_id).await?;
+    let (ships, _) = get_ships_impl(c, profile_id, now).await?;
 
     // Convert to ShipInstance
     let ship_instances: Vec<ShipInstance> = ships
@@ -0,0 +1,344 @@
+//! Monte-Carlo route simulation.
+//!
+//! Sails a fleet built from master data through a map stage many times, using
+//! the same routing rules as a real sortie, and tallies where it goes. Where the
+//! player picks the route, every pick is taken equally often. Rolls draw from
+//! the thread-local RNG, so seed it with [`emukc_crypto::rng::seed`] for a
+//! reproducible run.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use emukc_crypto::rng;
+use emukc_model::codex::{Codex, map::MapStageDefinition};
+use serde::Serialize;
+
+use crate::err::GameplayError;
+
+use super::map_route::{
+    FleetRouteContext, FleetRouteShipStats, RouteDecision, cell_has_routing_outgoing,
+    evaluate_route_decision, route_rule_label, select_player_route,
+};
+
+/// `decided_by` label of a destination the player picks (能動分岐).
+const PLAYER_CHOICE_LABEL: &str = "player choice";
+
+/// One ship of a simulated fleet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteSimShip {
+    /// Ship master id.
+    pub mst_id: i64,
+
+    /// Ship level, which scales the base `LoS`.
+    pub level: i64,
+
+    /// Master ids of the equipped items, extra slot included.
+    pub slot_items: Vec<i64>,
+}
+
+/// Tallies of a route simulation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RouteSimulation {
+    /// Number of simulated sorties.
+    pub runs: u64,
+
+    /// Sorties that entered each cell, keyed by cell number.
+    pub arrivals: BTreeMap<i64, u64>,
+
+    /// Departures per branching cell, keyed by the departing cell number.
+    pub branches: BTreeMap<i64, RouteSimBranches>,
+
+    /// Sorties that stopped at a cell because routing failed, with the error.
+    pub failures: BTreeMap<i64, RouteSimFailure>,
+}
+
+/// How sorties left one cell.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RouteSimBranches {
+    /// Departures that had to pick a next cell at random because
+    /// `Unknown`/`SourceUnknown` rules could not be evaluated.
+    pub random_fallbacks: u64,
+
+    /// Departures per destination cell number.
+    pub targets: BTreeMap<i64, RouteSimTarget>,
+}
+
+/// Departures from one cell to one destination.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RouteSimTarget {
+    /// Number of departures.
+    pub count: u64,
+
+    /// Labels of the rules that sent the fleet here.
+    pub decided_by: BTreeSet<String>,
+}
+
+/// Sorties that could not leave a cell.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RouteSimFailure {
+    /// Number of sorties stopped here.
+    pub count: u64,
+
+    /// The routing error.
+    pub error: String,
+}
+
+impl RouteSimulation {
+    /// Share of sorties that entered `cell_no`.
+    pub fn arrival_rate(&self, cell_no: i64) -> f64 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        self.arrivals.get(&cell_no).copied().unwrap_or_default() as f64 / self.runs as f64
+    }
+}
+
+/// Simulate `runs` sorties of `fleet` through `stage`.
+///
+/// # Arguments
+///
+/// * `codex` - The codex the fleet's master data comes from.
+/// * `stage` - The map stage to sail.
+/// * `fleet` - The fleet, flagship first.
+/// * `hq_level` - HQ level, used by the formula 3 `LoS` penalty.
+/// * `runs` - Number of simulated sorties.
+pub fn simulate_routes(
+    codex: &Codex,
+    stage: &MapStageDefinition,
+    fleet: &[RouteSimShip],
+    hq_level: i64,
+    runs: u64,
+) -> Result<RouteSimulation, GameplayError> {
+    if fleet.is_empty() {
+        return Err(GameplayError::WrongType("fleet has no ships".to_string()));
+    }
+    let ships =
+        fleet.iter().map(|ship| route_ship_stats(codex, ship)).collect::<Result<Vec<_>, _>>()?;
+
+    simulate_context(stage, &FleetRouteContext::from_ships(&ships, hq_level), runs)
+}
+
+fn simulate_context(
+    stage: &MapStageDefinition,
+    base_context: &FleetRouteContext,
+    runs: u64,
+) -> Result<RouteSimulation, GameplayError> {
+    let sources = stage.start_source_cells();
+    if sources.is_empty() {
+        return Err(GameplayError::EntryNotFound("start source cell not found".to_string()));
+    }
+    // a sortie never enters a cell twice, so this bounds every route
+    let max_hops = stage.cells.len() + 1;
+
+    let mut simulation = RouteSimulation {
+        runs,
+        ..Default::default()
+    };
+    for _ in 0..runs {
+        let mut current = sources[rng::usize(0..sources.len())];
+        let mut context = base_context.clone();
+        context.visited_cell_ids.insert(current.cell_no);
+
+        for _ in 0..max_hops {
+            if !cell_has_routing_outgoing(current.cell_no, stage) {
+                break;
+            }
+            let decision = if current.select_cells.is_empty() {
+                evaluate_route_decision(current, stage, &context, None)
+            } else {
+                // the player could go either way, so try each pick equally often
+                let pick = current.select_cells[rng::usize(0..current.select_cells.len())];
+                select_player_route(current, Some(pick)).map(|cell_no| RouteDecision {
+                    cell_no,
+                    rules: Vec::new(),
+                    random_fallback: false,
+                })
+            };
+            let decision = match decision {
+                Ok(decision) => decision,
+                Err(err) => {
+                    let failure = simulation.failures.entry(current.cell_no).or_default();
+                    failure.count += 1;
+                    failure.error = err.to_string();
+                    break;
+                }
+            };
+
+            if current.next_cells.len() > 1 || !decision.rules.is_empty() {
+                let branches = simulation.branches.entry(current.cell_no).or_default();
+                if decision.random_fallback {
+                    branches.random_fallbacks += 1;
+                }
+                let target = branches.targets.entry(decision.cell_no).or_default();
+                target.count += 1;
+                target.decided_by.extend(decision.rules.iter().map(|rule| route_rule_label(rule)));
+                if !current.select_cells.is_empty() {
+                    target.decided_by.insert(PLAYER_CHOICE_LABEL.to_string());
+                }
+            }
+
+            *simulation.arrivals.entry(decision.cell_no).or_default() += 1;
+            context.visited_cell_ids.insert(decision.cell_no);
+            let Some(next) = stage.cell(decision.cell_no) else {
+                break;
+            };
+            current = next;
+        }
+    }
+
+    Ok(simulation)
+}
+
+/// Route stats of a ship at its level, carrying `ship.slot_items`.
+fn route_ship_stats(
+    codex: &Codex,
+    ship: &RouteSimShip,
+) -> Result<FleetRouteShipStats, GameplayError> {
+    let mst = codex
+        .manifest
+        .find_ship(ship.mst_id)
+        .ok_or_else(|| GameplayError::EntryNotFound(format!("ship {} not found", ship.mst_id)))?;
+    let base_los = codex.ship_extra.get(&ship.mst_id).map_or(0, |extra| {
+        let [base, max] = extra.saku;
+        (max - base) * ship.level / 99 + base
+    });
+
+    let mut speed = mst.api_soku;
+    let mut los_now = base_los;
+    let mut equipment = Vec::with_capacity(ship.slot_items.len());
+    for mst_id in &ship.slot_items {
+        let item = codex
+            .manifest
+            .find_slotitem(*mst_id)
+            .ok_or_else(|| GameplayError::EntryNotFound(format!("slot item {mst_id} not found")))?;
+        speed += item.api_soku;
+        los_now += item.api_saku;
+        equipment.push((item.api_type[2], item.api_id, item.api_saku));
+    }
+
+    Ok(FleetRouteShipStats {
+        ship_id: ship.mst_id,
+        ship_type: Some(mst.api_stype),
+        speed,
+        los_now,
+        equipment,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use emukc_model::codex::map::{
+        MapCellDefinition, RouteOperator, RoutePredicate, RouteRule, SpeedClass,
+    };
+
+    use super::*;
+
+    fn cell(cell_no: i64, next_cells: Vec<i64>) -> MapCellDefinition {
+        MapCellDefinition {
+            cell_no,
+            next_cells,
+            ..Default::default()
+        }
+    }
+
+    fn rule(from_cell_no: i64, to_cell_no: i64, predicate: RoutePredicate) -> RouteRule {
+        RouteRule {
+            from_cell_no,
+            to_cell_no,
+            predicate,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn simulate_routes_tallies_branches_and_fallbacks() {
+        // 0 → 1, then 1 branches on speed to 2 or 3, and 2 splits on an
+        // unparsed condition between 4 and 5
+        let mut routing_rules = BTreeMap::new();
+        routing_rules.insert(
+            1,
+            vec![
+                RouteRule {
+                    raw_text: "fast fleet".to_string(),
+                    ..rule(
+                        1,
+                        2,
+                        RoutePredicate::Speed {
+                            class: SpeedClass::Fast,
+                        },
+                    )
+                },
+                RouteRule {
+                    priority: 1,
+                    ..rule(
+                        1,
+                        3,
+                        RoutePredicate::FleetSize {
+                            op: RouteOperator::Gte,
+                            value: 1,
+                        },
+                    )
+                },
+            ],
+        );
+        routing_rules.insert(
+            2,
+            vec![
+                rule(
+                    2,
+                    4,
+                    RoutePredicate::SourceUnknown {
+                        raw_text: "?".to_string(),
+                    },
+                ),
+                rule(
+                    2,
+                    5,
+                    RoutePredicate::SourceUnknown {
+                        raw_text: "?".to_string(),
+                    },
+                ),
+            ],
+        );
+        let stage = MapStageDefinition {
+            cells: vec![
+                cell(0, vec![1]),
+                cell(1, vec![2, 3]),
+                cell(2, vec![4, 5]),
+                cell(3, vec![]),
+                cell(4, vec![]),
+                cell(5, vec![]),
+            ],
+            routing_rules,
+            ..Default::default()
+        };
+        let fast = FleetRouteContext::from_ships(
+            &[FleetRouteShipStats {
+                ship_id: 1,
+                ship_type: Some(2),
+                speed: 10,
+                ..Default::default()
+            }],
+            1,
+        );
+        let runs = 2_000;
+
+        rng::seed(19);
+        let simulation = simulate_context(&stage, &fast, runs).unwrap();
+        rng::reseed_from_entropy();
+
+        assert_eq!(simulation.arrival_rate(1), 1.0);
+        assert_eq!(simulation.arrival_rate(2), 1.0);
+        assert_eq!(simulation.arrival_rate(3), 0.0);
+        let rate = simulation.arrival_rate(4);
+        assert!((0.4..0.6).contains(&rate), "rate {rate}");
+        assert_eq!(
+            simulation.branches[&1].targets[&2].decided_by,
+            BTreeSet::from(["fast fleet".to_string()])
+        );
+        assert_eq!(simulation.branches[&1].random_fallbacks, 0);
+        assert_eq!(simulation.branches[&2].random_fallbacks, runs);
+        assert!(simulation.branches[&2].targets[&4].decided_by.is_empty());
+    }
+}
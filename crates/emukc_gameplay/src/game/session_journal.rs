@@ -0,0 +1,179 @@
+//! Append-only journal backing the runtime session stores.
+//!
+//! Every change to a journaled store is appended as one JSON line, so the
+//! store can be rebuilt after a restart by replaying the file. Opening a
+//! journal compacts it: the store writes back only the state that survived,
+//! and sessions whose profile has been idle longer than the TTL are dropped.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use emukc_time::chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// How long an untouched session survives a restart.
+pub const DEFAULT_SESSION_TTL: Duration = Duration::hours(24);
+
+/// One journal line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct JournalEntry<O> {
+    pub at: DateTime<Utc>,
+    pub profile_id: i64,
+    pub op: O,
+}
+
+pub(crate) struct SessionJournal {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl SessionJournal {
+    /// Open the journal at `path`, creating it if missing.
+    ///
+    /// # Returns
+    ///
+    /// The journal and its entries, minus those of profiles idle since
+    /// before `now - ttl`. Lines that do not parse, such as one cut short by
+    /// a crash, are skipped.
+    pub(crate) fn open<O: DeserializeOwned>(
+        path: impl AsRef<Path>,
+        ttl: Duration,
+        now: DateTime<Utc>,
+    ) -> io::Result<(Self, Vec<JournalEntry<O>>)> {
+        let path = path.as_ref().to_path_buf();
+        let mut entries = Vec::new();
+        if path.exists() {
+            for (index, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<JournalEntry<O>>(&line) {
+                    Ok(entry) => entries.push(entry),
+                    Err(e) => {
+                        warn!("skipping {}:{}: {}", path.display(), index + 1, e);
+                    }
+                }
+            }
+        }
+
+        let mut last_seen: HashMap<i64, DateTime<Utc>> = HashMap::new();
+        for entry in &entries {
+            let seen = last_seen.entry(entry.profile_id).or_insert(entry.at);
+            *seen = (*seen).max(entry.at);
+        }
+        let expired = entries.len();
+        entries.retain(|entry| last_seen[&entry.profile_id] + ttl >= now);
+        let expired = expired - entries.len();
+        if expired > 0 {
+            info!("expired {} stale entries from {}", expired, path.display());
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok((
+            Self {
+                path,
+                file: Mutex::new(file),
+            },
+            entries,
+        ))
+    }
+
+    /// Append `op` for `profile_id`.
+    ///
+    /// Stores cannot fail, so a write error is only logged.
+    pub(crate) fn append<O: Serialize>(&self, profile_id: i64, op: &O) {
+        let entry = JournalEntry {
+            at: Utc::now(),
+            profile_id,
+            op,
+        };
+        let result = serde_json::to_string(&entry).map_err(io::Error::from).and_then(|line| {
+            let mut file = self.file.lock();
+            writeln!(file, "{line}")?;
+            file.flush()
+        });
+        if let Err(e) = result {
+            warn!("failed to append to {}: {}", self.path.display(), e);
+        }
+    }
+
+    /// Replace the journal with `entries`.
+    pub(crate) fn rewrite<O: Serialize>(&self, entries: &[JournalEntry<O>]) -> io::Result<()> {
+        let mut file = self.file.lock();
+
+        let tmp = self.path.with_extension("tmp");
+        {
+            let mut out = io::BufWriter::new(File::create(&tmp)?);
+            for entry in entries {
+                serde_json::to_writer(&mut out, entry)?;
+                out.write_all(b"\n")?;
+            }
+            out.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()?;
+        }
+        fs::rename(&tmp, &self.path)?;
+
+        *file = OpenOptions::new().append(true).open(&self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "emukc-journal-{}-{}.jsonl",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn entries_replay_and_expire_per_profile() {
+        let path = temp_path("expire");
+        let now = Utc::now();
+        let lines = [
+            JournalEntry {
+                at: now - Duration::hours(30),
+                profile_id: 1,
+                op: 10,
+            },
+            JournalEntry {
+                at: now - Duration::hours(30),
+                profile_id: 2,
+                op: 20,
+            },
+            JournalEntry {
+                at: now - Duration::hours(1),
+                profile_id: 2,
+                op: 21,
+            },
+        ];
+        let mut text: String =
+            lines.iter().map(|entry| serde_json::to_string(entry).unwrap() + "\n").collect();
+        text.push_str("{\"at\":"); // torn last write
+        fs::write(&path, text).unwrap();
+
+        let (journal, entries) =
+            SessionJournal::open::<i64>(&path, DEFAULT_SESSION_TTL, now).unwrap();
+        let ops: Vec<_> = entries.iter().map(|entry| entry.op).collect();
+        assert_eq!(ops, vec![20, 21]);
+
+        journal.rewrite(&entries[1..]).unwrap();
+        journal.append(3, &30);
+        let (_, entries) = SessionJournal::open::<i64>(&path, DEFAULT_SESSION_TTL, now).unwrap();
+        let ops: Vec<_> = entries.iter().map(|entry| (entry.profile_id, entry.op)).collect();
+        assert_eq!(ops, vec![(2, 21), (3, 30)]);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
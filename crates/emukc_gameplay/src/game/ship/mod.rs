@@ -14,9 +14,10 @@ use emukc_model::{
     codex::Codex,
     kc2::{KcApiShip, KcApiSlotItem, KcUseItemType},
 };
-use emukc_time::chrono::{Duration, Utc};
+use emukc_time::chrono::{DateTime, Duration, Utc};
 
 use super::{
+    airbase::ensure_not_deployed_impl,
     picturebook::add_ship_to_picturebook_impl,
     slot_item::{find_slot_items_by_id_impl, update_slot_item_impl},
     use_item::deduct_use_item_impl,
@@ -149,7 +150,7 @@ impl<T: HasContext + ?Sized> ShipOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        let (ships, sps) = get_ships_impl(&tx, profile_id).await?;
+        let (ships, sps) = get_ships_impl(&tx, profile_id, self.clock().now(profile_id)).await?;
 
         tx.commit().await?;
 
@@ -201,10 +202,12 @@ impl<T: HasContext + ?Sized> ShipOps for T {
     }
 
     async fn set_exslot_item(&self, ship_id: i64, slot_item_id: i64) -> Result<(), GameplayError> {
+        let codex = self.codex();
         let db = self.db();
         let tx = db.begin().await?;
 
-        set_exslot_item_impl(&tx, ship_id, slot_item_id).await?;
+        let now = self.clock().now(find_ship_profile_id_impl(&tx, ship_id).await?);
+        set_exslot_item_impl(&tx, codex, ship_id, slot_item_id, now).await?;
 
         tx.commit().await?;
 
@@ -221,7 +224,8 @@ impl<T: HasContext + ?Sized> ShipOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        set_slot_item_impl(&tx, codex, ship_id, slot_idx, slot_item_id).await?;
+        let now = self.clock().now(find_ship_profile_id_impl(&tx, ship_id).await?);
+        set_slot_item_impl(&tx, codex, ship_id, slot_idx, slot_item_id, now).await?;
 
         tx.commit().await?;
 
@@ -419,6 +423,7 @@ where
 pub(crate) async fn get_ships_impl<C>(
     c: &C,
     profile_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(Vec<ship::Model>, Vec<Vec<sp_effect_item::Model>>), GameplayError>
 where
     C: ConnectionTrait,
@@ -439,8 +444,8 @@ where
                     "morale timer for profile {profile_id} not found"
                 ))
             })?;
-        let last_time_checked = timer.last_time_regen.unwrap_or_else(Utc::now);
-        let num_of_3_minutes_passed = (Utc::now() - last_time_checked).num_minutes() / 3;
+        let last_time_checked = timer.last_time_regen.unwrap_or(now);
+        let num_of_3_minutes_passed = (now - last_time_checked).num_minutes() / 3;
 
         debug!("{} mins passed", num_of_3_minutes_passed);
 
@@ -525,10 +530,25 @@ where
     Ok(m)
 }
 
+/// Profile owning a ship.
+async fn find_ship_profile_id_impl<C>(c: &C, ship_id: i64) -> Result<i64, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let ship = ship::Entity::find_by_id(ship_id)
+        .one(c)
+        .await?
+        .ok_or_else(|| GameplayError::EntryNotFound(format!("ship with id {ship_id} not found")))?;
+
+    Ok(ship.profile_id)
+}
+
 pub(crate) async fn set_exslot_item_impl<C>(
     c: &C,
+    codex: &Codex,
     ship_id: i64,
     slot_item_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<ship::Model, GameplayError>
 where
     C: ConnectionTrait,
@@ -543,6 +563,9 @@ where
             slot_item::Entity::find_by_id(slot_item_id).one(c).await?.ok_or_else(|| {
                 GameplayError::EntryNotFound(format!("slot item with id {slot_item_id} not found"))
             })?;
+        codex.check_equip(ship.mst_id, slot_item_model.mst_id, slot_item_model.level, true)?;
+        ensure_not_deployed_impl(c, slot_item_id, now).await?;
+
         let mut am = slot_item_model.into_active_model();
 
         am.equip_on = ActiveValue::Set(ship_id);
@@ -563,6 +586,7 @@ pub(crate) async fn set_slot_item_impl<C>(
     ship_id: i64,
     slot_idx: i64,
     slot_item_id: i64,
+    now: DateTime<Utc>,
 ) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
@@ -579,6 +603,8 @@ where
             slot_item::Entity::find_by_id(slot_item_id).one(c).await?.ok_or_else(|| {
                 GameplayError::EntryNotFound(format!("slot item with id {slot_item_id} not found"))
             })?;
+        codex.check_equip(ship.mst_id, slot_item_model.mst_id, slot_item_model.level, false)?;
+        ensure_not_deployed_impl(c, slot_item_id, now).await?;
 
         let mut am = slot_item_model.into_active_model();
         am.equip_on = ActiveValue::Set(ship_id);
@@ -841,14 +867,14 @@ where
     Ok(am)
 }
 
-pub(super) async fn init<C>(c: &C, profile_id: i64) -> Result<(), GameplayError>
+pub(super) async fn init<C>(c: &C, profile_id: i64, now: DateTime<Utc>) -> Result<(), GameplayError>
 where
     C: ConnectionTrait,
 {
     {
         morale_timer::ActiveModel {
             id: ActiveValue::Set(profile_id),
-            last_time_regen: ActiveValue::Set(Some(Utc::now())),
+            last_time_regen: ActiveValue::Set(Some(now)),
         }
         .insert(c)
         .await?;
@@ -6,8 +6,13 @@ use emukc_db::{
     sea_orm::{ActiveValue, TransactionTrait, TryIntoModel, entity::prelude::*},
 };
 use emukc_model::{prelude::*, profile::slot_item::SlotItem};
+use emukc_time::chrono::{DateTime, Utc};
 
-use crate::{err::GameplayError, game::material::add_material_impl, gameplay::HasContext};
+use crate::{
+    err::GameplayError,
+    game::{airbase::ensure_not_deployed_impl, material::add_material_impl},
+    gameplay::HasContext,
+};
 
 use super::picturebook::add_slot_item_to_picturebook_impl;
 
@@ -243,7 +248,9 @@ impl<T: HasContext + ?Sized> SlotItemOps for T {
         let db = self.db();
         let tx = db.begin().await?;
 
-        let scrapped_materials = destroy_items_impl(&tx, codex, profile_id, item_ids).await?;
+        let scrapped_materials =
+            destroy_items_impl(&tx, codex, profile_id, item_ids, self.clock().now(profile_id))
+                .await?;
 
         Ok(scrapped_materials)
     }
@@ -442,6 +449,7 @@ pub(crate) async fn destroy_items_impl<C>(
     codex: &Codex,
     profile_id: i64,
     item_ids: &[i64],
+    now: DateTime<Utc>,
 ) -> Result<Vec<(MaterialCategory, i64)>, GameplayError>
 where
     C: ConnectionTrait,
@@ -460,6 +468,7 @@ where
         .await?;
 
     for item in items {
+        ensure_not_deployed_impl(c, item.id, now).await?;
         let mst = codex.find::<ApiMstSlotitem>(&item.mst_id)?;
 
         mst.api_broken.iter().enumerate().for_each(|(i, v)| {
@@ -3,7 +3,10 @@ use emukc_crypto::rng;
 use emukc_model::{
     codex::{
         Codex,
-        map::{EnemyComposition, EnemyFleetDefinition, MapDefinition, MapVariantDefinition},
+        map::{
+            EnemyComposition, EnemyFleetDefinition, MapDefinition, MapStageDefinition,
+            MapVariantDefinition,
+        },
     },
     kc2::{KcApiShip, KcApiSlotItem, UserHQRank, level, start2::ApiMstShip},
 };
@@ -38,6 +41,25 @@ pub(super) fn build_sortie_enemy_ships(
     Ok((enemy_ships, enemy_level, enemy_rank, enemy_deck_name))
 }
 
+/// Weaken the boss flagship by the debuff gimmicks put on the stage.
+pub(super) fn apply_boss_debuff(
+    stage: &MapStageDefinition,
+    cell_no: i64,
+    enemy_ships: &mut [BattleShipInput],
+) {
+    let debuff = stage.boss_debuff;
+    if debuff.is_empty() || !stage.boss_cell_nos().contains(&cell_no) {
+        return;
+    }
+    let Some(flagship) = enemy_ships.first_mut() else {
+        return;
+    };
+    let ship = &mut flagship.ship;
+    ship.api_soukou[0] = (ship.api_soukou[0] - debuff.armor).max(0);
+    ship.api_maxhp = (ship.api_maxhp - debuff.hp).max(1);
+    ship.api_nowhp = ship.api_nowhp.min(ship.api_maxhp);
+}
+
 pub(super) fn build_sortie_enemy_ship(
     codex: &Codex,
     ship_id: i64,
@@ -292,6 +314,7 @@ pub(super) fn select_enemy_composition_for_roll(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use emukc_model::codex::map::BossDebuff;
     use std::collections::BTreeMap;
 
     fn test_codex() -> Codex {
@@ -318,6 +341,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         }
     }
 
@@ -339,6 +363,7 @@ mod tests {
             default_variant: String::new(),
             rank_stage_ids: BTreeMap::new(),
             variants: BTreeMap::new(),
+            gimmicks: Vec::new(),
         }
     }
 
@@ -492,6 +517,31 @@ mod tests {
         assert!(result.ship.api_maxhp > 0);
     }
 
+    #[test]
+    fn apply_boss_debuff_weakens_boss_flagship_only() {
+        let codex = test_codex();
+        let mut stage = empty_variant();
+        stage.boss_cell_no = 5;
+        stage.boss_debuff = BossDebuff {
+            armor: 10,
+            hp: 20,
+        };
+        let mut ships = vec![
+            build_sortie_enemy_ship(&codex, 1501, 30).unwrap(),
+            build_sortie_enemy_ship(&codex, 1501, 30).unwrap(),
+        ];
+        let (armor, hp) = (ships[0].ship.api_soukou[0], ships[0].ship.api_maxhp);
+
+        apply_boss_debuff(&stage, 4, &mut ships);
+        assert_eq!(ships[0].ship.api_maxhp, hp);
+
+        apply_boss_debuff(&stage, 5, &mut ships);
+        assert_eq!(ships[0].ship.api_soukou[0], (armor - 10).max(0));
+        assert_eq!(ships[0].ship.api_maxhp, (hp - 20).max(1));
+        assert!(ships[0].ship.api_nowhp <= ships[0].ship.api_maxhp);
+        assert_eq!(ships[1].ship.api_maxhp, hp);
+    }
+
     #[test]
     fn build_sortie_enemy_ship_uses_new_ship_for_friendly_id_as_fallback() {
         let codex = test_codex();
@@ -0,0 +1,112 @@
+//! Friend fleet (友軍艦隊) selection for sortie night battles.
+
+use emukc_battle::{BattleShipInput, FriendFleetInput};
+use emukc_crypto::rng;
+use emukc_db::sea_orm::ConnectionTrait;
+use emukc_model::codex::{Codex, friend_fleet::FriendFleet, select_weighted_for_roll};
+
+use crate::err::GameplayError;
+use crate::game::settings::game::get_game_settings_impl;
+
+/// Draw the friend fleet joining a night battle, if the player requested one.
+///
+/// # Arguments
+///
+/// * `c` - The database connection.
+/// * `codex` - The codex holding the friend fleet table.
+/// * `profile_id` - The profile ID.
+/// * `map_id` - Map id (`area * 10 + map`).
+/// * `cell_no` - Cell number of the battle node.
+pub(super) async fn build_friend_fleet_impl<C>(
+    c: &C,
+    codex: &Codex,
+    profile_id: i64,
+    map_id: i64,
+    cell_no: i64,
+) -> Result<Option<FriendFleetInput>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let settings = get_game_settings_impl(c, profile_id).await?;
+    if !settings.friend_fleet_req_flag {
+        return Ok(None);
+    }
+
+    let candidates =
+        codex.friend_fleets.candidates(map_id, cell_no, settings.friend_fleet_req_type);
+    let total_weight = candidates.iter().map(|(_, weight)| weight).sum::<u64>();
+    if total_weight == 0 {
+        return Ok(None);
+    }
+    let Some(fleet) = select_friend_fleet_for_roll(&candidates, rng::u64(0..total_weight)) else {
+        return Ok(None);
+    };
+
+    friend_fleet_input(codex, fleet).map(Some)
+}
+
+/// Pick the fleet whose weight range contains `roll`.
+pub(super) fn select_friend_fleet_for_roll<'a>(
+    candidates: &[(&'a FriendFleet, u64)],
+    roll: u64,
+) -> Option<&'a FriendFleet> {
+    let indexed: Vec<(i64, u64)> =
+        candidates.iter().enumerate().map(|(idx, (_, weight))| (idx as i64, *weight)).collect();
+    select_weighted_for_roll(&indexed, roll).map(|idx| candidates[idx as usize].0)
+}
+
+fn friend_fleet_input(
+    codex: &Codex,
+    fleet: &FriendFleet,
+) -> Result<FriendFleetInput, GameplayError> {
+    let ships = fleet
+        .ships
+        .iter()
+        .map(|entry| {
+            let (ship, slot_items) = codex.new_friend_fleet_ship(entry).ok_or_else(|| {
+                GameplayError::BadManifest(format!(
+                    "friend fleet ship {} or its equipment not found",
+                    entry.ship_id,
+                ))
+            })?;
+            Ok(BattleShipInput {
+                ship,
+                slot_items,
+                effect_list: vec![],
+                married: false,
+            })
+        })
+        .collect::<Result<Vec<_>, GameplayError>>()?;
+
+    Ok(FriendFleetInput {
+        production_type: fleet.production_type,
+        ships,
+        voice_ids: fleet.ships.iter().map(|entry| entry.voice_id).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fleet(production_type: i64) -> FriendFleet {
+        FriendFleet {
+            cells: vec![],
+            weights: [1, 1],
+            production_type,
+            ships: vec![],
+        }
+    }
+
+    #[test]
+    fn roll_walks_weight_ranges() {
+        let (first, second) = (fleet(1), fleet(2));
+        let candidates = [(&first, 3), (&second, 7)];
+
+        assert_eq!(select_friend_fleet_for_roll(&candidates, 0).unwrap().production_type, 1);
+        assert_eq!(select_friend_fleet_for_roll(&candidates, 2).unwrap().production_type, 1);
+        assert_eq!(select_friend_fleet_for_roll(&candidates, 3).unwrap().production_type, 2);
+        assert_eq!(select_friend_fleet_for_roll(&candidates, 9).unwrap().production_type, 2);
+        assert!(select_friend_fleet_for_roll(&candidates, 10).is_none());
+    }
+}
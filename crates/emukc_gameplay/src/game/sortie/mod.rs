@@ -1,13 +1,18 @@
 mod enemy_ship;
+mod friend_fleet;
 mod route_context;
 
 use enemy_ship::{
-    build_sortie_enemy_ships, fallback_enemy_composition, resolve_sortie_enemy_fleet,
-    select_random_enemy_composition,
+    apply_boss_debuff, build_sortie_enemy_ships, fallback_enemy_composition,
+    resolve_sortie_enemy_fleet, select_random_enemy_composition,
+};
+use friend_fleet::build_friend_fleet_impl;
+use route_context::{
+    build_fleet_route_context, build_sortie_friend_ships, engagement_for_cell,
+    fleet_transport_points,
 };
-use route_context::{build_fleet_route_context, build_sortie_friend_ships, engagement_for_cell};
 
-use std::collections::BTreeSet;
+use std::{borrow::Cow, collections::BTreeSet};
 
 use async_trait::async_trait;
 use emukc_crypto::rng;
@@ -18,12 +23,16 @@ use emukc_model::codex::map::{RouteOperator, RoutePredicate, RouteRule, SpeedCla
 use emukc_model::{
     codex::{
         Codex,
-        map::{EnemyComposition, MapCellDefinition, MapStageDefinition, split_map_id},
+        map::{
+            EnemyComposition, MapCellDefinition, MapDefinition, MapStageDefinition, split_map_id,
+        },
     },
     kc2::{MaterialCategory, start2::ApiMstShip},
+    profile::map_record::MapGaugeType,
     thirdparty::QuestActionEvent,
 };
-use serde::Serialize;
+use emukc_time::chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use crate::{err::GameplayError, gameplay::HasContext};
 
@@ -33,45 +42,57 @@ use super::battle::repository::SortieRepository;
 use super::map_progress::assign_stage_id;
 #[cfg(test)]
 use super::map_route::{route_predicate_matches, select_route_target_for_roll};
-#[cfg(test)]
-use super::sortie_result::eligible_sortie_ship_drops;
 use emukc_battle::{
-    BattleContext, BattleNightHougeki, BattleShipInput, BattleType, EngagementType,
+    AirRaidInput, BattleAirRaid, BattleContext, BattleFriendlyBattle, BattleFriendlyInfo,
+    BattleKouku, BattleNightHougeki, BattleRuntimeShip, BattleShipInput, BattleSupportInput,
+    BattleType, CombinedFleetInput, CombinedFleetType, EngagementType, execute_air_raid,
 };
 #[cfg(test)]
 use enemy_ship::{build_sortie_enemy_ship, select_enemy_composition_for_roll};
 
 use super::{
+    airbase::{
+        AirBaseStrike, build_air_base_waves_impl, build_air_raid_bases_impl,
+        settle_air_base_waves_impl,
+    },
     basic::find_profile,
     battle::{
-        practice::PracticeBattleResponse,
+        practice::{BattleCombinedArrays, PracticeBattleResponse},
         rng::ProductionRng,
         sortie::{
-            SortieBattleInput, build_day_response, build_night_response, pending_battle,
-            run_day_battle, run_night_battle, run_sp_midnight_battle, take_day_battle_result,
+            SortieBattleInput, build_day_response, build_night_response, node_historical_bonuses,
+            pending_battle, run_day_battle, run_night_battle, run_sp_midnight_battle,
+            take_day_battle_result,
         },
     },
+    expedition::{SupportMissionKind, charge_support_fleet_impl, find_support_fleet_impl},
     fleet::get_fleet_ships_impl,
     map::{
         active_map_catalog, check_and_unlock_dependencies_impl, ensure_map_records_impl,
         find_map_definition, find_map_record_impl, refresh_all_map_records_impl,
     },
-    map_progress::resolve_record_stage_id,
-    map_route::{cell_has_routing_outgoing, evaluate_route_destination},
-    material::add_material_impl,
+    map_progress::{
+        GimmickEvent, active_gimmick_ids, apply_gimmicks, record_gimmick_event_impl,
+        resolve_record_stage_id,
+    },
+    map_route::{cell_has_routing_outgoing, evaluate_route_destination, select_player_route},
+    material::{add_material_impl, deduct_material_impl, get_mat_impl},
     quest::update::update_quest_progress_for_action,
     sortie_result::{
-        SortieBattleResultSnapshot, apply_sortie_map_result, build_sortie_quest_event,
-        calculate_battle_admiral_exp, calculate_sortie_base_exp, calculate_sortie_ship_exp,
-        try_grant_sortie_ship_drop, update_sortie_result_stats,
+        SortieBattleResultSnapshot, apply_sortie_map_result, build_sortie_escort_result,
+        build_sortie_quest_event, calculate_battle_admiral_exp, calculate_sortie_base_exp,
+        calculate_sortie_ship_exp, select_sortie_escape, try_grant_sortie_ship_drop,
+        update_sortie_result_stats,
     },
 };
 
-pub use super::sortie_result::{SortieBattleResultEnemyInfo, SortieBattleResultResponse};
+pub use super::sortie_result::{
+    SortieBattleResultEnemyInfo, SortieBattleResultEscape, SortieBattleResultResponse,
+};
 
 pub type SortieBattleResponse = PracticeBattleResponse;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveSortieState {
     pub deck_id: i64,
     pub map_id: i64,
@@ -83,6 +104,20 @@ pub struct ActiveSortieState {
     pub pending_battle_cell_id: Option<i64>,
     pub visited_cell_ids: BTreeSet<i64>,
     pub locked_enemy_composition: Option<EnemyComposition>,
+    /// Airbases sortied before the map start.
+    pub air_base_strikes: Vec<AirBaseStrike>,
+    /// `api_combined_flag` of the sortied fleet; 0 for a single fleet.
+    pub combined_type: i64,
+    /// Ships sent home mid-sortie (退避); they sit out the remaining battles.
+    pub escaped_ship_ids: Vec<i64>,
+    /// Retreat offered by the last battle result as `[escapee, tow]` ship ids.
+    pub pending_escape: Option<[i64; 2]>,
+    /// Event gimmicks in effect for this sortie.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub gimmick_ids: BTreeSet<String>,
+    /// Cell whose airbase raid has been resolved; one raid per cell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub air_raid_cell_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -124,6 +159,8 @@ pub struct SortieStartResponse {
     pub limit_state: i64,
     pub airsearch: Option<SortieAirSearch>,
     pub enemy_deck_preview: Option<Vec<SortieEnemyDeckPreview>>,
+    /// Destinations the player picks from at the arrival cell (能動分岐).
+    pub select_route: Option<Vec<i64>>,
 }
 
 /// Resource acquisition at a non-battle node.
@@ -164,6 +201,8 @@ pub struct SortieNextResponse {
     pub limit_state: Option<i64>,
     pub itemget: Option<Vec<SortieItemGet>>,
     pub happening: Option<SortieHappening>,
+    /// Destinations the player picks from at the arrival cell (能動分岐).
+    pub select_route: Option<Vec<i64>>,
 }
 
 #[expect(non_snake_case)]
@@ -171,6 +210,8 @@ pub struct SortieNextResponse {
 pub struct SortieNightBattleResponse {
     pub api_deck_id: i64,
     pub api_formation: [i64; 3],
+    /// Fleets fighting at night: 1 = main, 2 = escort.
+    pub api_active_deck: [i64; 2],
     pub api_f_nowhps: Vec<i64>,
     pub api_f_maxhps: Vec<i64>,
     pub api_fParam: Vec<[i64; 4]>,
@@ -180,6 +221,8 @@ pub struct SortieNightBattleResponse {
     pub api_e_maxhps: Vec<i64>,
     pub api_eSlot: Vec<[i64; 5]>,
     pub api_eParam: Vec<[i64; 4]>,
+    #[serde(flatten)]
+    pub combined: BattleCombinedArrays,
     pub api_smoke_type: i64,
     pub api_balloon_cell: i64,
     pub api_atoll_cell: i64,
@@ -187,6 +230,13 @@ pub struct SortieNightBattleResponse {
     pub api_flare_pos: [i64; 2],
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_hougeki: Option<BattleNightHougeki>,
+    /// Friend fleet (友軍艦隊) that joined this night battle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_friendly_info: Option<BattleFriendlyInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_friendly_kouku: Option<BattleKouku>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_friendly_battle: Option<BattleFriendlyBattle>,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -253,6 +303,21 @@ pub trait SortieOps {
         profile_id: i64,
     ) -> Result<SortieGobackPortResponse, GameplayError>;
 
+    /// Send home the ship offered by the last combined-fleet battle result,
+    /// together with its towing destroyer (`api_req_combined_battle/goback_port`).
+    async fn combined_goback_port(
+        &self,
+        profile_id: i64,
+    ) -> Result<SortieGobackPortResponse, GameplayError>;
+
+    /// Resolve an enemy air raid on the airbases of the sortie's area.
+    ///
+    /// The enemy fleet of the current cell, which must be an air raid cell,
+    /// raids every airbase of the area once; only squadrons set to air defense
+    /// intercept. Lost planes are written back and each point of base damage
+    /// costs one fuel and one bauxite.
+    async fn sortie_air_raid(&self, profile_id: i64) -> Result<BattleAirRaid, GameplayError>;
+
     /// Clear any stale sortie state for a profile without erroring if none exists.
     async fn clear_sortie_state_if_any(&self, profile_id: i64);
 }
@@ -278,8 +343,9 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             )));
         }
 
-        ensure_map_records_impl(&tx, codex, profile_id).await?;
-        refresh_all_map_records_impl(&tx, codex, profile_id).await?;
+        let now = self.clock().now(profile_id);
+        ensure_map_records_impl(&tx, codex, profile_id, now).await?;
+        refresh_all_map_records_impl(&tx, codex, profile_id, now).await?;
         let definition = find_map_definition(codex, maparea_id, mapinfo_no)?;
         let record = find_map_record_impl(&tx, profile_id, definition.map_id).await?;
         if !record.unlocked {
@@ -295,6 +361,9 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 definition.map_id,
             ))
         })?;
+        let gimmick_ids = active_gimmick_ids(&definition, &record);
+        let stage = apply_gimmicks(&definition, stage, &gimmick_ids);
+        let stage = &*stage;
         let source_cell = select_start_source_cell(stage).map_err(|err| {
             GameplayError::EntryNotFound(format!("{} for map {}", err, definition.map_id))
         })?;
@@ -307,8 +376,18 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             .ok_or_else(|| GameplayError::EntryNotFound(format!("cell {first_cell} not found")))?;
         let locked_enemy_composition =
             select_locked_enemy_composition(definition.map_id, stage, current_cell.cell_no);
+        record_gimmick_event_impl(
+            &tx,
+            profile_id,
+            &definition,
+            stage,
+            GimmickEvent::Visit {
+                cell_no: first_cell,
+            },
+        )
+        .await?;
 
-        let active = ActiveSortieState {
+        let mut active = ActiveSortieState {
             deck_id,
             map_id: definition.map_id,
             map_name: definition.name.clone(),
@@ -319,11 +398,23 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             pending_battle_cell_id: None,
             visited_cell_ids: BTreeSet::from([source_cell.cell_no, first_cell]),
             locked_enemy_composition: locked_enemy_composition.clone(),
+            air_base_strikes: Vec::new(),
+            combined_type: if deck_id == 1 {
+                profile.combined_type
+            } else {
+                0
+            },
+            escaped_ship_ids: Vec::new(),
+            pending_escape: None,
+            gimmick_ids,
+            air_raid_cell_id: None,
         };
         tx.commit().await?;
         self.sortie_store()
             .with_profile_lock(profile_id, async {
                 clear_pending_sortie_runtime_state(self.sortie_store(), profile_id);
+                active.air_base_strikes =
+                    self.sortie_store().take_air_base_strikes(profile_id, maparea_id);
                 let _ = self.sortie_store().insert_active(profile_id, active);
             })
             .await;
@@ -356,6 +447,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 .as_ref()
                 .map(build_enemy_deck_preview)
                 .filter(|preview| !preview.is_empty()),
+            select_route: sortie_select_route(current_cell),
         })
     }
 
@@ -379,6 +471,8 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                         "cannot advance sortie while a battle result is pending".to_string(),
                     ));
                 }
+                // Advancing declines any retreat the last battle offered.
+                active.pending_escape = None;
 
                 let catalog = active_map_catalog(codex);
                 let definition =
@@ -402,12 +496,8 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 }
                 let _ = store.insert_active(profile_id, active.clone());
 
-                let stage = definition.stage(&active.stage_id).ok_or_else(|| {
-                    GameplayError::EntryNotFound(format!(
-                        "stage `{}` not found for map {}",
-                        active.stage_id, active.map_id,
-                    ))
-                })?;
+                let stage = sortie_stage(definition, &active)?;
+                let stage = &*stage;
                 let current = stage.cell(active.current_cell_id).ok_or_else(|| {
                     GameplayError::EntryNotFound(format!(
                         "cell {} not found in map {}",
@@ -429,8 +519,11 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 tx.commit().await?;
                 route_context.visited_cell_ids = active.visited_cell_ids.clone();
 
-                let next_cell_id =
-                    evaluate_route_destination(current, stage, &route_context, selected_cell_id)?;
+                let next_cell_id = if current.select_cells.is_empty() {
+                    evaluate_route_destination(current, stage, &route_context, selected_cell_id)?
+                } else {
+                    select_player_route(current, selected_cell_id)?
+                };
                 let next = stage.cell(next_cell_id).ok_or_else(|| {
                     GameplayError::EntryNotFound(format!("cell {next_cell_id} not found"))
                 })?;
@@ -449,12 +542,24 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 let (itemget, happening) =
                     resolve_non_battle_node_effect(&tx, codex, profile_id, next, &fleet_ships)
                         .await?;
+                record_gimmick_event_impl(
+                    &tx,
+                    profile_id,
+                    definition,
+                    stage,
+                    GimmickEvent::Visit {
+                        cell_no: next_cell_id,
+                    },
+                )
+                .await?;
                 tx.commit().await?;
 
                 let (maparea_id, mapinfo_no) = split_map_id(active.map_id);
                 // rashin_flg keys on the departing cell's physical out-degree
-                // (branch node), not the fleet-resolved candidate count.
-                let departing_is_branch = current.next_cells.len() > 1;
+                // (branch node), not the fleet-resolved candidate count. The
+                // compass does not spin where the player picked the route.
+                let departing_is_branch =
+                    current.next_cells.len() > 1 && current.select_cells.is_empty();
                 Ok(SortieNextResponse {
                     rashin_flg: departing_is_branch,
                     rashin_id: if departing_is_branch {
@@ -482,6 +587,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                     limit_state: Some(0),
                     itemget,
                     happening,
+                    select_route: sortie_select_route(next),
                 })
             })
             .await
@@ -499,6 +605,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             profile_id,
             formation_id,
             BattleType::Normal,
+            self.clock().now(profile_id),
         )
         .await
     }
@@ -515,6 +622,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             profile_id,
             formation_id,
             BattleType::AirBattle,
+            self.clock().now(profile_id),
         )
         .await
     }
@@ -531,6 +639,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             profile_id,
             formation_id,
             BattleType::LdAirBattle,
+            self.clock().now(profile_id),
         )
         .await
     }
@@ -547,6 +656,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             profile_id,
             formation_id,
             BattleType::LdShooting,
+            self.clock().now(profile_id),
         )
         .await
     }
@@ -583,12 +693,8 @@ impl<T: HasContext + ?Sized> SortieOps for T {
         let definition = catalog.as_ref().map_definition(active.map_id).ok_or_else(|| {
             GameplayError::EntryNotFound(format!("map definition {} not found", active.map_id))
         })?;
-        let stage = definition.stage(&active.stage_id).ok_or_else(|| {
-            GameplayError::EntryNotFound(format!(
-                "stage `{}` not found for map {}",
-                active.stage_id, active.map_id,
-            ))
-        })?;
+        let stage = sortie_stage(definition, &active)?;
+        let stage = &*stage;
         let current_cell = stage.cell(pending_cell_id).ok_or_else(|| {
             GameplayError::EntryNotFound(format!("cell {pending_cell_id} not found"))
         })?;
@@ -603,14 +709,55 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             win_rank = %snapshot.win_rank,
             "sortie_battle_result: boss check"
         );
-        let first_clear =
-            apply_sortie_map_result(&tx, profile_id, definition, stage, is_boss_cell, &snapshot)
-                .await?;
+        record_gimmick_event_impl(
+            &tx,
+            profile_id,
+            definition,
+            stage,
+            GimmickEvent::Win {
+                cell_no: current_cell.cell_no,
+                rank: &snapshot.win_rank,
+            },
+        )
+        .await?;
+        let transport_points =
+            if is_boss_cell && definition.gauge_type == Some(MapGaugeType::Landing as i64) {
+                let mut ships = without_escaped_ships(
+                    get_fleet_ships_impl(&tx, profile_id, active.deck_id).await?,
+                    &active,
+                );
+                if active.combined_type > 0 {
+                    ships.extend(without_escaped_ships(
+                        get_fleet_ships_impl(&tx, profile_id, 2).await?,
+                        &active,
+                    ));
+                }
+                fleet_transport_points(&tx, codex, &ships).await?
+            } else {
+                0
+            };
+        let first_clear = apply_sortie_map_result(
+            &tx,
+            profile_id,
+            definition,
+            stage,
+            is_boss_cell,
+            &snapshot,
+            transport_points,
+            self.clock().now(profile_id),
+        )
+        .await?;
         tracing::debug!(
             map_id = definition.map_id,
             first_clear,
             "sortie_battle_result: map result applied"
         );
+        let difficulty = if definition.is_event {
+            let record = find_map_record_impl(&tx, profile_id, definition.map_id).await?;
+            Some(record.selected_rank as i64).filter(|rank| *rank > 0)
+        } else {
+            None
+        };
         let ship_drop = try_grant_sortie_ship_drop(
             &tx,
             codex,
@@ -618,6 +765,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             stage,
             pending_cell_id,
             &snapshot.win_rank,
+            difficulty,
         )
         .await?;
         let quest_event = build_sortie_quest_event(definition, &active, &snapshot)?;
@@ -653,8 +801,34 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             None
         };
 
+        let escape = if active.combined_type > 0
+            && cell_has_routing_outgoing(current_cell.cell_no, stage)
+        {
+            select_sortie_escape(&session.friendly, &session.friendly_escort, |ship| {
+                codex.find::<ApiMstShip>(&ship.ship.api_ship_id).map(|m| m.api_stype).unwrap_or(0)
+            })
+        } else {
+            None
+        };
+        let api_escape = match escape {
+            Some(ship_ids) => Some(build_sortie_escape_positions(&tx, profile_id, ship_ids).await?),
+            None => None,
+        };
+        active.pending_escape = escape;
+
         tx.commit().await?;
 
+        let enemy_escort_nowhps = session.packet.enemy_nowhps_combined.iter().flatten();
+        let dests = session
+            .packet
+            .enemy_nowhps
+            .iter()
+            .chain(enemy_escort_nowhps)
+            .filter(|hp| **hp <= 0)
+            .count() as i64;
+        let destsf = i64::from(session.packet.enemy_nowhps.first().copied().unwrap_or(1) <= 0);
+        let escort = snapshot.escort.clone();
+
         // Refresh stage identity from DB before deciding sortie fate.
         // apply_sortie_map_result may have changed stage_id via gauge clear.
         // Serialize the in-memory state mutation to prevent TOCTOU races.
@@ -677,10 +851,8 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                         api_get_base_exp: snapshot.get_base_exp,
                         api_get_ship_exp: snapshot.get_ship_exp,
                         api_get_exp_lvup: snapshot.get_exp_lvup,
-                        api_dests: session.packet.enemy_nowhps.iter().filter(|hp| **hp <= 0).count() as i64,
-                        api_destsf: i64::from(
-                            session.packet.enemy_nowhps.first().copied().unwrap_or(1) <= 0,
-                        ),
+                        api_dests: dests,
+                        api_destsf: destsf,
                         api_quest_name: snapshot.quest_name,
                         api_quest_level: snapshot.quest_level,
                         api_enemy_info: SortieBattleResultEnemyInfo {
@@ -692,14 +864,17 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                         api_get_flag: [0, i64::from(ship_drop.is_some()), 0],
                         api_get_ship: ship_drop,
                         api_next_map_ids: next_map_ids,
+                        api_mvp_combined: escort.as_ref().map(|escort| escort.mvp),
+                        api_get_ship_exp_combined: escort
+                            .as_ref()
+                            .map(|escort| escort.get_ship_exp.clone()),
+                        api_get_exp_lvup_combined: escort.map(|escort| escort.get_exp_lvup),
+                        api_escape_flag: 0,
+                        api_escape: None,
                     });
                 }
-                let stage = definition.stage(&active.stage_id).ok_or_else(|| {
-                    GameplayError::EntryNotFound(format!(
-                        "stage `{}` not found for map {}",
-                        active.stage_id, active.map_id,
-                    ))
-                })?;
+                let stage = sortie_stage(definition, &active)?;
+                let stage = &*stage;
                 let current_cell = stage.cell(pending_cell_id).ok_or_else(|| {
                     GameplayError::EntryNotFound(format!("cell {pending_cell_id} not found"))
                 })?;
@@ -725,10 +900,8 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                     api_get_base_exp: snapshot.get_base_exp,
                     api_get_ship_exp: snapshot.get_ship_exp,
                     api_get_exp_lvup: snapshot.get_exp_lvup,
-                    api_dests: session.packet.enemy_nowhps.iter().filter(|hp| **hp <= 0).count() as i64,
-                    api_destsf: i64::from(
-                        session.packet.enemy_nowhps.first().copied().unwrap_or(1) <= 0,
-                    ),
+                    api_dests: dests,
+                    api_destsf: destsf,
                     api_quest_name: snapshot.quest_name,
                     api_quest_level: snapshot.quest_level,
                     api_enemy_info: SortieBattleResultEnemyInfo {
@@ -740,6 +913,13 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                     api_get_flag: [0, i64::from(ship_drop.is_some()), 0],
                     api_get_ship: ship_drop,
                     api_next_map_ids: next_map_ids,
+                    api_mvp_combined: escort.as_ref().map(|escort| escort.mvp),
+                    api_get_ship_exp_combined: escort
+                        .as_ref()
+                        .map(|escort| escort.get_ship_exp.clone()),
+                    api_get_exp_lvup_combined: escort.map(|escort| escort.get_exp_lvup),
+                    api_escape_flag: i64::from(api_escape.is_some()),
+                    api_escape,
                 })
             })
             .await
@@ -762,6 +942,9 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             ));
         }
 
+        let friend_fleet =
+            build_friend_fleet_impl(self.db(), codex, profile_id, pending.map_id, pending.cell_id)
+                .await?;
         let mut rng = ProductionRng;
         let night = run_night_battle(
             store,
@@ -771,6 +954,7 @@ impl<T: HasContext + ?Sized> SortieOps for T {
             pending.packet.formation[1],
             EngagementType::from_api_id(pending.packet.formation[2])
                 .unwrap_or(EngagementType::SameCourse),
+            friend_fleet,
             &mut rng,
         )
         .ok_or_else(|| {
@@ -812,6 +996,9 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 );
                 snapshot.get_ship_exp = ship_exp;
                 snapshot.get_exp_lvup = ship_lvup;
+                snapshot.escort =
+                    build_sortie_escort_result(snapshot.get_base_exp, &updated.friendly_escort);
+                snapshot.enemy_nowhps = enemy_nowhps(&updated.enemy, &updated.enemy_escort);
             }
             store.insert_pending_result(profile_id, snapshot);
         }
@@ -848,14 +1035,13 @@ impl<T: HasContext + ?Sized> SortieOps for T {
         let definition = catalog.as_ref().map_definition(active.map_id).ok_or_else(|| {
             GameplayError::EntryNotFound(format!("map definition {} not found", active.map_id))
         })?;
-        let stage = definition.stage(&active.stage_id).ok_or_else(|| {
-            GameplayError::EntryNotFound(format!(
-                "stage `{}` not found for map {}",
-                active.stage_id, active.map_id,
-            ))
-        })?;
+        let stage = sortie_stage(definition, &active)?;
+        let stage = &*stage;
 
-        let fleet_ships = get_fleet_ships_impl(&tx, profile_id, active.deck_id).await?;
+        let fleet_ships = without_escaped_ships(
+            get_fleet_ships_impl(&tx, profile_id, active.deck_id).await?,
+            &active,
+        );
         if fleet_ships.is_empty() {
             return Err(GameplayError::WrongType(format!(
                 "fleet {} has no ships for sortie battle",
@@ -864,16 +1050,22 @@ impl<T: HasContext + ?Sized> SortieOps for T {
         }
 
         let friend_ships = build_sortie_friend_ships(&tx, &fleet_ships).await?;
+        let friend_escort = build_sortie_escort_ships(&tx, profile_id, &active).await?;
         let enemy_fleet = resolve_sortie_enemy_fleet(active.map_id, stage, active.current_cell_id);
         let enemy_composition = active
             .locked_enemy_composition
             .clone()
             .or_else(|| select_random_enemy_composition(&enemy_fleet))
             .unwrap_or_else(|| fallback_enemy_composition(active.current_cell_id));
-        let (enemy_ships, enemy_level, enemy_rank, enemy_deck_name) =
+        let (mut enemy_ships, enemy_level, enemy_rank, enemy_deck_name) =
             build_sortie_enemy_ships(codex, definition, &enemy_fleet, &enemy_composition)?;
+        apply_boss_debuff(stage, active.current_cell_id, &mut enemy_ships);
+        let enemy_escort = split_enemy_escort(&mut enemy_ships);
 
         let enemy_formation_id = enemy_fleet.formations.first().copied().unwrap_or(1);
+        let friend_fleet =
+            build_friend_fleet_impl(&tx, codex, profile_id, active.map_id, active.current_cell_id)
+                .await?;
         let mut rng = ProductionRng;
         let (day_session, night_session) = run_sp_midnight_battle(
             store,
@@ -891,18 +1083,30 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                     engagement: engagement_for_cell(active.map_id, active.current_cell_id),
                     friend_ships: friend_ships.clone(),
                     enemy_ships: enemy_ships.clone(),
+                    air_base_waves: Vec::new(),
+                    combined: combined_fleet_input(&active, friend_escort, enemy_escort),
+                    support: None,
+                    historical_bonuses: node_historical_bonuses(
+                        codex,
+                        active.map_id,
+                        active.current_cell_id,
+                    ),
                 },
             },
             enemy_formation_id,
+            friend_fleet,
             &mut rng,
         );
 
         let base_exp = calculate_sortie_base_exp(active.map_level, active.current_cell_id);
         let get_exp =
             calculate_battle_admiral_exp(base_exp, &night_session.outcome.win_rank.to_string());
-        let friendly_nowhps: Vec<i64> = pending_battle(store, profile_id)
-            .map(|s| s.friendly.iter().map(|f| f.hp().max(0)).collect())
-            .unwrap_or_default();
+        let current = pending_battle(store, profile_id).ok_or_else(|| {
+            GameplayError::EntryNotFound(format!(
+                "sortie battle session not found for profile {profile_id}",
+            ))
+        })?;
+        let friendly_nowhps: Vec<i64> = current.friendly.iter().map(|f| f.hp().max(0)).collect();
         let ct_flagship = friend_ships
             .first()
             .and_then(|s| codex.manifest.find_ship(s.ship.api_ship_id))
@@ -921,12 +1125,8 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 friendly_ship_ids: day_session.friendly_ship_ids.clone(),
                 enemy_ship_ids: day_session.enemy_ship_ids.clone(),
                 friendly_nowhps,
-                enemy_ship_types: day_session
-                    .enemy_ship_ids
-                    .iter()
-                    .map(|&id| codex.find::<ApiMstShip>(&id).map(|m| m.api_stype).unwrap_or(0))
-                    .collect(),
-                enemy_nowhps: night_session.packet.enemy_nowhps.clone(),
+                enemy_ship_types: enemy_ship_types(codex, &current.enemy, &current.enemy_escort),
+                enemy_nowhps: enemy_nowhps(&current.enemy, &current.enemy_escort),
                 win_rank: night_session.outcome.win_rank.to_string(),
                 get_exp,
                 member_lv: profile.hq_level,
@@ -940,16 +1140,12 @@ impl<T: HasContext + ?Sized> SortieOps for T {
                 enemy_level,
                 enemy_rank,
                 enemy_deck_name,
+                escort: build_sortie_escort_result(base_exp, &current.friendly_escort),
             },
         );
 
         active.pending_battle_cell_id = Some(active.current_cell_id);
-
-        let current = pending_battle(store, profile_id).ok_or_else(|| {
-            GameplayError::EntryNotFound(format!(
-                "sortie battle session not found for profile {profile_id}",
-            ))
-        })?;
+        active.pending_escape = None;
 
         tx.commit().await?;
         let _ = store.insert_active(profile_id, active);
@@ -973,6 +1169,104 @@ impl<T: HasContext + ?Sized> SortieOps for T {
         Ok(SortieGobackPortResponse::default())
     }
 
+    async fn combined_goback_port(
+        &self,
+        profile_id: i64,
+    ) -> Result<SortieGobackPortResponse, GameplayError> {
+        let store = self.sortie_store();
+        store
+            .with_profile_lock(profile_id, async {
+                let mut active = store.get_active(profile_id).ok_or_else(|| {
+                    GameplayError::EntryNotFound(format!(
+                        "active sortie not found for profile {profile_id}",
+                    ))
+                })?;
+                let escape = active.pending_escape.take().ok_or_else(|| {
+                    GameplayError::WrongType("no retreat offered for this sortie".to_string())
+                })?;
+                active.escaped_ship_ids.extend(escape);
+                let _ = store.insert_active(profile_id, active);
+                Ok(SortieGobackPortResponse::default())
+            })
+            .await
+    }
+
+    async fn sortie_air_raid(&self, profile_id: i64) -> Result<BattleAirRaid, GameplayError> {
+        let store = self.sortie_store();
+        let codex = self.codex();
+        let db = self.db();
+        store
+            .with_profile_lock(profile_id, async {
+                let tx = db.begin().await?;
+
+                let mut active = store.get_active(profile_id).ok_or_else(|| {
+                    GameplayError::EntryNotFound(format!(
+                        "active sortie not found for profile {profile_id}",
+                    ))
+                })?;
+                let catalog = active_map_catalog(codex);
+                let definition =
+                    catalog.as_ref().map_definition(active.map_id).ok_or_else(|| {
+                        GameplayError::EntryNotFound(format!(
+                            "map definition {} not found",
+                            active.map_id
+                        ))
+                    })?;
+                let stage = sortie_stage(definition, &active)?;
+                let stage = &*stage;
+                let current_cell = stage.cell(active.current_cell_id).ok_or_else(|| {
+                    GameplayError::EntryNotFound(format!(
+                        "cell {} not found in map {}",
+                        active.current_cell_id, active.map_id,
+                    ))
+                })?;
+                ensure_air_raid_pending(&active, current_cell)?;
+                let (area_id, _) = split_map_id(active.map_id);
+                let bases = build_air_raid_bases_impl(&tx, profile_id, area_id).await?;
+                if bases.is_empty() {
+                    return Err(GameplayError::EntryNotFound(format!("airbase in area {area_id}")));
+                }
+
+                let enemy_fleet =
+                    resolve_sortie_enemy_fleet(active.map_id, stage, active.current_cell_id);
+                let enemy_composition = select_random_enemy_composition(&enemy_fleet)
+                    .unwrap_or_else(|| fallback_enemy_composition(active.current_cell_id));
+                let (enemy_ships, ..) =
+                    build_sortie_enemy_ships(codex, definition, &enemy_fleet, &enemy_composition)?;
+
+                let mut rng = ProductionRng;
+                let simulation = execute_air_raid(
+                    codex,
+                    AirRaidInput {
+                        bases,
+                        enemy_ships,
+                    },
+                    &mut rng,
+                );
+                settle_air_base_waves_impl(&tx, profile_id, area_id, &simulation.bases).await?;
+
+                let damage: i64 = simulation.base_damage.iter().sum();
+                if damage > 0 {
+                    let material = get_mat_impl(&tx, profile_id).await?;
+                    deduct_material_impl(
+                        &tx,
+                        profile_id,
+                        &[
+                            (MaterialCategory::Fuel, damage.min(material.fuel)),
+                            (MaterialCategory::Bauxite, damage.min(material.bauxite)),
+                        ],
+                    )
+                    .await?;
+                }
+
+                tx.commit().await?;
+                active.air_raid_cell_id = Some(active.current_cell_id);
+                let _ = store.insert_active(profile_id, active);
+                Ok(simulation.packet)
+            })
+            .await
+    }
+
     async fn clear_sortie_state_if_any(&self, profile_id: i64) {
         let store = self.sortie_store();
         clear_pending_sortie_runtime_state(store, profile_id);
@@ -1009,6 +1303,20 @@ async fn refresh_sortie_stage(
     Ok(true)
 }
 
+/// The stage `active` plays, with the gimmicks in effect for the sortie applied.
+fn sortie_stage<'a>(
+    definition: &'a MapDefinition,
+    active: &ActiveSortieState,
+) -> Result<Cow<'a, MapStageDefinition>, GameplayError> {
+    let stage = definition.stage(&active.stage_id).ok_or_else(|| {
+        GameplayError::EntryNotFound(format!(
+            "stage `{}` not found for map {}",
+            active.stage_id, active.map_id,
+        ))
+    })?;
+    Ok(apply_gimmicks(definition, stage, &active.gimmick_ids))
+}
+
 async fn sortie_battle_impl(
     store: &crate::game::sortie_store::SortieStore,
     codex: &Codex,
@@ -1016,6 +1324,7 @@ async fn sortie_battle_impl(
     profile_id: i64,
     formation_id: i64,
     battle_type: BattleType,
+    now: DateTime<Utc>,
 ) -> Result<SortieBattleResponse, GameplayError> {
     store
         .with_profile_lock(profile_id, async {
@@ -1031,22 +1340,13 @@ async fn sortie_battle_impl(
             }
 
             let profile = find_profile(&tx, profile_id).await?;
-            if profile.combined_type > 0 {
-                return Err(GameplayError::WrongType(
-                    "combined sortie battle is not implemented yet".to_string(),
-                ));
-            }
 
             let catalog = active_map_catalog(codex);
             let definition = catalog.as_ref().map_definition(active.map_id).ok_or_else(|| {
                 GameplayError::EntryNotFound(format!("map definition {} not found", active.map_id))
             })?;
-            let stage = definition.stage(&active.stage_id).ok_or_else(|| {
-                GameplayError::EntryNotFound(format!(
-                    "stage `{}` not found for map {}",
-                    active.stage_id, active.map_id,
-                ))
-            })?;
+            let stage = sortie_stage(definition, &active)?;
+            let stage = &*stage;
             let current_cell = stage.cell(active.current_cell_id).ok_or_else(|| {
                 GameplayError::EntryNotFound(format!(
                     "cell {} not found in map {}",
@@ -1060,7 +1360,10 @@ async fn sortie_battle_impl(
                 )));
             }
 
-            let fleet_ships = get_fleet_ships_impl(&tx, profile_id, active.deck_id).await?;
+            let fleet_ships = without_escaped_ships(
+                get_fleet_ships_impl(&tx, profile_id, active.deck_id).await?,
+                &active,
+            );
             if fleet_ships.is_empty() {
                 return Err(GameplayError::WrongType(format!(
                     "fleet {} has no ships for sortie battle",
@@ -1069,6 +1372,7 @@ async fn sortie_battle_impl(
             }
 
             let friend_ships = build_sortie_friend_ships(&tx, &fleet_ships).await?;
+            let friend_escort = build_sortie_escort_ships(&tx, profile_id, &active).await?;
             let enemy_fleet =
                 resolve_sortie_enemy_fleet(active.map_id, stage, current_cell.cell_no);
             let enemy_composition = active
@@ -1076,8 +1380,45 @@ async fn sortie_battle_impl(
                 .clone()
                 .or_else(|| select_random_enemy_composition(&enemy_fleet))
                 .unwrap_or_else(|| fallback_enemy_composition(current_cell.cell_no));
-            let (enemy_ships, enemy_level, enemy_rank, enemy_deck_name) =
+            let (mut enemy_ships, enemy_level, enemy_rank, enemy_deck_name) =
                 build_sortie_enemy_ships(codex, definition, &enemy_fleet, &enemy_composition)?;
+            apply_boss_debuff(stage, current_cell.cell_no, &mut enemy_ships);
+            let enemy_escort = split_enemy_escort(&mut enemy_ships);
+
+            let (area_id, _) = split_map_id(active.map_id);
+            let air_base_waves = build_air_base_waves_impl(
+                &tx,
+                profile_id,
+                area_id,
+                &active.air_base_strikes,
+                active.current_cell_id,
+            )
+            .await?;
+            let boss = stage.boss_cell_nos().contains(&current_cell.cell_no);
+            let support_fleet = find_support_fleet_impl(
+                &tx,
+                codex,
+                profile_id,
+                area_id,
+                if boss {
+                    SupportMissionKind::Boss
+                } else {
+                    SupportMissionKind::Node
+                },
+                now,
+            )
+            .await?;
+            let support = match &support_fleet {
+                Some(support_fleet) => {
+                    let ships = get_fleet_ships_impl(&tx, profile_id, support_fleet.index).await?;
+                    Some(BattleSupportInput {
+                        deck_id: support_fleet.index,
+                        ships: build_sortie_friend_ships(&tx, &ships).await?,
+                        boss,
+                    })
+                }
+                None => None,
+            };
 
             let mut rng = ProductionRng;
             let session = run_day_battle(
@@ -1096,10 +1437,28 @@ async fn sortie_battle_impl(
                         engagement: engagement_for_cell(active.map_id, active.current_cell_id),
                         friend_ships: friend_ships.clone(),
                         enemy_ships: enemy_ships.clone(),
+                        air_base_waves,
+                        combined: combined_fleet_input(
+                            &active,
+                            friend_escort.clone(),
+                            enemy_escort.clone(),
+                        ),
+                        support,
+                        historical_bonuses: node_historical_bonuses(
+                            codex,
+                            active.map_id,
+                            active.current_cell_id,
+                        ),
                     },
                 },
                 &mut rng,
             );
+            settle_air_base_waves_impl(&tx, profile_id, area_id, &session.air_base_waves).await?;
+            if session.packet.support_flag > 0
+                && let Some(support_fleet) = &support_fleet
+            {
+                charge_support_fleet_impl(&tx, codex, profile_id, support_fleet).await?;
+            }
 
             let base_exp = calculate_sortie_base_exp(active.map_level, active.current_cell_id);
             let get_exp =
@@ -1122,6 +1481,8 @@ async fn sortie_battle_impl(
                 active.deck_id,
                 friend_ships,
                 enemy_ships,
+                &friend_escort,
+                &enemy_escort,
                 session.packet.clone(),
             );
             store.insert_pending_result(
@@ -1130,12 +1491,12 @@ async fn sortie_battle_impl(
                     friendly_ship_ids: session.friendly_ship_ids.clone(),
                     enemy_ship_ids: session.enemy_ship_ids.clone(),
                     friendly_nowhps,
-                    enemy_ship_types: session
-                        .enemy_ship_ids
-                        .iter()
-                        .map(|&id| codex.find::<ApiMstShip>(&id).map(|m| m.api_stype).unwrap_or(0))
-                        .collect(),
-                    enemy_nowhps: session.packet.enemy_nowhps.clone(),
+                    enemy_ship_types: enemy_ship_types(
+                        codex,
+                        &session.enemy,
+                        &session.enemy_escort,
+                    ),
+                    enemy_nowhps: enemy_nowhps(&session.enemy, &session.enemy_escort),
                     win_rank: session.outcome.win_rank.to_string(),
                     get_exp,
                     member_lv: profile.hq_level,
@@ -1149,10 +1510,12 @@ async fn sortie_battle_impl(
                     enemy_level,
                     enemy_rank,
                     enemy_deck_name,
+                    escort: build_sortie_escort_result(base_exp, &session.friendly_escort),
                 },
             );
 
             active.pending_battle_cell_id = Some(active.current_cell_id);
+            active.pending_escape = None;
 
             tx.commit().await?;
             let _ = store.insert_active(profile_id, active);
@@ -1161,6 +1524,95 @@ async fn sortie_battle_impl(
         .await
 }
 
+/// Map `[escapee, tow]` ship ids to the 1-based combined-fleet positions of
+/// `api_escape` (escort ships are 7–12).
+async fn build_sortie_escape_positions<C>(
+    c: &C,
+    profile_id: i64,
+    [escapee, tow]: [i64; 2],
+) -> Result<SortieBattleResultEscape, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    let main = get_fleet_ships_impl(c, profile_id, 1).await?;
+    let escort = get_fleet_ships_impl(c, profile_id, 2).await?;
+    let position = |ship_id: i64| {
+        main.iter()
+            .position(|ship| ship.id == ship_id)
+            .or_else(|| escort.iter().position(|ship| ship.id == ship_id).map(|idx| idx + 6))
+            .map_or(-1, |idx| idx as i64 + 1)
+    };
+    Ok(SortieBattleResultEscape {
+        api_escape_idx: vec![position(escapee)],
+        api_tow_idx: vec![position(tow)],
+    })
+}
+
+/// Drop ships that retreated earlier in the sortie.
+fn without_escaped_ships(ships: Vec<ship::Model>, active: &ActiveSortieState) -> Vec<ship::Model> {
+    ships.into_iter().filter(|ship| !active.escaped_ship_ids.contains(&ship.id)).collect()
+}
+
+/// Escort fleet (deck 2) of a combined sortie; empty for a single fleet.
+async fn build_sortie_escort_ships<C>(
+    c: &C,
+    profile_id: i64,
+    active: &ActiveSortieState,
+) -> Result<Vec<BattleShipInput>, GameplayError>
+where
+    C: ConnectionTrait,
+{
+    if active.combined_type == 0 {
+        return Ok(Vec::new());
+    }
+    let escort = without_escaped_ships(get_fleet_ships_impl(c, profile_id, 2).await?, active);
+    build_sortie_friend_ships(c, &escort).await
+}
+
+/// Enemy compositions of more than six ships are combined fleets: ships 7–12
+/// form the escort.
+fn split_enemy_escort(enemy_ships: &mut Vec<BattleShipInput>) -> Vec<BattleShipInput> {
+    if enemy_ships.len() > 6 {
+        enemy_ships.split_off(6)
+    } else {
+        Vec::new()
+    }
+}
+
+fn combined_fleet_input(
+    active: &ActiveSortieState,
+    friend_escort_ships: Vec<BattleShipInput>,
+    enemy_escort_ships: Vec<BattleShipInput>,
+) -> Option<CombinedFleetInput> {
+    if friend_escort_ships.is_empty() && enemy_escort_ships.is_empty() {
+        return None;
+    }
+    Some(CombinedFleetInput {
+        friendly_type: CombinedFleetType::from_api_id(active.combined_type),
+        friend_escort_ships,
+        enemy_escort_ships,
+    })
+}
+
+/// Ship types of the enemy main fleet followed by its escort, for sink quests.
+fn enemy_ship_types(
+    codex: &Codex,
+    enemy: &[BattleRuntimeShip],
+    enemy_escort: &[BattleRuntimeShip],
+) -> Vec<i64> {
+    enemy
+        .iter()
+        .chain(enemy_escort)
+        .map(|ship| {
+            codex.find::<ApiMstShip>(&ship.ship.api_ship_id).map(|m| m.api_stype).unwrap_or(0)
+        })
+        .collect()
+}
+
+fn enemy_nowhps(enemy: &[BattleRuntimeShip], enemy_escort: &[BattleRuntimeShip]) -> Vec<i64> {
+    enemy.iter().chain(enemy_escort).map(|ship| ship.hp().max(0)).collect()
+}
+
 fn build_sortie_cell_data(map_id: i64, stage: &MapStageDefinition) -> Vec<SortieCellData> {
     stage
         .cells
@@ -1188,6 +1640,12 @@ fn select_start_source_cell(stage: &MapStageDefinition) -> Result<&MapCellDefini
     }
 }
 
+/// Destinations offered to the player on arriving at `cell`, if it is a
+/// player-chosen branch.
+fn sortie_select_route(cell: &MapCellDefinition) -> Option<Vec<i64>> {
+    (!cell.select_cells.is_empty()).then(|| cell.select_cells.clone())
+}
+
 fn default_sortie_airsearch() -> SortieAirSearch {
     SortieAirSearch {
         plane_type: 0,
@@ -1231,9 +1689,34 @@ fn sortie_bosscomp(stage: &MapStageDefinition) -> bool {
     stage.enemy_fleets.contains_key(&stage.boss_cell_no)
 }
 
+/// `event_id` of an air raid cell (空襲戦), whose enemy raids the area's airbases.
+const AIR_RAID_EVENT_ID: i64 = 10;
+
+/// Reject an airbase raid off an air raid cell, or one already resolved there.
+fn ensure_air_raid_pending(
+    active: &ActiveSortieState,
+    cell: &MapCellDefinition,
+) -> Result<(), GameplayError> {
+    if cell.event_id != AIR_RAID_EVENT_ID {
+        return Err(GameplayError::WrongType(format!(
+            "cell {} is not an air raid cell",
+            cell.cell_no
+        )));
+    }
+    if active.air_raid_cell_id == Some(cell.cell_no) {
+        return Err(GameplayError::WrongType(format!(
+            "air raid at cell {} is already resolved",
+            cell.cell_no
+        )));
+    }
+
+    Ok(())
+}
+
 /// `KanColle` `event_id` values for non-battle cells:
 /// 0 = start, 1 = no event, 2 = resource obtain, 3 = maelstrom (渦潮),
-/// 4 = normal battle, 5 = boss, 6 = imaginary (気のせい), 7 = air battle.
+/// 4 = normal battle, 5 = boss, 6 = imaginary (気のせい), 7 = air battle,
+/// 10 = air raid (空襲戦).
 ///
 /// Resolve resource acquisition or maelstrom loss for the given cell.
 /// Only `event_kind`=0 cells produce effects; battle cells are handled elsewhere.
@@ -1,3 +1,4 @@
+use emukc_battle::{BattleRuntimeShip, BattleShipInput, calculate_mvp};
 use emukc_crypto::rng;
 use emukc_db::{
     entity::profile::ship,
@@ -6,25 +7,26 @@ use emukc_db::{
 use emukc_model::{
     codex::{
         Codex,
-        map::{MapDefinition, MapStageDefinition, MapVariantDefinition},
+        map::{MapDefinition, MapStageDefinition, MapVariantDefinition, ShipDropTable},
     },
     kc2::{KcSortieResultRank, level},
+    profile::map_record::MapGaugeType,
     thirdparty::QuestActionEvent,
 };
-use emukc_time::chrono::Utc;
-use serde::Serialize;
+use emukc_time::chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use crate::err::GameplayError;
 
 use super::{
     basic::find_profile,
     map::find_map_record_impl,
-    map_progress::assign_stage_id,
+    map_progress::{assign_stage_id, transport_gauge_damage},
     ship::{add_ship_impl, update_ship_impl},
     sortie::ActiveSortieState,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SortieBattleResultSnapshot {
     pub friendly_ship_ids: Vec<i64>,
     pub enemy_ship_ids: Vec<i64>,
@@ -44,6 +46,18 @@ pub struct SortieBattleResultSnapshot {
     pub enemy_level: i64,
     pub enemy_rank: String,
     pub enemy_deck_name: String,
+    /// Escort fleet results of a combined-fleet battle.
+    pub escort: Option<SortieEscortResult>,
+}
+
+/// Escort fleet (第二艦隊) half of a combined-fleet battle result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortieEscortResult {
+    pub ship_ids: Vec<i64>,
+    pub nowhps: Vec<i64>,
+    pub mvp: i64,
+    pub get_ship_exp: Vec<i64>,
+    pub get_exp_lvup: Vec<Vec<i64>>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -84,8 +98,29 @@ pub struct SortieBattleResultResponse {
     pub api_get_ship: Option<SortieBattleResultGetShip>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_next_map_ids: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_mvp_combined: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_get_ship_exp_combined: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_get_exp_lvup_combined: Option<Vec<Vec<i64>>>,
+    /// 1 when a damaged ship may be sent home (`api_req_combined_battle/goback_port`).
+    pub api_escape_flag: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_escape: Option<SortieBattleResultEscape>,
+}
+
+/// Escape offer of a combined-fleet battle result, in 1-based combined-fleet
+/// positions (escort ships are 7–12).
+#[derive(Debug, Clone, Serialize)]
+pub struct SortieBattleResultEscape {
+    pub api_escape_idx: Vec<i64>,
+    pub api_tow_idx: Vec<i64>,
 }
 
+/// 艦隊司令部施設 slot item master id.
+const FLEET_COMMAND_FACILITY: i64 = 107;
+
 pub(super) fn calculate_sortie_base_exp(map_level: i64, cell_id: i64) -> i64 {
     (map_level.max(1) * 25 + cell_id * 10).clamp(30, 1200)
 }
@@ -144,6 +179,67 @@ pub(super) fn calculate_sortie_ship_exp(
     (exp, lvup)
 }
 
+/// MVP and experience of a combined fleet's escort; `None` for a single fleet.
+pub(super) fn build_sortie_escort_result(
+    base_exp: i64,
+    escort: &[BattleRuntimeShip],
+) -> Option<SortieEscortResult> {
+    if escort.is_empty() {
+        return None;
+    }
+    let nowhps: Vec<i64> = escort.iter().map(|ship| ship.hp().max(0)).collect();
+    let inputs: Vec<BattleShipInput> = escort
+        .iter()
+        .cloned()
+        .map(|ship| BattleShipInput {
+            ship: ship.ship,
+            slot_items: ship.slot_items,
+            effect_list: ship.effect_list,
+            married: ship.married,
+        })
+        .collect();
+    let mvp = calculate_mvp(escort);
+    let (get_ship_exp, get_exp_lvup) =
+        calculate_sortie_ship_exp(&inputs, base_exp, mvp, &nowhps, false, 1.0);
+    Some(SortieEscortResult {
+        ship_ids: escort.iter().map(|ship| ship.ship.api_id).collect(),
+        nowhps,
+        mvp,
+        get_ship_exp,
+        get_exp_lvup,
+    })
+}
+
+/// Pick the retreat a fleet command facility (艦隊司令部施設) offers after a
+/// combined-fleet battle.
+///
+/// The main flagship must carry the facility. The first heavily damaged ship
+/// other than either flagship goes home, towed by the first escort destroyer
+/// that is not yet moderately damaged. Returns the `[escapee, tow]` ship ids.
+pub(super) fn select_sortie_escape(
+    main: &[BattleRuntimeShip],
+    escort: &[BattleRuntimeShip],
+    ship_type: impl Fn(&BattleRuntimeShip) -> i64,
+) -> Option<[i64; 2]> {
+    let has_facility = main.first().is_some_and(|flagship| {
+        flagship.slot_items.iter().any(|item| item.api_slotitem_id == FLEET_COMMAND_FACILITY)
+    });
+    if !has_facility || escort.is_empty() {
+        return None;
+    }
+    let escapee = main
+        .iter()
+        .skip(1)
+        .chain(escort.iter().skip(1))
+        .find(|ship| ship.hp() > 0 && ship.hp() * 4 <= ship.ship.api_maxhp)?;
+    let tow = escort.iter().skip(1).find(|ship| {
+        ship.ship.api_id != escapee.ship.api_id
+            && ship_type(ship) == 2
+            && ship.hp() * 2 > ship.ship.api_maxhp
+    })?;
+    Some([escapee.ship.api_id, tow.ship.api_id])
+}
+
 fn build_exp_lvup_vector(before_exp: i64, after_exp: i64) -> Vec<i64> {
     let mut result = vec![before_exp];
     let (_, mut next_exp) = level::exp_to_ship_level(before_exp);
@@ -203,30 +299,6 @@ fn parse_sortie_result_rank(win_rank: &str) -> Result<KcSortieResultRank, Gamepl
     }
 }
 
-pub(super) fn eligible_sortie_ship_drops<'a>(
-    codex: &Codex,
-    variant: &'a MapVariantDefinition,
-    cell_no: i64,
-    win_rank: &str,
-) -> Vec<&'a emukc_model::codex::map::ShipDropDefinition> {
-    if !matches!(win_rank, "S" | "A" | "B") {
-        return Vec::new();
-    }
-
-    variant
-        .ship_drops(cell_no)
-        .map(|drops| {
-            drops
-                .iter()
-                .filter(|drop| {
-                    !drop.tags.iter().any(|tag| tag == "limited")
-                        && codex.new_ship(drop.ship_id).is_some()
-                })
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
 pub(super) async fn try_grant_sortie_ship_drop<C>(
     c: &C,
     codex: &Codex,
@@ -234,16 +306,20 @@ pub(super) async fn try_grant_sortie_ship_drop<C>(
     variant: &MapVariantDefinition,
     cell_no: i64,
     win_rank: &str,
+    difficulty: Option<i64>,
 ) -> Result<Option<SortieBattleResultGetShip>, GameplayError>
 where
     C: ConnectionTrait,
 {
-    let candidates = eligible_sortie_ship_drops(codex, variant, cell_no, win_rank);
-    if candidates.is_empty() {
+    let table = ShipDropTable::build(codex, variant, cell_no, win_rank, difficulty);
+    let total_weight = table.total_weight();
+    if total_weight <= 0 {
         return Ok(None);
     }
 
-    let selected = { candidates[rng::usize(0..candidates.len())] };
+    let Some(selected) = table.select_for_roll(rng::f64(), rng::u64(0..total_weight as u64)) else {
+        return Ok(None);
+    };
     let mst = codex
         .manifest
         .find_ship(selected.ship_id)
@@ -297,14 +373,45 @@ where
     am.hq_level = ActiveValue::Set(hq_level);
     let updated_profile = am.update(c).await?;
 
-    for (idx, ship_id) in snapshot.friendly_ship_ids.iter().copied().enumerate() {
+    apply_sortie_ship_results(
+        c,
+        codex,
+        &snapshot.friendly_ship_ids,
+        &snapshot.friendly_nowhps,
+        &snapshot.get_ship_exp,
+    )
+    .await?;
+    if let Some(escort) = &snapshot.escort {
+        apply_sortie_ship_results(c, codex, &escort.ship_ids, &escort.nowhps, &escort.get_ship_exp)
+            .await?;
+    }
+
+    snapshot.member_lv = updated_profile.hq_level;
+    snapshot.member_exp = updated_profile.experience;
+    Ok(snapshot)
+}
+
+/// Write one fleet's battle HP, supply cost and experience back to its ships.
+///
+/// `get_ship_exp` is the API array, whose first entry is the `-1` placeholder.
+async fn apply_sortie_ship_results<C>(
+    c: &C,
+    codex: &Codex,
+    ship_ids: &[i64],
+    nowhps: &[i64],
+    get_ship_exp: &[i64],
+) -> Result<(), GameplayError>
+where
+    C: ConnectionTrait,
+{
+    for (idx, ship_id) in ship_ids.iter().copied().enumerate() {
         let ship_model = ship::Entity::find_by_id(ship_id).one(c).await?.ok_or_else(|| {
             GameplayError::EntryNotFound(format!("ship with id {ship_id} not found"))
         })?;
         let mut api_ship: emukc_model::kc2::KcApiShip = ship_model.into();
 
         // Apply battle damage: update HP from battle result.
-        let final_hp = snapshot.friendly_nowhps.get(idx).copied().unwrap_or(1);
+        let final_hp = nowhps.get(idx).copied().unwrap_or(1);
         api_ship.api_nowhp = final_hp.max(0);
         let is_sunk = final_hp <= 0;
 
@@ -323,7 +430,7 @@ where
             }
 
             // Apply EXP gain.
-            let gain = snapshot.get_ship_exp.get(idx + 1).copied().unwrap_or(-1);
+            let gain = get_ship_exp.get(idx + 1).copied().unwrap_or(-1);
             if gain > 0 {
                 let raw_exp = ship_model.exp_now + gain;
                 let (ship_level, next_exp) = level::exp_to_ship_level(raw_exp);
@@ -351,12 +458,10 @@ where
 
         update_ship_impl(c, codex, &api_ship).await?;
     }
-
-    snapshot.member_lv = updated_profile.hq_level;
-    snapshot.member_exp = updated_profile.experience;
-    Ok(snapshot)
+    Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn apply_sortie_map_result<C>(
     c: &C,
     profile_id: i64,
@@ -364,6 +469,8 @@ pub(super) async fn apply_sortie_map_result<C>(
     stage: &MapStageDefinition,
     is_boss_cell: bool,
     snapshot: &SortieBattleResultSnapshot,
+    transport_points: i64,
+    now: DateTime<Utc>,
 ) -> Result<i64, GameplayError>
 where
     C: ConnectionTrait,
@@ -379,7 +486,6 @@ where
     }
 
     let record = find_map_record_impl(c, profile_id, definition.map_id).await?;
-    let now = Utc::now();
     let was_cleared = record.cleared;
     let current_hp = record.current_hp;
     let current_gauge_index = record.gauge_index;
@@ -399,7 +505,12 @@ where
     if let Some(max_hp) = definition.max_hp {
         // Shared stage/gauge state stays in place, but event-specific API expansion is not part of
         // the current non-event-map roadmap.
-        let next_hp = (current_hp.unwrap_or(max_hp) - 1).max(0);
+        let damage = if definition.gauge_type == Some(MapGaugeType::Landing as i64) {
+            transport_gauge_damage(transport_points, &snapshot.win_rank)
+        } else {
+            1
+        };
+        let next_hp = (current_hp.unwrap_or(max_hp) - damage).max(0);
         let stage_cleared = next_hp <= 0;
         if !stage_cleared {
             am.current_hp = ActiveValue::Set(Some(next_hp));
@@ -500,6 +611,7 @@ mod tests {
             enemy_level: 0,
             enemy_rank: String::new(),
             enemy_deck_name: String::new(),
+            escort: None,
         }
     }
 
@@ -541,6 +653,12 @@ mod tests {
             pending_battle_cell_id: Some(3),
             visited_cell_ids: BTreeSet::new(),
             locked_enemy_composition: None,
+            air_base_strikes: Vec::new(),
+            combined_type: 0,
+            escaped_ship_ids: Vec::new(),
+            pending_escape: None,
+            gimmick_ids: BTreeSet::new(),
+            air_raid_cell_id: None,
         };
 
         let event = build_sortie_quest_event(&definition, &active, &snapshot("A")).unwrap();
@@ -611,6 +729,12 @@ mod tests {
             pending_battle_cell_id: Some(4),
             visited_cell_ids: BTreeSet::new(),
             locked_enemy_composition: None,
+            air_base_strikes: Vec::new(),
+            combined_type: 0,
+            escaped_ship_ids: Vec::new(),
+            pending_escape: None,
+            gimmick_ids: BTreeSet::new(),
+            air_raid_cell_id: None,
         };
 
         let event = build_sortie_quest_event(&definition, &active, &snapshot("S")).unwrap();
@@ -626,7 +750,7 @@ mod tests {
     }
 
     #[test]
-    fn eligible_sortie_ship_drops_keep_only_non_limited_known_ships() {
+    fn sortie_ship_drop_table_keeps_only_non_limited_known_ships() {
         let codex = Codex::load_without_cache_source("../../.data/codex").unwrap();
         let variant = MapVariantDefinition {
             ship_drops: BTreeMap::from([(
@@ -636,26 +760,29 @@ mod tests {
                         ship_id: 1,
                         raw_ship_name: "睦月".to_string(),
                         tags: Vec::new(),
+                        weights: None,
                     },
                     ShipDropDefinition {
                         ship_id: 2,
                         raw_ship_name: "如月".to_string(),
                         tags: vec!["limited".to_string()],
+                        weights: None,
                     },
                     ShipDropDefinition {
                         ship_id: 999_999,
                         raw_ship_name: "unknown".to_string(),
                         tags: Vec::new(),
+                        weights: None,
                     },
                 ],
             )]),
             ..Default::default()
         };
 
-        let drops = eligible_sortie_ship_drops(&codex, &variant, 1, "S");
+        let table = ShipDropTable::build(&codex, &variant, 1, "S", None);
 
-        assert_eq!(drops.len(), 1);
-        assert_eq!(drops[0].ship_id, 1);
+        assert_eq!(table.entries.len(), 1);
+        assert_eq!(table.entries[0].drop.ship_id, 1);
     }
 
     // --- gauge progression tests ---
@@ -708,6 +835,7 @@ mod tests {
             selected_rank: ActiveValue::Set(map_record::SelectedRank::NotSet),
             event_state: ActiveValue::Set(None),
             unlocked: ActiveValue::Set(true),
+            gimmick_state: ActiveValue::Set(None),
             ..Default::default()
         };
         record.insert(db).await.unwrap();
@@ -739,7 +867,9 @@ mod tests {
 
         let snap = snapshot("S");
         let result =
-            apply_sortie_map_result(&db, pid, &definition, &stage, true, &snap).await.unwrap();
+            apply_sortie_map_result(&db, pid, &definition, &stage, true, &snap, 0, Utc::now())
+                .await
+                .unwrap();
         assert_eq!(result, 0, "gauge advance should not report first-clear");
 
         let idx = get_gauge_index(&db, pid, definition.map_id).await;
@@ -769,13 +899,16 @@ mod tests {
             selected_rank: ActiveValue::Set(map_record::SelectedRank::NotSet),
             event_state: ActiveValue::Set(None),
             unlocked: ActiveValue::Set(true),
+            gimmick_state: ActiveValue::Set(None),
             ..Default::default()
         };
         record.insert(&db).await.unwrap();
 
         let snap = snapshot("S");
         let result =
-            apply_sortie_map_result(&db, pid, &definition, &stage, true, &snap).await.unwrap();
+            apply_sortie_map_result(&db, pid, &definition, &stage, true, &snap, 0, Utc::now())
+                .await
+                .unwrap();
         assert_eq!(result, 1, "final gauge clear should report first-clear");
 
         let cleared = is_cleared(&db, pid, definition.map_id).await;
@@ -792,11 +925,71 @@ mod tests {
 
         let snap = snapshot("S");
         let result =
-            apply_sortie_map_result(&db, pid, &definition, &stage, true, &snap).await.unwrap();
+            apply_sortie_map_result(&db, pid, &definition, &stage, true, &snap, 0, Utc::now())
+                .await
+                .unwrap();
         assert_eq!(result, 1, "single-gauge clear should report first-clear");
         assert!(is_cleared(&db, pid, definition.map_id).await);
     }
 
+    #[tokio::test]
+    async fn transport_gauge_drops_by_fleet_tp() {
+        let db = emukc_db::prelude::new_mem_db().await.unwrap();
+        let pid = insert_test_profile(&db).await;
+        let definition = MapDefinition {
+            gauge_type: Some(MapGaugeType::Landing as i64),
+            ..gauge_map_definition(1, 100)
+        };
+        let stage = gauge_stage();
+        insert_gauge_record(&db, pid, definition.map_id).await;
+        let mut am = get_record(&db, pid, definition.map_id).await.into_active_model();
+        am.current_hp = ActiveValue::Set(Some(100));
+        am.update(&db).await.unwrap();
+
+        apply_sortie_map_result(
+            &db,
+            pid,
+            &definition,
+            &stage,
+            true,
+            &snapshot("A"),
+            40,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_record(&db, pid, definition.map_id).await.current_hp, Some(72));
+
+        apply_sortie_map_result(
+            &db,
+            pid,
+            &definition,
+            &stage,
+            true,
+            &snapshot("B"),
+            40,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_record(&db, pid, definition.map_id).await.current_hp, Some(72));
+
+        let result = apply_sortie_map_result(
+            &db,
+            pid,
+            &definition,
+            &stage,
+            true,
+            &snapshot("S"),
+            80,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result, 1);
+        assert!(is_cleared(&db, pid, definition.map_id).await);
+    }
+
     #[tokio::test]
     async fn non_boss_does_not_advance_gauge() {
         let db = emukc_db::prelude::new_mem_db().await.unwrap();
@@ -807,7 +1000,9 @@ mod tests {
 
         let snap = snapshot("S");
         let result =
-            apply_sortie_map_result(&db, pid, &definition, &stage, false, &snap).await.unwrap();
+            apply_sortie_map_result(&db, pid, &definition, &stage, false, &snap, 0, Utc::now())
+                .await
+                .unwrap();
         assert_eq!(result, 0);
 
         let idx = get_gauge_index(&db, pid, definition.map_id).await;
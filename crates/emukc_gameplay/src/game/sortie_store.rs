@@ -6,29 +6,75 @@
 //! falls back to a process-global instance, so existing tuple-based test
 //! contexts keep working.  The binary-crate [`State`] overrides it with an
 //! instance-scoped store, giving each route-test its own isolated copy.
+//!
+//! A store opened with a journal (see [`SortieStore::with_journal`]) also
+//! appends every change to it, so in-flight sorties and practice battles
+//! survive a server restart.
 
-use std::{collections::HashMap, fmt, future::Future, sync::Arc, sync::LazyLock};
+use std::{
+    borrow::Cow, collections::HashMap, fmt, future::Future, io, path::Path, sync::Arc,
+    sync::LazyLock,
+};
 
+use emukc_time::chrono::{DateTime, Duration, Utc};
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex as AsyncMutex;
 
 use super::{
+    airbase::AirBaseStrike,
     battle::{
         practice::{PracticeBattleResultSnapshot, PracticeBattleSession},
         practice_repository::PracticeRepository,
         repository::SortieRepository,
         sortie::SortieBattleSession,
     },
+    session_journal::{JournalEntry, SessionJournal},
     sortie::ActiveSortieState,
     sortie_result::SortieBattleResultSnapshot,
 };
 
+/// A journaled change to a [`SortieStore`]; `None` removes the entry.
+///
+/// Ops only live for one append or replay step, so their size does not matter.
+#[expect(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+enum SortieJournalOp<'a> {
+    Active(Option<Cow<'a, ActiveSortieState>>),
+    PendingBattle(Option<Cow<'a, SortieBattleSession>>),
+    PendingResult(Option<Cow<'a, SortieBattleResultSnapshot>>),
+    AirBaseStrikes(Option<(i64, Cow<'a, [AirBaseStrike]>)>),
+    Clear,
+}
+
+/// A journaled change to a [`PracticeStore`]; `None` removes the entry.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+enum PracticeJournalOp<'a> {
+    PendingBattle(Option<Cow<'a, PracticeBattleSession>>),
+    PendingResult(Option<Cow<'a, PracticeBattleResultSnapshot>>),
+    Clear,
+}
+
+/// Last change time per profile, stamped on the compacted entries so idle
+/// sessions keep ageing across restarts.
+fn last_seen<O>(entries: &[JournalEntry<O>]) -> HashMap<i64, DateTime<Utc>> {
+    let mut seen = HashMap::new();
+    for entry in entries {
+        seen.insert(entry.profile_id, entry.at);
+    }
+    seen
+}
+
 /// Runtime state backing a single sortie lifecycle.
 pub struct SortieStore {
     active_sorties: Mutex<HashMap<i64, ActiveSortieState>>,
     pending_results: Mutex<HashMap<i64, SortieBattleResultSnapshot>>,
     pending_battles: Mutex<HashMap<i64, SortieBattleSession>>,
+    air_base_strikes: Mutex<HashMap<i64, (i64, Vec<AirBaseStrike>)>>,
     profile_locks: Mutex<HashMap<i64, Arc<AsyncMutex<()>>>>,
+    journal: Option<SessionJournal>,
 }
 
 impl SortieStore {
@@ -38,7 +84,94 @@ impl SortieStore {
             active_sorties: Mutex::new(HashMap::new()),
             pending_results: Mutex::new(HashMap::new()),
             pending_battles: Mutex::new(HashMap::new()),
+            air_base_strikes: Mutex::new(HashMap::new()),
             profile_locks: Mutex::new(HashMap::new()),
+            journal: None,
+        }
+    }
+
+    /// Create a store backed by the journal at `path`, restoring the sorties
+    /// it holds.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The journal file, created if missing.
+    /// * `ttl` - How long a profile's untouched sortie is kept.
+    pub fn with_journal(path: impl AsRef<Path>, ttl: Duration) -> io::Result<Self> {
+        let (journal, entries) =
+            SessionJournal::open::<SortieJournalOp<'static>>(path, ttl, Utc::now())?;
+        let seen = last_seen(&entries);
+
+        let store = Self::new();
+        for entry in entries {
+            store.apply(entry.profile_id, entry.op);
+        }
+
+        let mut snapshot = Vec::new();
+        let mut push = |profile_id: i64, op: SortieJournalOp<'static>| {
+            snapshot.push(JournalEntry {
+                at: seen[&profile_id],
+                profile_id,
+                op,
+            });
+        };
+        for (id, state) in store.active_sorties.lock().iter() {
+            push(*id, SortieJournalOp::Active(Some(Cow::Owned(state.clone()))));
+        }
+        for (id, session) in store.pending_battles.lock().iter() {
+            push(*id, SortieJournalOp::PendingBattle(Some(Cow::Owned(session.clone()))));
+        }
+        for (id, result) in store.pending_results.lock().iter() {
+            push(*id, SortieJournalOp::PendingResult(Some(Cow::Owned(result.clone()))));
+        }
+        for (id, (area, strikes)) in store.air_base_strikes.lock().iter() {
+            push(*id, SortieJournalOp::AirBaseStrikes(Some((*area, Cow::Owned(strikes.clone())))));
+        }
+        journal.rewrite(&snapshot)?;
+        if !snapshot.is_empty() {
+            info!("restored {} sortie session entries", snapshot.len());
+        }
+
+        Ok(Self {
+            journal: Some(journal),
+            ..store
+        })
+    }
+
+    fn record(&self, profile_id: i64, op: SortieJournalOp<'_>) {
+        if let Some(journal) = &self.journal {
+            journal.append(profile_id, &op);
+        }
+    }
+
+    /// Apply a replayed change without journaling it again.
+    fn apply(&self, profile_id: i64, op: SortieJournalOp<'static>) {
+        match op {
+            SortieJournalOp::Active(Some(state)) => {
+                self.active_sorties.lock().insert(profile_id, state.into_owned());
+            }
+            SortieJournalOp::Active(None) => {
+                self.active_sorties.lock().remove(&profile_id);
+            }
+            SortieJournalOp::PendingBattle(Some(session)) => {
+                self.pending_battles.lock().insert(profile_id, session.into_owned());
+            }
+            SortieJournalOp::PendingBattle(None) => {
+                self.pending_battles.lock().remove(&profile_id);
+            }
+            SortieJournalOp::PendingResult(Some(result)) => {
+                self.pending_results.lock().insert(profile_id, result.into_owned());
+            }
+            SortieJournalOp::PendingResult(None) => {
+                self.pending_results.lock().remove(&profile_id);
+            }
+            SortieJournalOp::AirBaseStrikes(Some((area, strikes))) => {
+                self.air_base_strikes.lock().insert(profile_id, (area, strikes.into_owned()));
+            }
+            SortieJournalOp::AirBaseStrikes(None) => {
+                self.air_base_strikes.lock().remove(&profile_id);
+            }
+            SortieJournalOp::Clear => self.clear_maps(),
         }
     }
 
@@ -49,7 +182,12 @@ impl SortieStore {
     }
 
     pub(super) fn remove_active_sortie(&self, profile_id: i64) -> Option<ActiveSortieState> {
-        self.active_sorties.lock().remove(&profile_id)
+        let mut active = self.active_sorties.lock();
+        let removed = active.remove(&profile_id);
+        if removed.is_some() {
+            self.record(profile_id, SortieJournalOp::Active(None));
+        }
+        removed
     }
 
     // ── pending results ─────────────────────────────────────────────
@@ -65,7 +203,12 @@ impl SortieStore {
         &self,
         profile_id: i64,
     ) -> Option<SortieBattleResultSnapshot> {
-        self.pending_results.lock().remove(&profile_id)
+        let mut results = self.pending_results.lock();
+        let taken = results.remove(&profile_id);
+        if taken.is_some() {
+            self.record(profile_id, SortieJournalOp::PendingResult(None));
+        }
+        taken
     }
 
     pub(super) fn insert_pending_result_sortie(
@@ -73,7 +216,9 @@ impl SortieStore {
         profile_id: i64,
         snapshot: SortieBattleResultSnapshot,
     ) {
-        self.pending_results.lock().insert(profile_id, snapshot);
+        let mut results = self.pending_results.lock();
+        self.record(profile_id, SortieJournalOp::PendingResult(Some(Cow::Borrowed(&snapshot))));
+        results.insert(profile_id, snapshot);
     }
 
     // ── pending battles ─────────────────────────────────────────────
@@ -87,21 +232,68 @@ impl SortieStore {
         profile_id: i64,
         session: SortieBattleSession,
     ) {
-        self.pending_battles.lock().insert(profile_id, session);
+        let mut battles = self.pending_battles.lock();
+        self.record(profile_id, SortieJournalOp::PendingBattle(Some(Cow::Borrowed(&session))));
+        battles.insert(profile_id, session);
     }
 
     pub(super) fn take_pending_battle_sortie(
         &self,
         profile_id: i64,
     ) -> Option<SortieBattleSession> {
-        self.pending_battles.lock().remove(&profile_id)
+        let mut battles = self.pending_battles.lock();
+        let taken = battles.remove(&profile_id);
+        if taken.is_some() {
+            self.record(profile_id, SortieJournalOp::PendingBattle(None));
+        }
+        taken
+    }
+
+    // ── airbase strikes ─────────────────────────────────────────────
+
+    /// Hold sortied airbases until the next sortie into `area_id`.
+    pub(crate) fn insert_air_base_strikes(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+        strikes: Vec<AirBaseStrike>,
+    ) {
+        let mut held = self.air_base_strikes.lock();
+        self.record(
+            profile_id,
+            SortieJournalOp::AirBaseStrikes(Some((area_id, Cow::Borrowed(&strikes)))),
+        );
+        held.insert(profile_id, (area_id, strikes));
+    }
+
+    /// Remove the held strikes, returning them only if they target `area_id`.
+    pub(crate) fn take_air_base_strikes(
+        &self,
+        profile_id: i64,
+        area_id: i64,
+    ) -> Vec<AirBaseStrike> {
+        let mut held = self.air_base_strikes.lock();
+        let taken = held.remove(&profile_id);
+        if taken.is_some() {
+            self.record(profile_id, SortieJournalOp::AirBaseStrikes(None));
+        }
+        match taken {
+            Some((area, strikes)) if area == area_id => strikes,
+            _ => Vec::new(),
+        }
     }
 
     /// Clear all runtime state.
     pub fn clear(&self) {
+        self.clear_maps();
+        self.record(0, SortieJournalOp::Clear);
+    }
+
+    fn clear_maps(&self) {
         self.active_sorties.lock().clear();
         self.pending_results.lock().clear();
         self.pending_battles.lock().clear();
+        self.air_base_strikes.lock().clear();
     }
 
     /// Acquire a per-profile serialization lock and run the given future.
@@ -151,7 +343,9 @@ impl SortieRepository for SortieStore {
         profile_id: i64,
         state: ActiveSortieState,
     ) -> Option<ActiveSortieState> {
-        self.active_sorties.lock().insert(profile_id, state)
+        let mut active = self.active_sorties.lock();
+        self.record(profile_id, SortieJournalOp::Active(Some(Cow::Borrowed(&state))));
+        active.insert(profile_id, state)
     }
 
     fn remove_active(&self, profile_id: i64) -> Option<ActiveSortieState> {
@@ -189,6 +383,7 @@ impl SortieRepository for SortieStore {
 pub struct PracticeStore {
     pending_battles: Mutex<HashMap<i64, PracticeBattleSession>>,
     pending_results: Mutex<HashMap<i64, PracticeBattleResultSnapshot>>,
+    journal: Option<SessionJournal>,
 }
 
 impl PracticeStore {
@@ -197,11 +392,82 @@ impl PracticeStore {
         Self {
             pending_battles: Mutex::new(HashMap::new()),
             pending_results: Mutex::new(HashMap::new()),
+            journal: None,
+        }
+    }
+
+    /// Create a store backed by the journal at `path`, restoring the
+    /// practice battles it holds.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The journal file, created if missing.
+    /// * `ttl` - How long a profile's untouched practice battle is kept.
+    pub fn with_journal(path: impl AsRef<Path>, ttl: Duration) -> io::Result<Self> {
+        let (journal, entries) =
+            SessionJournal::open::<PracticeJournalOp<'static>>(path, ttl, Utc::now())?;
+        let seen = last_seen(&entries);
+
+        let store = Self::new();
+        for entry in entries {
+            store.apply(entry.profile_id, entry.op);
+        }
+
+        let mut snapshot = Vec::new();
+        for (id, session) in store.pending_battles.lock().iter() {
+            snapshot.push(JournalEntry {
+                at: seen[id],
+                profile_id: *id,
+                op: PracticeJournalOp::PendingBattle(Some(Cow::Owned(session.clone()))),
+            });
+        }
+        for (id, result) in store.pending_results.lock().iter() {
+            snapshot.push(JournalEntry {
+                at: seen[id],
+                profile_id: *id,
+                op: PracticeJournalOp::PendingResult(Some(Cow::Owned(result.clone()))),
+            });
+        }
+        journal.rewrite(&snapshot)?;
+
+        Ok(Self {
+            journal: Some(journal),
+            ..store
+        })
+    }
+
+    fn record(&self, profile_id: i64, op: PracticeJournalOp<'_>) {
+        if let Some(journal) = &self.journal {
+            journal.append(profile_id, &op);
+        }
+    }
+
+    /// Apply a replayed change without journaling it again.
+    fn apply(&self, profile_id: i64, op: PracticeJournalOp<'static>) {
+        match op {
+            PracticeJournalOp::PendingBattle(Some(session)) => {
+                self.pending_battles.lock().insert(profile_id, session.into_owned());
+            }
+            PracticeJournalOp::PendingBattle(None) => {
+                self.pending_battles.lock().remove(&profile_id);
+            }
+            PracticeJournalOp::PendingResult(Some(result)) => {
+                self.pending_results.lock().insert(profile_id, result.into_owned());
+            }
+            PracticeJournalOp::PendingResult(None) => {
+                self.pending_results.lock().remove(&profile_id);
+            }
+            PracticeJournalOp::Clear => self.clear_maps(),
         }
     }
 
     /// Clear all runtime state.
     pub fn clear(&self) {
+        self.clear_maps();
+        self.record(0, PracticeJournalOp::Clear);
+    }
+
+    fn clear_maps(&self) {
         self.pending_battles.lock().clear();
         self.pending_results.lock().clear();
     }
@@ -228,11 +494,18 @@ impl PracticeRepository for PracticeStore {
     }
 
     fn insert_pending_battle(&self, profile_id: i64, session: PracticeBattleSession) {
-        self.pending_battles.lock().insert(profile_id, session);
+        let mut battles = self.pending_battles.lock();
+        self.record(profile_id, PracticeJournalOp::PendingBattle(Some(Cow::Borrowed(&session))));
+        battles.insert(profile_id, session);
     }
 
     fn take_pending_battle(&self, profile_id: i64) -> Option<PracticeBattleSession> {
-        self.pending_battles.lock().remove(&profile_id)
+        let mut battles = self.pending_battles.lock();
+        let taken = battles.remove(&profile_id);
+        if taken.is_some() {
+            self.record(profile_id, PracticeJournalOp::PendingBattle(None));
+        }
+        taken
     }
 
     fn get_pending_result(&self, profile_id: i64) -> Option<PracticeBattleResultSnapshot> {
@@ -240,11 +513,18 @@ impl PracticeRepository for PracticeStore {
     }
 
     fn insert_pending_result(&self, profile_id: i64, result: PracticeBattleResultSnapshot) {
-        self.pending_results.lock().insert(profile_id, result);
+        let mut results = self.pending_results.lock();
+        self.record(profile_id, PracticeJournalOp::PendingResult(Some(Cow::Borrowed(&result))));
+        results.insert(profile_id, result);
     }
 
     fn take_pending_result(&self, profile_id: i64) -> Option<PracticeBattleResultSnapshot> {
-        self.pending_results.lock().remove(&profile_id)
+        let mut results = self.pending_results.lock();
+        let taken = results.remove(&profile_id);
+        if taken.is_some() {
+            self.record(profile_id, PracticeJournalOp::PendingResult(None));
+        }
+        taken
     }
 }
 
@@ -252,6 +532,7 @@ impl PracticeRepository for PracticeStore {
 mod tests {
     use super::*;
     use crate::game::battle::practice_repository::PracticeRepository;
+    use crate::game::battle::repository::SortieRepository;
     use emukc_battle::BattleOutcome;
     use emukc_model::kc2::KcSortieResultRank;
 
@@ -320,6 +601,95 @@ mod tests {
         assert!(store.take_pending_result(42).is_none());
     }
 
+    #[test]
+    fn test_air_base_strikes_only_apply_to_their_area() {
+        let store = SortieStore::new();
+        let strike = AirBaseStrike {
+            rid: 1,
+            cells: [5, 7],
+        };
+
+        store.insert_air_base_strikes(1, 6, vec![strike]);
+        assert!(store.take_air_base_strikes(1, 7).is_empty());
+        assert!(store.take_air_base_strikes(1, 6).is_empty());
+
+        store.insert_air_base_strikes(1, 6, vec![strike]);
+        assert_eq!(store.take_air_base_strikes(1, 6), vec![strike]);
+        assert!(store.take_air_base_strikes(1, 6).is_empty());
+    }
+
+    fn journal_path(name: &str) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("emukc-store-{}-{}.jsonl", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn active_sortie(map_id: i64) -> ActiveSortieState {
+        ActiveSortieState {
+            deck_id: 1,
+            map_id,
+            map_name: "test".to_string(),
+            map_level: 1,
+            stage_id: "default".to_string(),
+            current_cell_id: 3,
+            boss_cell_id: 5,
+            pending_battle_cell_id: Some(3),
+            visited_cell_ids: [1, 3].into(),
+            locked_enemy_composition: None,
+            air_base_strikes: vec![],
+            combined_type: 0,
+            escaped_ship_ids: vec![],
+            pending_escape: None,
+            gimmick_ids: Default::default(),
+            air_raid_cell_id: None,
+        }
+    }
+
+    #[test]
+    fn test_journaled_stores_survive_reopen() {
+        let sortie_path = journal_path("sortie");
+        let practice_path = journal_path("practice");
+        let ttl = crate::game::DEFAULT_SESSION_TTL;
+        let strike = AirBaseStrike {
+            rid: 1,
+            cells: [5, 7],
+        };
+
+        {
+            let sortie = SortieStore::with_journal(&sortie_path, ttl).unwrap();
+            let _ = sortie.insert_active(1, active_sortie(11));
+            let _ = sortie.insert_active(2, active_sortie(12));
+            assert!(sortie.remove_active(2).is_some());
+            sortie.insert_air_base_strikes(1, 6, vec![strike]);
+
+            let practice = PracticeStore::with_journal(&practice_path, ttl).unwrap();
+            practice.insert_pending_battle(1, minimal_session(1));
+            practice.insert_pending_result(1, minimal_result(1));
+            assert!(practice.take_pending_result(1).is_some());
+        }
+
+        let sortie = SortieStore::with_journal(&sortie_path, ttl).unwrap();
+        let active = sortie.get_active(1).unwrap();
+        assert_eq!(active.map_id, 11);
+        assert_eq!(active.visited_cell_ids, [1, 3].into());
+        assert!(sortie.get_active(2).is_none());
+        assert_eq!(sortie.take_air_base_strikes(1, 6), vec![strike]);
+
+        let practice = PracticeStore::with_journal(&practice_path, ttl).unwrap();
+        assert_eq!(practice.get_pending_battle(1).unwrap().formation, [1, 1, 1]);
+        assert!(practice.get_pending_result(1).is_none());
+
+        // a cleared store stays cleared
+        sortie.clear();
+        drop(sortie);
+        let sortie = SortieStore::with_journal(&sortie_path, ttl).unwrap();
+        assert!(sortie.get_active(1).is_none());
+
+        std::fs::remove_file(&sortie_path).unwrap();
+        std::fs::remove_file(&practice_path).unwrap();
+    }
+
     #[test]
     fn test_practice_store_instances_are_isolated() {
         let a = PracticeStore::new();
@@ -15,7 +15,7 @@ use emukc_db::{
 use emukc_model::{
     codex::{
         Codex,
-        map::{EnemyFleetDefinition, MapDefinition, MapVariantDefinition},
+        map::{EnemyFleetDefinition, MapDefinition, MapVariantDefinition, ShipDropTable},
     },
     kc2::level,
     prelude::{ApiMstShip, Kc3rdEnemyShip, Kc3rdEnemyShipSlotInfo},
@@ -140,6 +140,7 @@ fn successful_boss_snapshot() -> SortieBattleResultSnapshot {
         enemy_level: 0,
         enemy_rank: String::new(),
         enemy_deck_name: String::new(),
+        escort: None,
     }
 }
 
@@ -320,6 +321,10 @@ async fn sortie_midnight_battle_updates_pending_snapshot() {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend.clone()],
                 enemy_ships: vec![enemy.clone()],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
         },
         &mut rng,
@@ -351,6 +356,7 @@ async fn sortie_midnight_battle_updates_pending_snapshot() {
             enemy_level: 1,
             enemy_rank: "Test".to_string(),
             enemy_deck_name: "Test".to_string(),
+            escort: None,
         },
     );
 
@@ -398,9 +404,14 @@ async fn sortie_sp_midnight_battle_runs_night_only() {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend.clone()],
                 enemy_ships: vec![enemy.clone()],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
         },
         1,
+        None,
         &mut rng,
     );
 
@@ -456,6 +467,10 @@ async fn sortie_god_mode_keeps_friendly_at_full_hp_end_to_end() {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
         },
         &mut rng,
@@ -520,6 +535,10 @@ async fn sortie_one_hit_kill_clears_enemies_and_rejects_night_battle() {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![friend],
                 enemy_ships: vec![enemy_a, enemy_b],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
         },
         &mut rng,
@@ -585,6 +604,7 @@ fn fallback_enemy_fleet_is_only_used_when_catalog_data_is_missing() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
     variant.enemy_fleets.insert(
         2,
@@ -610,6 +630,43 @@ fn fallback_enemy_fleet_is_only_used_when_catalog_data_is_missing() {
     assert_eq!(fallback.compositions[0].ship_ids, vec![1501]);
 }
 
+#[test]
+fn air_raid_resolves_once_and_only_on_air_raid_cells() {
+    let mut active = ActiveSortieState {
+        deck_id: 1,
+        map_id: 61,
+        map_name: "6-1".to_string(),
+        map_level: 1,
+        stage_id: String::new(),
+        current_cell_id: 3,
+        boss_cell_id: 9,
+        pending_battle_cell_id: None,
+        visited_cell_ids: BTreeSet::from([0, 3]),
+        locked_enemy_composition: None,
+        air_base_strikes: Vec::new(),
+        combined_type: 0,
+        escaped_ship_ids: Vec::new(),
+        pending_escape: None,
+        gimmick_ids: BTreeSet::new(),
+        air_raid_cell_id: None,
+    };
+    let mut cell = MapCellDefinition {
+        cell_no: 3,
+        color_no: 4,
+        event_id: 4,
+        event_kind: 1,
+        ..Default::default()
+    };
+
+    assert!(matches!(ensure_air_raid_pending(&active, &cell), Err(GameplayError::WrongType(_))));
+
+    cell.event_id = 10;
+    ensure_air_raid_pending(&active, &cell).unwrap();
+
+    active.air_raid_cell_id = Some(3);
+    assert!(matches!(ensure_air_raid_pending(&active, &cell), Err(GameplayError::WrongType(_))));
+}
+
 #[tokio::test]
 async fn maelstrom_drains_ship_resource_without_touching_profile_materials() {
     let db = new_mem_db().await.unwrap();
@@ -641,6 +698,7 @@ async fn maelstrom_drains_ship_resource_without_touching_profile_materials() {
         node_label: Some("H".to_string()),
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
 
     let (itemget, happening) = resolve_non_battle_node_effect(
@@ -703,6 +761,7 @@ async fn maelstrom_drains_ammo_when_color_no_is_4() {
         node_label: Some("H".to_string()),
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
 
     let (itemget, happening) = resolve_non_battle_node_effect(
@@ -795,6 +854,7 @@ async fn maelstrom_radar_reduces_fuel_loss_across_all_tiers() {
         node_label: Some("H".to_string()),
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
 
     const STOCK: i64 = 1000;
@@ -889,6 +949,7 @@ async fn maelstrom_zero_resource_ship_skips_loss_without_underflow() {
         node_label: Some("H".to_string()),
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
 
     let (itemget, happening) = resolve_non_battle_node_effect(
@@ -953,6 +1014,10 @@ fn kouku_and_shelling_combined_sinking_protection_keeps_flagship_alive() {
                 engagement: EngagementType::SameCourse,
                 friend_ships: vec![flagship],
                 enemy_ships: vec![enemy_cvl],
+                air_base_waves: Vec::new(),
+                combined: None,
+                support: None,
+                historical_bonuses: Vec::new(),
             },
         },
         &mut rng,
@@ -994,7 +1059,7 @@ fn kouku_and_shelling_combined_sinking_protection_keeps_flagship_alive() {
 }
 
 #[test]
-fn eligible_sortie_ship_drops_skip_limited_and_non_victory_results() {
+fn sortie_ship_drop_table_skips_limited_and_non_victory_results() {
     let codex = Codex::load_without_cache_source("../../.data/codex").unwrap();
     let variant = MapVariantDefinition {
         variant_key: String::new(),
@@ -1009,28 +1074,32 @@ fn eligible_sortie_ship_drops_skip_limited_and_non_victory_results() {
                     ship_id: 1,
                     raw_ship_name: "睦月".to_string(),
                     tags: Vec::new(),
+                    weights: None,
                 },
                 emukc_model::codex::map::ShipDropDefinition {
                     ship_id: 2,
                     raw_ship_name: "如月".to_string(),
                     tags: vec!["limited".to_string()],
+                    weights: None,
                 },
                 emukc_model::codex::map::ShipDropDefinition {
                     ship_id: 999999,
                     raw_ship_name: "missing".to_string(),
                     tags: Vec::new(),
+                    weights: None,
                 },
             ],
         )]),
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
 
-    let eligible = eligible_sortie_ship_drops(&codex, &variant, 1, "S");
-    assert_eq!(eligible.len(), 1);
-    assert_eq!(eligible[0].ship_id, 1);
-    assert!(eligible_sortie_ship_drops(&codex, &variant, 1, "C").is_empty());
+    let table = ShipDropTable::build(&codex, &variant, 1, "S", None);
+    assert_eq!(table.entries.len(), 1);
+    assert_eq!(table.entries[0].drop.ship_id, 1);
+    assert!(ShipDropTable::build(&codex, &variant, 1, "C", None).entries.is_empty());
 }
 
 fn empty_stage() -> MapStageDefinition {
@@ -1231,6 +1300,7 @@ fn route_rules_prefer_executable_predicates_over_static_next_cells() {
         node_label: None,
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
     let variant = MapVariantDefinition {
         variant_key: String::new(),
@@ -1266,6 +1336,7 @@ fn route_rules_prefer_executable_predicates_over_static_next_cells() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
     let context = FleetRouteContext {
         fleet_size: 4,
@@ -1301,6 +1372,7 @@ fn route_rules_use_unique_unconditional_fallback_when_predicate_is_unknown() {
         node_label: None,
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
     let variant = MapVariantDefinition {
         variant_key: String::new(),
@@ -1336,6 +1408,7 @@ fn route_rules_use_unique_unconditional_fallback_when_predicate_is_unknown() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
 
     let next = evaluate_route_destination(&current, &variant, &FleetRouteContext::default(), None)
@@ -1363,6 +1436,7 @@ fn selected_route_is_accepted_when_all_rules_are_unknown() {
         node_label: None,
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
     let variant = MapVariantDefinition {
         variant_key: String::new(),
@@ -1400,6 +1474,7 @@ fn selected_route_is_accepted_when_all_rules_are_unknown() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
 
     let next =
@@ -1419,6 +1494,7 @@ fn fallback_rule_does_not_compete_with_matching_specific_rule() {
         node_label: None,
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
     let variant = MapVariantDefinition {
         variant_key: String::new(),
@@ -1454,6 +1530,7 @@ fn fallback_rule_does_not_compete_with_matching_specific_rule() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
     let context = FleetRouteContext {
         fleet_size: 4,
@@ -1494,6 +1571,7 @@ fn cell_zero_uses_explicit_start_rules_before_static_next_cells() {
         node_label: Some("Start".to_string()),
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
     let variant = MapVariantDefinition {
         variant_key: String::new(),
@@ -1509,6 +1587,7 @@ fn cell_zero_uses_explicit_start_rules_before_static_next_cells() {
                 node_label: Some("A".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
             MapCellDefinition {
                 cell_no: 2,
@@ -1519,6 +1598,7 @@ fn cell_zero_uses_explicit_start_rules_before_static_next_cells() {
                 node_label: Some("C".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
         ],
         routing_rules: BTreeMap::from([(
@@ -1538,6 +1618,7 @@ fn cell_zero_uses_explicit_start_rules_before_static_next_cells() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
 
     let next = evaluate_route_destination(&current, &variant, &FleetRouteContext::default(), None)
@@ -1556,6 +1637,7 @@ fn ambiguous_cell_zero_without_rules_is_rejected() {
         node_label: Some("Start".to_string()),
         master_cell_id: None,
         distance: None,
+        select_cells: Vec::new(),
     };
     let variant = MapVariantDefinition {
         variant_key: String::new(),
@@ -1567,6 +1649,7 @@ fn ambiguous_cell_zero_without_rules_is_rejected() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: vec!["missing_start_routes".to_string()],
+        boss_debuff: Default::default(),
     };
 
     let error = evaluate_route_destination(&current, &variant, &FleetRouteContext::default(), None)
@@ -1589,6 +1672,7 @@ fn start_source_cells_include_nonzero_route_cell_roots() {
                 node_label: Some("Start".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
             MapCellDefinition {
                 cell_no: 1,
@@ -1599,6 +1683,7 @@ fn start_source_cells_include_nonzero_route_cell_roots() {
                 node_label: Some("A".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
             MapCellDefinition {
                 cell_no: 2,
@@ -1609,6 +1694,7 @@ fn start_source_cells_include_nonzero_route_cell_roots() {
                 node_label: Some("B".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
             MapCellDefinition {
                 cell_no: 13,
@@ -1619,6 +1705,7 @@ fn start_source_cells_include_nonzero_route_cell_roots() {
                 node_label: Some("M".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
             MapCellDefinition {
                 cell_no: 14,
@@ -1629,6 +1716,7 @@ fn start_source_cells_include_nonzero_route_cell_roots() {
                 node_label: Some("N".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
             MapCellDefinition {
                 cell_no: 22,
@@ -1639,6 +1727,7 @@ fn start_source_cells_include_nonzero_route_cell_roots() {
                 node_label: Some("Start".to_string()),
                 master_cell_id: None,
                 distance: None,
+                select_cells: Vec::new(),
             },
         ],
         routing_rules: BTreeMap::new(),
@@ -1647,6 +1736,7 @@ fn start_source_cells_include_nonzero_route_cell_roots() {
         required_defeat_count: None,
         clear_to_variant_key: None,
         parse_warnings: Vec::new(),
+        boss_debuff: Default::default(),
     };
 
     let sources =
@@ -1694,6 +1784,7 @@ async fn first_gauge_clear_switches_map_variant_without_finishing_map() {
             selected_rank: ActiveValue::Set(map_record::SelectedRank::NotSet),
             event_state: ActiveValue::Set(None),
             unlocked: ActiveValue::Set(true),
+            gimmick_state: ActiveValue::Set(None),
         }
         .insert(&context.0)
         .await
@@ -1709,9 +1800,18 @@ async fn first_gauge_clear_switches_map_variant_without_finishing_map() {
     let snapshot = successful_boss_snapshot();
 
     assert_eq!(
-        apply_sortie_map_result(&context.0, profile_id, &definition, &variant, true, &snapshot)
-            .await
-            .unwrap(),
+        apply_sortie_map_result(
+            &context.0,
+            profile_id,
+            &definition,
+            &variant,
+            true,
+            &snapshot,
+            0,
+            Utc::now()
+        )
+        .await
+        .unwrap(),
         0
     );
 
@@ -1771,6 +1871,7 @@ async fn start_sortie_returns_post_p_unlock_layout_after_first_gauge_clear() {
             selected_rank: ActiveValue::Set(map_record::SelectedRank::NotSet),
             event_state: ActiveValue::Set(None),
             unlocked: ActiveValue::Set(true),
+            gimmick_state: ActiveValue::Set(None),
         }
         .insert(&context.0)
         .await
@@ -1780,9 +1881,18 @@ async fn start_sortie_returns_post_p_unlock_layout_after_first_gauge_clear() {
     let definition = context.1.maps.map_definition(73).unwrap().clone();
     let variant = definition.variant("pre_p_unlock").unwrap().clone();
     let snapshot = successful_boss_snapshot();
-    apply_sortie_map_result(&context.0, profile_id, &definition, &variant, true, &snapshot)
-        .await
-        .unwrap();
+    apply_sortie_map_result(
+        &context.0,
+        profile_id,
+        &definition,
+        &variant,
+        true,
+        &snapshot,
+        0,
+        Utc::now(),
+    )
+    .await
+    .unwrap();
 
     let ship = context.add_ship(profile_id, 951).await.unwrap();
     context.update_fleet_ships(profile_id, 1, &[ship.api_id, -1, -1, -1, -1, -1]).await.unwrap();
@@ -1830,6 +1940,7 @@ async fn hp_gauge_clear_advances_to_next_gauge_before_marking_map_cleared() {
                 ..Default::default()
             },
         )]),
+        gimmicks: Vec::new(),
     };
     let stage = definition.variant("").unwrap().clone();
     map_record::ActiveModel {
@@ -1846,6 +1957,7 @@ async fn hp_gauge_clear_advances_to_next_gauge_before_marking_map_cleared() {
         selected_rank: ActiveValue::Set(map_record::SelectedRank::NotSet),
         event_state: ActiveValue::Set(Some(1)),
         unlocked: ActiveValue::Set(true),
+        gimmick_state: ActiveValue::Set(None),
     }
     .insert(&context.0)
     .await
@@ -1859,6 +1971,8 @@ async fn hp_gauge_clear_advances_to_next_gauge_before_marking_map_cleared() {
             &stage,
             true,
             &successful_boss_snapshot(),
+            0,
+            Utc::now(),
         )
         .await
         .unwrap(),
@@ -1923,6 +2037,7 @@ async fn hp_gauge_clear_switches_stage_before_marking_map_cleared() {
                 },
             ),
         ]),
+        gimmicks: Vec::new(),
     };
     let stage = definition.variant("pre").unwrap().clone();
     map_record::ActiveModel {
@@ -1939,6 +2054,7 @@ async fn hp_gauge_clear_switches_stage_before_marking_map_cleared() {
         selected_rank: ActiveValue::Set(map_record::SelectedRank::NotSet),
         event_state: ActiveValue::Set(Some(1)),
         unlocked: ActiveValue::Set(true),
+        gimmick_state: ActiveValue::Set(None),
     }
     .insert(&context.0)
     .await
@@ -1952,6 +2068,8 @@ async fn hp_gauge_clear_switches_stage_before_marking_map_cleared() {
             &stage,
             true,
             &successful_boss_snapshot(),
+            0,
+            Utc::now(),
         )
         .await
         .unwrap(),
@@ -2007,6 +2125,7 @@ async fn final_hp_gauge_clear_marks_map_cleared() {
                 ..Default::default()
             },
         )]),
+        gimmicks: Vec::new(),
     };
     let stage = definition.variant("").unwrap().clone();
     map_record::ActiveModel {
@@ -2023,6 +2142,7 @@ async fn final_hp_gauge_clear_marks_map_cleared() {
         selected_rank: ActiveValue::Set(map_record::SelectedRank::NotSet),
         event_state: ActiveValue::Set(Some(1)),
         unlocked: ActiveValue::Set(true),
+        gimmick_state: ActiveValue::Set(None),
     }
     .insert(&context.0)
     .await
@@ -2036,6 +2156,8 @@ async fn final_hp_gauge_clear_marks_map_cleared() {
             &stage,
             true,
             &successful_boss_snapshot(),
+            0,
+            Utc::now(),
         )
         .await
         .unwrap(),
@@ -2083,8 +2205,14 @@ async fn clearing_map_1_1_unlocks_dependents_via_cascade() {
     let snapshot = successful_boss_snapshot();
 
     let first_clear = apply_sortie_map_result(
-        &context.0, profile_id, definition, stage, true, // boss cell
+        &context.0,
+        profile_id,
+        definition,
+        stage,
+        true, // boss cell
         &snapshot,
+        0,
+        Utc::now(),
     )
     .await
     .unwrap();
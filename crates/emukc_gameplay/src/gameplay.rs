@@ -1,8 +1,11 @@
 //! A wrapper around the game's data and logic.
 
+use std::sync::LazyLock;
+
 use async_trait::async_trait;
 use emukc_db::sea_orm::DbConn;
 use emukc_model::codex::Codex;
+use emukc_time::GameClock;
 
 use crate::{
     game::{
@@ -25,8 +28,14 @@ pub trait HasContext: Send + Sync {
 
     /// Get the practice runtime store.
     fn practice_store(&self) -> &PracticeStore;
+
+    /// Get the game clock.
+    fn clock(&self) -> &GameClock;
 }
 
+/// Game clock shared by contexts that do not own one.
+pub static GLOBAL_GAME_CLOCK: LazyLock<GameClock> = LazyLock::new(GameClock::new);
+
 /// Gameplay trait for the game's data and logic.
 #[async_trait]
 pub trait Gameplay: AccountOps + ProfileOps + GameOps {}
@@ -52,4 +61,8 @@ impl HasContext for (DbConn, Codex) {
     fn practice_store(&self) -> &PracticeStore {
         &GLOBAL_PRACTICE_STORE
     }
+
+    fn clock(&self) -> &GameClock {
+        &GLOBAL_GAME_CLOCK
+    }
 }
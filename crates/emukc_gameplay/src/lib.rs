@@ -24,12 +24,14 @@ pub mod prelude {
 
     #[doc(hidden)]
     pub use emukc_battle::{
-        BattleSimulation, NightBattleSimulation, render_day_battle, render_night_battle,
+        BattleAirRaid, BattleSimulation, NightBattleSimulation, render_day_battle,
+        render_night_battle,
     };
 
     #[doc(hidden)]
     pub use crate::{
         err::GameplayError,
+        game::DEFAULT_SESSION_TTL,
         game::PracticeStore,
         game::SortieStore,
         game::battle::practice_repository::PracticeRepository,
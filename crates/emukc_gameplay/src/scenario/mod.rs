@@ -177,12 +177,13 @@ where
 
     if !scenario.unlock_maps.is_empty() || !scenario.clear_maps.is_empty() {
         let codex = ctx.codex();
+        let now = ctx.clock().now(profile_id);
         let tx = ctx.db().begin().await?;
         for &map_id in &scenario.unlock_maps {
-            unlock_map_impl(&tx, codex, profile_id, map_id).await?;
+            unlock_map_impl(&tx, codex, profile_id, map_id, now).await?;
         }
         for &map_id in &scenario.clear_maps {
-            clear_and_unlock_map_impl(&tx, codex, profile_id, map_id).await?;
+            clear_and_unlock_map_impl(&tx, codex, profile_id, map_id, now).await?;
         }
         tx.commit().await?;
     }
@@ -31,6 +31,9 @@ pub enum UserError {
     #[error("Profile already exists.")]
     ProfileExists,
 
+    #[error("Invalid profile archive: {0}")]
+    InvalidArchive(String),
+
     #[error("Database error: {0}")]
     Db(#[from] emukc_db::sea_orm::DbErr),
 
@@ -12,7 +12,11 @@ use prelude::async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    game::{init_profile_game_data, wipe_profile_game_data},
+    err::GameplayError,
+    game::{
+        ProfileArchive, export_profile_impl, import_profile_impl, init_profile_game_data,
+        wipe_profile_game_data,
+    },
     gameplay::HasContext,
 };
 
@@ -76,6 +80,35 @@ pub trait ProfileOps {
     ///
     /// * `profile_id` - The profile ID to find.
     async fn find_profile(&self, profile_id: i64) -> Result<Profile, UserError>;
+
+    /// Export the full state of a profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the account owning the profile.
+    /// * `profile_id` - The profile ID to export.
+    async fn export_profile(
+        &self,
+        access_token: &str,
+        profile_id: i64,
+    ) -> Result<ProfileArchive, UserError>;
+
+    /// Import an exported profile as a new profile of the account.
+    ///
+    /// The archive is validated against the codex first; nothing is written
+    /// if it is rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the account.
+    /// * `archive` - The exported profile.
+    /// * `profile_name` - The name of the new profile, the archived name if `None`.
+    async fn import_profile(
+        &self,
+        access_token: &str,
+        archive: &ProfileArchive,
+        profile_name: Option<&str>,
+    ) -> Result<StartGameInfo, UserError>;
 }
 
 #[async_trait]
@@ -112,7 +145,8 @@ impl<T: HasContext + ?Sized> ProfileOps for T {
             issue_token(&tx, account_model.uid, profile_model.id, TokenType::Session).await?;
 
         // populate game data
-        init_profile_game_data(&tx, codex, profile_model.id).await?;
+        init_profile_game_data(&tx, codex, profile_model.id, self.clock().now(profile_model.id))
+            .await?;
 
         tx.commit().await?;
 
@@ -194,10 +228,82 @@ impl<T: HasContext + ?Sized> ProfileOps for T {
 
         let tx = db.begin().await?;
 
-        init_profile_game_data(&tx, codex, profile_id).await?;
+        init_profile_game_data(&tx, codex, profile_id, self.clock().now(profile_id)).await?;
 
         tx.commit().await?;
 
         Ok(())
     }
+
+    async fn export_profile(
+        &self,
+        access_token: &str,
+        profile_id: i64,
+    ) -> Result<ProfileArchive, UserError> {
+        let db = self.db();
+        let tx = db.begin().await?;
+
+        let account_model = verify_access_token(&tx, access_token).await?;
+        profile::Entity::find()
+            .filter(profile::Column::AccountId.eq(account_model.uid))
+            .filter(profile::Column::Id.eq(profile_id))
+            .one(&tx)
+            .await?
+            .ok_or(UserError::ProfileNotFound)?;
+
+        let archive = export_profile_impl(&tx, profile_id, self.clock().now(profile_id)).await?;
+
+        tx.commit().await?;
+
+        Ok(archive)
+    }
+
+    async fn import_profile(
+        &self,
+        access_token: &str,
+        archive: &ProfileArchive,
+        profile_name: Option<&str>,
+    ) -> Result<StartGameInfo, UserError> {
+        let db = self.db();
+        let codex = self.codex();
+        let tx = db.begin().await?;
+
+        let account_model = verify_access_token(&tx, access_token).await?;
+
+        let name = profile_name.unwrap_or(&archive.profile.name);
+        let profile_model = profile::Entity::find()
+            .filter(profile::Column::AccountId.eq(account_model.uid))
+            .filter(profile::Column::Name.eq(name))
+            .one(&tx)
+            .await?;
+
+        if profile_model.is_some() {
+            return Err(UserError::ProfileExists);
+        }
+
+        let profile_model =
+            match import_profile_impl(&tx, codex, account_model.uid, Some(name), archive).await {
+                Ok(model) => model,
+                Err(GameplayError::InvalidArchive(e)) => return Err(UserError::InvalidArchive(e)),
+                Err(e) => return Err(e.into()),
+            };
+
+        let session =
+            issue_token(&tx, account_model.uid, profile_model.id, TokenType::Session).await?;
+
+        tx.commit().await?;
+
+        info!(
+            "imported profile {} ({} ships, {} slot items) for account {}",
+            profile_model.id,
+            archive.ships.len(),
+            archive.slot_items.len(),
+            account_model.uid
+        );
+
+        Ok(StartGameInfo {
+            profile: profile_model.into(),
+            session,
+        })
+    }
 }
@@ -8,7 +8,7 @@ use emukc_model::codex::group::DeGroupParam;
 use emukc_model::kc2::{
     KcApiIncentiveItem, KcApiIncentiveMode, KcApiIncentiveType, KcSlotItemType3, MaterialCategory,
 };
-use emukc_model::prelude::{ApiMstShip, Kc3rdQuest, Kc3rdSlotItem};
+use emukc_model::prelude::{ApiMstShip, Kc3rdQuest};
 use emukc_model::profile::kdock::ConstructionDockStatus;
 use emukc_model::profile::ndock::RepairDockStatus;
 
@@ -144,33 +144,13 @@ async fn create_slotitems() {
     let (context, _, session) = new_game_session().await;
     let pid = session.profile.id;
 
-    let slot_items = context
-        .1
-        .slotitem_extra_info
-        .values()
-        .filter_map(|v| {
-            if v.craftable {
-                Some(v.clone())
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<Kc3rdSlotItem>>();
-    let chunk = slot_items.chunks(3).next().unwrap();
-
-    let costs = vec![
-        (MaterialCategory::Fuel, 100),
-        (MaterialCategory::Ammo, 100),
-        (MaterialCategory::Steel, 100),
-        (MaterialCategory::Bauxite, 100),
-        (MaterialCategory::DevMat, 1),
-    ];
-
-    let ids = chunk.iter().map(|v| v.api_id).collect::<Vec<i64>>();
-
-    let (ids, material) = context.create_slotitem(pid, &ids, &costs).await.unwrap();
-
-    println!("{:?}", ids);
+    let (developed, material) = context.create_slotitem(pid, [10, 10, 10, 10], 3).await.unwrap();
+    assert_eq!(developed.len(), 3);
+    for item in &developed {
+        assert_eq!(item.is_success(), item.mst_id > 0, "{item:?}");
+    }
+
+    println!("{:?}", developed);
     println!("{:?}", material);
 }
 
@@ -0,0 +1,222 @@
+//! Equipment legality across every equip path.
+
+use emukc_db::{
+    entity::profile::ship,
+    prelude::new_mem_db,
+    sea_orm::{ActiveModelTrait, ActiveValue, EntityTrait, IntoActiveModel},
+};
+use emukc_gameplay::prelude::*;
+use emukc_model::{
+    codex::{Codex, equip::EquipError},
+    kc2::KcSlotItemType3,
+    prelude::{ApiMstShip, ApiMstSlotitem, ApiMstStype, Kc3rdShip, Kc3rdShipSlotInfo},
+};
+
+const CARRIER: i64 = 1;
+const DESTROYER: i64 = 2;
+const TORPEDO_BOMBER: i64 = 10;
+const SMALL_GUN: i64 = 11;
+
+fn add_ship(codex: &mut Codex, api_id: i64, api_stype: i64, equip_type3: KcSlotItemType3) {
+    codex.manifest.api_mst_stype.push(ApiMstStype {
+        api_id: api_stype,
+        api_equip_type: [((equip_type3 as i64).to_string(), 1)].into(),
+        api_kcnt: 0,
+        api_name: String::new(),
+        api_scnt: 0,
+        api_sortno: 0,
+    });
+    codex.manifest.api_mst_ship.push(ApiMstShip {
+        api_id,
+        api_stype,
+        api_slot_num: 2,
+        api_taik: Some([30, 50]),
+        api_maxeq: Some([12, 12, 0, 0, 0]),
+        ..Default::default()
+    });
+    codex.ship_extra.insert(
+        api_id,
+        Kc3rdShip {
+            api_id,
+            kaih: [0, 0],
+            tais: [0, 0],
+            saku: [0, 0],
+            luck: [0, 0],
+            luck_bonus: 0.0,
+            armor_bonus: 0,
+            cnum: 1,
+            buildable: false,
+            buildable_lsc: false,
+            slots: vec![
+                Kc3rdShipSlotInfo {
+                    onslot: 12,
+                    item_id: 0,
+                    stars: 0,
+                };
+                2
+            ],
+            remodel: None,
+            remodel_back_to: None,
+            remodel_back_requirement: None,
+        },
+    );
+}
+
+fn add_slot_item(codex: &mut Codex, api_id: i64, type3: KcSlotItemType3) {
+    codex.manifest.api_mst_slotitem.push(ApiMstSlotitem {
+        api_id,
+        api_type: [1, 1, type3 as i64, 1, 1],
+        ..Default::default()
+    });
+}
+
+/// A carrier taking torpedo bombers and a destroyer taking small guns, which
+/// also fit its reinforcement expansion slot.
+fn codex() -> Codex {
+    let mut codex = Codex::default();
+    add_ship(&mut codex, CARRIER, 7, KcSlotItemType3::CarrierBasedTorpedoBomber);
+    add_ship(&mut codex, DESTROYER, 2, KcSlotItemType3::SmallCaliberMainGun);
+    add_slot_item(&mut codex, TORPEDO_BOMBER, KcSlotItemType3::CarrierBasedTorpedoBomber);
+    add_slot_item(&mut codex, SMALL_GUN, KcSlotItemType3::SmallCaliberMainGun);
+    codex.manifest.api_mst_equip_exslot.push(KcSlotItemType3::SmallCaliberMainGun as i64);
+    codex
+}
+
+struct Fixture {
+    context: (emukc_db::sea_orm::DbConn, Codex),
+    profile_id: i64,
+    carrier: i64,
+    destroyer: i64,
+    torpedo_bomber: i64,
+}
+
+async fn fixture() -> Fixture {
+    let context = (new_mem_db().await.unwrap(), codex());
+    let account = context.sign_up("equip", "1234567").await.unwrap();
+    let profile_id =
+        context.new_profile(&account.access_token.token, "equip").await.unwrap().profile.id;
+
+    let carrier = context.add_ship(profile_id, CARRIER).await.unwrap().api_id;
+    let destroyer = context.add_ship(profile_id, DESTROYER).await.unwrap().api_id;
+    for ship_id in [carrier, destroyer] {
+        open_exslot(&context, ship_id).await;
+    }
+    let torpedo_bomber =
+        context.add_slot_item(profile_id, TORPEDO_BOMBER, 0, 0).await.unwrap().api_id;
+
+    Fixture {
+        context,
+        profile_id,
+        carrier,
+        destroyer,
+        torpedo_bomber,
+    }
+}
+
+async fn open_exslot(context: &(emukc_db::sea_orm::DbConn, Codex), ship_id: i64) {
+    let model = ship::Entity::find_by_id(ship_id).one(&context.0).await.unwrap().unwrap();
+    let mut am = model.into_active_model();
+    am.slot_ex = ActiveValue::Set(-1);
+    am.update(&context.0).await.unwrap();
+}
+
+fn is_equip_error(result: Result<impl Sized, GameplayError>) -> bool {
+    matches!(result, Err(GameplayError::Equip(_)))
+}
+
+#[tokio::test]
+async fn set_slot_item_rejects_illegal_item() {
+    let Fixture {
+        context,
+        carrier,
+        destroyer,
+        torpedo_bomber,
+        ..
+    } = fixture().await;
+
+    assert!(matches!(
+        context.set_slot_item(destroyer, 0, torpedo_bomber).await,
+        Err(GameplayError::Equip(EquipError::NotEquippable { .. }))
+    ));
+    context.set_slot_item(carrier, 0, torpedo_bomber).await.unwrap();
+}
+
+#[tokio::test]
+async fn set_exslot_item_rejects_illegal_item() {
+    let Fixture {
+        context,
+        profile_id,
+        carrier,
+        destroyer,
+        torpedo_bomber,
+    } = fixture().await;
+
+    assert!(matches!(
+        context.set_exslot_item(carrier, torpedo_bomber).await,
+        Err(GameplayError::Equip(EquipError::ExslotNotAllowed { .. }))
+    ));
+    let gun = context.add_slot_item(profile_id, SMALL_GUN, 0, 0).await.unwrap().api_id;
+    context.set_exslot_item(destroyer, gun).await.unwrap();
+}
+
+#[tokio::test]
+async fn slot_deprive_rejects_illegal_item() {
+    let Fixture {
+        context,
+        profile_id,
+        carrier,
+        destroyer,
+        torpedo_bomber,
+    } = fixture().await;
+    context.set_slot_item(carrier, 0, torpedo_bomber).await.unwrap();
+
+    let params = SlotDepriveParams {
+        from_ship_id: carrier,
+        to_ship_id: destroyer,
+        from_ex_slot: false,
+        to_ex_slot: false,
+        from_slot_idx: 0,
+        to_slot_idx: 0,
+    };
+    assert!(is_equip_error(context.slot_deprive(profile_id, &params).await));
+
+    // nothing moved
+    let carrier_ship = context.find_ship(carrier).await.unwrap().unwrap();
+    assert_eq!(carrier_ship.api_slot[0], torpedo_bomber);
+}
+
+#[tokio::test]
+async fn preset_slot_apply_rejects_illegal_item() {
+    let Fixture {
+        context,
+        profile_id,
+        carrier,
+        destroyer,
+        torpedo_bomber,
+    } = fixture().await;
+    context.set_slot_item(carrier, 0, torpedo_bomber).await.unwrap();
+    context.register_preset_slot(profile_id, 1, carrier).await.unwrap();
+    context.unset_all_slots(carrier).await.unwrap();
+
+    assert!(is_equip_error(context.apply_preset_slot(profile_id, 1, destroyer, 1).await));
+    context.apply_preset_slot(profile_id, 1, carrier, 1).await.unwrap();
+    assert_eq!(context.find_ship(carrier).await.unwrap().unwrap().api_slot[0], torpedo_bomber);
+}
+
+#[tokio::test]
+async fn preset_slot_apply_skips_exslot_item_that_does_not_fit() {
+    let Fixture {
+        context,
+        profile_id,
+        carrier,
+        destroyer,
+        ..
+    } = fixture().await;
+    let gun = context.add_slot_item(profile_id, SMALL_GUN, 0, 0).await.unwrap().api_id;
+    context.set_exslot_item(destroyer, gun).await.unwrap();
+    context.register_preset_slot(profile_id, 1, destroyer).await.unwrap();
+    context.unset_all_slots(destroyer).await.unwrap();
+
+    context.apply_preset_slot(profile_id, 1, carrier, 1).await.unwrap();
+    assert_eq!(context.find_ship(carrier).await.unwrap().unwrap().api_slot_ex, -1);
+}
@@ -3,7 +3,7 @@
 use std::sync::atomic::{AtomicI64, Ordering};
 
 use emukc_db::{
-    entity::profile::{self, quest, ship},
+    entity::profile::{self, practice, quest, ship},
     prelude::new_mem_db,
     sea_orm::{
         ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
@@ -13,6 +13,7 @@ use emukc_gameplay::prelude::*;
 use emukc_model::{
     codex::Codex,
     kc2::{KcShipType, level},
+    profile::practice::RivalType,
     thirdparty::{Kc3rdQuestCondition, Kc3rdQuestRequirement},
 };
 use emukc_time::chrono::Utc;
@@ -525,3 +526,61 @@ async fn practice_battle_result_without_prior_battle_returns_entry_not_found() {
     let err = context.practice_battle_result(pid).await.unwrap_err();
     assert!(matches!(err, GameplayError::EntryNotFound(_)), "expected EntryNotFound, got {err:?}");
 }
+
+#[tokio::test]
+async fn practice_rivals_include_other_local_profiles() {
+    let (context, session) = new_game_session().await;
+    let pid = session.profile.id;
+    let ships = add_ships_with_type(&context, pid, KcShipType::BB, 2, 99).await;
+    context.update_fleet_ships(pid, 1, &[ships[0], ships[1], -1, -1, -1, -1]).await.unwrap();
+
+    let account = context.sign_up("rival", "1234567").await.unwrap();
+    let rival = context.new_profile(&account.access_token.token, "rival admiral").await.unwrap();
+    let rival_pid = rival.profile.id;
+    let rival_ships = add_ships_with_type(&context, rival_pid, KcShipType::CA, 2, 42).await;
+    context
+        .update_fleet_ships(rival_pid, 1, &[rival_ships[0], rival_ships[1], -1, -1, -1, -1])
+        .await
+        .unwrap();
+    context.update_deck_name(rival_pid, 1, "Sparring Fleet").await.unwrap();
+
+    context.change_practice_matching_kind(pid, RivalType::All).await.unwrap();
+    let rivals = context.get_practice_rivals(pid).await.unwrap();
+    assert_eq!(rivals.rivals.len(), 5);
+    assert_eq!(rivals.cfg.generated_type, practice::config::RivalType::All);
+
+    let local = rivals.rivals.iter().find(|r| r.name == "rival admiral").unwrap();
+    assert_eq!(local.details.deck_name, "Sparring Fleet");
+    assert_eq!(local.details.ships.len(), 2);
+    assert!(local.details.ships.iter().all(|ship| ship.level == 42));
+    assert_ne!(local.details.ships[0].id, rival_ships[0]);
+
+    let battle = context.practice_battle(pid, 1, 1, local.id).await.unwrap();
+    let mst_id = context.find_ship(rival_ships[0]).await.unwrap().unwrap().api_ship_id;
+    assert_eq!(battle.api_ship_ke[0], mst_id);
+    assert_eq!(battle.api_ship_lv[0], 42);
+}
+
+#[tokio::test]
+async fn generated_practice_rivals_scale_with_hq_level() {
+    let (context, session) = new_game_session().await;
+    let pid = session.profile.id;
+
+    let mut am = profile::Entity::find_by_id(pid)
+        .one(&context.0)
+        .await
+        .unwrap()
+        .unwrap()
+        .into_active_model();
+    am.hq_level = ActiveValue::Set(100);
+    am.update(&context.0).await.unwrap();
+
+    context.change_practice_matching_kind(pid, RivalType::SecondaryGroup).await.unwrap();
+    let rivals = context.get_practice_rivals(pid).await.unwrap();
+    assert_eq!(rivals.rivals.len(), 5);
+    for rival in &rivals.rivals {
+        assert!((85..100).contains(&rival.level), "rival level {}", rival.level);
+        assert!(rival.details.ships.len() >= 5, "{} ships", rival.details.ships.len());
+        assert!(rival.details.ships.iter().all(|ship| (1..=99).contains(&ship.level)));
+    }
+}
@@ -650,8 +650,10 @@ async fn monthly_map_record_resets_on_map_info_read() {
                     required_defeat_count: Some(4),
                     clear_to_variant_key: None,
                     parse_warnings: vec![],
+                    boss_debuff: Default::default(),
                 },
             )]),
+            gimmicks: Vec::new(),
         },
     );
     codex.manifest.api_mst_mapinfo.push(ApiMstMapinfo {
@@ -0,0 +1,234 @@
+//! Ship construction (建造) tables.
+//!
+//! Candidates are the buildable ships (`buildable` for normal construction,
+//! `buildable_lsc` for large ship construction). Each ship type has a minimum
+//! recipe and a base weight; the weight is then scaled by the ship's rarity
+//! (`api_backs`) and by any secretary bonus. Per-ship rules can raise the
+//! minimum recipe further (e.g. the Yamato class).
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::ApiMstShip;
+
+use super::{Codex, development::SecretaryGroup};
+
+/// Minimum recipe and base weight for a set of ship types.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstructionTypeRule {
+    /// Ship types (`api_stype`) covered by this rule.
+    pub stypes: Vec<i64>,
+
+    /// Minimum `[fuel, ammo, steel, bauxite, devmat]` the recipe must supply.
+    pub min_recipe: [i64; 5],
+
+    /// Base weight of every ship of these types.
+    pub weight: u64,
+}
+
+/// Extra requirement for a single ship.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstructionShipRule {
+    /// Ship master id.
+    pub ship_id: i64,
+
+    /// Minimum `[fuel, ammo, steel, bauxite, devmat]` the recipe must supply.
+    pub min_recipe: [i64; 5],
+}
+
+/// Weight multiplier for ship types when the secretary belongs to a group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstructionSecretaryBonus {
+    /// Secretary groups granting the bonus.
+    pub secretaries: Vec<SecretaryGroup>,
+
+    /// Ship types (`api_stype`) boosted.
+    pub stypes: Vec<i64>,
+
+    /// Weight multiplier.
+    pub multiplier: u64,
+}
+
+/// Rules for one kind of construction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstructionPool {
+    /// Per ship type minimum recipe and base weight; types without a rule
+    /// cannot be built.
+    pub type_rules: Vec<ConstructionTypeRule>,
+
+    /// Weight multiplier indexed by rarity (`api_backs`); rarities past the
+    /// end use the last value.
+    pub rarity_weights: Vec<u64>,
+
+    /// Secretary bonuses, applied cumulatively.
+    #[serde(default)]
+    pub secretary_bonuses: Vec<ConstructionSecretaryBonus>,
+
+    /// Per ship extra requirements.
+    #[serde(default)]
+    pub ship_rules: Vec<ConstructionShipRule>,
+}
+
+impl ConstructionPool {
+    fn rarity_weight(&self, backs: i64) -> u64 {
+        let idx = usize::try_from(backs).unwrap_or(0);
+        self.rarity_weights.get(idx).or(self.rarity_weights.last()).copied().unwrap_or(1)
+    }
+
+    /// Weight of one ship for a recipe and secretary group, `0` if it cannot be built.
+    pub fn weight_of(
+        &self,
+        ship: &ApiMstShip,
+        recipe: &[i64; 5],
+        secretary: SecretaryGroup,
+    ) -> u64 {
+        let meets = |min: &[i64; 5]| recipe.iter().zip(min).all(|(have, min)| have >= min);
+
+        let Some(rule) = self.type_rules.iter().find(|rule| rule.stypes.contains(&ship.api_stype))
+        else {
+            return 0;
+        };
+        if !meets(&rule.min_recipe) {
+            return 0;
+        }
+        if self.ship_rules.iter().any(|r| r.ship_id == ship.api_id && !meets(&r.min_recipe)) {
+            return 0;
+        }
+
+        let bonus: u64 = self
+            .secretary_bonuses
+            .iter()
+            .filter(|b| b.secretaries.contains(&secretary) && b.stypes.contains(&ship.api_stype))
+            .map(|b| b.multiplier)
+            .product();
+        rule.weight * self.rarity_weight(ship.api_backs.unwrap_or(1)) * bonus
+    }
+}
+
+/// Normal and large ship construction pools.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstructionTable {
+    /// Normal construction.
+    pub normal: ConstructionPool,
+
+    /// Large ship construction (大型艦建造).
+    pub large: ConstructionPool,
+}
+
+impl Codex {
+    /// Weighted construction candidates.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipe` - `[fuel, ammo, steel, bauxite, devmat]` put into the build.
+    /// * `large` - Whether this is a large ship construction.
+    /// * `secretary_stype` - `api_stype` of the first fleet's flagship.
+    ///
+    /// # Returns
+    ///
+    /// `(ship master id, weight)` pairs in master id order, zero weights dropped.
+    /// Without a loaded table (e.g. a codex generated before construction
+    /// tables existed) nothing can be built, since no recipe gate is known.
+    pub fn construction_candidates(
+        &self,
+        recipe: &[i64; 5],
+        large: bool,
+        secretary_stype: i64,
+    ) -> Vec<(i64, u64)> {
+        let pool = if large {
+            &self.construction.large
+        } else {
+            &self.construction.normal
+        };
+        let secretary = SecretaryGroup::from_stype(secretary_stype);
+
+        self.ship_extra
+            .values()
+            .filter(|info| {
+                if large {
+                    info.buildable_lsc
+                } else {
+                    info.buildable
+                }
+            })
+            .filter_map(|info| {
+                let mst = self.find::<ApiMstShip>(&info.api_id).ok()?;
+                let weight = pool.weight_of(mst, recipe, secretary);
+                (weight > 0).then_some((info.api_id, weight))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ship(api_id: i64, api_stype: i64, backs: i64) -> ApiMstShip {
+        ApiMstShip {
+            api_id,
+            api_stype,
+            api_backs: Some(backs),
+            ..Default::default()
+        }
+    }
+
+    fn pool() -> ConstructionPool {
+        ConstructionPool {
+            type_rules: vec![
+                ConstructionTypeRule {
+                    stypes: vec![2],
+                    min_recipe: [30, 30, 30, 30, 1],
+                    weight: 100,
+                },
+                ConstructionTypeRule {
+                    stypes: vec![9],
+                    min_recipe: [400, 100, 600, 30, 1],
+                    weight: 30,
+                },
+                ConstructionTypeRule {
+                    stypes: vec![11],
+                    min_recipe: [300, 30, 400, 300, 1],
+                    weight: 20,
+                },
+            ],
+            rarity_weights: vec![100, 100, 100, 100, 60, 25],
+            secretary_bonuses: vec![ConstructionSecretaryBonus {
+                secretaries: vec![SecretaryGroup::Carrier],
+                stypes: vec![11],
+                multiplier: 2,
+            }],
+            ship_rules: vec![ConstructionShipRule {
+                ship_id: 131,
+                min_recipe: [3500, 3500, 6000, 2000, 1],
+            }],
+        }
+    }
+
+    #[test]
+    fn minimum_recipe_excludes_big_ships() {
+        let pool = pool();
+        let recipe = [30, 30, 30, 30, 1];
+        assert_eq!(pool.weight_of(&ship(1, 2, 1), &recipe, SecretaryGroup::Destroyer), 10_000);
+        assert_eq!(pool.weight_of(&ship(26, 9, 5), &recipe, SecretaryGroup::Destroyer), 0);
+        assert_eq!(pool.weight_of(&ship(99, 13, 1), &recipe, SecretaryGroup::Destroyer), 0);
+    }
+
+    #[test]
+    fn rarity_and_secretary_scale_weight() {
+        let pool = pool();
+        let recipe = [300, 30, 400, 300, 1];
+        let carrier = ship(83, 11, 5);
+        assert_eq!(pool.weight_of(&carrier, &recipe, SecretaryGroup::Destroyer), 20 * 25);
+        assert_eq!(pool.weight_of(&carrier, &recipe, SecretaryGroup::Carrier), 20 * 25 * 2);
+        // Rarity past the table end uses the last multiplier.
+        assert_eq!(pool.weight_of(&ship(84, 11, 8), &recipe, SecretaryGroup::Destroyer), 20 * 25);
+    }
+
+    #[test]
+    fn ship_rule_raises_minimum() {
+        let pool = pool();
+        let yamato = ship(131, 9, 7);
+        assert_eq!(pool.weight_of(&yamato, &[1500, 1500, 2000, 1000, 1], SecretaryGroup::Other), 0);
+        assert!(pool.weight_of(&yamato, &[3500, 3500, 6000, 2000, 1], SecretaryGroup::Other) > 0);
+    }
+}
@@ -0,0 +1,304 @@
+//! Equipment development (開発) tables.
+//!
+//! A development roll picks a pool by the recipe's dominant resource and the
+//! secretary ship's group, then draws one entry by weight out of
+//! [`DEVELOPMENT_WEIGHT_TOTAL`]. Whatever weight the pool leaves unassigned is
+//! the failure (penguin) chance.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{kc2::KcShipType, prelude::ApiMstSlotitem};
+
+use super::{Codex, select_weighted_for_roll};
+
+/// Total weight every development pool is rolled against.
+pub const DEVELOPMENT_WEIGHT_TOTAL: u64 = 1000;
+
+/// Success weight shared by the legacy craftable pool when no table is loaded.
+const LEGACY_SUCCESS_WEIGHT: u64 = 700;
+
+/// HQ levels required per point of equipment rarity.
+const HQ_LEVELS_PER_RARITY: i64 = 10;
+
+/// The resource a development recipe leans on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DevelopmentFocus {
+    /// Fuel is the largest input.
+    Fuel,
+    /// Ammo is the largest input.
+    Ammo,
+    /// Steel is the largest input.
+    Steel,
+    /// Bauxite is the largest input.
+    Bauxite,
+}
+
+impl DevelopmentFocus {
+    /// Dominant resource of a `[fuel, ammo, steel, bauxite]` recipe.
+    ///
+    /// Ties resolve to the earlier resource in that order.
+    pub fn from_recipe(recipe: &[i64; 4]) -> Self {
+        let mut focus = Self::Fuel;
+        let mut best = recipe[0];
+        for (value, candidate) in recipe[1..].iter().zip([Self::Ammo, Self::Steel, Self::Bauxite]) {
+            if *value > best {
+                best = *value;
+                focus = candidate;
+            }
+        }
+        focus
+    }
+}
+
+/// Secretary ship group selecting development and construction pools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretaryGroup {
+    /// Battleships and battlecruisers, including aviation battleships.
+    Battleship,
+    /// Heavy, light, torpedo and training cruisers.
+    Cruiser,
+    /// Destroyers and escorts.
+    Destroyer,
+    /// Fleet, light and armored carriers.
+    Carrier,
+    /// Submarines.
+    Submarine,
+    /// Every other ship type.
+    Other,
+}
+
+impl SecretaryGroup {
+    /// Group of the secretary ship's `api_stype`.
+    pub fn from_stype(stype: i64) -> Self {
+        match KcShipType::n(stype) {
+            Some(KcShipType::FBB | KcShipType::BB | KcShipType::BBV | KcShipType::XBB) => {
+                Self::Battleship
+            }
+            Some(
+                KcShipType::CA
+                | KcShipType::CAV
+                | KcShipType::CL
+                | KcShipType::CLT
+                | KcShipType::CT,
+            ) => Self::Cruiser,
+            Some(KcShipType::DD | KcShipType::DE) => Self::Destroyer,
+            Some(KcShipType::CV | KcShipType::CVL | KcShipType::CVB) => Self::Carrier,
+            Some(KcShipType::SS | KcShipType::SSV) => Self::Submarine,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// One equipment a pool can produce.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevelopmentEntry {
+    /// Slot item master id.
+    pub slotitem_id: i64,
+
+    /// Weight out of [`DEVELOPMENT_WEIGHT_TOTAL`].
+    pub weight: u64,
+
+    /// Minimum `[fuel, ammo, steel, bauxite]` the recipe must supply.
+    #[serde(default)]
+    pub min_recipe: [i64; 4],
+}
+
+/// A weighted pool for one recipe focus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevelopmentPool {
+    /// Recipe focus this pool serves.
+    pub focus: DevelopmentFocus,
+
+    /// Secretary groups this pool serves; empty means any.
+    #[serde(default)]
+    pub secretaries: Vec<SecretaryGroup>,
+
+    /// Producible equipment.
+    pub entries: Vec<DevelopmentEntry>,
+}
+
+impl DevelopmentPool {
+    fn serves(&self, focus: DevelopmentFocus, secretary: SecretaryGroup) -> bool {
+        self.focus == focus
+            && (self.secretaries.is_empty() || self.secretaries.contains(&secretary))
+    }
+}
+
+/// The full development table. Pools are matched in order, so secretary-specific
+/// pools must precede the catch-all pool of the same focus.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevelopmentTable {
+    /// Development pools.
+    pub pools: Vec<DevelopmentPool>,
+}
+
+impl DevelopmentTable {
+    /// Weighted candidates for a recipe and secretary group.
+    ///
+    /// Entries whose minimum recipe is not met are dropped, which raises the
+    /// failure chance instead of renormalizing the pool.
+    pub fn candidates(
+        &self,
+        recipe: &[i64; 4],
+        secretary: SecretaryGroup,
+    ) -> Option<Vec<(i64, u64)>> {
+        let focus = DevelopmentFocus::from_recipe(recipe);
+        let pool = self.pools.iter().find(|pool| pool.serves(focus, secretary))?;
+        Some(
+            pool.entries
+                .iter()
+                .filter(|entry| recipe.iter().zip(entry.min_recipe).all(|(have, min)| *have >= min))
+                .map(|entry| (entry.slotitem_id, entry.weight))
+                .collect(),
+        )
+    }
+}
+
+impl Codex {
+    /// Resolve one development attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipe` - `[fuel, ammo, steel, bauxite]` put into the attempt.
+    /// * `secretary_stype` - `api_stype` of the first fleet's flagship.
+    /// * `hq_level` - HQ level; equipment rarer than `hq_level / 10` fails.
+    /// * `roll` - Uniform roll in `0..DEVELOPMENT_WEIGHT_TOTAL`.
+    ///
+    /// # Returns
+    ///
+    /// The developed slot item master id, or `None` on failure.
+    pub fn roll_development(
+        &self,
+        recipe: &[i64; 4],
+        secretary_stype: i64,
+        hq_level: i64,
+        roll: u64,
+    ) -> Option<i64> {
+        let secretary = SecretaryGroup::from_stype(secretary_stype);
+        let candidates = self
+            .development
+            .candidates(recipe, secretary)
+            .unwrap_or_else(|| self.legacy_development_candidates());
+        let slotitem_id = select_weighted_for_roll(&candidates, roll)?;
+
+        let rarity = self.find::<ApiMstSlotitem>(&slotitem_id).map_or(0, |mst| mst.api_rare);
+        (rarity * HQ_LEVELS_PER_RARITY <= hq_level).then_some(slotitem_id)
+    }
+
+    /// Uniform pool over every craftable slot item, used when no table pool
+    /// matches (e.g. a codex generated before development tables existed).
+    fn legacy_development_candidates(&self) -> Vec<(i64, u64)> {
+        let craftable: Vec<i64> = self
+            .slotitem_extra_info
+            .values()
+            .filter(|info| info.craftable)
+            .map(|info| info.api_id)
+            .collect();
+        if craftable.is_empty() {
+            return Vec::new();
+        }
+        let weight = (LEGACY_SUCCESS_WEIGHT / craftable.len() as u64).max(1);
+        craftable.into_iter().map(|id| (id, weight)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> DevelopmentTable {
+        DevelopmentTable {
+            pools: vec![
+                DevelopmentPool {
+                    focus: DevelopmentFocus::Bauxite,
+                    secretaries: vec![SecretaryGroup::Carrier],
+                    entries: vec![DevelopmentEntry {
+                        slotitem_id: 19,
+                        weight: 500,
+                        min_recipe: [0; 4],
+                    }],
+                },
+                DevelopmentPool {
+                    focus: DevelopmentFocus::Bauxite,
+                    secretaries: vec![],
+                    entries: vec![DevelopmentEntry {
+                        slotitem_id: 25,
+                        weight: 300,
+                        min_recipe: [0; 4],
+                    }],
+                },
+                DevelopmentPool {
+                    focus: DevelopmentFocus::Steel,
+                    secretaries: vec![],
+                    entries: vec![
+                        DevelopmentEntry {
+                            slotitem_id: 2,
+                            weight: 400,
+                            min_recipe: [0; 4],
+                        },
+                        DevelopmentEntry {
+                            slotitem_id: 8,
+                            weight: 100,
+                            min_recipe: [10, 10, 250, 10],
+                        },
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn focus_follows_largest_resource_with_stable_ties() {
+        assert_eq!(DevelopmentFocus::from_recipe(&[10, 10, 10, 10]), DevelopmentFocus::Fuel);
+        assert_eq!(DevelopmentFocus::from_recipe(&[10, 30, 30, 10]), DevelopmentFocus::Ammo);
+        assert_eq!(DevelopmentFocus::from_recipe(&[10, 90, 250, 30]), DevelopmentFocus::Steel);
+        assert_eq!(DevelopmentFocus::from_recipe(&[10, 10, 10, 20]), DevelopmentFocus::Bauxite);
+    }
+
+    #[test]
+    fn secretary_group_selects_specific_pool_first() {
+        let table = table();
+        let carrier = table.candidates(&[10, 10, 10, 20], SecretaryGroup::Carrier);
+        assert_eq!(carrier, Some(vec![(19, 500)]));
+        let destroyer = table.candidates(&[10, 10, 10, 20], SecretaryGroup::Destroyer);
+        assert_eq!(destroyer, Some(vec![(25, 300)]));
+        assert_eq!(table.candidates(&[10, 30, 10, 10], SecretaryGroup::Destroyer), None);
+    }
+
+    #[test]
+    fn min_recipe_gates_entries() {
+        let table = table();
+        let low = table.candidates(&[10, 10, 100, 10], SecretaryGroup::Battleship).unwrap();
+        assert_eq!(low, vec![(2, 400)]);
+        let high = table.candidates(&[10, 10, 250, 10], SecretaryGroup::Battleship).unwrap();
+        assert_eq!(high, vec![(2, 400), (8, 100)]);
+    }
+
+    #[test]
+    fn roll_beyond_pool_weight_fails() {
+        let candidates = [(2, 400), (8, 100)];
+        assert_eq!(select_weighted_for_roll(&candidates, 0), Some(2));
+        assert_eq!(select_weighted_for_roll(&candidates, 399), Some(2));
+        assert_eq!(select_weighted_for_roll(&candidates, 400), Some(8));
+        assert_eq!(select_weighted_for_roll(&candidates, 500), None);
+    }
+
+    #[test]
+    fn rarity_above_hq_gate_fails() {
+        let mut codex = Codex {
+            development: table(),
+            ..Default::default()
+        };
+        codex.manifest.api_mst_slotitem.push(ApiMstSlotitem {
+            api_id: 8,
+            api_rare: 2,
+            ..Default::default()
+        });
+
+        let recipe = [10, 10, 250, 10];
+        assert_eq!(codex.roll_development(&recipe, 9, 19, 450), None);
+        assert_eq!(codex.roll_development(&recipe, 9, 20, 450), Some(8));
+    }
+}
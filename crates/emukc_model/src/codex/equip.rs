@@ -0,0 +1,290 @@
+//! Which ship may carry which slot item, and where.
+//!
+//! A regular slot takes the equipment types of the ship type
+//! (`api_mst_stype.api_equip_type`), unless `api_mst_equip_ship` lists the
+//! ship, in which case that list alone decides, down to single items for
+//! some types. The extra slot (補強増設) first honours the per-item grants of
+//! `api_mst_equip_exslot_ship`, some of which need an improvement level, then
+//! takes the types of `api_mst_equip_exslot` the ship can equip, minus the
+//! types `api_mst_equip_limit_exslot` bars for that ship.
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::prelude::{ApiMstShip, ApiMstSlotitem};
+
+use super::Codex;
+
+/// `api_stypes` key of an `api_mst_equip_exslot_ship` grant that covers
+/// every ship type.
+const EXSLOT_ANY_STYPE: &str = "99";
+
+/// Why a slot item cannot be equipped.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EquipError {
+    /// The ship is not in the manifest
+    #[error("ship {0} not found")]
+    ShipNotFound(i64),
+
+    /// The slot item is not in the manifest
+    #[error("slot item {0} not found")]
+    SlotItemNotFound(i64),
+
+    /// The ship cannot equip the slot item at all
+    #[error("ship {ship_mst_id} cannot equip slot item {slotitem_mst_id}")]
+    NotEquippable {
+        /// Ship master ID.
+        ship_mst_id: i64,
+        /// Slot item master ID.
+        slotitem_mst_id: i64,
+    },
+
+    /// The slot item does not fit the ship's extra slot
+    #[error("slot item {slotitem_mst_id} cannot go in the extra slot of ship {ship_mst_id}")]
+    ExslotNotAllowed {
+        /// Ship master ID.
+        ship_mst_id: i64,
+        /// Slot item master ID.
+        slotitem_mst_id: i64,
+    },
+
+    /// The slot item fits the ship's extra slot only from a higher improvement level
+    #[error(
+        "slot item {slotitem_mst_id} needs improvement level {required} for the extra slot of ship {ship_mst_id}, got {level}"
+    )]
+    ExslotLevelRequired {
+        /// Ship master ID.
+        ship_mst_id: i64,
+        /// Slot item master ID.
+        slotitem_mst_id: i64,
+        /// Lowest improvement level accepted.
+        required: i64,
+        /// Improvement level of the slot item.
+        level: i64,
+    },
+}
+
+impl Codex {
+    /// Check whether a ship may carry a slot item.
+    ///
+    /// # Arguments
+    ///
+    /// * `ship_mst_id` - The ship manifest ID.
+    /// * `slotitem_mst_id` - The slot item manifest ID.
+    /// * `level` - Improvement level of the slot item.
+    /// * `ex_slot` - Whether the item goes in the extra slot.
+    pub fn check_equip(
+        &self,
+        ship_mst_id: i64,
+        slotitem_mst_id: i64,
+        level: i64,
+        ex_slot: bool,
+    ) -> Result<(), EquipError> {
+        let ship_mst =
+            self.manifest.find_ship(ship_mst_id).ok_or(EquipError::ShipNotFound(ship_mst_id))?;
+        let slotitem_mst = self
+            .manifest
+            .find_slotitem(slotitem_mst_id)
+            .ok_or(EquipError::SlotItemNotFound(slotitem_mst_id))?;
+
+        if ex_slot {
+            self.check_exslot_equip(ship_mst, slotitem_mst, level)
+        } else if self.ship_equips(ship_mst, slotitem_mst) {
+            Ok(())
+        } else {
+            Err(EquipError::NotEquippable {
+                ship_mst_id,
+                slotitem_mst_id,
+            })
+        }
+    }
+
+    /// Check if the ship can equip the slot item in a regular slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `ship_mst_id` - The ship manifest ID.
+    /// * `slotitem_mst_id` - The slot item manifest ID.
+    pub fn can_equip_slotitem(&self, ship_mst_id: i64, slotitem_mst_id: i64) -> bool {
+        self.check_equip(ship_mst_id, slotitem_mst_id, 0, false).is_ok()
+    }
+
+    /// Whether the ship's equipment types admit the slot item.
+    fn ship_equips(&self, ship_mst: &ApiMstShip, slotitem_mst: &ApiMstSlotitem) -> bool {
+        let type3 = slotitem_mst.api_type[2].to_string();
+
+        if let Some(equip_ship) = self.manifest.api_mst_equip_ship.get(&ship_mst.api_id.to_string())
+        {
+            // overrides the ship type entirely; a list narrows the type to those items
+            return match equip_ship.api_equip_type.get(&type3) {
+                Some(Some(slotitem_ids)) => slotitem_ids.contains(&slotitem_mst.api_id),
+                Some(None) => true,
+                None => false,
+            };
+        }
+
+        self.manifest
+            .api_mst_stype
+            .iter()
+            .find(|m| m.api_id == ship_mst.api_stype)
+            .and_then(|m| m.api_equip_type.get(&type3))
+            .is_some_and(|v| *v > 0)
+    }
+
+    fn check_exslot_equip(
+        &self,
+        ship_mst: &ApiMstShip,
+        slotitem_mst: &ApiMstSlotitem,
+        level: i64,
+    ) -> Result<(), EquipError> {
+        let ship_mst_id = ship_mst.api_id;
+        let slotitem_mst_id = slotitem_mst.api_id;
+        let type3 = slotitem_mst.api_type[2];
+
+        let grant = self
+            .manifest
+            .api_mst_equip_exslot_ship
+            .get(&slotitem_mst_id.to_string())
+            .filter(|grant| {
+                let listed = |keys: &Option<BTreeMap<String, i64>>, key: i64| {
+                    keys.as_ref().is_some_and(|keys| keys.contains_key(&key.to_string()))
+                };
+                listed(&grant.api_ship_ids, ship_mst_id)
+                    || listed(&grant.api_ctypes, ship_mst.api_ctype)
+                    || listed(&grant.api_stypes, ship_mst.api_stype)
+                    || grant.api_stypes.as_ref().is_some_and(|s| s.contains_key(EXSLOT_ANY_STYPE))
+            });
+        if grant.is_some_and(|grant| level >= grant.api_req_level) {
+            return Ok(());
+        }
+
+        let barred = self
+            .manifest
+            .api_mst_equip_limit_exslot
+            .get(&ship_mst_id.to_string())
+            .is_some_and(|types| types.contains(&type3));
+        if !barred
+            && self.manifest.api_mst_equip_exslot.contains(&type3)
+            && self.ship_equips(ship_mst, slotitem_mst)
+        {
+            return Ok(());
+        }
+
+        Err(match grant {
+            Some(grant) => EquipError::ExslotLevelRequired {
+                ship_mst_id,
+                slotitem_mst_id,
+                required: grant.api_req_level,
+                level,
+            },
+            None => EquipError::ExslotNotAllowed {
+                ship_mst_id,
+                slotitem_mst_id,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::{ApiMstEquipExslotShip, ApiMstEquipShip, ApiMstStype};
+
+    use super::*;
+
+    const DD: i64 = 2;
+    const SMALL_GUN: i64 = 1;
+    const TORPEDO: i64 = 5;
+    const SMALL_RADAR: i64 = 12;
+    const REPAIR_PERSONNEL: i64 = 23;
+
+    /// A destroyer (1), its sister (2) listed in `api_mst_equip_ship`, and one
+    /// item per type, with the item id equal to its type.
+    fn codex() -> Codex {
+        let mut codex = Codex::default();
+        for api_id in [1, 2] {
+            codex.manifest.api_mst_ship.push(ApiMstShip {
+                api_id,
+                api_stype: DD,
+                api_ctype: 12,
+                ..Default::default()
+            });
+        }
+        codex.manifest.api_mst_stype.push(ApiMstStype {
+            api_id: DD,
+            api_equip_type: [SMALL_GUN, TORPEDO, SMALL_RADAR, REPAIR_PERSONNEL]
+                .into_iter()
+                .map(|t| (t.to_string(), 1))
+                .collect(),
+            ..Default::default()
+        });
+        for type3 in [SMALL_GUN, TORPEDO, SMALL_RADAR, REPAIR_PERSONNEL] {
+            codex.manifest.api_mst_slotitem.push(ApiMstSlotitem {
+                api_id: type3,
+                api_type: [0, 0, type3, 0, 0],
+                ..Default::default()
+            });
+        }
+        codex.manifest.api_mst_equip_ship.insert(
+            "2".to_string(),
+            ApiMstEquipShip {
+                api_equip_type: BTreeMap::from([
+                    (SMALL_GUN.to_string(), None),
+                    (SMALL_RADAR.to_string(), Some(vec![99])),
+                ]),
+            },
+        );
+        codex.manifest.api_mst_equip_exslot = vec![REPAIR_PERSONNEL];
+        codex
+    }
+
+    #[test]
+    fn regular_slot_follows_ship_type_and_ship_override() {
+        let codex = codex();
+
+        assert!(codex.can_equip_slotitem(1, TORPEDO));
+        assert!(codex.can_equip_slotitem(1, SMALL_RADAR));
+        assert!(codex.can_equip_slotitem(2, SMALL_GUN));
+        // the override drops torpedoes and narrows radars to item 99
+        assert!(!codex.can_equip_slotitem(2, TORPEDO));
+        assert!(!codex.can_equip_slotitem(2, SMALL_RADAR));
+        assert_eq!(codex.check_equip(3, SMALL_GUN, 0, false), Err(EquipError::ShipNotFound(3)));
+        assert_eq!(codex.check_equip(1, 7, 0, false), Err(EquipError::SlotItemNotFound(7)));
+    }
+
+    #[test]
+    fn extra_slot_honours_allowlist_limits_and_grants() {
+        let mut codex = codex();
+
+        assert_eq!(codex.check_equip(1, REPAIR_PERSONNEL, 0, true), Ok(()));
+        assert_eq!(
+            codex.check_equip(1, SMALL_RADAR, 0, true),
+            Err(EquipError::ExslotNotAllowed {
+                ship_mst_id: 1,
+                slotitem_mst_id: SMALL_RADAR,
+            })
+        );
+
+        codex.manifest.api_mst_equip_limit_exslot.insert("1".to_string(), vec![REPAIR_PERSONNEL]);
+        assert!(codex.check_equip(1, REPAIR_PERSONNEL, 0, true).is_err());
+
+        codex.manifest.api_mst_equip_exslot_ship.insert(
+            SMALL_RADAR.to_string(),
+            ApiMstEquipExslotShip {
+                api_ctypes: Some(BTreeMap::from([("12".to_string(), 1)])),
+                api_req_level: 4,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            codex.check_equip(1, SMALL_RADAR, 3, true),
+            Err(EquipError::ExslotLevelRequired {
+                ship_mst_id: 1,
+                slotitem_mst_id: SMALL_RADAR,
+                required: 4,
+                level: 3,
+            })
+        );
+        assert_eq!(codex.check_equip(1, SMALL_RADAR, 4, true), Ok(()));
+    }
+}
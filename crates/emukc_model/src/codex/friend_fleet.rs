@@ -0,0 +1,200 @@
+//! Friend fleet (友軍艦隊) tables.
+//!
+//! Event maps send a friend fleet into the night battle of some nodes when
+//! the player has asked for one (`api_req_member/set_friendly_request`).
+//! Every map lists its candidate fleets; one is drawn by the weight of the
+//! player's request type.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::kc2::{KcApiShip, KcApiSlotItem, level};
+
+use super::Codex;
+
+/// One ship of a friend fleet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriendFleetShip {
+    /// Ship master id.
+    pub ship_id: i64,
+
+    /// Ship level.
+    pub level: i64,
+
+    /// Equipment master ids, at most five.
+    #[serde(default)]
+    pub slots: Vec<i64>,
+
+    /// Reinforcement expansion slot master id, `0` for none.
+    #[serde(default)]
+    pub slot_ex: i64,
+
+    /// Max HP override; the ship's own max HP when absent.
+    #[serde(default)]
+    pub hp: Option<i64>,
+
+    /// `[firepower, torpedo, anti-air, armor]` override, as in `api_Param`.
+    #[serde(default)]
+    pub param: Option<[i64; 4]>,
+
+    /// Voice played when the fleet arrives, `0` for none.
+    #[serde(default)]
+    pub voice_id: i64,
+}
+
+/// A friend fleet that may join a map's night battles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriendFleet {
+    /// Cell numbers the fleet covers; empty for every night battle of the map.
+    #[serde(default)]
+    pub cells: Vec<i64>,
+
+    /// Draw weight for a normal (`0`) and a strong (`1`) request.
+    pub weights: [u64; 2],
+
+    /// `api_production_type` of the arrival cut-in.
+    #[serde(default = "default_production_type")]
+    pub production_type: i64,
+
+    /// Ships, flagship first.
+    pub ships: Vec<FriendFleetShip>,
+}
+
+const fn default_production_type() -> i64 {
+    1
+}
+
+/// Friend fleets keyed by map id (`area * 10 + map`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriendFleetTable {
+    /// Candidate fleets per map.
+    pub maps: BTreeMap<i64, Vec<FriendFleet>>,
+}
+
+impl FriendFleetTable {
+    /// Weighted friend fleets covering a map cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_id` - Map id (`area * 10 + map`).
+    /// * `cell_no` - Cell number of the battle node.
+    /// * `request_type` - `api_request_type`: `0` normal, `1` strong.
+    ///
+    /// # Returns
+    ///
+    /// `(fleet, weight)` pairs in table order, zero weights dropped.
+    pub fn candidates(
+        &self,
+        map_id: i64,
+        cell_no: i64,
+        request_type: i64,
+    ) -> Vec<(&FriendFleet, u64)> {
+        let weight_idx = usize::from(request_type > 0);
+        self.maps
+            .get(&map_id)
+            .into_iter()
+            .flatten()
+            .filter(|fleet| fleet.cells.is_empty() || fleet.cells.contains(&cell_no))
+            .map(|fleet| (fleet, fleet.weights[weight_idx]))
+            .filter(|(_, weight)| *weight > 0)
+            .collect()
+    }
+}
+
+impl Codex {
+    /// Create a friend fleet ship with its equipment.
+    ///
+    /// Slot items get the ids `1..`, in slot order with the expansion slot
+    /// last, and the stats include their bonuses before the table's `hp` /
+    /// `param` overrides. Returns `None` when the ship or an item is unknown.
+    pub fn new_friend_fleet_ship(
+        &self,
+        entry: &FriendFleetShip,
+    ) -> Option<(KcApiShip, Vec<KcApiSlotItem>)> {
+        let (mut ship, _) = self.new_ship(entry.ship_id)?;
+        let slot_item = |api_id: i64, mst_id: i64| KcApiSlotItem {
+            api_id,
+            api_slotitem_id: mst_id,
+            api_locked: 0,
+            api_level: 0,
+            api_alv: None,
+        };
+
+        let mut slot_items: Vec<KcApiSlotItem> = Vec::new();
+        for (idx, mst_id) in entry.slots.iter().take(5).enumerate() {
+            self.manifest.find_slotitem(*mst_id)?;
+            let item = slot_item(idx as i64 + 1, *mst_id);
+            ship.api_slot[idx] = item.api_id;
+            slot_items.push(item);
+        }
+        if entry.slot_ex > 0 {
+            self.manifest.find_slotitem(entry.slot_ex)?;
+            let item = slot_item(slot_items.len() as i64 + 1, entry.slot_ex);
+            ship.api_slot_ex = item.api_id;
+            slot_items.push(item);
+        }
+
+        let exp_now = level::ship_level_required_exp(entry.level.clamp(1, 99));
+        let (_, next_exp) = level::exp_to_ship_level(exp_now);
+        ship.api_lv = entry.level;
+        ship.api_exp = [exp_now, next_exp, 0];
+        self.cal_ship_status(&mut ship, &slot_items, false).ok()?;
+
+        if let Some(hp) = entry.hp {
+            ship.api_maxhp = hp;
+        }
+        ship.api_nowhp = ship.api_maxhp;
+        if let Some([firepower, torpedo, aa, armor]) = entry.param {
+            ship.api_karyoku[0] = firepower;
+            ship.api_raisou[0] = torpedo;
+            ship.api_taiku[0] = aa;
+            ship.api_soukou[0] = armor;
+        }
+        ship.api_cond = 49;
+
+        Some((ship, slot_items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fleet(cells: Vec<i64>, weights: [u64; 2]) -> FriendFleet {
+        FriendFleet {
+            cells,
+            weights,
+            production_type: 1,
+            ships: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn candidates_follow_cell_and_request_type() {
+        let table = FriendFleetTable {
+            maps: BTreeMap::from([(
+                591,
+                vec![fleet(vec![], [10, 0]), fleet(vec![17], [5, 20]), fleet(vec![3], [1, 1])],
+            )]),
+        };
+
+        let normal: Vec<u64> =
+            table.candidates(591, 17, 0).into_iter().map(|(_, weight)| weight).collect();
+        assert_eq!(normal, vec![10, 5]);
+
+        let strong: Vec<u64> =
+            table.candidates(591, 17, 1).into_iter().map(|(_, weight)| weight).collect();
+        assert_eq!(strong, vec![20]);
+
+        assert!(table.candidates(592, 17, 0).is_empty());
+    }
+
+    #[test]
+    fn production_type_defaults_to_normal() {
+        let fleet: FriendFleet =
+            serde_json::from_str(r#"{"weights": [1, 1], "ships": []}"#).unwrap();
+        assert_eq!(fleet.production_type, 1);
+        assert!(fleet.cells.is_empty());
+    }
+}
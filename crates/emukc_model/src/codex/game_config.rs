@@ -65,6 +65,95 @@ impl Default for ExpConfig {
     }
 }
 
+/// Sortie ship drop configuration.
+///
+/// Applies to drop entries without explicit per-rank weights, and to cells
+/// without a no-drop entry.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DropConfig {
+    /// Chance of no drop on an S rank.
+    pub no_drop_rate_s: f64,
+
+    /// Chance of no drop on an A rank.
+    pub no_drop_rate_a: f64,
+
+    /// Chance of no drop on a B rank.
+    pub no_drop_rate_b: f64,
+
+    /// Drop weight by ship rarity (`api_backs`), starting at rarity 1.
+    pub rarity_weights: Vec<i64>,
+
+    /// Highest rarity that drops on an A rank.
+    pub max_rarity_a: i64,
+
+    /// Highest rarity that drops on a B rank.
+    pub max_rarity_b: i64,
+
+    /// Lowest rarity that `difficulty_factors` apply to.
+    pub rare_rarity: i64,
+
+    /// Weight multiplier of rare ships by event difficulty, 丁 to 甲.
+    pub difficulty_factors: Vec<f64>,
+}
+
+/// Stock KanColle does not publish drop rates; these defaults approximate the
+/// community-observed shape: some sorties drop nothing, rarer ships drop less
+/// often, the rarest only on S rank, and harder event difficulties favor rare
+/// ships. Override in `[game.drop]` in `emukc.config.toml`.
+#[expect(clippy::doc_markdown)]
+impl Default for DropConfig {
+    fn default() -> Self {
+        Self {
+            no_drop_rate_s: 0.1,
+            no_drop_rate_a: 0.3,
+            no_drop_rate_b: 0.5,
+            rarity_weights: vec![100, 100, 100, 60, 30, 12, 5, 2],
+            max_rarity_a: 5,
+            max_rarity_b: 4,
+            rare_rarity: 5,
+            difficulty_factors: vec![0.5, 0.75, 1.0, 1.5],
+        }
+    }
+}
+
+impl DropConfig {
+    /// Chance of no drop for a win rank; ranks below B never drop.
+    pub fn no_drop_rate(&self, win_rank: &str) -> f64 {
+        match win_rank {
+            "S" => self.no_drop_rate_s,
+            "A" => self.no_drop_rate_a,
+            "B" => self.no_drop_rate_b,
+            _ => 1.0,
+        }
+    }
+
+    /// Drop weight of a ship of `rarity` on `win_rank`, scaled by event
+    /// `difficulty` (1 = 丁 to 4 = 甲) for rare ships.
+    pub fn rarity_weight(&self, rarity: i64, win_rank: &str, difficulty: Option<i64>) -> i64 {
+        let max_rarity = match win_rank {
+            "S" => i64::MAX,
+            "A" => self.max_rarity_a,
+            "B" => self.max_rarity_b,
+            _ => return 0,
+        };
+        if rarity > max_rarity {
+            return 0;
+        }
+
+        let idx = (rarity.max(1) - 1) as usize;
+        let Some(&weight) = self.rarity_weights.get(idx).or(self.rarity_weights.last()) else {
+            return 0;
+        };
+        let factor = difficulty
+            .filter(|_| rarity >= self.rare_rarity)
+            .and_then(|d| self.difficulty_factors.get((d - 1).max(0) as usize))
+            .copied()
+            .unwrap_or(1.0);
+
+        (weight as f64 * factor).round() as i64
+    }
+}
+
 /// Game configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GameConfig {
@@ -81,6 +170,10 @@ pub struct GameConfig {
     #[serde(default)]
     pub exp: ExpConfig,
 
+    /// Sortie ship drop configuration.
+    #[serde(default)]
+    pub drop: DropConfig,
+
     /// God mode: friendly ships take zero damage. Debug only.
     #[serde(default)]
     pub god_mode: bool,
@@ -101,6 +194,34 @@ mod tests {
         assert_eq!(cfg.practice_exp_boost, 1.0);
     }
 
+    #[test]
+    fn drop_config_default_pins_rates() {
+        let cfg = DropConfig::default();
+        assert_eq!(cfg.no_drop_rate_s, 0.1);
+        assert_eq!(cfg.no_drop_rate_a, 0.3);
+        assert_eq!(cfg.no_drop_rate_b, 0.5);
+        assert_eq!(cfg.rarity_weights, vec![100, 100, 100, 60, 30, 12, 5, 2]);
+        assert_eq!(cfg.max_rarity_a, 5);
+        assert_eq!(cfg.max_rarity_b, 4);
+        assert_eq!(cfg.rare_rarity, 5);
+        assert_eq!(cfg.difficulty_factors, vec![0.5, 0.75, 1.0, 1.5]);
+    }
+
+    #[test]
+    fn drop_config_gates_rarity_by_rank_and_scales_by_difficulty() {
+        let cfg = DropConfig::default();
+        assert_eq!(cfg.rarity_weight(3, "B", None), 100);
+        assert_eq!(cfg.rarity_weight(5, "B", None), 0);
+        assert_eq!(cfg.rarity_weight(5, "A", None), 30);
+        assert_eq!(cfg.rarity_weight(6, "A", None), 0);
+        assert_eq!(cfg.rarity_weight(6, "S", None), 12);
+        assert_eq!(cfg.rarity_weight(6, "S", Some(4)), 18);
+        assert_eq!(cfg.rarity_weight(6, "S", Some(1)), 6);
+        assert_eq!(cfg.rarity_weight(3, "S", Some(4)), 100);
+        assert_eq!(cfg.rarity_weight(3, "C", None), 0);
+        assert_eq!(cfg.no_drop_rate("C"), 1.0);
+    }
+
     #[test]
     fn docking_config_default_pins_no_adjustment() {
         let cfg = DockingConfig::default();
@@ -0,0 +1,131 @@
+//! Historical ship bonus (特効) tables.
+//!
+//! Event maps, and a few regular ones, multiply the damage some ships deal
+//! at some nodes, usually ships that fought the operation the map is based
+//! on. A bonus names the ships it boosts by id or class, may require them
+//! to carry certain equipment, and may only count against some enemies.
+//! The multiplier applies after the damage cap.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// One damage bonus of a map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalBonus {
+    /// Cell numbers the bonus applies at; empty for every node of the map.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cells: Vec<i64>,
+
+    /// Enemy ship master ids the bonus counts against; empty for every enemy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enemy_ids: Vec<i64>,
+
+    /// Boosted ship master ids.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ship_ids: Vec<i64>,
+
+    /// Boosted ship classes (`api_ctype`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ship_classes: Vec<i64>,
+
+    /// Equipment master ids the ship must carry one of; empty for none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equipment: Vec<i64>,
+
+    /// Post-cap damage multiplier.
+    pub multiplier: f64,
+}
+
+impl HistoricalBonus {
+    /// Whether the bonus boosts a ship.
+    ///
+    /// A bonus naming neither ships nor classes boosts every ship carrying
+    /// the required equipment.
+    ///
+    /// # Arguments
+    ///
+    /// * `ship_id` - Ship master id.
+    /// * `ship_class` - Ship class (`api_ctype`).
+    /// * `equipment` - Master ids of the ship's equipment.
+    pub fn applies_to_ship(&self, ship_id: i64, ship_class: i64, equipment: &[i64]) -> bool {
+        let ship_matches = (self.ship_ids.is_empty() && self.ship_classes.is_empty())
+            || self.ship_ids.contains(&ship_id)
+            || self.ship_classes.contains(&ship_class);
+        let equipment_matches =
+            self.equipment.is_empty() || self.equipment.iter().any(|id| equipment.contains(id));
+        ship_matches && equipment_matches
+    }
+
+    /// Whether the bonus counts against an enemy ship.
+    pub fn applies_to_enemy(&self, enemy_id: i64) -> bool {
+        self.enemy_ids.is_empty() || self.enemy_ids.contains(&enemy_id)
+    }
+}
+
+/// Historical bonuses keyed by map id (`area * 10 + map`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalBonusTable {
+    /// Bonuses per map.
+    pub maps: BTreeMap<i64, Vec<HistoricalBonus>>,
+}
+
+impl HistoricalBonusTable {
+    /// Bonuses in effect at a map cell, in table order.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_id` - Map id (`area * 10 + map`).
+    /// * `cell_no` - Cell number of the battle node.
+    pub fn for_node(&self, map_id: i64, cell_no: i64) -> Vec<&HistoricalBonus> {
+        self.maps
+            .get(&map_id)
+            .into_iter()
+            .flatten()
+            .filter(|bonus| bonus.cells.is_empty() || bonus.cells.contains(&cell_no))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonus_matches_node_ship_and_enemy() {
+        let table: HistoricalBonusTable = serde_json::from_str(
+            r#"{
+                "maps": {
+                    "591": [
+                        { "cells": [17], "ship_ids": [487], "multiplier": 1.25 },
+                        { "ship_classes": [20], "equipment": [68], "enemy_ids": [1653], "multiplier": 1.1 }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(table.for_node(591, 17).len(), 2);
+        let [bonus] = table.for_node(591, 3)[..] else {
+            panic!("expected only the map-wide bonus");
+        };
+        assert!(table.for_node(592, 17).is_empty());
+
+        assert!(bonus.applies_to_ship(1, 20, &[68, 0]));
+        assert!(!bonus.applies_to_ship(1, 20, &[]));
+        assert!(!bonus.applies_to_ship(1, 21, &[68]));
+        assert!(bonus.applies_to_enemy(1653));
+        assert!(!bonus.applies_to_enemy(1501));
+
+        let any_ship = HistoricalBonus {
+            cells: vec![],
+            enemy_ids: vec![],
+            ship_ids: vec![],
+            ship_classes: vec![],
+            equipment: vec![],
+            multiplier: 1.0,
+        };
+        assert!(any_ship.applies_to_ship(1, 1, &[]));
+        assert!(any_ship.applies_to_enemy(1501));
+    }
+}
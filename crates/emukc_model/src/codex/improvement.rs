@@ -0,0 +1,257 @@
+//! Akashi's Arsenal (明石の改修工廠) improvement recipes.
+//!
+//! A recipe improves one equipment when the second ship of the first fleet
+//! is one of its assistant ships on the current JST weekday. Every attempt
+//! costs the recipe's resources; the development materials, improvement
+//! materials and consumed equipment depend on the star range the equipment
+//! is in. Recipes with an upgrade turn a ★max equipment into another one.
+
+pub use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+/// Highest improvement level (★max).
+pub const MAX_IMPROVEMENT_LEVEL: i64 = 10;
+
+/// Improvement recipes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImprovementTable {
+    /// All recipes, ordered by id.
+    pub recipes: Vec<ImprovementRecipe>,
+}
+
+/// How one equipment is improved.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImprovementRecipe {
+    /// Recipe id, sent as `api_id` of the improvement list.
+    pub id: i64,
+
+    /// Improved slot item master id.
+    pub slotitem_id: i64,
+
+    /// Fuel per attempt.
+    pub fuel: i64,
+
+    /// Ammo per attempt.
+    pub ammo: i64,
+
+    /// Steel per attempt.
+    pub steel: i64,
+
+    /// Bauxite per attempt.
+    pub bauxite: i64,
+
+    /// Assistant ships and the weekdays they help on.
+    pub helpers: Vec<ImprovementHelper>,
+
+    /// Costs per star range.
+    pub stages: Vec<ImprovementStage>,
+
+    /// What the equipment turns into when improved at ★max.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade: Option<ImprovementUpgrade>,
+}
+
+/// An assistant ship of a recipe.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImprovementHelper {
+    /// Ship master id; `0` for any ship.
+    pub ship_id: i64,
+
+    /// Weekdays (JST) the ship helps on.
+    pub weekdays: Vec<Weekday>,
+}
+
+/// Costs of improving from a range of levels.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImprovementStage {
+    /// Lowest level of the range.
+    pub from_level: i64,
+
+    /// Highest level of the range, inclusive.
+    pub to_level: i64,
+
+    /// Development materials per attempt.
+    pub devmat: i64,
+
+    /// Development materials per guaranteed attempt.
+    pub certain_devmat: i64,
+
+    /// Improvement materials (screws) per attempt.
+    pub screw: i64,
+
+    /// Improvement materials per guaranteed attempt.
+    pub certain_screw: i64,
+
+    /// Slot items consumed by a successful attempt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slot_items: Vec<ImprovementItemCost>,
+
+    /// Use items consumed by a successful attempt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub use_items: Vec<ImprovementItemCost>,
+}
+
+/// An item consumed by an improvement.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImprovementItemCost {
+    /// Item master id.
+    pub id: i64,
+
+    /// Number of items.
+    pub count: i64,
+}
+
+/// The equipment a ★max equipment is upgraded to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImprovementUpgrade {
+    /// Slot item master id after the upgrade.
+    pub slotitem_id: i64,
+
+    /// Improvement level after the upgrade.
+    pub level: i64,
+}
+
+impl ImprovementRecipe {
+    /// Whether the recipe is offered with an assistant ship on a weekday.
+    ///
+    /// # Arguments
+    ///
+    /// * `helper_ship_id` - Master id of the second ship of the first fleet.
+    /// * `weekday` - The JST weekday.
+    pub fn available(&self, helper_ship_id: i64, weekday: Weekday) -> bool {
+        self.helpers.iter().any(|helper| {
+            (helper.ship_id == 0 || helper.ship_id == helper_ship_id)
+                && helper.weekdays.contains(&weekday)
+        })
+    }
+
+    /// Costs of improving an equipment at `level`, if it can be improved further.
+    pub fn stage(&self, level: i64) -> Option<&ImprovementStage> {
+        if level >= MAX_IMPROVEMENT_LEVEL && self.upgrade.is_none() {
+            return None;
+        }
+        self.stages.iter().find(|stage| (stage.from_level..=stage.to_level).contains(&level))
+    }
+}
+
+impl ImprovementTable {
+    /// Find a recipe by id.
+    pub fn recipe(&self, id: i64) -> Option<&ImprovementRecipe> {
+        self.recipes.iter().find(|recipe| recipe.id == id)
+    }
+
+    /// Recipes offered with an assistant ship on a weekday, one per equipment.
+    ///
+    /// When an equipment has several, a recipe with an upgrade is preferred.
+    ///
+    /// # Arguments
+    ///
+    /// * `helper_ship_id` - Master id of the second ship of the first fleet.
+    /// * `weekday` - The JST weekday.
+    pub fn available_recipes(
+        &self,
+        helper_ship_id: i64,
+        weekday: Weekday,
+    ) -> Vec<&ImprovementRecipe> {
+        let mut recipes: Vec<&ImprovementRecipe> = Vec::new();
+        for recipe in self.recipes.iter().filter(|r| r.available(helper_ship_id, weekday)) {
+            match recipes.iter_mut().find(|r| r.slotitem_id == recipe.slotitem_id) {
+                Some(existing) if existing.upgrade.is_none() && recipe.upgrade.is_some() => {
+                    *existing = recipe;
+                }
+                Some(_) => {}
+                None => recipes.push(recipe),
+            }
+        }
+        recipes
+    }
+}
+
+/// Chance that a regular (not guaranteed) attempt succeeds.
+///
+/// The game does not publish the rates; these follow community estimates.
+///
+/// # Arguments
+///
+/// * `level` - Improvement level before the attempt.
+pub fn improvement_success_rate(level: i64) -> f64 {
+    match level {
+        ..=2 => 1.0,
+        3..=5 => 0.9,
+        6..=7 => 0.8,
+        8 => 0.7,
+        _ => 0.6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(from_level: i64, to_level: i64) -> ImprovementStage {
+        ImprovementStage {
+            from_level,
+            to_level,
+            devmat: 1,
+            certain_devmat: 2,
+            screw: 1,
+            certain_screw: 2,
+            ..Default::default()
+        }
+    }
+
+    fn table() -> ImprovementTable {
+        ImprovementTable {
+            recipes: vec![
+                ImprovementRecipe {
+                    id: 1,
+                    slotitem_id: 2,
+                    helpers: vec![ImprovementHelper {
+                        ship_id: 0,
+                        weekdays: vec![Weekday::Mon, Weekday::Tue],
+                    }],
+                    stages: vec![stage(0, 5), stage(6, 9)],
+                    ..Default::default()
+                },
+                ImprovementRecipe {
+                    id: 2,
+                    slotitem_id: 2,
+                    helpers: vec![ImprovementHelper {
+                        ship_id: 182,
+                        weekdays: vec![Weekday::Mon],
+                    }],
+                    stages: vec![stage(0, 5), stage(6, 9), stage(10, 10)],
+                    upgrade: Some(ImprovementUpgrade {
+                        slotitem_id: 63,
+                        level: 0,
+                    }),
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn available_recipes_follow_helper_and_weekday() {
+        let table = table();
+
+        let ids = |helper, weekday| {
+            table.available_recipes(helper, weekday).iter().map(|r| r.id).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(182, Weekday::Mon), vec![2]);
+        assert_eq!(ids(187, Weekday::Mon), vec![1]);
+        assert_eq!(ids(182, Weekday::Tue), vec![1]);
+        assert!(ids(182, Weekday::Sun).is_empty());
+    }
+
+    #[test]
+    fn stage_stops_at_max_without_upgrade() {
+        let table = table();
+
+        assert_eq!(table.recipe(1).unwrap().stage(5).unwrap().from_level, 0);
+        assert_eq!(table.recipe(1).unwrap().stage(9).unwrap().from_level, 6);
+        assert!(table.recipe(1).unwrap().stage(MAX_IMPROVEMENT_LEVEL).is_none());
+        assert_eq!(table.recipe(2).unwrap().stage(MAX_IMPROVEMENT_LEVEL).unwrap().from_level, 10);
+        assert!(table.recipe(3).is_none());
+    }
+}
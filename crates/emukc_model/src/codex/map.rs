@@ -3,7 +3,9 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 mod debug;
+mod gimmick;
 mod merge;
+mod ship_drop;
 mod types;
 
 #[expect(deprecated)]
@@ -13,6 +15,8 @@ use crate::{
     profile::map_record::MapRefreshType,
 };
 
+pub use gimmick::*;
+pub use ship_drop::{ShipDropEntry, ShipDropTable};
 pub use types::*;
 
 use merge::merge_definition as merge_definition_impl;
@@ -22,6 +26,9 @@ pub use merge::{build_cell_no_map, merge_routing_overlay};
 /// P-unlock map whose route is gated by a sub-gauge unlock.
 const P_UNLOCK_VARIANT_KEYS: [&str; 2] = ["pre_p_unlock", "post_p_unlock"];
 
+/// Route text marking a branch where the player picks the next cell.
+const PLAYER_SELECT_ROUTE_TEXT: &str = "能動分岐";
+
 /// Warnings produced by `MapDefinition::validate()`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum MapValidationWarning {
@@ -41,6 +48,12 @@ pub enum MapValidationWarning {
         to_cell_no: i64,
         variant: String,
     },
+    SelectCellNotInNextCells {
+        map_id: i64,
+        from_cell_no: i64,
+        to_cell_no: i64,
+        variant: String,
+    },
 }
 
 impl MapCatalog {
@@ -97,6 +110,7 @@ impl MapCatalog {
                     default_variant: String::new(),
                     rank_stage_ids: BTreeMap::new(),
                     variants: BTreeMap::new(),
+                    gimmicks: Vec::new(),
                 },
             );
         }
@@ -182,6 +196,7 @@ impl MapCatalog {
                                 node_label: Some("Start".to_string()),
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                             MapCellDefinition {
                                 cell_no: 1,
@@ -192,6 +207,7 @@ impl MapCatalog {
                                 node_label: None,
                                 master_cell_id: None,
                                 distance: None,
+                                select_cells: Vec::new(),
                             },
                         ],
                         routing_rules: BTreeMap::new(),
@@ -200,6 +216,7 @@ impl MapCatalog {
                         required_defeat_count: None,
                         clear_to_variant_key: None,
                         parse_warnings: Vec::new(),
+                        boss_debuff: Default::default(),
                     },
                 );
             }
@@ -209,7 +226,8 @@ impl MapCatalog {
 
 impl MapDefinition {
     /// Validate graph invariants across all variants.
-    /// Returns warnings for: self-loops, unreachable cells, routing-rule targets not in `next_cells`.
+    /// Returns warnings for: self-loops, unreachable cells, routing-rule and player-selectable
+    /// targets not in `next_cells`.
     /// Designed for warn-only use at codex load.
     pub fn validate(&self) -> Vec<MapValidationWarning> {
         let mut warnings = Vec::new();
@@ -238,6 +256,16 @@ impl MapDefinition {
                     has_incoming.insert(next);
                 }
             }
+            // Hidden nodes are reached through routes gimmicks open.
+            for effect in self.gimmicks.iter().flat_map(|gimmick| &gimmick.effects) {
+                if let GimmickEffect::OpenRoute {
+                    to,
+                    ..
+                } = effect
+                {
+                    has_incoming.extend(variant.node_cell_nos(to));
+                }
+            }
             let start_source_cell_nos = variant
                 .start_source_cells()
                 .into_iter()
@@ -272,6 +300,20 @@ impl MapDefinition {
                     }
                 }
             }
+
+            // Player-selectable targets not in next_cells
+            for cell in &variant.cells {
+                for &to_cell_no in &cell.select_cells {
+                    if !cell.next_cells.contains(&to_cell_no) {
+                        warnings.push(MapValidationWarning::SelectCellNotInNextCells {
+                            map_id,
+                            from_cell_no: cell.cell_no,
+                            to_cell_no,
+                            variant: vkey.clone(),
+                        });
+                    }
+                }
+            }
         }
 
         warnings
@@ -455,6 +497,41 @@ impl MapVariantDefinition {
             .collect()
     }
 
+    /// Mark cells whose routing rules are all unparsed `能動分岐` (player-chosen branch)
+    /// text as selectable, offering those rules' targets. Cells that already list
+    /// `select_cells` are left alone.
+    pub fn derive_select_cells(&mut self) {
+        for cell in &mut self.cells {
+            if !cell.select_cells.is_empty() {
+                continue;
+            }
+            let Some(rules) =
+                self.routing_rules.get(&cell.cell_no).filter(|rules| !rules.is_empty())
+            else {
+                continue;
+            };
+            let player_chosen = rules.iter().all(|rule| {
+                matches!(
+                    rule.predicate,
+                    RoutePredicate::Unknown { .. } | RoutePredicate::SourceUnknown { .. }
+                ) && rule.raw_text.contains(PLAYER_SELECT_ROUTE_TEXT)
+            });
+            if !player_chosen {
+                continue;
+            }
+            let mut targets = Vec::new();
+            for rule in rules {
+                if cell.next_cells.contains(&rule.to_cell_no) && !targets.contains(&rule.to_cell_no)
+                {
+                    targets.push(rule.to_cell_no);
+                }
+            }
+            if targets.len() > 1 {
+                cell.select_cells = targets;
+            }
+        }
+    }
+
     /// Whether a sortie can resolve a start cell for this variant. Boolean form of
     /// [`start_source_cells`](Self::start_source_cells), used by catalog assembly to guard
     /// destructive P-unlock normalization against an unroutable topology fold.
@@ -493,6 +570,14 @@ fn fold_base_topology_into_variant(
         if cell.distance.is_none() {
             cell.distance = base_cell.distance;
         }
+        if cell.select_cells.is_empty() {
+            cell.select_cells = base_cell
+                .select_cells
+                .iter()
+                .copied()
+                .filter(|next| cell_set.contains(next))
+                .collect();
+        }
     }
 
     if variant.routing_rules.is_empty() {
@@ -652,8 +737,10 @@ mod tests {
                     required_defeat_count: None,
                     clear_to_variant_key: None,
                     parse_warnings: Vec::new(),
+                    boss_debuff: Default::default(),
                 },
             )]),
+            gimmicks: Vec::new(),
         }
     }
 
@@ -752,6 +839,60 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn derive_select_cells_from_player_choice_rules() {
+        let player_choice = |from_cell_no, to_cell_no| RouteRule {
+            from_cell_no,
+            to_cell_no,
+            priority: 0,
+            weight: None,
+            probability_pct: None,
+            predicate: RoutePredicate::Unknown {
+                raw_text: "能動分岐".to_string(),
+            },
+            raw_text: "能動分岐".to_string(),
+        };
+        let mut def = valid_map_definition();
+        let variant = def.variants.get_mut("").unwrap();
+        variant.routing_rules.insert(0, vec![player_choice(0, 1), player_choice(0, 2)]);
+        variant.routing_rules.insert(
+            1,
+            vec![RouteRule {
+                predicate: RoutePredicate::Always,
+                raw_text: String::new(),
+                ..player_choice(1, 3)
+            }],
+        );
+
+        variant.derive_select_cells();
+        assert_eq!(variant.cell(0).unwrap().select_cells, vec![1, 2]);
+        assert!(variant.cell(1).unwrap().select_cells.is_empty());
+    }
+
+    #[test]
+    fn select_cell_not_in_next_cells() {
+        let mut def = valid_map_definition();
+        let variant = def.variants.get_mut("").unwrap();
+        variant.cells[0].select_cells = vec![1, 99];
+
+        let warnings = def.validate();
+        assert!(warnings.iter().any(|w| matches!(
+            w,
+            MapValidationWarning::SelectCellNotInNextCells {
+                from_cell_no: 0,
+                to_cell_no: 99,
+                ..
+            }
+        )));
+        assert!(!warnings.iter().any(|w| matches!(
+            w,
+            MapValidationWarning::SelectCellNotInNextCells {
+                to_cell_no: 1,
+                ..
+            }
+        )));
+    }
+
     #[test]
     fn synthetic_variant_boss_sentinel_is_zero() {
         let mut catalog = MapCatalog {
@@ -778,6 +919,7 @@ mod tests {
                 default_variant: String::new(),
                 rank_stage_ids: BTreeMap::new(),
                 variants: BTreeMap::new(),
+                gimmicks: Vec::new(),
             },
         );
         catalog.ensure_synthetic_variants();
@@ -854,6 +996,7 @@ mod tests {
                 ("pre_p_unlock".to_string(), pre),
                 ("post_p_unlock".to_string(), post),
             ]),
+            gimmicks: Vec::new(),
         }
     }
 
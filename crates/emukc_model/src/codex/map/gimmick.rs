@@ -0,0 +1,162 @@
+//! Event map gimmicks (ギミック).
+//!
+//! A gimmick is a list of conditions the player has to meet on a map, such as
+//! visiting a node or winning a battle there with some rank. Once all of them
+//! are met the gimmick triggers and its effects apply to the map: the boss
+//! fleet is debuffed, a hidden route opens, or the map moves to another stage
+//! (a new phase with more nodes).
+//!
+//! In map data a gimmick reads like:
+//!
+//! ```json
+//! {
+//!   "gimmick_id": "boss_debuff",
+//!   "gauge_index": 2,
+//!   "ranks": [3, 4],
+//!   "conditions": [
+//!     { "kind": "win", "node": "P", "rank": "S" },
+//!     { "kind": "visit", "node": "U", "count": 2 }
+//!   ],
+//!   "effects": [{ "kind": "boss_debuff", "armor": 20 }]
+//! }
+//! ```
+//!
+//! Nodes are referred to by label, or by cell number for unlabeled cells.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapGimmickDefinition {
+    pub gimmick_id: String,
+    /// Gauge the gimmick belongs to; `None` applies to every gauge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gauge_index: Option<i64>,
+    /// Selected ranks (1 丁 … 4 甲) the gimmick exists on; empty for all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranks: Vec<i64>,
+    /// All of these must be met, in any order, across any number of sorties.
+    pub conditions: Vec<GimmickCondition>,
+    pub effects: Vec<GimmickEffect>,
+}
+
+impl MapGimmickDefinition {
+    /// Whether the gimmick exists on `gauge_index` with `rank` selected.
+    pub fn applies_to(&self, gauge_index: i64, rank: i64) -> bool {
+        self.gauge_index.is_none_or(|gauge| gauge == gauge_index)
+            && (self.ranks.is_empty() || self.ranks.contains(&rank))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GimmickCondition {
+    /// Reach `node` `count` times.
+    Visit {
+        node: String,
+        #[serde(default = "default_count")]
+        count: i64,
+    },
+    /// Win the battle at `node` with `rank` or better, `count` times.
+    Win {
+        node: String,
+        rank: String,
+        #[serde(default = "default_count")]
+        count: i64,
+    },
+    /// Another gimmick of the map has triggered.
+    Gimmick {
+        gimmick_id: String,
+    },
+}
+
+impl GimmickCondition {
+    /// How many times the condition has to be met.
+    pub fn required_count(&self) -> i64 {
+        match self {
+            Self::Visit {
+                count,
+                ..
+            }
+            | Self::Win {
+                count,
+                ..
+            } => (*count).max(1),
+            Self::Gimmick {
+                ..
+            } => 1,
+        }
+    }
+}
+
+fn default_count() -> i64 {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GimmickEffect {
+    /// Weaken the ships of the boss fleet.
+    BossDebuff {
+        #[serde(default)]
+        armor: i64,
+        #[serde(default)]
+        hp: i64,
+    },
+    /// Open a route from `from` to `to`, taken ahead of the node's rules.
+    OpenRoute {
+        from: String,
+        to: String,
+    },
+    /// Move the map to another stage, e.g. a phase revealing new nodes.
+    SwitchStage {
+        stage_id: String,
+    },
+}
+
+/// Stat reduction applied to the boss fleet by triggered gimmicks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BossDebuff {
+    #[serde(default)]
+    pub armor: i64,
+    #[serde(default)]
+    pub hp: i64,
+}
+
+impl BossDebuff {
+    pub fn is_empty(&self) -> bool {
+        self.armor <= 0 && self.hp <= 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gimmick_parses_from_map_data() {
+        let gimmick: MapGimmickDefinition = serde_json::from_str(
+            r#"{
+                "gimmick_id": "boss_debuff",
+                "gauge_index": 2,
+                "conditions": [
+                    { "kind": "win", "node": "P", "rank": "S" },
+                    { "kind": "visit", "node": "U", "count": 2 }
+                ],
+                "effects": [{ "kind": "boss_debuff", "armor": 20 }]
+            }"#,
+        )
+        .unwrap();
+
+        assert!(gimmick.applies_to(2, 1));
+        assert!(!gimmick.applies_to(1, 1));
+        assert_eq!(gimmick.conditions[0].required_count(), 1);
+        assert_eq!(gimmick.conditions[1].required_count(), 2);
+        assert_eq!(
+            gimmick.effects,
+            vec![GimmickEffect::BossDebuff {
+                armor: 20,
+                hp: 0
+            }]
+        );
+    }
+}
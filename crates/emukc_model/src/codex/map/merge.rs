@@ -39,6 +39,9 @@ pub(super) fn merge_definition(definition: &mut MapDefinition, other: MapDefinit
     if definition.rank_stage_ids.is_empty() {
         definition.rank_stage_ids = other.rank_stage_ids;
     }
+    if definition.gimmicks.is_empty() {
+        definition.gimmicks = other.gimmicks;
+    }
     let definition_has_named_variants = definition.variants.keys().any(|key| !key.is_empty());
     let fallback_variant = other.variants.get("").cloned();
     for (variant_key, variant) in other.variants {
@@ -143,6 +146,7 @@ fn remap_variant_to_definition_identity(
     for cell in &mut other.cells {
         cell.cell_no = remap_cell_no(cell.cell_no, &cell_no_map);
         remap_cell_nos(&mut cell.next_cells, &cell_no_map);
+        remap_cell_nos(&mut cell.select_cells, &cell_no_map);
     }
 
     let mut routing_rules = BTreeMap::<i64, Vec<RouteRule>>::new();
@@ -358,6 +362,9 @@ fn merge_cells(cells: &mut Vec<MapCellDefinition>, other_cells: Vec<MapCellDefin
                 if cell.distance.is_none() {
                     cell.distance = other.distance;
                 }
+                if cell.select_cells.is_empty() {
+                    cell.select_cells = other.select_cells;
+                }
             }
         }
     }
@@ -387,6 +394,7 @@ mod tests {
             node_label: Some(node_label.to_string()),
             master_cell_id: None,
             distance: None,
+            select_cells: Vec::new(),
         }
     }
 
@@ -407,6 +415,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
         let other = MapVariantDefinition {
             variant_key: String::new(),
@@ -456,6 +465,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
 
         merge_variant_definition(&mut definition, other);
@@ -501,6 +511,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
 
         // WikiWiki uses different cell numbering: A=5, B=6, C=7
@@ -589,6 +600,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
 
         // "Z" doesn't exist in primary — rule should preserve original cell_no
@@ -634,6 +646,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
 
         let empty_map = BTreeMap::new();
@@ -700,6 +713,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
         let other_labels: BTreeMap<String, i64> = BTreeMap::from([("A".into(), 10)]);
         // Rule targets cell 99 which doesn't exist in primary topology
@@ -740,6 +754,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
         let other_labels: BTreeMap<String, i64> = BTreeMap::from([("A".into(), 10)]);
         let fleets = BTreeMap::from([(
@@ -775,6 +790,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         };
         let other_labels: BTreeMap<String, i64> =
             BTreeMap::from([("A".into(), 10), ("Start".into(), 20)]);
@@ -817,6 +833,7 @@ mod tests {
             required_defeat_count: None,
             clear_to_variant_key: None,
             parse_warnings: Vec::new(),
+            boss_debuff: Default::default(),
         }
     }
 
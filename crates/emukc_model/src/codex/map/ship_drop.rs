@@ -0,0 +1,225 @@
+//! Weighted sortie ship drops.
+
+use crate::codex::{Codex, select_weighted_for_roll};
+
+use super::{MapVariantDefinition, NO_SHIP_DROP, ShipDropDefinition};
+
+/// Tag of drop entries that are not rolled (limited-time drops).
+const LIMITED_TAG: &str = "limited";
+
+/// Rarity assumed for ships without `api_backs`.
+const DEFAULT_RARITY: i64 = 1;
+
+/// One droppable ship and its weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShipDropEntry<'a> {
+    pub drop: &'a ShipDropDefinition,
+    pub weight: i64,
+}
+
+/// The drop outcomes of a cell for one win rank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShipDropTable<'a> {
+    /// Chance that nothing drops.
+    pub no_drop_rate: f64,
+    /// Ships that can drop, all with a positive weight.
+    pub entries: Vec<ShipDropEntry<'a>>,
+}
+
+impl<'a> ShipDropTable<'a> {
+    /// Build the table of `cell_no` for `win_rank`.
+    ///
+    /// Entries without explicit weights are weighted by ship rarity through
+    /// `game_cfg.drop`; `difficulty` (1 = 丁 to 4 = 甲) scales rare ships on
+    /// event maps. A [`NO_SHIP_DROP`] entry with weights sets the no-drop
+    /// chance relative to the ships; otherwise the configured rate applies.
+    ///
+    /// # Arguments
+    ///
+    /// * `codex` - The codex.
+    /// * `variant` - The map stage.
+    /// * `cell_no` - The cell number.
+    /// * `win_rank` - The battle rank, `S` to `E`.
+    /// * `difficulty` - The event difficulty, `None` for normal maps.
+    pub fn build(
+        codex: &Codex,
+        variant: &'a MapVariantDefinition,
+        cell_no: i64,
+        win_rank: &str,
+        difficulty: Option<i64>,
+    ) -> Self {
+        let cfg = &codex.game_cfg.drop;
+        let drops = variant.ship_drops(cell_no).unwrap_or_default();
+
+        let mut no_drop_weight = None;
+        let mut entries = Vec::new();
+        for drop in drops {
+            if drop.ship_id == NO_SHIP_DROP {
+                no_drop_weight = drop.weights.map(|weights| weights.for_rank(win_rank));
+                continue;
+            }
+            if drop.tags.iter().any(|tag| tag == LIMITED_TAG)
+                || codex.new_ship(drop.ship_id).is_none()
+            {
+                continue;
+            }
+
+            let weight = match drop.weights {
+                Some(weights) => weights.for_rank(win_rank),
+                None => {
+                    let rarity = codex
+                        .manifest
+                        .find_ship(drop.ship_id)
+                        .and_then(|mst| mst.api_backs)
+                        .unwrap_or(DEFAULT_RARITY);
+                    cfg.rarity_weight(rarity, win_rank, difficulty)
+                }
+            };
+            entries.push(ShipDropEntry {
+                drop,
+                weight,
+            });
+        }
+
+        match no_drop_weight {
+            Some(weight) => Self::with_no_drop_weight(entries, weight),
+            None => Self::new(entries, cfg.no_drop_rate(win_rank)),
+        }
+    }
+
+    /// A table from weighted entries and a no-drop chance.
+    pub fn new(entries: Vec<ShipDropEntry<'a>>, no_drop_rate: f64) -> Self {
+        let entries: Vec<_> = entries.into_iter().filter(|entry| entry.weight > 0).collect();
+        let no_drop_rate = if entries.is_empty() {
+            1.0
+        } else {
+            no_drop_rate.clamp(0.0, 1.0)
+        };
+
+        Self {
+            no_drop_rate,
+            entries,
+        }
+    }
+
+    /// A table where "nothing" competes with the ships at `no_drop_weight`.
+    pub fn with_no_drop_weight(entries: Vec<ShipDropEntry<'a>>, no_drop_weight: i64) -> Self {
+        let ship_weight: i64 = entries.iter().map(|entry| entry.weight.max(0)).sum();
+        let no_drop_weight = no_drop_weight.max(0);
+        let total = ship_weight + no_drop_weight;
+        let rate = if total > 0 {
+            no_drop_weight as f64 / total as f64
+        } else {
+            1.0
+        };
+
+        Self::new(entries, rate)
+    }
+
+    /// Sum of the ship weights.
+    pub fn total_weight(&self) -> i64 {
+        self.entries.iter().map(|entry| entry.weight).sum()
+    }
+
+    /// Chance that `entry` drops.
+    pub fn drop_rate(&self, entry: &ShipDropEntry<'_>) -> f64 {
+        let total = self.total_weight();
+        if total == 0 {
+            return 0.0;
+        }
+
+        (1.0 - self.no_drop_rate) * entry.weight as f64 / total as f64
+    }
+
+    /// Pick the drop for the given rolls.
+    ///
+    /// # Arguments
+    ///
+    /// * `no_drop_roll` - Uniform in `[0, 1)`; nothing drops below the no-drop rate.
+    /// * `weight_roll` - Uniform in `[0, total_weight)`.
+    pub fn select_for_roll(
+        &self,
+        no_drop_roll: f64,
+        weight_roll: u64,
+    ) -> Option<&'a ShipDropDefinition> {
+        if no_drop_roll < self.no_drop_rate {
+            return None;
+        }
+
+        let candidates: Vec<(i64, u64)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| (idx as i64, entry.weight as u64))
+            .collect();
+        select_weighted_for_roll(&candidates, weight_roll)
+            .map(|idx| self.entries[idx as usize].drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drop(ship_id: i64) -> ShipDropDefinition {
+        ShipDropDefinition {
+            ship_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn select_for_roll_honors_no_drop_and_weights() {
+        let (a, b) = (drop(1), drop(2));
+        let table = ShipDropTable::new(
+            vec![
+                ShipDropEntry {
+                    drop: &a,
+                    weight: 3,
+                },
+                ShipDropEntry {
+                    drop: &b,
+                    weight: 1,
+                },
+            ],
+            0.25,
+        );
+
+        assert_eq!(table.select_for_roll(0.1, 0), None);
+        assert_eq!(table.select_for_roll(0.5, 0).map(|d| d.ship_id), Some(1));
+        assert_eq!(table.select_for_roll(0.5, 2).map(|d| d.ship_id), Some(1));
+        assert_eq!(table.select_for_roll(0.5, 3).map(|d| d.ship_id), Some(2));
+        assert!((table.drop_rate(&table.entries[0]) - 0.5625).abs() < 1e-9);
+        assert!((table.drop_rate(&table.entries[1]) - 0.1875).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_weights_and_empty_tables_never_drop() {
+        let a = drop(1);
+        let table = ShipDropTable::new(
+            vec![ShipDropEntry {
+                drop: &a,
+                weight: 0,
+            }],
+            0.0,
+        );
+
+        assert!(table.entries.is_empty());
+        assert_eq!(table.no_drop_rate, 1.0);
+        assert_eq!(table.select_for_roll(0.99, 0), None);
+    }
+
+    #[test]
+    fn no_drop_weight_competes_with_ships() {
+        let a = drop(1);
+        let table = ShipDropTable::with_no_drop_weight(
+            vec![ShipDropEntry {
+                drop: &a,
+                weight: 30,
+            }],
+            10,
+        );
+
+        assert!((table.no_drop_rate - 0.25).abs() < 1e-9);
+    }
+}
@@ -1,4 +1,4 @@
-use super::split_map_id;
+use super::{BossDebuff, MapGimmickDefinition, split_map_id};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use serde::{Deserialize, Deserializer as SerdeDeserializer, Serialize, Serializer};
@@ -55,6 +55,8 @@ pub struct MapDefinition {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub rank_stage_ids: BTreeMap<i64, String>,
     pub variants: BTreeMap<String, MapVariantDefinition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gimmicks: Vec<MapGimmickDefinition>,
 }
 
 impl MapDefinition {
@@ -80,6 +82,7 @@ impl MapDefinition {
             default_variant: String::new(),
             rank_stage_ids: BTreeMap::new(),
             variants: BTreeMap::new(),
+            gimmicks: Vec::new(),
         }
     }
 }
@@ -100,6 +103,9 @@ pub struct MapVariantDefinition {
     pub clear_to_variant_key: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub parse_warnings: Vec<String>,
+    /// Set on the stage a sortie plays once gimmicks have debuffed the boss.
+    #[serde(default, skip_serializing_if = "BossDebuff::is_empty")]
+    pub boss_debuff: BossDebuff,
 }
 
 impl MapVariantDefinition {
@@ -118,6 +124,25 @@ impl MapVariantDefinition {
         index
     }
 
+    /// Returns the cells a gimmick node reference points at: every cell carrying the label,
+    /// or the cell with that number when no cell is labeled so.
+    pub fn node_cell_nos(&self, node: &str) -> Vec<i64> {
+        let labeled = self
+            .cells
+            .iter()
+            .filter(|cell| cell.node_label.as_deref() == Some(node))
+            .map(|cell| cell.cell_no)
+            .collect::<Vec<_>>();
+        if !labeled.is_empty() {
+            return labeled;
+        }
+        node.parse::<i64>()
+            .ok()
+            .filter(|cell_no| self.cells.iter().any(|cell| cell.cell_no == *cell_no))
+            .into_iter()
+            .collect()
+    }
+
     /// Returns all cell numbers that share the boss node's `node_label`.
     ///
     /// When a boss node is reachable via multiple incoming routes, each route produces
@@ -186,6 +211,10 @@ pub struct MapCellDefinition {
     pub master_cell_id: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub distance: Option<i64>,
+    /// Destinations the player picks from when leaving this cell (能動分岐);
+    /// empty when the route is decided by the routing rules.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub select_cells: Vec<i64>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -207,11 +236,40 @@ pub struct EnemyComposition {
     pub raw_ship_names: Vec<String>,
 }
 
+/// Ship ID of the drop entry that stands for "nothing dropped".
+pub const NO_SHIP_DROP: i64 = 0;
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ShipDropDefinition {
+    /// The dropped ship, or [`NO_SHIP_DROP`] for the cell's no-drop outcome.
     pub ship_id: i64,
     pub raw_ship_name: String,
     pub tags: Vec<String>,
+    /// Explicit per-rank weights; rarity-based weights apply when `None`.
+    pub weights: Option<RankDropWeights>,
+}
+
+/// Relative drop weights by win rank. A rank weighted 0 cannot drop the entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankDropWeights {
+    #[serde(default)]
+    pub s: i64,
+    #[serde(default)]
+    pub a: i64,
+    #[serde(default)]
+    pub b: i64,
+}
+
+impl RankDropWeights {
+    /// The weight for a win rank; ranks below B never drop.
+    pub fn for_rank(&self, win_rank: &str) -> i64 {
+        match win_rank {
+            "S" => self.s,
+            "A" => self.a,
+            "B" => self.b,
+            _ => 0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -224,6 +282,8 @@ enum CompactShipDropDefinition {
         tags: Vec<String>,
         #[serde(default, skip_serializing_if = "String::is_empty")]
         raw_ship_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        weights: Option<RankDropWeights>,
     },
 }
 
@@ -232,13 +292,14 @@ impl Serialize for ShipDropDefinition {
     where
         S: Serializer,
     {
-        if self.tags.is_empty() && self.raw_ship_name.is_empty() {
+        if self.tags.is_empty() && self.raw_ship_name.is_empty() && self.weights.is_none() {
             CompactShipDropDefinition::ShipId(self.ship_id).serialize(serializer)
         } else {
             CompactShipDropDefinition::Detailed {
                 ship_id: self.ship_id,
                 tags: self.tags.clone(),
                 raw_ship_name: self.raw_ship_name.clone(),
+                weights: self.weights,
             }
             .serialize(serializer)
         }
@@ -256,15 +317,18 @@ impl<'de> Deserialize<'de> for ShipDropDefinition {
                 ship_id,
                 raw_ship_name: String::new(),
                 tags: Vec::new(),
+                weights: None,
             },
             CompactShipDropDefinition::Detailed {
                 ship_id,
                 tags,
                 raw_ship_name,
+                weights,
             } => Self {
                 ship_id,
                 raw_ship_name,
                 tags,
+                weights,
             },
         })
     }
@@ -487,11 +551,13 @@ mod tests {
                 ship_id: 1,
                 raw_ship_name: "睦月".to_string(),
                 tags: Vec::new(),
+                weights: None,
             },
             ShipDropDefinition {
                 ship_id: 2,
                 raw_ship_name: "如月".to_string(),
                 tags: vec!["limited".to_string()],
+                weights: None,
             },
         ];
 
@@ -530,16 +596,19 @@ mod tests {
                     ship_id: 1,
                     raw_ship_name: String::new(),
                     tags: Vec::new(),
+                    weights: None,
                 },
                 ShipDropDefinition {
                     ship_id: 2,
                     raw_ship_name: String::new(),
                     tags: vec!["limited".to_string()],
+                    weights: None,
                 },
                 ShipDropDefinition {
                     ship_id: 3,
                     raw_ship_name: "綾波".to_string(),
                     tags: vec!["rare".to_string()],
+                    weights: None,
                 },
             ]
         );
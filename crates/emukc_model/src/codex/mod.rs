@@ -11,9 +11,15 @@ use crate::{
     thirdparty::{self, Kc3rdQuestCondition, Kc3rdQuestConditionShip, Kc3rdQuestRequirement},
 };
 
+pub mod construction;
+pub mod development;
+pub mod equip;
+pub mod friend_fleet;
 pub mod furniture;
 pub mod game_config;
 pub mod group;
+pub mod historical_bonus;
+pub mod improvement;
 pub mod incentive;
 /// Map catalog and cache parsing support.
 pub mod map;
@@ -88,6 +94,26 @@ pub struct Codex {
     /// Map catalog
     pub maps: map::MapCatalog,
 
+    /// Equipment development table.
+    #[serde(default)]
+    pub development: development::DevelopmentTable,
+
+    /// Ship construction table.
+    #[serde(default)]
+    pub construction: construction::ConstructionTable,
+
+    /// Friend fleet table.
+    #[serde(default)]
+    pub friend_fleets: friend_fleet::FriendFleetTable,
+
+    /// Historical ship bonus table.
+    #[serde(default)]
+    pub historical_bonuses: historical_bonus::HistoricalBonusTable,
+
+    /// Equipment improvement table.
+    #[serde(default)]
+    pub improvements: improvement::ImprovementTable,
+
     /// Cache source.
     pub cache_source: Option<CacheSource>,
     // TODO(#0): add more limitations.
@@ -105,6 +131,11 @@ const PATH_QUEST: &str = "quest.json";
 const PATH_EXPEDITION_CONDITION: &str = "expedition_condition.json";
 const PATH_MUSIC_LIST: &str = "music_list.json";
 const PATH_MAP_CATALOG: &str = "map_catalog.json";
+const PATH_DEVELOPMENT_TABLE: &str = "development_table.json";
+const PATH_CONSTRUCTION_TABLE: &str = "construction_table.json";
+const PATH_FRIEND_FLEET_TABLE: &str = "friend_fleet_table.json";
+const PATH_HISTORICAL_BONUS_TABLE: &str = "historical_bonus_table.json";
+const PATH_IMPROVEMENT_TABLE: &str = "improvement_table.json";
 const PATH_GAME_CFG: &str = "game_config.json";
 const PATH_CACHE_SOURCE: &str = "cache_source.json";
 
@@ -149,6 +180,16 @@ impl Codex {
     ///
     /// the `GameConfig` is loaded from `dir/game_config.json`.
     ///
+    /// the `DevelopmentTable` is loaded from `dir/development_table.json` if present.
+    ///
+    /// the `ConstructionTable` is loaded from `dir/construction_table.json` if present.
+    ///
+    /// the `FriendFleetTable` is loaded from `dir/friend_fleet_table.json` if present.
+    ///
+    /// the `HistoricalBonusTable` is loaded from `dir/historical_bonus_table.json` if present.
+    ///
+    /// the `ImprovementTable` is loaded from `dir/improvement_table.json` if present.
+    ///
     /// # Arguments
     ///
     /// * `dir` - The directory path.
@@ -256,6 +297,17 @@ impl Codex {
             maps.maps.retain(|map_id, _| known_map_ids.contains(map_id));
         }
 
+        let development: Option<development::DevelopmentTable> =
+            Self::load_optional_item(path.join(PATH_DEVELOPMENT_TABLE))?;
+        let construction: Option<construction::ConstructionTable> =
+            Self::load_optional_item(path.join(PATH_CONSTRUCTION_TABLE))?;
+        let friend_fleets: Option<friend_fleet::FriendFleetTable> =
+            Self::load_optional_item(path.join(PATH_FRIEND_FLEET_TABLE))?;
+        let historical_bonuses: Option<historical_bonus::HistoricalBonusTable> =
+            Self::load_optional_item(path.join(PATH_HISTORICAL_BONUS_TABLE))?;
+        let improvements: Option<improvement::ImprovementTable> =
+            Self::load_optional_item(path.join(PATH_IMPROVEMENT_TABLE))?;
+
         for def in maps.maps.values() {
             for warning in def.validate() {
                 tracing::warn!("{warning:?}");
@@ -275,6 +327,11 @@ impl Codex {
             expedition_conditions,
             music_list,
             maps,
+            development: development.unwrap_or_default(),
+            construction: construction.unwrap_or_default(),
+            friend_fleets: friend_fleets.unwrap_or_default(),
+            historical_bonuses: historical_bonuses.unwrap_or_default(),
+            improvements: improvements.unwrap_or_default(),
             game_cfg: Self::load_single_item(path.join(PATH_GAME_CFG))?,
             cache_source,
         })
@@ -424,6 +481,51 @@ impl Codex {
             std::fs::write(path, serde_json::to_string_pretty(&self.maps)?)?;
         }
 
+        // development table
+        {
+            let path = dst.join(PATH_DEVELOPMENT_TABLE);
+            if path.exists() && !overwrite {
+                return Err(CodexError::AlreadyExist(path.display().to_string()));
+            }
+            std::fs::write(path, serde_json::to_string_pretty(&self.development)?)?;
+        }
+
+        // construction table
+        {
+            let path = dst.join(PATH_CONSTRUCTION_TABLE);
+            if path.exists() && !overwrite {
+                return Err(CodexError::AlreadyExist(path.display().to_string()));
+            }
+            std::fs::write(path, serde_json::to_string_pretty(&self.construction)?)?;
+        }
+
+        // friend fleet table
+        {
+            let path = dst.join(PATH_FRIEND_FLEET_TABLE);
+            if path.exists() && !overwrite {
+                return Err(CodexError::AlreadyExist(path.display().to_string()));
+            }
+            std::fs::write(path, serde_json::to_string_pretty(&self.friend_fleets)?)?;
+        }
+
+        // historical bonus table
+        {
+            let path = dst.join(PATH_HISTORICAL_BONUS_TABLE);
+            if path.exists() && !overwrite {
+                return Err(CodexError::AlreadyExist(path.display().to_string()));
+            }
+            std::fs::write(path, serde_json::to_string_pretty(&self.historical_bonuses)?)?;
+        }
+
+        // improvement table
+        {
+            let path = dst.join(PATH_IMPROVEMENT_TABLE);
+            if path.exists() && !overwrite {
+                return Err(CodexError::AlreadyExist(path.display().to_string()));
+            }
+            std::fs::write(path, serde_json::to_string_pretty(&self.improvements)?)?;
+        }
+
         // cache source
         if let Some(source) = &self.cache_source {
             let path = dst.join(PATH_CACHE_SOURCE);
@@ -459,6 +561,21 @@ impl Codex {
     }
 }
 
+/// Pick the weighted candidate covering `roll`.
+///
+/// Candidates occupy consecutive bands in order; a `roll` past the last band
+/// (e.g. the failure band of a development pool) yields `None`.
+pub fn select_weighted_for_roll(candidates: &[(i64, u64)], roll: u64) -> Option<i64> {
+    let mut acc = 0;
+    for (id, weight) in candidates {
+        acc += weight;
+        if roll < acc {
+            return Some(*id);
+        }
+    }
+    None
+}
+
 fn normalize_loaded_quest_groups(quests: &mut thirdparty::Kc3rdQuestMap) {
     for quest in quests.values_mut() {
         normalize_requirement_groups(&mut quest.requirements);
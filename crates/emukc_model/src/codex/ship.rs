@@ -456,49 +456,6 @@ impl Codex {
         Ok(extra)
     }
 
-    /// Check if the ship can equip the slot item.
-    ///
-    /// # Arguments
-    ///
-    /// * `ship_mst_id` - The ship manifest ID.
-    /// * `slotitem_mst_id` - The slot item manifest ID.
-    pub fn can_equip_slotitem(&self, ship_mst_id: i64, slotitem_mst_id: i64) -> bool {
-        let Ok(ship_mst) = self.find_ship_mst(ship_mst_id) else {
-            error!("ship mst id {} not found", ship_mst_id);
-            return false;
-        };
-
-        let Ok(slotitem_mst) = self.find::<ApiMstSlotitem>(&slotitem_mst_id) else {
-            error!("slotitem mst id {} not found", slotitem_mst_id);
-            return false;
-        };
-
-        let slotitem_type = slotitem_mst.api_type[2];
-
-        let ship_id_str = ship_mst_id.to_string();
-        let mst_equip_slot = self.manifest.api_mst_equip_ship.get(&ship_id_str);
-        let Some(mst_equip_slot) = mst_equip_slot else {
-            error!("equip ship mst id {} not found in api_mst_equip_ship", ship_mst_id);
-            return false;
-        };
-
-        let slot_item_type_str = slotitem_type.to_string();
-        if let Some(Some(slot_id_list)) = mst_equip_slot.api_equip_type.get(&slot_item_type_str)
-            && slot_id_list.contains(&slotitem_mst_id)
-        {
-            return true;
-        }
-
-        match self.manifest.api_mst_stype.iter().find(|m| m.api_id == ship_mst.api_stype) {
-            Some(mst_stype) => {
-                let key = slotitem_type.to_string();
-                let v = mst_stype.api_equip_type.get(&key);
-                v.is_some() && v.unwrap_or(&0) > &0
-            }
-            None => false,
-        }
-    }
-
     /// Get the ship type.
     ///
     /// # Arguments
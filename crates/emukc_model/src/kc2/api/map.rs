@@ -47,6 +47,12 @@ pub struct KcApiMapHappening {
     pub api_icon_id: i64,
 }
 
+/// Destinations offered at a player-chosen branch (能動分岐).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KcApiMapSelectRoute {
+    pub api_select_cells: Vec<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KcApiMapStart {
     pub api_cell_data: Vec<KcApiMapCellData>,
@@ -78,6 +84,9 @@ pub struct KcApiMapStart {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_happening: Option<KcApiMapHappening>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_select_route: Option<KcApiMapSelectRoute>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,4 +124,7 @@ pub struct KcApiMapNext {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_happening: Option<KcApiMapHappening>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_select_route: Option<KcApiMapSelectRoute>,
 }
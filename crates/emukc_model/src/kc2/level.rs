@@ -51,6 +51,9 @@ static HQ_EXP_TABLE: LazyLock<Vec<i64>> = LazyLock::new(|| {
     hq_exp_table
 });
 
+/// The highest HQ level.
+pub const MAX_HQ_LEVEL: i64 = 120;
+
 /// Get the HQ level and the total required exp for the next level.
 ///
 /// # Arguments
@@ -67,7 +70,19 @@ pub fn exp_to_hq_level(exp: i64) -> (i64, i64) {
         }
     }
 
-    (120, 0)
+    (MAX_HQ_LEVEL, 0)
+}
+
+/// Get the total exp an HQ level starts at.
+///
+/// # Arguments
+///
+/// * `lv` - The HQ level.
+pub fn hq_level_required_exp(lv: i64) -> i64 {
+    if lv < 2 {
+        return 0;
+    }
+    HQ_EXP_TABLE.get(lv as usize - 2).copied().unwrap_or(0)
 }
 
 static SHIP_EXP_TABLE: LazyLock<Vec<i64>> = LazyLock::new(|| {
@@ -276,4 +291,12 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_hq_level_required_exp_vice_versa() {
+        for lv in 1..=super::MAX_HQ_LEVEL {
+            let exp = super::hq_level_required_exp(lv);
+            assert_eq!(super::exp_to_hq_level(exp).0, lv, "lv {lv} starts at {exp}");
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::kc2::{KcApiAirBase, KcApiDistance, KcApiPlaneInfo};
@@ -87,6 +88,12 @@ pub struct PlaneInfo {
 
     /// plane max count
     pub max_count: i64,
+
+    /// relocation completion time, while reassigning
+    pub relocate_until: Option<DateTime<Utc>>,
+
+    /// last time the condition changed
+    pub condition_at: Option<DateTime<Utc>>,
 }
 
 impl From<Airbase> for KcApiAirBase {
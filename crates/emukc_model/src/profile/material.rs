@@ -232,9 +232,14 @@ impl MaterialConfig {
     ///
     /// * `material` - The material to apply self replenish
     /// * `player_lv` - The player level
-    pub fn apply_self_replenish(&self, material: &mut Material, player_lv: i64) {
+    /// * `now` - The current game time
+    pub fn apply_self_replenish(
+        &self,
+        material: &mut Material,
+        player_lv: i64,
+        now: DateTime<Utc>,
+    ) {
         let soft_cap = self.get_soft_cap(player_lv);
-        let now = chrono::Utc::now();
 
         if material.bauxite < soft_cap {
             let diff = now.timestamp_millis() - material.last_update_bauxite.timestamp_millis();
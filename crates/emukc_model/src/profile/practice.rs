@@ -113,7 +113,7 @@ pub struct Rival {
 }
 
 /// Rival type
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, Default, enumn::N)]
 pub enum RivalType {
     /// First group, whales and chads
     #[default]
@@ -1,5 +1,7 @@
 //! Fleet composition validation for quests
 
+use serde::{Deserialize, Serialize};
+
 use crate::{codex::Codex, prelude::ApiMstShip, profile::fleet::Fleet};
 
 use super::{
@@ -14,7 +16,7 @@ pub struct ShipInstance {
 }
 
 /// Battle-independent fleet snapshot for quest validation.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FleetShipSnapshot {
     pub mst_id: i64,
     pub level: i64,
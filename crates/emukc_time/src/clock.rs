@@ -0,0 +1,166 @@
+//! The game clock.
+//!
+//! Every time-driven rule (expedition / dock timers, morale recovery,
+//! periodic quest and map resets) asks the game clock for "now" instead of the
+//! system time. A profile's clock runs in real time until it is frozen or
+//! shifted through the debug API, which is how QA finishes a timer or fires a
+//! weekly reset without waiting for it.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::RwLock;
+
+/// How a profile's clock departs from the system time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockOverride {
+    /// Added to the system time while the clock runs.
+    pub offset: Duration,
+
+    /// The time the clock is stopped at, if frozen.
+    pub frozen_at: Option<DateTime<Utc>>,
+}
+
+impl Default for ClockOverride {
+    fn default() -> Self {
+        Self {
+            offset: Duration::zero(),
+            frozen_at: None,
+        }
+    }
+}
+
+impl ClockOverride {
+    fn now(&self, system_now: DateTime<Utc>) -> DateTime<Utc> {
+        self.frozen_at.unwrap_or(system_now + self.offset)
+    }
+}
+
+/// Per-profile game clock.
+#[derive(Debug, Default)]
+pub struct GameClock {
+    overrides: RwLock<BTreeMap<i64, ClockOverride>>,
+}
+
+impl GameClock {
+    /// Create a clock where every profile runs in real time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current game time of a profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile ID.
+    pub fn now(&self, profile_id: i64) -> DateTime<Utc> {
+        let system_now = Utc::now();
+        self.overrides.read().get(&profile_id).map_or(system_now, |clock| clock.now(system_now))
+    }
+
+    /// The override of a profile, default when it runs in real time.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile ID.
+    pub fn override_of(&self, profile_id: i64) -> ClockOverride {
+        self.overrides.read().get(&profile_id).copied().unwrap_or_default()
+    }
+
+    /// Stop a profile's clock at `at`, or at its current time when `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile ID.
+    /// * `at` - The time to stop at.
+    pub fn freeze(&self, profile_id: i64, at: Option<DateTime<Utc>>) -> DateTime<Utc> {
+        let system_now = Utc::now();
+        let mut overrides = self.overrides.write();
+        let clock = overrides.entry(profile_id).or_default();
+        let frozen_at = at.unwrap_or_else(|| clock.now(system_now));
+        clock.frozen_at = Some(frozen_at);
+        frozen_at
+    }
+
+    /// Let a frozen clock run again from where it stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile ID.
+    pub fn resume(&self, profile_id: i64) -> DateTime<Utc> {
+        let system_now = Utc::now();
+        let mut overrides = self.overrides.write();
+        let clock = overrides.entry(profile_id).or_default();
+        if let Some(frozen_at) = clock.frozen_at.take() {
+            clock.offset = frozen_at - system_now;
+        }
+        clock.now(system_now)
+    }
+
+    /// Move a profile's clock forward (or back, for a negative `by`).
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile ID.
+    /// * `by` - The time to jump.
+    pub fn advance(&self, profile_id: i64, by: Duration) -> DateTime<Utc> {
+        let system_now = Utc::now();
+        let mut overrides = self.overrides.write();
+        let clock = overrides.entry(profile_id).or_default();
+        match clock.frozen_at.as_mut() {
+            Some(frozen_at) => *frozen_at += by,
+            None => clock.offset += by,
+        }
+        clock.now(system_now)
+    }
+
+    /// Put a profile's clock back on the system time.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile_id` - The profile ID.
+    pub fn reset(&self, profile_id: i64) -> DateTime<Utc> {
+        self.overrides.write().remove(&profile_id);
+        Utc::now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frozen_clock_stands_still_and_advances_on_demand() {
+        let clock = GameClock::new();
+        let frozen_at = clock.freeze(1, None);
+        assert_eq!(clock.now(1), frozen_at);
+
+        let later = clock.advance(1, Duration::hours(3));
+        assert_eq!(later, frozen_at + Duration::hours(3));
+        assert_eq!(clock.now(1), later);
+    }
+
+    #[test]
+    fn clocks_are_per_profile() {
+        let clock = GameClock::new();
+        clock.advance(1, Duration::days(7));
+
+        assert!(clock.now(1) - clock.now(2) > Duration::days(6));
+        assert_eq!(clock.override_of(2), ClockOverride::default());
+    }
+
+    #[test]
+    fn resume_continues_from_the_frozen_time() {
+        let clock = GameClock::new();
+        let frozen_at = clock.freeze(1, None) + Duration::days(1);
+        clock.freeze(1, Some(frozen_at));
+
+        let resumed = clock.resume(1);
+        assert!(resumed >= frozen_at);
+        assert!(clock.override_of(1).frozen_at.is_none());
+        assert!(clock.now(1) - frozen_at < Duration::minutes(1));
+
+        clock.reset(1);
+        assert_eq!(clock.override_of(1), ClockOverride::default());
+    }
+}
@@ -1,6 +1,10 @@
 //! Time utilities for the game.
 
-use chrono::{DateTime, Datelike, FixedOffset, Local, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, Local, TimeZone, Timelike, Utc, Weekday};
+
+mod clock;
+
+pub use clock::{ClockOverride, GameClock};
 
 /// A utility struct for time operations in `EmuKC`.
 pub struct KcTime;
@@ -35,13 +39,13 @@ impl KcTime {
     ///
     /// # Arguments
     ///
+    /// * `now` - The current time.
     /// * `hour` - The hour of the day.
     ///
     /// # Returns
     ///
     /// A `DateTime<Utc>` representing today's specified hour JST.
-    pub fn jst_today_hour_utc(hour: u32) -> DateTime<Utc> {
-        let now = Utc::now();
+    pub fn jst_today_hour_utc(now: &DateTime<Utc>, hour: u32) -> DateTime<Utc> {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_now = now.with_timezone(&tokyo_tz);
         let today = tokyo_now.date_naive();
@@ -53,12 +57,15 @@ impl KcTime {
 
     /// Get this week's Monday's JST (UTC+9) at 5 AM, and convert it to UTC.
     ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
     /// # Returns
     ///
     /// A `DateTime<Utc>` representing this week's Monday's 5 AM JST.
     #[must_use]
-    pub fn jst_monday_0500_utc() -> DateTime<Utc> {
-        let now = Utc::now();
+    pub fn jst_monday_0500_utc(now: &DateTime<Utc>) -> DateTime<Utc> {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_now = now.with_timezone(&tokyo_tz);
         let today = tokyo_now.date_naive();
@@ -74,28 +81,48 @@ impl KcTime {
 
     /// Get the day of the month in JST (UTC+9) today.
     ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
     /// # Returns
     ///
     /// The day of the month in JST.
-    pub fn jst_day_of_month() -> u32 {
-        let now = Utc::now();
+    pub fn jst_day_of_month(now: &DateTime<Utc>) -> u32 {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_now = now.with_timezone(&tokyo_tz);
         let today = tokyo_now.date_naive();
         today.day()
     }
 
+    /// Get the day of the week in JST (UTC+9) today.
+    ///
+    /// Unlike the daily reset, the day turns over at midnight JST, which is
+    /// when Akashi's Arsenal changes its lineup.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
+    /// # Returns
+    ///
+    /// The day of the week in JST.
+    pub fn jst_weekday(now: &DateTime<Utc>) -> Weekday {
+        let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
+        now.with_timezone(&tokyo_tz).weekday()
+    }
+
     /// Get the date of the nth day of the month in JST (UTC+9) at 5 AM, and convert it to UTC.
     ///
     /// # Arguments
     ///
+    /// * `now` - The current time.
     /// * `n` - The nth day of the month.
     ///
     /// # Returns
     ///
     /// A `DateTime<Utc>` representing the nth day of the month at 5 AM JST.
-    pub fn jst_0500_of_nth_day(n: u32) -> DateTime<Utc> {
-        let now = Utc::now();
+    pub fn jst_0500_of_nth_day(now: &DateTime<Utc>, n: u32) -> DateTime<Utc> {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_now = now.with_timezone(&tokyo_tz);
         let today = tokyo_now.date_naive();
@@ -109,11 +136,14 @@ impl KcTime {
 
     /// Get the date of the first day of the quarter in JST (UTC+9) at 5 AM, and convert it to UTC.
     ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
     /// # Returns
     ///
     /// A `DateTime<Utc>` representing the first day of the quarter at 5 AM JST.
-    pub fn jst_0500_day_one_of_quarter() -> DateTime<Utc> {
-        let now = Utc::now();
+    pub fn jst_0500_day_one_of_quarter(now: &DateTime<Utc>) -> DateTime<Utc> {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_now = now.with_timezone(&tokyo_tz);
         let today = tokyo_now.date_naive();
@@ -130,11 +160,14 @@ impl KcTime {
 
     /// Get the date of the first day of the year in JST (UTC+9) at 5 AM, and convert it to UTC.
     ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
     /// # Returns
     ///
     /// A `DateTime<Utc>` representing the first day of the year at 5 AM JST.
-    pub fn jst_0500_day_one_of_year() -> DateTime<Utc> {
-        let now = Utc::now();
+    pub fn jst_0500_day_one_of_year(now: &DateTime<Utc>) -> DateTime<Utc> {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_now = now.with_timezone(&tokyo_tz);
         let today = tokyo_now.date_naive();
@@ -151,16 +184,18 @@ impl KcTime {
     ///
     /// # Arguments
     ///
+    /// * `now` - The current time.
     /// * `t` - The time to check.
     /// * `before_hour` - The hour to check before.
     /// * `after_hour` - The hour to check after.
     pub fn is_before_or_after_jst_today_hour(
+        now: &DateTime<Utc>,
         t: &DateTime<Utc>,
         before_hour: u32,
         after_hour: u32,
     ) -> bool {
-        let before = Self::jst_today_hour_utc(before_hour);
-        let after = Self::jst_today_hour_utc(after_hour);
+        let before = Self::jst_today_hour_utc(now, before_hour);
+        let after = Self::jst_today_hour_utc(now, after_hour);
 
         t < &before || t >= &after
     }
@@ -177,7 +212,7 @@ impl KcTime {
     pub fn jst_next_day_0500(ts: &DateTime<Utc>) -> DateTime<Utc> {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_then = ts.with_timezone(&tokyo_tz);
-        let today_0500 = Self::jst_today_hour_utc(5);
+        let today_0500 = Self::jst_today_hour_utc(ts, 5);
         if tokyo_then < today_0500 {
             today_0500
         } else {
@@ -197,7 +232,7 @@ impl KcTime {
     pub fn jst_next_monday_0500(ts: &DateTime<Utc>) -> DateTime<Utc> {
         let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
         let tokyo_then = ts.with_timezone(&tokyo_tz);
-        let monday_0500 = Self::jst_monday_0500_utc();
+        let monday_0500 = Self::jst_monday_0500_utc(ts);
         if tokyo_then < monday_0500 {
             monday_0500
         } else {
@@ -332,10 +367,10 @@ enum NthDay {
 
 fn jst_next_nth_day_of_the_month(nth_day: NthDay, ts: &DateTime<Utc>) -> DateTime<Utc> {
     let tokyo_tz = FixedOffset::east_opt(9 * 3600).unwrap();
-    let mut tokyo_time = ts.with_timezone(&tokyo_tz);
+    let mut date = ts.with_timezone(&tokyo_tz).date_naive();
 
     loop {
-        let day = tokyo_time.day();
+        let day = date.day();
 
         let day_found = match nth_day {
             NthDay::Day3_7_0 => matches!(day % 10, 0 | 3 | 7),
@@ -343,16 +378,15 @@ fn jst_next_nth_day_of_the_month(nth_day: NthDay, ts: &DateTime<Utc>) -> DateTim
         };
 
         if day_found {
-            let target_time = tokyo_tz
-                .with_ymd_and_hms(tokyo_time.year(), tokyo_time.month(), day, 5, 0, 0)
-                .unwrap();
+            let target_time =
+                tokyo_tz.from_local_datetime(&date.and_hms_opt(5, 0, 0).unwrap()).unwrap();
 
-            if target_time < tokyo_time {
+            if target_time > *ts {
                 return target_time.with_timezone(&Utc);
             }
         }
 
-        tokyo_time += chrono::Duration::days(1);
+        date += chrono::Duration::days(1);
     }
 }
 
@@ -363,4 +397,55 @@ pub mod prelude {
     //! The `emukc_time` crate prelude.
     #[doc(hidden)]
     pub use crate::KcTime;
+
+    #[doc(hidden)]
+    pub use crate::{ClockOverride, GameClock};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jst(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(y, m, d, h, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn next_daily_and_weekly_resets_follow_the_start_time() {
+        assert_eq!(KcTime::jst_next_day_0500(&jst(2024, 5, 10, 4)), jst(2024, 5, 10, 5));
+        assert_eq!(KcTime::jst_next_day_0500(&jst(2024, 5, 10, 6)), jst(2024, 5, 11, 5));
+        // 2024-05-10 is a Friday.
+        assert_eq!(KcTime::jst_next_monday_0500(&jst(2024, 5, 10, 6)), jst(2024, 5, 13, 5));
+    }
+
+    #[test]
+    fn next_nth_day_reset_is_after_the_start_time() {
+        assert_eq!(
+            KcTime::jst_next_370th_day_of_the_month(&jst(2024, 5, 3, 4)),
+            jst(2024, 5, 3, 5)
+        );
+        assert_eq!(
+            KcTime::jst_next_370th_day_of_the_month(&jst(2024, 5, 3, 6)),
+            jst(2024, 5, 7, 5)
+        );
+        assert_eq!(
+            KcTime::jst_next_28th_day_of_the_month(&jst(2024, 5, 28, 6)),
+            jst(2024, 6, 2, 5)
+        );
+    }
+
+    #[test]
+    fn weekday_turns_over_at_midnight_jst() {
+        // 2024-05-10 is a Friday; 15:00 UTC on the 9th is midnight JST on the 10th.
+        assert_eq!(KcTime::jst_weekday(&jst(2024, 5, 10, 0)), Weekday::Fri);
+        assert_eq!(KcTime::jst_weekday(&jst(2024, 5, 9, 23)), Weekday::Thu);
+        assert_eq!(
+            KcTime::jst_weekday(&Utc.with_ymd_and_hms(2024, 5, 9, 15, 0, 0).unwrap()),
+            Weekday::Fri
+        );
+    }
 }
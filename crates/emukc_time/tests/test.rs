@@ -31,23 +31,25 @@ mod test {
         println!("Current time in Tokyo: {}", tokyo_now);
         println!("Today at 5 AM JST: {}", five_am_jst);
         println!("Today at 5 AM JST in UTC: {}", five_am_utc);
-        assert_eq!(KcTime::jst_today_hour_utc(5), five_am_utc);
+        assert_eq!(KcTime::jst_today_hour_utc(&now, 5), five_am_utc);
     }
 
     #[test]
     fn test_jst_monday_0500() {
-        println!("Current time in UTC: {}", Utc::now());
-        println!("Monday at 5 AM JST in UTC: {}", KcTime::jst_monday_0500_utc());
+        let now = Utc::now();
+        println!("Current time in UTC: {}", now);
+        println!("Monday at 5 AM JST in UTC: {}", KcTime::jst_monday_0500_utc(&now));
     }
 
     #[test]
     fn test_jst_nth_0500() {
+        let now = Utc::now();
         for i in 1..=31 {
-            if i == KcTime::jst_day_of_month() {
+            if i == KcTime::jst_day_of_month(&now) {
                 println!(
                     "{}th day of month at 5 AM JST in UTC: {:?}",
                     i,
-                    KcTime::jst_0500_of_nth_day(i)
+                    KcTime::jst_0500_of_nth_day(&now, i)
                 );
             }
         }
@@ -55,15 +57,20 @@ mod test {
 
     #[test]
     fn test_jst_day_one_of_quarter() {
+        let now = Utc::now();
         println!(
             "First day of quarter at 5 AM JST in UTC: {:?}",
-            KcTime::jst_0500_day_one_of_quarter()
+            KcTime::jst_0500_day_one_of_quarter(&now)
         );
     }
 
     #[test]
     fn test_jst_day_one_of_year() {
-        println!("First day of year at 5 AM JST in UTC: {:?}", KcTime::jst_0500_day_one_of_year());
+        let now = Utc::now();
+        println!(
+            "First day of year at 5 AM JST in UTC: {:?}",
+            KcTime::jst_0500_day_one_of_year(&now)
+        );
     }
 
     #[test]
@@ -72,11 +79,11 @@ mod test {
         let before_hour = 3;
         let after_hour = 15;
         println!("Current time in UTC: {}", now);
-        println!("Before 3 AM JST today: {}", KcTime::jst_today_hour_utc(before_hour));
-        println!("After 3 PM JST today: {}", KcTime::jst_today_hour_utc(after_hour));
+        println!("Before 3 AM JST today: {}", KcTime::jst_today_hour_utc(&now, before_hour));
+        println!("After 3 PM JST today: {}", KcTime::jst_today_hour_utc(&now, after_hour));
         println!(
             "Is before 3 AM JST today or after 3 PM JST today? {}",
-            KcTime::is_before_or_after_jst_today_hour(&now, before_hour, after_hour)
+            KcTime::is_before_or_after_jst_today_hour(&now, &now, before_hour, after_hour)
         );
     }
 
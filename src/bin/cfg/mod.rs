@@ -42,6 +42,9 @@ pub struct AppConfig {
 
     /// The URL to the game files CDN
     pub game_cdn: Vec<String>,
+
+    /// Hours an untouched sortie or practice battle survives a restart
+    pub session_ttl_hours: Option<u32>,
 }
 
 impl AppConfig {
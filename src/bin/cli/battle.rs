@@ -13,7 +13,7 @@ use emukc_internal::{
     crypto::rng,
     db::sea_orm::DbConn,
     prelude::{
-        AccountOps, BattleSimulation, Codex, HasContext, PRESETS, PracticeStore, Preset,
+        AccountOps, BattleSimulation, Codex, GameClock, HasContext, PRESETS, PracticeStore, Preset,
         ProfileOps, Scenario, ShipOps, SortieOps, SortieRepository, SortieStore, apply_scenario,
         new_mem_db, render_day_battle,
     },
@@ -235,6 +235,7 @@ struct SimContext {
     codex: Codex,
     sortie_store: SortieStore,
     practice_store: PracticeStore,
+    clock: GameClock,
 }
 
 impl HasContext for SimContext {
@@ -250,6 +251,9 @@ impl HasContext for SimContext {
     fn practice_store(&self) -> &PracticeStore {
         &self.practice_store
     }
+    fn clock(&self) -> &GameClock {
+        &self.clock
+    }
 }
 
 /// Where a sim run sorties: map area + info no, and the friendly formation.
@@ -326,6 +330,7 @@ where
                 codex,
                 sortie_store: SortieStore::new(),
                 practice_store: PracticeStore::new(),
+                clock: GameClock::new(),
             };
             let profile_id = create_sim_profile(&ctx).await?;
             apply_scenario(&ctx, profile_id, &scenario).await.context("apply scenario")?;
@@ -403,8 +408,11 @@ async fn run_first_battle_outcome(
     let simulation = BattleSimulation {
         friendly: session.friendly,
         enemy: session.enemy,
+        friendly_escort: session.friendly_escort,
+        enemy_escort: session.enemy_escort,
         packet: session.packet,
         outcome: session.outcome,
+        air_base_waves: Vec::new(),
     };
     let transcript = render_day_battle(&simulation);
 
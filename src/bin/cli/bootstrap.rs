@@ -103,6 +103,7 @@ mod tests {
             proxy: proxy.map(ToOwned::to_owned),
             gadgets_cdn: vec![],
             game_cdn: vec![],
+            session_ttl_hours: None,
         }
     }
 
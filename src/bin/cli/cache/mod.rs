@@ -2,14 +2,22 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use dump::DumpArguments;
 use make_list::MakeListArguments;
+use mods::ModsArguments;
+use pack::PackArguments;
 use populate::PopulateArguments;
+use unpack::UnpackArguments;
+use verify::VerifyArguments;
 
 use crate::cfg::AppConfig;
 
 mod dump;
 mod foo;
 mod make_list;
+mod mods;
+mod pack;
 mod populate;
+mod unpack;
+mod verify;
 
 #[derive(Debug, Subcommand)]
 enum Commands {
@@ -19,6 +27,14 @@ enum Commands {
     Populate(PopulateArguments),
     #[command(about = "Dump cache list")]
     Dump(DumpArguments),
+    #[command(about = "Verify cached files and repair damaged ones")]
+    Verify(VerifyArguments),
+    #[command(about = "Pack cached files into a bundle for offline machines")]
+    Pack(PackArguments),
+    #[command(about = "Load a bundle into the cache")]
+    Unpack(UnpackArguments),
+    #[command(about = "List mod packs, report conflicts and toggle them per profile")]
+    Mods(ModsArguments),
     #[command(about = "foo")]
     Foo,
 }
@@ -41,6 +57,10 @@ pub(super) async fn exec(args: &CacheArgs, config: &AppConfig) -> Result<()> {
         Commands::Populate(args) => populate::exec(args, config).await?,
         Commands::MakeList(args) => make_list::exec(args, config).await?,
         Commands::Dump(args) => dump::exec(args, config).await?,
+        Commands::Verify(args) => verify::exec(args, config).await?,
+        Commands::Pack(args) => pack::exec(args, config).await?,
+        Commands::Unpack(args) => unpack::exec(args, config).await?,
+        Commands::Mods(args) => mods::exec(args, config).await?,
         Commands::Foo => foo::exec(config).await?,
     }
 
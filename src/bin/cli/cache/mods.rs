@@ -0,0 +1,118 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::{cfg::AppConfig, state};
+
+#[derive(Args, Debug)]
+pub(super) struct ModsArguments {
+    #[arg(help = "Profile whose toggles apply, manifests only if omitted")]
+    #[arg(long)]
+    pub profile: Option<i64>,
+
+    #[command(subcommand)]
+    pub action: ModsAction,
+}
+
+#[derive(Debug, Subcommand)]
+pub(super) enum ModsAction {
+    #[command(about = "List mod packs by priority")]
+    List,
+
+    #[command(about = "List paths provided by more than one active mod")]
+    Conflicts,
+
+    #[command(about = "Enable a mod pack for the profile")]
+    Enable {
+        #[arg(help = "Mod pack directory name")]
+        id: String,
+    },
+
+    #[command(about = "Disable a mod pack for the profile")]
+    Disable {
+        #[arg(help = "Mod pack directory name")]
+        id: String,
+    },
+
+    #[command(about = "Follow the mod pack's manifest again for the profile")]
+    Reset {
+        #[arg(help = "Mod pack directory name")]
+        id: String,
+    },
+}
+
+/// Inspect mod packs and toggle them per profile
+///
+/// This opens the cache database itself, so it only works while the server is
+/// stopped; a running server toggles packs through `/api/v1/debug/mods/set`.
+pub(super) async fn exec(args: &ModsArguments, config: &AppConfig) -> Result<()> {
+    let state = state::State::new(config, false).await?;
+    let kache = &state.kache;
+
+    let (id, enabled) = match &args.action {
+        ModsAction::List => {
+            let mods = kache.mods(args.profile).await?;
+            if mods.is_empty() {
+                println!("no mod packs found");
+            }
+            for status in mods {
+                let manifest = &status.pack.manifest;
+                println!(
+                    "{:>5} {} {} ({}){}{}",
+                    manifest.priority,
+                    if status.is_active() {
+                        "[x]"
+                    } else {
+                        "[ ]"
+                    },
+                    status.pack.id,
+                    manifest.name,
+                    if status.toggled {
+                        ", toggled for the profile"
+                    } else {
+                        ""
+                    },
+                    if status.compatible {
+                        String::new()
+                    } else {
+                        format!(
+                            ", needs client {}..={}",
+                            manifest.min_version.as_deref().unwrap_or(""),
+                            manifest.max_version.as_deref().unwrap_or("")
+                        )
+                    },
+                );
+            }
+            return Ok(());
+        }
+        ModsAction::Conflicts => {
+            let conflicts = kache.mod_conflicts(args.profile).await?;
+            for conflict in &conflicts {
+                println!(
+                    "{}: {} shadows {}",
+                    conflict.path,
+                    conflict.winner,
+                    conflict.shadowed.join(", ")
+                );
+            }
+            println!("{} conflicting paths", conflicts.len());
+            return Ok(());
+        }
+        ModsAction::Enable {
+            id,
+        } => (id, Some(true)),
+        ModsAction::Disable {
+            id,
+        } => (id, Some(false)),
+        ModsAction::Reset {
+            id,
+        } => (id, None),
+    };
+
+    let Some(profile_id) = args.profile else {
+        anyhow::bail!("--profile is required to toggle a mod pack");
+    };
+    kache.set_mod_enabled(profile_id, id, enabled).await?;
+    println!("mod {id} updated for profile {profile_id}");
+
+    Ok(())
+}
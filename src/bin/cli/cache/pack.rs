@@ -0,0 +1,90 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use emukc::cache::{BundleFilter, unified_rel_path};
+use serde::Deserialize;
+
+use crate::{cfg::AppConfig, state};
+
+#[derive(Args, Debug)]
+pub(super) struct PackArguments {
+    #[arg(help = "Output bundle file")]
+    #[arg(long, short, value_name = "FILE")]
+    pub output: PathBuf,
+
+    #[arg(help = "Overwrite existing file")]
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[arg(help = "Only pack paths under this prefix, e.g. `kcs2/img/`; repeatable")]
+    #[arg(long, value_name = "PREFIX")]
+    pub prefix: Vec<String>,
+
+    #[arg(help = "Only pack paths listed in a cache list file, as written by `make-list`")]
+    #[arg(long, value_name = "FILE")]
+    pub list: Option<PathBuf>,
+
+    #[arg(
+        help = "Only pack the map and gauge resources of this sea area, e.g. 1 for maps 1-x; repeatable"
+    )]
+    #[arg(long, value_name = "AREA")]
+    pub area: Vec<i64>,
+}
+
+#[derive(Deserialize)]
+struct ListEntry {
+    path: String,
+}
+
+/// Pack cached files into a bundle
+pub(super) async fn exec(args: &PackArguments, config: &AppConfig) -> Result<()> {
+    if args.output.exists() && !args.overwrite {
+        return Err(anyhow::anyhow!("File already exists"));
+    }
+
+    let paths = match &args.list {
+        Some(list) => Some(read_list(list).await?),
+        None => None,
+    };
+    let filter = BundleFilter {
+        prefixes: args.prefix.clone(),
+        paths,
+        areas: args.area.clone(),
+    };
+
+    let state = state::State::new(config, false).await?;
+    let report = state.kache.pack(&args.output, &filter).await?;
+
+    for path in &report.missing {
+        println!("missing: {path}");
+    }
+    for path in &report.corrupted {
+        println!("corrupted: {path}");
+    }
+    println!(
+        "{} files ({} bytes) packed into {}, {} missing, {} corrupted",
+        report.packed.len(),
+        report.bytes,
+        args.output.display(),
+        report.missing.len(),
+        report.corrupted.len(),
+    );
+
+    Ok(())
+}
+
+async fn read_list(path: &PathBuf) -> Result<HashSet<String>> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("reading cache list {}", path.display()))?;
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let entry: ListEntry = serde_json::from_str(line)?;
+            Ok(unified_rel_path(&entry.path))
+        })
+        .collect()
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::{cfg::AppConfig, state};
+
+#[derive(Args, Debug)]
+pub(super) struct UnpackArguments {
+    #[arg(help = "Bundle file written by `pack`")]
+    #[arg(long, short, value_name = "FILE")]
+    pub input: PathBuf,
+}
+
+/// Load a bundle into the cache
+pub(super) async fn exec(args: &UnpackArguments, config: &AppConfig) -> Result<()> {
+    let state = state::State::new(config, false).await?;
+    let report = state.kache.unpack(&args.input).await?;
+
+    for path in &report.kept {
+        println!("kept newer: {path}");
+    }
+    for path in &report.rejected {
+        println!("rejected: {path}");
+    }
+    println!(
+        "{} files installed, {} unchanged, {} kept newer, {} rejected",
+        report.installed.len(),
+        report.unchanged.len(),
+        report.kept.len(),
+        report.rejected.len(),
+    );
+
+    Ok(())
+}
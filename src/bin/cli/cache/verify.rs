@@ -0,0 +1,53 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::{cfg::AppConfig, state};
+
+#[derive(Args, Debug)]
+pub(super) struct VerifyArguments {
+    #[arg(help = "Re-fetch damaged files and quarantine what cannot be fetched")]
+    #[arg(long)]
+    pub repair: bool,
+}
+
+/// Check cached files against their recorded size and hash
+pub(super) async fn exec(args: &VerifyArguments, config: &AppConfig) -> Result<()> {
+    let state = state::State::new(config, false).await?;
+    let report = state.kache.verify(args.repair).await?;
+
+    for (label, paths) in [
+        ("missing", &report.missing),
+        ("truncated", &report.truncated),
+        ("corrupted", &report.corrupted),
+        ("orphaned", &report.orphaned),
+        ("re-fetched", &report.refetched),
+        ("quarantined", &report.quarantined),
+        ("recorded", &report.recorded),
+    ] {
+        for path in paths {
+            println!("{label}: {path}");
+        }
+    }
+
+    println!(
+        "{} entries checked, {} missing, {} truncated, {} corrupted, {} orphaned, {} without digest",
+        report.checked,
+        report.missing.len(),
+        report.truncated.len(),
+        report.corrupted.len(),
+        report.orphaned.len(),
+        report.unrecorded.len(),
+    );
+    if args.repair {
+        println!(
+            "{} re-fetched, {} quarantined, {} digests recorded",
+            report.refetched.len(),
+            report.quarantined.len(),
+            report.recorded.len(),
+        );
+    } else if !report.is_clean() {
+        println!("run with --repair to fix");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,208 @@
+//! `clock` — inspect or shift a profile's game clock on a running server.
+//!
+//! The game clock lives in the server process, so this command signs in to the
+//! server at `--server` (derived from the configuration by default) and calls
+//! the `/api/v1/debug/clock` endpoints.
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use emukc_internal::{
+    network::{client::new_reqwest_client, reqwest},
+    prelude::ClockStatus,
+    time::chrono::{DateTime, Utc},
+};
+use http::header;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::json;
+
+use crate::cfg::AppConfig;
+
+#[derive(Debug, Args)]
+pub(super) struct ClockArgs {
+    #[arg(help = "Server URL, defaults to the configured bind address")]
+    #[arg(long)]
+    server: Option<String>,
+
+    #[arg(help = "Account user name")]
+    #[arg(long)]
+    name: String,
+
+    #[arg(help = "Account password")]
+    #[arg(long)]
+    pass: String,
+
+    #[arg(help = "Profile ID")]
+    #[arg(long, default_value_t = 1)]
+    profile: i64,
+
+    #[command(subcommand)]
+    action: ClockAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum ClockAction {
+    #[command(about = "Print the profile's game time")]
+    Show,
+
+    #[command(about = "Stop the profile's game clock")]
+    Freeze {
+        #[arg(help = "RFC 3339 time to stop at, defaults to the current game time")]
+        #[arg(long)]
+        at: Option<DateTime<Utc>>,
+    },
+
+    #[command(about = "Let a frozen game clock run again")]
+    Resume,
+
+    #[command(about = "Jump the game clock, e.g. `90m`, `1d12h`, `-2h`")]
+    Advance {
+        #[arg(help = "Duration made of `<n>w`, `<n>d`, `<n>h`, `<n>m` and `<n>s` parts")]
+        #[arg(allow_hyphen_values = true)]
+        by: String,
+    },
+
+    #[command(about = "Put the game clock back on the system time")]
+    Reset,
+}
+
+pub(super) async fn exec(args: &ClockArgs, cfg: &AppConfig) -> Result<()> {
+    let server = args.server.clone().unwrap_or_else(|| default_server(cfg));
+    let server = server.trim_end_matches('/');
+    let client = new_reqwest_client(None, None)?;
+
+    let auth: serde_json::Value = post(
+        &client,
+        &format!("{server}/api/v1/auth/sign-in"),
+        None,
+        &json!({ "username": args.name, "password": args.pass }),
+    )
+    .await
+    .context("failed to sign in")?;
+    let token = auth["access_token"]["token"]
+        .as_str()
+        .context("sign-in response carries no access token")?
+        .to_string();
+
+    let profile_id = args.profile;
+    let (endpoint, body) = match &args.action {
+        ClockAction::Show => ("get", json!({ "profile_id": profile_id })),
+        ClockAction::Freeze {
+            at,
+        } => ("freeze", json!({ "profile_id": profile_id, "at": at })),
+        ClockAction::Resume => ("resume", json!({ "profile_id": profile_id })),
+        ClockAction::Advance {
+            by,
+        } => ("advance", json!({ "profile_id": profile_id, "seconds": parse_duration(by)? })),
+        ClockAction::Reset => ("reset", json!({ "profile_id": profile_id })),
+    };
+
+    let status: ClockStatus =
+        post(&client, &format!("{server}/api/v1/debug/clock/{endpoint}"), Some(&token), &body)
+            .await?;
+
+    println!(
+        "profile {profile_id}: {} ({:+}s{})",
+        status.now.to_rfc3339(),
+        status.offset_secs,
+        if status.frozen {
+            ", frozen"
+        } else {
+            ""
+        }
+    );
+
+    Ok(())
+}
+
+fn default_server(cfg: &AppConfig) -> String {
+    let scheme = if cfg.tls_cert.is_some() && cfg.tls_key.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    let mut addr = cfg.bind;
+    if addr.ip().is_unspecified() {
+        addr.set_ip(std::net::Ipv4Addr::LOCALHOST.into());
+    }
+    format!("{scheme}://{addr}")
+}
+
+async fn post<B, R>(client: &reqwest::Client, url: &str, token: Option<&str>, body: &B) -> Result<R>
+where
+    B: Serialize,
+    R: DeserializeOwned,
+{
+    let mut request = client
+        .post(url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(body)?);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+
+    let response = request.send().await.with_context(|| format!("failed to reach {url}"))?;
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        bail!("{url} returned {status}: {text}");
+    }
+
+    serde_json::from_str(&text).with_context(|| format!("invalid response from {url}"))
+}
+
+/// Parse a duration such as `1d12h` or `-90m` into seconds.
+fn parse_duration(raw: &str) -> Result<i64> {
+    let (sign, body) = match raw.trim().strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, raw.trim()),
+    };
+    if body.is_empty() {
+        bail!("empty duration `{raw}`");
+    }
+
+    let mut total = 0i64;
+    let mut digits = String::new();
+    for ch in body.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let unit = match ch {
+            'w' => 7 * 86_400,
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => bail!("unknown duration unit `{ch}` in `{raw}`"),
+        };
+        let n: i64 = digits.parse().with_context(|| format!("missing number in `{raw}`"))?;
+        total += n * unit;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        bail!("duration `{raw}` ends without a unit");
+    }
+
+    Ok(sign * total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_sums_units() {
+        assert_eq!(parse_duration("90m").unwrap(), 90 * 60);
+        assert_eq!(parse_duration("1d12h").unwrap(), 36 * 3600);
+        assert_eq!(parse_duration("1w").unwrap(), 7 * 86_400);
+        assert_eq!(parse_duration("-2h").unwrap(), -7200);
+    }
+
+    #[test]
+    fn parse_duration_rejects_bad_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("12").is_err());
+        assert!(parse_duration("3x").is_err());
+        assert!(parse_duration("h").is_err());
+    }
+}
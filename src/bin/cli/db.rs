@@ -0,0 +1,77 @@
+//! `db` — inspect or apply the game database's schema migrations.
+//!
+//! The server migrates on start; `db migrate` does it ahead of time, e.g.
+//! right after an upgrade, and `db status` shows what would run.
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use emukc_internal::{
+    db::migration,
+    prelude::{connect_db, migrate_db},
+};
+
+use crate::{cfg::AppConfig, state::DB_NAME};
+
+#[derive(Debug, Args)]
+pub(super) struct DbArgs {
+    #[command(subcommand)]
+    action: DbAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum DbAction {
+    #[command(about = "Print the schema version and pending migrations")]
+    Status,
+
+    #[command(about = "Apply pending migrations")]
+    Migrate {
+        #[arg(help = "Skip the backup taken before existing data is migrated")]
+        #[arg(long)]
+        no_backup: bool,
+    },
+}
+
+pub(super) async fn exec(args: &DbArgs, cfg: &AppConfig) -> Result<()> {
+    let path = cfg.workspace_root.join(DB_NAME);
+    let db = connect_db(&path).await?;
+
+    match &args.action {
+        DbAction::Status => {
+            let status = migration::status(&db).await?;
+            println!("database: {}", path.display());
+            if status.fresh {
+                println!("schema:   empty, created at v{} on first use", status.latest);
+                return Ok(());
+            }
+            match status.current {
+                Some(version) => println!("schema:   v{version} of v{}", status.latest),
+                None => println!("schema:   unversioned (v0) of v{}", status.latest),
+            }
+            for applied in &status.applied {
+                println!(
+                    "  [x] v{} {} ({})",
+                    applied.version,
+                    applied.name,
+                    applied.applied_at.format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+            for pending in &status.pending {
+                println!("  [ ] v{} {}", pending.version, pending.name);
+            }
+        }
+        DbAction::Migrate {
+            no_backup,
+        } => {
+            let report = migrate_db(&db, &path, !no_backup).await?;
+            if let Some(backup) = &report.backup {
+                println!("backed up to {}", backup.display());
+            }
+            for applied in &report.applied {
+                println!("applied v{} {}", applied.version, applied.name);
+            }
+            println!("schema is at v{}", migration::latest_version());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,285 @@
+//! `drop-sim` — Monte-Carlo check of a map cell's ship drop rates.
+//!
+//! Builds the cell's drop table the same way sortie results do, rolls it
+//! `--runs` times with a seeded RNG and compares the observed frequencies with
+//! the expected rates. Exits with an error if any outcome strays too far.
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use emukc_internal::{crypto::rng::GameRng, model::codex::map::ShipDropTable, prelude::Codex};
+use serde::Serialize;
+
+use crate::cfg::AppConfig;
+
+/// Allowed deviation from the expected count, in standard deviations.
+const TOLERANCE_SIGMAS: f64 = 4.0;
+
+#[derive(Debug, Args)]
+pub(super) struct DropSimArgs {
+    #[arg(help = "Map ID, e.g. 11 for 1-1")]
+    #[arg(long)]
+    map: i64,
+
+    #[arg(help = "Cell number")]
+    #[arg(long)]
+    cell: i64,
+
+    #[arg(help = "Battle rank: S, A or B")]
+    #[arg(long, default_value = "S")]
+    rank: String,
+
+    #[arg(help = "Event difficulty, 1 (丁) to 4 (甲)")]
+    #[arg(long)]
+    difficulty: Option<i64>,
+
+    #[arg(help = "Number of simulated battles")]
+    #[arg(long, default_value_t = 100_000)]
+    runs: u64,
+
+    #[arg(help = "RNG seed")]
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+
+    #[arg(help = "Print structured JSON output")]
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct DropSimReport {
+    map_id: i64,
+    cell_no: i64,
+    rank: String,
+    difficulty: Option<i64>,
+    runs: u64,
+    seed: u64,
+    outcomes: Vec<DropSimOutcome>,
+    passed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct DropSimOutcome {
+    /// Ship ID, `None` for "no drop".
+    ship_id: Option<i64>,
+    name: String,
+    weight: Option<i64>,
+    expected: f64,
+    observed: f64,
+    within_tolerance: bool,
+}
+
+pub(super) async fn exec(args: &DropSimArgs, cfg: &AppConfig) -> Result<()> {
+    let codex_root = cfg.codex_root()?;
+    let codex = Codex::load_without_cache_source(&codex_root)
+        .with_context(|| format!("failed to load codex from {}", codex_root.display()))?;
+    if args.runs == 0 {
+        bail!("--runs must be positive");
+    }
+
+    let catalog = codex.map_catalog();
+    let definition = catalog
+        .as_ref()
+        .map_definition(args.map)
+        .with_context(|| format!("map {} not found", args.map))?;
+    let stage = match args.difficulty {
+        Some(difficulty) if definition.is_event => definition.stage_for_rank(difficulty),
+        _ => definition.default_stage_id().and_then(|stage_id| definition.stage(stage_id)),
+    }
+    .with_context(|| format!("no stage found for map {}", args.map))?;
+
+    let difficulty = args.difficulty.filter(|_| definition.is_event);
+    let table = ShipDropTable::build(&codex, stage, args.cell, &args.rank, difficulty);
+    let report = run(&codex, &table, args, difficulty);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if !report.passed {
+        bail!("observed drop rates deviate from the expected rates");
+    }
+
+    Ok(())
+}
+
+fn run(
+    codex: &Codex,
+    table: &ShipDropTable<'_>,
+    args: &DropSimArgs,
+    difficulty: Option<i64>,
+) -> DropSimReport {
+    let counts = simulate(table, args.runs, &GameRng::seeded(args.seed));
+
+    let mut outcomes = vec![outcome(
+        None,
+        "(no drop)".to_string(),
+        None,
+        table.no_drop_rate,
+        counts[0],
+        args.runs,
+    )];
+    for (entry, count) in table.entries.iter().zip(&counts[1..]) {
+        let name = codex
+            .manifest
+            .find_ship(entry.drop.ship_id)
+            .map(|mst| mst.api_name.clone())
+            .unwrap_or_else(|| entry.drop.raw_ship_name.clone());
+        outcomes.push(outcome(
+            Some(entry.drop.ship_id),
+            name,
+            Some(entry.weight),
+            table.drop_rate(entry),
+            *count,
+            args.runs,
+        ));
+    }
+
+    DropSimReport {
+        map_id: args.map,
+        cell_no: args.cell,
+        rank: args.rank.clone(),
+        difficulty,
+        runs: args.runs,
+        seed: args.seed,
+        passed: outcomes.iter().all(|outcome| outcome.within_tolerance),
+        outcomes,
+    }
+}
+
+/// Roll `table` `runs` times; index 0 counts "no drop", index `i + 1` entry `i`.
+fn simulate(table: &ShipDropTable<'_>, runs: u64, rng: &GameRng) -> Vec<u64> {
+    let mut counts = vec![0; table.entries.len() + 1];
+    let total_weight = table.total_weight().max(0) as u64;
+
+    for _ in 0..runs {
+        let no_drop_roll = rng.f64();
+        let weight_roll = if total_weight > 0 {
+            rng.u64(0..total_weight)
+        } else {
+            0
+        };
+        let index = table
+            .select_for_roll(no_drop_roll, weight_roll)
+            .and_then(|drop| table.entries.iter().position(|entry| std::ptr::eq(entry.drop, drop)))
+            .map_or(0, |position| position + 1);
+        counts[index] += 1;
+    }
+
+    counts
+}
+
+fn outcome(
+    ship_id: Option<i64>,
+    name: String,
+    weight: Option<i64>,
+    expected: f64,
+    count: u64,
+    runs: u64,
+) -> DropSimOutcome {
+    DropSimOutcome {
+        ship_id,
+        name,
+        weight,
+        expected,
+        observed: count as f64 / runs as f64,
+        within_tolerance: within_tolerance(expected, count, runs),
+    }
+}
+
+/// Whether `count` hits out of `runs` fit a binomial with rate `expected`.
+fn within_tolerance(expected: f64, count: u64, runs: u64) -> bool {
+    let runs = runs as f64;
+    let mean = expected * runs;
+    let sigma = (runs * expected * (1.0 - expected)).sqrt();
+
+    // the extra hit keeps certain outcomes (rate 0 or 1) exact
+    (count as f64 - mean).abs() <= TOLERANCE_SIGMAS * sigma + 1.0
+}
+
+fn print_report(report: &DropSimReport) {
+    println!(
+        "map {} cell {} rank {}{} — {} runs, seed {}",
+        report.map_id,
+        report.cell_no,
+        report.rank,
+        report.difficulty.map(|d| format!(" difficulty {d}")).unwrap_or_default(),
+        report.runs,
+        report.seed,
+    );
+    println!("{:>8}  {:<20} {:>6} {:>9} {:>9}", "ship", "name", "weight", "expected", "observed");
+    for outcome in &report.outcomes {
+        println!(
+            "{:>8}  {:<20} {:>6} {:>8.3}% {:>8.3}%{}",
+            outcome.ship_id.map(|id| id.to_string()).unwrap_or_else(|| "-".to_string()),
+            outcome.name,
+            outcome.weight.map(|w| w.to_string()).unwrap_or_else(|| "-".to_string()),
+            outcome.expected * 100.0,
+            outcome.observed * 100.0,
+            if outcome.within_tolerance {
+                ""
+            } else {
+                "  MISMATCH"
+            },
+        );
+    }
+    println!(
+        "{}",
+        if report.passed {
+            "PASS"
+        } else {
+            "FAIL"
+        }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use emukc_internal::model::codex::map::{ShipDropDefinition, ShipDropEntry};
+
+    use super::*;
+
+    #[test]
+    fn simulate_matches_expected_rates() {
+        let (a, b) = (
+            ShipDropDefinition {
+                ship_id: 1,
+                ..Default::default()
+            },
+            ShipDropDefinition {
+                ship_id: 2,
+                ..Default::default()
+            },
+        );
+        let table = ShipDropTable::new(
+            vec![
+                ShipDropEntry {
+                    drop: &a,
+                    weight: 9,
+                },
+                ShipDropEntry {
+                    drop: &b,
+                    weight: 1,
+                },
+            ],
+            0.5,
+        );
+
+        let runs = 20_000;
+        let counts = simulate(&table, runs, &GameRng::seeded(7));
+
+        assert_eq!(counts.iter().sum::<u64>(), runs);
+        assert!(within_tolerance(table.no_drop_rate, counts[0], runs));
+        assert!(within_tolerance(table.drop_rate(&table.entries[0]), counts[1], runs));
+        assert!(within_tolerance(table.drop_rate(&table.entries[1]), counts[2], runs));
+    }
+
+    #[test]
+    fn within_tolerance_rejects_skewed_counts() {
+        assert!(within_tolerance(0.5, 5_000, 10_000));
+        assert!(!within_tolerance(0.5, 6_000, 10_000));
+        assert!(within_tolerance(0.0, 0, 10_000));
+        assert!(!within_tolerance(0.0, 5, 10_000));
+    }
+}
@@ -0,0 +1,269 @@
+//! `map` — offline checks against the codex map catalog.
+//!
+//! `map route-sim` sails a fleet through a map `--runs` times with the same
+//! routing rules as a real sortie and reports how often each node is reached,
+//! which rules decided each branch and where unparsed rules left the route to
+//! chance.
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use emukc_internal::{
+    crypto::rng,
+    gameplay::game::{RouteSimShip, RouteSimulation, simulate_routes},
+    model::codex::map::MapStageDefinition,
+    prelude::Codex,
+};
+use serde::Serialize;
+
+use crate::cfg::AppConfig;
+
+/// Level of a ship the fleet spec gives none for.
+const DEFAULT_SHIP_LEVEL: i64 = 99;
+
+#[derive(Debug, Args)]
+pub(super) struct MapArgs {
+    #[command(subcommand)]
+    action: MapAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum MapAction {
+    #[command(about = "Estimate a fleet's routing through a map with a Monte-Carlo run")]
+    RouteSim(RouteSimArgs),
+}
+
+#[derive(Debug, Args)]
+struct RouteSimArgs {
+    #[arg(help = "Map, e.g. 1-1 or 11")]
+    map: String,
+
+    #[arg(help = "Fleet, flagship first: comma-separated `ship[@level][:item+item...]` \
+                  master ids, e.g. 131@99:9+9+59,186:75")]
+    #[arg(long)]
+    fleet: String,
+
+    #[arg(help = "HQ level, for the formula 3 LoS penalty")]
+    #[arg(long, default_value_t = 120)]
+    hq_level: i64,
+
+    #[arg(help = "Event difficulty, 1 (丁) to 4 (甲)")]
+    #[arg(long)]
+    difficulty: Option<i64>,
+
+    #[arg(help = "Map variant key, overriding the default or difficulty stage")]
+    #[arg(long)]
+    stage: Option<String>,
+
+    #[arg(help = "Number of simulated sorties")]
+    #[arg(long, default_value_t = 10_000)]
+    runs: u64,
+
+    #[arg(help = "RNG seed")]
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+
+    #[arg(help = "Print structured JSON output")]
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RouteSimReport {
+    map_id: i64,
+    stage: String,
+    seed: u64,
+    #[serde(flatten)]
+    simulation: RouteSimulation,
+}
+
+pub(super) async fn exec(args: &MapArgs, cfg: &AppConfig) -> Result<()> {
+    match &args.action {
+        MapAction::RouteSim(args) => route_sim(args, cfg),
+    }
+}
+
+fn route_sim(args: &RouteSimArgs, cfg: &AppConfig) -> Result<()> {
+    let map_id = parse_map_id(&args.map)?;
+    let fleet = parse_fleet(&args.fleet)?;
+    if args.runs == 0 {
+        bail!("--runs must be positive");
+    }
+
+    let codex_root = cfg.codex_root()?;
+    let codex = Codex::load_without_cache_source(&codex_root)
+        .with_context(|| format!("failed to load codex from {}", codex_root.display()))?;
+    let catalog = codex.map_catalog();
+    let definition = catalog
+        .as_ref()
+        .map_definition(map_id)
+        .with_context(|| format!("map {map_id} not found"))?;
+    let stage = match (&args.stage, args.difficulty) {
+        (Some(stage_id), _) => definition.stage(stage_id),
+        (None, Some(difficulty)) if definition.is_event => definition.stage_for_rank(difficulty),
+        _ => definition.default_stage_id().and_then(|stage_id| definition.stage(stage_id)),
+    }
+    .with_context(|| format!("no stage found for map {map_id}"))?;
+
+    rng::seed(args.seed);
+    let simulation = simulate_routes(&codex, stage, &fleet, args.hq_level, args.runs)?;
+    let report = RouteSimReport {
+        map_id,
+        stage: stage.variant_key.clone(),
+        seed: args.seed,
+        simulation,
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report, stage);
+    }
+
+    Ok(())
+}
+
+/// Parse `1-1` or `11` into a map id.
+fn parse_map_id(map: &str) -> Result<i64> {
+    let map_id = match map.split_once('-') {
+        Some((area, no)) => area.trim().parse::<i64>()? * 10 + no.trim().parse::<i64>()?,
+        None => map.trim().parse()?,
+    };
+    if map_id <= 0 {
+        bail!("invalid map `{map}`");
+    }
+    Ok(map_id)
+}
+
+/// Parse a fleet spec, see [`RouteSimArgs::fleet`].
+fn parse_fleet(spec: &str) -> Result<Vec<RouteSimShip>> {
+    let fleet = spec
+        .split(',')
+        .map(str::trim)
+        .filter(|ship| !ship.is_empty())
+        .map(|ship| {
+            let (ship, items) = ship.split_once(':').unwrap_or((ship, ""));
+            let (mst_id, level) = match ship.split_once('@') {
+                Some((mst_id, level)) => (mst_id, level.parse()?),
+                None => (ship, DEFAULT_SHIP_LEVEL),
+            };
+            let slot_items = items
+                .split('+')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(RouteSimShip {
+                mst_id: mst_id.trim().parse()?,
+                level,
+                slot_items,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("invalid fleet spec `{spec}`"))?;
+    if fleet.is_empty() || fleet.len() > 7 {
+        bail!("a fleet has 1 to 7 ships, got {}", fleet.len());
+    }
+    Ok(fleet)
+}
+
+fn print_report(report: &RouteSimReport, stage: &MapStageDefinition) {
+    let label = |cell_no: i64| {
+        stage
+            .cell(cell_no)
+            .and_then(|cell| cell.node_label.clone())
+            .map_or_else(|| cell_no.to_string(), |label| format!("{label} ({cell_no})"))
+    };
+    let simulation = &report.simulation;
+
+    println!(
+        "map {} stage {} — {} runs, seed {}",
+        report.map_id, report.stage, simulation.runs, report.seed
+    );
+    println!("{:>10}  {:>9}", "node", "arrival");
+    for cell_no in simulation.arrivals.keys() {
+        println!(
+            "{:>10}  {:>8.3}%{}",
+            label(*cell_no),
+            simulation.arrival_rate(*cell_no) * 100.0,
+            if *cell_no == stage.boss_cell_no {
+                "  boss"
+            } else {
+                ""
+            },
+        );
+    }
+
+    if !simulation.branches.is_empty() {
+        println!();
+        println!("branches:");
+    }
+    for (from, branches) in &simulation.branches {
+        let departures = branches.targets.values().map(|target| target.count).sum::<u64>();
+        println!("  {}:", label(*from));
+        for (to, target) in &branches.targets {
+            println!(
+                "    -> {:<10} {:>8.3}%  {}",
+                label(*to),
+                target.count as f64 / departures as f64 * 100.0,
+                if target.decided_by.is_empty() {
+                    "-".to_string()
+                } else {
+                    target.decided_by.iter().cloned().collect::<Vec<_>>().join("; ")
+                },
+            );
+        }
+        if branches.random_fallbacks > 0 {
+            println!(
+                "    random fallback in {:.3}% of departures (unknown routing rules)",
+                branches.random_fallbacks as f64 / departures as f64 * 100.0
+            );
+        }
+    }
+
+    for (cell_no, failure) in &simulation.failures {
+        println!();
+        println!("stuck at {} in {} runs: {}", label(*cell_no), failure.count, failure.error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_map_id_accepts_both_forms() {
+        assert_eq!(parse_map_id("1-1").unwrap(), 11);
+        assert_eq!(parse_map_id("7-5").unwrap(), 75);
+        assert_eq!(parse_map_id("61").unwrap(), 61);
+        assert!(parse_map_id("x-1").is_err());
+        assert!(parse_map_id("0").is_err());
+    }
+
+    #[test]
+    fn parse_fleet_reads_levels_and_items() {
+        let fleet = parse_fleet("131@80:9+9+59, 186:75,1").unwrap();
+        assert_eq!(
+            fleet,
+            vec![
+                RouteSimShip {
+                    mst_id: 131,
+                    level: 80,
+                    slot_items: vec![9, 9, 59],
+                },
+                RouteSimShip {
+                    mst_id: 186,
+                    level: DEFAULT_SHIP_LEVEL,
+                    slot_items: vec![75],
+                },
+                RouteSimShip {
+                    mst_id: 1,
+                    level: DEFAULT_SHIP_LEVEL,
+                    slot_items: vec![],
+                },
+            ]
+        );
+        assert!(parse_fleet("").is_err());
+        assert!(parse_fleet("131@x").is_err());
+        assert!(parse_fleet("1,2,3,4,5,6,7,8").is_err());
+    }
+}
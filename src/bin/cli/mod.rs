@@ -11,7 +11,13 @@ mod auto;
 mod battle;
 mod bootstrap;
 mod cache;
+mod clock;
+mod db;
 mod dev;
+mod drop_sim;
+mod map;
+mod profile;
+mod replay;
 mod serve;
 mod version;
 mod wikiwiki_map;
@@ -64,6 +70,24 @@ enum Commands {
     #[command(about = "Cache management")]
     Cache(cache::CacheArgs),
 
+    #[command(about = "Inspect or shift a profile's game clock on a running server")]
+    Clock(clock::ClockArgs),
+
+    #[command(about = "Inspect or apply database schema migrations")]
+    Db(db::DbArgs),
+
+    #[command(about = "Check a map cell's ship drop rates with a Monte-Carlo run")]
+    DropSim(drop_sim::DropSimArgs),
+
+    #[command(about = "Simulate routing through the codex maps")]
+    Map(map::MapArgs),
+
+    #[command(about = "Export a profile to a portable archive or import one")]
+    Profile(profile::ProfileArgs),
+
+    #[command(about = "Replay a KCSAPI dump against a scratch profile and diff the responses")]
+    Replay(replay::ReplayArgs),
+
     #[command(about = "Start the server")]
     Serve(serve::ServeArgs),
 
@@ -210,6 +234,17 @@ pub async fn init() -> ExitCode {
         Some(Commands::Bootstrap(args)) => bootstrap::exec(&cfg, &args).await,
         Some(Commands::WikiwikiMap(args)) => wikiwiki_map::exec(&args).await,
         Some(Commands::Cache(args)) => cache::exec(&args, &cfg).await,
+        Some(Commands::Clock(args)) => clock::exec(&args, &cfg).await,
+        Some(Commands::Db(args)) => db::exec(&args, &cfg).await,
+        Some(Commands::DropSim(args)) => drop_sim::exec(&args, &cfg).await,
+        Some(Commands::Map(args)) => map::exec(&args, &cfg).await,
+        Some(Commands::Profile(args)) => {
+            let Some(state) = prepare_state(&cfg).await else {
+                return ExitCode::FAILURE;
+            };
+            profile::exec(&args, &state).await
+        }
+        Some(Commands::Replay(args)) => replay::exec(&args, &cfg).await,
         Some(Commands::Serve(args)) => {
             let Some(state) = prepare_state(&cfg).await else {
                 return ExitCode::FAILURE;
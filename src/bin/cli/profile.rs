@@ -0,0 +1,120 @@
+//! `profile` — export a profile to a portable archive or import one.
+//!
+//! Archives are JSON documents; a `.zst` file extension selects zstd
+//! compression.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use emukc_internal::prelude::{AccountOps, ProfileArchive, ProfileOps};
+
+use crate::state::State;
+
+/// zstd level for `.zst` archives.
+const ZSTD_LEVEL: i32 = 9;
+
+#[derive(Debug, Args)]
+pub(super) struct ProfileArgs {
+    #[arg(help = "Account user name")]
+    #[arg(long)]
+    name: String,
+
+    #[arg(help = "Account password")]
+    #[arg(long)]
+    pass: String,
+
+    #[command(subcommand)]
+    action: ProfileAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProfileAction {
+    #[command(about = "Write a profile's full state to an archive")]
+    Export {
+        #[arg(help = "Profile ID")]
+        #[arg(long, default_value_t = 1)]
+        profile: i64,
+
+        #[arg(help = "Output file, zstd-compressed if it ends with `.zst`")]
+        #[arg(long, short, value_name = "FILE")]
+        output: PathBuf,
+    },
+
+    #[command(about = "Create a new profile from an archive")]
+    Import {
+        #[arg(help = "Archive file, zstd-compressed if it ends with `.zst`")]
+        #[arg(long, short, value_name = "FILE")]
+        input: PathBuf,
+
+        #[arg(help = "Name of the new profile, defaults to the archived name")]
+        #[arg(long)]
+        profile_name: Option<String>,
+    },
+}
+
+pub(super) async fn exec(args: &ProfileArgs, state: &State) -> Result<()> {
+    let auth = state.sign_in(&args.name, &args.pass).await?;
+    let token = &auth.access_token.token;
+
+    match &args.action {
+        ProfileAction::Export {
+            profile,
+            output,
+        } => {
+            let archive = state.export_profile(token, *profile).await?;
+            write_archive(output, &archive)?;
+            println!(
+                "exported profile {profile} ({} ships, {} slot items) to {}",
+                archive.ships.len(),
+                archive.slot_items.len(),
+                output.display()
+            );
+        }
+        ProfileAction::Import {
+            input,
+            profile_name,
+        } => {
+            let archive = read_archive(input)?;
+            let info = state.import_profile(token, &archive, profile_name.as_deref()).await?;
+            println!(
+                "imported {} as profile {} `{}`",
+                input.display(),
+                info.profile.id,
+                info.profile.name
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn is_zstd(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zst"))
+}
+
+fn write_archive(path: &Path, archive: &ProfileArchive) -> Result<()> {
+    let json = serde_json::to_vec_pretty(archive)?;
+    let bytes = if is_zstd(path) {
+        zstd::encode_all(json.as_slice(), ZSTD_LEVEL)?
+    } else {
+        json
+    };
+
+    fs::write(path, bytes).with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn read_archive(path: &Path) -> Result<ProfileArchive> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let json = if is_zstd(path) {
+        zstd::decode_all(bytes.as_slice())
+            .with_context(|| format!("failed to decompress {}", path.display()))?
+    } else {
+        bytes
+    };
+
+    serde_json::from_slice(&json).with_context(|| format!("invalid archive {}", path.display()))
+}
@@ -0,0 +1,313 @@
+//! Structural JSON diff between a recorded and a replayed KCSAPI response.
+//!
+//! Fields are addressed by a dotted path with array indices collapsed to `[]`
+//! (`api_data.api_ship[].api_lv`), so the same field across list entries
+//! aggregates into one per-endpoint line. Ignored fields keep their presence
+//! and JSON type checked; only their values are skipped.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Field names whose values differ on every run: timestamps and instance ids.
+const DEFAULT_IGNORES: &[&str] =
+    &["*time", "*time_str", "*_date", "api_id", "api_member_id", "api_nickname_id", "api_token"];
+
+/// How a replayed field departs from the recorded one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum DiffKind {
+    /// Recorded, but missing from the replay.
+    Missing,
+    /// Only present in the replay.
+    Extra,
+    /// Present on both sides with a different JSON type.
+    Type,
+    /// Arrays of a different length.
+    Length,
+    /// Same type, different value.
+    Value,
+}
+
+/// One field difference.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct FieldDiff {
+    pub path: String,
+    pub kind: DiffKind,
+    pub expected: Option<Value>,
+    pub actual: Option<Value>,
+}
+
+/// Field-name patterns whose values are not compared. `*` matches any run of
+/// characters.
+#[derive(Debug, Clone)]
+pub(super) struct IgnoreRules {
+    patterns: Vec<String>,
+    shape_only: bool,
+}
+
+impl IgnoreRules {
+    /// The default timestamp / id rules plus `extra` patterns.
+    ///
+    /// With `shape_only` no value is compared: only field names, types and
+    /// array lengths.
+    pub fn new(extra: &[String], shape_only: bool) -> Self {
+        Self {
+            patterns: DEFAULT_IGNORES
+                .iter()
+                .map(ToString::to_string)
+                .chain(extra.iter().cloned())
+                .collect(),
+            shape_only,
+        }
+    }
+
+    fn ignores(&self, key: &str) -> bool {
+        self.patterns.iter().any(|pattern| glob_match(pattern, key))
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((head, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut text) = text.strip_prefix(head) else {
+        return false;
+    };
+    let mut parts = rest.split('*').peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return text.ends_with(part);
+        }
+        match text.find(part) {
+            Some(idx) => text = &text[idx + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Diff `actual` against `expected`.
+pub(super) fn diff_values(expected: &Value, actual: &Value, rules: &IgnoreRules) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+    walk("", expected, actual, rules, false, &mut diffs);
+    diffs
+}
+
+fn walk(
+    path: &str,
+    expected: &Value,
+    actual: &Value,
+    rules: &IgnoreRules,
+    ignored: bool,
+    diffs: &mut Vec<FieldDiff>,
+) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    let field = |kind, expected: Option<&Value>, actual: Option<&Value>| FieldDiff {
+        path: path.to_string(),
+        kind,
+        expected: expected.cloned(),
+        actual: actual.cloned(),
+    };
+
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let key_ignored = ignored || rules.ignores(key);
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        walk(&child(key), expected_value, actual_value, rules, key_ignored, diffs);
+                    }
+                    None => diffs.push(FieldDiff {
+                        path: child(key),
+                        kind: DiffKind::Missing,
+                        expected: Some(expected_value.clone()),
+                        actual: None,
+                    }),
+                }
+            }
+            for (key, actual_value) in actual {
+                if !expected.contains_key(key) {
+                    diffs.push(FieldDiff {
+                        path: child(key),
+                        kind: DiffKind::Extra,
+                        expected: None,
+                        actual: Some(actual_value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(expected_items), Value::Array(actual_items)) => {
+            if expected_items.len() != actual_items.len() {
+                diffs.push(field(
+                    DiffKind::Length,
+                    Some(&expected_items.len().into()),
+                    Some(&actual_items.len().into()),
+                ));
+            }
+            let item_path = format!("{path}[]");
+            for (expected_item, actual_item) in expected_items.iter().zip(actual_items) {
+                walk(&item_path, expected_item, actual_item, rules, ignored, diffs);
+            }
+        }
+        _ if type_name(expected) != type_name(actual) => {
+            diffs.push(field(DiffKind::Type, Some(expected), Some(actual)));
+        }
+        _ if ignored || rules.shape_only => {}
+        _ if expected != actual => {
+            diffs.push(field(DiffKind::Value, Some(expected), Some(actual)));
+        }
+        _ => {}
+    }
+}
+
+/// Aggregated differences of one field across an endpoint's records.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct FieldSummary {
+    pub kind: DiffKind,
+    pub count: usize,
+    /// First observed recorded value.
+    pub expected: Option<Value>,
+    /// First observed replayed value.
+    pub actual: Option<Value>,
+}
+
+/// Per-endpoint replay result.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(super) struct EndpointReport {
+    pub records: usize,
+    /// Records with at least one difference.
+    pub mismatched: usize,
+    /// Keyed by `"<path> (<kind>)"`.
+    pub fields: BTreeMap<String, FieldSummary>,
+}
+
+impl EndpointReport {
+    /// Fold one record's differences into the report.
+    pub fn add_record(&mut self, diffs: Vec<FieldDiff>) {
+        self.records += 1;
+        if !diffs.is_empty() {
+            self.mismatched += 1;
+        }
+        for diff in diffs {
+            let key = format!("{} ({})", diff.path, kind_label(diff.kind));
+            self.fields.entry(key).and_modify(|summary| summary.count += 1).or_insert(
+                FieldSummary {
+                    kind: diff.kind,
+                    count: 1,
+                    expected: diff.expected,
+                    actual: diff.actual,
+                },
+            );
+        }
+    }
+}
+
+fn kind_label(kind: DiffKind) -> &'static str {
+    match kind {
+        DiffKind::Missing => "missing",
+        DiffKind::Extra => "extra",
+        DiffKind::Type => "type",
+        DiffKind::Length => "length",
+        DiffKind::Value => "value",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn kinds(diffs: &[FieldDiff]) -> Vec<(&str, DiffKind)> {
+        diffs.iter().map(|diff| (diff.path.as_str(), diff.kind)).collect()
+    }
+
+    #[test]
+    fn glob_patterns_match_field_names() {
+        assert!(glob_match("*time", "api_complete_time"));
+        assert!(glob_match("api_*_id", "api_ship_id"));
+        assert!(glob_match("api_id", "api_id"));
+        assert!(!glob_match("api_id", "api_ship_id"));
+        assert!(!glob_match("*time", "api_time_str"));
+        assert!(glob_match("*", "anything"));
+    }
+
+    #[test]
+    fn reports_missing_extra_type_and_value() {
+        let expected =
+            json!({"api_result": 1, "api_data": {"api_lv": 10, "api_name": "a", "api_old": 1}});
+        let actual =
+            json!({"api_result": 1, "api_data": {"api_lv": 11, "api_name": 1, "api_new": 1}});
+
+        let diffs = diff_values(&expected, &actual, &IgnoreRules::new(&[], false));
+
+        assert_eq!(
+            kinds(&diffs),
+            vec![
+                ("api_data.api_lv", DiffKind::Value),
+                ("api_data.api_name", DiffKind::Type),
+                ("api_data.api_old", DiffKind::Missing),
+                ("api_data.api_new", DiffKind::Extra),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignored_fields_still_check_type() {
+        let expected = json!({"api_id": 5, "api_complete_time": 100, "api_member_id": "1"});
+        let actual = json!({"api_id": 9, "api_complete_time": 200, "api_member_id": 1});
+
+        let diffs = diff_values(&expected, &actual, &IgnoreRules::new(&[], false));
+
+        assert_eq!(kinds(&diffs), vec![("api_member_id", DiffKind::Type)]);
+    }
+
+    #[test]
+    fn arrays_collapse_indices_and_report_length() {
+        let expected = json!({"api_ship": [{"api_lv": 1}, {"api_lv": 2}]});
+        let actual = json!({"api_ship": [{"api_lv": 3}]});
+
+        let diffs = diff_values(&expected, &actual, &IgnoreRules::new(&[], false));
+        assert_eq!(
+            kinds(&diffs),
+            vec![("api_ship", DiffKind::Length), ("api_ship[].api_lv", DiffKind::Value)]
+        );
+
+        let shape = diff_values(&expected, &actual, &IgnoreRules::new(&[], true));
+        assert_eq!(kinds(&shape), vec![("api_ship", DiffKind::Length)]);
+    }
+
+    #[test]
+    fn endpoint_report_aggregates_by_field() {
+        let mut report = EndpointReport::default();
+        let rules = IgnoreRules::new(&["api_lv".to_string()], false);
+        report.add_record(diff_values(&json!({"a": 1}), &json!({"a": 2}), &rules));
+        report.add_record(diff_values(&json!({"a": 1}), &json!({"a": 3}), &rules));
+        report.add_record(diff_values(&json!({"api_lv": 1}), &json!({"api_lv": 2}), &rules));
+
+        assert_eq!(report.records, 3);
+        assert_eq!(report.mismatched, 2);
+        let summary = &report.fields["a (value)"];
+        assert_eq!(summary.count, 2);
+        assert_eq!(summary.expected, Some(json!(1)));
+        assert_eq!(summary.actual, Some(json!(2)));
+    }
+}
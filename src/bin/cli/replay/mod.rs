@@ -0,0 +1,228 @@
+//! `replay` — feed a KCSAPI dump back through the router and diff responses.
+//!
+//! Reads the JSONL records written by the `EMUKC_KCSAPI_DUMP` capture (or a
+//! capture of the real server in the same shape), replays every `/kcsapi/`
+//! request in order against a scratch profile on an in-memory database, and
+//! reports per endpoint how the replayed responses differ from the recorded
+//! ones. Exits non-zero when any response differs.
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+use axum::body::{Body, to_bytes};
+use clap::Args;
+use emukc_internal::prelude::{AccountOps, ProfileOps};
+use http::{Method, Request, header};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tower::Service;
+
+use crate::{cfg::AppConfig, net, state::State};
+
+mod diff;
+
+use diff::{EndpointReport, IgnoreRules, diff_values};
+
+/// Query / form keys that carry the session token.
+const TOKEN_KEYS: &[&str] = &["token", "st", "api_token"];
+
+/// Widest value rendered in the human report.
+const VALUE_PREVIEW_LEN: usize = 60;
+
+#[derive(Debug, Args)]
+pub(super) struct ReplayArgs {
+    #[arg(help = "KCSAPI dump file (JSONL records of `EMUKC_KCSAPI_DUMP`)")]
+    input: PathBuf,
+
+    #[arg(help = "Extra field name pattern whose values are not compared; `*` matches \
+                any characters (repeatable)")]
+    #[arg(long = "ignore", value_name = "PATTERN")]
+    ignore: Vec<String>,
+
+    #[arg(help = "Compare field names, types and array lengths only")]
+    #[arg(long)]
+    shape_only: bool,
+
+    #[arg(help = "Print the structured report as JSON")]
+    #[arg(long)]
+    json: bool,
+}
+
+/// One line of a KCSAPI dump. Unused fields (`ts`, `status`) are skipped.
+#[derive(Debug, Deserialize)]
+struct DumpRecord {
+    method: String,
+    path: String,
+    #[serde(default)]
+    query: String,
+    #[serde(default)]
+    request: String,
+    response: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ReplayReport {
+    records: usize,
+    mismatched: usize,
+    endpoints: BTreeMap<String, EndpointReport>,
+}
+
+pub(super) async fn exec(args: &ReplayArgs, cfg: &AppConfig) -> Result<()> {
+    let raw = fs::read_to_string(&args.input)
+        .with_context(|| format!("failed to read dump from {}", args.input.display()))?;
+    let records = parse_dump(&raw)?;
+
+    let state = State::scratch(cfg).await?;
+    let token = scratch_session(&state).await?;
+    let mut app = net::app(&state);
+    let rules = IgnoreRules::new(&args.ignore, args.shape_only);
+
+    let mut endpoints: BTreeMap<String, EndpointReport> = BTreeMap::new();
+    for record in records.iter().filter(|record| record.path.starts_with("/kcsapi/")) {
+        let actual = replay_record(&mut app, record, &token).await?;
+        let expected = parse_svdata(&record.response);
+        endpoints
+            .entry(record.path.clone())
+            .or_default()
+            .add_record(diff_values(&expected, &actual, &rules));
+    }
+
+    let report = ReplayReport {
+        records: endpoints.values().map(|endpoint| endpoint.records).sum(),
+        mismatched: endpoints.values().map(|endpoint| endpoint.mismatched).sum(),
+        endpoints,
+    };
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if report.mismatched > 0 {
+        bail!("{} of {} replayed responses differ", report.mismatched, report.records);
+    }
+
+    Ok(())
+}
+
+fn parse_dump(raw: &str) -> Result<Vec<DumpRecord>> {
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("invalid dump record at line {}", idx + 1))
+        })
+        .collect()
+}
+
+/// Sign up a throwaway account and start a game session on a fresh profile.
+async fn scratch_session(state: &State) -> Result<String> {
+    let account = state.sign_up("replay", "replay-password").await?;
+    let profile = state.new_profile(&account.access_token.token, "replay").await?;
+    let session = state.start_game(&account.access_token.token, profile.profile.id).await?;
+    Ok(session.session.token)
+}
+
+async fn replay_record(app: &mut axum::Router, record: &DumpRecord, token: &str) -> Result<Value> {
+    let method = Method::from_bytes(record.method.as_bytes())
+        .with_context(|| format!("invalid method `{}`", record.method))?;
+    let uri = if record.query.is_empty() {
+        record.path.clone()
+    } else {
+        format!("{}?{}", record.path, rewrite_token(&record.query, token))
+    };
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(rewrite_token(&record.request, token)))?;
+
+    let response = app.call(request).await?;
+    let body = to_bytes(response.into_body(), usize::MAX).await?;
+    Ok(parse_svdata(&String::from_utf8_lossy(&body)))
+}
+
+/// Point every token key of a urlencoded string at the scratch session.
+fn rewrite_token(encoded: &str, token: &str) -> String {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in url::form_urlencoded::parse(encoded.as_bytes()) {
+        let value = if TOKEN_KEYS.contains(&key.as_ref()) {
+            token.into()
+        } else {
+            value
+        };
+        serializer.append_pair(&key, &value);
+    }
+    serializer.finish()
+}
+
+/// Parse a `svdata=`-prefixed body; a body that is not JSON is kept as a string.
+fn parse_svdata(body: &str) -> Value {
+    let trimmed = body.trim();
+    let json = trimmed.strip_prefix("svdata=").unwrap_or(trimmed);
+    serde_json::from_str(json).unwrap_or_else(|_| Value::String(json.to_string()))
+}
+
+fn print_report(report: &ReplayReport) {
+    for (path, endpoint) in &report.endpoints {
+        println!("{path}: {}/{} record(s) differ", endpoint.mismatched, endpoint.records);
+        for (field, summary) in &endpoint.fields {
+            println!(
+                "  {field} x{}: {} -> {}",
+                summary.count,
+                preview(summary.expected.as_ref()),
+                preview(summary.actual.as_ref()),
+            );
+        }
+    }
+    println!("replayed {} record(s), {} differ", report.records, report.mismatched);
+}
+
+fn preview(value: Option<&Value>) -> String {
+    let Some(value) = value else {
+        return "<absent>".to_string();
+    };
+    let rendered = value.to_string();
+    if rendered.chars().count() > VALUE_PREVIEW_LEN {
+        format!("{}...", rendered.chars().take(VALUE_PREVIEW_LEN).collect::<String>())
+    } else {
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_token_replaces_only_token_keys() {
+        let rewritten = rewrite_token("api_token=old&api_verno=1&api_deck_id=2", "new");
+        assert_eq!(rewritten, "api_token=new&api_verno=1&api_deck_id=2");
+
+        assert_eq!(rewrite_token("", "new"), "");
+    }
+
+    #[test]
+    fn parse_dump_skips_blank_lines_and_reports_bad_ones() {
+        let raw = concat!(
+            r#"{"ts":"t","method":"POST","path":"/kcsapi/api_port/port","query":"","request":"api_token=a","status":200,"response":"svdata={}"}"#,
+            "\n\n",
+            r#"{"method":"POST","path":"/kcsapi/api_get_member/deck","response":"svdata={}"}"#,
+            "\n",
+        );
+        let records = parse_dump(raw).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].path, "/kcsapi/api_get_member/deck");
+        assert!(records[1].request.is_empty());
+
+        let err = parse_dump("{}\n").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn parse_svdata_strips_prefix() {
+        assert_eq!(parse_svdata("svdata={\"api_result\":1}")["api_result"], 1);
+        assert_eq!(parse_svdata("not json"), Value::String("not json".to_string()));
+    }
+}
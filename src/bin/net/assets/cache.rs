@@ -1,12 +1,53 @@
+use std::convert::Infallible;
+
 use axum::{
     body::Body,
+    extract::FromRequestParts,
     response::{IntoResponse, Response},
 };
-use http::StatusCode;
+use emukc::cache::GetOption;
+use http::{StatusCode, header, request::Parts};
 use tokio_util::io::ReaderStream;
 
 use crate::net::AppState;
 
+/// Cookie naming the profile whose mods the game client is served, set with the game page.
+pub const MOD_PROFILE_COOKIE: &str = "emukc_profile";
+
+/// The profile whose mods are served, read from the [`MOD_PROFILE_COOKIE`].
+///
+/// The cookie only selects mods, so it is taken as is.
+pub struct ModProfile(pub Option<i64>);
+
+impl<S> FromRequestParts<S> for ModProfile
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let profile_id = parts
+            .headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(find_profile_cookie);
+
+        Ok(Self(profile_id))
+    }
+}
+
+fn find_profile_cookie(cookies: &str) -> Option<i64> {
+    cookies.split(';').find_map(|cookie| {
+        let (name, value) = cookie.trim().split_once('=')?;
+        if name == MOD_PROFILE_COOKIE {
+            value.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
 /// Cache file handler
 ///
 /// # Arguments
@@ -14,13 +55,20 @@ use crate::net::AppState;
 /// - `app` - the application state
 /// - `rel_path` - the relative path of the file
 /// - `version` - the version of the file
-pub async fn get_file(app: AppState, rel_path: &str, version: Option<&str>) -> impl IntoResponse {
+/// - `profile_id` - the profile whose mods are served
+pub async fn get_file(
+    app: AppState,
+    rel_path: &str,
+    version: Option<&str>,
+    profile_id: Option<i64>,
+) -> impl IntoResponse {
     if rel_path.ends_with(".min.map") || rel_path.ends_with(".js.map") {
         // we don't want to serve source maps
         return Response::builder().status(StatusCode::NOT_FOUND).body(Body::empty()).unwrap();
     }
 
-    let Ok(f) = app.kache.get(rel_path, version).await else {
+    let opt = GetOption::new().with_profile(profile_id);
+    let Ok(f) = app.kache.get_with_opt(rel_path, version, &opt).await else {
         error!("❗️ cannot get file: {}", rel_path);
         return Response::builder().status(StatusCode::NOT_FOUND).body(Body::empty()).unwrap();
     };
@@ -28,3 +76,15 @@ pub async fn get_file(app: AppState, rel_path: &str, version: Option<&str>) -> i
 
     Response::builder().status(StatusCode::OK).body(Body::from_stream(stream)).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_cookie_is_found_among_others() {
+        assert_eq!(find_profile_cookie("a=1; emukc_profile=42; b=2"), Some(42));
+        assert_eq!(find_profile_cookie("emukc_profile=x"), None);
+        assert_eq!(find_profile_cookie("other_emukc_profile=1"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use axum::response::{IntoResponse, Response};
 use emukc_internal::{
+    cache::KacheError,
     model::codex::CodexError,
     prelude::{GameplayError, UserError},
 };
@@ -45,6 +46,7 @@ impl From<GameplayError> for ApiError {
             GameplayError::ProfileNotFound(e) => Self::NotFound(e.to_string()),
             GameplayError::Db(db_err) => Self::Internal(db_err.to_string()),
             GameplayError::BadManifest(e)
+            | GameplayError::InvalidArchive(e)
             | GameplayError::WrongType(e)
             | GameplayError::Insufficient(e)
             | GameplayError::QuestStatusInvalid(e) => Self::Internal(e),
@@ -56,6 +58,7 @@ impl From<GameplayError> for ApiError {
             GameplayError::EntryNotFound(e) | GameplayError::Locked(e) => Self::NotFound(e),
             GameplayError::Json(e) => Self::Internal(e.to_string()),
             GameplayError::Reward(e) => Self::Internal(e.to_string()),
+            GameplayError::Equip(e) => Self::Internal(e.to_string()),
         }
     }
 }
@@ -72,6 +75,15 @@ impl From<CodexError> for ApiError {
     }
 }
 
+impl From<KacheError> for ApiError {
+    fn from(value: KacheError) -> Self {
+        match value {
+            KacheError::ModNotFound(e) => Self::NotFound(format!("mod {e} not found")),
+            e => Self::Internal(e.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         match self {
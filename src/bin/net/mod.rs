@@ -32,6 +32,12 @@ const LOG_TAG: &str = "emukc::net";
 
 type AppState = Extension<StateArc>;
 
+/// Build the application router over `state` without the transport layers
+/// (compression, tracing, CORS), for driving requests in-process.
+pub(super) fn app(state: &State) -> axum::Router {
+    router::new().layer(AddExtensionLayer::new(StateArc::new(state.clone())))
+}
+
 /// Start the network service.
 ///
 /// This function will start the network service and listen for incoming connections.
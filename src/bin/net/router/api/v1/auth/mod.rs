@@ -1,13 +1,16 @@
-use axum::{Json, Router, routing::post};
+use axum::{Json, Router, extract::DefaultBodyLimit, routing::post};
 use emukc_internal::{
     model::{profile::Profile, user::token::Token},
-    prelude::{AccountOps, ProfileOps},
+    prelude::{AccountOps, ProfileArchive, ProfileOps},
 };
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::net::{AppState, err::ApiError};
 
+/// Profile archives outgrow the default 2 MiB request body limit.
+const IMPORT_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
 pub(super) fn router() -> Router {
     Router::new()
         .route("/sign-in", post(sign_in))
@@ -15,6 +18,11 @@ pub(super) fn router() -> Router {
         .route("/new-profile", post(new_profile))
         .route("/start-game", post(start_game))
         .route("/wipe", post(wipe_profile))
+        .route("/export-profile", post(export_profile))
+        .route(
+            "/import-profile",
+            post(import_profile).layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
+        )
 }
 
 #[derive(Serialize, Deserialize, Debug, Validate)]
@@ -43,6 +51,17 @@ struct StartGameRequest {
     profile_id: i64,
 }
 
+#[derive(Serialize, Deserialize, Debug, Validate)]
+struct ImportProfileRequest {
+    #[validate(length(equal = 44))]
+    access_token: String,
+
+    #[validate(length(min = 4))]
+    name: Option<String>,
+
+    archive: ProfileArchive,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct ProfileResponse {
     profile: Profile,
@@ -124,3 +143,29 @@ async fn wipe_profile(
 
     Ok(Json(()))
 }
+
+async fn export_profile(
+    state: AppState,
+    Json(params): Json<StartGameRequest>,
+) -> Result<Json<ProfileArchive>, ApiError> {
+    params.validate().map_err(ApiError::from)?;
+
+    let archive = state.export_profile(&params.access_token, params.profile_id).await?;
+
+    Ok(Json(archive))
+}
+
+async fn import_profile(
+    state: AppState,
+    Json(params): Json<ImportProfileRequest>,
+) -> Result<Json<ProfileResponse>, ApiError> {
+    params.validate().map_err(ApiError::from)?;
+
+    let info =
+        state.import_profile(&params.access_token, &params.archive, params.name.as_deref()).await?;
+
+    Ok(Json(ProfileResponse {
+        profile: info.profile,
+        session: info.session,
+    }))
+}
@@ -0,0 +1,67 @@
+use axum::{Json, Router, routing::post};
+use emukc_internal::{prelude::*, time::chrono};
+use serde::{Deserialize, Serialize};
+
+use crate::net::{AppState, err::ApiError};
+
+pub(super) fn router() -> Router {
+    axum::Router::new()
+        .route("/get", post(get))
+        .route("/freeze", post(freeze))
+        .route("/resume", post(resume))
+        .route("/advance", post(advance))
+        .route("/reset", post(reset))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct ProfileParams {
+    profile_id: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct FreezeParams {
+    profile_id: i64,
+    at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct AdvanceParams {
+    profile_id: i64,
+    seconds: i64,
+}
+
+pub(super) async fn get(
+    state: AppState,
+    Json(params): Json<ProfileParams>,
+) -> Result<Json<ClockStatus>, ApiError> {
+    Ok(Json(state.get_clock(params.profile_id).await?))
+}
+
+pub(super) async fn freeze(
+    state: AppState,
+    Json(params): Json<FreezeParams>,
+) -> Result<Json<ClockStatus>, ApiError> {
+    Ok(Json(state.freeze_clock(params.profile_id, params.at).await?))
+}
+
+pub(super) async fn resume(
+    state: AppState,
+    Json(params): Json<ProfileParams>,
+) -> Result<Json<ClockStatus>, ApiError> {
+    Ok(Json(state.resume_clock(params.profile_id).await?))
+}
+
+pub(super) async fn advance(
+    state: AppState,
+    Json(params): Json<AdvanceParams>,
+) -> Result<Json<ClockStatus>, ApiError> {
+    let by = chrono::Duration::seconds(params.seconds);
+    Ok(Json(state.advance_clock(params.profile_id, by).await?))
+}
+
+pub(super) async fn reset(
+    state: AppState,
+    Json(params): Json<ProfileParams>,
+) -> Result<Json<ClockStatus>, ApiError> {
+    Ok(Json(state.reset_clock(params.profile_id).await?))
+}
@@ -2,10 +2,14 @@ use axum::{Router, middleware};
 
 use crate::net::auth;
 
+mod clock;
+mod mods;
 mod ship;
 
 pub(super) fn router() -> Router {
     Router::new()
+        .merge(Router::new().nest("/clock", clock::router()))
+        .merge(Router::new().nest("/mods", mods::router()))
         .merge(Router::new().nest("/ship", ship::router()))
         .route_layer(middleware::from_fn(auth::auth_middleware))
 }
@@ -0,0 +1,137 @@
+use axum::{Json, Router, routing::post};
+use emukc_internal::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::net::{AppState, err::ApiError};
+
+pub(super) fn router() -> Router {
+    axum::Router::new().route("/get", post(get)).route("/set", post(set))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct ProfileParams {
+    profile_id: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct SetParams {
+    profile_id: i64,
+    mod_id: String,
+    /// The toggle, `None` to follow the pack's manifest again.
+    enabled: Option<bool>,
+}
+
+/// A mod pack as seen by the profile.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct ModResp {
+    id: String,
+    name: String,
+    priority: i64,
+    enabled: bool,
+    toggled: bool,
+    compatible: bool,
+    active: bool,
+}
+
+impl From<ModStatus> for ModResp {
+    fn from(status: ModStatus) -> Self {
+        Self {
+            active: status.is_active(),
+            id: status.pack.id,
+            name: status.pack.manifest.name,
+            priority: status.pack.manifest.priority,
+            enabled: status.enabled,
+            toggled: status.toggled,
+            compatible: status.compatible,
+        }
+    }
+}
+
+pub(super) async fn get(
+    state: AppState,
+    Json(params): Json<ProfileParams>,
+) -> Result<Json<Vec<ModResp>>, ApiError> {
+    let mods = state.kache.mods(Some(params.profile_id)).await?;
+
+    Ok(Json(mods.into_iter().map(Into::into).collect()))
+}
+
+/// Toggle a mod pack through the server's own cache, so it applies to the
+/// next file the profile requests.
+pub(super) async fn set(
+    state: AppState,
+    Json(params): Json<SetParams>,
+) -> Result<Json<Vec<ModResp>>, ApiError> {
+    state.kache.set_mod_enabled(params.profile_id, &params.mod_id, params.enabled).await?;
+    let mods = state.kache.mods(Some(params.profile_id)).await?;
+
+    Ok(Json(mods.into_iter().map(Into::into).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::Extension;
+    use emukc_internal::{cache::MOD_MANIFEST, prelude::*};
+
+    use super::*;
+    use crate::state::{PaymentStore, State};
+
+    #[tokio::test]
+    async fn set_applies_to_the_running_cache() {
+        let cache_root = tempfile::tempdir().unwrap();
+        let mods_root = tempfile::tempdir().unwrap();
+        let pack = mods_root.path().join("skins");
+        std::fs::create_dir_all(&pack).unwrap();
+        std::fs::write(pack.join(MOD_MANIFEST), r#"{ "name": "Skins", "enabled": false }"#)
+            .unwrap();
+
+        let kache = Kache::builder()
+            .with_cache_root(cache_root.path().to_path_buf())
+            .with_mods_root(Some(mods_root.path().to_path_buf()))
+            .with_gadgets_cdn("https://example.invalid/gadgets".to_string())
+            .with_content_cdn("https://example.invalid/content".to_string())
+            .build()
+            .unwrap();
+        let state = Extension(Arc::new(State {
+            db: Arc::new(new_mem_db().await.unwrap()),
+            kache: Arc::new(kache),
+            codex: Arc::new(Codex::default()),
+            sortie_store: Arc::new(SortieStore::new()),
+            practice_store: Arc::new(PracticeStore::new()),
+            payment_store: Arc::new(PaymentStore::new()),
+            clock: Arc::new(GameClock::new()),
+        }));
+
+        // reading the toggles caches them for the profile
+        let Json(mods) = get(
+            state.clone(),
+            Json(ProfileParams {
+                profile_id: 1,
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(!mods[0].active);
+
+        let set_params = |enabled| SetParams {
+            profile_id: 1,
+            mod_id: "skins".to_string(),
+            enabled,
+        };
+        let Json(mods) = set(state.clone(), Json(set_params(Some(true)))).await.unwrap();
+        assert!(mods[0].active && mods[0].toggled);
+        assert!(state.kache.mods(Some(1)).await.unwrap()[0].is_active());
+        assert!(!state.kache.mods(Some(2)).await.unwrap()[0].is_active());
+
+        let Json(mods) = set(state.clone(), Json(set_params(None))).await.unwrap();
+        assert!(!mods[0].active && !mods[0].toggled);
+
+        let missing = SetParams {
+            mod_id: "missing".to_string(),
+            ..set_params(Some(true))
+        };
+        assert!(matches!(set(state, Json(missing)).await, Err(ApiError::NotFound(_))));
+    }
+}
@@ -22,5 +22,5 @@ async fn file_handler(
     Query(params): Query<KcVersionQuery>,
 ) -> Response {
     let rel_path = format!("gadget_html5/{path}");
-    assets::cache::get_file(state, &rel_path, params.version.as_deref()).await.into_response()
+    assets::cache::get_file(state, &rel_path, params.version.as_deref(), None).await.into_response()
 }
@@ -7,12 +7,13 @@ use axum::{
     routing::get,
 };
 use emukc_internal::prelude::PKG_VERSION;
+use http::header;
 use serde::{Deserialize, Serialize};
 use tera::Tera;
 
 use crate::net::{
     AppState,
-    assets::{GameSiteAssets, GameStaticFile},
+    assets::{GameSiteAssets, GameStaticFile, cache::MOD_PROFILE_COOKIE},
     auth::{GameSession, kcs_api_auth_middleware},
 };
 use crate::state::State;
@@ -55,7 +56,10 @@ async fn home(uri: Uri, Extension(session): Extension<GameSession>) -> impl Into
     context.insert("ifr_url", &url);
     let result = tera.render_str(html, &context).unwrap();
 
-    Html(result)
+    // let the game client's resource requests pick this profile's mods
+    let cookie = format!("{MOD_PROFILE_COOKIE}={profile_id}; Path=/; SameSite=Lax");
+
+    ([(header::SET_COOKIE, cookie)], Html(result))
 }
 
 // emukc/css/*
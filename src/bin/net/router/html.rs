@@ -16,5 +16,5 @@ async fn file_handler(state: AppState, Path(path): Path<String>) -> Response {
 
     let cache_rel_path = format!("html/{path}");
 
-    assets::cache::get_file(state, &cache_rel_path, None).await.into_response()
+    assets::cache::get_file(state, &cache_rel_path, None, None).await.into_response()
 }
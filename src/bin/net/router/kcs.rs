@@ -7,7 +7,7 @@ use axum::{
 
 use crate::net::{
     AppState,
-    assets::{self},
+    assets::{self, cache::ModProfile},
 };
 
 use super::KcVersionQuery;
@@ -20,7 +20,10 @@ async fn file_handler(
     state: AppState,
     Path(path): Path<String>,
     Query(params): Query<KcVersionQuery>,
+    ModProfile(profile_id): ModProfile,
 ) -> Response {
     let rel_path = format!("kcs/{path}");
-    assets::cache::get_file(state, &rel_path, params.version.as_deref()).await.into_response()
+    assets::cache::get_file(state, &rel_path, params.version.as_deref(), profile_id)
+        .await
+        .into_response()
 }
@@ -10,7 +10,7 @@ use tera::Tera;
 
 use crate::net::{
     AppState,
-    assets::{self, GameSiteAssets, GameStaticFile},
+    assets::{self, GameSiteAssets, GameStaticFile, cache::ModProfile},
     router::version::gen_version_png,
 };
 
@@ -40,6 +40,7 @@ async fn file_handler(
     state: AppState,
     Path(path): Path<String>,
     Query(params): Query<KcVersionQuery>,
+    ModProfile(profile_id): ModProfile,
 ) -> Response {
     info!("kcs2: {}", path);
 
@@ -90,5 +91,7 @@ async fn file_handler(
     // 	return GameStaticFile(local_path.to_str().unwrap().to_string()).into_response();
     // }
 
-    assets::cache::get_file(state, &cache_rel_path, params.version.as_deref()).await.into_response()
+    assets::cache::get_file(state, &cache_rel_path, params.version.as_deref(), profile_id)
+        .await
+        .into_response()
 }
@@ -0,0 +1,17 @@
+use axum::Extension;
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+    let air_corps = state.get_air_corps(pid).await?;
+    Ok(KcApiResponse::success(&air_corps))
+}
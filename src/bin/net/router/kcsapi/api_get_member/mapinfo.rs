@@ -29,7 +29,7 @@ pub(super) async fn handler(
             api_maintenance_level: v.maintenance_level,
         })
         .collect();
-    let api_air_base = airbases.into_iter().map(std::convert::Into::into).collect();
+    let api_air_base = state.get_air_corps(pid).await?;
 
     let api_map_info = state.get_map_infos(pid).await?;
 
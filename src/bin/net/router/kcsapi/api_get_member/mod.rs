@@ -1,5 +1,6 @@
 use axum::{Router, routing::post};
 
+mod base_air_corps;
 mod basic;
 mod chart_additional_info;
 mod deck;
@@ -28,6 +29,7 @@ mod useitem;
 
 pub(super) fn router() -> Router {
     Router::new()
+        .route("/base_air_corps", post(base_air_corps::handler))
         .route("/basic", post(basic::handler))
         .route("/chart_additional_info", post(chart_additional_info::handler))
         .route("/deck", post(deck::handler))
@@ -106,25 +106,19 @@ mod tests {
         let pid = session.profile.id;
 
         let before = build_require_info_response(&context, pid).await.unwrap();
-        let craftable =
-            context.1.slotitem_extra_info.values().find(|item| item.craftable).unwrap().api_id;
-        let costs = vec![
-            (MaterialCategory::Fuel, 10),
-            (MaterialCategory::Ammo, 10),
-            (MaterialCategory::Steel, 10),
-            (MaterialCategory::Bauxite, 10),
-            (MaterialCategory::DevMat, 1),
-        ];
-
-        let (ids, _materials) = context.create_slotitem(pid, &[craftable], &costs).await.unwrap();
-        let created_id = ids[0];
-        assert!(created_id > 0);
+        // 12cm単装砲, recorded rather than rolled so the test is deterministic.
+        let (items, _materials) =
+            context.record_development(pid, [10, 10, 10, 10], &[1]).await.unwrap();
+        let developed = items[0];
+        assert!(developed.is_success());
+        let created_id = developed.id;
 
         let after = build_require_info_response(&context, pid).await.unwrap();
         assert_eq!(after.api_slot_item.len(), before.api_slot_item.len() + 1);
         assert!(after.api_slot_item.iter().any(|item| item.api_id == created_id));
 
-        let type3 = context.1.find::<ApiMstSlotitem>(&craftable).unwrap().api_type[2].to_string();
+        let type3 =
+            context.1.find::<ApiMstSlotitem>(&developed.mst_id).unwrap().api_type[2].to_string();
         let unset_key = format!("api_slottype{type3}");
         assert!(
             after.api_unsetslot.get(&unset_key).is_some_and(|items| items.contains(&created_id))
@@ -1,5 +1,5 @@
 use axum::Extension;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::net::{
     AppState,
@@ -22,12 +22,19 @@ struct Resp {
     // api_event_object: KcApiEventObject,
     api_parallel_quest_count: i64,
     api_dest_ship_slot: i64,
-    // api_plane_info: Vec<KcApiPlaneInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_plane_info: Option<PortPlaneInfo>,
     // api_furniture_affect_items: Vec<i64>,
     api_c_flags: Vec<i64>,
     api_c_flag2: i64,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+struct PortPlaneInfo {
+    /// slot items relocating after leaving an airbase squadron
+    api_base_convert_slot: Vec<i64>,
+}
+
 pub(super) async fn handler(
     state: AppState,
     Extension(session): Extension<GameSession>,
@@ -96,6 +103,11 @@ async fn build_port_response<T: GameOps + ?Sized>(
     let api_c_flags: Vec<i64> = vec![0]; // event functional flags
     let api_c_flag2 = 0; // mini event item usage lock flag
 
+    let relocating = state.get_relocating_planes(pid).await?;
+    let api_plane_info = (!relocating.is_empty()).then_some(PortPlaneInfo {
+        api_base_convert_slot: relocating,
+    });
+
     Ok(Resp {
         api_material,
         api_deck_port,
@@ -109,6 +121,7 @@ async fn build_port_response<T: GameOps + ?Sized>(
         api_c_flags,
         api_c_flag2,
         api_combined_flag,
+        api_plane_info,
     })
 }
 
@@ -0,0 +1,33 @@
+use axum::{Extension, Form};
+use serde::{Deserialize, Serialize};
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct Params {
+    /// area id
+    api_area_id: i64,
+
+    /// airbase id
+    api_base_id: i64,
+
+    /// new name
+    api_name: String,
+}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    state.rename_airbase(pid, params.api_area_id, params.api_base_id, &params.api_name).await?;
+
+    Ok(KcApiResponse::empty())
+}
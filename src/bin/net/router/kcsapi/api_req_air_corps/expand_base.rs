@@ -0,0 +1,27 @@
+use axum::{Extension, Form};
+use serde::{Deserialize, Serialize};
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct Params {
+    /// area id
+    api_area_id: i64,
+}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let base = state.expand_airbase(pid, params.api_area_id).await?;
+
+    Ok(KcApiResponse::success(&vec![base]))
+}
@@ -0,0 +1,16 @@
+use axum::{Router, routing::post};
+
+mod change_name;
+mod expand_base;
+mod set_action;
+mod set_plane;
+mod supply;
+
+pub(super) fn router() -> Router {
+    Router::new()
+        .route("/change_name", post(change_name::handler))
+        .route("/expand_base", post(expand_base::handler))
+        .route("/set_action", post(set_action::handler))
+        .route("/set_plane", post(set_plane::handler))
+        .route("/supply", post(supply::handler))
+}
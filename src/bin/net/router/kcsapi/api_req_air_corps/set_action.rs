@@ -0,0 +1,47 @@
+use axum::{Extension, Form};
+use serde::{Deserialize, Serialize};
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    err::ApiError,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct Params {
+    /// area id
+    api_area_id: i64,
+
+    /// airbase ids
+    #[serde(deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec")]
+    api_base_id: Vec<i64>,
+
+    /// action kinds, 0: idle, 1: sortie, 2: air defense, 3: evasion, 4: rest
+    #[serde(deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec")]
+    api_action_kind: Vec<i64>,
+}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    if params.api_base_id.len() != params.api_action_kind.len() {
+        return Err(ApiError::Unknown(format!(
+            "base and action count mismatch: {} vs {}",
+            params.api_base_id.len(),
+            params.api_action_kind.len()
+        ))
+        .into());
+    }
+    let actions: Vec<(i64, i64)> =
+        params.api_base_id.iter().copied().zip(params.api_action_kind.iter().copied()).collect();
+
+    state.set_airbase_actions(pid, params.api_area_id, &actions).await?;
+
+    Ok(KcApiResponse::empty())
+}
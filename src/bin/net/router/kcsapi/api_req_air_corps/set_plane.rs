@@ -0,0 +1,68 @@
+use axum::{Extension, Form};
+use serde::{Deserialize, Serialize};
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    err::ApiError,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct Params {
+    /// area id
+    api_area_id: i64,
+
+    /// airbase id
+    api_base_id: i64,
+
+    /// squadron ids
+    #[serde(deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec")]
+    api_squadron_id: Vec<i64>,
+
+    /// slot item ids, `-1` removes the plane
+    #[serde(deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec")]
+    api_item_id: Vec<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Resp {
+    api_plane_info: Vec<KcApiPlaneInfo>,
+    api_distance: KcApiDistance,
+    api_after_bauxite: i64,
+}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    if params.api_squadron_id.len() != params.api_item_id.len() {
+        return Err(ApiError::Unknown(format!(
+            "squadron and item count mismatch: {} vs {}",
+            params.api_squadron_id.len(),
+            params.api_item_id.len()
+        ))
+        .into());
+    }
+    let assignments: Vec<(i64, i64)> =
+        params.api_squadron_id.iter().copied().zip(params.api_item_id.iter().copied()).collect();
+
+    let (base, material) =
+        state.set_airbase_planes(pid, params.api_area_id, params.api_base_id, &assignments).await?;
+
+    let api_plane_info = base
+        .api_plane_info
+        .into_iter()
+        .filter(|info| params.api_squadron_id.contains(&info.api_squadron_id))
+        .collect();
+
+    Ok(KcApiResponse::success(&Resp {
+        api_plane_info,
+        api_distance: base.api_distance,
+        api_after_bauxite: material.bauxite,
+    }))
+}
@@ -0,0 +1,55 @@
+use axum::{Extension, Form};
+use serde::{Deserialize, Serialize};
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct Params {
+    /// area id
+    api_area_id: i64,
+
+    /// airbase id
+    api_base_id: i64,
+
+    /// squadron ids
+    #[serde(deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec")]
+    api_squadron_id: Vec<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Resp {
+    api_plane_info: Vec<KcApiPlaneInfo>,
+    api_distance: KcApiDistance,
+    api_after_fuel: i64,
+    api_after_bauxite: i64,
+}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let (base, material) = state
+        .supply_airbase(pid, params.api_area_id, params.api_base_id, &params.api_squadron_id)
+        .await?;
+
+    let api_plane_info = base
+        .api_plane_info
+        .into_iter()
+        .filter(|info| params.api_squadron_id.contains(&info.api_squadron_id))
+        .collect();
+
+    Ok(KcApiResponse::success(&Resp {
+        api_plane_info,
+        api_distance: base.api_distance,
+        api_after_fuel: material.fuel,
+        api_after_bauxite: material.bauxite,
+    }))
+}
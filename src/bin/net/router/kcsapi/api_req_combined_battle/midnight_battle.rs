@@ -0,0 +1,18 @@
+use axum::Extension;
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+    let resp = state.sortie_midnight_battle(pid).await?;
+
+    Ok(KcApiResponse::success(&resp))
+}
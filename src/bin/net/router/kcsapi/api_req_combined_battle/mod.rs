@@ -0,0 +1,110 @@
+use axum::{Router, routing::post};
+
+mod airbattle;
+mod battle;
+mod battle_water;
+mod battleresult;
+mod each_battle;
+mod each_battle_water;
+mod ec_battle;
+mod ec_midnight_battle;
+mod goback_port;
+mod ld_airbattle;
+mod ld_shooting;
+mod midnight_battle;
+mod sp_midnight;
+
+// The day battle variants differ only in which fleets are combined. The
+// simulation picks the phase order from the stored combined type and the
+// enemy composition, so they all resolve through the same sortie battle.
+pub(super) fn router() -> Router {
+    Router::new()
+        .route("/airbattle", post(airbattle::handler))
+        .route("/battle", post(battle::handler))
+        .route("/battle_water", post(battle_water::handler))
+        .route("/battleresult", post(battleresult::handler))
+        .route("/each_battle", post(each_battle::handler))
+        .route("/each_battle_water", post(each_battle_water::handler))
+        .route("/ec_battle", post(ec_battle::handler))
+        .route("/ec_midnight_battle", post(ec_midnight_battle::handler))
+        .route("/goback_port", post(goback_port::handler))
+        .route("/ld_airbattle", post(ld_airbattle::handler))
+        .route("/ld_shooting", post(ld_shooting::handler))
+        .route("/midnight_battle", post(midnight_battle::handler))
+        .route("/sp_midnight", post(sp_midnight::handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::net::router::kcsapi::test_utils::{app_state, new_test_context};
+    use crate::state::State;
+    use axum::{Extension, Form};
+    use emukc_internal::prelude::{FleetOps, MapOps, ShipOps, SortieOps};
+
+    async fn seed_combined_fleet(state: &Arc<State>, profile_id: i64, combined_type: i64) {
+        let main = state.add_ship(profile_id, 951).await.unwrap();
+        state.update_fleet_ships(profile_id, 1, &[main.api_id, -1, -1, -1, -1, -1]).await.unwrap();
+        state.unlock_fleet(profile_id, 2).await.unwrap();
+        let escort = state.add_ship(profile_id, 952).await.unwrap();
+        state
+            .update_fleet_ships(profile_id, 2, &[escort.api_id, -1, -1, -1, -1, -1])
+            .await
+            .unwrap();
+        state.set_combined_type(profile_id, combined_type).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn battle_water_and_battleresult_report_escort_fleet() {
+        let context = new_test_context().await;
+        let pid = context.session.profile.id;
+        seed_combined_fleet(&context.state, pid, 2).await;
+
+        context.state.start_sortie(pid, 1, 1, 1).await.unwrap();
+
+        let battle = battle_water::handler(
+            app_state(&context.state),
+            Extension(context.session.clone()),
+            Form(battle_water::Params {
+                api_formation: 14,
+                api_recovery_type: 0,
+                api_supply_flag: None,
+                api_ration_flag: None,
+                api_smoke_flag: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let data = battle.api_data.unwrap();
+        assert_eq!(data["api_formation"][0], 14);
+        assert_eq!(data["api_f_nowhps_combined"].as_array().unwrap().len(), 1);
+        assert!(data.get("api_e_nowhps_combined").is_none(), "enemy is a single fleet");
+
+        let result = battleresult::handler(
+            app_state(&context.state),
+            Extension(context.session.clone()),
+            Form(battleresult::Params::default()),
+        )
+        .await
+        .unwrap();
+        let result_data = result.api_data.unwrap();
+        assert_eq!(result_data["api_get_ship_exp_combined"].as_array().unwrap().len(), 2);
+        assert_eq!(result_data["api_escape_flag"], 0);
+    }
+
+    #[tokio::test]
+    async fn goback_port_rejects_sortie_without_escape_offer() {
+        let context = new_test_context().await;
+        let pid = context.session.profile.id;
+        seed_combined_fleet(&context.state, pid, 1).await;
+
+        context.state.start_sortie(pid, 1, 1, 1).await.unwrap();
+
+        let result =
+            goback_port::handler(app_state(&context.state), Extension(context.session.clone()))
+                .await;
+        assert!(result.is_err());
+    }
+}
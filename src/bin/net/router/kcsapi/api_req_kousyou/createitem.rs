@@ -1,12 +1,11 @@
 use axum::{Extension, Form};
 use serde::{Deserialize, Serialize};
 
-use emukc_internal::{crypto::rng, prelude::*};
+use emukc_internal::prelude::*;
 
 use crate::net::{
     AppState,
     auth::GameSession,
-    err::ApiError,
     resp::{KcApiResponse, KcApiResult},
 };
 
@@ -53,84 +52,36 @@ pub(super) async fn handler(
     let pid = session.profile.id;
     let codex = state.codex();
 
-    let costs = if params.api_multiple_flag == 0 {
-        vec![
-            (MaterialCategory::Fuel, params.api_item1),
-            (MaterialCategory::Ammo, params.api_item2),
-            (MaterialCategory::Steel, params.api_item3),
-            (MaterialCategory::Bauxite, params.api_item4),
-            (MaterialCategory::DevMat, 1),
-        ]
-    } else {
-        vec![
-            (MaterialCategory::Fuel, params.api_item1 * 3),
-            (MaterialCategory::Ammo, params.api_item2 * 3),
-            (MaterialCategory::Steel, params.api_item3 * 3),
-            (MaterialCategory::Bauxite, params.api_item4 * 3),
-            (MaterialCategory::DevMat, 3),
-        ]
-    };
-
-    let pool: Vec<Kc3rdSlotItem> = codex
-        .slotitem_extra_info
-        .values()
-        .filter_map(|info| {
-            if !info.craftable {
-                None
-            } else {
-                Some(info.clone())
-            }
-        })
-        .collect();
-
-    let _crafted_mst_ids: Vec<i64> = Vec::new();
-
-    let upper = if params.api_multiple_flag == 1 {
+    let recipe = [params.api_item1, params.api_item2, params.api_item3, params.api_item4];
+    let attempts = if params.api_multiple_flag == 1 {
         3
     } else {
         1
     };
 
-    let crafted_mst_ids = {
-        let mut ids = Vec::new();
-        for _ in 0..upper {
-            let next_u32 = rng::u32(0..100);
-            if next_u32 > 30 {
-                let item = rng::choose(&pool).ok_or(ApiError::Internal(
-                    "cannot pick random from slotitem crafting pool".to_owned(),
-                ))?;
-                ids.push(item.api_id);
-            } else {
-                ids.push(-1);
-            }
-        }
-        ids
-    };
+    let (developed, material) = state.create_slotitem(pid, recipe, attempts).await?;
 
-    let (ids, material) = state.create_slotitem(pid, &crafted_mst_ids, &costs).await?;
-
-    let api_create_flag = if ids.iter().any(|v| *v > 0) {
+    let api_create_flag = if developed.iter().any(DevelopedSlotItem::is_success) {
         1
     } else {
         0
     };
     let api_material: Vec<KcApiMaterialElement> = material.into();
     let api_material: Vec<i64> = api_material.into_iter().map(|v| v.api_value).collect();
-    let api_get_items: Vec<GetItem> = crafted_mst_ids
+    let api_get_items: Vec<GetItem> = developed
         .iter()
-        .zip(ids)
-        .map(|(mst_id, id)| GetItem {
-            api_id: id,
-            api_slotitem_id: *mst_id,
+        .map(|item| GetItem {
+            api_id: item.id,
+            api_slotitem_id: item.mst_id,
         })
         .collect();
 
     let api_unset_items = if api_create_flag != 0 {
-        let crafted_types = crafted_mst_ids
+        let crafted_types = developed
             .iter()
-            .filter_map(|id| {
-                if *id > 0 {
-                    codex.find::<ApiMstSlotitem>(id).map(|mst| mst.api_type[2]).ok()
+            .filter_map(|item| {
+                if item.is_success() {
+                    codex.find::<ApiMstSlotitem>(&item.mst_id).map(|mst| mst.api_type[2]).ok()
                 } else {
                     None
                 }
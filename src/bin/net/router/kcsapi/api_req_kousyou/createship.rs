@@ -1,12 +1,11 @@
 use axum::{Extension, Form};
 use serde::{Deserialize, Serialize};
 
-use emukc_internal::{crypto::rng, prelude::*};
+use emukc_internal::prelude::*;
 
 use crate::net::{
     AppState,
     auth::GameSession,
-    err::ApiError,
     resp::{KcApiResponse, KcApiResult},
 };
 
@@ -36,47 +35,20 @@ pub(super) async fn handler(
     Form(params): Form<Params>,
 ) -> KcApiResult {
     let pid = session.profile.id;
-    let codex = state.codex();
-
-    let mut costs = vec![
-        (MaterialCategory::Fuel, params.api_item1),
-        (MaterialCategory::Ammo, params.api_item2),
-        (MaterialCategory::Steel, params.api_item3),
-        (MaterialCategory::Bauxite, params.api_item4),
-        (MaterialCategory::DevMat, params.api_item5),
-    ];
-
-    if params.api_highspeed != 0 {
-        costs.push((MaterialCategory::Torch, (1 + 9 * params.api_large_flag)));
-    };
-
-    let pool: Vec<Kc3rdShip> = codex
-        .ship_extra
-        .values()
-        .filter_map(|info| {
-            if !info.buildable || (params.api_large_flag == 0 && !info.buildable_lsc) {
-                None
-            } else {
-                Some(info.clone())
-            }
-        })
-        .collect();
-
-    let ship = {
-        rng::choose(&pool).ok_or(ApiError::Internal(format!(
-            "Failed to choose a ship from the pool of {} ships",
-            pool.len()
-        )))?
-    };
 
     state
         .create_ship(
             pid,
             params.api_kdock_id,
-            ship.api_id,
+            [
+                params.api_item1,
+                params.api_item2,
+                params.api_item3,
+                params.api_item4,
+                params.api_item5,
+            ],
             params.api_large_flag > 0,
             params.api_highspeed > 0,
-            &costs,
         )
         .await?;
 
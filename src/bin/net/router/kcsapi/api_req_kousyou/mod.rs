@@ -11,6 +11,9 @@ mod preset_dev_items_delete;
 mod preset_dev_items_expand;
 mod preset_dev_items_register;
 mod preset_dev_items_update_name;
+mod remodel_slot;
+mod remodel_slotlist;
+mod remodel_slotlist_detail;
 
 pub(super) fn router() -> Router {
     Router::new()
@@ -25,4 +28,7 @@ pub(super) fn router() -> Router {
         .route("/preset_dev_items_delete", post(preset_dev_items_delete::handler))
         .route("/preset_dev_items_update_name", post(preset_dev_items_update_name::handler))
         .route("/preset_dev_items_expand", post(preset_dev_items_expand::handler))
+        .route("/remodel_slotlist", post(remodel_slotlist::handler))
+        .route("/remodel_slotlist_detail", post(remodel_slotlist_detail::handler))
+        .route("/remodel_slot", post(remodel_slot::handler))
 }
@@ -0,0 +1,62 @@
+use axum::{Extension, Form};
+use serde::{Deserialize, Serialize};
+
+use emukc_internal::prelude::*;
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct Params {
+    /// recipe id
+    api_id: i64,
+    /// 0: normal, 1: guaranteed
+    api_certain_flag: i64,
+    /// slot item instance id
+    api_slot_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resp {
+    api_remodel_flag: i64,
+    /// slot item master id before and after
+    api_remodel_id: [i64; 2],
+    api_after_material: Vec<i64>,
+    api_voice_ship_id: i64,
+    api_voice_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_after_slot: Option<KcApiSlotItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_use_slot_id: Option<Vec<i64>>,
+}
+
+#[axum_macros::debug_handler]
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let result = state
+        .improve_slot_item(pid, params.api_id, params.api_slot_id, params.api_certain_flag == 1)
+        .await?;
+
+    let api_after_material: Vec<KcApiMaterialElement> = result.material.into();
+    let api_after_material: Vec<i64> =
+        api_after_material.into_iter().map(|v| v.api_value).collect();
+
+    Ok(KcApiResponse::success(&Resp {
+        api_remodel_flag: i64::from(result.success),
+        api_remodel_id: [result.before_mst_id, result.after_mst_id],
+        api_after_material,
+        api_voice_ship_id: result.helper_mst_id,
+        api_voice_id: 0,
+        api_after_slot: result.slot_item,
+        api_use_slot_id: (!result.consumed_slot_item_ids.is_empty())
+            .then_some(result.consumed_slot_item_ids),
+    }))
+}
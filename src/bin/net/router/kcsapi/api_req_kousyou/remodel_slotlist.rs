@@ -0,0 +1,62 @@
+use axum::Extension;
+use serde::{Deserialize, Serialize};
+
+use emukc_internal::prelude::*;
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemodelSlotElement {
+    /// recipe id
+    api_id: i64,
+    /// slot item master id
+    api_slot_id: i64,
+    api_req_fuel: i64,
+    api_req_bull: i64,
+    api_req_steel: i64,
+    api_req_bauxite: i64,
+    /// development material
+    api_req_buildkit: i64,
+    /// improvement material
+    api_req_remodelkit: i64,
+    api_req_slot_id: i64,
+    api_req_slot_num: i64,
+    /// 0: normal, 1/2: special improvement bubble
+    api_sp_type: i64,
+}
+
+#[axum_macros::debug_handler]
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let recipes = state.get_improvement_list(pid).await?;
+
+    let list: Vec<RemodelSlotElement> = recipes
+        .iter()
+        .map(|recipe| {
+            let stage = recipe.stages.first();
+            RemodelSlotElement {
+                api_id: recipe.id,
+                api_slot_id: recipe.slotitem_id,
+                api_req_fuel: recipe.fuel,
+                api_req_bull: recipe.ammo,
+                api_req_steel: recipe.steel,
+                api_req_bauxite: recipe.bauxite,
+                api_req_buildkit: stage.map_or(0, |s| s.devmat),
+                api_req_remodelkit: stage.map_or(0, |s| s.screw),
+                api_req_slot_id: 0,
+                api_req_slot_num: 0,
+                api_sp_type: 0,
+            }
+        })
+        .collect();
+
+    Ok(KcApiResponse::success(&list))
+}
@@ -0,0 +1,72 @@
+use axum::{Extension, Form};
+use serde::{Deserialize, Serialize};
+
+use emukc_internal::prelude::*;
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct Params {
+    /// recipe id
+    api_id: i64,
+    /// slot item instance id
+    api_slot_id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Resp {
+    api_req_buildkit: i64,
+    api_req_remodelkit: i64,
+    api_certain_buildkit: i64,
+    api_certain_remodelkit: i64,
+    api_req_slot_id: i64,
+    api_req_slot_num: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_req_useitem_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_req_useitem_num: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_req_useitem_id2: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_req_useitem_num2: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_req_slot_id2: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_req_slot_num2: Option<i64>,
+    /// 1 if the improvement upgrades the slot item
+    api_change_flag: i64,
+}
+
+#[axum_macros::debug_handler]
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let detail = state.get_improvement_detail(pid, params.api_id, params.api_slot_id).await?;
+    let stage = &detail.stage;
+    let slot_item = |i: usize| stage.slot_items.get(i);
+    let use_item = |i: usize| stage.use_items.get(i);
+
+    Ok(KcApiResponse::success(&Resp {
+        api_req_buildkit: stage.devmat,
+        api_req_remodelkit: stage.screw,
+        api_certain_buildkit: stage.certain_devmat,
+        api_certain_remodelkit: stage.certain_screw,
+        api_req_slot_id: slot_item(0).map_or(0, |item| item.id),
+        api_req_slot_num: slot_item(0).map_or(0, |item| item.count),
+        api_req_useitem_id: use_item(0).map(|item| item.id),
+        api_req_useitem_num: use_item(0).map(|item| item.count),
+        api_req_useitem_id2: use_item(1).map(|item| item.id),
+        api_req_useitem_num2: use_item(1).map(|item| item.count),
+        api_req_slot_id2: slot_item(1).map(|item| item.id),
+        api_req_slot_num2: slot_item(1).map(|item| item.count),
+        api_change_flag: i64::from(detail.upgrade),
+    }))
+}
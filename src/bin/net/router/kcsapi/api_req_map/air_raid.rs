@@ -0,0 +1,24 @@
+use axum::{Extension, Form};
+use serde::Deserialize;
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Deserialize, Debug)]
+pub(super) struct Params {}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(_params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let raid = state.sortie_air_raid(pid).await?;
+
+    Ok(KcApiResponse::success(&raid))
+}
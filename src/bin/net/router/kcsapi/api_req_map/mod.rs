@@ -1,15 +1,19 @@
 use axum::{Router, routing::post};
 
+mod air_raid;
 mod next;
 mod projection;
 mod select_eventmap_rank;
 mod start;
+mod start_air_base;
 
 pub(super) fn router() -> Router {
     Router::new()
+        .route("/air_raid", post(air_raid::handler))
         .route("/next", post(next::handler))
         .route("/select_eventmap_rank", post(select_eventmap_rank::handler))
         .route("/start", post(start::handler))
+        .route("/start_air_base", post(start_air_base::handler))
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
 use emukc_internal::prelude::{
     KcApiMapAirSearch, KcApiMapCellData, KcApiMapEnemyDeckInfo, KcApiMapHappening, KcApiMapItemGet,
-    KcApiMapNext, KcApiMapStart, SortieAirSearch, SortieCellData, SortieEnemyDeckPreview,
-    SortieHappening, SortieItemGet, SortieNextResponse, SortieStartResponse,
+    KcApiMapNext, KcApiMapSelectRoute, KcApiMapStart, SortieAirSearch, SortieCellData,
+    SortieEnemyDeckPreview, SortieHappening, SortieItemGet, SortieNextResponse,
+    SortieStartResponse,
 };
 
 pub(super) fn project_start(response: SortieStartResponse) -> KcApiMapStart {
@@ -27,6 +28,7 @@ pub(super) fn project_start(response: SortieStartResponse) -> KcApiMapStart {
             .map(|preview| preview.into_iter().map(project_enemy_deck_preview).collect()),
         api_itemget: None,
         api_happening: None,
+        api_select_route: response.select_route.map(project_select_route),
     }
 }
 
@@ -53,6 +55,13 @@ pub(super) fn project_next(response: SortieNextResponse) -> KcApiMapNext {
         api_limit_state: response.limit_state,
         api_itemget: response.itemget.map(|items| items.into_iter().map(project_itemget).collect()),
         api_happening: response.happening.map(project_happening),
+        api_select_route: response.select_route.map(project_select_route),
+    }
+}
+
+fn project_select_route(select_cells: Vec<i64>) -> KcApiMapSelectRoute {
+    KcApiMapSelectRoute {
+        api_select_cells: select_cells,
     }
 }
 
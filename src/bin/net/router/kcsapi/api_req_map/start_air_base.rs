@@ -0,0 +1,83 @@
+use axum::{Extension, Form};
+use serde::Deserialize;
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    resp::{KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Deserialize, Debug)]
+pub(super) struct Params {
+    /// area id
+    pub(super) api_area_id: i64,
+
+    /// sortied airbase ids
+    #[serde(deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec")]
+    pub(super) api_base_id: Vec<i64>,
+
+    /// target cells of the first airbase
+    #[serde(deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec")]
+    pub(super) api_strike_point_1: Vec<i64>,
+
+    /// target cells of the second airbase
+    #[serde(
+        default,
+        deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec"
+    )]
+    pub(super) api_strike_point_2: Vec<i64>,
+
+    /// target cells of the third airbase
+    #[serde(
+        default,
+        deserialize_with = "crate::net::router::kcsapi::form_utils::deserialize_form_ivec"
+    )]
+    pub(super) api_strike_point_3: Vec<i64>,
+}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let points =
+        [&params.api_strike_point_1, &params.api_strike_point_2, &params.api_strike_point_3];
+    let strikes: Vec<AirBaseStrike> = params
+        .api_base_id
+        .iter()
+        .zip(points)
+        .map(|(&rid, cells)| AirBaseStrike {
+            rid,
+            cells: [
+                cells.first().copied().unwrap_or(0),
+                cells.get(1).or(cells.first()).copied().unwrap_or(0),
+            ],
+        })
+        .collect();
+
+    state.start_air_base(pid, params.api_area_id, &strikes).await?;
+
+    Ok(KcApiResponse::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_deserialize_strike_points() {
+        let params: Params = serde_urlencoded::from_str(
+            "api_area_id=6&api_base_id=1%2C2&api_strike_point_1=5%2C5&api_strike_point_2=3%2C7",
+        )
+        .unwrap();
+
+        assert_eq!(params.api_area_id, 6);
+        assert_eq!(params.api_base_id, vec![1, 2]);
+        assert_eq!(params.api_strike_point_1, vec![5, 5]);
+        assert_eq!(params.api_strike_point_2, vec![3, 7]);
+        assert!(params.api_strike_point_3.is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use axum::{Extension, Form};
+use emukc::model::profile::practice::RivalType;
+use serde::{Deserialize, Serialize};
+
+use crate::net::{
+    AppState,
+    auth::GameSession,
+    err::ApiError,
+    resp::{KcApiError, KcApiResponse, KcApiResult},
+};
+use emukc_internal::prelude::*;
+
+#[derive(Deserialize)]
+pub(super) struct Params {
+    api_selected_kind: i64,
+}
+
+#[derive(Serialize)]
+struct Resp {
+    api_update_flag: i64,
+}
+
+pub(super) async fn handler(
+    state: AppState,
+    Extension(session): Extension<GameSession>,
+    Form(params): Form<Params>,
+) -> KcApiResult {
+    let pid = session.profile.id;
+
+    let kind = RivalType::n(params.api_selected_kind).ok_or_else(|| {
+        KcApiError::from(ApiError::Unknown(format!(
+            "unknown matching kind {}",
+            params.api_selected_kind
+        )))
+    })?;
+    let cfg = state.change_practice_matching_kind(pid, kind).await?;
+
+    Ok(KcApiResponse::success(&Resp {
+        api_update_flag: i64::from(cfg.selected_type != cfg.generated_type),
+    }))
+}
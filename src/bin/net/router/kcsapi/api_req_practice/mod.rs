@@ -2,11 +2,13 @@ use axum::{Router, routing::post};
 
 mod battle;
 mod battle_result;
+mod change_matching_kind;
 mod midnight_battle;
 
 pub(super) fn router() -> Router {
     Router::new()
         .route("/battle", post(battle::handler))
         .route("/battle_result", post(battle_result::handler))
+        .route("/change_matching_kind", post(change_matching_kind::handler))
         .route("/midnight_battle", post(midnight_battle::handler))
 }
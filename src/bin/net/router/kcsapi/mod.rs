@@ -24,7 +24,9 @@ use crate::{
 mod api_dmm_payment;
 mod api_get_member;
 mod api_port;
+mod api_req_air_corps;
 mod api_req_battle_midnight;
+mod api_req_combined_battle;
 mod api_req_furniture;
 mod api_req_hensei;
 mod api_req_hokyu;
@@ -50,8 +52,10 @@ pub(super) fn router() -> Router {
         .merge(Router::new().nest("/api_get_member", api_get_member::router()))
         .merge(Router::new().nest("/api_port", api_port::router()))
         .merge(Router::new().nest("/api_req_init", api_req_init::router()))
+        .merge(Router::new().nest("/api_req_air_corps", api_req_air_corps::router()))
         .merge(Router::new().nest("/api_req_furniture", api_req_furniture::router()))
         .merge(Router::new().nest("/api_req_battle_midnight", api_req_battle_midnight::router()))
+        .merge(Router::new().nest("/api_req_combined_battle", api_req_combined_battle::router()))
         .merge(Router::new().nest("/api_req_hensei", api_req_hensei::router()))
         .merge(Router::new().nest("/api_req_hokyu", api_req_hokyu::router()))
         .merge(Router::new().nest("/api_req_kaisou", api_req_kaisou::router()))
@@ -267,6 +271,7 @@ pub(super) mod test_utils {
             sortie_store: Arc::new(SortieStore::new()),
             practice_store: Arc::new(PracticeStore::new()),
             payment_store: Arc::new(crate::state::PaymentStore::new()),
+            clock: Arc::new(GameClock::new()),
         });
 
         let account = state.sign_up("router-test", "1234567").await.unwrap();
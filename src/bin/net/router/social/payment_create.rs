@@ -111,6 +111,7 @@ mod tests {
             sortie_store: Arc::new(SortieStore::new()),
             practice_store: Arc::new(PracticeStore::new()),
             payment_store: Arc::new(crate::state::PaymentStore::new()),
+            clock: Arc::new(GameClock::new()),
         });
 
         let account = state.sign_up("test-user", "1234567").await.unwrap();
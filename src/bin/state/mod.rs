@@ -5,7 +5,11 @@ use std::{fs::create_dir, sync::Arc};
 use anyhow::bail;
 use emukc_internal::{
     db::sea_orm::DbConn,
-    prelude::{Codex, HasContext, Kache, PracticeStore, SortieStore, prepare},
+    prelude::{
+        Codex, DEFAULT_SESSION_TTL, GameClock, HasContext, Kache, PracticeStore, SortieStore,
+        new_mem_db, prepare,
+    },
+    time::chrono::Duration,
 };
 
 use crate::cfg::AppConfig;
@@ -14,7 +18,9 @@ mod payment_store;
 
 pub use payment_store::{PaymentSession, PaymentStore};
 
-const DB_NAME: &str = "emukc.db";
+pub(crate) const DB_NAME: &str = "emukc.db";
+const SORTIE_JOURNAL_NAME: &str = "emukc.sortie.jsonl";
+const PRACTICE_JOURNAL_NAME: &str = "emukc.practice.jsonl";
 
 /// Application state
 #[derive(Debug, Clone)]
@@ -36,6 +42,9 @@ pub struct State {
 
     /// Payment session store (instance-scoped)
     pub payment_store: Arc<PaymentStore>,
+
+    /// Game clock (instance-scoped)
+    pub clock: Arc<GameClock>,
 }
 
 impl State {
@@ -57,22 +66,47 @@ impl State {
         let db_path = cfg.workspace_root.join(DB_NAME);
         let db = Arc::new(prepare(&db_path, false).await?);
 
-        let kache = Kache::builder()
-            .with_cache_root(cfg.cache_root.clone())
-            .with_mods_root(cfg.mods_root.clone())
-            .with_gadgets_cdns(cfg.gadgets_cdn.clone())
-            .with_content_cdns(cfg.game_cdn.clone())
-            .with_proxy(cfg.proxy.to_owned())
-            .build()?;
+        // in-flight sorties and practice battles, kept across restarts
+        let ttl = cfg
+            .session_ttl_hours
+            .map_or(DEFAULT_SESSION_TTL, |hours| Duration::hours(i64::from(hours)));
+        let sortie_store =
+            SortieStore::with_journal(cfg.workspace_root.join(SORTIE_JOURNAL_NAME), ttl)?;
+        let practice_store =
+            PracticeStore::with_journal(cfg.workspace_root.join(PRACTICE_JOURNAL_NAME), ttl)?;
 
         // kache system
-        let kache = Arc::new(kache);
+        let kache = Arc::new(new_kache(cfg)?);
 
         // codex
         let codex_root = cfg.codex_root()?;
         let codex = Codex::load(&codex_root, load_cache_source)?;
         let codex = Arc::new(codex);
 
+        Ok(Self {
+            db,
+            kache,
+            codex,
+            sortie_store: Arc::new(sortie_store),
+            practice_store: Arc::new(practice_store),
+            payment_store: Arc::new(PaymentStore::new()),
+            clock: Arc::new(GameClock::new()),
+        })
+    }
+
+    /// Create a throwaway application state backed by an in-memory database.
+    ///
+    /// The kache and codex come from `cfg`; nothing is written to the
+    /// workspace database.
+    ///
+    /// # Parameters
+    ///
+    /// - `cfg` - Application configuration
+    pub async fn scratch(cfg: &AppConfig) -> anyhow::Result<Self> {
+        let db = Arc::new(new_mem_db().await?);
+        let kache = Arc::new(new_kache(cfg)?);
+        let codex = Arc::new(Codex::load_without_cache_source(cfg.codex_root()?)?);
+
         Ok(Self {
             db,
             kache,
@@ -80,10 +114,21 @@ impl State {
             sortie_store: Arc::new(SortieStore::new()),
             practice_store: Arc::new(PracticeStore::new()),
             payment_store: Arc::new(PaymentStore::new()),
+            clock: Arc::new(GameClock::new()),
         })
     }
 }
 
+fn new_kache(cfg: &AppConfig) -> anyhow::Result<Kache> {
+    Ok(Kache::builder()
+        .with_cache_root(cfg.cache_root.clone())
+        .with_mods_root(cfg.mods_root.clone())
+        .with_gadgets_cdns(cfg.gadgets_cdn.clone())
+        .with_content_cdns(cfg.game_cdn.clone())
+        .with_proxy(cfg.proxy.to_owned())
+        .build()?)
+}
+
 pub type StateArc = Arc<State>;
 
 impl HasContext for State {
@@ -102,6 +147,10 @@ impl HasContext for State {
     fn practice_store(&self) -> &PracticeStore {
         self.practice_store.as_ref()
     }
+
+    fn clock(&self) -> &GameClock {
+        self.clock.as_ref()
+    }
 }
 
 #[cfg(test)]
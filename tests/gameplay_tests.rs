@@ -18,6 +18,7 @@ pub struct TestContext {
     codex: &'static Codex,
     sortie_store: SortieStore,
     practice_store: PracticeStore,
+    clock: GameClock,
 }
 
 impl TestContext {
@@ -29,6 +30,7 @@ impl TestContext {
             codex: &CODEX,
             sortie_store: SortieStore::new(),
             practice_store: PracticeStore::new(),
+            clock: GameClock::new(),
         }
     }
 }
@@ -49,6 +51,10 @@ impl HasContext for TestContext {
     fn practice_store(&self) -> &PracticeStore {
         &self.practice_store
     }
+
+    fn clock(&self) -> &GameClock {
+        &self.clock
+    }
 }
 
 #[path = "gameplay_tests/map/mod.rs"]
@@ -77,3 +83,6 @@ mod scenario;
 
 #[path = "gameplay_tests/battle_golden.rs"]
 mod battle_golden;
+
+#[path = "gameplay_tests/profile_archive.rs"]
+mod profile_archive;
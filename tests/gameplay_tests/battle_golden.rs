@@ -34,8 +34,11 @@ mod tests {
         let simulation = BattleSimulation {
             friendly: session.friendly,
             enemy: session.enemy,
+            friendly_escort: session.friendly_escort,
+            enemy_escort: session.enemy_escort,
             packet: session.packet,
             outcome: session.outcome,
+            air_base_waves: Vec::new(),
         };
         let transcript = render_day_battle(&simulation);
 
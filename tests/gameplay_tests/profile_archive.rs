@@ -0,0 +1,54 @@
+//! Tests for profile export/import.
+
+#[cfg(test)]
+mod tests {
+    use emukc_internal::prelude::*;
+
+    #[tokio::test]
+    async fn exported_profile_imports_with_remapped_instances() {
+        let context = crate::TestContext::new().await;
+        let account = context.sign_up("test-archive", "1234567").await.unwrap();
+        let token = account.access_token.token.clone();
+        let profile = context.new_profile(&token, "archived").await.unwrap();
+        let pid = profile.profile.id;
+
+        let ship = context.add_ship(pid, 1).await.unwrap();
+        context.update_fleet_ships(pid, 1, &[ship.api_id, -1, -1, -1, -1, -1]).await.unwrap();
+        let source = context.export_profile(&token, pid).await.unwrap();
+
+        let imported = context.import_profile(&token, &source, Some("imported")).await.unwrap();
+        let new_pid = imported.profile.id;
+        assert_ne!(new_pid, pid);
+        assert_eq!(imported.profile.name, "imported");
+
+        let copy = context.export_profile(&token, new_pid).await.unwrap();
+        assert_eq!(copy.ships.len(), source.ships.len());
+        assert_eq!(copy.slot_items.len(), source.slot_items.len());
+        assert_eq!(copy.map_records.len(), source.map_records.len());
+        assert_eq!(copy.quest_progress.len(), source.quest_progress.len());
+
+        // the fleet points at the imported copy of the ship, not the original
+        let fleet = context.get_fleet(new_pid, 1).await.unwrap();
+        let copied_ship = copy.ships.iter().find(|s| s.mst_id == ship.api_ship_id).unwrap();
+        assert_eq!(fleet.ships[0], copied_ship.id);
+        assert_ne!(fleet.ships[0], ship.api_id);
+        for item in copy.slot_items.iter().filter(|item| item.equip_on > 0) {
+            assert!(copy.ships.iter().any(|s| s.id == item.equip_on));
+        }
+    }
+
+    #[tokio::test]
+    async fn import_rejects_unknown_manifest_ids() {
+        let context = crate::TestContext::new().await;
+        let account = context.sign_up("test-archive-bad", "1234567").await.unwrap();
+        let token = account.access_token.token.clone();
+        let profile = context.new_profile(&token, "archived").await.unwrap();
+
+        context.add_ship(profile.profile.id, 1).await.unwrap();
+        let mut archive = context.export_profile(&token, profile.profile.id).await.unwrap();
+        archive.ships[0].mst_id = 999_999;
+
+        let err = context.import_profile(&token, &archive, Some("broken")).await.unwrap_err();
+        assert!(matches!(err, UserError::InvalidArchive(_)), "unexpected error: {err}");
+    }
+}
@@ -1,7 +1,7 @@
 //! Integration tests: equipment development advances development-quest progress.
 //!
 //! Reproduces the batch-craft scenario from plan 2026-06-22-005. `api_multiple_flag=1`
-//! crafts up to 3 items in one `create_slotitem` call; each successful item must advance
+//! crafts up to 3 items in one development call; each successful item must advance
 //! a development quest by exactly 1.
 //!
 //! Dev quests (codex `quest.json`):
@@ -56,7 +56,7 @@ mod tests {
         assert_eq!(remaining_for(&context, pid, 605).await, 1, "605 baseline requires 1 craft");
 
         // One successful craft (slotitem mst_id 1 = 12cm単装砲).
-        context.create_slotitem(pid, &[1], &[]).await.unwrap();
+        context.record_development(pid, [0; 4], &[1]).await.unwrap();
 
         assert_eq!(
             remaining_for(&context, pid, 605).await,
@@ -75,7 +75,7 @@ mod tests {
         assert_eq!(remaining_for(&context, pid, 607).await, 3, "607 baseline requires 3 crafts");
 
         // Batch craft: 3 successful items in one call (api_multiple_flag=1 semantics).
-        context.create_slotitem(pid, &[1, 1, 1], &[]).await.unwrap();
+        context.record_development(pid, [0; 4], &[1, 1, 1]).await.unwrap();
 
         assert_eq!(
             remaining_for(&context, pid, 607).await,
@@ -92,7 +92,7 @@ mod tests {
         context.quest_start(pid, 607).await.unwrap();
 
         // Batch of 3 where the middle craft failed (mst_id -1 = failure → no item, no event).
-        context.create_slotitem(pid, &[1, -1, 1], &[]).await.unwrap();
+        context.record_development(pid, [0; 4], &[1, -1, 1]).await.unwrap();
 
         assert_eq!(
             remaining_for(&context, pid, 607).await,